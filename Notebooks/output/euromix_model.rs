@@ -1,88 +1,158 @@
 // Generated native Rust code from SBML model: euromix_model
 // Uses SymPy CSE for optimized derivatives and Jacobian
 
-use diffsol::{OdeBuilder, OdeSolverMethod, OdeSolverStopReason, Vector};
+use diffsol::{OdeBuilder, OdeSolverMethod, Vector, VectorHost};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use wasm_pk_core::*;
 
-type M = diffsol::NalgebraMat<f64>;
-type LS = diffsol::NalgebraLU<f64>;
-
-#[derive(Serialize, Deserialize)]
-pub struct SimulationResult {
-    pub species: std::collections::HashMap<String, Vec<f64>>,
-    pub time: Vec<f64>,
-}
-
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct SimulationParams {
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub BM: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub BSA: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub scVFat: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub scVRich: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub scVLiver: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub scVBlood: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub scVArt: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub scFBlood: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub scFFat: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub scFPoor: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub scFLiver: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub scFSkin: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub fSA_exposed: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Height_sc: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Height_vs: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Falv: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub PCFat: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub PCLiver: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub PCRich: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub PCPoor: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub PCSkin_sc: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub PCSkin: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub PCAir: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub kGut: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Kp_sc_vs: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Km: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Michaelis: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Vmax: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub CLH: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Ke: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub fub: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Air: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Urine: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Gut: f64,
 
     // Initial amounts (optional, for runtime dosing)
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QFat: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QRich: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QPoor: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QLiver: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QMetab: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QGut: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QSkin_u: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QSkin_e: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QSkin_sc_u: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QSkin_sc_e: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QArt: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QVen: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QExcret: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_QAir: Option<f64>,
-    pub final_time: Option<f64>,
+    /// Times, outputs, doses and solver settings, the same for every model
+    #[serde(flatten)]
+    pub options: SimulationOptions,
+}
+
+/// Seconds per model time unit, if the SBML declares its time units
+const MODEL_TIME_SECONDS: Option<f64> = Some(3600.0);
+/// SBML id of the model's time units
+const MODEL_TIME_UNIT: Option<&str> = Some("HR");
+
+/// Model time units per time_unit (1 when it is not given)
+fn time_scale(sim_params: &SimulationParams) -> f64 {
+    sim_params.options.time_scale(&MODEL)
+}
+
+/// The parameters with every time and step size in model time units
+fn in_model_time(mut sim_params: SimulationParams) -> SimulationParams {
+    let scale = time_scale(&sim_params);
+    sim_params.options = sim_params.options.in_model_time(scale);
+    sim_params
+}
+
+/// The parameters with the defaults a run uses for its times and solver options
+fn with_defaults(mut sim_params: SimulationParams) -> SimulationParams {
+    sim_params.options = sim_params.options.with_defaults(&MODEL);
+    sim_params
 }
 
 pub fn run_simulation(params: &str) -> String {
-    println!("Starting simulation...");
+    wasm_pk_core::simulate::<EuromixModel>(params)
+}
 
-    let sim_params: SimulationParams = match serde_json::from_str(params) {
-        Ok(p) => p,
-        Err(e) => {
-            eprintln!("Error parsing params: {}", e);
-            return serde_json::to_string(&SimulationResult {
-                species: HashMap::new(),
-                time: vec![],
-            }).unwrap();
-        }
-    };
+/// Run one simulation per parameter object; each result carries its own error
+pub fn run_simulation_batch(params_list: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_batch::<EuromixModel>(params_list)
+}
+
+/// Names of the solver parameter vector entries, in index order
+const PARAMETER_ORDER: &[&str] = &["BM", "BSA", "scVFat", "scVRich", "scVLiver", "scVBlood", "scVArt", "scFBlood", "scFFat", "scFPoor", "scFLiver", "scFSkin", "fSA_exposed", "Height_sc", "Height_vs", "Falv", "PCFat", "PCLiver", "PCRich", "PCPoor", "PCSkin_sc", "PCSkin", "PCAir", "kGut", "Kp_sc_vs", "Km", "Michaelis", "Vmax", "CLH", "Ke", "fub", "Air", "Urine", "Gut", "Fat", "Rich", "Liver", "Skin_e", "Skin_u", "Skin_sc_e", "Skin_sc_u", "f_su", "f_se", "VBlood", "FBlood", "Poor", "Art", "FFat", "FPoor", "FLiver", "FSkin", "Ven", "FRich", "FSkin_e", "FSkin_u", "init_QFat", "init_QRich", "init_QPoor", "init_QLiver", "init_QMetab", "init_QGut", "init_QSkin_u", "init_QSkin_e", "init_QSkin_sc_u", "init_QSkin_sc_e", "init_QArt", "init_QVen", "init_QExcret", "init_QAir"];
+
+/// Index mapping of the solver parameter vector: model parameters, derived
+/// parameter values, then the initial state as init_<species> (JSON array)
+pub fn get_parameter_order() -> String {
+    serde_json::to_string(PARAMETER_ORDER).unwrap()
+}
 
+/// Resolve one run's parameters into the solver parameter vector
+fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
     let BM = sim_params.BM;
     let BSA = sim_params.BSA;
     let scVFat = sim_params.scVFat;
@@ -121,34 +191,212 @@ pub fn run_simulation(params: &str) -> String {
     let Rich = BM*scVRich;
     let Liver = BM*scVLiver;
     let Skin_e = BSA*Height_vs*fSA_exposed;
-    let Skin_u = BSA*Height_vs*(1.0 - 1.0*fSA_exposed);
+    let Skin_u = BSA*Height_vs*(1.0 - fSA_exposed);
     let Skin_sc_e = BSA*Height_sc*fSA_exposed;
-    let Skin_sc_u = BSA*Height_sc*(1.0 - 1.0*fSA_exposed);
-    let f_su = Kp_sc_vs*BSA*(1.0 - 1.0*fSA_exposed);
-    let f_se = Kp_sc_vs*BSA*fSA_exposed;
+    let Skin_sc_u = BSA*Height_sc*(1.0 - fSA_exposed);
+    let f_su = BSA*Kp_sc_vs*(1.0 - fSA_exposed);
+    let f_se = BSA*Kp_sc_vs*fSA_exposed;
     let VBlood = BM*scVBlood;
-    let FBlood = scFBlood*BM;
-    let Poor = -1.0*Skin_sc_u + (-1.0*Skin_sc_e + (-1.0*Skin_u + (BM*(-1.0*scVBlood + (-1.0*scVLiver + (-1.0*scVRich + (1.0 - 1.0*scVFat))) - 1.0/10.0) - 1.0*Skin_e)));
+    let FBlood = BM*scFBlood;
+    let Poor = BM*(-scVBlood - scVFat - scVLiver - scVRich + 0.9) - Skin_e - Skin_sc_e - Skin_sc_u - Skin_u;
     let Art = VBlood*scVArt;
     let FFat = FBlood*scFFat;
     let FPoor = FBlood*scFPoor;
     let FLiver = FBlood*scFLiver;
     let FSkin = FBlood*scFSkin;
-    let Ven = -1.0*Art + VBlood;
-    let FRich = -1.0*FSkin + (-1.0*FLiver + (-1.0*FPoor + (FBlood - 1.0*FFat)));
+    let Ven = -Art + VBlood;
+    let FRich = FBlood - FFat - FLiver - FPoor - FSkin;
     let FSkin_e = FSkin*fSA_exposed;
-    let FSkin_u = FSkin - 1.0*FSkin_e;
+    let FSkin_u = FSkin - FSkin_e;
+
+    vec![
+        BM,
+        BSA,
+        scVFat,
+        scVRich,
+        scVLiver,
+        scVBlood,
+        scVArt,
+        scFBlood,
+        scFFat,
+        scFPoor,
+        scFLiver,
+        scFSkin,
+        fSA_exposed,
+        Height_sc,
+        Height_vs,
+        Falv,
+        PCFat,
+        PCLiver,
+        PCRich,
+        PCPoor,
+        PCSkin_sc,
+        PCSkin,
+        PCAir,
+        kGut,
+        Kp_sc_vs,
+        Km,
+        Michaelis,
+        Vmax,
+        CLH,
+        Ke,
+        fub,
+        Air,
+        Urine,
+        Gut,
+        Fat,
+        Rich,
+        Liver,
+        Skin_e,
+        Skin_u,
+        Skin_sc_e,
+        Skin_sc_u,
+        f_su,
+        f_se,
+        VBlood,
+        FBlood,
+        Poor,
+        Art,
+        FFat,
+        FPoor,
+        FLiver,
+        FSkin,
+        Ven,
+        FRich,
+        FSkin_e,
+        FSkin_u,
+        sim_params.init_QFat.unwrap_or(0.0),
+        sim_params.init_QRich.unwrap_or(0.0),
+        sim_params.init_QPoor.unwrap_or(0.0),
+        sim_params.init_QLiver.unwrap_or(0.0),
+        sim_params.init_QMetab.unwrap_or(0.0),
+        sim_params.init_QGut.unwrap_or(1.0),
+        sim_params.init_QSkin_u.unwrap_or(0.0),
+        sim_params.init_QSkin_e.unwrap_or(0.0),
+        sim_params.init_QSkin_sc_u.unwrap_or(0.0),
+        sim_params.init_QSkin_sc_e.unwrap_or(0.0),
+        sim_params.init_QArt.unwrap_or(0.0),
+        sim_params.init_QVen.unwrap_or(0.0),
+        sim_params.init_QExcret.unwrap_or(0.0),
+        sim_params.init_QAir.unwrap_or(0.0),
+    ]
+}
+
+/// Range of each checked entry of p, with the parameters a derived value reads
+const PARAMETER_CHECKS: &[ParameterCheck] = &[
+    ("BM", 0, ParameterRange::Positive, &[]),
+    ("BSA", 1, ParameterRange::Unknown, &[]),
+    ("scVFat", 2, ParameterRange::Fraction, &[]),
+    ("scVRich", 3, ParameterRange::Fraction, &[]),
+    ("scVLiver", 4, ParameterRange::Fraction, &[]),
+    ("scVBlood", 5, ParameterRange::Fraction, &[]),
+    ("scVArt", 6, ParameterRange::Fraction, &[]),
+    ("scFBlood", 7, ParameterRange::NonNegative, &[]),
+    ("scFFat", 8, ParameterRange::Fraction, &[]),
+    ("scFPoor", 9, ParameterRange::Fraction, &[]),
+    ("scFLiver", 10, ParameterRange::Fraction, &[]),
+    ("scFSkin", 11, ParameterRange::Fraction, &[]),
+    ("fSA_exposed", 12, ParameterRange::Fraction, &[]),
+    ("Height_sc", 13, ParameterRange::Unknown, &[]),
+    ("Height_vs", 14, ParameterRange::Unknown, &[]),
+    ("Falv", 15, ParameterRange::NonNegative, &[]),
+    ("PCFat", 16, ParameterRange::Unknown, &[]),
+    ("PCLiver", 17, ParameterRange::Unknown, &[]),
+    ("PCRich", 18, ParameterRange::Unknown, &[]),
+    ("PCPoor", 19, ParameterRange::Unknown, &[]),
+    ("PCSkin_sc", 20, ParameterRange::Unknown, &[]),
+    ("PCSkin", 21, ParameterRange::Unknown, &[]),
+    ("PCAir", 22, ParameterRange::Unknown, &[]),
+    ("kGut", 23, ParameterRange::Unknown, &[]),
+    ("Kp_sc_vs", 24, ParameterRange::Unknown, &[]),
+    ("Km", 25, ParameterRange::Unknown, &[]),
+    ("Michaelis", 26, ParameterRange::Unknown, &[]),
+    ("Vmax", 27, ParameterRange::Unknown, &[]),
+    ("CLH", 28, ParameterRange::NonNegative, &[]),
+    ("Ke", 29, ParameterRange::NonNegative, &[]),
+    ("fub", 30, ParameterRange::Fraction, &[]),
+    ("Air", 31, ParameterRange::NonNegative, &[]),
+    ("Urine", 32, ParameterRange::NonNegative, &[]),
+    ("Gut", 33, ParameterRange::NonNegative, &[]),
+    ("Fat", 34, ParameterRange::NonNegative, &["BM", "scVFat"]),
+    ("Rich", 35, ParameterRange::NonNegative, &["BM", "scVRich"]),
+    ("Liver", 36, ParameterRange::NonNegative, &["BM", "scVLiver"]),
+    ("Skin_e", 37, ParameterRange::NonNegative, &["BSA", "fSA_exposed", "Height_vs"]),
+    ("Skin_u", 38, ParameterRange::NonNegative, &["BSA", "fSA_exposed", "Height_vs"]),
+    ("Skin_sc_e", 39, ParameterRange::NonNegative, &["BSA", "fSA_exposed", "Height_sc"]),
+    ("Skin_sc_u", 40, ParameterRange::NonNegative, &["BSA", "fSA_exposed", "Height_sc"]),
+    ("f_su", 41, ParameterRange::Fraction, &["BSA", "fSA_exposed", "Kp_sc_vs"]),
+    ("f_se", 42, ParameterRange::Fraction, &["BSA", "fSA_exposed", "Kp_sc_vs"]),
+    ("VBlood", 43, ParameterRange::NonNegative, &["BM", "scVBlood"]),
+    ("FBlood", 44, ParameterRange::NonNegative, &["BM", "scFBlood"]),
+    ("Poor", 45, ParameterRange::NonNegative, &["BM", "BSA", "scVFat", "scVRich", "scVLiver", "scVBlood", "fSA_exposed", "Height_sc", "Height_vs"]),
+    ("Art", 46, ParameterRange::NonNegative, &["BM", "scVBlood", "scVArt"]),
+    ("FFat", 47, ParameterRange::NonNegative, &["BM", "scFBlood", "scFFat"]),
+    ("FPoor", 48, ParameterRange::NonNegative, &["BM", "scFBlood", "scFPoor"]),
+    ("FLiver", 49, ParameterRange::NonNegative, &["BM", "scFBlood", "scFLiver"]),
+    ("FSkin", 50, ParameterRange::NonNegative, &["BM", "scFBlood", "scFSkin"]),
+    ("Ven", 51, ParameterRange::NonNegative, &["BM", "scVBlood", "scVArt"]),
+    ("FRich", 52, ParameterRange::NonNegative, &["BM", "scFBlood", "scFFat", "scFPoor", "scFLiver", "scFSkin"]),
+    ("FSkin_e", 53, ParameterRange::NonNegative, &["BM", "scFBlood", "scFSkin", "fSA_exposed"]),
+    ("FSkin_u", 54, ParameterRange::NonNegative, &["BM", "scFBlood", "scFSkin", "fSA_exposed"]),
+];
+
+/// Check parameters without running: `{errors, warnings}`, each `{field, message}` (JSON)
+pub fn validate_parameters(params: &str) -> String {
+    wasm_pk_core::validate_parameters::<EuromixModel>(params)
+}
+
+/// The model as a type, for code generic over models; see PkModel
+pub struct EuromixModel;
 
+impl PkModel for EuromixModel {
+    type Params = SimulationParams;
+    const N_STATES: usize = 14;
+    const INFO: ModelInfo = MODEL;
 
+    fn rhs(y: &[f64], p: &[f64], _t: f64, _windows: &[UptakeWindow], dy: &mut [f64]) {
+        // Parameters from p
+        let Falv = p[15];
+        let PCFat = p[16];
+        let PCLiver = p[17];
+        let PCRich = p[18];
+        let PCPoor = p[19];
+        let PCSkin_sc = p[20];
+        let PCSkin = p[21];
+        let PCAir = p[22];
+        let kGut = p[23];
+        let Km = p[25];
+        let Michaelis = p[26];
+        let Vmax = p[27];
+        let CLH = p[28];
+        let Ke = p[29];
+        let fub = p[30];
+        let Air = p[31];
+        let Fat = p[34];
+        let Rich = p[35];
+        let Liver = p[36];
+        let Skin_e = p[37];
+        let Skin_u = p[38];
+        let Skin_sc_e = p[39];
+        let Skin_sc_u = p[40];
+        let f_su = p[41];
+        let f_se = p[42];
+        let FBlood = p[44];
+        let Poor = p[45];
+        let Art = p[46];
+        let FFat = p[47];
+        let FPoor = p[48];
+        let FLiver = p[49];
+        let Ven = p[51];
+        let FRich = p[52];
+        let FSkin_e = p[53];
+        let FSkin_u = p[54];
 
-    // RHS Closure
-    let rhs = |y: &diffsol::NalgebraVec<f64>, _p: &diffsol::NalgebraVec<f64>, t: f64, dy: &mut diffsol::NalgebraVec<f64>| {
         // Map species names to y indices
         let QFat = y[0];
         let QRich = y[1];
         let QPoor = y[2];
         let QLiver = y[3];
-        let QMetab = y[4];
         let QGut = y[5];
         let QSkin_u = y[6];
         let QSkin_e = y[7];
@@ -156,21 +404,20 @@ pub fn run_simulation(params: &str) -> String {
         let QSkin_sc_e = y[9];
         let QArt = y[10];
         let QVen = y[11];
-        let QExcret = y[12];
         let QAir = y[13];
 
         // Temporary variables (CSE)
         let x0 = Art.powi(-1);
-        let x1 = -1.0*QArt*x0;
+        let x1 = -QArt*x0;
         let x2 = Fat.powi(-1)*PCFat.powi(-1);
         let x3 = QFat*x2;
-        let x4 = 1.0*FFat;
+        let x4 = FFat;
         let x5 = PCRich.powi(-1)*Rich.powi(-1);
         let x6 = QRich*x5;
-        let x7 = 1.0*FRich;
+        let x7 = FRich;
         let x8 = PCPoor.powi(-1)*Poor.powi(-1);
         let x9 = QPoor*x8;
-        let x10 = 1.0*FPoor;
+        let x10 = FPoor;
         let x11 = Liver.powi(-1);
         let x12 = QLiver*x11;
         let x13 = Liver > 0.0;
@@ -186,14 +433,14 @@ pub fn run_simulation(params: &str) -> String {
         };
         let x16 = x14*x15;
         let x17 = Liver*Vmax;
-        let x18 = Michaelis > 1.0/2.0;
+        let x18 = Michaelis > 0.5;
         let x19 = PCLiver.powi(-1);
         let x20 = CLH*x19;
-        let x21 = fub*if x18 {
+        let x21 = fub*(if x18 {
             x16*x17
         } else {
             x14*x20
-        };
+        });
         let x22 = QArt*x0;
         let x23 = FLiver*x22;
         let x24 = FLiver*x19;
@@ -234,105 +481,84 @@ pub fn run_simulation(params: &str) -> String {
         let x43 = FSkin_e*x32;
         let x44 = x42*x43;
         let x45 = FSkin_e*x22;
-        let x46 = 1.0*f_su;
-        let x47 = 1.0*f_se;
+        let x46 = f_su;
+        let x47 = f_se;
         let x48 = FBlood*Ven.powi(-1);
-        let x49 = -1.0*QVen*x48;
+        let x49 = -QVen*x48;
         let x50 = Ke*fub;
         let x51 = x22*x50;
         let x52 = FBlood*Air.powi(-1);
         let x53 = Falv*PCAir.powi(-1);
-        let x54 = -1.0*QAir*x52 + x22*x53;
-        let x55 = x2*x4;
-        let x56 = x5*x7;
-        let x57 = x10*x8;
-        let x58 = if x13 {
-            x11
-        } else {
-            0.0
-        };
-        let x59 = fub*if x18 {
-            x15*x17*x58*(1.0 - 1.0*x16)
-        } else {
-            x20*x58
-        };
-        let x60 = x11*x24;
-        let x61 = 1.0*kGut;
-        let x62 = 1.0*x0;
-        let x63 = f_su*x35;
-        let x64 = 1.0*if x30 {
-            x29
-        } else {
-            0.0
-        };
-        let x65 = x46*if x27 {
-            x26
-        } else {
-            0.0
-        };
-        let x66 = f_se*x35;
-        let x67 = 1.0*if x41 {
-            x40
-        } else {
-            0.0
-        };
-        let x68 = x47*if x38 {
-            x37
-        } else {
-            0.0
-        };
-        let x69 = 1.0*x48;
-        let x70 = 1.0*x52;
+        let x54 = -QAir*x52 + x22*x53;
 
         // Derivatives
-        dy[0] = x4*(-1.0*x1 - 1.0*x3);
-        dy[1] = x7*(-1.0*x1 - 1.0*x6);
-        dy[2] = x10*(-1.0*x1 - 1.0*x9);
-        dy[3] = 1.0*QGut*kGut - 1.0*x21 + 1.0*x23 - 1.0*x25;
-        dy[4] = 1.0*x21;
-        dy[5] = -1.0*QGut*kGut;
-        dy[6] = 1.0*f_su*x28 - 1.0*f_su*x31*x35 - 1.0*x34 + 1.0*x36;
-        dy[7] = -1.0*f_se*x35*x42 + 1.0*f_se*x39 - 1.0*x44 + 1.0*x45;
-        dy[8] = x46*(-1.0*x28 + x31*x35);
-        dy[9] = x47*(x35*x42 - 1.0*x39);
-        dy[10] = -1.0*FFat*x22 - 1.0*FPoor*x22 - 1.0*FRich*x22 - 1.0*x23 - 1.0*x36 - 1.0*x45 - 1.0*x49 - 1.0*x51 - 1.0*x54;
-        dy[11] = 1.0*FFat*x3 + 1.0*FPoor*x9 + 1.0*FRich*x6 + 1.0*x25 + 1.0*x34 + 1.0*x44 + 1.0*x49;
-        dy[12] = 1.0*x51;
-        dy[13] = 1.0*x54;
-    };
+        dy[0] = x4*(-x1 - x3);
+        dy[1] = x7*(-x1 - x6);
+        dy[2] = x10*(-x1 - x9);
+        dy[3] = QGut*kGut - x21 + x23 - x25;
+        dy[4] = x21;
+        dy[5] = -QGut*kGut;
+        dy[6] = f_su*x28 - f_su*x31*x35 - x34 + x36;
+        dy[7] = -f_se*x35*x42 + f_se*x39 - x44 + x45;
+        dy[8] = x46*(-x28 + x31*x35);
+        dy[9] = x47*(x35*x42 - x39);
+        dy[10] = -FFat*x22 - FPoor*x22 - FRich*x22 - x23 - x36 - x45 - x49 - x51 - x54;
+        dy[11] = FFat*x3 + FPoor*x9 + FRich*x6 + x25 + x34 + x44 + x49;
+        dy[12] = x51;
+        dy[13] = x54;
+    }
+
+    fn jac(y: &[f64], p: &[f64], _t: f64, v: &[f64], jv: &mut [f64]) {
+        jv.fill(0.0);
 
-    // Jacobian Closure (Matrix-Vector Product)
-    let jac = |y: &diffsol::NalgebraVec<f64>, _p: &diffsol::NalgebraVec<f64>, t: f64, v: &diffsol::NalgebraVec<f64>, jv: &mut diffsol::NalgebraVec<f64>| {
-        for i in 0..jv.len() { jv[i] = 0.0; }
+        // Parameters from p
+        let Falv = p[15];
+        let PCFat = p[16];
+        let PCLiver = p[17];
+        let PCRich = p[18];
+        let PCPoor = p[19];
+        let PCSkin_sc = p[20];
+        let PCSkin = p[21];
+        let PCAir = p[22];
+        let kGut = p[23];
+        let Km = p[25];
+        let Michaelis = p[26];
+        let Vmax = p[27];
+        let CLH = p[28];
+        let Ke = p[29];
+        let fub = p[30];
+        let Air = p[31];
+        let Fat = p[34];
+        let Rich = p[35];
+        let Liver = p[36];
+        let Skin_e = p[37];
+        let Skin_u = p[38];
+        let Skin_sc_e = p[39];
+        let Skin_sc_u = p[40];
+        let f_su = p[41];
+        let f_se = p[42];
+        let FBlood = p[44];
+        let Poor = p[45];
+        let Art = p[46];
+        let FFat = p[47];
+        let FPoor = p[48];
+        let FLiver = p[49];
+        let Ven = p[51];
+        let FRich = p[52];
+        let FSkin_e = p[53];
+        let FSkin_u = p[54];
 
         // Map species names to y indices
-        let QFat = y[0];
-        let QRich = y[1];
-        let QPoor = y[2];
         let QLiver = y[3];
-        let QMetab = y[4];
-        let QGut = y[5];
-        let QSkin_u = y[6];
-        let QSkin_e = y[7];
-        let QSkin_sc_u = y[8];
-        let QSkin_sc_e = y[9];
-        let QArt = y[10];
-        let QVen = y[11];
-        let QExcret = y[12];
-        let QAir = y[13];
 
         // Temporary variables (CSE)
         let x0 = Art.powi(-1);
-        let x1 = -1.0*QArt*x0;
         let x2 = Fat.powi(-1)*PCFat.powi(-1);
-        let x3 = QFat*x2;
-        let x4 = 1.0*FFat;
+        let x4 = FFat;
         let x5 = PCRich.powi(-1)*Rich.powi(-1);
-        let x6 = QRich*x5;
-        let x7 = 1.0*FRich;
+        let x7 = FRich;
         let x8 = PCPoor.powi(-1)*Poor.powi(-1);
-        let x9 = QPoor*x8;
-        let x10 = 1.0*FPoor;
+        let x10 = FPoor;
         let x11 = Liver.powi(-1);
         let x12 = QLiver*x11;
         let x13 = Liver > 0.0;
@@ -348,63 +574,28 @@ pub fn run_simulation(params: &str) -> String {
         };
         let x16 = x14*x15;
         let x17 = Liver*Vmax;
-        let x18 = Michaelis > 1.0/2.0;
+        let x18 = Michaelis > 0.5;
         let x19 = PCLiver.powi(-1);
         let x20 = CLH*x19;
-        let x21 = fub*if x18 {
-            x16*x17
-        } else {
-            x14*x20
-        };
-        let x22 = QArt*x0;
-        let x23 = FLiver*x22;
         let x24 = FLiver*x19;
-        let x25 = x12*x24;
         let x26 = Skin_sc_u.powi(-1);
         let x27 = Skin_sc_u > 0.0;
-        let x28 = if x27 {
-            QSkin_sc_u*x26
-        } else {
-            0.0
-        };
         let x29 = Skin_u.powi(-1);
         let x30 = Skin_u > 0.0;
-        let x31 = if x30 {
-            QSkin_u*x29
-        } else {
-            0.0
-        };
         let x32 = PCSkin.powi(-1);
         let x33 = FSkin_u*x32;
-        let x34 = x31*x33;
         let x35 = PCSkin_sc.powi(-1);
-        let x36 = FSkin_u*x22;
         let x37 = Skin_sc_e.powi(-1);
         let x38 = Skin_sc_e > 0.0;
-        let x39 = if x38 {
-            QSkin_sc_e*x37
-        } else {
-            0.0
-        };
         let x40 = Skin_e.powi(-1);
         let x41 = Skin_e > 0.0;
-        let x42 = if x41 {
-            QSkin_e*x40
-        } else {
-            0.0
-        };
         let x43 = FSkin_e*x32;
-        let x44 = x42*x43;
-        let x45 = FSkin_e*x22;
-        let x46 = 1.0*f_su;
-        let x47 = 1.0*f_se;
+        let x46 = f_su;
+        let x47 = f_se;
         let x48 = FBlood*Ven.powi(-1);
-        let x49 = -1.0*QVen*x48;
         let x50 = Ke*fub;
-        let x51 = x22*x50;
         let x52 = FBlood*Air.powi(-1);
         let x53 = Falv*PCAir.powi(-1);
-        let x54 = -1.0*QAir*x52 + x22*x53;
         let x55 = x2*x4;
         let x56 = x5*x7;
         let x57 = x10*x8;
@@ -413,77 +604,1512 @@ pub fn run_simulation(params: &str) -> String {
         } else {
             0.0
         };
-        let x59 = fub*if x18 {
-            x15*x17*x58*(1.0 - 1.0*x16)
+        let x59 = fub*(if x18 {
+            x15*x17*x58*(1.0 - x16)
         } else {
             x20*x58
-        };
+        });
         let x60 = x11*x24;
-        let x61 = 1.0*kGut;
-        let x62 = 1.0*x0;
+        let x61 = kGut;
+        let x62 = x0;
         let x63 = f_su*x35;
-        let x64 = 1.0*if x30 {
+        let x64 = if x30 {
             x29
         } else {
             0.0
         };
-        let x65 = x46*if x27 {
+        let x65 = x46*(if x27 {
             x26
         } else {
             0.0
-        };
+        });
         let x66 = f_se*x35;
-        let x67 = 1.0*if x41 {
+        let x67 = if x41 {
             x40
         } else {
             0.0
         };
-        let x68 = x47*if x38 {
+        let x68 = x47*(if x38 {
             x37
         } else {
             0.0
-        };
-        let x69 = 1.0*x48;
-        let x70 = 1.0*x52;
+        });
+        let x69 = x48;
+        let x70 = x52;
 
         // Jacobian-Vector Product
-        jv[0] += (-1.0*x55) * v[0];
+        jv[0] += (-x55) * v[0];
         jv[0] += (x0*x4) * v[10];
-        jv[1] += (-1.0*x56) * v[1];
+        jv[1] += (-x56) * v[1];
         jv[1] += (x0*x7) * v[10];
-        jv[2] += (-1.0*x57) * v[2];
+        jv[2] += (-x57) * v[2];
         jv[2] += (x0*x10) * v[10];
-        jv[3] += (-1.0*x59 - 1.0*x60) * v[3];
+        jv[3] += (-x59 - x60) * v[3];
         jv[3] += (x61) * v[5];
         jv[3] += (FLiver*x62) * v[10];
-        jv[4] += (1.0*x59) * v[3];
-        jv[5] += (-1.0*x61) * v[5];
-        jv[6] += (-1.0*x64*(x33 + x63)) * v[6];
+        jv[4] += (x59) * v[3];
+        jv[5] += (-x61) * v[5];
+        jv[6] += (-x64*(x33 + x63)) * v[6];
         jv[6] += (x65) * v[8];
         jv[6] += (FSkin_u*x62) * v[10];
-        jv[7] += (-1.0*x67*(x43 + x66)) * v[7];
+        jv[7] += (-x67*(x43 + x66)) * v[7];
         jv[7] += (x68) * v[9];
         jv[7] += (FSkin_e*x62) * v[10];
         jv[8] += (x63*x64) * v[6];
-        jv[8] += (-1.0*x65) * v[8];
+        jv[8] += (-x65) * v[8];
         jv[9] += (x66*x67) * v[7];
-        jv[9] += (-1.0*x68) * v[9];
-        jv[10] += (-1.0*x62*(FFat + FLiver + FPoor + FRich + FSkin_e + FSkin_u + x50 + x53)) * v[10];
+        jv[9] += (-x68) * v[9];
+        jv[10] += (-x62*(FFat + FLiver + FPoor + FRich + FSkin_e + FSkin_u + x50 + x53)) * v[10];
         jv[10] += (x69) * v[11];
         jv[10] += (x70) * v[13];
         jv[11] += (x55) * v[0];
         jv[11] += (x56) * v[1];
         jv[11] += (x57) * v[2];
-        jv[11] += (1.0*x60) * v[3];
+        jv[11] += (x60) * v[3];
         jv[11] += (x33*x64) * v[6];
         jv[11] += (x43*x67) * v[7];
-        jv[11] += (-1.0*x69) * v[11];
+        jv[11] += (-x69) * v[11];
         jv[12] += (x50*x62) * v[10];
         jv[13] += (x53*x62) * v[10];
-        jv[13] += (-1.0*x70) * v[13];
+        jv[13] += (-x70) * v[13];
+    }
+
+    fn init(p: &[f64], _t: f64, y: &mut [f64]) {
+        y.copy_from_slice(&p[55..69]);
+    }
+    fn defaults() -> serde_json::Value {
+        serde_json::from_str(&get_default_parameters()).unwrap()
+    }
+
+    fn options(sim_params: &SimulationParams) -> &SimulationOptions {
+        &sim_params.options
+    }
+
+    fn options_mut(sim_params: &mut SimulationParams) -> &mut SimulationOptions {
+        &mut sim_params.options
+    }
+
+    fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+        parameter_vector(sim_params)
+    }
+
+    fn build_problem() -> BuiltProblem<'static> {
+        BuiltProblem::dense(MODEL, equations())
+    }
+
+    fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+        prepare_run(sim_params)
+    }
+}
+
+/// The run of checked parameters: the solver works in model time units, while
+/// results and checkpoints keep the caller's
+fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+    let resolved = serde_json::to_value(with_defaults(sim_params.clone())).unwrap();
+    let scale = time_scale(&sim_params);
+    let sim_params = in_model_time(sim_params);
+    let p = parameter_vector(&sim_params);
+    Ok(Run {
+        p,
+        resolved,
+        scale,
+        uptake_windows: vec![],
+        options: sim_params.options,
+    })
+}
+
+/// The model's functions for the solver; the closures capture nothing, so they
+/// are plain function pointers
+fn equations() -> Equations<'static> {
+    let eval_observables = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let Fat = p[34];
+        let Rich = p[35];
+        let Liver = p[36];
+        let Skin_e = p[37];
+        let Skin_u = p[38];
+        let Skin_sc_e = p[39];
+        let Skin_sc_u = p[40];
+        let f_su = p[41];
+        let f_se = p[42];
+        let VBlood = p[43];
+        let FBlood = p[44];
+        let Poor = p[45];
+        let Art = p[46];
+        let FFat = p[47];
+        let FPoor = p[48];
+        let FLiver = p[49];
+        let FSkin = p[50];
+        let Ven = p[51];
+        let FRich = p[52];
+        let FSkin_e = p[53];
+        let FSkin_u = p[54];
+        vec![Fat, Rich, Liver, Skin_e, Skin_u, Skin_sc_e, Skin_sc_u, f_su, f_se, VBlood, FBlood, Poor, Art, FFat, FPoor, FLiver, FSkin, Ven, FRich, FSkin_e, FSkin_u]
     };
 
-    let init = |_y0: &diffsol::NalgebraVec<f64>, _t: f64, y: &mut diffsol::NalgebraVec<f64>| {
+    let eval_volumes = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let Air = p[31];
+        let Urine = p[32];
+        let Gut = p[33];
+        let Fat = p[34];
+        let Rich = p[35];
+        let Liver = p[36];
+        let Skin_e = p[37];
+        let Skin_u = p[38];
+        let Skin_sc_e = p[39];
+        let Skin_sc_u = p[40];
+        let Poor = p[45];
+        let Art = p[46];
+        let Ven = p[51];
+        vec![Fat, Rich, Poor, Liver, Liver, Gut, Skin_u, Skin_e, Skin_sc_u, Skin_sc_e, Art, Ven, Urine, Air]
+    };
+
+    let eval_mass = |y: &[f64], _p: &[f64]| -> f64 {
+        y[0] + y[1] + y[2] + y[3] + y[4] + y[5] + y[6] + y[7] + y[8] + y[9] + y[10] + y[11] + y[12] + y[13]
+    };
+
+    Equations {
+        rhs: EuromixModel::rhs,
+        jac: EuromixModel::jac,
+        init: EuromixModel::init,
+        roots: no_roots,
+        triggers: no_triggers,
+        apply_events: no_events,
+        observables: eval_observables,
+        volumes: eval_volumes,
+        mass: eval_mass,
+        jac_matrix: None,
+    }
+}
+
+/// Solver stats of the last run of the last simulation call (JSON, null if it never started)
+pub fn get_last_run_stats() -> String {
+    LAST_RUN_STATS.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+pub fn run_simulation_at_times(params: &str, times: &[f64]) -> String {
+    wasm_pk_core::run_simulation_at_times::<EuromixModel>(params, times)
+}
+
+/// Run a simulation and return `time,<species...>` CSV, one row per stored time
+pub fn run_simulation_csv(params: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_csv::<EuromixModel>(params)
+}
+
+/// Cmax, Tmax, AUC, AUC(0-inf), terminal half-life and trough of one species (JSON)
+pub fn compute_pk_metrics(result_json: &str, species: &str) -> Result<String, String> {
+    wasm_pk_core::compute_pk_metrics(result_json, species)
+}
+
+/// AUC of one species over each `[t1, t2]` window (JSON array, same order)
+pub fn compute_partial_auc(result_json: &str, species: &str, windows: &str) -> Result<String, String> {
+    wasm_pk_core::compute_partial_auc(result_json, species, windows)
+}
+
+/// Run the model once per value of one parameter, e.g. body weight from 20 to 100 kg
+pub fn run_parameter_sweep(base_params: &str, sweep: &str) -> Result<String, String> {
+    wasm_pk_core::run_parameter_sweep::<EuromixModel>(base_params, sweep)
+}
+
+/// Continue a run_simulation result from its last recorded state for `additional_time`,
+/// with `new_params` merged over the parameters that run used. Its doses, output_times
+/// and uptake windows are not repeated; times in `new_params` count from the
+/// continuation start. The segment is returned on the combined time axis, after
+/// the earlier trajectory when `concatenate` is set.
+pub fn continue_simulation(previous_result: &str, new_params: &str, additional_time: f64, concatenate: bool) -> Result<String, String> {
+    wasm_pk_core::continue_simulation::<EuromixModel>(previous_result, new_params, additional_time, concatenate)
+}
+
+/// Checkpoint of the last run of the last simulation call (JSON, null if it never
+/// started or its final state is not finite)
+pub fn export_checkpoint() -> String {
+    LAST_CHECKPOINT.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+/// Resume an exported checkpoint up to the absolute `final_time` in `params`, which are
+/// merged over the checkpoint's parameters. Its pending doses are applied unless
+/// `params` gives new doses. The result and the new checkpoint use absolute time.
+pub fn run_from_checkpoint(checkpoint: &str, params: &str) -> Result<String, String> {
+    wasm_pk_core::run_from_checkpoint::<EuromixModel>(checkpoint, params)
+}
+
+const SPECIES_IDS: &[&str] = &["QFat", "QRich", "QPoor", "QLiver", "QMetab", "QGut", "QSkin_u", "QSkin_e", "QSkin_sc_u", "QSkin_sc_e", "QArt", "QVen", "QExcret", "QAir"];
+
+/// Parameters read by the derivative of each state, for failure hints
+const STATE_PARAMETERS: [&[&str]; 14] = [&["Art", "FFat", "Fat", "PCFat"], &["Art", "FRich", "PCRich", "Rich"], &["Art", "FPoor", "PCPoor", "Poor"], &["Art", "CLH", "FLiver", "Km", "Liver", "Michaelis", "PCLiver", "Vmax", "fub", "kGut"], &["CLH", "Km", "Liver", "Michaelis", "PCLiver", "Vmax", "fub"], &["kGut"], &["Art", "FSkin_u", "PCSkin", "PCSkin_sc", "Skin_sc_u", "Skin_u", "f_su"], &["Art", "FSkin_e", "PCSkin", "PCSkin_sc", "Skin_e", "Skin_sc_e", "f_se"], &["PCSkin_sc", "Skin_sc_u", "Skin_u", "f_su"], &["PCSkin_sc", "Skin_e", "Skin_sc_e", "f_se"], &["Air", "Art", "FBlood", "FFat", "FLiver", "FPoor", "FRich", "FSkin_e", "FSkin_u", "Falv", "Ke", "PCAir", "Ven", "fub"], &["FBlood", "FFat", "FLiver", "FPoor", "FRich", "FSkin_e", "FSkin_u", "Fat", "Liver", "PCFat", "PCLiver", "PCPoor", "PCRich", "PCSkin", "Poor", "Rich", "Skin_e", "Skin_u", "Ven"], &["Art", "Ke", "fub"], &["Air", "Art", "FBlood", "Falv", "PCAir"]];
+
+/// SBML ids of the assignment-rule observables, in evaluation order
+const OBSERVABLE_IDS: [&str; 21] = ["Fat", "Rich", "Liver", "Skin_e", "Skin_u", "Skin_sc_e", "Skin_sc_u", "f_su", "f_se", "VBlood", "FBlood", "Poor", "Art", "FFat", "FPoor", "FLiver", "FSkin", "Ven", "FRich", "FSkin_e", "FSkin_u"];
+
+/// What the shared solver loop and option checks need to know about the model
+const MODEL: ModelInfo = ModelInfo {
+    species: SPECIES_IDS,
+    state_parameters: &STATE_PARAMETERS,
+    event_ids: &[],
+    event_targets: &[],
+    event_initial_values: &[],
+    event_persistent: &[],
+    observables: &OBSERVABLE_IDS,
+    species_is_amount: &SPECIES_IS_AMOUNT,
+    species_volumes: &SPECIES_VOLUMES,
+    series_units: &SERIES_UNITS,
+    time_seconds: MODEL_TIME_SECONDS,
+    time_unit: MODEL_TIME_UNIT,
+    parameters: PARAMETER_ORDER,
+    parameter_checks: PARAMETER_CHECKS,
+    algebraic: &[],
+};
+
+/// Run a simulation and return the trajectory as little-endian f64 bytes
+pub fn run_simulation_binary(params: &str) -> Result<Vec<u8>, String> {
+    wasm_pk_core::run_simulation_binary::<EuromixModel>(params)
+}
+
+/// Describe the run_simulation_binary layout and its species order (JSON)
+pub fn get_binary_layout() -> String {
+    wasm_pk_core::get_binary_layout::<EuromixModel>()
+}
+
+/// Decode run_simulation_binary output into times and named species series
+pub fn decode_binary_result(bytes: &[u8]) -> Result<(Vec<f64>, OrderedSeries), String> {
+    wasm_pk_core::decode_binary_result::<EuromixModel>(bytes)
+}
+
+/// Parameters run_simulation_with_sensitivities can differentiate with respect to
+pub const SENSITIVITY_PARAMETERS: &[&str] = &["BM", "BSA", "scVFat", "scVRich", "scVLiver", "scVBlood", "scVArt", "scFBlood", "scFFat", "scFPoor", "scFLiver", "scFSkin", "fSA_exposed", "Height_sc", "Height_vs", "Falv", "PCFat", "PCLiver", "PCRich", "PCPoor", "PCSkin_sc", "PCSkin", "PCAir", "kGut", "Kp_sc_vs", "Km", "Michaelis", "Vmax", "CLH", "Ke", "fub", "Air", "Urine", "Gut"];
+
+#[derive(Serialize, Deserialize)]
+pub struct ParameterSensitivity {
+    pub parameter: String,
+    pub species: OrderedSeries,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SensitivityResult {
+    pub time: Vec<f64>,
+    pub species: OrderedSeries,
+    pub sensitivities: Vec<ParameterSensitivity>,
+    pub error: Option<ErrorPayload>,
+}
+
+/// Integrate the states together with dY/dp for the parameters named in `wrt`
+pub fn run_simulation_with_sensitivities(params: &str, wrt: Vec<String>) -> Result<String, String> {
+    let sim_params = EuromixModel::parse_params(params)?;
+    let output_scale = if sim_params.options.convert_output_time.unwrap_or(false) { time_scale(&sim_params) } else { 1.0 };
+    let sim_params = in_model_time(sim_params);
+    let base: [f64; 34] = [sim_params.BM, sim_params.BSA, sim_params.scVFat, sim_params.scVRich, sim_params.scVLiver, sim_params.scVBlood, sim_params.scVArt, sim_params.scFBlood, sim_params.scFFat, sim_params.scFPoor, sim_params.scFLiver, sim_params.scFSkin, sim_params.fSA_exposed, sim_params.Height_sc, sim_params.Height_vs, sim_params.Falv, sim_params.PCFat, sim_params.PCLiver, sim_params.PCRich, sim_params.PCPoor, sim_params.PCSkin_sc, sim_params.PCSkin, sim_params.PCAir, sim_params.kGut, sim_params.Kp_sc_vs, sim_params.Km, sim_params.Michaelis, sim_params.Vmax, sim_params.CLH, sim_params.Ke, sim_params.fub, sim_params.Air, sim_params.Urine, sim_params.Gut];
+    let mut slot: [Option<usize>; 34] = [None; 34];
+    let mut p0 = Vec::with_capacity(wrt.len());
+    for (k, name) in wrt.iter().enumerate() {
+        let j = SENSITIVITY_PARAMETERS.iter().position(|p| p == name)
+            .ok_or_else(|| format!("unknown sensitivity parameter {}", name))?;
+        if slot[j].replace(k).is_some() {
+            return Err(format!("duplicate sensitivity parameter {}", name));
+        }
+        p0.push(base[j]);
+    }
+    if wrt.is_empty() {
+        return Err("wrt must name at least one parameter".to_string());
+    }
+    let theta = |p: &V, j: usize| -> f64 {
+        match slot[j] {
+            Some(k) => p[k],
+            None => base[j],
+        }
+    };
+
+    let rhs = |y: &V, p: &V, _t: f64, dy: &mut V| {
+        let BM = theta(p, 0);
+        let BSA = theta(p, 1);
+        let scVFat = theta(p, 2);
+        let scVRich = theta(p, 3);
+        let scVLiver = theta(p, 4);
+        let scVBlood = theta(p, 5);
+        let scVArt = theta(p, 6);
+        let scFBlood = theta(p, 7);
+        let scFFat = theta(p, 8);
+        let scFPoor = theta(p, 9);
+        let scFLiver = theta(p, 10);
+        let scFSkin = theta(p, 11);
+        let fSA_exposed = theta(p, 12);
+        let Height_sc = theta(p, 13);
+        let Height_vs = theta(p, 14);
+        let Falv = theta(p, 15);
+        let PCFat = theta(p, 16);
+        let PCLiver = theta(p, 17);
+        let PCRich = theta(p, 18);
+        let PCPoor = theta(p, 19);
+        let PCSkin_sc = theta(p, 20);
+        let PCSkin = theta(p, 21);
+        let PCAir = theta(p, 22);
+        let kGut = theta(p, 23);
+        let Kp_sc_vs = theta(p, 24);
+        let Km = theta(p, 25);
+        let Michaelis = theta(p, 26);
+        let Vmax = theta(p, 27);
+        let CLH = theta(p, 28);
+        let Ke = theta(p, 29);
+        let fub = theta(p, 30);
+        let Air = theta(p, 31);
+        let QFat = y[0];
+        let QRich = y[1];
+        let QPoor = y[2];
+        let QLiver = y[3];
+        let QGut = y[5];
+        let QSkin_u = y[6];
+        let QSkin_e = y[7];
+        let QSkin_sc_u = y[8];
+        let QSkin_sc_e = y[9];
+        let QArt = y[10];
+        let QVen = y[11];
+        let QAir = y[13];
+        let x0 = scVBlood.powi(-1);
+        let x1 = scVArt.powi(-1);
+        let x2 = QArt*x1;
+        let x3 = x0*x2;
+        let x4 = scVFat.powi(-1);
+        let x5 = PCFat.powi(-1);
+        let x6 = QFat*x5;
+        let x7 = x4*x6;
+        let x8 = -x7;
+        let x9 = x3 + x8;
+        let x10 = scFBlood*scFFat;
+        let x11 = x10;
+        let x12 = scVRich.powi(-1);
+        let x13 = PCRich.powi(-1);
+        let x14 = QRich*x13;
+        let x15 = x12*x14;
+        let x16 = x15 - x3;
+        let x17 = scFFat + scFLiver + scFPoor + scFSkin - 1.0;
+        let x18 = scFBlood*x17;
+        let x19 = x18;
+        let x20 = scVBlood + scVFat + scVLiver + scVRich - 0.9;
+        let x21 = fSA_exposed - 1.0;
+        let x22 = -BSA*x21;
+        let x23 = Height_sc*fSA_exposed;
+        let x24 = BSA*x23;
+        let x25 = Height_vs*fSA_exposed;
+        let x26 = BSA*x25;
+        let x27 = x24 + x26;
+        let x28 = BM*x20 + Height_sc*x22 + Height_vs*x22 + x27;
+        let x29 = x28.powi(-1);
+        let x30 = PCPoor.powi(-1);
+        let x31 = QPoor*x30;
+        let x32 = x29*x31;
+        let x33 = BM*x32;
+        let x34 = x3 + x33;
+        let x35 = scFBlood*scFPoor;
+        let x36 = x35;
+        let x37 = QGut*kGut;
+        let x38 = BM*scVLiver;
+        let x39 = BM.powi(-1);
+        let x40 = scVLiver.powi(-1);
+        let x41 = x39*x40;
+        let x42 = x38 > 0.0;
+        let x43 = if x42 {
+    QLiver*x41
+} else {
+    0.0
+};
+        let x44 = Km*PCLiver + x43;
+        let x45 = x44.powi(-1);
+        let x46 = x43*x45;
+        let x47 = x38*x46;
+        let x48 = Michaelis > 0.5;
+        let x49 = PCLiver.powi(-1);
+        let x50 = x43*x49;
+        let x51 = if x48 {
+    Vmax*x47
+} else {
+    CLH*x50
+};
+        let x52 = fub*x51;
+        let x53 = scFBlood*scFLiver;
+        let x54 = x3*x53;
+        let x55 = QLiver*x40;
+        let x56 = x49*x55;
+        let x57 = fSA_exposed;
+        let x58 = x57 - 1.0;
+        let x59 = Height_sc.powi(-1);
+        let x60 = BSA.powi(-1);
+        let x61 = x21.powi(-1);
+        let x62 = x60*x61;
+        let x63 = x59*x62;
+        let x64 = Height_sc*x21;
+        let x65 = BSA*x64;
+        let x66 = x65 < 0.0;
+        let x67 = if x66 {
+    -QSkin_sc_u*x63
+} else {
+    0.0
+};
+        let x68 = Kp_sc_vs*x67;
+        let x69 = PCSkin_sc.powi(-1);
+        let x70 = Height_vs.powi(-1);
+        let x71 = x62*x70;
+        let x72 = Height_vs*x21;
+        let x73 = BSA*x72;
+        let x74 = x73 < 0.0;
+        let x75 = if x74 {
+    -QSkin_u*x71
+} else {
+    0.0
+};
+        let x76 = x69*x75;
+        let x77 = Kp_sc_vs*x76;
+        let x78 = scFBlood*scFSkin;
+        let x79 = PCSkin.powi(-1);
+        let x80 = BM*x79;
+        let x81 = x75*x80;
+        let x82 = -x3*x78;
+        let x83 = -BSA*x68 + BSA*x77 + x78*x81 + x82;
+        let x84 = fSA_exposed.powi(-1);
+        let x85 = x60*x84;
+        let x86 = x59*x85;
+        let x87 = x24 > 0.0;
+        let x88 = if x87 {
+    QSkin_sc_e*x86
+} else {
+    0.0
+};
+        let x89 = x70*x85;
+        let x90 = x26 > 0.0;
+        let x91 = if x90 {
+    QSkin_e*x89
+} else {
+    0.0
+};
+        let x92 = Kp_sc_vs*x69*x91;
+        let x93 = x80*x91;
+        let x94 = -BSA*Kp_sc_vs*x88 + BSA*x92 + x78*x93 + x82;
+        let x95 = x67 - x76;
+        let x96 = x21;
+        let x97 = BSA*Kp_sc_vs;
+        let x98 = x96*x97;
+        let x99 = -x69*x91 + x88;
+        let x100 = -x99;
+        let x101 = x57*x97;
+        let x102 = scVArt - 1.0;
+        let x103 = x102.powi(-1);
+        let x104 = QVen*x103;
+        let x105 = x0*x104;
+        let x106 = Ke*fub;
+        let x107 = x106*x39;
+        let x108 = x107*x3;
+        let x109 = fSA_exposed*x78;
+        let x110 = Air.powi(-1);
+        let x111 = QAir*x110;
+        let x112 = scFBlood*x111;
+        let x113 = PCAir.powi(-1);
+        let x114 = Falv*x113;
+        let x115 = x114*x39;
+        let x116 = -BM*x112 + x115*x3;
+        let x117 = fSA_exposed*x91;
+        let x118 = scFPoor*x31;
+        let x119 = BM*x20;
+        let x120 = x119 + x27 - x65 - x73;
+        let x121 = BM*x120.powi(-1);
+        let x122 = x21*x75;
+        let x123 = x122*x80;
+        let x124 = scFFat*x7 + scFLiver*x56 + scFSkin*x117*x80 - scFSkin*x123 + x105 - x118*x121 - x15*x17;
+        let x125 = scFBlood;
+        dy[0] = x11*x9;
+        dy[1] = x16*x19;
+        dy[2] = x34*x36;
+        dy[3] = x37 - x52 - x53*x56 + x54;
+        dy[4] = x52;
+        dy[5] = -x37;
+        dy[6] = x58*x83;
+        dy[7] = -x57*x94;
+        dy[8] = x95*x98;
+        dy[9] = x100*x101;
+        dy[10] = QArt*scFBlood*scFSkin*x0*x1*x21 + QArt*scFBlood*x0*x1*x17 - scFBlood*x105 - x10*x3 - x108 - x109*x3 - x116 - x3*x35 - x54;
+        dy[11] = x124*x125;
+        dy[12] = x108;
+        dy[13] = x116;
+    };
+
+    let jac = |y: &V, p: &V, _t: f64, v: &V, jv: &mut V| {
+        jv.fill(0.0);
+        let BM = theta(p, 0);
+        let BSA = theta(p, 1);
+        let scVFat = theta(p, 2);
+        let scVRich = theta(p, 3);
+        let scVLiver = theta(p, 4);
+        let scVBlood = theta(p, 5);
+        let scVArt = theta(p, 6);
+        let scFBlood = theta(p, 7);
+        let scFFat = theta(p, 8);
+        let scFPoor = theta(p, 9);
+        let scFLiver = theta(p, 10);
+        let scFSkin = theta(p, 11);
+        let fSA_exposed = theta(p, 12);
+        let Height_sc = theta(p, 13);
+        let Height_vs = theta(p, 14);
+        let Falv = theta(p, 15);
+        let PCFat = theta(p, 16);
+        let PCLiver = theta(p, 17);
+        let PCRich = theta(p, 18);
+        let PCPoor = theta(p, 19);
+        let PCSkin_sc = theta(p, 20);
+        let PCSkin = theta(p, 21);
+        let PCAir = theta(p, 22);
+        let kGut = theta(p, 23);
+        let Kp_sc_vs = theta(p, 24);
+        let Km = theta(p, 25);
+        let Michaelis = theta(p, 26);
+        let Vmax = theta(p, 27);
+        let CLH = theta(p, 28);
+        let Ke = theta(p, 29);
+        let fub = theta(p, 30);
+        let Air = theta(p, 31);
+        let QLiver = y[3];
+        let x0 = scVBlood.powi(-1);
+        let x1 = scVArt.powi(-1);
+        let x4 = scVFat.powi(-1);
+        let x5 = PCFat.powi(-1);
+        let x10 = scFBlood*scFFat;
+        let x11 = x10;
+        let x12 = scVRich.powi(-1);
+        let x13 = PCRich.powi(-1);
+        let x17 = scFFat + scFLiver + scFPoor + scFSkin - 1.0;
+        let x18 = scFBlood*x17;
+        let x19 = x18;
+        let x20 = scVBlood + scVFat + scVLiver + scVRich - 0.9;
+        let x21 = fSA_exposed - 1.0;
+        let x23 = Height_sc*fSA_exposed;
+        let x24 = BSA*x23;
+        let x25 = Height_vs*fSA_exposed;
+        let x26 = BSA*x25;
+        let x27 = x24 + x26;
+        let x30 = PCPoor.powi(-1);
+        let x35 = scFBlood*scFPoor;
+        let x36 = x35;
+        let x38 = BM*scVLiver;
+        let x39 = BM.powi(-1);
+        let x40 = scVLiver.powi(-1);
+        let x41 = x39*x40;
+        let x42 = x38 > 0.0;
+        let x43 = if x42 {
+    QLiver*x41
+} else {
+    0.0
+};
+        let x44 = Km*PCLiver + x43;
+        let x45 = x44.powi(-1);
+        let x46 = x43*x45;
+        let x48 = Michaelis > 0.5;
+        let x49 = PCLiver.powi(-1);
+        let x53 = scFBlood*scFLiver;
+        let x57 = fSA_exposed;
+        let x59 = Height_sc.powi(-1);
+        let x60 = BSA.powi(-1);
+        let x61 = x21.powi(-1);
+        let x62 = x60*x61;
+        let x63 = x59*x62;
+        let x64 = Height_sc*x21;
+        let x65 = BSA*x64;
+        let x66 = x65 < 0.0;
+        let x69 = PCSkin_sc.powi(-1);
+        let x70 = Height_vs.powi(-1);
+        let x71 = x62*x70;
+        let x72 = Height_vs*x21;
+        let x73 = BSA*x72;
+        let x74 = x73 < 0.0;
+        let x78 = scFBlood*scFSkin;
+        let x79 = PCSkin.powi(-1);
+        let x80 = BM*x79;
+        let x84 = fSA_exposed.powi(-1);
+        let x85 = x60*x84;
+        let x86 = x59*x85;
+        let x87 = x24 > 0.0;
+        let x89 = x70*x85;
+        let x90 = x26 > 0.0;
+        let x96 = x21;
+        let x97 = BSA*Kp_sc_vs;
+        let x98 = x96*x97;
+        let x101 = x57*x97;
+        let x102 = scVArt - 1.0;
+        let x103 = x102.powi(-1);
+        let x106 = Ke*fub;
+        let x107 = x106*x39;
+        let x109 = fSA_exposed*x78;
+        let x110 = Air.powi(-1);
+        let x113 = PCAir.powi(-1);
+        let x114 = Falv*x113;
+        let x115 = x114*x39;
+        let x119 = BM*x20;
+        let x120 = x119 + x27 - x65 - x73;
+        let x121 = BM*x120.powi(-1);
+        let x125 = scFBlood;
+        let x126 = x11*x4;
+        let x127 = x126*x5;
+        let x128 = x0;
+        let x129 = x1*x128;
+        let x130 = x12*x19;
+        let x131 = x13*x130;
+        let x132 = x121*x36;
+        let x133 = x132*x30;
+        let x134 = if x42 {
+    x41
+} else {
+    0.0
+};
+        let x135 = Vmax*x45;
+        let x136 = CLH*x49;
+        let x137 = fub*(if x48 {
+    x134*x135*x38*(1.0 - x46)
+} else {
+    x134*x136
+});
+        let x138 = x40*x49*x53;
+        let x139 = kGut;
+        let x140 = if x74 {
+    -x71
+} else {
+    0.0
+};
+        let x141 = BSA*x69;
+        let x142 = Kp_sc_vs*x141;
+        let x143 = x78*x80;
+        let x144 = x142 + x143;
+        let x145 = x144*x96;
+        let x146 = x98*(if x66 {
+    -x63
+} else {
+    0.0
+});
+        let x147 = x0*x1;
+        let x148 = scFSkin;
+        let x149 = scFBlood*x148;
+        let x150 = x149*x21;
+        let x151 = if x90 {
+    x89
+} else {
+    0.0
+};
+        let x152 = x144*x57;
+        let x153 = x101*(if x87 {
+    x86
+} else {
+    0.0
+});
+        let x154 = x57*x78;
+        let x155 = x142*x96;
+        let x156 = x142*x57;
+        let x157 = x0*x125;
+        let x158 = x103*x157;
+        let x159 = BM*x125;
+        let x160 = x110*x159;
+        jv[0] += (-x127) * v[0];
+        jv[0] += (x10*x129) * v[10];
+        jv[1] += (x131) * v[1];
+        jv[1] += (-x129*x18) * v[10];
+        jv[2] += (x133) * v[2];
+        jv[2] += (x129*x35) * v[10];
+        jv[3] += (-x137 - x138) * v[3];
+        jv[3] += (x139) * v[5];
+        jv[3] += (x129*x53) * v[10];
+        jv[4] += (x137) * v[3];
+        jv[5] += (-x139) * v[5];
+        jv[6] += (x140*x145) * v[6];
+        jv[6] += (-x146) * v[8];
+        jv[6] += (-x147*x150) * v[10];
+        jv[7] += (-x151*x152) * v[7];
+        jv[7] += (x153) * v[9];
+        jv[7] += (x147*x154) * v[10];
+        jv[8] += (-x140*x155) * v[6];
+        jv[8] += (x146) * v[8];
+        jv[9] += (x151*x156) * v[7];
+        jv[9] += (-x153) * v[9];
+        jv[10] += (x129*(scFBlood*scFSkin*x21 + scFBlood*x17 - x10 - x107 - x109 - x115 - x35 - x53)) * v[10];
+        jv[10] += (-x158) * v[11];
+        jv[10] += (x160) * v[13];
+        jv[11] += (x127) * v[0];
+        jv[11] += (-x131) * v[1];
+        jv[11] += (-x133) * v[2];
+        jv[11] += (x138) * v[3];
+        jv[11] += (-x140*x150*x80) * v[6];
+        jv[11] += (x143*x151*x57) * v[7];
+        jv[11] += (x158) * v[11];
+        jv[12] += (x107*x129) * v[10];
+        jv[13] += (x115*x129) * v[10];
+        jv[13] += (-x160) * v[13];
+    };
+
+    let rhs_sens = |y: &V, p: &V, _t: f64, v: &V, jv: &mut V| {
+        jv.fill(0.0);
+        let BM = theta(p, 0);
+        let BSA = theta(p, 1);
+        let scVFat = theta(p, 2);
+        let scVRich = theta(p, 3);
+        let scVLiver = theta(p, 4);
+        let scVBlood = theta(p, 5);
+        let scVArt = theta(p, 6);
+        let scFBlood = theta(p, 7);
+        let scFFat = theta(p, 8);
+        let scFPoor = theta(p, 9);
+        let scFLiver = theta(p, 10);
+        let scFSkin = theta(p, 11);
+        let fSA_exposed = theta(p, 12);
+        let Height_sc = theta(p, 13);
+        let Height_vs = theta(p, 14);
+        let Falv = theta(p, 15);
+        let PCFat = theta(p, 16);
+        let PCLiver = theta(p, 17);
+        let PCRich = theta(p, 18);
+        let PCPoor = theta(p, 19);
+        let PCSkin_sc = theta(p, 20);
+        let PCSkin = theta(p, 21);
+        let PCAir = theta(p, 22);
+        let Kp_sc_vs = theta(p, 24);
+        let Km = theta(p, 25);
+        let Michaelis = theta(p, 26);
+        let Vmax = theta(p, 27);
+        let CLH = theta(p, 28);
+        let Ke = theta(p, 29);
+        let fub = theta(p, 30);
+        let Air = theta(p, 31);
+        let QFat = y[0];
+        let QRich = y[1];
+        let QPoor = y[2];
+        let QLiver = y[3];
+        let QGut = y[5];
+        let QSkin_u = y[6];
+        let QSkin_e = y[7];
+        let QSkin_sc_u = y[8];
+        let QSkin_sc_e = y[9];
+        let QArt = y[10];
+        let QVen = y[11];
+        let QAir = y[13];
+        let x0 = scVBlood.powi(-1);
+        let x1 = scVArt.powi(-1);
+        let x2 = QArt*x1;
+        let x3 = x0*x2;
+        let x4 = scVFat.powi(-1);
+        let x5 = PCFat.powi(-1);
+        let x6 = QFat*x5;
+        let x7 = x4*x6;
+        let x8 = -x7;
+        let x9 = x3 + x8;
+        let x10 = scFBlood*scFFat;
+        let x11 = x10;
+        let x12 = scVRich.powi(-1);
+        let x13 = PCRich.powi(-1);
+        let x14 = QRich*x13;
+        let x15 = x12*x14;
+        let x16 = x15 - x3;
+        let x17 = scFFat + scFLiver + scFPoor + scFSkin - 1.0;
+        let x18 = scFBlood*x17;
+        let x19 = x18;
+        let x20 = scVBlood + scVFat + scVLiver + scVRich - 0.9;
+        let x21 = fSA_exposed - 1.0;
+        let x22 = -BSA*x21;
+        let x23 = Height_sc*fSA_exposed;
+        let x24 = BSA*x23;
+        let x25 = Height_vs*fSA_exposed;
+        let x26 = BSA*x25;
+        let x27 = x24 + x26;
+        let x28 = BM*x20 + Height_sc*x22 + Height_vs*x22 + x27;
+        let x29 = x28.powi(-1);
+        let x30 = PCPoor.powi(-1);
+        let x31 = QPoor*x30;
+        let x32 = x29*x31;
+        let x33 = BM*x32;
+        let x34 = x3 + x33;
+        let x35 = scFBlood*scFPoor;
+        let x36 = x35;
+        let x38 = BM*scVLiver;
+        let x39 = BM.powi(-1);
+        let x40 = scVLiver.powi(-1);
+        let x41 = x39*x40;
+        let x42 = x38 > 0.0;
+        let x43 = if x42 {
+    QLiver*x41
+} else {
+    0.0
+};
+        let x44 = Km*PCLiver + x43;
+        let x45 = x44.powi(-1);
+        let x46 = x43*x45;
+        let x47 = x38*x46;
+        let x48 = Michaelis > 0.5;
+        let x49 = PCLiver.powi(-1);
+        let x50 = x43*x49;
+        let x51 = if x48 {
+    Vmax*x47
+} else {
+    CLH*x50
+};
+        let x53 = scFBlood*scFLiver;
+        let x55 = QLiver*x40;
+        let x56 = x49*x55;
+        let x57 = fSA_exposed;
+        let x58 = x57 - 1.0;
+        let x59 = Height_sc.powi(-1);
+        let x60 = BSA.powi(-1);
+        let x61 = x21.powi(-1);
+        let x62 = x60*x61;
+        let x63 = x59*x62;
+        let x64 = Height_sc*x21;
+        let x65 = BSA*x64;
+        let x66 = x65 < 0.0;
+        let x67 = if x66 {
+    -QSkin_sc_u*x63
+} else {
+    0.0
+};
+        let x68 = Kp_sc_vs*x67;
+        let x69 = PCSkin_sc.powi(-1);
+        let x70 = Height_vs.powi(-1);
+        let x71 = x62*x70;
+        let x72 = Height_vs*x21;
+        let x73 = BSA*x72;
+        let x74 = x73 < 0.0;
+        let x75 = if x74 {
+    -QSkin_u*x71
+} else {
+    0.0
+};
+        let x76 = x69*x75;
+        let x77 = Kp_sc_vs*x76;
+        let x78 = scFBlood*scFSkin;
+        let x79 = PCSkin.powi(-1);
+        let x80 = BM*x79;
+        let x81 = x75*x80;
+        let x82 = -x3*x78;
+        let x83 = -BSA*x68 + BSA*x77 + x78*x81 + x82;
+        let x84 = fSA_exposed.powi(-1);
+        let x85 = x60*x84;
+        let x86 = x59*x85;
+        let x87 = x24 > 0.0;
+        let x88 = if x87 {
+    QSkin_sc_e*x86
+} else {
+    0.0
+};
+        let x89 = x70*x85;
+        let x90 = x26 > 0.0;
+        let x91 = if x90 {
+    QSkin_e*x89
+} else {
+    0.0
+};
+        let x92 = Kp_sc_vs*x69*x91;
+        let x93 = x80*x91;
+        let x94 = -BSA*Kp_sc_vs*x88 + BSA*x92 + x78*x93 + x82;
+        let x95 = x67 - x76;
+        let x96 = x21;
+        let x97 = BSA*Kp_sc_vs;
+        let x98 = x96*x97;
+        let x99 = -x69*x91 + x88;
+        let x100 = -x99;
+        let x101 = x57*x97;
+        let x102 = scVArt - 1.0;
+        let x103 = x102.powi(-1);
+        let x104 = QVen*x103;
+        let x105 = x0*x104;
+        let x106 = Ke*fub;
+        let x107 = x106*x39;
+        let x109 = fSA_exposed*x78;
+        let x110 = Air.powi(-1);
+        let x111 = QAir*x110;
+        let x112 = scFBlood*x111;
+        let x113 = PCAir.powi(-1);
+        let x114 = Falv*x113;
+        let x115 = x114*x39;
+        let x117 = fSA_exposed*x91;
+        let x118 = scFPoor*x31;
+        let x119 = BM*x20;
+        let x120 = x119 + x27 - x65 - x73;
+        let x121 = BM*x120.powi(-1);
+        let x122 = x21*x75;
+        let x123 = x122*x80;
+        let x124 = scFFat*x7 + scFLiver*x56 + scFSkin*x117*x80 - scFSkin*x123 + x105 - x118*x121 - x15*x17;
+        let x125 = scFBlood;
+        let x126 = x11*x4;
+        let x128 = x0;
+        let x130 = x12*x19;
+        let x132 = x121*x36;
+        let x135 = Vmax*x45;
+        let x136 = CLH*x49;
+        let x141 = BSA*x69;
+        let x142 = Kp_sc_vs*x141;
+        let x143 = x78*x80;
+        let x144 = x142 + x143;
+        let x145 = x144*x96;
+        let x148 = scFSkin;
+        let x149 = scFBlood*x148;
+        let x150 = x149*x21;
+        let x152 = x144*x57;
+        let x154 = x57*x78;
+        let x155 = x142*x96;
+        let x156 = x142*x57;
+        let x157 = x0*x125;
+        let x159 = BM*x125;
+        let x161 = x6*scVFat.powi(-2);
+        let x162 = scVBlood.powi(-2);
+        let x163 = x162*x2;
+        let x164 = QArt*scVArt.powi(-2);
+        let x165 = x10*x164;
+        let x166 = scFFat;
+        let x167 = QFat*x126*PCFat.powi(-2);
+        let x168 = x14*scVRich.powi(-2);
+        let x169 = x164*x18;
+        let x170 = scFLiver;
+        let x171 = scFPoor;
+        let x172 = x125*x16;
+        let x173 = QRich*x130*PCRich.powi(-2);
+        let x174 = x23 + x25 - x64 - x72;
+        let x175 = x120.powi(-2);
+        let x176 = BM*x175*x31*x36;
+        let x177 = BM.powi(2);
+        let x178 = x177*x31;
+        let x179 = x175*x178;
+        let x180 = -x179*x36;
+        let x181 = x28.powi(-2);
+        let x182 = x164*x35;
+        let x183 = BSA*x176;
+        let x184 = -x183;
+        let x185 = QPoor*x132*PCPoor.powi(-2);
+        let x186 = x177.powi(-1);
+        let x187 = if x42 {
+    -x186*x55
+} else {
+    0.0
+};
+        let x188 = BM*x187;
+        let x189 = fub;
+        let x190 = x189*(if x48 {
+    scVLiver*x135*(-x188*x46 + x188 + x43)
+} else {
+    x136*x187
+});
+        let x191 = scVLiver.powi(-2);
+        let x192 = QLiver*x191;
+        let x193 = if x42 {
+    -x192*x39
+} else {
+    0.0
+};
+        let x194 = scVLiver*x193;
+        let x195 = fub*(if x48 {
+    BM*x135*(-x194*x46 + x194 + x43)
+} else {
+    x136*x193
+});
+        let x196 = x163;
+        let x197 = x164*x53;
+        let x198 = -x56;
+        let x199 = x198 + x3;
+        let x200 = Vmax*x38*x43*x44.powi(-2);
+        let x201 = PCLiver.powi(-2);
+        let x202 = fub*(if x48 {
+    -Km*x200
+} else {
+    -CLH*x201*x43
+});
+        let x203 = QGut;
+        let x204 = x189*(if x48 {
+    -PCLiver*x200
+} else {
+    0.0
+});
+        let x205 = x189*(if x48 {
+    x47
+} else {
+    0.0
+});
+        let x206 = x189*(if x48 {
+    0.0
+} else {
+    x50
+});
+        let x207 = x51;
+        let x208 = x122*x149;
+        let x209 = BSA.powi(-2);
+        let x210 = x209*x61;
+        let x211 = QSkin_sc_u*x59;
+        let x212 = BSA*(if x66 {
+    x210*x211
+} else {
+    0.0
+});
+        let x213 = QSkin_u*x70;
+        let x214 = if x74 {
+    x210*x213
+} else {
+    0.0
+};
+        let x215 = x0*x164;
+        let x216 = -x3;
+        let x217 = x21*(x216 + x81);
+        let x218 = x60*x21.powi(-2);
+        let x219 = x21*(if x66 {
+    x211*x218
+} else {
+    0.0
+});
+        let x220 = x21*(if x74 {
+    x213*x218
+} else {
+    0.0
+});
+        let x221 = Height_sc.powi(-2);
+        let x222 = x98*(if x66 {
+    QSkin_sc_u*x221*x62
+} else {
+    0.0
+});
+        let x223 = Height_vs.powi(-2);
+        let x224 = if x74 {
+    QSkin_u*x223*x62
+} else {
+    0.0
+};
+        let x225 = PCSkin_sc.powi(-2);
+        let x226 = x97;
+        let x227 = x122*x225*x226;
+        let x228 = BM*PCSkin.powi(-2);
+        let x229 = BSA*x96;
+        let x230 = x154*x91;
+        let x231 = x209*x84;
+        let x232 = QSkin_sc_e*x59;
+        let x233 = if x87 {
+    -x231*x232
+} else {
+    0.0
+};
+        let x234 = QSkin_e*x70;
+        let x235 = if x90 {
+    -x231*x234
+} else {
+    0.0
+};
+        let x236 = x57*(-x216 - x93);
+        let x237 = x60*fSA_exposed.powi(-2);
+        let x238 = if x87 {
+    -x232*x237
+} else {
+    0.0
+};
+        let x239 = if x90 {
+    -x234*x237
+} else {
+    0.0
+};
+        let x240 = fSA_exposed*x239;
+        let x241 = x101*(if x87 {
+    -QSkin_sc_e*x221*x85
+} else {
+    0.0
+});
+        let x242 = if x90 {
+    -QSkin_e*x223*x85
+} else {
+    0.0
+};
+        let x243 = x101*x225*x91;
+        let x244 = BSA*x57;
+        let x245 = x186*x3;
+        let x246 = x106*x245;
+        let x247 = x112 + x114*x245;
+        let x248 = x21*x78;
+        let x249 = QVen*x102.powi(-2);
+        let x250 = x115*x164;
+        let x251 = x107*x164;
+        let x252 = x3*x39;
+        let x253 = x252;
+        let x254 = x113*x253;
+        let x255 = Falv*x253*PCAir.powi(-2);
+        let x256 = x189*x252;
+        let x257 = Ke*x253;
+        let x258 = QAir*x159*Air.powi(-2);
+        let x259 = scFSkin*x79;
+        let x260 = fSA_exposed*x259;
+        let x261 = x21*x259;
+        let x262 = x118*x175;
+        let x263 = scFPoor*x179;
+        if let Some(k) = slot[2] {
+            jv[0] += (x11*x161) * v[k];
+        }
+        if let Some(k) = slot[5] {
+            jv[0] += (-x11*x163) * v[k];
+        }
+        if let Some(k) = slot[6] {
+            jv[0] += (-x128*x165) * v[k];
+        }
+        if let Some(k) = slot[7] {
+            jv[0] += (x166*x9) * v[k];
+        }
+        if let Some(k) = slot[8] {
+            jv[0] += (x125*x9) * v[k];
+        }
+        if let Some(k) = slot[16] {
+            jv[0] += (x167) * v[k];
+        }
+        if let Some(k) = slot[0] {
+            jv[1] += (0.0) * v[k];
+        }
+        if let Some(k) = slot[3] {
+            jv[1] += (-x168*x19) * v[k];
+        }
+        if let Some(k) = slot[5] {
+            jv[1] += (x163*x19) * v[k];
+        }
+        if let Some(k) = slot[6] {
+            jv[1] += (x128*x169) * v[k];
+        }
+        if let Some(k) = slot[7] {
+            jv[1] += (x16*(x148 + x166 + x170 + x171 - 1.0)) * v[k];
+        }
+        if let Some(k) = slot[8] {
+            jv[1] += (x172) * v[k];
+        }
+        if let Some(k) = slot[9] {
+            jv[1] += (x172) * v[k];
+        }
+        if let Some(k) = slot[10] {
+            jv[1] += (x172) * v[k];
+        }
+        if let Some(k) = slot[11] {
+            jv[1] += (x172) * v[k];
+        }
+        if let Some(k) = slot[18] {
+            jv[1] += (-x173) * v[k];
+        }
+        if let Some(k) = slot[0] {
+            jv[2] += (x32*x36*(-x119*x29 + 1.0)) * v[k];
+        }
+        if let Some(k) = slot[1] {
+            jv[2] += (-x174*x176) * v[k];
+        }
+        if let Some(k) = slot[2] {
+            jv[2] += (x180) * v[k];
+        }
+        if let Some(k) = slot[3] {
+            jv[2] += (x180) * v[k];
+        }
+        if let Some(k) = slot[4] {
+            jv[2] += (x180) * v[k];
+        }
+        if let Some(k) = slot[5] {
+            jv[2] += (-x36*(x163 + x178*x181)) * v[k];
+        }
+        if let Some(k) = slot[6] {
+            jv[2] += (-x128*x182) * v[k];
+        }
+        if let Some(k) = slot[7] {
+            jv[2] += (x171*x34) * v[k];
+        }
+        if let Some(k) = slot[9] {
+            jv[2] += (x125*x34) * v[k];
+        }
+        if let Some(k) = slot[13] {
+            jv[2] += (x184) * v[k];
+        }
+        if let Some(k) = slot[14] {
+            jv[2] += (x184) * v[k];
+        }
+        if let Some(k) = slot[19] {
+            jv[2] += (-x185) * v[k];
+        }
+        if let Some(k) = slot[0] {
+            jv[3] += (-x190) * v[k];
+        }
+        if let Some(k) = slot[4] {
+            jv[3] += (QLiver*scFBlood*scFLiver*x191*x49 - x195) * v[k];
+        }
+        if let Some(k) = slot[5] {
+            jv[3] += (-x196*x53) * v[k];
+        }
+        if let Some(k) = slot[6] {
+            jv[3] += (-x128*x197) * v[k];
+        }
+        if let Some(k) = slot[7] {
+            jv[3] += (x170*x199) * v[k];
+        }
+        if let Some(k) = slot[10] {
+            jv[3] += (x125*x199) * v[k];
+        }
+        if let Some(k) = slot[17] {
+            jv[3] += (QLiver*scFBlood*scFLiver*x201*x40 - x202) * v[k];
+        }
+        if let Some(k) = slot[23] {
+            jv[3] += (x203) * v[k];
+        }
+        if let Some(k) = slot[25] {
+            jv[3] += (-x204) * v[k];
+        }
+        if let Some(k) = slot[27] {
+            jv[3] += (-x205) * v[k];
+        }
+        if let Some(k) = slot[28] {
+            jv[3] += (-x206) * v[k];
+        }
+        if let Some(k) = slot[30] {
+            jv[3] += (-x207) * v[k];
+        }
+        if let Some(k) = slot[0] {
+            jv[4] += (x190) * v[k];
+        }
+        if let Some(k) = slot[4] {
+            jv[4] += (x195) * v[k];
+        }
+        if let Some(k) = slot[17] {
+            jv[4] += (x202) * v[k];
+        }
+        if let Some(k) = slot[25] {
+            jv[4] += (x204) * v[k];
+        }
+        if let Some(k) = slot[27] {
+            jv[4] += (x205) * v[k];
+        }
+        if let Some(k) = slot[28] {
+            jv[4] += (x206) * v[k];
+        }
+        if let Some(k) = slot[30] {
+            jv[4] += (x207) * v[k];
+        }
+        if let Some(k) = slot[23] {
+            jv[5] += (-x203) * v[k];
+        }
+        if let Some(k) = slot[0] {
+            jv[6] += (x208*x79) * v[k];
+        }
+        if let Some(k) = slot[1] {
+            jv[6] += (x58*(-Kp_sc_vs*x212 + x142*x214 + x143*x214 - x68 + x77)) * v[k];
+        }
+        if let Some(k) = slot[5] {
+            jv[6] += (x150*x163) * v[k];
+        }
+        if let Some(k) = slot[6] {
+            jv[6] += (x150*x215) * v[k];
+        }
+        if let Some(k) = slot[7] {
+            jv[6] += (x148*x217) * v[k];
+        }
+        if let Some(k) = slot[11] {
+            jv[6] += (x125*x217) * v[k];
+        }
+        if let Some(k) = slot[12] {
+            jv[6] += (x142*x220 + x143*x220 - x219*x97 + x83) * v[k];
+        }
+        if let Some(k) = slot[13] {
+            jv[6] += (-x222) * v[k];
+        }
+        if let Some(k) = slot[14] {
+            jv[6] += (x145*x224) * v[k];
+        }
+        if let Some(k) = slot[20] {
+            jv[6] += (-x227) * v[k];
+        }
+        if let Some(k) = slot[21] {
+            jv[6] += (-x208*x228) * v[k];
+        }
+        if let Some(k) = slot[24] {
+            jv[6] += (-x229*x95) * v[k];
+        }
+        if let Some(k) = slot[0] {
+            jv[7] += (-x230*x79) * v[k];
+        }
+        if let Some(k) = slot[1] {
+            jv[7] += (x57*(BSA*Kp_sc_vs*x233 + Kp_sc_vs*x88 - x142*x235 - x143*x235 - x92)) * v[k];
+        }
+        if let Some(k) = slot[5] {
+            jv[7] += (-x154*x163) * v[k];
+        }
+        if let Some(k) = slot[6] {
+            jv[7] += (-x154*x215) * v[k];
+        }
+        if let Some(k) = slot[7] {
+            jv[7] += (scFSkin*x236) * v[k];
+        }
+        if let Some(k) = slot[11] {
+            jv[7] += (scFBlood*x236) * v[k];
+        }
+        if let Some(k) = slot[12] {
+            jv[7] += (BSA*Kp_sc_vs*fSA_exposed*x238 - x142*x240 - x143*x240 - x94) * v[k];
+        }
+        if let Some(k) = slot[13] {
+            jv[7] += (x241) * v[k];
+        }
+        if let Some(k) = slot[14] {
+            jv[7] += (-x152*x242) * v[k];
+        }
+        if let Some(k) = slot[20] {
+            jv[7] += (x243) * v[k];
+        }
+        if let Some(k) = slot[21] {
+            jv[7] += (x228*x230) * v[k];
+        }
+        if let Some(k) = slot[24] {
+            jv[7] += (x244*x99) * v[k];
+        }
+        if let Some(k) = slot[1] {
+            jv[8] += (Kp_sc_vs*x96*(-x141*x214 + x212 + x95)) * v[k];
+        }
+        if let Some(k) = slot[12] {
+            jv[8] += (x226*(x219 - x220*x69 + x95)) * v[k];
+        }
+        if let Some(k) = slot[13] {
+            jv[8] += (x222) * v[k];
+        }
+        if let Some(k) = slot[14] {
+            jv[8] += (-x155*x224) * v[k];
+        }
+        if let Some(k) = slot[20] {
+            jv[8] += (x227) * v[k];
+        }
+        if let Some(k) = slot[24] {
+            jv[8] += (x229*x95) * v[k];
+        }
+        if let Some(k) = slot[1] {
+            jv[9] += (Kp_sc_vs*x57*(-BSA*x233 + BSA*x235*x69 - x99)) * v[k];
+        }
+        if let Some(k) = slot[12] {
+            jv[9] += (x226*(-fSA_exposed*x238 + fSA_exposed*x239*x69 - x99)) * v[k];
+        }
+        if let Some(k) = slot[13] {
+            jv[9] += (-x241) * v[k];
+        }
+        if let Some(k) = slot[14] {
+            jv[9] += (x156*x242) * v[k];
+        }
+        if let Some(k) = slot[20] {
+            jv[9] += (-x243) * v[k];
+        }
+        if let Some(k) = slot[24] {
+            jv[9] += (x100*x244) * v[k];
+        }
+        if let Some(k) = slot[0] {
+            jv[10] += (x246 + x247) * v[k];
+        }
+        if let Some(k) = slot[5] {
+            jv[10] += (x162*(scFBlood*x104 + x10*x2 + x107*x2 + x109*x2 + x115*x2 - x18*x2 - x2*x248 + x2*x35 + x2*x53)) * v[k];
+        }
+        if let Some(k) = slot[6] {
+            jv[10] += (x128*(scFBlood*x249 + x109*x164 - x164*x248 + x165 - x169 + x182 + x197 + x250 + x251)) * v[k];
+        }
+        if let Some(k) = slot[7] {
+            jv[10] += (BM*QAir*x110 + QArt*scFSkin*x0*x1*x21 + QArt*x0*x1*x17 - fSA_exposed*scFSkin*x3 - scFFat*x3 - scFLiver*x3 - scFPoor*x3 - x105) * v[k];
+        }
+        if let Some(k) = slot[11] {
+            jv[10] += (0.0) * v[k];
+        }
+        if let Some(k) = slot[15] {
+            jv[10] += (-x254) * v[k];
+        }
+        if let Some(k) = slot[22] {
+            jv[10] += (x255) * v[k];
+        }
+        if let Some(k) = slot[29] {
+            jv[10] += (-x256) * v[k];
+        }
+        if let Some(k) = slot[30] {
+            jv[10] += (-x257) * v[k];
+        }
+        if let Some(k) = slot[31] {
+            jv[10] += (-x258) * v[k];
+        }
+        if let Some(k) = slot[0] {
+            jv[11] += (x125*(-scFPoor*x32 + x117*x259 + x118*x119*x181 - x122*x259)) * v[k];
+        }
+        if let Some(k) = slot[1] {
+            jv[11] += (x159*(x174*x262 - x214*x261 + x235*x260)) * v[k];
+        }
+        if let Some(k) = slot[2] {
+            jv[11] += (x125*(-scFFat*x161 + x263)) * v[k];
+        }
+        if let Some(k) = slot[3] {
+            jv[11] += (x125*(x168*x17 + x263)) * v[k];
+        }
+        if let Some(k) = slot[4] {
+            jv[11] += (x125*(-scFLiver*x192*x49 + x263)) * v[k];
+        }
+        if let Some(k) = slot[5] {
+            jv[11] += (x125*(-x104*x162 + x263)) * v[k];
+        }
+        if let Some(k) = slot[6] {
+            jv[11] += (-x157*x249) * v[k];
+        }
+        if let Some(k) = slot[7] {
+            jv[11] += (x124) * v[k];
+        }
+        if let Some(k) = slot[8] {
+            jv[11] += (x125*(-x15 - x8)) * v[k];
+        }
+        if let Some(k) = slot[9] {
+            jv[11] += (-x125*(x15 + x33)) * v[k];
+        }
+        if let Some(k) = slot[10] {
+            jv[11] += (x125*(-x15 - x198)) * v[k];
+        }
+        if let Some(k) = slot[11] {
+            jv[11] += (x125*(BM*fSA_exposed*x79*x91 - x123 - x15)) * v[k];
+        }
+        if let Some(k) = slot[12] {
+            jv[11] += (x149*x80*(-x220 + x240 - x75 + x91)) * v[k];
+        }
+        if let Some(k) = slot[13] {
+            jv[11] += (x183) * v[k];
+        }
+        if let Some(k) = slot[14] {
+            jv[11] += (x159*(BSA*x262 - x224*x261 + x242*x260)) * v[k];
+        }
+        if let Some(k) = slot[16] {
+            jv[11] += (-x167) * v[k];
+        }
+        if let Some(k) = slot[17] {
+            jv[11] += (-x201*x53*x55) * v[k];
+        }
+        if let Some(k) = slot[18] {
+            jv[11] += (x173) * v[k];
+        }
+        if let Some(k) = slot[19] {
+            jv[11] += (x185) * v[k];
+        }
+        if let Some(k) = slot[21] {
+            jv[11] += (x149*x228*(-x117 + x21*x75)) * v[k];
+        }
+        if let Some(k) = slot[0] {
+            jv[12] += (-x246) * v[k];
+        }
+        if let Some(k) = slot[5] {
+            jv[12] += (-x107*x196) * v[k];
+        }
+        if let Some(k) = slot[6] {
+            jv[12] += (-x128*x251) * v[k];
+        }
+        if let Some(k) = slot[29] {
+            jv[12] += (x256) * v[k];
+        }
+        if let Some(k) = slot[30] {
+            jv[12] += (x257) * v[k];
+        }
+        if let Some(k) = slot[0] {
+            jv[13] += (-x247) * v[k];
+        }
+        if let Some(k) = slot[5] {
+            jv[13] += (-x115*x196) * v[k];
+        }
+        if let Some(k) = slot[6] {
+            jv[13] += (-x128*x250) * v[k];
+        }
+        if let Some(k) = slot[7] {
+            jv[13] += (-BM*x111) * v[k];
+        }
+        if let Some(k) = slot[15] {
+            jv[13] += (x254) * v[k];
+        }
+        if let Some(k) = slot[22] {
+            jv[13] += (-x255) * v[k];
+        }
+        if let Some(k) = slot[31] {
+            jv[13] += (x258) * v[k];
+        }
+    };
+
+    let init = |_p: &V, _t: f64, y: &mut V| {
         y[0] = sim_params.init_QFat.unwrap_or(0.0);
         y[1] = sim_params.init_QRich.unwrap_or(0.0);
         y[2] = sim_params.init_QPoor.unwrap_or(0.0);
@@ -499,399 +2125,2600 @@ pub fn run_simulation(params: &str) -> String {
         y[12] = sim_params.init_QExcret.unwrap_or(0.0);
         y[13] = sim_params.init_QAir.unwrap_or(0.0);
     };
+    let init_sens = |_p: &V, _t: f64, _v: &V, y: &mut V| {
+        y.fill(0.0);
+    };
+
     let problem = OdeBuilder::<M>::new()
-        .rhs_implicit(rhs, jac)
-        .init(init, 14)
+        .p(p0)
+        .t0(sim_params.options.t_start.unwrap_or(0.0))
+        .rtol(sim_params.options.rtol.unwrap_or(1e-6))
+        .atol([sim_params.options.atol.unwrap_or(1e-6)])
+        .rhs_sens_implicit(rhs, jac, rhs_sens)
+        .init_sens(init, init_sens, 14)
         .build()
-        .unwrap();
+        .map_err(|e| e.to_string())?;
+    let mut solver = problem.bdf_sens::<LS>().map_err(|e| e.to_string())?;
 
-    let mut solver = problem.bdf::<LS>().unwrap();
     let mut time = Vec::new();
-
-    // Initialize result vectors
-    let mut qfat = Vec::new();
-    let mut qrich = Vec::new();
-    let mut qpoor = Vec::new();
-    let mut qliver = Vec::new();
-    let mut qmetab = Vec::new();
-    let mut qgut = Vec::new();
-    let mut qskin_u = Vec::new();
-    let mut qskin_e = Vec::new();
-    let mut qskin_sc_u = Vec::new();
-    let mut qskin_sc_e = Vec::new();
-    let mut qart = Vec::new();
-    let mut qven = Vec::new();
-    let mut qexcret = Vec::new();
-    let mut qair = Vec::new();
-
-    qfat.push(solver.state().y[0]);
-    qrich.push(solver.state().y[1]);
-    qpoor.push(solver.state().y[2]);
-    qliver.push(solver.state().y[3]);
-    qmetab.push(solver.state().y[4]);
-    qgut.push(solver.state().y[5]);
-    qskin_u.push(solver.state().y[6]);
-    qskin_e.push(solver.state().y[7]);
-    qskin_sc_u.push(solver.state().y[8]);
-    qskin_sc_e.push(solver.state().y[9]);
-    qart.push(solver.state().y[10]);
-    qven.push(solver.state().y[11]);
-    qexcret.push(solver.state().y[12]);
-    qair.push(solver.state().y[13]);
-    time.push(0.0);
-
-    let final_time = sim_params.final_time.unwrap_or(24.0);
-    solver.set_stop_time(final_time).unwrap();
+    let mut states: Vec<Vec<f64>> = vec![Vec::new(); 14];
+    let mut sens: Vec<Vec<Vec<f64>>> = vec![vec![Vec::new(); 14]; wrt.len()];
+    let mut error = None;
+    let final_time = sim_params.options.final_time.unwrap_or(24.0);
+    solver.set_stop_time(final_time).map_err(|e| e.to_string())?;
     loop {
-        match solver.step() {
-            Ok(OdeSolverStopReason::InternalTimestep) => {
-            qfat.push(solver.state().y[0]);
-            qrich.push(solver.state().y[1]);
-            qpoor.push(solver.state().y[2]);
-            qliver.push(solver.state().y[3]);
-            qmetab.push(solver.state().y[4]);
-            qgut.push(solver.state().y[5]);
-            qskin_u.push(solver.state().y[6]);
-            qskin_e.push(solver.state().y[7]);
-            qskin_sc_u.push(solver.state().y[8]);
-            qskin_sc_e.push(solver.state().y[9]);
-            qart.push(solver.state().y[10]);
-            qven.push(solver.state().y[11]);
-            qexcret.push(solver.state().y[12]);
-            qair.push(solver.state().y[13]);
-                time.push(solver.state().t);
-            },
-            Ok(OdeSolverStopReason::TstopReached) => break,
-            Ok(OdeSolverStopReason::RootFound(_)) => break,
-            Err(_) => panic!("Solver Error"),
+        let state = solver.state();
+        time.push(state.t / output_scale);
+        for (i, series) in states.iter_mut().enumerate() {
+            series.push(state.y[i]);
+        }
+        for (s, series) in state.s.iter().zip(sens.iter_mut()) {
+            for (i, values) in series.iter_mut().enumerate() {
+                values.push(s[i]);
+            }
+        }
+        if state.t >= final_time {
+            break;
+        }
+        if let Err(e) = solver.step() {
+            error = Some(MODEL.solver_error(e.to_string(), solver.state().t, solver.state().y.as_slice()).payload());
+            break;
         }
     }
 
-    let mut species_map = HashMap::new();
-        species_map.insert("qfat".to_string(), qfat);
-        species_map.insert("qrich".to_string(), qrich);
-        species_map.insert("qpoor".to_string(), qpoor);
-        species_map.insert("qliver".to_string(), qliver);
-        species_map.insert("qmetab".to_string(), qmetab);
-        species_map.insert("qgut".to_string(), qgut);
-        species_map.insert("qskin_u".to_string(), qskin_u);
-        species_map.insert("qskin_e".to_string(), qskin_e);
-        species_map.insert("qskin_sc_u".to_string(), qskin_sc_u);
-        species_map.insert("qskin_sc_e".to_string(), qskin_sc_e);
-        species_map.insert("qart".to_string(), qart);
-        species_map.insert("qven".to_string(), qven);
-        species_map.insert("qexcret".to_string(), qexcret);
-        species_map.insert("qair".to_string(), qair);
-
-    let result = SimulationResult {
+    let named = |series: Vec<Vec<f64>>| {
+        let mut map = OrderedSeries::default();
+        for (name, values) in SPECIES_IDS.iter().zip(series) {
+            map.insert(name.to_string(), values);
+        }
+        map
+    };
+    let result = SensitivityResult {
         time,
-        species: species_map,
+        species: named(states),
+        sensitivities: wrt
+            .into_iter()
+            .zip(sens)
+            .map(|(parameter, series)| ParameterSensitivity { parameter, species: named(series) })
+            .collect(),
+        error,
     };
+    serde_json::to_string(&result).map_err(|e| e.to_string())
+}
+
+/// One observable of get_model_metadata
+#[derive(Serialize)]
+struct ObservableInfo {
+    id: &'static str,
+    units: Option<&'static str>,
+}
+
+/// SBML document the model was generated from
+#[derive(Serialize)]
+struct SbmlSource {
+    id: Option<&'static str>,
+    name: Option<&'static str>,
+    level: Option<u32>,
+    version: Option<u32>,
+    /// SHA-256 of the source file
+    sha256: Option<&'static str>,
+}
+
+/// Generator that wrote this module
+#[derive(Serialize)]
+struct GeneratorInfo {
+    name: &'static str,
+    version: Option<&'static str>,
+    /// ISO 8601 UTC time of generation
+    generated_at: Option<&'static str>,
+}
 
-    serde_json::to_string(&result).unwrap()
+#[derive(Serialize)]
+struct ModelMetadata {
+    model_id: &'static str,
+    num_species: usize,
+    num_parameters: usize,
+    time_units: &'static str,
+    substance_units: &'static str,
+    volume_units: &'static str,
+    sbml: SbmlSource,
+    generator: GeneratorInfo,
+    diffsol_version: &'static str,
+    /// Optional features compiled in: "events", "sensitivities", "embedded_sbml"
+    features: &'static [&'static str],
+    observables: &'static [ObservableInfo],
 }
 
 pub fn get_model_metadata() -> String {
-    let metadata = serde_json::json!({
-        "model_id": "euromix_model",
-        "num_species": 14,
-        "num_parameters": 34,
-        "time_units": "HR",
-        "substance_units": "MilliMOL",
-        "volume_units": "L"
-    });
+    let metadata = ModelMetadata {
+        model_id: "euromix_model",
+        num_species: 14,
+        num_parameters: 34,
+        time_units: "HR",
+        substance_units: "MilliMOL",
+        volume_units: "L",
+        sbml: SbmlSource { id: Some("euromix"), name: None, level: Some(3), version: Some(2), sha256: Some("f938dd3639ac38d7e86702c8b285f93c8e550ee29fc02b6576180915dcdda900") },
+        generator: GeneratorInfo { name: "sbml_rust_generator", version: Some("1.0.0"), generated_at: Some("2026-10-17T00:28:18Z") },
+        diffsol_version: "0.6.3",
+        features: &["sensitivities", "embedded_sbml"],
+        observables: &[
+            ObservableInfo { id: "Fat", units: None },
+            ObservableInfo { id: "Rich", units: None },
+            ObservableInfo { id: "Liver", units: None },
+            ObservableInfo { id: "Skin_e", units: None },
+            ObservableInfo { id: "Skin_u", units: None },
+            ObservableInfo { id: "Skin_sc_e", units: None },
+            ObservableInfo { id: "Skin_sc_u", units: None },
+            ObservableInfo { id: "f_su", units: Some("L_PER_HR") },
+            ObservableInfo { id: "f_se", units: Some("L_PER_HR") },
+            ObservableInfo { id: "VBlood", units: Some("L") },
+            ObservableInfo { id: "FBlood", units: Some("L_PER_HR") },
+            ObservableInfo { id: "Poor", units: None },
+            ObservableInfo { id: "Art", units: None },
+            ObservableInfo { id: "FFat", units: Some("L_PER_HR") },
+            ObservableInfo { id: "FPoor", units: Some("L_PER_HR") },
+            ObservableInfo { id: "FLiver", units: Some("L_PER_HR") },
+            ObservableInfo { id: "FSkin", units: Some("L_PER_HR") },
+            ObservableInfo { id: "Ven", units: None },
+            ObservableInfo { id: "FRich", units: Some("L_PER_HR") },
+            ObservableInfo { id: "FSkin_e", units: Some("L_PER_HR") },
+            ObservableInfo { id: "FSkin_u", units: Some("L_PER_HR") },
+        ],
+    };
     serde_json::to_string(&metadata).unwrap()
 }
 
+/// One entry of get_parameters_info
+#[derive(Serialize)]
+struct ParameterInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    units: Option<&'static str>,
+    /// SBML notes as plain text
+    description: Option<&'static str>,
+    default_value: f64,
+    required: bool,
+    is_compartment: bool,
+    /// Soft bounds for input forms; validate_parameters has the hard checks
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+const PARAMETER_INFO: &[ParameterInfo] = &[
+    ParameterInfo { id: "BM", name: Some("body weight"), units: Some("KiloGM"), description: None, default_value: 70.0, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "BSA", name: Some("body surface area"), units: Some("DeciM2"), description: None, default_value: 190.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "scVFat", name: Some("fat volume as fraction of total body weight"), units: Some("L_PER_KiloGM"), description: None, default_value: 0.209, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scVRich", name: Some("richly perfused tissues volume as fraction of total body weight"), units: Some("L_PER_KiloGM"), description: None, default_value: 0.105, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scVLiver", name: Some("liver volume as fraction of total body weight"), units: Some("L_PER_KiloGM"), description: None, default_value: 0.024, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scVBlood", name: Some("blood volume as fraction of total body weight"), units: Some("L_PER_KiloGM"), description: None, default_value: 0.068, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scVArt", name: Some("arterial blood volume as fraction of total blood volume"), units: Some("UNITLESS"), description: None, default_value: 0.333333333333333, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scFBlood", name: Some("total blood flow per unit mass"), units: Some("L_PER_KiloGM_HR"), description: None, default_value: 4.8, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "scFFat", name: Some("fraction of blood flow going to adipose tissue"), units: Some("UNITLESS"), description: None, default_value: 0.085, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scFPoor", name: Some("fraction total blood flow going to poorly perfused tissue"), units: Some("UNITLESS"), description: None, default_value: 0.12, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scFLiver", name: Some("fraction total blood flow going to liver"), units: Some("UNITLESS"), description: None, default_value: 0.27, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scFSkin", name: Some("fraction total blood flow going to skin"), units: Some("UNITLESS"), description: None, default_value: 0.05, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "fSA_exposed", name: Some("fraction of skin surface area actually exposed"), units: Some("UNITLESS"), description: None, default_value: 0.1, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "Height_sc", name: Some("thickness stratum corneum"), units: Some("DeciM"), description: None, default_value: 0.0001, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Height_vs", name: Some("thickness viable epidermis"), units: Some("DeciM"), description: None, default_value: 0.0122, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Falv", name: Some("alveolar ventilation rate"), units: Some("L_PER_HR"), description: None, default_value: 2220.0, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "PCFat", name: Some("partition coefficient fat over blood"), units: Some("UNITLESS"), description: None, default_value: 2.53, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCLiver", name: Some("partition coefficient liver over blood"), units: Some("UNITLESS"), description: None, default_value: 0.923, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCRich", name: Some("partition coefficient poorly perfused tissue over blood"), units: Some("UNITLESS"), description: None, default_value: 0.875, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCPoor", name: Some("partition coefficient richly perfused tissue over blood"), units: Some("UNITLESS"), description: None, default_value: 0.647, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCSkin_sc", name: Some("partition coefficient viable skin over stratum corneum"), units: Some("UNITLESS"), description: None, default_value: 0.889, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCSkin", name: Some("partition coefficient viable skin over blood"), units: Some("UNITLESS"), description: None, default_value: 0.889, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCAir", name: Some("partition coefficient blood over air"), units: Some("UNITLESS"), description: None, default_value: 1e+99, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "kGut", name: Some("absorption rate constant gut"), units: Some("PER_H"), description: None, default_value: 1.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Kp_sc_vs", name: Some("diffusion rate from stratum corneum to viable epidermis"), units: Some("DeciM_PER_HR"), description: None, default_value: 0.01, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Km", name: Some("Michaelis-Menten constant liver"), units: Some("MilliMOL"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Michaelis", name: Some("flag for Michaelis-Menten or linear metabolism"), units: Some("UNITLESS"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Vmax", name: Some("maximum rate of metabolism in the liver"), units: Some("MilliMOL_PER_L_HR"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "CLH", name: Some("hepatic clearance rate"), units: Some("L_PER_HR"), description: None, default_value: 132.0, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "Ke", name: Some("renal excretion rate"), units: Some("L_PER_HR"), description: None, default_value: 7.5, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "fub", name: Some("fraction unbound in blood"), units: Some("UNITLESS"), description: None, default_value: 0.51, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "Air", name: Some("alveolar air"), units: Some("L"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+    ParameterInfo { id: "Urine", name: Some("urine"), units: Some("L"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+    ParameterInfo { id: "Gut", name: Some("gut"), units: Some("L"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+];
+
 pub fn get_parameters_info() -> String {
-    let params = serde_json::json!([
-        {
-            "id": "BM",
-            "default_value": 70.0,
-            "required": true
-        },
-        {
-            "id": "BSA",
-            "default_value": 190.0,
-            "required": true
-        },
-        {
-            "id": "scVFat",
-            "default_value": 0.209,
-            "required": true
-        },
-        {
-            "id": "scVRich",
-            "default_value": 0.105,
-            "required": true
-        },
-        {
-            "id": "scVLiver",
-            "default_value": 0.024,
-            "required": true
-        },
-        {
-            "id": "scVBlood",
-            "default_value": 0.068,
-            "required": true
-        },
-        {
-            "id": "scVArt",
-            "default_value": 0.333333333333333,
-            "required": true
-        },
-        {
-            "id": "scFBlood",
-            "default_value": 4.8,
-            "required": true
-        },
-        {
-            "id": "scFFat",
-            "default_value": 0.085,
-            "required": true
-        },
-        {
-            "id": "scFPoor",
-            "default_value": 0.12,
-            "required": true
-        },
-        {
-            "id": "scFLiver",
-            "default_value": 0.27,
-            "required": true
-        },
-        {
-            "id": "scFSkin",
-            "default_value": 0.05,
-            "required": true
-        },
-        {
-            "id": "fSA_exposed",
-            "default_value": 0.1,
-            "required": true
-        },
-        {
-            "id": "Height_sc",
-            "default_value": 0.0001,
-            "required": true
-        },
-        {
-            "id": "Height_vs",
-            "default_value": 0.0122,
-            "required": true
-        },
-        {
-            "id": "Falv",
-            "default_value": 2220.0,
-            "required": true
-        },
-        {
-            "id": "PCFat",
-            "default_value": 2.53,
-            "required": true
-        },
-        {
-            "id": "PCLiver",
-            "default_value": 0.923,
-            "required": true
-        },
-        {
-            "id": "PCRich",
-            "default_value": 0.875,
-            "required": true
-        },
-        {
-            "id": "PCPoor",
-            "default_value": 0.647,
-            "required": true
-        },
-        {
-            "id": "PCSkin_sc",
-            "default_value": 0.889,
-            "required": true
-        },
-        {
-            "id": "PCSkin",
-            "default_value": 0.889,
-            "required": true
-        },
-        {
-            "id": "PCAir",
-            "default_value": 1e+99,
-            "required": true
-        },
-        {
-            "id": "kGut",
-            "default_value": 1.0,
-            "required": true
-        },
-        {
-            "id": "Kp_sc_vs",
-            "default_value": 0.01,
-            "required": true
-        },
-        {
-            "id": "Km",
-            "default_value": 0.0,
-            "required": true
-        },
-        {
-            "id": "Michaelis",
-            "default_value": 0.0,
-            "required": true
-        },
-        {
-            "id": "Vmax",
-            "default_value": 0.0,
-            "required": true
-        },
-        {
-            "id": "CLH",
-            "default_value": 132.0,
-            "required": true
-        },
-        {
-            "id": "Ke",
-            "default_value": 7.5,
-            "required": true
-        },
-        {
-            "id": "fub",
-            "default_value": 0.51,
-            "required": true
-        },
-        {
-            "id": "Air",
-            "default_value": 1.0,
-            "required": true
-        },
-        {
-            "id": "Urine",
-            "default_value": 1.0,
-            "required": true
-        },
-        {
-            "id": "Gut",
-            "default_value": 1.0,
-            "required": true
-        }
-    ]);
-    serde_json::to_string(&params).unwrap()
+    serde_json::to_string(PARAMETER_INFO).unwrap()
+}
+
+/// One entry of get_species_info
+#[derive(Serialize)]
+struct SpeciesInfo {
+    id: &'static str,
+    initial_amount: f64,
+    #[serde(rename = "override")]
+    override_field: &'static str,
+    units: Option<&'static str>,
+    compartment: Option<&'static str>,
+    compartment_volume: Option<f64>,
+    /// False when the SBML value is a concentration (hasOnlySubstanceUnits=false)
+    is_amount: bool,
+    is_boundary: bool,
 }
 
 pub fn get_species_info() -> String {
-    let species = serde_json::json!([
-        {
-            "id": "QFat",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QRich",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QPoor",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QLiver",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QMetab",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QGut",
-            "initial_amount": 1.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QSkin_u",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QSkin_e",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QSkin_sc_u",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QSkin_sc_e",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QArt",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QVen",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QExcret",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        },
-        {
-            "id": "QAir",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        }
-    ]);
+    let p = parameter_vector(&EuromixModel::default_params());
+    let species: &[SpeciesInfo] = &[
+        SpeciesInfo { id: "QFat", initial_amount: 0.0, override_field: "init_QFat", units: Some("MilliMOL"), compartment: Some("Fat"), compartment_volume: Some(p[34]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QRich", initial_amount: 0.0, override_field: "init_QRich", units: Some("MilliMOL"), compartment: Some("Rich"), compartment_volume: Some(p[35]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QPoor", initial_amount: 0.0, override_field: "init_QPoor", units: Some("MilliMOL"), compartment: Some("Poor"), compartment_volume: Some(p[45]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QLiver", initial_amount: 0.0, override_field: "init_QLiver", units: Some("MilliMOL"), compartment: Some("Liver"), compartment_volume: Some(p[36]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QMetab", initial_amount: 0.0, override_field: "init_QMetab", units: Some("MilliMOL"), compartment: Some("Liver"), compartment_volume: Some(p[36]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QGut", initial_amount: 1.0, override_field: "init_QGut", units: Some("MilliMOL"), compartment: Some("Gut"), compartment_volume: Some(p[33]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QSkin_u", initial_amount: 0.0, override_field: "init_QSkin_u", units: Some("MilliMOL"), compartment: Some("Skin_u"), compartment_volume: Some(p[38]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QSkin_e", initial_amount: 0.0, override_field: "init_QSkin_e", units: Some("MilliMOL"), compartment: Some("Skin_e"), compartment_volume: Some(p[37]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QSkin_sc_u", initial_amount: 0.0, override_field: "init_QSkin_sc_u", units: Some("MilliMOL"), compartment: Some("Skin_sc_u"), compartment_volume: Some(p[40]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QSkin_sc_e", initial_amount: 0.0, override_field: "init_QSkin_sc_e", units: Some("MilliMOL"), compartment: Some("Skin_sc_e"), compartment_volume: Some(p[39]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QArt", initial_amount: 0.0, override_field: "init_QArt", units: Some("MilliMOL"), compartment: Some("Art"), compartment_volume: Some(p[46]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QVen", initial_amount: 0.0, override_field: "init_QVen", units: Some("MilliMOL"), compartment: Some("Ven"), compartment_volume: Some(p[51]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QExcret", initial_amount: 0.0, override_field: "init_QExcret", units: Some("MilliMOL"), compartment: Some("Urine"), compartment_volume: Some(p[32]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QAir", initial_amount: 0.0, override_field: "init_QAir", units: Some("MilliMOL"), compartment: Some("Air"), compartment_volume: Some(p[31]), is_amount: true, is_boundary: false },
+    ];
     serde_json::to_string(&species).unwrap()
 }
 
+/// Whether each species' values are amounts rather than concentrations
+const SPECIES_IS_AMOUNT: [bool; 14] = [true, true, true, true, true, true, true, true, true, true, true, true, true, true];
+/// Compartment converting each species between amount and concentration, or why none does
+const SPECIES_VOLUMES: [Result<&str, &str>; 14] = [Ok("Fat"), Ok("Rich"), Ok("Poor"), Ok("Liver"), Ok("Liver"), Ok("Gut"), Ok("Skin_u"), Ok("Skin_e"), Ok("Skin_sc_u"), Ok("Skin_sc_e"), Ok("Art"), Ok("Ven"), Ok("Urine"), Ok("Air")];
+
+/// Unit of each species and observable series; None where the SBML gives none
+const SERIES_UNITS: [(&str, Option<&str>); 35] = [
+    ("QFat", Some("MilliMOL")),
+    ("QRich", Some("MilliMOL")),
+    ("QPoor", Some("MilliMOL")),
+    ("QLiver", Some("MilliMOL")),
+    ("QMetab", Some("MilliMOL")),
+    ("QGut", Some("MilliMOL")),
+    ("QSkin_u", Some("MilliMOL")),
+    ("QSkin_e", Some("MilliMOL")),
+    ("QSkin_sc_u", Some("MilliMOL")),
+    ("QSkin_sc_e", Some("MilliMOL")),
+    ("QArt", Some("MilliMOL")),
+    ("QVen", Some("MilliMOL")),
+    ("QExcret", Some("MilliMOL")),
+    ("QAir", Some("MilliMOL")),
+    ("Fat", None),
+    ("Rich", None),
+    ("Liver", None),
+    ("Skin_e", None),
+    ("Skin_u", None),
+    ("Skin_sc_e", None),
+    ("Skin_sc_u", None),
+    ("f_su", Some("L_PER_HR")),
+    ("f_se", Some("L_PER_HR")),
+    ("VBlood", Some("L")),
+    ("FBlood", Some("L_PER_HR")),
+    ("Poor", None),
+    ("Art", None),
+    ("FFat", Some("L_PER_HR")),
+    ("FPoor", Some("L_PER_HR")),
+    ("FLiver", Some("L_PER_HR")),
+    ("FSkin", Some("L_PER_HR")),
+    ("Ven", None),
+    ("FRich", Some("L_PER_HR")),
+    ("FSkin_e", Some("L_PER_HR")),
+    ("FSkin_u", Some("L_PER_HR")),
+];
+
+/// One entry of get_compartments_info
+#[derive(Serialize)]
+struct CompartmentInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    /// Size at the default parameters; None when it changes during a run
+    size: Option<f64>,
+    /// Rule or initial assignment computing the size, e.g. "BM*scVLiver"
+    formula: Option<&'static str>,
+    units: Option<&'static str>,
+    species: &'static [&'static str],
+}
+
+pub fn get_compartments_info() -> String {
+    let p = parameter_vector(&EuromixModel::default_params());
+    let compartments: &[CompartmentInfo] = &[
+        CompartmentInfo { id: "Air", name: Some("alveolar air"), size: Some(p[31]), formula: None, units: Some("L"), species: &["QAir"] },
+        CompartmentInfo { id: "Urine", name: Some("urine"), size: Some(p[32]), formula: None, units: Some("L"), species: &["QExcret"] },
+        CompartmentInfo { id: "Fat", name: Some("adipose tissue"), size: Some(p[34]), formula: Some("BM*scVFat"), units: Some("L"), species: &["QFat"] },
+        CompartmentInfo { id: "Rich", name: Some("richly perfused tissue"), size: Some(p[35]), formula: Some("BM*scVRich"), units: Some("L"), species: &["QRich"] },
+        CompartmentInfo { id: "Liver", name: Some("liver"), size: Some(p[36]), formula: Some("BM*scVLiver"), units: Some("L"), species: &["QLiver", "QMetab"] },
+        CompartmentInfo { id: "Art", name: Some("arterial blood"), size: Some(p[46]), formula: Some("VBlood*scVArt"), units: Some("L"), species: &["QArt"] },
+        CompartmentInfo { id: "Ven", name: Some("venous blood"), size: Some(p[51]), formula: Some("-Art + VBlood"), units: Some("L"), species: &["QVen"] },
+        CompartmentInfo { id: "Skin_e", name: Some("viable epidermis exposed skin"), size: Some(p[37]), formula: Some("BSA*Height_vs*fSA_exposed"), units: Some("L"), species: &["QSkin_e"] },
+        CompartmentInfo { id: "Skin_u", name: Some("viable epidermis unexposed skin"), size: Some(p[38]), formula: Some("BSA*Height_vs*(1 - fSA_exposed)"), units: Some("L"), species: &["QSkin_u"] },
+        CompartmentInfo { id: "Skin_sc_e", name: Some("stratum corneum exposed skin"), size: Some(p[39]), formula: Some("BSA*Height_sc*fSA_exposed"), units: Some("L"), species: &["QSkin_sc_e"] },
+        CompartmentInfo { id: "Skin_sc_u", name: Some("stratum corneum unexposed skin"), size: Some(p[40]), formula: Some("BSA*Height_sc*(1 - fSA_exposed)"), units: Some("L"), species: &["QSkin_sc_u"] },
+        CompartmentInfo { id: "Poor", name: Some("poorly perfused tissue"), size: Some(p[45]), formula: Some("BM*(-scVBlood - scVFat - scVLiver - scVRich + 0.9) - Skin_e - Skin_sc_e - Skin_sc_u - Skin_u"), units: Some("L"), species: &["QPoor"] },
+        CompartmentInfo { id: "Gut", name: Some("gut"), size: Some(p[33]), formula: None, units: Some("L"), species: &["QGut"] },
+    ];
+    serde_json::to_string(&compartments).unwrap()
+}
+
 pub fn get_default_parameters() -> String {
-    let defaults = serde_json::json!({
-        "BM": 70.0,
-        "BSA": 190.0,
-        "scVFat": 0.209,
-        "scVRich": 0.105,
-        "scVLiver": 0.024,
-        "scVBlood": 0.068,
-        "scVArt": 0.333333333333333,
-        "scFBlood": 4.8,
-        "scFFat": 0.085,
-        "scFPoor": 0.12,
-        "scFLiver": 0.27,
-        "scFSkin": 0.05,
-        "fSA_exposed": 0.1,
-        "Height_sc": 0.0001,
-        "Height_vs": 0.0122,
-        "Falv": 2220.0,
-        "PCFat": 2.53,
-        "PCLiver": 0.923,
-        "PCRich": 0.875,
-        "PCPoor": 0.647,
-        "PCSkin_sc": 0.889,
-        "PCSkin": 0.889,
-        "PCAir": 1e+99,
-        "kGut": 1.0,
-        "Kp_sc_vs": 0.01,
-        "Km": 0.0,
-        "Michaelis": 0.0,
-        "Vmax": 0.0,
-        "CLH": 132.0,
-        "Ke": 7.5,
-        "fub": 0.51,
-        "Air": 1.0,
-        "Urine": 1.0,
-        "Gut": 1.0,
-        "final_time": 24.0
-    });
-    serde_json::to_string(&defaults).unwrap()
+    let defaults: &[(&str, f64)] = &[
+        ("BM", 70.0),
+        ("BSA", 190.0),
+        ("scVFat", 0.209),
+        ("scVRich", 0.105),
+        ("scVLiver", 0.024),
+        ("scVBlood", 0.068),
+        ("scVArt", 0.333333333333333),
+        ("scFBlood", 4.8),
+        ("scFFat", 0.085),
+        ("scFPoor", 0.12),
+        ("scFLiver", 0.27),
+        ("scFSkin", 0.05),
+        ("fSA_exposed", 0.1),
+        ("Height_sc", 0.0001),
+        ("Height_vs", 0.0122),
+        ("Falv", 2220.0),
+        ("PCFat", 2.53),
+        ("PCLiver", 0.923),
+        ("PCRich", 0.875),
+        ("PCPoor", 0.647),
+        ("PCSkin_sc", 0.889),
+        ("PCSkin", 0.889),
+        ("PCAir", 1e+99),
+        ("kGut", 1.0),
+        ("Kp_sc_vs", 0.01),
+        ("Km", 0.0),
+        ("Michaelis", 0.0),
+        ("Vmax", 0.0),
+        ("CLH", 132.0),
+        ("Ke", 7.5),
+        ("fub", 0.51),
+        ("Air", 1.0),
+        ("Urine", 1.0),
+        ("Gut", 1.0),
+        ("final_time", 24.0),
+    ];
+    let defaults: serde_json::Map<String, serde_json::Value> = defaults.iter().map(|&(id, value)| (id.to_string(), value.into())).collect();
+    serde_json::Value::Object(defaults).to_string()
+}
+/// Equations in model order, serialized as a JSON object
+struct EquationTable(&'static [(&'static str, &'static str)]);
+
+impl Serialize for EquationTable {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (id, expression) in self.0 {
+            map.serialize_entry(id, expression)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Serialize)]
+struct ModelEquations {
+    derivatives: EquationTable,
+    assignment_rules: EquationTable,
+}
+
+pub fn get_model_equations() -> String {
+    let equations = ModelEquations {
+        derivatives: EquationTable(&[
+            ("QFat", "-FFat*QFat/(Fat*PCFat) + FFat*QArt/Art"),
+            ("QRich", "-FRich*QRich/(PCRich*Rich) + FRich*QArt/Art"),
+            ("QPoor", "-FPoor*QPoor/(PCPoor*Poor) + FPoor*QArt/Art"),
+            ("QLiver", "-FLiver*QLiver/(Liver*PCLiver) + QGut*kGut - fub*Piecewise((Liver*Vmax*Piecewise((QLiver/Liver, Liver > 0), (0, True))/(Km*PCLiver + Piecewise((QLiver/Liver, Liver > 0), (0, True))), Michaelis > 0.5), (CLH*Piecewise((QLiver/Liver, Liver > 0), (0, True))/PCLiver, True)) + FLiver*QArt/Art"),
+            ("QMetab", "fub*Piecewise((Liver*Vmax*Piecewise((QLiver/Liver, Liver > 0), (0, True))/(Km*PCLiver + Piecewise((QLiver/Liver, Liver > 0), (0, True))), Michaelis > 0.5), (CLH*Piecewise((QLiver/Liver, Liver > 0), (0, True))/PCLiver, True))"),
+            ("QGut", "-QGut*kGut"),
+            ("QSkin_u", "-FSkin_u*Piecewise((QSkin_u/Skin_u, Skin_u > 0), (0, True))/PCSkin + f_su*Piecewise((QSkin_sc_u/Skin_sc_u, Skin_sc_u > 0), (0, True)) - f_su*Piecewise((QSkin_u/Skin_u, Skin_u > 0), (0, True))/PCSkin_sc + FSkin_u*QArt/Art"),
+            ("QSkin_e", "-FSkin_e*Piecewise((QSkin_e/Skin_e, Skin_e > 0), (0, True))/PCSkin + f_se*Piecewise((QSkin_sc_e/Skin_sc_e, Skin_sc_e > 0), (0, True)) - f_se*Piecewise((QSkin_e/Skin_e, Skin_e > 0), (0, True))/PCSkin_sc + FSkin_e*QArt/Art"),
+            ("QSkin_sc_u", "-f_su*Piecewise((QSkin_sc_u/Skin_sc_u, Skin_sc_u > 0), (0, True)) + f_su*Piecewise((QSkin_u/Skin_u, Skin_u > 0), (0, True))/PCSkin_sc"),
+            ("QSkin_sc_e", "-f_se*Piecewise((QSkin_sc_e/Skin_sc_e, Skin_sc_e > 0), (0, True)) + f_se*Piecewise((QSkin_e/Skin_e, Skin_e > 0), (0, True))/PCSkin_sc"),
+            ("QArt", "FBlood*QVen/Ven - FFat*QArt/Art - FLiver*QArt/Art - FPoor*QArt/Art - FRich*QArt/Art - FSkin_e*QArt/Art - FSkin_u*QArt/Art - Falv*QArt/(Art*PCAir) - Ke*QArt*fub/Art + FBlood*QAir/Air"),
+            ("QVen", "-FBlood*QVen/Ven + FFat*QFat/(Fat*PCFat) + FLiver*QLiver/(Liver*PCLiver) + FPoor*QPoor/(PCPoor*Poor) + FRich*QRich/(PCRich*Rich) + FSkin_e*Piecewise((QSkin_e/Skin_e, Skin_e > 0), (0, True))/PCSkin + FSkin_u*Piecewise((QSkin_u/Skin_u, Skin_u > 0), (0, True))/PCSkin"),
+            ("QExcret", "Ke*QArt*fub/Art"),
+            ("QAir", "Falv*QArt/(Art*PCAir) - FBlood*QAir/Air"),
+        ]),
+        assignment_rules: EquationTable(&[
+            ("Fat", "BM*scVFat"),
+            ("Rich", "BM*scVRich"),
+            ("Liver", "BM*scVLiver"),
+            ("Skin_e", "BSA*Height_vs*fSA_exposed"),
+            ("Skin_u", "BSA*Height_vs*(1 - fSA_exposed)"),
+            ("Skin_sc_e", "BSA*Height_sc*fSA_exposed"),
+            ("Skin_sc_u", "BSA*Height_sc*(1 - fSA_exposed)"),
+            ("f_su", "BSA*Kp_sc_vs*(1 - fSA_exposed)"),
+            ("f_se", "BSA*Kp_sc_vs*fSA_exposed"),
+            ("VBlood", "BM*scVBlood"),
+            ("FBlood", "BM*scFBlood"),
+            ("Poor", "BM*(-scVBlood - scVFat - scVLiver - scVRich + 0.9) - Skin_e - Skin_sc_e - Skin_sc_u - Skin_u"),
+            ("Art", "VBlood*scVArt"),
+            ("FFat", "FBlood*scFFat"),
+            ("FPoor", "FBlood*scFPoor"),
+            ("FLiver", "FBlood*scFLiver"),
+            ("FSkin", "FBlood*scFSkin"),
+            ("Ven", "-Art + VBlood"),
+            ("FRich", "FBlood - FFat - FLiver - FPoor - FSkin"),
+            ("FSkin_e", "FSkin*fSA_exposed"),
+            ("FSkin_u", "FSkin - FSkin_e"),
+        ]),
+    };
+    serde_json::to_string_pretty(&equations).unwrap()
+}
+/// Source SBML document, verbatim
+const MODEL_SBML: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<sbml xmlns="http://www.sbml.org/sbml/level3/version2/core" level="3" version="2">
+  <model metaid="euromix" id="euromix" substanceUnits="MilliMOL" timeUnits="HR" volumeUnits="L" extentUnits="MilliMOL">
+    <annotation>
+      <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+        <rdf:Description rdf:about="#euromix">
+          <dcterms:creator>
+            <rdf:Bag>
+              <rdf:li rdf:parseType="Resource">
+                <vCard4:hasName rdf:parseType="Resource">
+                  <vCard4:family-name>Palm</vCard4:family-name>
+                  <vCard4:given-name>Margriet</vCard4:given-name>
+                </vCard4:hasName>
+                <vCard4:organization-name>Dutch National Institute for Public Health and the Environment (RIVM)</vCard4:organization-name>
+              </rdf:li>
+              <rdf:li rdf:parseType="Resource">
+                <vCard4:hasName rdf:parseType="Resource">
+                  <vCard4:family-name>Minnema</vCard4:family-name>
+                  <vCard4:given-name>Jordi</vCard4:given-name>
+                </vCard4:hasName>
+                <vCard4:organization-name>Dutch National Institute for Public Health and the Environment (RIVM)</vCard4:organization-name>
+              </rdf:li>
+              <rdf:li rdf:parseType="Resource">
+                <vCard4:hasName rdf:parseType="Resource">
+                  <vCard4:family-name>Kruisselbrink</vCard4:family-name>
+                  <vCard4:given-name>Johannes</vCard4:given-name>
+                </vCard4:hasName>
+                <vCard4:organization-name>Wageningen University &amp; Research, Biometris</vCard4:organization-name>
+              </rdf:li>
+            </rdf:Bag>
+          </dcterms:creator>
+          <bqbiol:hasTaxon>
+            <rdf:Bag>
+              <rdf:li rdf:resource="http://identifiers.org/taxonomy/40674"/>
+            </rdf:Bag>
+          </bqbiol:hasTaxon>
+          <bqbiol:hasProperty>
+            <rdf:Bag>
+              <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+              <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_25212"/>
+            </rdf:Bag>
+          </bqbiol:hasProperty>
+        </rdf:Description>
+      </rdf:RDF>
+    </annotation>
+    <listOfFunctionDefinitions>
+      <functionDefinition id="conc">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <lambda>
+            <bvar>
+              <ci> q </ci>
+            </bvar>
+            <bvar>
+              <ci> vol </ci>
+            </bvar>
+            <piecewise>
+              <piece>
+                <apply>
+                  <divide/>
+                  <ci> q </ci>
+                  <ci> vol </ci>
+                </apply>
+                <apply>
+                  <gt/>
+                  <ci> vol </ci>
+                  <cn type="integer"> 0 </cn>
+                </apply>
+              </piece>
+              <otherwise>
+                <cn type="integer"> 0 </cn>
+              </otherwise>
+            </piecewise>
+          </lambda>
+        </math>
+      </functionDefinition>
+      <functionDefinition id="metab_MM">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <lambda>
+            <bvar>
+              <ci> Vmax </ci>
+            </bvar>
+            <bvar>
+              <ci> Km </ci>
+            </bvar>
+            <bvar>
+              <ci> PC </ci>
+            </bvar>
+            <bvar>
+              <ci> q </ci>
+            </bvar>
+            <bvar>
+              <ci> vol </ci>
+            </bvar>
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> vol </ci>
+                <ci> Vmax </ci>
+                <apply>
+                  <ci> conc </ci>
+                  <ci> q </ci>
+                  <ci> vol </ci>
+                </apply>
+              </apply>
+              <apply>
+                <plus/>
+                <apply>
+                  <times/>
+                  <ci> PC </ci>
+                  <ci> Km </ci>
+                </apply>
+                <apply>
+                  <ci> conc </ci>
+                  <ci> q </ci>
+                  <ci> vol </ci>
+                </apply>
+              </apply>
+            </apply>
+          </lambda>
+        </math>
+      </functionDefinition>
+      <functionDefinition id="metab_MA">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <lambda>
+            <bvar>
+              <ci> CLH </ci>
+            </bvar>
+            <bvar>
+              <ci> PC </ci>
+            </bvar>
+            <bvar>
+              <ci> q </ci>
+            </bvar>
+            <bvar>
+              <ci> vol </ci>
+            </bvar>
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> CLH </ci>
+                <apply>
+                  <ci> conc </ci>
+                  <ci> q </ci>
+                  <ci> vol </ci>
+                </apply>
+              </apply>
+              <ci> PC </ci>
+            </apply>
+          </lambda>
+        </math>
+      </functionDefinition>
+    </listOfFunctionDefinitions>
+    <listOfUnitDefinitions>
+      <unitDefinition id="MilliMOL">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="-3" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="HR">
+        <listOfUnits>
+          <unit kind="second" exponent="1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="L">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="KiloGM">
+        <listOfUnits>
+          <unit kind="gram" exponent="1" scale="3" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="DeciM2">
+        <listOfUnits>
+          <unit kind="metre" exponent="2" scale="-1" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="L_PER_KiloGM">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="gram" exponent="-1" scale="3" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="UNITLESS">
+        <listOfUnits>
+          <unit kind="dimensionless" exponent="1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="L_PER_KiloGM_HR">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="gram" exponent="-1" scale="3" multiplier="1"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="DeciM">
+        <listOfUnits>
+          <unit kind="metre" exponent="1" scale="-1" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="L_PER_HR">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="PER_H">
+        <listOfUnits>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="DeciM_PER_HR">
+        <listOfUnits>
+          <unit kind="metre" exponent="1" scale="-1" multiplier="1"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="MilliMOL_PER_L_HR">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="-3" multiplier="1"/>
+          <unit kind="litre" exponent="-1" scale="0" multiplier="1"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+    </listOfUnitDefinitions>
+    <listOfCompartments>
+      <compartment metaid="meta_Air" id="Air" name="alveolar air" spatialDimensions="3" size="1" units="L" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Air">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00448"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/NCIT_C150891"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Urine" id="Urine" name="urine" spatialDimensions="3" size="1" units="L" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Urine">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00556"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/UBERON_0001088"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Fat" id="Fat" name="adipose tissue" spatialDimensions="3" size="14.63" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Fat">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00460"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/UBERON_0001013"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Rich" id="Rich" name="richly perfused tissue" spatialDimensions="3" size="7.35" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Rich">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00453"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/UBERON_0013756"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Liver" id="Liver" name="liver" spatialDimensions="3" size="1.68" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Liver">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00558"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/UBERON_0002107"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Art" id="Art" name="arterial blood" spatialDimensions="3" size="1.58666666666667" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Art">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00552"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/UBERON_0013755"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Ven" id="Ven" name="venous blood" spatialDimensions="3" size="3.17333333333333" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Ven">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00561"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/UBERON_0013756"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Skin_e" id="Skin_e" name="viable epidermis exposed skin" spatialDimensions="3" size="0.2318" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Skin_e">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00456"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Skin_u" id="Skin_u" name="viable epidermis unexposed skin" spatialDimensions="3" size="2.0862" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Skin_u">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00455"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Skin_sc_e" id="Skin_sc_e" name="stratum corneum exposed skin" spatialDimensions="3" size="0.0019" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Skin_sc_e">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00458"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Skin_sc_u" id="Skin_sc_u" name="stratum corneum unexposed skin" spatialDimensions="3" size="0.0171" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Skin_sc_u">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00457"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Poor" id="Poor" name="poorly perfused tissue" spatialDimensions="3" size="32.243" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Poor">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00454"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Gut" id="Gut" name="gut" spatialDimensions="3" size="1" units="L" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Gut">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00477"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/UBERON_0001555"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+    </listOfCompartments>
+    <listOfSpecies>
+      <species metaid="meta_QFat" id="QFat" name="amount of chemical in fat tissues" compartment="Fat" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QFat">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00550"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QRich" id="QRich" name="amount of chemical in richly tissues" compartment="Rich" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QRich">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00629"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QPoor" id="QPoor" name="amount of chemical in poor tissues" compartment="Poor" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QPoor">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00630"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QLiver" id="QLiver" name="amount of chemical in liver" compartment="Liver" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QLiver">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00497"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QMetab" id="QMetab" name="amount of chemical metabolized (cumulated)" compartment="Liver" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QMetab">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00058"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_25212"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QGut" id="QGut" name="amount of chemical in gut lumen" compartment="Gut" initialAmount="1.0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QGut">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00496"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QSkin_u" id="QSkin_u" name="amount of chemical in viable epidermis of unexposed skin" compartment="Skin_u" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QSkin_u">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QSkin_e" id="QSkin_e" name="amount of chemical in viable epidermis of exposed skin" compartment="Skin_e" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QSkin_e">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QSkin_sc_u" id="QSkin_sc_u" name="amount of chemical in skin stratum corneum of unexposed skin" compartment="Skin_sc_u" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QSkin_sc_u">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QSkin_sc_e" id="QSkin_sc_e" name="amount of chemical in skin stratum corneum of exposed skin" compartment="Skin_sc_e" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QSkin_sc_e">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QArt" id="QArt" name="amount of chemical in arterial blood" compartment="Art" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QArt">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00631"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QVen" id="QVen" name="amount of chemical in venous blood" compartment="Ven" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QVen">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00632"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QExcret" id="QExcret" name="amount of chemical excreted in urine (cumulated)" compartment="Urine" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QExcret">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00274"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_QAir" id="QAir" name="amount of chemical in alveolar air" compartment="Air" initialAmount="0" substanceUnits="MilliMOL" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QAir">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00633"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+    </listOfSpecies>
+    <listOfParameters>
+      <parameter metaid="meta_BM" id="BM" name="body weight" value="70" units="KiloGM" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_BM">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00008"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_BSA" id="BSA" name="body surface area" value="190" units="DeciM2" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_BSA">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00010"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_scVFat" id="scVFat" name="fat volume as fraction of total body weight" value="0.209" units="L_PER_KiloGM" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_scVFat">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00086"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_scVRich" id="scVRich" name="richly perfused tissues volume as fraction of total body weight" value="0.105" units="L_PER_KiloGM" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_scVRich">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00102"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_scVLiver" id="scVLiver" name="liver volume as fraction of total body weight" value="0.024" units="L_PER_KiloGM" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_scVLiver">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00078"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_scVBlood" id="scVBlood" name="blood volume as fraction of total body weight" value="0.068" units="L_PER_KiloGM" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_scVBlood">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00107"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter id="scVArt" name="arterial blood volume as fraction of total blood volume" value="0.333333333333333" units="UNITLESS" constant="true"/>
+      <parameter id="scFBlood" name="total blood flow per unit mass" value="4.8" units="L_PER_KiloGM_HR" constant="true"/>
+      <parameter metaid="meta_scFFat" id="scFFat" name="fraction of blood flow going to adipose tissue" value="0.085" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_scFFat">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00033"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_scFPoor" id="scFPoor" name="fraction total blood flow going to poorly perfused tissue" value="0.12" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_scFPoor">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00047"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_scFLiver" id="scFLiver" name="fraction total blood flow going to liver" value="0.27" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_scFLiver">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00025"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_scFSkin" id="scFSkin" name="fraction total blood flow going to skin" value="0.05" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_scFSkin">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00037"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_fSA_exposed" id="fSA_exposed" name="fraction of skin surface area actually exposed" value="0.1" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_fSA_exposed">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00110"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter id="Height_sc" name="thickness stratum corneum" value="0.0001" units="DeciM" constant="true"/>
+      <parameter id="Height_vs" name="thickness viable epidermis" value="0.0122" units="DeciM" constant="true"/>
+      <parameter metaid="meta_Falv" id="Falv" name="alveolar ventilation rate" value="2220" units="L_PER_HR" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Falv">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00114"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_PCFat" id="PCFat" name="partition coefficient fat over blood" value="2.53" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_PCFat">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_PCLiver" id="PCLiver" name="partition coefficient liver over blood" value="0.923" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_PCLiver">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00577"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_PCRich" id="PCRich" name="partition coefficient poorly perfused tissue over blood" value="0.875" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_PCRich">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00587"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_PCPoor" id="PCPoor" name="partition coefficient richly perfused tissue over blood" value="0.647" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_PCPoor">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00584"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_PCSkin_sc" id="PCSkin_sc" name="partition coefficient viable skin over stratum corneum" value="0.889" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_PCSkin_sc">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00182"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_PCSkin" id="PCSkin" name="partition coefficient viable skin over blood" value="0.889" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_PCSkin">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00588"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_PCAir" id="PCAir" name="partition coefficient blood over air" value="1e+99" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_PCAir">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00568"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_kGut" id="kGut" name="absorption rate constant gut" value="1" units="PER_H" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_kGut">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00141"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter id="Kp_sc_vs" name="diffusion rate from stratum corneum to viable epidermis" value="0.01" units="DeciM_PER_HR" constant="true"/>
+      <parameter metaid="meta_Km" id="Km" name="Michaelis-Menten constant liver" value="0" units="MilliMOL" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Km">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00214"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter id="Michaelis" name="flag for Michaelis-Menten or linear metabolism" value="0" units="UNITLESS" constant="true"/>
+      <parameter metaid="meta_Vmax" id="Vmax" name="maximum rate of metabolism in the liver" value="0" units="MilliMOL_PER_L_HR" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vmax">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00213"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_CLH" id="CLH" name="hepatic clearance rate" value="132" units="L_PER_HR" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_CLH">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00234"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_Ke" id="Ke" name="renal excretion rate" value="7.5" units="L_PER_HR" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Ke">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00237"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_fub" id="fub" name="fraction unbound in blood" value="0.51" units="UNITLESS" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_fub">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00622"/>
+                </rdf:Bag>
+              </bqmodel:is>
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/CHEBI_59999"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter id="f_su" value="1.71" units="L_PER_HR" constant="false"/>
+      <parameter id="f_se" value="0.19" units="L_PER_HR" constant="false"/>
+      <parameter metaid="meta_VBlood" id="VBlood" name="total blood volume" value="4.76" units="L" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_VBlood">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00108"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FBlood" id="FBlood" name="blood flow" value="336" units="L_PER_HR" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FBlood">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00030"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FFat" id="FFat" name="blood flow to the fat" value="28.56" units="L_PER_HR" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FFat">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00032"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FPoor" id="FPoor" name="blood flow to poorly perfused tissues" value="40.32" units="L_PER_HR" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FPoor">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00046"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FLiver" id="FLiver" name="blood flow to the liver" value="90.72" units="L_PER_HR" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FLiver">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00024"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FSkin" id="FSkin" name="blood flow to the skin" value="16.8" units="L_PER_HR" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FSkin">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00036"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FRich" id="FRich" name="blood flow to richly perfused tissues" value="159.6" units="L_PER_HR" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FRich">
+              <bqmodel:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://purl.obolibrary.org/obo/PBPKO_00048"/>
+                </rdf:Bag>
+              </bqmodel:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter id="FSkin_e" name="blood flow to exposed skin" value="1.68" units="L_PER_HR" constant="false"/>
+      <parameter id="FSkin_u" name="blood flow to unexposed skin" value="15.12" units="L_PER_HR" constant="false"/>
+    </listOfParameters>
+    <listOfInitialAssignments/>
+    <listOfRules>
+      <assignmentRule variable="Fat">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BM </ci>
+            <ci> scVFat </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Rich">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BM </ci>
+            <ci> scVRich </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Liver">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BM </ci>
+            <ci> scVLiver </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Art">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> VBlood </ci>
+            <ci> scVArt </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Ven">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <minus/>
+            <ci> VBlood </ci>
+            <ci> Art </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Skin_e">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BSA </ci>
+            <ci> Height_vs </ci>
+            <ci> fSA_exposed </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Skin_u">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BSA </ci>
+            <ci> Height_vs </ci>
+            <apply>
+              <minus/>
+              <cn type="integer"> 1 </cn>
+              <ci> fSA_exposed </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Skin_sc_e">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BSA </ci>
+            <ci> Height_sc </ci>
+            <ci> fSA_exposed </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Skin_sc_u">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BSA </ci>
+            <ci> Height_sc </ci>
+            <apply>
+              <minus/>
+              <cn type="integer"> 1 </cn>
+              <ci> fSA_exposed </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Poor">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <minus/>
+            <apply>
+              <minus/>
+              <apply>
+                <minus/>
+                <apply>
+                  <minus/>
+                  <apply>
+                    <times/>
+                    <ci> BM </ci>
+                    <apply>
+                      <minus/>
+                      <apply>
+                        <minus/>
+                        <apply>
+                          <minus/>
+                          <apply>
+                            <minus/>
+                            <apply>
+                              <minus/>
+                              <cn type="integer"> 1 </cn>
+                              <ci> scVFat </ci>
+                            </apply>
+                            <ci> scVRich </ci>
+                          </apply>
+                          <ci> scVLiver </ci>
+                        </apply>
+                        <ci> scVBlood </ci>
+                      </apply>
+                      <cn> 0.1 </cn>
+                    </apply>
+                  </apply>
+                  <ci> Skin_e </ci>
+                </apply>
+                <ci> Skin_u </ci>
+              </apply>
+              <ci> Skin_sc_e </ci>
+            </apply>
+            <ci> Skin_sc_u </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="f_su">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Kp_sc_vs </ci>
+            <ci> BSA </ci>
+            <apply>
+              <minus/>
+              <cn type="integer"> 1 </cn>
+              <ci> fSA_exposed </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="f_se">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Kp_sc_vs </ci>
+            <ci> BSA </ci>
+            <ci> fSA_exposed </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="VBlood">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BM </ci>
+            <ci> scVBlood </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="FBlood">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> scFBlood </ci>
+            <ci> BM </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="FFat">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> FBlood </ci>
+            <ci> scFFat </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="FPoor">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> FBlood </ci>
+            <ci> scFPoor </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="FLiver">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> FBlood </ci>
+            <ci> scFLiver </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="FSkin">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> FBlood </ci>
+            <ci> scFSkin </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="FRich">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <minus/>
+            <apply>
+              <minus/>
+              <apply>
+                <minus/>
+                <apply>
+                  <minus/>
+                  <ci> FBlood </ci>
+                  <ci> FFat </ci>
+                </apply>
+                <ci> FPoor </ci>
+              </apply>
+              <ci> FLiver </ci>
+            </apply>
+            <ci> FSkin </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="FSkin_e">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> FSkin </ci>
+            <ci> fSA_exposed </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="FSkin_u">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <minus/>
+            <ci> FSkin </ci>
+            <ci> FSkin_e </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+    </listOfRules>
+    <listOfReactions>
+      <reaction id="_J0" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QArt" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QAir" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> Falv </ci>
+                <apply>
+                  <divide/>
+                  <ci> QArt </ci>
+                  <ci> Art </ci>
+                </apply>
+              </apply>
+              <ci> PCAir </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J1" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QAir" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QArt" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> FBlood </ci>
+              <apply>
+                <divide/>
+                <ci> QAir </ci>
+                <ci> Air </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J2" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QArt" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QFat" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> FFat </ci>
+              <apply>
+                <divide/>
+                <ci> QArt </ci>
+                <ci> Art </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J3" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QFat" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QVen" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> FFat </ci>
+                <apply>
+                  <divide/>
+                  <ci> QFat </ci>
+                  <ci> Fat </ci>
+                </apply>
+              </apply>
+              <ci> PCFat </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J4" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QArt" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QRich" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> FRich </ci>
+              <apply>
+                <divide/>
+                <ci> QArt </ci>
+                <ci> Art </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J5" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QRich" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QVen" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> FRich </ci>
+                <apply>
+                  <divide/>
+                  <ci> QRich </ci>
+                  <ci> Rich </ci>
+                </apply>
+              </apply>
+              <ci> PCRich </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J6" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QArt" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QPoor" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> FPoor </ci>
+              <apply>
+                <divide/>
+                <ci> QArt </ci>
+                <ci> Art </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J7" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QPoor" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QVen" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> FPoor </ci>
+                <apply>
+                  <divide/>
+                  <ci> QPoor </ci>
+                  <ci> Poor </ci>
+                </apply>
+              </apply>
+              <ci> PCPoor </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J8" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QArt" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QLiver" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> FLiver </ci>
+              <apply>
+                <divide/>
+                <ci> QArt </ci>
+                <ci> Art </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J9" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QLiver" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QVen" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> FLiver </ci>
+                <apply>
+                  <divide/>
+                  <ci> QLiver </ci>
+                  <ci> Liver </ci>
+                </apply>
+              </apply>
+              <ci> PCLiver </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J10" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QGut" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QLiver" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> kGut </ci>
+              <ci> QGut </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J11" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QArt" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QSkin_u" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> FSkin_u </ci>
+              <apply>
+                <divide/>
+                <ci> QArt </ci>
+                <ci> Art </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J12" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QSkin_u" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QVen" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> FSkin_u </ci>
+                <apply>
+                  <ci> conc </ci>
+                  <ci> QSkin_u </ci>
+                  <ci> Skin_u </ci>
+                </apply>
+              </apply>
+              <ci> PCSkin </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J13" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QSkin_u" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QSkin_sc_u" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> f_su </ci>
+                <apply>
+                  <ci> conc </ci>
+                  <ci> QSkin_u </ci>
+                  <ci> Skin_u </ci>
+                </apply>
+              </apply>
+              <ci> PCSkin_sc </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J14" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QSkin_sc_u" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QSkin_u" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> f_su </ci>
+              <apply>
+                <ci> conc </ci>
+                <ci> QSkin_sc_u </ci>
+                <ci> Skin_sc_u </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J15" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QArt" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QSkin_e" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> FSkin_e </ci>
+              <apply>
+                <divide/>
+                <ci> QArt </ci>
+                <ci> Art </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J16" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QSkin_e" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QVen" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> FSkin_e </ci>
+                <apply>
+                  <ci> conc </ci>
+                  <ci> QSkin_e </ci>
+                  <ci> Skin_e </ci>
+                </apply>
+              </apply>
+              <ci> PCSkin </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J17" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QSkin_e" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QSkin_sc_e" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> f_se </ci>
+                <apply>
+                  <ci> conc </ci>
+                  <ci> QSkin_e </ci>
+                  <ci> Skin_e </ci>
+                </apply>
+              </apply>
+              <ci> PCSkin_sc </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J18" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QSkin_sc_e" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QSkin_e" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> f_se </ci>
+              <apply>
+                <ci> conc </ci>
+                <ci> QSkin_sc_e </ci>
+                <ci> Skin_sc_e </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J19" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QVen" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QArt" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> FBlood </ci>
+              <apply>
+                <divide/>
+                <ci> QVen </ci>
+                <ci> Ven </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J20" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QLiver" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QMetab" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> fub </ci>
+              <piecewise>
+                <piece>
+                  <apply>
+                    <ci> metab_MM </ci>
+                    <ci> Vmax </ci>
+                    <ci> Km </ci>
+                    <ci> PCLiver </ci>
+                    <ci> QLiver </ci>
+                    <ci> Liver </ci>
+                  </apply>
+                  <apply>
+                    <gt/>
+                    <ci> Michaelis </ci>
+                    <cn> 0.5 </cn>
+                  </apply>
+                </piece>
+                <otherwise>
+                  <apply>
+                    <ci> metab_MA </ci>
+                    <ci> CLH </ci>
+                    <ci> PCLiver </ci>
+                    <ci> QLiver </ci>
+                    <ci> Liver </ci>
+                  </apply>
+                </otherwise>
+              </piecewise>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction id="_J21" reversible="true">
+        <listOfReactants>
+          <speciesReference species="QArt" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="QExcret" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Ke </ci>
+              <ci> fub </ci>
+              <apply>
+                <divide/>
+                <ci> QArt </ci>
+                <ci> Art </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+    </listOfReactions>
+  </model>
+</sbml>
+"##;
+
+/// SBML document this module was generated from
+pub fn get_model_sbml() -> Result<String, String> {
+    Ok(MODEL_SBML.to_string())
+}
+/// TypeScript definitions of the parameters, result and metadata of this model
+const TYPES_TS: &str = r#"// TypeScript definitions for euromix_model, generated with the module
+
+export type SpeciesId = "QFat" | "QRich" | "QPoor" | "QLiver" | "QMetab" | "QGut" | "QSkin_u" | "QSkin_e" | "QSkin_sc_u" | "QSkin_sc_e" | "QArt" | "QVen" | "QExcret" | "QAir";
+export type ObservableId = "Fat" | "Rich" | "Liver" | "Skin_e" | "Skin_u" | "Skin_sc_e" | "Skin_sc_u" | "f_su" | "f_se" | "VBlood" | "FBlood" | "Poor" | "Art" | "FFat" | "FPoor" | "FLiver" | "FSkin" | "Ven" | "FRich" | "FSkin_e" | "FSkin_u";
+export type SolverMethod = "bdf" | "tr_bdf2";
+export type JacobianMode = "analytic" | "finite_difference";
+export type SimulationMode = "transient" | "steady_state";
+export type TimeUnit = "s" | "min" | "h" | "d";
+export type OutputKind = "amount" | "concentration" | "both";
+
+export interface Dose {
+  time: number;
+  amount: number;
+  target: SpeciesId;
+  units?: string | null;
+}
+
+/** Input of run_simulation; omitted fields take the model defaults */
+export interface SimulationParams {
+  BM?: number;
+  BSA?: number;
+  scVFat?: number;
+  scVRich?: number;
+  scVLiver?: number;
+  scVBlood?: number;
+  scVArt?: number;
+  scFBlood?: number;
+  scFFat?: number;
+  scFPoor?: number;
+  scFLiver?: number;
+  scFSkin?: number;
+  fSA_exposed?: number;
+  Height_sc?: number;
+  Height_vs?: number;
+  Falv?: number;
+  PCFat?: number;
+  PCLiver?: number;
+  PCRich?: number;
+  PCPoor?: number;
+  PCSkin_sc?: number;
+  PCSkin?: number;
+  PCAir?: number;
+  kGut?: number;
+  Kp_sc_vs?: number;
+  Km?: number;
+  Michaelis?: number;
+  Vmax?: number;
+  CLH?: number;
+  Ke?: number;
+  fub?: number;
+  Air?: number;
+  Urine?: number;
+  Gut?: number;
+  init_QFat?: number | null;
+  init_QRich?: number | null;
+  init_QPoor?: number | null;
+  init_QLiver?: number | null;
+  init_QMetab?: number | null;
+  init_QGut?: number | null;
+  init_QSkin_u?: number | null;
+  init_QSkin_e?: number | null;
+  init_QSkin_sc_u?: number | null;
+  init_QSkin_sc_e?: number | null;
+  init_QArt?: number | null;
+  init_QVen?: number | null;
+  init_QExcret?: number | null;
+  init_QAir?: number | null;
+  t_start?: number | null;
+  final_time?: number | null;
+  time_unit?: TimeUnit | null;
+  convert_output_time?: boolean | null;
+  output_times?: number[] | null;
+  output_dt?: number | null;
+  max_output_points?: number | null;
+  include_observables?: boolean | null;
+  output_kind?: OutputKind | null;
+  include_parameters?: boolean | null;
+  mass_balance?: boolean | null;
+  validate?: boolean | null;
+  doses?: Dose[] | null;
+  rtol?: number | null;
+  atol?: number | null;
+  max_steps?: number | null;
+  max_step?: number | null;
+  initial_step?: number | null;
+  check_finite?: boolean | null;
+  clamp_nonnegative?: boolean | null;
+  clamp_threshold?: number | null;
+  solver?: SolverMethod | null;
+  jacobian?: JacobianMode | null;
+  mode?: SimulationMode | null;
+  steady_state_tol?: number | null;
+  steady_state_exclude?: string[] | null;
+  steady_state_min_time?: number | null;
+}
+
+export interface SimulationResult {
+  species: Record<SpeciesId, number[]>;
+  time: number[];
+  /** null when the parameters were rejected or include_parameters is false */
+  parameters: SimulationParams | null;
+  /** Filled when include_observables is set */
+  observables: Partial<Record<ObservableId, number[]>>;
+  /** Unit of each species and observable series and of time; null if unknown */
+  units: Record<string, string | null>;
+  /** Filled when output_kind asks for them */
+  amounts: Partial<Record<SpeciesId, number[]>>;
+  concentrations: Partial<Record<SpeciesId, number[]>>;
+  /** Species left in their native representation, and why */
+  notes: string[];
+  steady_state: SteadyState | null;
+  applied_doses: Dose[];
+  events: EventRecord[];
+  /** Whether each event trigger held at the end; continuations start from it */
+  trigger_state: boolean[];
+  steps: number;
+  stats: SolverStats | null;
+  diagnostics: Diagnostics | null;
+  error: SimulationError | null;
+  timing: Timing;
 }
+
+/** Wall-clock milliseconds of the call */
+export interface Timing {
+  solve_ms: number;
+  serialize_ms: number;
+  total_ms: number;
+}
+
+export interface SteadyState {
+  reached: boolean;
+  time: number;
+  rms: number;
+  state: number[];
+}
+
+export interface EventRecord {
+  time: number;
+  id: string;
+  assignments: Partial<Record<SpeciesId, number>>;
+}
+
+export interface SolverStats {
+  steps: number;
+  rhs_evals: number;
+  jacobian_evals: number;
+  newton_iterations: number;
+  newton_failures: number;
+  final_step_size: number;
+}
+
+export interface Diagnostics {
+  total_mass: number[];
+  max_mass_drift: number;
+}
+
+export interface SimulationError {
+  kind: "invalid_params" | "setup" | "solver_failure" | "event_error" | "max_steps_exceeded" | "cancelled";
+  message: string;
+  field: string | null;
+  time: number | null;
+  state: number[] | null;
+  hint: string | null;
+}
+
+/** get_model_metadata */
+export interface ModelMetadata {
+  model_id: string;
+  num_species: number;
+  num_parameters: number;
+  time_units: string;
+  substance_units: string;
+  volume_units: string;
+  sbml: {
+    id: string | null;
+    name: string | null;
+    level: number | null;
+    version: number | null;
+    sha256: string | null;
+  };
+  generator: { name: string; version: string | null; generated_at: string | null };
+  diffsol_version: string;
+  features: ("events" | "sensitivities" | "embedded_sbml")[];
+  observables: { id: ObservableId; units: string | null }[];
+}
+
+/** One entry of get_parameters_info */
+export interface ParameterInfo {
+  id: keyof SimulationParams;
+  name: string | null;
+  units: string | null;
+  description: string | null;
+  default_value: number;
+  required: boolean;
+  is_compartment: boolean;
+  min: number | null;
+  max: number | null;
+}
+
+/** One entry of get_species_info */
+export interface SpeciesInfo {
+  id: SpeciesId;
+  initial_amount: number;
+  override: keyof SimulationParams;
+  units: string | null;
+  compartment: string | null;
+  compartment_volume: number | null;
+  is_amount: boolean;
+  is_boundary: boolean;
+}
+
+/** One entry of get_compartments_info */
+export interface CompartmentInfo {
+  id: string;
+  name: string | null;
+  size: number | null;
+  formula: string | null;
+  units: string | null;
+  species: SpeciesId[];
+}
+
+/** get_model_equations */
+export interface ModelEquations {
+  derivatives: Record<SpeciesId, string>;
+  assignment_rules: Record<string, string>;
+}
+"#;
+
+pub fn get_types_ts() -> String {
+    TYPES_TS.to_string()
+}
+
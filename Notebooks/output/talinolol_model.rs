@@ -1,93 +1,660 @@
 // Generated WASM-compatible Rust code from SBML model: talinolol_model
 // Uses SymPy CSE for optimized derivatives and Jacobian
 
-use diffsol::{OdeBuilder, OdeSolverMethod, OdeSolverStopReason, Vector};
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use wasm_pk_core::*;
 
-type M = diffsol::NalgebraMat<f64>;
-type LS = diffsol::NalgebraLU<f64>;
-
-#[derive(Serialize, Deserialize)]
-pub struct SimulationResult {
-    pub species: std::collections::HashMap<String, Vec<f64>>,
-    pub time: Vec<f64>,
-}
-
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct SimulationParams {
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub BW: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub HEIGHT: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub HR: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub HRrest: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub COBW: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub COHRI: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Fblood: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub HCT: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub f_shunting_forearm: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FVgu: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FVki: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FVli: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FVlu: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FVfo: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FVve: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FVar: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FVpo: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FVhv: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FVfov: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FQgu: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FQki: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FQh: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FQlu: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub FQfo: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub conversion_min_per_day: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub f_cirrhosis: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub PODOSE_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Ka_dis_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Mr_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub fup_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub ftissue_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Kp_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub IVDOSE_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub ti_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Ri_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub cum_dose_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub cum_dose_intestine_tal: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Vurine: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Vfeces: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Vstomach: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Vfo: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Vfov: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Vduodenum: f64,
-    pub final_time: Option<f64>,
+
+    // Initial amounts (optional, for runtime dosing)
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cki_plasma_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cli_plasma_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Clu_plasma_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cgu_plasma_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cre_plasma_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cfo_plasma_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Car_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cve_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cpo_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Chv_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cfov_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Clu_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cre_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Aurine_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Afeces_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cduodenum_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_IVDOSE_tal: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_cum_dose_tal: Option<f64>,
+    /// Times, outputs, doses and solver settings, the same for every model
+    #[serde(flatten)]
+    pub options: SimulationOptions,
 }
 
+/// Seconds per model time unit, if the SBML declares its time units
+const MODEL_TIME_SECONDS: Option<f64> = Some(60.0);
+/// SBML id of the model's time units
+const MODEL_TIME_UNIT: Option<&str> = Some("min");
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
 #[wasm_bindgen]
 extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn log(s: &str);
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, delay: i32);
 }
 
-macro_rules! console_log {
-    ($($t:tt)*) => (log(&format_args!($($t)*).to_string()))
+/// Set what the model logs: "off", "error", "info" (run starts, ignored doses) or
+/// "debug" (also batch progress, the steps of each run and every event firing)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn set_log_level(level: &str) -> Result<(), String> {
+    wasm_pk_core::set_log_level(level)
 }
 
-#[wasm_bindgen]
+/// Runs when the module is instantiated, so a panic logs its message and location
+/// with console.error rather than only trapping as "unreachable"
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen(start)]
+pub fn init_panic_hook() {
+    console_error_panic_hook::set_once();
+}
+
+/// Model time units per time_unit (1 when it is not given)
+fn time_scale(sim_params: &SimulationParams) -> f64 {
+    sim_params.options.time_scale(&MODEL)
+}
+
+/// The parameters with every time and step size in model time units
+fn in_model_time(mut sim_params: SimulationParams) -> SimulationParams {
+    let scale = time_scale(&sim_params);
+    sim_params.options = sim_params.options.in_model_time(scale);
+    sim_params
+}
+
+/// The parameters with the defaults a run uses for its times and solver options
+fn with_defaults(mut sim_params: SimulationParams) -> SimulationParams {
+    sim_params.options = sim_params.options.with_defaults(&MODEL);
+    sim_params
+}
+
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
 pub fn run_simulation(params: &str) -> String {
-    console_log!("Starting simulation...");
-
-    let sim_params: SimulationParams = match serde_json::from_str(params) {
-        Ok(p) => p,
-        Err(e) => {
-            console_log!("Error parsing params: {}", e);
-            return serde_json::to_string(&SimulationResult {
-                species: HashMap::new(),
-                time: vec![],
-            }).unwrap();
+    wasm_pk_core::simulate::<TalinololModel>(params)
+}
+
+/// Run one simulation per parameter object; each result carries its own error
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_simulation_batch(params_list: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_batch::<TalinololModel>(params_list)
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub fn run_simulation_js(params: JsValue) -> Result<JsValue, JsValue> {
+    let call_start = now_ms();
+    let sim_params = params_from_js(params)?;
+    result_to_js(TalinololModel::simulate_run(sim_params), call_start)
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+fn params_from_js(params: JsValue) -> Result<SimulationParams, JsValue> {
+    let overrides: serde_json::Map<String, serde_json::Value> =
+        serde_wasm_bindgen::from_value(params).map_err(|e| JsError::new(&e.to_string()))?;
+    resolve_params::<TalinololModel>(overrides).map_err(JsValue::from)
+}
+
+/// Call `callback(fraction, steps_taken)` while simulations run, `fraction` going from 0
+/// at t_start to 1 at final_time: at most once per `interval_ms` (default 100), or
+/// every `every_steps` solver steps if given. Returning `false` cancels the run, which
+/// then fails with a `cancelled` error at the time reached.
+/// Applies to every entry point, per run in batches; pass no callback to remove it.
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub fn set_progress_callback(callback: Option<js_sys::Function>, interval_ms: Option<f64>, every_steps: Option<usize>) {
+    wasm_pk_core::set_progress_callback(callback, interval_ms, every_steps)
+}
+
+/// Solver steps between yields of run_simulation_async
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+const DEFAULT_CHUNK_STEPS: usize = 1000;
+
+/// run_simulation_js without blocking the event loop: every `chunk_steps` solver
+/// steps (default 1000) the run waits for the next macrotask. Resolves to the
+/// result object; rejects on invalid parameters.
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub async fn run_simulation_async(params: JsValue, chunk_steps: Option<usize>) -> Result<JsValue, JsValue> {
+    let call_start = now_ms();
+    let sim_params = params_from_js(params)?;
+    let chunk_steps = chunk_steps.unwrap_or(DEFAULT_CHUNK_STEPS).max(1);
+    let mut reporter = ProgressReporter::default();
+    let mut yielded_at = 0;
+    let pause = move |fraction: f64, steps: usize| {
+        let go_on = reporter.report(fraction, steps);
+        let due = steps >= yielded_at + chunk_steps;
+        if due {
+            yielded_at = steps;
+        }
+        async move {
+            if due {
+                next_tick().await;
+            }
+            go_on
         }
     };
+    let result = TalinololModel::build_problem().solve(vec![prepare_run(sim_params)], |_| {}, pause).await.pop().unwrap();
+    result_to_js(result, call_start)
+}
+
+/// Resolve on the next macrotask, after the browser has had a chance to render
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+async fn next_tick() {
+    let tick = js_sys::Promise::new(&mut |resolve, _| set_timeout(&resolve, 0));
+    // The promise only ever resolves
+    let _ = wasm_bindgen_futures::JsFuture::from(tick).await;
+}
+
+/// Simulation result kept in WASM memory, read as typed arrays
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub struct SimulationHandle {
+    result: SimulationResult,
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+impl SimulationHandle {
+    /// Stored output times (Float64Array)
+    pub fn times(&self) -> Vec<f64> {
+        self.result.time.clone()
+    }
+
+    /// Trajectory of one species by SBML id (Float64Array)
+    pub fn species(&self, name: &str) -> Result<Vec<f64>, JsError> {
+        self.result
+            .species
+            .get(name)
+            .cloned()
+            .ok_or_else(|| JsError::new(&format!("unknown species {}", name)))
+    }
+
+    /// Species ids in SBML order
+    pub fn species_names(&self) -> Vec<String> {
+        self.result.species.0.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Solver failure message, if integration stopped early
+    pub fn error(&self) -> Option<String> {
+        self.result.error.as_ref().map(|e| e.message.clone())
+    }
+}
+
+/// Fast path for large trajectories: no JSON in either direction
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub fn run_simulation_typed(params: JsValue) -> Result<SimulationHandle, JsValue> {
+    let sim_params = params_from_js(params)?;
+    Ok(SimulationHandle { result: TalinololModel::simulate_run(sim_params) })
+}
+
+/// Parameters resolved and a problem built once for repeated runs, e.g. behind a
+/// dashboard slider
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub struct ModelSession {
+    session: Session<TalinololModel>,
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+impl ModelSession {
+    /// Start from a parameter object merged over the defaults, as run_simulation_js does
+    #[wasm_bindgen(constructor)]
+    pub fn new(params: JsValue) -> Result<ModelSession, JsValue> {
+        Ok(ModelSession { session: Session::new(params_from_js(params)?)? })
+    }
+
+    /// Change one numeric parameter or option; rejected values leave the session unchanged
+    pub fn set_parameter(&mut self, name: &str, value: f64) -> Result<(), JsValue> {
+        Ok(self.session.set_parameter(name, value)?)
+    }
+
+    pub fn set_final_time(&mut self, final_time: f64) -> Result<(), JsValue> {
+        self.set_parameter("final_time", final_time)
+    }
+
+    /// Run with the current parameters; the session can be run again
+    pub fn run(&mut self) -> SimulationHandle {
+        SimulationHandle { result: self.session.run() }
+    }
+}
+
+/// run_simulation for C callers: `params_json` is a NUL-terminated JSON string and
+/// the result JSON is released with wasm_pk_free_string.
+///
+/// # Safety
+/// `params_json` must be null or point to a NUL-terminated string.
+#[cfg(feature = "c-api")]
+#[no_mangle]
+pub unsafe extern "C" fn wasm_pk_run_simulation(params_json: *const std::ffi::c_char) -> *mut std::ffi::c_char {
+    let call_start = now_ms();
+    let params = if params_json.is_null() {
+        Err("params_json is null".to_string())
+    } else {
+        unsafe { std::ffi::CStr::from_ptr(params_json) }.to_str().map_err(|e| format!("params_json: {}", e))
+    };
+    let json = match params {
+        Ok(params) => run_simulation(params),
+        Err(e) => result_to_json(failed_result(SimulationError::invalid(e)), call_start),
+    };
+    into_c_string(json)
+}
+
+/// get_default_parameters for C callers; release the string with wasm_pk_free_string
+#[cfg(feature = "c-api")]
+#[no_mangle]
+pub extern "C" fn wasm_pk_get_default_parameters() -> *mut std::ffi::c_char {
+    into_c_string(get_default_parameters())
+}
+
+/// Release a string returned by a wasm_pk_ function; null is ignored
+///
+/// # Safety
+/// `s` must be null or a string from this library that was not released yet.
+#[cfg(feature = "c-api")]
+#[no_mangle]
+pub unsafe extern "C" fn wasm_pk_free_string(s: *mut std::ffi::c_char) {
+    if !s.is_null() {
+        drop(unsafe { std::ffi::CString::from_raw(s) });
+    }
+}
+
+#[cfg(feature = "c-api")]
+fn into_c_string(json: String) -> *mut std::ffi::c_char {
+    // serde_json escapes NUL, so the JSON has no interior one
+    std::ffi::CString::new(json).unwrap().into_raw()
+}
 
+#[cfg(all(feature = "wasm", feature = "wasi"))]
+compile_error!("features `wasm` and `wasi` export the same names; build wasi with --no-default-features");
+
+/// Exports of the `wasi` feature: UTF-8 strings as pointer and length in linear memory.
+/// Every returned buffer, and every wasm_pk_alloc one, is released with wasm_pk_free.
+#[cfg(feature = "wasi")]
+mod wasi {
+    /// Buffer of `len` bytes for the host to write input into
+    #[no_mangle]
+    pub extern "C" fn wasm_pk_alloc(len: usize) -> *mut u8 {
+        Box::into_raw(vec![0u8; len].into_boxed_slice()) as *mut u8
+    }
+
+    /// Release a buffer from wasm_pk_alloc or a returned one; null is ignored
+    ///
+    /// # Safety
+    /// `ptr` must be null or a buffer of exactly `len` bytes from this module that was
+    /// not released yet.
+    #[no_mangle]
+    pub unsafe extern "C" fn wasm_pk_free(ptr: *mut u8, len: usize) {
+        if !ptr.is_null() {
+            drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) });
+        }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    unsafe fn into_buffer(json: String, out_len: *mut usize) -> *mut u8 {
+        let bytes = json.into_bytes().into_boxed_slice();
+        unsafe { *out_len = bytes.len() };
+        Box::into_raw(bytes) as *mut u8
+    }
+
+    /// # Safety
+    /// `ptr` must be null or point to `len` readable bytes.
+    unsafe fn read_str<'a>(ptr: *const u8, len: usize) -> Result<&'a str, String> {
+        if ptr.is_null() {
+            return Err("params is null".to_string());
+        }
+        std::str::from_utf8(unsafe { std::slice::from_raw_parts(ptr, len) }).map_err(|e| format!("params: {}", e))
+    }
+
+    /// run_simulation on the `params_len` bytes of JSON at `params`
+    ///
+    /// # Safety
+    /// `params` must be null or point to `params_len` readable bytes, and `out_len` to
+    /// writable memory.
+    #[no_mangle]
+    pub unsafe extern "C" fn run_simulation(params: *const u8, params_len: usize, out_len: *mut usize) -> *mut u8 {
+        let call_start = super::now_ms();
+        let json = match unsafe { read_str(params, params_len) } {
+            Ok(params) => super::run_simulation(params),
+            Err(e) => super::result_to_json(super::failed_result(super::SimulationError::invalid(e)), call_start),
+        };
+        unsafe { into_buffer(json, out_len) }
+    }
+
+    /// validate_parameters on the `params_len` bytes of JSON at `params`
+    ///
+    /// # Safety
+    /// As for run_simulation.
+    #[no_mangle]
+    pub unsafe extern "C" fn validate_parameters(params: *const u8, params_len: usize, out_len: *mut usize) -> *mut u8 {
+        let json = match unsafe { read_str(params, params_len) } {
+            Ok(params) => super::validate_parameters(params),
+            Err(message) => {
+                let issue = super::ParameterIssue { field: None, message };
+                serde_json::to_string(&super::ParameterReport { errors: vec![issue], warnings: vec![] }).unwrap()
+            }
+        };
+        unsafe { into_buffer(json, out_len) }
+    }
+
+    /// set_log_level on the `level_len` bytes at `level`; 0 on success, -1 for an
+    /// unknown level
+    ///
+    /// # Safety
+    /// `level` must be null or point to `level_len` readable bytes.
+    #[no_mangle]
+    pub unsafe extern "C" fn set_log_level(level: *const u8, level_len: usize) -> i32 {
+        match unsafe { read_str(level, level_len) }.and_then(super::set_log_level) {
+            Ok(()) => 0,
+            Err(_) => -1,
+        }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[no_mangle]
+    pub unsafe extern "C" fn get_default_parameters(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_default_parameters(), out_len) }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[cfg(not(feature = "minimal"))]
+    #[no_mangle]
+    pub unsafe extern "C" fn get_parameters_info(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_parameters_info(), out_len) }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[cfg(not(feature = "minimal"))]
+    #[no_mangle]
+    pub unsafe extern "C" fn get_species_info(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_species_info(), out_len) }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[cfg(not(feature = "minimal"))]
+    #[no_mangle]
+    pub unsafe extern "C" fn get_compartments_info(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_compartments_info(), out_len) }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[cfg(not(feature = "minimal"))]
+    #[no_mangle]
+    pub unsafe extern "C" fn get_model_metadata(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_model_metadata(), out_len) }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[cfg(not(feature = "minimal"))]
+    #[no_mangle]
+    pub unsafe extern "C" fn get_model_equations(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_model_equations(), out_len) }
+    }
+}
+
+/// Python module of the `python` feature, built with maturin as `sbml_model`.
+/// JSON arguments and results are dicts and lists on the Python side.
+#[cfg(feature = "python")]
+mod python {
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+
+    /// Python objects of the JSON the model returns
+    fn loads<'py>(py: Python<'py>, json: &str) -> PyResult<Bound<'py, PyAny>> {
+        py.import("json")?.call_method1("loads", (json,))
+    }
+
+    /// JSON of a dict or list argument; a string is taken as JSON already
+    fn dumps(value: &Bound<'_, PyAny>) -> PyResult<String> {
+        match value.extract::<String>() {
+            Ok(json) => Ok(json),
+            Err(_) => value.py().import("json")?.call_method1("dumps", (value,))?.extract(),
+        }
+    }
+
+    fn loads_ok<'py>(py: Python<'py>, result: Result<String, String>) -> PyResult<Bound<'py, PyAny>> {
+        loads(py, &result.map_err(PyValueError::new_err)?)
+    }
+
+    /// Run one simulation with `params` merged over the defaults; a failed run
+    /// has its message in the result's error
+    #[pyfunction]
+    #[pyo3(signature = (params = None))]
+    fn run_simulation<'py>(py: Python<'py>, params: Option<&Bound<'py, PyAny>>) -> PyResult<Bound<'py, PyAny>> {
+        let params = params.map(dumps).transpose()?.unwrap_or_else(|| "{}".to_string());
+        loads(py, &super::run_simulation(&params))
+    }
+
+    /// One result per parameter dict, each with its own error
+    #[pyfunction]
+    fn run_simulation_batch<'py>(py: Python<'py>, params_list: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        loads_ok(py, super::run_simulation_batch(&dumps(params_list)?))
+    }
+
+    /// Every problem with `params`, without running
+    #[pyfunction]
+    fn validate_parameters<'py>(py: Python<'py>, params: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        loads(py, &super::validate_parameters(&dumps(params)?))
+    }
+
+    /// Cmax, Tmax, AUC and half-life of one species of a result
+    #[pyfunction]
+    fn compute_pk_metrics<'py>(py: Python<'py>, result: &Bound<'py, PyAny>, species: &str) -> PyResult<Bound<'py, PyAny>> {
+        loads_ok(py, super::compute_pk_metrics(&dumps(result)?, species))
+    }
+
+    /// Default value of every parameter and option
+    #[pyfunction]
+    fn get_default_parameters(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_default_parameters())
+    }
+
+    /// Name, default, units and range of each parameter
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_parameters_info(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_parameters_info())
+    }
+
+    /// Id, initial value, units and compartment of each species
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_species_info(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_species_info())
+    }
+
+    /// Size, formula, units and species of each compartment
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_compartments_info(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_compartments_info())
+    }
+
+    /// Model id, sizes, units and provenance
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_model_metadata(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_model_metadata())
+    }
+
+    /// Derivatives and assignment rules as text
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_model_equations(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_model_equations())
+    }
+
+    /// The SBML document the model was generated from
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_model_sbml() -> PyResult<String> {
+        super::get_model_sbml().map_err(PyValueError::new_err)
+    }
+
+    /// Set what the model logs to stderr: "off", "error", "info" or "debug"
+    #[pyfunction]
+    fn set_log_level(level: &str) -> PyResult<()> {
+        super::set_log_level(level).map_err(PyValueError::new_err)
+    }
+
+    #[pymodule]
+    fn sbml_model(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(run_simulation, m)?)?;
+        m.add_function(wrap_pyfunction!(run_simulation_batch, m)?)?;
+        m.add_function(wrap_pyfunction!(validate_parameters, m)?)?;
+        m.add_function(wrap_pyfunction!(compute_pk_metrics, m)?)?;
+        m.add_function(wrap_pyfunction!(get_default_parameters, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_parameters_info, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_species_info, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_compartments_info, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_model_metadata, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_model_equations, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_model_sbml, m)?)?;
+        m.add_function(wrap_pyfunction!(set_log_level, m)?)?;
+        Ok(())
+    }
+}
+
+/// Names of the solver parameter vector entries, in index order
+const PARAMETER_ORDER: &[&str] = &["BW", "HEIGHT", "HR", "HRrest", "COBW", "COHRI", "Fblood", "HCT", "f_shunting_forearm", "FVgu", "FVki", "FVli", "FVlu", "FVfo", "FVve", "FVar", "FVpo", "FVhv", "FVfov", "FQgu", "FQki", "FQh", "FQlu", "FQfo", "conversion_min_per_day", "f_cirrhosis", "PODOSE_tal", "Ka_dis_tal", "Mr_tal", "fup_tal", "ftissue_tal", "Kp_tal", "IVDOSE_tal", "ti_tal", "Ri_tal", "cum_dose_tal", "cum_dose_intestine_tal", "Vurine", "Vfeces", "Vstomach", "Vfo", "Vfov", "Vduodenum", "f_shunts", "f_tissue_loss", "FVre", "FQre", "BSA", "CO", "Vgu", "Vki", "Vli", "Vlu", "Vve", "Var", "Vpo", "Vhv", "Vfo_plasma", "Vfo_tissue", "Ki_tal", "Vre", "QC", "Vgu_plasma", "Vgu_tissue", "Vki_plasma", "Vki_tissue", "Vli_plasma", "Vli_tissue", "Vlu_plasma", "Vlu_tissue", "Vre_plasma", "Vre_tissue", "Qgu", "Qki", "Qh", "Qlu", "Qre", "Qfo", "Qpo", "Qha", "init_Cki_plasma_tal", "init_Cli_plasma_tal", "init_Clu_plasma_tal", "init_Cgu_plasma_tal", "init_Cre_plasma_tal", "init_Cfo_plasma_tal", "init_Car_tal", "init_Cve_tal", "init_Cpo_tal", "init_Chv_tal", "init_Cfov_tal", "init_Clu_tal", "init_Cre_tal", "init_Aurine_tal", "init_Afeces_tal", "init_Cduodenum_tal", "init_IVDOSE_tal", "init_cum_dose_tal"];
+
+/// Index mapping of the solver parameter vector: model parameters, derived
+/// parameter values, then the initial state as init_<species> (JSON array)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_parameter_order() -> String {
+    serde_json::to_string(PARAMETER_ORDER).unwrap()
+}
+
+/// Resolve one run's parameters into the solver parameter vector
+fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
     let BW = sim_params.BW;
     let HEIGHT = sim_params.HEIGHT;
     let HR = sim_params.HR;
@@ -133,81 +700,259 @@ pub fn run_simulation(params: &str) -> String {
     let Vduodenum = sim_params.Vduodenum;
     let f_shunts = f_cirrhosis;
     let f_tissue_loss = f_cirrhosis;
-    let FVre = 1.0 - 1.0*(FVfo + (FVar + (FVve + (FVlu + (FVli + (FVgu + FVki))))));
-    let FQre = 1.0 - 1.0*(FQfo + (FQh + FQki));
-    let BSA = 4853.0/200000.0*(BW*1.0.powi(-1)).powf(2689.0/5000.0)*(HEIGHT*1.0.powi(-1)).powf(991.0/2500.0);
-    let CO = BW*COBW + 1.0/60.0*(HR - 1.0*HRrest)*COHRI;
+    let FVre = -FVar - FVfo - FVgu - FVki - FVli - FVlu - FVve + 1.0;
+    let FQre = -FQfo - FQh - FQki + 1.0;
+    let BSA = 0.024265*BW.powf(0.5378)*HEIGHT.powf(0.3964);
+    let CO = BW*COBW + 0.016666666666666666*COHRI*(HR - HRrest);
     let Vgu = BW*FVgu;
     let Vki = BW*FVki;
     let Vli = BW*FVli;
     let Vlu = BW*FVlu;
-    let Vve = BW*FVve - 1.0*FVve*(FVar + FVve).powi(-1)*BW*Fblood*(-1.0*FVar + (1.0 - 1.0*FVve));
-    let Var = BW*FVar - 1.0*FVar*(FVar + FVve).powi(-1)*BW*Fblood*(-1.0*FVar + (1.0 - 1.0*FVve));
-    let Vpo = (1.0 - 1.0*HCT)*(BW*FVpo - 1.0*FVpo*(FVfo + (FVhv + (FVpo + (FVar + FVve)))).powi(-1)*BW*Fblood*(1.0 - 1.0*(FVfo + (FVhv + (FVpo + (FVar + FVve))))));
-    let Vhv = (1.0 - 1.0*HCT)*(BW*FVhv - 1.0*FVhv*(FVfo + (FVhv + (FVpo + (FVar + FVve)))).powi(-1)*BW*Fblood*(1.0 - 1.0*(FVfo + (FVhv + (FVpo + (FVar + FVve))))));
-    let Vfo_plasma = Vfo*Fblood*(1.0 - 1.0*HCT);
-    let Vfo_tissue = Vfo*(1.0 - 1.0*Fblood);
-    let Ki_tal = 693.0/1000.0*ti_tal.powi(-1)*60.0;
-    let Afov_tal = Cfov_tal*Vfov;
-    let Xurine_tal = Aurine_tal*Mr_tal;
-    let Xfeces_tal = Afeces_tal*Mr_tal;
+    let Vve = -BW*FVve*Fblood*(FVar + FVve).powi(-1)*(-FVar - FVve + 1.0) + BW*FVve;
+    let Var = -BW*FVar*Fblood*(FVar + FVve).powi(-1)*(-FVar - FVve + 1.0) + BW*FVar;
+    let Vpo = (1.0 - HCT)*(-BW*FVpo*Fblood*(FVar + FVfo + FVhv + FVpo + FVve).powi(-1)*(-FVar - FVfo - FVhv - FVpo - FVve + 1.0) + BW*FVpo);
+    let Vhv = (1.0 - HCT)*(-BW*FVhv*Fblood*(FVar + FVfo + FVhv + FVpo + FVve).powi(-1)*(-FVar - FVfo - FVhv - FVpo - FVve + 1.0) + BW*FVhv);
+    let Vfo_plasma = Fblood*Vfo*(1.0 - HCT);
+    let Vfo_tissue = Vfo*(1.0 - Fblood);
+    let Ki_tal = 41.58*ti_tal.powi(-1);
     let Vre = BW*FVre;
-    let QC = 1.0/1000.0*CO*60.0;
-    let Vgu_plasma = Vgu*Fblood*(1.0 - 1.0*HCT);
-    let Vgu_tissue = Vgu*(1.0 - 1.0*Fblood);
-    let Vki_plasma = Vki*Fblood*(1.0 - 1.0*HCT);
-    let Vki_tissue = Vki*(1.0 - 1.0*Fblood);
-    let Vli_plasma = Vli*Fblood*(1.0 - 1.0*HCT);
-    let Vli_tissue = Vli*(1.0 - 1.0*f_tissue_loss)*(1.0 - 1.0*Fblood);
-    let Vlu_plasma = Vlu*Fblood*(1.0 - 1.0*HCT);
-    let Vlu_tissue = Vlu*(1.0 - 1.0*Fblood);
-    let Ave_tal = Cve_tal*Vve;
-    let Aar_tal = Car_tal*Var;
-    let Apo_tal = Cpo_tal*Vpo;
-    let Ahv_tal = Chv_tal*Vhv;
-    let Afo_plasma_tal = Cfo_plasma_tal*Vfo_plasma;
-    let Xfov_tal = Afov_tal*Mr_tal;
-    let Mfov_tal = Afov_tal*Vfov.powi(-1)*Mr_tal;
-    let Vre_plasma = Vre*Fblood*(1.0 - 1.0*HCT);
-    let Vre_tissue = Vre*(1.0 - 1.0*Fblood);
-    let Qgu = QC*FQgu;
-    let Qki = QC*FQki;
-    let Qh = QC*FQh;
-    let Qlu = QC*FQlu;
-    let Qre = QC*FQre;
-    let Qfo = QC*FQfo;
-    let Agu_plasma_tal = Cgu_plasma_tal*Vgu_plasma;
-    let Aki_plasma_tal = Cki_plasma_tal*Vki_plasma;
-    let Ali_plasma_tal = Cli_plasma_tal*Vli_plasma;
-    let Alu_plasma_tal = Clu_plasma_tal*Vlu_plasma;
-    let Xve_tal = Ave_tal*Mr_tal;
-    let Mve_tal = Ave_tal*Vve.powi(-1)*Mr_tal;
-    let Xar_tal = Aar_tal*Mr_tal;
-    let Mar_tal = Aar_tal*Var.powi(-1)*Mr_tal;
-    let Xpo_tal = Apo_tal*Mr_tal;
-    let Mpo_tal = Apo_tal*Vpo.powi(-1)*Mr_tal;
-    let Xhv_tal = Ahv_tal*Mr_tal;
-    let Mhv_tal = Ahv_tal*Vhv.powi(-1)*Mr_tal;
-    let Xfo_plasma_tal = Afo_plasma_tal*Mr_tal;
-    let Mfo_plasma_tal = Afo_plasma_tal*Vfo_plasma.powi(-1)*Mr_tal;
-    let Are_plasma_tal = Cre_plasma_tal*Vre_plasma;
+    let QC = 0.06*CO;
+    let Vgu_plasma = Fblood*Vgu*(1.0 - HCT);
+    let Vgu_tissue = Vgu*(1.0 - Fblood);
+    let Vki_plasma = Fblood*Vki*(1.0 - HCT);
+    let Vki_tissue = Vki*(1.0 - Fblood);
+    let Vli_plasma = Fblood*Vli*(1.0 - HCT);
+    let Vli_tissue = Vli*(1.0 - Fblood)*(1.0 - f_tissue_loss);
+    let Vlu_plasma = Fblood*Vlu*(1.0 - HCT);
+    let Vlu_tissue = Vlu*(1.0 - Fblood);
+    let Vre_plasma = Fblood*Vre*(1.0 - HCT);
+    let Vre_tissue = Vre*(1.0 - Fblood);
+    let Qgu = FQgu*QC;
+    let Qki = FQki*QC;
+    let Qh = FQh*QC;
+    let Qlu = FQlu*QC;
+    let Qre = FQre*QC;
+    let Qfo = FQfo*QC;
     let Qpo = Qgu;
-    let Qha = -1.0*Qgu + Qh;
-    let Xgu_plasma_tal = Agu_plasma_tal*Mr_tal;
-    let Mgu_plasma_tal = Agu_plasma_tal*Vgu_plasma.powi(-1)*Mr_tal;
-    let Xki_plasma_tal = Aki_plasma_tal*Mr_tal;
-    let Mki_plasma_tal = Aki_plasma_tal*Vki_plasma.powi(-1)*Mr_tal;
-    let Xli_plasma_tal = Ali_plasma_tal*Mr_tal;
-    let Mli_plasma_tal = Ali_plasma_tal*Vli_plasma.powi(-1)*Mr_tal;
-    let Xlu_plasma_tal = Alu_plasma_tal*Mr_tal;
-    let Mlu_plasma_tal = Alu_plasma_tal*Vlu_plasma.powi(-1)*Mr_tal;
-    let Xre_plasma_tal = Are_plasma_tal*Mr_tal;
-    let Mre_plasma_tal = Are_plasma_tal*Vre_plasma.powi(-1)*Mr_tal;
-
-
-
-    // RHS Closure
-    let rhs = |y: &diffsol::NalgebraVec<f64>, _p: &diffsol::NalgebraVec<f64>, t: f64, dy: &mut diffsol::NalgebraVec<f64>| {
+    let Qha = -Qgu + Qh;
+
+    vec![
+        BW,
+        HEIGHT,
+        HR,
+        HRrest,
+        COBW,
+        COHRI,
+        Fblood,
+        HCT,
+        f_shunting_forearm,
+        FVgu,
+        FVki,
+        FVli,
+        FVlu,
+        FVfo,
+        FVve,
+        FVar,
+        FVpo,
+        FVhv,
+        FVfov,
+        FQgu,
+        FQki,
+        FQh,
+        FQlu,
+        FQfo,
+        conversion_min_per_day,
+        f_cirrhosis,
+        PODOSE_tal,
+        Ka_dis_tal,
+        Mr_tal,
+        fup_tal,
+        ftissue_tal,
+        Kp_tal,
+        IVDOSE_tal,
+        ti_tal,
+        Ri_tal,
+        cum_dose_tal,
+        cum_dose_intestine_tal,
+        Vurine,
+        Vfeces,
+        Vstomach,
+        Vfo,
+        Vfov,
+        Vduodenum,
+        f_shunts,
+        f_tissue_loss,
+        FVre,
+        FQre,
+        BSA,
+        CO,
+        Vgu,
+        Vki,
+        Vli,
+        Vlu,
+        Vve,
+        Var,
+        Vpo,
+        Vhv,
+        Vfo_plasma,
+        Vfo_tissue,
+        Ki_tal,
+        Vre,
+        QC,
+        Vgu_plasma,
+        Vgu_tissue,
+        Vki_plasma,
+        Vki_tissue,
+        Vli_plasma,
+        Vli_tissue,
+        Vlu_plasma,
+        Vlu_tissue,
+        Vre_plasma,
+        Vre_tissue,
+        Qgu,
+        Qki,
+        Qh,
+        Qlu,
+        Qre,
+        Qfo,
+        Qpo,
+        Qha,
+        sim_params.init_Cki_plasma_tal.unwrap_or(0.0),
+        sim_params.init_Cli_plasma_tal.unwrap_or(0.0),
+        sim_params.init_Clu_plasma_tal.unwrap_or(0.0),
+        sim_params.init_Cgu_plasma_tal.unwrap_or(0.0),
+        sim_params.init_Cre_plasma_tal.unwrap_or(0.0),
+        sim_params.init_Cfo_plasma_tal.unwrap_or(0.0),
+        sim_params.init_Car_tal.unwrap_or(0.0),
+        sim_params.init_Cve_tal.unwrap_or(0.0),
+        sim_params.init_Cpo_tal.unwrap_or(0.0),
+        sim_params.init_Chv_tal.unwrap_or(0.0),
+        sim_params.init_Cfov_tal.unwrap_or(0.0),
+        sim_params.init_Clu_tal.unwrap_or(0.0),
+        sim_params.init_Cre_tal.unwrap_or(0.0),
+        sim_params.init_Aurine_tal.unwrap_or(0.0),
+        sim_params.init_Afeces_tal.unwrap_or(0.0),
+        sim_params.init_Cduodenum_tal.unwrap_or(0.0),
+        sim_params.init_IVDOSE_tal.unwrap_or(IVDOSE_tal),
+        sim_params.init_cum_dose_tal.unwrap_or(cum_dose_tal),
+    ]
+}
+
+/// Range of each checked entry of p, with the parameters a derived value reads
+const PARAMETER_CHECKS: &[ParameterCheck] = &[
+    ("BW", 0, ParameterRange::Positive, &[]),
+    ("HEIGHT", 1, ParameterRange::Unknown, &[]),
+    ("HR", 2, ParameterRange::Unknown, &[]),
+    ("HRrest", 3, ParameterRange::Unknown, &[]),
+    ("COBW", 4, ParameterRange::NonNegative, &[]),
+    ("COHRI", 5, ParameterRange::NonNegative, &[]),
+    ("Fblood", 6, ParameterRange::Fraction, &[]),
+    ("HCT", 7, ParameterRange::BelowOne, &[]),
+    ("f_shunting_forearm", 8, ParameterRange::Fraction, &[]),
+    ("FVgu", 9, ParameterRange::Fraction, &[]),
+    ("FVki", 10, ParameterRange::Fraction, &[]),
+    ("FVli", 11, ParameterRange::Fraction, &[]),
+    ("FVlu", 12, ParameterRange::Fraction, &[]),
+    ("FVfo", 13, ParameterRange::Fraction, &[]),
+    ("FVve", 14, ParameterRange::Fraction, &[]),
+    ("FVar", 15, ParameterRange::Fraction, &[]),
+    ("FVpo", 16, ParameterRange::Fraction, &[]),
+    ("FVhv", 17, ParameterRange::Fraction, &[]),
+    ("FVfov", 18, ParameterRange::Fraction, &[]),
+    ("FQgu", 19, ParameterRange::Fraction, &[]),
+    ("FQki", 20, ParameterRange::Fraction, &[]),
+    ("FQh", 21, ParameterRange::Fraction, &[]),
+    ("FQlu", 22, ParameterRange::Fraction, &[]),
+    ("FQfo", 23, ParameterRange::Fraction, &[]),
+    ("conversion_min_per_day", 24, ParameterRange::Unknown, &[]),
+    ("f_cirrhosis", 25, ParameterRange::Fraction, &[]),
+    ("PODOSE_tal", 26, ParameterRange::Unknown, &[]),
+    ("Ka_dis_tal", 27, ParameterRange::Unknown, &[]),
+    ("Mr_tal", 28, ParameterRange::Unknown, &[]),
+    ("fup_tal", 29, ParameterRange::Fraction, &[]),
+    ("ftissue_tal", 30, ParameterRange::NonNegative, &[]),
+    ("Kp_tal", 31, ParameterRange::Unknown, &[]),
+    ("IVDOSE_tal", 32, ParameterRange::Unknown, &[]),
+    ("ti_tal", 33, ParameterRange::Unknown, &[]),
+    ("Ri_tal", 34, ParameterRange::Unknown, &[]),
+    ("cum_dose_tal", 35, ParameterRange::Unknown, &[]),
+    ("cum_dose_intestine_tal", 36, ParameterRange::Unknown, &[]),
+    ("Vurine", 37, ParameterRange::NonNegative, &[]),
+    ("Vfeces", 38, ParameterRange::NonNegative, &[]),
+    ("Vstomach", 39, ParameterRange::NonNegative, &[]),
+    ("Vfo", 40, ParameterRange::NonNegative, &[]),
+    ("Vfov", 41, ParameterRange::NonNegative, &[]),
+    ("Vduodenum", 42, ParameterRange::NonNegative, &[]),
+    ("f_shunts", 43, ParameterRange::Fraction, &["f_cirrhosis"]),
+    ("f_tissue_loss", 44, ParameterRange::Fraction, &["f_cirrhosis"]),
+    ("FVre", 45, ParameterRange::Fraction, &["FVgu", "FVki", "FVli", "FVlu", "FVfo", "FVve", "FVar"]),
+    ("FQre", 46, ParameterRange::Fraction, &["FQki", "FQh", "FQfo"]),
+    ("CO", 48, ParameterRange::NonNegative, &["BW", "HR", "HRrest", "COBW", "COHRI"]),
+    ("Vgu", 49, ParameterRange::NonNegative, &["BW", "FVgu"]),
+    ("Vki", 50, ParameterRange::NonNegative, &["BW", "FVki"]),
+    ("Vli", 51, ParameterRange::NonNegative, &["BW", "FVli"]),
+    ("Vlu", 52, ParameterRange::NonNegative, &["BW", "FVlu"]),
+    ("Vve", 53, ParameterRange::NonNegative, &["BW", "Fblood", "FVve", "FVar"]),
+    ("Var", 54, ParameterRange::NonNegative, &["BW", "Fblood", "FVve", "FVar"]),
+    ("Vpo", 55, ParameterRange::NonNegative, &["BW", "Fblood", "HCT", "FVfo", "FVve", "FVar", "FVpo", "FVhv"]),
+    ("Vhv", 56, ParameterRange::NonNegative, &["BW", "Fblood", "HCT", "FVfo", "FVve", "FVar", "FVpo", "FVhv"]),
+    ("Vfo_plasma", 57, ParameterRange::NonNegative, &["Fblood", "HCT", "Vfo"]),
+    ("Vfo_tissue", 58, ParameterRange::NonNegative, &["Fblood", "Vfo"]),
+    ("Vre", 60, ParameterRange::NonNegative, &["BW", "FVgu", "FVki", "FVli", "FVlu", "FVfo", "FVve", "FVar"]),
+    ("QC", 61, ParameterRange::NonNegative, &["BW", "HR", "HRrest", "COBW", "COHRI"]),
+    ("Vgu_plasma", 62, ParameterRange::NonNegative, &["BW", "Fblood", "HCT", "FVgu"]),
+    ("Vgu_tissue", 63, ParameterRange::NonNegative, &["BW", "Fblood", "FVgu"]),
+    ("Vki_plasma", 64, ParameterRange::NonNegative, &["BW", "Fblood", "HCT", "FVki"]),
+    ("Vki_tissue", 65, ParameterRange::NonNegative, &["BW", "Fblood", "FVki"]),
+    ("Vli_plasma", 66, ParameterRange::NonNegative, &["BW", "Fblood", "HCT", "FVli"]),
+    ("Vli_tissue", 67, ParameterRange::NonNegative, &["BW", "Fblood", "FVli", "f_cirrhosis"]),
+    ("Vlu_plasma", 68, ParameterRange::NonNegative, &["BW", "Fblood", "HCT", "FVlu"]),
+    ("Vlu_tissue", 69, ParameterRange::NonNegative, &["BW", "Fblood", "FVlu"]),
+    ("Vre_plasma", 70, ParameterRange::NonNegative, &["BW", "Fblood", "HCT", "FVgu", "FVki", "FVli", "FVlu", "FVfo", "FVve", "FVar"]),
+    ("Vre_tissue", 71, ParameterRange::NonNegative, &["BW", "Fblood", "FVgu", "FVki", "FVli", "FVlu", "FVfo", "FVve", "FVar"]),
+    ("Qgu", 72, ParameterRange::NonNegative, &["BW", "HR", "HRrest", "COBW", "COHRI", "FQgu"]),
+    ("Qki", 73, ParameterRange::NonNegative, &["BW", "HR", "HRrest", "COBW", "COHRI", "FQki"]),
+    ("Qh", 74, ParameterRange::NonNegative, &["BW", "HR", "HRrest", "COBW", "COHRI", "FQh"]),
+    ("Qlu", 75, ParameterRange::NonNegative, &["BW", "HR", "HRrest", "COBW", "COHRI", "FQlu"]),
+    ("Qre", 76, ParameterRange::NonNegative, &["BW", "HR", "HRrest", "COBW", "COHRI", "FQki", "FQh", "FQfo"]),
+    ("Qfo", 77, ParameterRange::NonNegative, &["BW", "HR", "HRrest", "COBW", "COHRI", "FQfo"]),
+    ("Qpo", 78, ParameterRange::NonNegative, &["BW", "HR", "HRrest", "COBW", "COHRI", "FQgu"]),
+    ("Qha", 79, ParameterRange::NonNegative, &["BW", "HR", "HRrest", "COBW", "COHRI", "FQgu", "FQh"]),
+];
+
+/// Check parameters without running: `{errors, warnings}`, each `{field, message}` (JSON)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn validate_parameters(params: &str) -> String {
+    wasm_pk_core::validate_parameters::<TalinololModel>(params)
+}
+
+/// The model as a type, for code generic over models; see PkModel
+pub struct TalinololModel;
+
+impl PkModel for TalinololModel {
+    type Params = SimulationParams;
+    const N_STATES: usize = 18;
+    const INFO: ModelInfo = MODEL;
+
+    fn rhs(y: &[f64], p: &[f64], _t: f64, _windows: &[UptakeWindow], dy: &mut [f64]) {
+        // Parameters from p
+        let f_shunting_forearm = p[8];
+        let Mr_tal = p[28];
+        let fup_tal = p[29];
+        let ftissue_tal = p[30];
+        let Kp_tal = p[31];
+        let Ri_tal = p[34];
+        let f_shunts = p[43];
+        let Ki_tal = p[59];
+        let Qgu = p[72];
+        let Qki = p[73];
+        let Qh = p[74];
+        let Qlu = p[75];
+        let Qre = p[76];
+        let Qfo = p[77];
+        let Qpo = p[78];
+        let Qha = p[79];
+
         // Map species names to y indices
         let Cki_plasma_tal = y[0];
         let Cli_plasma_tal = y[1];
@@ -222,258 +967,4792 @@ pub fn run_simulation(params: &str) -> String {
         let Cfov_tal = y[10];
         let Clu_tal = y[11];
         let Cre_tal = y[12];
-        let Aurine_tal = y[13];
-        let Afeces_tal = y[14];
-        let Cduodenum_tal = y[15];
+        let IVDOSE_tal = y[16];
 
         // Temporary variables (CSE)
-        let x0 = 1.0*Qki;
-        let x1 = 1.0*f_shunts;
+        let x0 = Qki;
+        let x1 = f_shunts;
         let x2 = Car_tal*Qha;
         let x3 = Cpo_tal*Qpo;
         let x4 = Qha + Qpo;
-        let x5 = Cve_tal*Qlu;
+        let x5 = Clu_plasma_tal*Qlu;
         let x6 = Kp_tal*fup_tal;
-        let x7 = ftissue_tal*(-1.0*Clu_plasma_tal*x6 + Clu_tal);
-        let x8 = -1.0*Clu_plasma_tal*Qlu;
-        let x9 = 1.0*Qgu;
-        let x10 = Car_tal*Qre;
-        let x11 = ftissue_tal*(-1.0*Cre_plasma_tal*x6 + Cre_tal);
-        let x12 = Cre_plasma_tal*Qre;
+        let x7 = ftissue_tal*(Clu_plasma_tal*x6 - Clu_tal);
+        let x8 = -Cve_tal*Qlu;
+        let x9 = Qgu;
+        let x10 = Cre_plasma_tal*Qre;
+        let x11 = ftissue_tal*(Cre_plasma_tal*x6 - Cre_tal);
+        let x12 = Car_tal*Qre;
         let x13 = Car_tal*f_shunting_forearm;
         let x14 = f_shunting_forearm - 1.0;
         let x15 = Cfo_plasma_tal*x14;
-        let x16 = 1.0*Qfo;
+        let x16 = Qfo;
         let x17 = f_shunts*x2;
         let x18 = f_shunts - 1.0;
         let x19 = Chv_tal*Qh;
-        let x20 = f_shunts*x3;
-        let x21 = 1.0*x18*x4;
-        let x22 = 1.0*Qpo;
-        let x23 = ftissue_tal*x6;
-        let x24 = 1.0*Qlu;
-        let x25 = 1.0*ftissue_tal;
-        let x26 = 1.0*Qre;
-        let x27 = 1.0*Qfo*x14;
-        let x28 = Qfo*f_shunting_forearm;
-        let x29 = 1.0*Qh;
-        let x30 = x25*x6;
-        let x31 = -1.0*x25;
+        let x20 = Mr_tal.powi(-1);
+        let x21 = IVDOSE_tal*Ki_tal;
+        let x22 = f_shunts*x3;
 
         // Derivatives
-        dy[0] = x0*(Car_tal - 1.0*Cki_plasma_tal);
-        dy[1] = (x1 - 1.0)*(Cli_plasma_tal*x4 - 1.0*x2 - 1.0*x3);
-        dy[2] = 1.0*x5 + 1.0*x7 + 1.0*x8;
-        dy[3] = x9*(Car_tal - 1.0*Cgu_plasma_tal);
-        dy[4] = 1.0*x10 + 1.0*x11 - 1.0*x12;
-        dy[5] = x16*(-1.0*Car_tal*x14 - 1.0*x13 + x15);
-        dy[6] = 1.0*Car_tal*Qfo*x14 - 1.0*Car_tal*Qgu + 1.0*Car_tal*Qha*x18 - 1.0*Car_tal*Qki + 1.0*Cfo_plasma_tal*Qfo*x14 - 1.0*Qfo*x13 - 1.0*x10 - 1.0*x17 - 1.0*x8;
-        dy[7] = 1.0*Cfov_tal*Qfo + 1.0*Cki_plasma_tal*Qki + 1.0*IVDOSE_tal*Ki_tal*Mr_tal.powi(-1) + 1.0*x12 + 1.0*x19 - 1.0*x5;
-        dy[8] = 1.0*Cgu_plasma_tal*Qgu + 1.0*x18*x3 - 1.0*x20;
-        dy[9] = -1.0*Cli_plasma_tal*x18*x4 + 1.0*x17 - 1.0*x19 + 1.0*x20;
-        dy[10] = x16*(-1.0*Cfov_tal + x13 - 1.0*x15);
-        dy[11] = -1.0*x7;
-        dy[12] = -1.0*x11;
+        dy[0] = x0*(Car_tal - Cki_plasma_tal);
+        dy[1] = (x1 - 1.0)*(Cli_plasma_tal*x4 - x2 - x3);
+        dy[2] = -x5 - x7 - x8;
+        dy[3] = x9*(Car_tal - Cgu_plasma_tal);
+        dy[4] = -x10 - x11 + x12;
+        dy[5] = x16*(-Car_tal*x14 - x13 + x15);
+        dy[6] = Car_tal*Qfo*x14 - Car_tal*Qgu + Car_tal*Qha*x18 - Car_tal*Qki + Cfo_plasma_tal*Qfo*x14 - Qfo*x13 - x12 - x17 + x5;
+        dy[7] = Cfov_tal*Qfo + Cki_plasma_tal*Qki + x10 + x19 + x20*x21 + x8;
+        dy[8] = Cgu_plasma_tal*Qgu + x18*x3 - x22;
+        dy[9] = -Cli_plasma_tal*x18*x4 + x17 - x19 + x22;
+        dy[10] = x16*(-Cfov_tal + x13 - x15);
+        dy[11] = x7;
+        dy[12] = x11;
         dy[13] = 0.0;
         dy[14] = 0.0;
         dy[15] = 0.0;
-    };
+        dy[16] = Ri_tal - x21;
+        dy[17] = Ri_tal;
+    }
+
+    fn jac(_y: &[f64], p: &[f64], _t: f64, v: &[f64], jv: &mut [f64]) {
+        jv.fill(0.0);
 
-    // Jacobian Closure (Matrix-Vector Product)
-    let jac = |y: &diffsol::NalgebraVec<f64>, _p: &diffsol::NalgebraVec<f64>, t: f64, v: &diffsol::NalgebraVec<f64>, jv: &mut diffsol::NalgebraVec<f64>| {
-        for i in 0..jv.len() { jv[i] = 0.0; }
+        // Parameters from p
+        let f_shunting_forearm = p[8];
+        let Mr_tal = p[28];
+        let fup_tal = p[29];
+        let ftissue_tal = p[30];
+        let Kp_tal = p[31];
+        let f_shunts = p[43];
+        let Ki_tal = p[59];
+        let Qgu = p[72];
+        let Qki = p[73];
+        let Qh = p[74];
+        let Qlu = p[75];
+        let Qre = p[76];
+        let Qfo = p[77];
+        let Qpo = p[78];
+        let Qha = p[79];
 
         // Map species names to y indices
-        let Cki_plasma_tal = y[0];
-        let Cli_plasma_tal = y[1];
-        let Clu_plasma_tal = y[2];
-        let Cgu_plasma_tal = y[3];
-        let Cre_plasma_tal = y[4];
-        let Cfo_plasma_tal = y[5];
-        let Car_tal = y[6];
-        let Cve_tal = y[7];
-        let Cpo_tal = y[8];
-        let Chv_tal = y[9];
-        let Cfov_tal = y[10];
-        let Clu_tal = y[11];
-        let Cre_tal = y[12];
-        let Aurine_tal = y[13];
-        let Afeces_tal = y[14];
-        let Cduodenum_tal = y[15];
+
 
         // Temporary variables (CSE)
-        let x0 = 1.0*Qki;
-        let x1 = 1.0*f_shunts;
-        let x2 = Car_tal*Qha;
-        let x3 = Cpo_tal*Qpo;
+        let x0 = Qki;
+        let x1 = f_shunts;
         let x4 = Qha + Qpo;
-        let x5 = Cve_tal*Qlu;
         let x6 = Kp_tal*fup_tal;
-        let x7 = ftissue_tal*(-1.0*Clu_plasma_tal*x6 + Clu_tal);
-        let x8 = -1.0*Clu_plasma_tal*Qlu;
-        let x9 = 1.0*Qgu;
-        let x10 = Car_tal*Qre;
-        let x11 = ftissue_tal*(-1.0*Cre_plasma_tal*x6 + Cre_tal);
-        let x12 = Cre_plasma_tal*Qre;
-        let x13 = Car_tal*f_shunting_forearm;
+        let x9 = Qgu;
         let x14 = f_shunting_forearm - 1.0;
-        let x15 = Cfo_plasma_tal*x14;
-        let x16 = 1.0*Qfo;
-        let x17 = f_shunts*x2;
+        let x16 = Qfo;
         let x18 = f_shunts - 1.0;
-        let x19 = Chv_tal*Qh;
-        let x20 = f_shunts*x3;
-        let x21 = 1.0*x18*x4;
-        let x22 = 1.0*Qpo;
-        let x23 = ftissue_tal*x6;
-        let x24 = 1.0*Qlu;
-        let x25 = 1.0*ftissue_tal;
-        let x26 = 1.0*Qre;
-        let x27 = 1.0*Qfo*x14;
-        let x28 = Qfo*f_shunting_forearm;
-        let x29 = 1.0*Qh;
-        let x30 = x25*x6;
-        let x31 = -1.0*x25;
+        let x20 = Mr_tal.powi(-1);
+        let x23 = x18*x4;
+        let x24 = Qpo;
+        let x25 = ftissue_tal*x6;
+        let x26 = Qlu;
+        let x27 = ftissue_tal;
+        let x28 = Qre;
+        let x29 = Qfo*x14;
+        let x30 = Qfo*f_shunting_forearm;
+        let x31 = Qh;
+        let x32 = x27*x6;
+        let x33 = -x27;
 
         // Jacobian-Vector Product
-        jv[0] += (-1.0*x0) * v[0];
+        jv[0] += (-x0) * v[0];
         jv[0] += (x0) * v[6];
-        jv[1] += (x21) * v[1];
-        jv[1] += (-1.0*Qha*x18) * v[6];
-        jv[1] += (-1.0*x18*x22) * v[8];
-        jv[2] += (-1.0*Qlu - 1.0*x23) * v[2];
-        jv[2] += (x24) * v[7];
-        jv[2] += (x25) * v[11];
-        jv[3] += (-1.0*x9) * v[3];
+        jv[1] += (x23) * v[1];
+        jv[1] += (-Qha*x18) * v[6];
+        jv[1] += (-x18*x24) * v[8];
+        jv[2] += (-Qlu - x25) * v[2];
+        jv[2] += (x26) * v[7];
+        jv[2] += (x27) * v[11];
+        jv[3] += (-x9) * v[3];
         jv[3] += (x9) * v[6];
-        jv[4] += (-1.0*Qre - 1.0*x23) * v[4];
-        jv[4] += (x26) * v[6];
-        jv[4] += (x25) * v[12];
-        jv[5] += (x27) * v[5];
-        jv[5] += (-1.0*x16*(2.0*f_shunting_forearm - 1.0)) * v[6];
-        jv[6] += (x24) * v[2];
-        jv[6] += (x27) * v[5];
-        jv[6] += (1.0*Qfo*x14 - 1.0*Qgu - 1.0*Qha*f_shunts + 1.0*Qha*x18 - 1.0*Qki - 1.0*Qre - 1.0*x28) * v[6];
+        jv[4] += (-Qre - x25) * v[4];
+        jv[4] += (x28) * v[6];
+        jv[4] += (x27) * v[12];
+        jv[5] += (x29) * v[5];
+        jv[5] += (-x16*(2.0*f_shunting_forearm - 1.0)) * v[6];
+        jv[6] += (x26) * v[2];
+        jv[6] += (x29) * v[5];
+        jv[6] += (Qfo*x14 - Qgu - Qha*f_shunts + Qha*x18 - Qki - Qre - x30) * v[6];
         jv[7] += (x0) * v[0];
-        jv[7] += (x26) * v[4];
-        jv[7] += (-1.0*x24) * v[7];
-        jv[7] += (x29) * v[9];
+        jv[7] += (x28) * v[4];
+        jv[7] += (-x26) * v[7];
+        jv[7] += (x31) * v[9];
         jv[7] += (x16) * v[10];
+        jv[7] += (Ki_tal*x20) * v[16];
         jv[8] += (x9) * v[3];
-        jv[8] += (-1.0*x22) * v[8];
-        jv[9] += (-1.0*x21) * v[1];
+        jv[8] += (-x24) * v[8];
+        jv[9] += (-x23) * v[1];
         jv[9] += (Qha*x1) * v[6];
         jv[9] += (Qpo*x1) * v[8];
-        jv[9] += (-1.0*x29) * v[9];
-        jv[10] += (-1.0*x27) * v[5];
-        jv[10] += (1.0*x28) * v[6];
-        jv[10] += (-1.0*x16) * v[10];
-        jv[11] += (x30) * v[2];
-        jv[11] += (x31) * v[11];
-        jv[12] += (x30) * v[4];
-        jv[12] += (x31) * v[12];
+        jv[9] += (-x31) * v[9];
+        jv[10] += (-x29) * v[5];
+        jv[10] += (x30) * v[6];
+        jv[10] += (-x16) * v[10];
+        jv[11] += (x32) * v[2];
+        jv[11] += (x33) * v[11];
+        jv[12] += (x32) * v[4];
+        jv[12] += (x33) * v[12];
+        jv[16] += (-Ki_tal) * v[16];
+    }
+
+    fn init(p: &[f64], _t: f64, y: &mut [f64]) {
+        y.copy_from_slice(&p[80..98]);
+    }
+    fn defaults() -> serde_json::Value {
+        serde_json::from_str(&get_default_parameters()).unwrap()
+    }
+
+    fn options(sim_params: &SimulationParams) -> &SimulationOptions {
+        &sim_params.options
+    }
+
+    fn options_mut(sim_params: &mut SimulationParams) -> &mut SimulationOptions {
+        &mut sim_params.options
+    }
+
+    fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+        parameter_vector(sim_params)
+    }
+
+    fn build_problem() -> BuiltProblem<'static> {
+        BuiltProblem::dense(MODEL, equations())
+    }
+
+    fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+        prepare_run(sim_params)
+    }
+}
+
+/// The run of checked parameters: the solver works in model time units, while
+/// results and checkpoints keep the caller's
+fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+    let resolved = serde_json::to_value(with_defaults(sim_params.clone())).unwrap();
+    let scale = time_scale(&sim_params);
+    let sim_params = in_model_time(sim_params);
+    let p = parameter_vector(&sim_params);
+    Ok(Run {
+        p,
+        resolved,
+        scale,
+        uptake_windows: vec![],
+        options: sim_params.options,
+    })
+}
+
+/// The model's functions for the solver; the closures capture nothing, so they
+/// are plain function pointers
+fn equations() -> Equations<'static> {
+    let eval_observables = |y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let Mr_tal = p[28];
+        let Vfov = p[41];
+        let f_shunts = p[43];
+        let f_tissue_loss = p[44];
+        let FVre = p[45];
+        let FQre = p[46];
+        let BSA = p[47];
+        let CO = p[48];
+        let Vgu = p[49];
+        let Vki = p[50];
+        let Vli = p[51];
+        let Vlu = p[52];
+        let Vve = p[53];
+        let Var = p[54];
+        let Vpo = p[55];
+        let Vhv = p[56];
+        let Vfo_plasma = p[57];
+        let Vfo_tissue = p[58];
+        let Ki_tal = p[59];
+        let Vre = p[60];
+        let QC = p[61];
+        let Vgu_plasma = p[62];
+        let Vgu_tissue = p[63];
+        let Vki_plasma = p[64];
+        let Vki_tissue = p[65];
+        let Vli_plasma = p[66];
+        let Vli_tissue = p[67];
+        let Vlu_plasma = p[68];
+        let Vlu_tissue = p[69];
+        let Vre_plasma = p[70];
+        let Vre_tissue = p[71];
+        let Qgu = p[72];
+        let Qki = p[73];
+        let Qh = p[74];
+        let Qlu = p[75];
+        let Qre = p[76];
+        let Qfo = p[77];
+        let Qpo = p[78];
+        let Qha = p[79];
+        let Cki_plasma_tal = y[0];
+        let Cli_plasma_tal = y[1];
+        let Clu_plasma_tal = y[2];
+        let Cgu_plasma_tal = y[3];
+        let Cre_plasma_tal = y[4];
+        let Cfo_plasma_tal = y[5];
+        let Car_tal = y[6];
+        let Cve_tal = y[7];
+        let Cpo_tal = y[8];
+        let Chv_tal = y[9];
+        let Cfov_tal = y[10];
+        let Aurine_tal = y[13];
+        let Afeces_tal = y[14];
+        let Afov_tal = Cfov_tal*Vfov;
+        let Xurine_tal = Aurine_tal*Mr_tal;
+        let Xfeces_tal = Afeces_tal*Mr_tal;
+        let Ave_tal = Cve_tal*Vve;
+        let Aar_tal = Car_tal*Var;
+        let Apo_tal = Cpo_tal*Vpo;
+        let Ahv_tal = Chv_tal*Vhv;
+        let Afo_plasma_tal = Cfo_plasma_tal*Vfo_plasma;
+        let Xfov_tal = Afov_tal*Mr_tal;
+        let Mfov_tal = Afov_tal*Mr_tal*Vfov.powi(-1);
+        let Agu_plasma_tal = Cgu_plasma_tal*Vgu_plasma;
+        let Aki_plasma_tal = Cki_plasma_tal*Vki_plasma;
+        let Ali_plasma_tal = Cli_plasma_tal*Vli_plasma;
+        let Alu_plasma_tal = Clu_plasma_tal*Vlu_plasma;
+        let Xve_tal = Ave_tal*Mr_tal;
+        let Mve_tal = Ave_tal*Mr_tal*Vve.powi(-1);
+        let Xar_tal = Aar_tal*Mr_tal;
+        let Mar_tal = Aar_tal*Mr_tal*Var.powi(-1);
+        let Xpo_tal = Apo_tal*Mr_tal;
+        let Mpo_tal = Apo_tal*Mr_tal*Vpo.powi(-1);
+        let Xhv_tal = Ahv_tal*Mr_tal;
+        let Mhv_tal = Ahv_tal*Mr_tal*Vhv.powi(-1);
+        let Xfo_plasma_tal = Afo_plasma_tal*Mr_tal;
+        let Mfo_plasma_tal = Afo_plasma_tal*Mr_tal*Vfo_plasma.powi(-1);
+        let Are_plasma_tal = Cre_plasma_tal*Vre_plasma;
+        let Xgu_plasma_tal = Agu_plasma_tal*Mr_tal;
+        let Mgu_plasma_tal = Agu_plasma_tal*Mr_tal*Vgu_plasma.powi(-1);
+        let Xki_plasma_tal = Aki_plasma_tal*Mr_tal;
+        let Mki_plasma_tal = Aki_plasma_tal*Mr_tal*Vki_plasma.powi(-1);
+        let Xli_plasma_tal = Ali_plasma_tal*Mr_tal;
+        let Mli_plasma_tal = Ali_plasma_tal*Mr_tal*Vli_plasma.powi(-1);
+        let Xlu_plasma_tal = Alu_plasma_tal*Mr_tal;
+        let Mlu_plasma_tal = Alu_plasma_tal*Mr_tal*Vlu_plasma.powi(-1);
+        let Xre_plasma_tal = Are_plasma_tal*Mr_tal;
+        let Mre_plasma_tal = Are_plasma_tal*Mr_tal*Vre_plasma.powi(-1);
+        vec![f_shunts, f_tissue_loss, FVre, FQre, BSA, CO, Vgu, Vki, Vli, Vlu, Vve, Var, Vpo, Vhv, Vfo_plasma, Vfo_tissue, Ki_tal, Afov_tal, Xurine_tal, Xfeces_tal, Vre, QC, Vgu_plasma, Vgu_tissue, Vki_plasma, Vki_tissue, Vli_plasma, Vli_tissue, Vlu_plasma, Vlu_tissue, Ave_tal, Aar_tal, Apo_tal, Ahv_tal, Afo_plasma_tal, Xfov_tal, Mfov_tal, Vre_plasma, Vre_tissue, Qgu, Qki, Qh, Qlu, Qre, Qfo, Agu_plasma_tal, Aki_plasma_tal, Ali_plasma_tal, Alu_plasma_tal, Xve_tal, Mve_tal, Xar_tal, Mar_tal, Xpo_tal, Mpo_tal, Xhv_tal, Mhv_tal, Xfo_plasma_tal, Mfo_plasma_tal, Are_plasma_tal, Qpo, Qha, Xgu_plasma_tal, Mgu_plasma_tal, Xki_plasma_tal, Mki_plasma_tal, Xli_plasma_tal, Mli_plasma_tal, Xlu_plasma_tal, Mlu_plasma_tal, Xre_plasma_tal, Mre_plasma_tal]
+    };
+
+    let eval_volumes = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let Vurine = p[37];
+        let Vfeces = p[38];
+        let Vfov = p[41];
+        let Vduodenum = p[42];
+        let Vve = p[53];
+        let Var = p[54];
+        let Vpo = p[55];
+        let Vhv = p[56];
+        let Vfo_plasma = p[57];
+        let Vgu_plasma = p[62];
+        let Vki_plasma = p[64];
+        let Vli_plasma = p[66];
+        let Vlu_plasma = p[68];
+        let Vlu_tissue = p[69];
+        let Vre_plasma = p[70];
+        let Vre_tissue = p[71];
+        vec![Vki_plasma, Vli_plasma, Vlu_plasma, Vgu_plasma, Vre_plasma, Vfo_plasma, Var, Vve, Vpo, Vhv, Vfov, Vlu_tissue, Vre_tissue, Vurine, Vfeces, Vduodenum, f64::NAN, f64::NAN]
     };
 
-    let init = |_y0: &diffsol::NalgebraVec<f64>, _t: f64, y: &mut diffsol::NalgebraVec<f64>| {
-        for i in 0..16 { y[i] = 0.0; }
+    let eval_mass = |y: &[f64], p: &[f64]| -> f64 {
+        let Mr_tal = p[28];
+        let Vfov = p[41];
+        let Vve = p[53];
+        let Var = p[54];
+        let Vpo = p[55];
+        let Vhv = p[56];
+        let Vfo_plasma = p[57];
+        let Vgu_plasma = p[62];
+        let Vki_plasma = p[64];
+        let Vli_plasma = p[66];
+        let Vlu_plasma = p[68];
+        let Vre_plasma = p[70];
+        y[0] * (Mr_tal*Vki_plasma) + y[1] * (Mr_tal*Vli_plasma) + y[2] * (Mr_tal*Vlu_plasma) + y[3] * (Mr_tal*Vgu_plasma) + y[4] * (Mr_tal*Vre_plasma) + y[5] * (Mr_tal*Vfo_plasma) + y[6] * (Mr_tal*Var) + y[7] * (Mr_tal*Vve) + y[8] * (Mr_tal*Vpo) + y[9] * (Mr_tal*Vhv) + y[10] * (Mr_tal*Vfov) + y[13] * (Mr_tal) + y[14] * (Mr_tal)
     };
 
-    let problem = OdeBuilder::<M>::new()
-        .rhs_implicit(rhs, jac)
-        .init(init, 16)
-        
-        .build()
-        .unwrap();
-
-    let mut solver = problem.bdf::<LS>().unwrap();
-    let mut time = Vec::new();
-
-    // Initialize result vectors
-    let mut cki_plasma_tal = Vec::new();
-    let mut cli_plasma_tal = Vec::new();
-    let mut clu_plasma_tal = Vec::new();
-    let mut cgu_plasma_tal = Vec::new();
-    let mut cre_plasma_tal = Vec::new();
-    let mut cfo_plasma_tal = Vec::new();
-    let mut car_tal = Vec::new();
-    let mut cve_tal = Vec::new();
-    let mut cpo_tal = Vec::new();
-    let mut chv_tal = Vec::new();
-    let mut cfov_tal = Vec::new();
-    let mut clu_tal = Vec::new();
-    let mut cre_tal = Vec::new();
-    let mut aurine_tal = Vec::new();
-    let mut afeces_tal = Vec::new();
-    let mut cduodenum_tal = Vec::new();
-
-    cki_plasma_tal.push(solver.state().y[0]);
-    cli_plasma_tal.push(solver.state().y[1]);
-    clu_plasma_tal.push(solver.state().y[2]);
-    cgu_plasma_tal.push(solver.state().y[3]);
-    cre_plasma_tal.push(solver.state().y[4]);
-    cfo_plasma_tal.push(solver.state().y[5]);
-    car_tal.push(solver.state().y[6]);
-    cve_tal.push(solver.state().y[7]);
-    cpo_tal.push(solver.state().y[8]);
-    chv_tal.push(solver.state().y[9]);
-    cfov_tal.push(solver.state().y[10]);
-    clu_tal.push(solver.state().y[11]);
-    cre_tal.push(solver.state().y[12]);
-    aurine_tal.push(solver.state().y[13]);
-    afeces_tal.push(solver.state().y[14]);
-    cduodenum_tal.push(solver.state().y[15]);
-    time.push(0.0);
-
-    let final_time = sim_params.final_time.unwrap_or(24.0);
-    solver.set_stop_time(final_time).unwrap();
-    loop {
-        match solver.step() {
-            Ok(OdeSolverStopReason::InternalTimestep) => {
-            cki_plasma_tal.push(solver.state().y[0]);
-            cli_plasma_tal.push(solver.state().y[1]);
-            clu_plasma_tal.push(solver.state().y[2]);
-            cgu_plasma_tal.push(solver.state().y[3]);
-            cre_plasma_tal.push(solver.state().y[4]);
-            cfo_plasma_tal.push(solver.state().y[5]);
-            car_tal.push(solver.state().y[6]);
-            cve_tal.push(solver.state().y[7]);
-            cpo_tal.push(solver.state().y[8]);
-            chv_tal.push(solver.state().y[9]);
-            cfov_tal.push(solver.state().y[10]);
-            clu_tal.push(solver.state().y[11]);
-            cre_tal.push(solver.state().y[12]);
-            aurine_tal.push(solver.state().y[13]);
-            afeces_tal.push(solver.state().y[14]);
-            cduodenum_tal.push(solver.state().y[15]);
-                time.push(solver.state().t);
-            },
-            Ok(OdeSolverStopReason::TstopReached) => break,
-            Err(_) => panic!("Solver Error"),
+    Equations {
+        rhs: TalinololModel::rhs,
+        jac: TalinololModel::jac,
+        init: TalinololModel::init,
+        roots: no_roots,
+        triggers: no_triggers,
+        apply_events: no_events,
+        observables: eval_observables,
+        volumes: eval_volumes,
+        mass: eval_mass,
+        jac_matrix: None,
+    }
+}
+
+/// Solver stats of the last run of the last simulation call (JSON, null if it never started)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_last_run_stats() -> String {
+    LAST_RUN_STATS.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_simulation_at_times(params: &str, times: &[f64]) -> String {
+    wasm_pk_core::run_simulation_at_times::<TalinololModel>(params, times)
+}
+
+/// Run a simulation and return `time,<species...>` CSV, one row per stored time
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_simulation_csv(params: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_csv::<TalinololModel>(params)
+}
+
+/// Cmax, Tmax, AUC, AUC(0-inf), terminal half-life and trough of one species (JSON)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn compute_pk_metrics(result_json: &str, species: &str) -> Result<String, String> {
+    wasm_pk_core::compute_pk_metrics(result_json, species)
+}
+
+/// AUC of one species over each `[t1, t2]` window (JSON array, same order)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn compute_partial_auc(result_json: &str, species: &str, windows: &str) -> Result<String, String> {
+    wasm_pk_core::compute_partial_auc(result_json, species, windows)
+}
+
+/// Run the model once per value of one parameter, e.g. body weight from 20 to 100 kg
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_parameter_sweep(base_params: &str, sweep: &str) -> Result<String, String> {
+    wasm_pk_core::run_parameter_sweep::<TalinololModel>(base_params, sweep)
+}
+
+/// Continue a run_simulation result from its last recorded state for `additional_time`,
+/// with `new_params` merged over the parameters that run used. Its doses, output_times
+/// and uptake windows are not repeated; times in `new_params` count from the
+/// continuation start. The segment is returned on the combined time axis, after
+/// the earlier trajectory when `concatenate` is set.
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn continue_simulation(previous_result: &str, new_params: &str, additional_time: f64, concatenate: bool) -> Result<String, String> {
+    wasm_pk_core::continue_simulation::<TalinololModel>(previous_result, new_params, additional_time, concatenate)
+}
+
+/// Checkpoint of the last run of the last simulation call (JSON, null if it never
+/// started or its final state is not finite)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn export_checkpoint() -> String {
+    LAST_CHECKPOINT.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+/// Resume an exported checkpoint up to the absolute `final_time` in `params`, which are
+/// merged over the checkpoint's parameters. Its pending doses are applied unless
+/// `params` gives new doses. The result and the new checkpoint use absolute time.
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_from_checkpoint(checkpoint: &str, params: &str) -> Result<String, String> {
+    wasm_pk_core::run_from_checkpoint::<TalinololModel>(checkpoint, params)
+}
+
+const SPECIES_IDS: &[&str] = &["Cki_plasma_tal", "Cli_plasma_tal", "Clu_plasma_tal", "Cgu_plasma_tal", "Cre_plasma_tal", "Cfo_plasma_tal", "Car_tal", "Cve_tal", "Cpo_tal", "Chv_tal", "Cfov_tal", "Clu_tal", "Cre_tal", "Aurine_tal", "Afeces_tal", "Cduodenum_tal", "IVDOSE_tal", "cum_dose_tal"];
+
+/// Parameters read by the derivative of each state, for failure hints
+const STATE_PARAMETERS: [&[&str]; 18] = [&["Qki"], &["Qha", "Qpo", "f_shunts"], &["Kp_tal", "Qlu", "ftissue_tal", "fup_tal"], &["Qgu"], &["Kp_tal", "Qre", "ftissue_tal", "fup_tal"], &["Qfo", "f_shunting_forearm"], &["Qfo", "Qgu", "Qha", "Qki", "Qlu", "Qre", "f_shunting_forearm", "f_shunts"], &["Ki_tal", "Mr_tal", "Qfo", "Qh", "Qki", "Qlu", "Qre"], &["Qgu", "Qpo", "f_shunts"], &["Qh", "Qha", "Qpo", "f_shunts"], &["Qfo", "f_shunting_forearm"], &["Kp_tal", "ftissue_tal", "fup_tal"], &["Kp_tal", "ftissue_tal", "fup_tal"], &[], &[], &[], &["Ki_tal", "Ri_tal"], &["Ri_tal"]];
+
+/// SBML ids of the assignment-rule observables, in evaluation order
+const OBSERVABLE_IDS: [&str; 72] = ["f_shunts", "f_tissue_loss", "FVre", "FQre", "BSA", "CO", "Vgu", "Vki", "Vli", "Vlu", "Vve", "Var", "Vpo", "Vhv", "Vfo_plasma", "Vfo_tissue", "Ki_tal", "Afov_tal", "Xurine_tal", "Xfeces_tal", "Vre", "QC", "Vgu_plasma", "Vgu_tissue", "Vki_plasma", "Vki_tissue", "Vli_plasma", "Vli_tissue", "Vlu_plasma", "Vlu_tissue", "Ave_tal", "Aar_tal", "Apo_tal", "Ahv_tal", "Afo_plasma_tal", "Xfov_tal", "Mfov_tal", "Vre_plasma", "Vre_tissue", "Qgu", "Qki", "Qh", "Qlu", "Qre", "Qfo", "Agu_plasma_tal", "Aki_plasma_tal", "Ali_plasma_tal", "Alu_plasma_tal", "Xve_tal", "Mve_tal", "Xar_tal", "Mar_tal", "Xpo_tal", "Mpo_tal", "Xhv_tal", "Mhv_tal", "Xfo_plasma_tal", "Mfo_plasma_tal", "Are_plasma_tal", "Qpo", "Qha", "Xgu_plasma_tal", "Mgu_plasma_tal", "Xki_plasma_tal", "Mki_plasma_tal", "Xli_plasma_tal", "Mli_plasma_tal", "Xlu_plasma_tal", "Mlu_plasma_tal", "Xre_plasma_tal", "Mre_plasma_tal"];
+
+/// What the shared solver loop and option checks need to know about the model
+const MODEL: ModelInfo = ModelInfo {
+    species: SPECIES_IDS,
+    state_parameters: &STATE_PARAMETERS,
+    event_ids: &[],
+    event_targets: &[],
+    event_initial_values: &[],
+    event_persistent: &[],
+    observables: &OBSERVABLE_IDS,
+    species_is_amount: &SPECIES_IS_AMOUNT,
+    species_volumes: &SPECIES_VOLUMES,
+    series_units: &SERIES_UNITS,
+    time_seconds: MODEL_TIME_SECONDS,
+    time_unit: MODEL_TIME_UNIT,
+    parameters: PARAMETER_ORDER,
+    parameter_checks: PARAMETER_CHECKS,
+    algebraic: &[],
+};
+
+/// Run a simulation and return the trajectory as little-endian f64 bytes
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_simulation_binary(params: &str) -> Result<Vec<u8>, String> {
+    wasm_pk_core::run_simulation_binary::<TalinololModel>(params)
+}
+
+/// Describe the run_simulation_binary layout and its species order (JSON)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_binary_layout() -> String {
+    wasm_pk_core::get_binary_layout::<TalinololModel>()
+}
+
+/// Decode run_simulation_binary output into times and named species series
+pub fn decode_binary_result(bytes: &[u8]) -> Result<(Vec<f64>, OrderedSeries), String> {
+    wasm_pk_core::decode_binary_result::<TalinololModel>(bytes)
+}
+
+/// One observable of get_model_metadata
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct ObservableInfo {
+    id: &'static str,
+    units: Option<&'static str>,
+}
+
+/// SBML document the model was generated from
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct SbmlSource {
+    id: Option<&'static str>,
+    name: Option<&'static str>,
+    level: Option<u32>,
+    version: Option<u32>,
+    /// SHA-256 of the source file
+    sha256: Option<&'static str>,
+}
+
+/// Generator that wrote this module
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct GeneratorInfo {
+    name: &'static str,
+    version: Option<&'static str>,
+    /// ISO 8601 UTC time of generation
+    generated_at: Option<&'static str>,
+}
+
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct ModelMetadata {
+    model_id: &'static str,
+    num_species: usize,
+    num_parameters: usize,
+    time_units: &'static str,
+    substance_units: &'static str,
+    volume_units: &'static str,
+    sbml: SbmlSource,
+    generator: GeneratorInfo,
+    diffsol_version: &'static str,
+    /// Optional features compiled in: "events", "sensitivities", "embedded_sbml"
+    features: &'static [&'static str],
+    observables: &'static [ObservableInfo],
+}
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_model_metadata() -> String {
+    let metadata = ModelMetadata {
+        model_id: "talinolol_model",
+        num_species: 18,
+        num_parameters: 43,
+        time_units: "HR",
+        substance_units: "MilliMOL",
+        volume_units: "L",
+        sbml: SbmlSource { id: Some("talinolol_body"), name: Some("Talinolol (TAL) body model"), level: Some(3), version: Some(2), sha256: Some("f4069a5c3201e605003ca5fcaff59629a306e3177381fe4a579628dcd2d2a63b") },
+        generator: GeneratorInfo { name: "sbml_rust_generator", version: Some("1.0.0"), generated_at: Some("2026-10-17T00:28:17Z") },
+        diffsol_version: "0.6.3",
+        features: &["embedded_sbml"],
+        observables: &[
+            ObservableInfo { id: "f_shunts", units: Some("dimensionless") },
+            ObservableInfo { id: "f_tissue_loss", units: Some("dimensionless") },
+            ObservableInfo { id: "FVre", units: Some("l_per_kg") },
+            ObservableInfo { id: "FQre", units: Some("dimensionless") },
+            ObservableInfo { id: "BSA", units: Some("m2") },
+            ObservableInfo { id: "CO", units: Some("ml_per_s") },
+            ObservableInfo { id: "Vgu", units: None },
+            ObservableInfo { id: "Vki", units: None },
+            ObservableInfo { id: "Vli", units: None },
+            ObservableInfo { id: "Vlu", units: None },
+            ObservableInfo { id: "Vve", units: None },
+            ObservableInfo { id: "Var", units: None },
+            ObservableInfo { id: "Vpo", units: None },
+            ObservableInfo { id: "Vhv", units: None },
+            ObservableInfo { id: "Vfo_plasma", units: None },
+            ObservableInfo { id: "Vfo_tissue", units: None },
+            ObservableInfo { id: "Ki_tal", units: Some("per_min") },
+            ObservableInfo { id: "Afov_tal", units: Some("mmole") },
+            ObservableInfo { id: "Xurine_tal", units: Some("mg") },
+            ObservableInfo { id: "Xfeces_tal", units: Some("mg") },
+            ObservableInfo { id: "Vre", units: None },
+            ObservableInfo { id: "QC", units: Some("l_per_min") },
+            ObservableInfo { id: "Vgu_plasma", units: None },
+            ObservableInfo { id: "Vgu_tissue", units: None },
+            ObservableInfo { id: "Vki_plasma", units: None },
+            ObservableInfo { id: "Vki_tissue", units: None },
+            ObservableInfo { id: "Vli_plasma", units: None },
+            ObservableInfo { id: "Vli_tissue", units: None },
+            ObservableInfo { id: "Vlu_plasma", units: None },
+            ObservableInfo { id: "Vlu_tissue", units: None },
+            ObservableInfo { id: "Ave_tal", units: Some("mmole") },
+            ObservableInfo { id: "Aar_tal", units: Some("mmole") },
+            ObservableInfo { id: "Apo_tal", units: Some("mmole") },
+            ObservableInfo { id: "Ahv_tal", units: Some("mmole") },
+            ObservableInfo { id: "Afo_plasma_tal", units: Some("mmole") },
+            ObservableInfo { id: "Xfov_tal", units: Some("mg") },
+            ObservableInfo { id: "Mfov_tal", units: Some("mg_per_l") },
+            ObservableInfo { id: "Vre_plasma", units: None },
+            ObservableInfo { id: "Vre_tissue", units: None },
+            ObservableInfo { id: "Qgu", units: Some("l_per_min") },
+            ObservableInfo { id: "Qki", units: Some("l_per_min") },
+            ObservableInfo { id: "Qh", units: Some("l_per_min") },
+            ObservableInfo { id: "Qlu", units: Some("l_per_min") },
+            ObservableInfo { id: "Qre", units: Some("l_per_min") },
+            ObservableInfo { id: "Qfo", units: Some("l_per_min") },
+            ObservableInfo { id: "Agu_plasma_tal", units: Some("mmole") },
+            ObservableInfo { id: "Aki_plasma_tal", units: Some("mmole") },
+            ObservableInfo { id: "Ali_plasma_tal", units: Some("mmole") },
+            ObservableInfo { id: "Alu_plasma_tal", units: Some("mmole") },
+            ObservableInfo { id: "Xve_tal", units: Some("mg") },
+            ObservableInfo { id: "Mve_tal", units: Some("mg_per_l") },
+            ObservableInfo { id: "Xar_tal", units: Some("mg") },
+            ObservableInfo { id: "Mar_tal", units: Some("mg_per_l") },
+            ObservableInfo { id: "Xpo_tal", units: Some("mg") },
+            ObservableInfo { id: "Mpo_tal", units: Some("mg_per_l") },
+            ObservableInfo { id: "Xhv_tal", units: Some("mg") },
+            ObservableInfo { id: "Mhv_tal", units: Some("mg_per_l") },
+            ObservableInfo { id: "Xfo_plasma_tal", units: Some("mg") },
+            ObservableInfo { id: "Mfo_plasma_tal", units: Some("mg_per_l") },
+            ObservableInfo { id: "Are_plasma_tal", units: Some("mmole") },
+            ObservableInfo { id: "Qpo", units: Some("l_per_min") },
+            ObservableInfo { id: "Qha", units: Some("l_per_min") },
+            ObservableInfo { id: "Xgu_plasma_tal", units: Some("mg") },
+            ObservableInfo { id: "Mgu_plasma_tal", units: Some("mg_per_l") },
+            ObservableInfo { id: "Xki_plasma_tal", units: Some("mg") },
+            ObservableInfo { id: "Mki_plasma_tal", units: Some("mg_per_l") },
+            ObservableInfo { id: "Xli_plasma_tal", units: Some("mg") },
+            ObservableInfo { id: "Mli_plasma_tal", units: Some("mg_per_l") },
+            ObservableInfo { id: "Xlu_plasma_tal", units: Some("mg") },
+            ObservableInfo { id: "Mlu_plasma_tal", units: Some("mg_per_l") },
+            ObservableInfo { id: "Xre_plasma_tal", units: Some("mg") },
+            ObservableInfo { id: "Mre_plasma_tal", units: Some("mg_per_l") },
+        ],
+    };
+    serde_json::to_string(&metadata).unwrap()
+}
+
+/// One entry of get_parameters_info
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct ParameterInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    units: Option<&'static str>,
+    /// SBML notes as plain text
+    description: Option<&'static str>,
+    default_value: f64,
+    required: bool,
+    is_compartment: bool,
+    /// Soft bounds for input forms; validate_parameters has the hard checks
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+#[cfg(not(feature = "minimal"))]
+const PARAMETER_INFO: &[ParameterInfo] = &[
+    ParameterInfo { id: "BW", name: Some("body weight [kg]"), units: Some("kg"), description: None, default_value: 75.0, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "HEIGHT", name: Some("height [cm]"), units: Some("cm"), description: None, default_value: 170.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "HR", name: Some("heart rate [1/min]"), units: Some("per_min"), description: None, default_value: 70.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "HRrest", name: Some("heart rate [1/min]"), units: Some("per_min"), description: None, default_value: 70.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "COBW", name: Some("cardiac output per bodyweight [ml/s/kg]"), units: Some("ml_per_s_kg"), description: None, default_value: 1.548, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "COHRI", name: Some("increase of cardiac output per heartbeat [ml/min*min]"), units: Some("ml"), description: None, default_value: 150.0, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "Fblood", name: Some("blood fraction of organ volume"), units: Some("dimensionless"), description: None, default_value: 0.02, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "HCT", name: Some("hematocrit"), units: Some("dimensionless"), description: None, default_value: 0.51, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "f_shunting_forearm", name: Some("shunting in forearm"), units: Some("dimensionless"), description: None, default_value: 0.28, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FVgu", name: Some("gut fractional tissue volume"), units: Some("l_per_kg"), description: None, default_value: 0.0171, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FVki", name: Some("kidney fractional tissue volume"), units: Some("l_per_kg"), description: None, default_value: 0.0044, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FVli", name: Some("liver fractional tissue volume"), units: Some("l_per_kg"), description: None, default_value: 0.021, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FVlu", name: Some("lung fractional tissue volume"), units: Some("l_per_kg"), description: None, default_value: 0.0076, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FVfo", name: Some("liver fractional tissue volume"), units: Some("l_per_kg"), description: Some("[Huang2020:Supp] Forearm blood flow 0.6430 L/hr; Physical Volume 0.3380 L"), default_value: 0.00482857142857143, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FVve", name: Some("venous fractional tissue volume"), units: Some("l_per_kg"), description: None, default_value: 0.0514, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FVar", name: Some("arterial fractional tissue volume"), units: Some("l_per_kg"), description: None, default_value: 0.0257, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FVpo", name: Some("portal fractional tissue volume"), units: Some("l_per_kg"), description: None, default_value: 0.001, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FVhv", name: Some("hepatic venous fractional tissue volume"), units: Some("l_per_kg"), description: None, default_value: 0.001, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FVfov", name: Some("fore arm venous fractional tissue volume"), units: Some("l_per_kg"), description: None, default_value: 0.001, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FQgu", name: Some("gut fractional tissue blood flow"), units: Some("dimensionless"), description: Some("Assumed FQgu is the sum of FQgu + FQsp + FQpa for correct portal blood flow. with FQgu=0.146, FQsp=0.017, FQpa=0.017"), default_value: 0.18, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FQki", name: Some("kidney fractional tissue blood flow"), units: Some("dimensionless"), description: None, default_value: 0.19, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FQh", name: Some("hepatic (venous side) fractional tissue blood flow"), units: Some("dimensionless"), description: None, default_value: 0.215, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FQlu", name: Some("lung fractional tissue blood flow"), units: Some("dimensionless"), description: None, default_value: 1.0, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "FQfo", name: Some("fore arm fractional tissue blood flow"), units: Some("dimensionless"), description: Some("[deprecated info][Huang2020:Supp] Forearm blood flow 0.6430 L/hr; Physical Volume 0.3380 L, Lung blood flow 300 L/hrCardiac output in humans is generally 5-6 L/min in an at-rest to more than 35 L/min in elite athletes during exercise.[95 ml/min in median cubital vein] https://bpgmobile.rnao.ca/sites/default/files/Appendix%20C%20Vein%20Anatomy.pdf"), default_value: 0.0146153846153846, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "conversion_min_per_day", name: Some("Conversion factor min to hours"), units: Some("min_per_day"), description: None, default_value: 1440.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "f_cirrhosis", name: Some("severity of cirrhosis [0, 0.95]"), units: Some("dimensionless"), description: None, default_value: 0.0, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "PODOSE_tal", name: Some("oral dose tal [mg]"), units: Some("mg"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Ka_dis_tal", name: Some("Ka_dis [1/hr] dissolution tal"), units: Some("per_hr"), description: None, default_value: 0.681894676931315, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Mr_tal", name: Some("Molecular weight tal [g/mole]"), units: Some("g_per_mole"), description: None, default_value: 363.495, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "fup_tal", name: Some("fraction unbound in plasma tal"), units: Some("dimensionless"), description: None, default_value: 0.4, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "ftissue_tal", name: Some("tissue distribution tal"), units: Some("l_per_min"), description: None, default_value: 0.641324628334905, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "Kp_tal", name: Some("tissue/plasma partition coefficient tal"), units: Some("dimensionless"), description: None, default_value: 6.62140199045977, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "IVDOSE_tal", name: Some("IV bolus dose tal [mg]"), units: Some("mg"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "ti_tal", name: Some("injection time tal [s]"), units: Some("second"), description: None, default_value: 10.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Ri_tal", name: Some("Ri [mg/min] rate of infusion tal"), units: Some("mg_per_min"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "cum_dose_tal", name: Some("Cumulative dose due to infusion tal"), units: Some("mg"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "cum_dose_intestine_tal", name: Some("Cumulative dose due to infusion intestine tal"), units: Some("mg"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Vurine", name: Some("urine"), units: Some("litre"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+    ParameterInfo { id: "Vfeces", name: Some("feces"), units: Some("litre"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+    ParameterInfo { id: "Vstomach", name: Some("stomach"), units: Some("litre"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+    ParameterInfo { id: "Vfo", name: Some("forearm"), units: Some("litre"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+    ParameterInfo { id: "Vfov", name: Some("forearm venous plasma"), units: Some("litre"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+    ParameterInfo { id: "Vduodenum", name: Some("small intestine duodenum D"), units: Some("litre"), description: None, default_value: 0.322563025707332, required: true, is_compartment: true, min: Some(0.0), max: None },
+];
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_parameters_info() -> String {
+    serde_json::to_string(PARAMETER_INFO).unwrap()
+}
+
+/// One entry of get_species_info
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct SpeciesInfo {
+    id: &'static str,
+    initial_amount: f64,
+    #[serde(rename = "override")]
+    override_field: &'static str,
+    units: Option<&'static str>,
+    compartment: Option<&'static str>,
+    compartment_volume: Option<f64>,
+    /// False when the SBML value is a concentration (hasOnlySubstanceUnits=false)
+    is_amount: bool,
+    is_boundary: bool,
+}
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_species_info() -> String {
+    let p = parameter_vector(&TalinololModel::default_params());
+    let species: &[SpeciesInfo] = &[
+        SpeciesInfo { id: "Cki_plasma_tal", initial_amount: 0.0, override_field: "init_Cki_plasma_tal", units: Some("mmole/litre"), compartment: Some("Vki_plasma"), compartment_volume: Some(p[64]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Cli_plasma_tal", initial_amount: 0.0, override_field: "init_Cli_plasma_tal", units: Some("mmole/litre"), compartment: Some("Vli_plasma"), compartment_volume: Some(p[66]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Clu_plasma_tal", initial_amount: 0.0, override_field: "init_Clu_plasma_tal", units: Some("mmole/litre"), compartment: Some("Vlu_plasma"), compartment_volume: Some(p[68]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Cgu_plasma_tal", initial_amount: 0.0, override_field: "init_Cgu_plasma_tal", units: Some("mmole/litre"), compartment: Some("Vgu_plasma"), compartment_volume: Some(p[62]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Cre_plasma_tal", initial_amount: 0.0, override_field: "init_Cre_plasma_tal", units: Some("mmole/litre"), compartment: Some("Vre_plasma"), compartment_volume: Some(p[70]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Cfo_plasma_tal", initial_amount: 0.0, override_field: "init_Cfo_plasma_tal", units: Some("mmole/litre"), compartment: Some("Vfo_plasma"), compartment_volume: Some(p[57]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Car_tal", initial_amount: 0.0, override_field: "init_Car_tal", units: Some("mmole/litre"), compartment: Some("Var"), compartment_volume: Some(p[54]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Cve_tal", initial_amount: 0.0, override_field: "init_Cve_tal", units: Some("mmole/litre"), compartment: Some("Vve"), compartment_volume: Some(p[53]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Cpo_tal", initial_amount: 0.0, override_field: "init_Cpo_tal", units: Some("mmole/litre"), compartment: Some("Vpo"), compartment_volume: Some(p[55]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Chv_tal", initial_amount: 0.0, override_field: "init_Chv_tal", units: Some("mmole/litre"), compartment: Some("Vhv"), compartment_volume: Some(p[56]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Cfov_tal", initial_amount: 0.0, override_field: "init_Cfov_tal", units: Some("mmole/litre"), compartment: Some("Vfov"), compartment_volume: Some(p[41]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Clu_tal", initial_amount: 0.0, override_field: "init_Clu_tal", units: Some("mmole/litre"), compartment: Some("Vlu_tissue"), compartment_volume: Some(p[69]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Cre_tal", initial_amount: 0.0, override_field: "init_Cre_tal", units: Some("mmole/litre"), compartment: Some("Vre_tissue"), compartment_volume: Some(p[71]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Aurine_tal", initial_amount: 0.0, override_field: "init_Aurine_tal", units: Some("mmole"), compartment: Some("Vurine"), compartment_volume: Some(p[37]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "Afeces_tal", initial_amount: 0.0, override_field: "init_Afeces_tal", units: Some("mmole"), compartment: Some("Vfeces"), compartment_volume: Some(p[38]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "Cduodenum_tal", initial_amount: 0.0, override_field: "init_Cduodenum_tal", units: Some("mmole/litre"), compartment: Some("Vduodenum"), compartment_volume: Some(p[42]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "IVDOSE_tal", initial_amount: 0.0, override_field: "init_IVDOSE_tal", units: Some("mg"), compartment: None, compartment_volume: None, is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "cum_dose_tal", initial_amount: 0.0, override_field: "init_cum_dose_tal", units: Some("mg"), compartment: None, compartment_volume: None, is_amount: true, is_boundary: false },
+    ];
+    serde_json::to_string(&species).unwrap()
+}
+
+/// Whether each species' values are amounts rather than concentrations
+const SPECIES_IS_AMOUNT: [bool; 18] = [false, false, false, false, false, false, false, false, false, false, false, false, false, true, true, false, true, true];
+/// Compartment converting each species between amount and concentration, or why none does
+const SPECIES_VOLUMES: [Result<&str, &str>; 18] = [Ok("Vki_plasma"), Ok("Vli_plasma"), Ok("Vlu_plasma"), Ok("Vgu_plasma"), Ok("Vre_plasma"), Ok("Vfo_plasma"), Ok("Var"), Ok("Vve"), Ok("Vpo"), Ok("Vhv"), Ok("Vfov"), Ok("Vlu_tissue"), Ok("Vre_tissue"), Ok("Vurine"), Ok("Vfeces"), Ok("Vduodenum"), Err("no compartment"), Err("no compartment")];
+
+/// Unit of each species and observable series; None where the SBML gives none
+const SERIES_UNITS: [(&str, Option<&str>); 90] = [
+    ("Cki_plasma_tal", Some("mmole/litre")),
+    ("Cli_plasma_tal", Some("mmole/litre")),
+    ("Clu_plasma_tal", Some("mmole/litre")),
+    ("Cgu_plasma_tal", Some("mmole/litre")),
+    ("Cre_plasma_tal", Some("mmole/litre")),
+    ("Cfo_plasma_tal", Some("mmole/litre")),
+    ("Car_tal", Some("mmole/litre")),
+    ("Cve_tal", Some("mmole/litre")),
+    ("Cpo_tal", Some("mmole/litre")),
+    ("Chv_tal", Some("mmole/litre")),
+    ("Cfov_tal", Some("mmole/litre")),
+    ("Clu_tal", Some("mmole/litre")),
+    ("Cre_tal", Some("mmole/litre")),
+    ("Aurine_tal", Some("mmole")),
+    ("Afeces_tal", Some("mmole")),
+    ("Cduodenum_tal", Some("mmole/litre")),
+    ("IVDOSE_tal", Some("mg")),
+    ("cum_dose_tal", Some("mg")),
+    ("f_shunts", Some("dimensionless")),
+    ("f_tissue_loss", Some("dimensionless")),
+    ("FVre", Some("l_per_kg")),
+    ("FQre", Some("dimensionless")),
+    ("BSA", Some("m2")),
+    ("CO", Some("ml_per_s")),
+    ("Vgu", None),
+    ("Vki", None),
+    ("Vli", None),
+    ("Vlu", None),
+    ("Vve", None),
+    ("Var", None),
+    ("Vpo", None),
+    ("Vhv", None),
+    ("Vfo_plasma", None),
+    ("Vfo_tissue", None),
+    ("Ki_tal", Some("per_min")),
+    ("Afov_tal", Some("mmole")),
+    ("Xurine_tal", Some("mg")),
+    ("Xfeces_tal", Some("mg")),
+    ("Vre", None),
+    ("QC", Some("l_per_min")),
+    ("Vgu_plasma", None),
+    ("Vgu_tissue", None),
+    ("Vki_plasma", None),
+    ("Vki_tissue", None),
+    ("Vli_plasma", None),
+    ("Vli_tissue", None),
+    ("Vlu_plasma", None),
+    ("Vlu_tissue", None),
+    ("Ave_tal", Some("mmole")),
+    ("Aar_tal", Some("mmole")),
+    ("Apo_tal", Some("mmole")),
+    ("Ahv_tal", Some("mmole")),
+    ("Afo_plasma_tal", Some("mmole")),
+    ("Xfov_tal", Some("mg")),
+    ("Mfov_tal", Some("mg_per_l")),
+    ("Vre_plasma", None),
+    ("Vre_tissue", None),
+    ("Qgu", Some("l_per_min")),
+    ("Qki", Some("l_per_min")),
+    ("Qh", Some("l_per_min")),
+    ("Qlu", Some("l_per_min")),
+    ("Qre", Some("l_per_min")),
+    ("Qfo", Some("l_per_min")),
+    ("Agu_plasma_tal", Some("mmole")),
+    ("Aki_plasma_tal", Some("mmole")),
+    ("Ali_plasma_tal", Some("mmole")),
+    ("Alu_plasma_tal", Some("mmole")),
+    ("Xve_tal", Some("mg")),
+    ("Mve_tal", Some("mg_per_l")),
+    ("Xar_tal", Some("mg")),
+    ("Mar_tal", Some("mg_per_l")),
+    ("Xpo_tal", Some("mg")),
+    ("Mpo_tal", Some("mg_per_l")),
+    ("Xhv_tal", Some("mg")),
+    ("Mhv_tal", Some("mg_per_l")),
+    ("Xfo_plasma_tal", Some("mg")),
+    ("Mfo_plasma_tal", Some("mg_per_l")),
+    ("Are_plasma_tal", Some("mmole")),
+    ("Qpo", Some("l_per_min")),
+    ("Qha", Some("l_per_min")),
+    ("Xgu_plasma_tal", Some("mg")),
+    ("Mgu_plasma_tal", Some("mg_per_l")),
+    ("Xki_plasma_tal", Some("mg")),
+    ("Mki_plasma_tal", Some("mg_per_l")),
+    ("Xli_plasma_tal", Some("mg")),
+    ("Mli_plasma_tal", Some("mg_per_l")),
+    ("Xlu_plasma_tal", Some("mg")),
+    ("Mlu_plasma_tal", Some("mg_per_l")),
+    ("Xre_plasma_tal", Some("mg")),
+    ("Mre_plasma_tal", Some("mg_per_l")),
+];
+
+/// One entry of get_compartments_info
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct CompartmentInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    /// Size at the default parameters; None when it changes during a run
+    size: Option<f64>,
+    /// Rule or initial assignment computing the size, e.g. "BM*scVLiver"
+    formula: Option<&'static str>,
+    units: Option<&'static str>,
+    species: &'static [&'static str],
+}
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_compartments_info() -> String {
+    let p = parameter_vector(&TalinololModel::default_params());
+    let compartments: &[CompartmentInfo] = &[
+        CompartmentInfo { id: "Vre", name: Some("rest of body"), size: Some(p[60]), formula: Some("BW*FVre"), units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vgu", name: Some("gut"), size: Some(p[49]), formula: Some("BW*FVgu"), units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vki", name: Some("kidney"), size: Some(p[50]), formula: Some("BW*FVki"), units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vli", name: Some("liver"), size: Some(p[51]), formula: Some("BW*FVli"), units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vlu", name: Some("lung"), size: Some(p[52]), formula: Some("BW*FVlu"), units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vve", name: Some("venous blood"), size: Some(p[53]), formula: Some("-BW*FVve*Fblood*(-FVar - FVve + 1)/(FVar + FVve) + BW*FVve"), units: Some("litre"), species: &["Cve_tal"] },
+        CompartmentInfo { id: "Var", name: Some("arterial blood"), size: Some(p[54]), formula: Some("-BW*FVar*Fblood*(-FVar - FVve + 1)/(FVar + FVve) + BW*FVar"), units: Some("litre"), species: &["Car_tal"] },
+        CompartmentInfo { id: "Vurine", name: Some("urine"), size: Some(p[37]), formula: None, units: Some("litre"), species: &["Aurine_tal"] },
+        CompartmentInfo { id: "Vfeces", name: Some("feces"), size: Some(p[38]), formula: None, units: Some("litre"), species: &["Afeces_tal"] },
+        CompartmentInfo { id: "Vstomach", name: Some("stomach"), size: Some(p[39]), formula: None, units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vpo", name: Some("portal plasma"), size: Some(p[55]), formula: Some("(1 - HCT)*(-BW*FVpo*Fblood*(-FVar - FVfo - FVhv - FVpo - FVve + 1)/(FVar + FVfo + FVhv + FVpo + FVve) + BW*FVpo)"), units: Some("litre"), species: &["Cpo_tal"] },
+        CompartmentInfo { id: "Vhv", name: Some("hepatic venous plasma"), size: Some(p[56]), formula: Some("(1 - HCT)*(-BW*FVhv*Fblood*(-FVar - FVfo - FVhv - FVpo - FVve + 1)/(FVar + FVfo + FVhv + FVpo + FVve) + BW*FVhv)"), units: Some("litre"), species: &["Chv_tal"] },
+        CompartmentInfo { id: "Vfo", name: Some("forearm"), size: Some(p[40]), formula: None, units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vfov", name: Some("forearm venous plasma"), size: Some(p[41]), formula: None, units: Some("litre"), species: &["Cfov_tal"] },
+        CompartmentInfo { id: "Vki_tissue", name: Some("ki tissue"), size: Some(p[65]), formula: Some("Vki*(1 - Fblood)"), units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vki_plasma", name: Some("ki plasma"), size: Some(p[64]), formula: Some("Fblood*Vki*(1 - HCT)"), units: Some("litre"), species: &["Cki_plasma_tal"] },
+        CompartmentInfo { id: "Vli_tissue", name: Some("li tissue"), size: Some(p[67]), formula: Some("Vli*(1 - Fblood)*(1 - f_tissue_loss)"), units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vli_plasma", name: Some("li plasma"), size: Some(p[66]), formula: Some("Fblood*Vli*(1 - HCT)"), units: Some("litre"), species: &["Cli_plasma_tal"] },
+        CompartmentInfo { id: "Vlu_tissue", name: Some("lu tissue"), size: Some(p[69]), formula: Some("Vlu*(1 - Fblood)"), units: Some("litre"), species: &["Clu_tal"] },
+        CompartmentInfo { id: "Vlu_plasma", name: Some("lu plasma"), size: Some(p[68]), formula: Some("Fblood*Vlu*(1 - HCT)"), units: Some("litre"), species: &["Clu_plasma_tal"] },
+        CompartmentInfo { id: "Vgu_tissue", name: Some("gu tissue"), size: Some(p[63]), formula: Some("Vgu*(1 - Fblood)"), units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vgu_plasma", name: Some("gu plasma"), size: Some(p[62]), formula: Some("Fblood*Vgu*(1 - HCT)"), units: Some("litre"), species: &["Cgu_plasma_tal"] },
+        CompartmentInfo { id: "Vre_tissue", name: Some("re tissue"), size: Some(p[71]), formula: Some("Vre*(1 - Fblood)"), units: Some("litre"), species: &["Cre_tal"] },
+        CompartmentInfo { id: "Vre_plasma", name: Some("re plasma"), size: Some(p[70]), formula: Some("Fblood*Vre*(1 - HCT)"), units: Some("litre"), species: &["Cre_plasma_tal"] },
+        CompartmentInfo { id: "Vfo_tissue", name: Some("fo tissue"), size: Some(p[58]), formula: Some("Vfo*(1 - Fblood)"), units: Some("litre"), species: &[] },
+        CompartmentInfo { id: "Vfo_plasma", name: Some("fo plasma"), size: Some(p[57]), formula: Some("Fblood*Vfo*(1 - HCT)"), units: Some("litre"), species: &["Cfo_plasma_tal"] },
+        CompartmentInfo { id: "Vduodenum", name: Some("small intestine duodenum D"), size: Some(p[42]), formula: None, units: Some("litre"), species: &["Cduodenum_tal"] },
+    ];
+    serde_json::to_string(&compartments).unwrap()
+}
+
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_default_parameters() -> String {
+    let defaults: &[(&str, f64)] = &[
+        ("BW", 75.0),
+        ("HEIGHT", 170.0),
+        ("HR", 70.0),
+        ("HRrest", 70.0),
+        ("COBW", 1.548),
+        ("COHRI", 150.0),
+        ("Fblood", 0.02),
+        ("HCT", 0.51),
+        ("f_shunting_forearm", 0.28),
+        ("FVgu", 0.0171),
+        ("FVki", 0.0044),
+        ("FVli", 0.021),
+        ("FVlu", 0.0076),
+        ("FVfo", 0.00482857142857143),
+        ("FVve", 0.0514),
+        ("FVar", 0.0257),
+        ("FVpo", 0.001),
+        ("FVhv", 0.001),
+        ("FVfov", 0.001),
+        ("FQgu", 0.18),
+        ("FQki", 0.19),
+        ("FQh", 0.215),
+        ("FQlu", 1.0),
+        ("FQfo", 0.0146153846153846),
+        ("conversion_min_per_day", 1440.0),
+        ("f_cirrhosis", 0.0),
+        ("PODOSE_tal", 0.0),
+        ("Ka_dis_tal", 0.681894676931315),
+        ("Mr_tal", 363.495),
+        ("fup_tal", 0.4),
+        ("ftissue_tal", 0.641324628334905),
+        ("Kp_tal", 6.62140199045977),
+        ("IVDOSE_tal", 0.0),
+        ("ti_tal", 10.0),
+        ("Ri_tal", 0.0),
+        ("cum_dose_tal", 0.0),
+        ("cum_dose_intestine_tal", 0.0),
+        ("Vurine", 1.0),
+        ("Vfeces", 1.0),
+        ("Vstomach", 1.0),
+        ("Vfo", 1.0),
+        ("Vfov", 1.0),
+        ("Vduodenum", 0.322563025707332),
+        ("final_time", 24.0),
+    ];
+    let defaults: serde_json::Map<String, serde_json::Value> = defaults.iter().map(|&(id, value)| (id.to_string(), value.into())).collect();
+    serde_json::Value::Object(defaults).to_string()
+}
+/// Equations in model order, serialized as a JSON object
+#[cfg(not(feature = "minimal"))]
+struct EquationTable(&'static [(&'static str, &'static str)]);
+
+#[cfg(not(feature = "minimal"))]
+impl Serialize for EquationTable {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (id, expression) in self.0 {
+            map.serialize_entry(id, expression)?;
         }
+        map.end()
     }
+}
+
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct ModelEquations {
+    derivatives: EquationTable,
+    assignment_rules: EquationTable,
+}
 
-    let mut species_map = HashMap::new();
-        species_map.insert("cki_plasma_tal".to_string(), cki_plasma_tal);
-        species_map.insert("cli_plasma_tal".to_string(), cli_plasma_tal);
-        species_map.insert("clu_plasma_tal".to_string(), clu_plasma_tal);
-        species_map.insert("cgu_plasma_tal".to_string(), cgu_plasma_tal);
-        species_map.insert("cre_plasma_tal".to_string(), cre_plasma_tal);
-        species_map.insert("cfo_plasma_tal".to_string(), cfo_plasma_tal);
-        species_map.insert("car_tal".to_string(), car_tal);
-        species_map.insert("cve_tal".to_string(), cve_tal);
-        species_map.insert("cpo_tal".to_string(), cpo_tal);
-        species_map.insert("chv_tal".to_string(), chv_tal);
-        species_map.insert("cfov_tal".to_string(), cfov_tal);
-        species_map.insert("clu_tal".to_string(), clu_tal);
-        species_map.insert("cre_tal".to_string(), cre_tal);
-        species_map.insert("aurine_tal".to_string(), aurine_tal);
-        species_map.insert("afeces_tal".to_string(), afeces_tal);
-        species_map.insert("cduodenum_tal".to_string(), cduodenum_tal);
-
-    let result = SimulationResult {
-        time,
-        species: species_map,
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_model_equations() -> String {
+    let equations = ModelEquations {
+        derivatives: EquationTable(&[
+            ("Cki_plasma_tal", "Car_tal*Qki - Cki_plasma_tal*Qki"),
+            ("Cli_plasma_tal", "Car_tal*Qha*(1 - f_shunts) - Cli_plasma_tal*(1 - f_shunts)*(Qha + Qpo) + Cpo_tal*Qpo*(1 - f_shunts)"),
+            ("Clu_plasma_tal", "-Clu_plasma_tal*Qlu + Cve_tal*Qlu - ftissue_tal*(Clu_plasma_tal*Kp_tal*fup_tal - Clu_tal)"),
+            ("Cgu_plasma_tal", "Car_tal*Qgu - Cgu_plasma_tal*Qgu"),
+            ("Cre_plasma_tal", "Car_tal*Qre - Cre_plasma_tal*Qre - ftissue_tal*(Cre_plasma_tal*Kp_tal*fup_tal - Cre_tal)"),
+            ("Cfo_plasma_tal", "-Car_tal*Qfo*f_shunting_forearm + Car_tal*Qfo*(1 - f_shunting_forearm) - Cfo_plasma_tal*Qfo*(1 - f_shunting_forearm)"),
+            ("Car_tal", "-Car_tal*Qfo*f_shunting_forearm - Car_tal*Qfo*(1 - f_shunting_forearm) - Car_tal*Qgu - Car_tal*Qha*f_shunts - Car_tal*Qha*(1 - f_shunts) - Car_tal*Qki - Car_tal*Qre - Cfo_plasma_tal*Qfo*(1 - f_shunting_forearm) + Clu_plasma_tal*Qlu"),
+            ("Cve_tal", "Cfov_tal*Qfo + Chv_tal*Qh + Cki_plasma_tal*Qki + Cre_plasma_tal*Qre - Cve_tal*Qlu + IVDOSE_tal*Ki_tal/Mr_tal"),
+            ("Cpo_tal", "Cgu_plasma_tal*Qgu - Cpo_tal*Qpo*f_shunts - Cpo_tal*Qpo*(1 - f_shunts)"),
+            ("Chv_tal", "Car_tal*Qha*f_shunts - Chv_tal*Qh + Cli_plasma_tal*(1 - f_shunts)*(Qha + Qpo) + Cpo_tal*Qpo*f_shunts"),
+            ("Cfov_tal", "Car_tal*Qfo*f_shunting_forearm + Cfo_plasma_tal*Qfo*(1 - f_shunting_forearm) - Cfov_tal*Qfo"),
+            ("Clu_tal", "ftissue_tal*(Clu_plasma_tal*Kp_tal*fup_tal - Clu_tal)"),
+            ("Cre_tal", "ftissue_tal*(Cre_plasma_tal*Kp_tal*fup_tal - Cre_tal)"),
+            ("Aurine_tal", "0"),
+            ("Afeces_tal", "0"),
+            ("Cduodenum_tal", "0"),
+            ("IVDOSE_tal", "-IVDOSE_tal*Ki_tal + Ri_tal"),
+            ("cum_dose_tal", "Ri_tal"),
+        ]),
+        assignment_rules: EquationTable(&[
+            ("f_shunts", "f_cirrhosis"),
+            ("f_tissue_loss", "f_cirrhosis"),
+            ("FVre", "-FVar - FVfo - FVgu - FVki - FVli - FVlu - FVve + 1"),
+            ("FQre", "-FQfo - FQh - FQki + 1"),
+            ("BSA", "0.024265*BW**0.5378*HEIGHT**0.3964"),
+            ("CO", "BW*COBW + COHRI*(HR - HRrest)/60"),
+            ("Vgu", "BW*FVgu"),
+            ("Vki", "BW*FVki"),
+            ("Vli", "BW*FVli"),
+            ("Vlu", "BW*FVlu"),
+            ("Vve", "-BW*FVve*Fblood*(-FVar - FVve + 1)/(FVar + FVve) + BW*FVve"),
+            ("Var", "-BW*FVar*Fblood*(-FVar - FVve + 1)/(FVar + FVve) + BW*FVar"),
+            ("Vpo", "(1 - HCT)*(-BW*FVpo*Fblood*(-FVar - FVfo - FVhv - FVpo - FVve + 1)/(FVar + FVfo + FVhv + FVpo + FVve) + BW*FVpo)"),
+            ("Vhv", "(1 - HCT)*(-BW*FVhv*Fblood*(-FVar - FVfo - FVhv - FVpo - FVve + 1)/(FVar + FVfo + FVhv + FVpo + FVve) + BW*FVhv)"),
+            ("Vfo_plasma", "Fblood*Vfo*(1 - HCT)"),
+            ("Vfo_tissue", "Vfo*(1 - Fblood)"),
+            ("Ki_tal", "41.58/ti_tal"),
+            ("Afov_tal", "Cfov_tal*Vfov"),
+            ("Xurine_tal", "Aurine_tal*Mr_tal"),
+            ("Xfeces_tal", "Afeces_tal*Mr_tal"),
+            ("Vre", "BW*FVre"),
+            ("QC", "3*CO/50"),
+            ("Vgu_plasma", "Fblood*Vgu*(1 - HCT)"),
+            ("Vgu_tissue", "Vgu*(1 - Fblood)"),
+            ("Vki_plasma", "Fblood*Vki*(1 - HCT)"),
+            ("Vki_tissue", "Vki*(1 - Fblood)"),
+            ("Vli_plasma", "Fblood*Vli*(1 - HCT)"),
+            ("Vli_tissue", "Vli*(1 - Fblood)*(1 - f_tissue_loss)"),
+            ("Vlu_plasma", "Fblood*Vlu*(1 - HCT)"),
+            ("Vlu_tissue", "Vlu*(1 - Fblood)"),
+            ("Ave_tal", "Cve_tal*Vve"),
+            ("Aar_tal", "Car_tal*Var"),
+            ("Apo_tal", "Cpo_tal*Vpo"),
+            ("Ahv_tal", "Chv_tal*Vhv"),
+            ("Afo_plasma_tal", "Cfo_plasma_tal*Vfo_plasma"),
+            ("Xfov_tal", "Afov_tal*Mr_tal"),
+            ("Mfov_tal", "Afov_tal*Mr_tal/Vfov"),
+            ("Vre_plasma", "Fblood*Vre*(1 - HCT)"),
+            ("Vre_tissue", "Vre*(1 - Fblood)"),
+            ("Qgu", "FQgu*QC"),
+            ("Qki", "FQki*QC"),
+            ("Qh", "FQh*QC"),
+            ("Qlu", "FQlu*QC"),
+            ("Qre", "FQre*QC"),
+            ("Qfo", "FQfo*QC"),
+            ("Agu_plasma_tal", "Cgu_plasma_tal*Vgu_plasma"),
+            ("Aki_plasma_tal", "Cki_plasma_tal*Vki_plasma"),
+            ("Ali_plasma_tal", "Cli_plasma_tal*Vli_plasma"),
+            ("Alu_plasma_tal", "Clu_plasma_tal*Vlu_plasma"),
+            ("Xve_tal", "Ave_tal*Mr_tal"),
+            ("Mve_tal", "Ave_tal*Mr_tal/Vve"),
+            ("Xar_tal", "Aar_tal*Mr_tal"),
+            ("Mar_tal", "Aar_tal*Mr_tal/Var"),
+            ("Xpo_tal", "Apo_tal*Mr_tal"),
+            ("Mpo_tal", "Apo_tal*Mr_tal/Vpo"),
+            ("Xhv_tal", "Ahv_tal*Mr_tal"),
+            ("Mhv_tal", "Ahv_tal*Mr_tal/Vhv"),
+            ("Xfo_plasma_tal", "Afo_plasma_tal*Mr_tal"),
+            ("Mfo_plasma_tal", "Afo_plasma_tal*Mr_tal/Vfo_plasma"),
+            ("Are_plasma_tal", "Cre_plasma_tal*Vre_plasma"),
+            ("Qpo", "Qgu"),
+            ("Qha", "-Qgu + Qh"),
+            ("Xgu_plasma_tal", "Agu_plasma_tal*Mr_tal"),
+            ("Mgu_plasma_tal", "Agu_plasma_tal*Mr_tal/Vgu_plasma"),
+            ("Xki_plasma_tal", "Aki_plasma_tal*Mr_tal"),
+            ("Mki_plasma_tal", "Aki_plasma_tal*Mr_tal/Vki_plasma"),
+            ("Xli_plasma_tal", "Ali_plasma_tal*Mr_tal"),
+            ("Mli_plasma_tal", "Ali_plasma_tal*Mr_tal/Vli_plasma"),
+            ("Xlu_plasma_tal", "Alu_plasma_tal*Mr_tal"),
+            ("Mlu_plasma_tal", "Alu_plasma_tal*Mr_tal/Vlu_plasma"),
+            ("Xre_plasma_tal", "Are_plasma_tal*Mr_tal"),
+            ("Mre_plasma_tal", "Are_plasma_tal*Mr_tal/Vre_plasma"),
+        ]),
     };
+    serde_json::to_string_pretty(&equations).unwrap()
+}
+/// Source SBML document, verbatim
+#[cfg(not(feature = "minimal"))]
+const MODEL_SBML: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<sbml xmlns="http://www.sbml.org/sbml/level3/version2/core" xmlns:comp="http://www.sbml.org/sbml/level3/version1/comp/version1" level="3" version="2" comp:required="true">
+  <notes>
+    <body xmlns="http://www.w3.org/1999/xhtml">
+      <p>Created with <a href="https://github.com/matthiaskoenig/sbmlutils">https://github.com/matthiaskoenig/sbmlutils</a>.
+<a href="https://doi.org/10.5281/zenodo.5525390">
+        <img src="https://zenodo.org/badge/DOI/10.5281/zenodo.5525390.svg" alt="DOI"/></a></p>
+      </body>
+    </notes>
+  <model metaid="meta_talinolol_body" id="talinolol_body" name="Talinolol (TAL) body model" substanceUnits="mmole" timeUnits="min" volumeUnits="litre" areaUnits="m2" lengthUnits="metre" extentUnits="mmole">
+    <notes>
+      <body xmlns="http://www.w3.org/1999/xhtml">
+        <h1>Talinolol (TAL) body model</h1>
+        <h2>Terms of use</h2>
+        <p>The content of this model has been carefully created in a manual research effort.
+This file has been created by <a href="https://livermetabolism.com">Matthias König</a>
+using <a href="https://github.com/matthiaskoenig/sbmlutils">sbmlutils</a>.
+For questions contact <a href="mailto:koenigmx@hu-berlin.de">koenigmx@hu-berlin.de</a>. Copyright © 2023 Matthias König.</p>
+        <a rel="license" href="http://creativecommons.org/licenses/by/4.0/">
+          <img alt="Creative Commons License" style="border-width:0" src="https://i.creativecommons.org/l/by/4.0/88x31.png"/>
+        </a>
+        <br/>This work is licensed under a <a rel="license" href="http://creativecommons.org/licenses/by/4.0/">Creative Commons Attribution 4.0 International License</a>.
+<p>Redistribution and use of any part of this model, with or without modification,
+are permitted provided that the following conditions are met:</p>
+        <ol>
+          <li>Redistributions of this SBML file must retain the above copyright notice, this
+list of conditions and the following disclaimer.</li>
+          <li>Redistributions in a different form must reproduce the above copyright notice,
+this list of conditions and the following disclaimer in the documentation and/or
+other materials provided with the distribution.</li>
+        </ol>
+        <p>This model is distributed in the hope that it will be useful, but WITHOUT ANY
+WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE.</p></body>
+      </notes>
+    <annotation>
+      <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+        <rdf:Description rdf:about="#meta_talinolol_body">
+          <dcterms:creator>
+            <rdf:Bag>
+              <rdf:li rdf:parseType="Resource">
+                <vCard4:hasName rdf:parseType="Resource">
+                  <vCard4:family-name>Stemmer Mallol</vCard4:family-name>
+                  <vCard4:given-name>Beatrice Amelie</vCard4:given-name>
+                </vCard4:hasName>
+                <vCard4:hasEmail>stemallb@hu-berlin.de</vCard4:hasEmail>
+                <vCard4:organization-name>Humboldt-University Berlin, Institute for Theoretical Biology</vCard4:organization-name>
+              </rdf:li>
+              <rdf:li rdf:parseType="Resource">
+                <vCard4:hasName rdf:parseType="Resource">
+                  <vCard4:family-name>König</vCard4:family-name>
+                  <vCard4:given-name>Matthias</vCard4:given-name>
+                </vCard4:hasName>
+                <vCard4:hasEmail>koenigmx@hu-berlin.de</vCard4:hasEmail>
+                <vCard4:organization-name>Humboldt-University Berlin, Institute for Theoretical Biology</vCard4:organization-name>
+              </rdf:li>
+            </rdf:Bag>
+          </dcterms:creator>
+          <dcterms:created rdf:parseType="Resource">
+            <dcterms:W3CDTF>1900-01-01T00:00:00Z</dcterms:W3CDTF>
+          </dcterms:created>
+          <dcterms:modified rdf:parseType="Resource">
+            <dcterms:W3CDTF>1900-01-01T00:00:00Z</dcterms:W3CDTF>
+          </dcterms:modified>
+        </rdf:Description>
+      </rdf:RDF>
+    </annotation>
+    <listOfUnitDefinitions>
+      <unitDefinition id="cm" name="cm">
+        <listOfUnits>
+          <unit kind="metre" exponent="1" scale="0" multiplier="0.01"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="g_per_mole" name="g/mole">
+        <listOfUnits>
+          <unit kind="gram" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="mole" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="hr" name="hr">
+        <listOfUnits>
+          <unit kind="second" exponent="1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="kg" name="kg">
+        <listOfUnits>
+          <unit kind="gram" exponent="1" scale="0" multiplier="1000"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="l_per_hr" name="l/hr">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="l_per_kg" name="l/kg">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="gram" exponent="-1" scale="0" multiplier="1000"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="l_per_min" name="l/min">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="l_per_min_kg" name="l/min/kg">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+          <unit kind="gram" exponent="-1" scale="0" multiplier="1000"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="l_per_min_mmole" name="l/min/mmole">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+          <unit kind="mole" exponent="-1" scale="0" multiplier="0.001"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="l_per_ml" name="l/ml">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="litre" exponent="-1" scale="0" multiplier="0.001"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="m2" name="meter^2">
+        <listOfUnits>
+          <unit kind="metre" exponent="2" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="m2_per_kg" name="meter^2/kg">
+        <listOfUnits>
+          <unit kind="metre" exponent="2" scale="0" multiplier="1"/>
+          <unit kind="gram" exponent="-1" scale="0" multiplier="1000"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mM" name="mmole/liter">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="litre" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mg" name="mg">
+        <listOfUnits>
+          <unit kind="gram" exponent="1" scale="0" multiplier="0.001"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mg_per_day" name="mg/day">
+        <listOfUnits>
+          <unit kind="gram" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="86400"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mg_per_g" name="mg/g">
+        <listOfUnits>
+          <unit kind="gram" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="gram" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mg_per_hr" name="mg/hr">
+        <listOfUnits>
+          <unit kind="gram" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mg_per_l" name="mg/l">
+        <listOfUnits>
+          <unit kind="gram" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="litre" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mg_per_min" name="mg/min">
+        <listOfUnits>
+          <unit kind="gram" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="min" name="min">
+        <listOfUnits>
+          <unit kind="second" exponent="1" scale="0" multiplier="60"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="min_per_day" name="min/day">
+        <listOfUnits>
+          <unit kind="second" exponent="1" scale="0" multiplier="60"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="86400"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="min_per_hr" name="min/hr">
+        <listOfUnits>
+          <unit kind="second" exponent="1" scale="0" multiplier="60"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="ml" name="ml">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="0.001"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="ml_per_l" name="ml/l">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="litre" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="ml_per_s" name="ml/s">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="ml_per_s_kg" name="ml/s/kg">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="1"/>
+          <unit kind="gram" exponent="-1" scale="0" multiplier="1000"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mmole" name="mmole">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="0" multiplier="0.001"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mmole_per_hr" name="mmole/hr">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mmole_per_hr_ml" name="mmole/hr/ml">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+          <unit kind="litre" exponent="-1" scale="0" multiplier="0.001"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mmole_per_l" name="mmole/l">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="litre" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mmole_per_min" name="mmole/min">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mmole_per_min_kg" name="mmole/min/kg">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+          <unit kind="gram" exponent="-1" scale="0" multiplier="1000"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mmole_per_min_l" name="mmole/min/liter">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="0" multiplier="0.001"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+          <unit kind="litre" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mulitre_per_g" name="microlitre/g">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1e-06"/>
+          <unit kind="gram" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="mul_per_min_mg" name="microliter/min/mg">
+        <listOfUnits>
+          <unit kind="litre" exponent="1" scale="0" multiplier="1e-06"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+          <unit kind="gram" exponent="-1" scale="0" multiplier="0.001"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="per_hr" name="1/hr">
+        <listOfUnits>
+          <unit kind="second" exponent="-1" scale="0" multiplier="3600"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="per_min" name="1/min">
+        <listOfUnits>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="per_min_kg" name="1/min/kg">
+        <listOfUnits>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+          <unit kind="gram" exponent="-1" scale="0" multiplier="1000"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="per_min_l" name="1/min/liter">
+        <listOfUnits>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+          <unit kind="litre" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+      <unitDefinition id="s_per_min" name="s/min">
+        <listOfUnits>
+          <unit kind="second" exponent="1" scale="0" multiplier="1"/>
+          <unit kind="second" exponent="-1" scale="0" multiplier="60"/>
+        </listOfUnits>
+      </unitDefinition>
+    </listOfUnitDefinitions>
+    <listOfCompartments>
+      <compartment metaid="meta_Vre" sboTerm="SBO:0000290" id="Vre" name="rest of body" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vre">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isVersionOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:62955"/>
+                </rdf:Bag>
+              </bqbiol:isVersionOf>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C14250"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vgu" sboTerm="SBO:0000290" id="Vgu" name="gut" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vgu">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:45615"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000545"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12736"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7199"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000648"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Vgu_RE" comp:portRef="Vgu_port" comp:submodelRef="GU"/>
+        </comp:listOfReplacedElements>
+      </compartment>
+      <compartment metaid="meta_Vki" sboTerm="SBO:0000290" id="Vki" name="kidney" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vki">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7203"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000671"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12415"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vli" sboTerm="SBO:0000290" id="Vli" name="liver" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vli">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7197"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000759"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12392"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vlu" sboTerm="SBO:0000290" id="Vlu" name="lung" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vlu">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7195"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000763"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12468"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vve" sboTerm="SBO:0000290" id="Vve" name="venous blood" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vve">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:50723"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Var" sboTerm="SBO:0000290" id="Var" name="arterial blood" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Var">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:50720"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vurine" sboTerm="SBO:0000290" id="Vurine" name="urine" spatialDimensions="3" size="1" units="litre" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vurine">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:12274"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13283"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0001419"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Vurine_RE" comp:portRef="Vurine_port" comp:submodelRef="KI"/>
+        </comp:listOfReplacedElements>
+      </compartment>
+      <compartment metaid="meta_Vfeces" sboTerm="SBO:0000290" id="Vfeces" name="feces" spatialDimensions="3" size="1" units="litre" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vfeces">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:64183"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13234"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000440"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Vfeces_RE" comp:portRef="Vfeces_port" comp:submodelRef="GU"/>
+        </comp:listOfReplacedElements>
+      </compartment>
+      <compartment metaid="meta_Vstomach" sboTerm="SBO:0000290" id="Vstomach" name="stomach" spatialDimensions="3" size="1" units="litre" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vstomach">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7148"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12391"/>
+                  <rdf:li rdf:resource="http://identifiers.org/UBERON:0000945"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0001307"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Vstomach_RE" comp:portRef="Vstomach_port" comp:submodelRef="GU"/>
+        </comp:listOfReplacedElements>
+      </compartment>
+      <compartment metaid="meta_Vpo" sboTerm="SBO:0000290" id="Vpo" name="portal plasma" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vpo">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:66645"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vhv" sboTerm="SBO:0000290" id="Vhv" name="hepatic venous plasma" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vhv">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:14337"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vfo" sboTerm="SBO:0000290" id="Vfo" name="forearm" spatialDimensions="3" size="1" units="litre" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vfo">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C32628"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:9663"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vfov" sboTerm="SBO:0000290" id="Vfov" name="forearm venous plasma" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vfov">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vki_tissue" sboTerm="SBO:0000290" id="Vki_tissue" name="ki tissue" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vki_tissue">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:45732"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C74601"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0001539"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7203"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000671"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12415"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Vki_tissue_RE" comp:portRef="Vki_port" comp:submodelRef="KI"/>
+        </comp:listOfReplacedElements>
+      </compartment>
+      <compartment metaid="meta_Vki_plasma" sboTerm="SBO:0000290" id="Vki_plasma" name="ki plasma" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vki_plasma">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7203"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000671"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12415"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Vki_plasma_RE" comp:portRef="Vext_port" comp:submodelRef="KI"/>
+        </comp:listOfReplacedElements>
+      </compartment>
+      <compartment metaid="meta_Vli_tissue" sboTerm="SBO:0000290" id="Vli_tissue" name="li tissue" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vli_tissue">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:45732"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C74601"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0001539"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7197"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000759"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12392"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Vli_tissue_RE" comp:portRef="Vli_port" comp:submodelRef="LI"/>
+        </comp:listOfReplacedElements>
+      </compartment>
+      <compartment metaid="meta_Vli_plasma" sboTerm="SBO:0000290" id="Vli_plasma" name="li plasma" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vli_plasma">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7197"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000759"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12392"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Vli_plasma_RE" comp:portRef="Vext_port" comp:submodelRef="LI"/>
+        </comp:listOfReplacedElements>
+      </compartment>
+      <compartment metaid="meta_Vlu_tissue" sboTerm="SBO:0000290" id="Vlu_tissue" name="lu tissue" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vlu_tissue">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:45732"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C74601"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0001539"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7195"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000763"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12468"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vlu_plasma" sboTerm="SBO:0000290" id="Vlu_plasma" name="lu plasma" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vlu_plasma">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7195"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000763"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12468"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vgu_tissue" sboTerm="SBO:0000290" id="Vgu_tissue" name="gu tissue" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vgu_tissue">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:45732"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C74601"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0001539"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:45615"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000545"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12736"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7199"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000648"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vgu_plasma" sboTerm="SBO:0000290" id="Vgu_plasma" name="gu plasma" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vgu_plasma">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:45615"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000545"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C12736"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7199"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000648"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Vgu_plasma_RE" comp:portRef="Vext_port" comp:submodelRef="GU"/>
+        </comp:listOfReplacedElements>
+      </compartment>
+      <compartment metaid="meta_Vre_tissue" sboTerm="SBO:0000290" id="Vre_tissue" name="re tissue" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vre_tissue">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:45732"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C74601"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0001539"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:62955"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C14250"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vre_plasma" sboTerm="SBO:0000290" id="Vre_plasma" name="re plasma" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vre_plasma">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:62955"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C14250"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vfo_tissue" sboTerm="SBO:0000290" id="Vfo_tissue" name="fo tissue" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vfo_tissue">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:45732"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C74601"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0001539"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C32628"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:9663"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vfo_plasma" sboTerm="SBO:0000290" id="Vfo_plasma" name="fo plasma" spatialDimensions="3" size="1" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vfo_plasma">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C13356"/>
+                  <rdf:li rdf:resource="http://identifiers.org/BTO:0000131"/>
+                </rdf:Bag>
+              </bqbiol:is>
+              <bqbiol:isPartOf>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C32628"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:9663"/>
+                </rdf:Bag>
+              </bqbiol:isPartOf>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </compartment>
+      <compartment metaid="meta_Vduodenum" sboTerm="SBO:0000290" id="Vduodenum" name="small intestine duodenum D" spatialDimensions="3" size="0.322563025707332" units="litre" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Vduodenum">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000290"/>
+                  <rdf:li rdf:resource="http://identifiers.org/FMA:7206"/>
+                  <rdf:li rdf:resource="http://identifiers.org/UBERON:0002114"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Vduodenum_RE1" comp:portRef="Vduodenum_port" comp:submodelRef="LI"/>
+          <comp:replacedElement metaid="Vduodenum_RE2" comp:portRef="Vduodenum_port" comp:submodelRef="GU"/>
+        </comp:listOfReplacedElements>
+      </compartment>
+    </listOfCompartments>
+    <listOfSpecies>
+      <species metaid="meta_Cki_plasma_tal" sboTerm="SBO:0000247" id="Cki_plasma_tal" name="talinolol (kidney plasma)" compartment="Vki_plasma" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Cki_plasma_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Cki_plasma_tal_RE" comp:portRef="tal_ext_port" comp:submodelRef="KI"/>
+        </comp:listOfReplacedElements>
+      </species>
+      <species metaid="meta_Cli_plasma_tal" sboTerm="SBO:0000247" id="Cli_plasma_tal" name="talinolol (liver plasma)" compartment="Vli_plasma" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Cli_plasma_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Cli_plasma_tal_RE" comp:portRef="tal_ext_port" comp:submodelRef="LI"/>
+        </comp:listOfReplacedElements>
+      </species>
+      <species metaid="meta_Clu_plasma_tal" sboTerm="SBO:0000247" id="Clu_plasma_tal" name="talinolol (lung plasma)" compartment="Vlu_plasma" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Clu_plasma_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_Cgu_plasma_tal" sboTerm="SBO:0000247" id="Cgu_plasma_tal" name="talinolol (gut plasma)" compartment="Vgu_plasma" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Cgu_plasma_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Cgu_plasma_tal_RE" comp:portRef="tal_ext_port" comp:submodelRef="GU"/>
+        </comp:listOfReplacedElements>
+      </species>
+      <species metaid="meta_Cre_plasma_tal" sboTerm="SBO:0000247" id="Cre_plasma_tal" name="talinolol (rest plasma)" compartment="Vre_plasma" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Cre_plasma_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_Cfo_plasma_tal" sboTerm="SBO:0000247" id="Cfo_plasma_tal" name="talinolol (forearm plasma)" compartment="Vfo_plasma" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Cfo_plasma_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_Car_tal" sboTerm="SBO:0000247" id="Car_tal" name="talinolol (arterial blood plasma)" compartment="Var" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Car_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_Cve_tal" sboTerm="SBO:0000247" id="Cve_tal" name="talinolol (venous blood plasma)" compartment="Vve" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Cve_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_Cpo_tal" sboTerm="SBO:0000247" id="Cpo_tal" name="talinolol (portal vein plasma)" compartment="Vpo" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Cpo_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_Chv_tal" sboTerm="SBO:0000247" id="Chv_tal" name="talinolol (hepatic vein plasma)" compartment="Vhv" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Chv_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_Cfov_tal" sboTerm="SBO:0000247" id="Cfov_tal" name="talinolol (forearm vein plasma)" compartment="Vfov" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Cfov_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_Clu_tal" sboTerm="SBO:0000247" id="Clu_tal" name="talinolol (lung)" compartment="Vlu_tissue" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Clu_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_Cre_tal" sboTerm="SBO:0000247" id="Cre_tal" name="talinolol (rest)" compartment="Vre_tissue" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Cre_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </species>
+      <species metaid="meta_Aurine_tal" sboTerm="SBO:0000247" id="Aurine_tal" name="talinolol (urine)" compartment="Vurine" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Aurine_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Aurine_tal_RE" comp:portRef="tal_urine_port" comp:submodelRef="KI"/>
+        </comp:listOfReplacedElements>
+      </species>
+      <species metaid="meta_Afeces_tal" sboTerm="SBO:0000247" id="Afeces_tal" name="talinolol (feces)" compartment="Vfeces" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="true" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Afeces_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Afeces_tal_RE" comp:portRef="tal_feces_port" comp:submodelRef="GU"/>
+        </comp:listOfReplacedElements>
+      </species>
+      <species metaid="meta_Cduodenum_tal" sboTerm="SBO:0000247" id="Cduodenum_tal" name="talinolol (duodenum)" compartment="Vduodenum" initialConcentration="0" substanceUnits="mmole" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Cduodenum_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000247"/>
+                  <rdf:li rdf:resource="http://identifiers.org/pubchem.compound/68770"/>
+                  <rdf:li rdf:resource="http://identifiers.org/CHEBI:135533"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Cduodenum_tal_RE1" comp:portRef="tal_duodenum_port" comp:submodelRef="GU"/>
+          <comp:replacedElement metaid="Cduodenum_tal_RE2" comp:portRef="tal_duodenum_port" comp:submodelRef="LI"/>
+        </comp:listOfReplacedElements>
+      </species>
+    </listOfSpecies>
+    <listOfParameters>
+      <parameter metaid="meta_BW" sboTerm="SBO:0000002" id="BW" name="body weight [kg]" value="75" units="kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_BW">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_HEIGHT" sboTerm="SBO:0000002" id="HEIGHT" name="height [cm]" value="170" units="cm" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_HEIGHT">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_HR" sboTerm="SBO:0000002" id="HR" name="heart rate [1/min]" value="70" units="per_min" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_HR">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_HRrest" sboTerm="SBO:0000002" id="HRrest" name="heart rate [1/min]" value="70" units="per_min" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_HRrest">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_BSA" sboTerm="SBO:0000002" id="BSA" name="body surface area [m^2]" value="0" units="m2" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_BSA">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_COBW" sboTerm="SBO:0000002" id="COBW" name="cardiac output per bodyweight [ml/s/kg]" value="1.548" units="ml_per_s_kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_COBW">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_CO" sboTerm="SBO:0000002" id="CO" name="cardiac output [ml/s]" value="108.33" units="ml_per_s" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_CO">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_QC" sboTerm="SBO:0000002" id="QC" name="cardiac output [L/hr]" value="6499800" units="l_per_min" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_QC">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_COHRI" sboTerm="SBO:0000002" id="COHRI" name="increase of cardiac output per heartbeat [ml/min*min]" value="150" units="ml" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_COHRI">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_Fblood" sboTerm="SBO:0000002" id="Fblood" name="blood fraction of organ volume" value="0.02" units="dimensionless" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Fblood">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_HCT" sboTerm="SBO:0000002" id="HCT" name="hematocrit" value="0.51" units="dimensionless" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_HCT">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_f_shunting_forearm" sboTerm="SBO:0000002" id="f_shunting_forearm" name="shunting in forearm" value="0.28" units="dimensionless" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_f_shunting_forearm">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVgu" sboTerm="SBO:0000002" id="FVgu" name="gut fractional tissue volume" value="0.0171" units="l_per_kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVgu">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVki" sboTerm="SBO:0000002" id="FVki" name="kidney fractional tissue volume" value="0.0044" units="l_per_kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVki">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVli" sboTerm="SBO:0000002" id="FVli" name="liver fractional tissue volume" value="0.021" units="l_per_kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVli">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVlu" sboTerm="SBO:0000002" id="FVlu" name="lung fractional tissue volume" value="0.0076" units="l_per_kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVlu">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVfo" sboTerm="SBO:0000002" id="FVfo" name="liver fractional tissue volume" value="0.00482857142857143" units="l_per_kg" constant="true">
+        <notes>
+          <body xmlns="http://www.w3.org/1999/xhtml">
+            <p>[Huang2020:Supp] Forearm blood flow 0.6430 L/hr; Physical Volume 0.3380 L</p>
+          </body>
+        </notes>
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVfo">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVre" sboTerm="SBO:0000002" id="FVre" name="rest of body fractional tissue volume" value="0" units="l_per_kg" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVre">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVve" sboTerm="SBO:0000002" id="FVve" name="venous fractional tissue volume" value="0.0514" units="l_per_kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVve">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVar" sboTerm="SBO:0000002" id="FVar" name="arterial fractional tissue volume" value="0.0257" units="l_per_kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVar">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVpo" sboTerm="SBO:0000002" id="FVpo" name="portal fractional tissue volume" value="0.001" units="l_per_kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVpo">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVhv" sboTerm="SBO:0000002" id="FVhv" name="hepatic venous fractional tissue volume" value="0.001" units="l_per_kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVhv">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FVfov" sboTerm="SBO:0000002" id="FVfov" name="fore arm venous fractional tissue volume" value="0.001" units="l_per_kg" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FVfov">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FQgu" sboTerm="SBO:0000002" id="FQgu" name="gut fractional tissue blood flow" value="0.18" units="dimensionless" constant="true">
+        <notes>
+          <body xmlns="http://www.w3.org/1999/xhtml">
+            <p>Assumed FQgu is the sum of FQgu + FQsp + FQpa for correct portal blood flow.
+with FQgu=0.146, FQsp=0.017, FQpa=0.017</p>
+          </body>
+        </notes>
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FQgu">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FQki" sboTerm="SBO:0000002" id="FQki" name="kidney fractional tissue blood flow" value="0.19" units="dimensionless" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FQki">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FQh" sboTerm="SBO:0000002" id="FQh" name="hepatic (venous side) fractional tissue blood flow" value="0.215" units="dimensionless" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FQh">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FQlu" sboTerm="SBO:0000002" id="FQlu" name="lung fractional tissue blood flow" value="1" units="dimensionless" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FQlu">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FQfo" sboTerm="SBO:0000002" id="FQfo" name="fore arm fractional tissue blood flow" value="0.0146153846153846" units="dimensionless" constant="true">
+        <notes>
+          <body xmlns="http://www.w3.org/1999/xhtml">
+            <p>[deprecated info][Huang2020:Supp] Forearm blood flow 0.6430 L/hr; Physical Volume 0.3380 L, Lung blood flow 300 L/hrCardiac output in humans is generally 5-6 L/min in an at-rest to more than 35 L/min in elite athletes during exercise.[95 ml/min in median cubital vein] https://bpgmobile.rnao.ca/sites/default/files/Appendix%20C%20Vein%20Anatomy.pdf</p>
+          </body>
+        </notes>
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FQfo">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_FQre" sboTerm="SBO:0000002" id="FQre" name="rest of body fractional tissue blood flow" value="0" units="dimensionless" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_FQre">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_conversion_min_per_day" sboTerm="SBO:0000002" id="conversion_min_per_day" name="Conversion factor min to hours" value="1440" units="min_per_day" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_conversion_min_per_day">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_f_cirrhosis" sboTerm="SBO:0000002" id="f_cirrhosis" name="severity of cirrhosis [0, 0.95]" value="0" units="dimensionless" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_f_cirrhosis">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C2951"/>
+                  <rdf:li rdf:resource="http://identifiers.org/efo/0001422"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_f_shunts" sboTerm="SBO:0000002" id="f_shunts" name="fraction of portal venous blood shunted by the liver" value="0" units="dimensionless" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_f_shunts">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                  <rdf:li rdf:resource="http://identifiers.org/HP:0002629"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_f_tissue_loss" sboTerm="SBO:0000002" id="f_tissue_loss" name="fraction of lost parenchymal liver volume" value="0" units="dimensionless" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_f_tissue_loss">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_PODOSE_tal" sboTerm="SBO:0000002" id="PODOSE_tal" name="oral dose tal [mg]" value="0" units="mg" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_PODOSE_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="PODOSE_tal_RE" comp:portRef="PODOSE_tal_port" comp:submodelRef="GU"/>
+        </comp:listOfReplacedElements>
+      </parameter>
+      <parameter metaid="meta_Ka_dis_tal" sboTerm="SBO:0000002" id="Ka_dis_tal" name="Ka_dis [1/hr] dissolution tal" value="0.681894676931315" units="per_hr" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Ka_dis_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Ka_dis_tal_RE" comp:portRef="Ka_dis_tal_port" comp:submodelRef="GU"/>
+        </comp:listOfReplacedElements>
+      </parameter>
+      <parameter metaid="meta_Mr_tal" sboTerm="SBO:0000647" id="Mr_tal" name="Molecular weight tal [g/mole]" value="363.495" units="g_per_mole" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Mr_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000647"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="Mr_tal_RE" comp:portRef="Mr_tal_port" comp:submodelRef="GU"/>
+        </comp:listOfReplacedElements>
+      </parameter>
+      <parameter metaid="meta_fup_tal" sboTerm="SBO:0000009" id="fup_tal" name="fraction unbound in plasma tal" value="0.4" units="dimensionless" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_fup_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000009"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <comp:listOfReplacedElements>
+          <comp:replacedElement metaid="fup_tal_RE1" comp:portRef="fup_tal_port" comp:submodelRef="KI"/>
+          <comp:replacedElement metaid="fup_tal_RE2" comp:portRef="fup_tal_port" comp:submodelRef="LI"/>
+        </comp:listOfReplacedElements>
+      </parameter>
+      <parameter metaid="meta_ftissue_tal" sboTerm="SBO:0000002" id="ftissue_tal" name="tissue distribution tal" value="0.641324628334905" units="l_per_min" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_ftissue_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_Kp_tal" sboTerm="SBO:0000002" id="Kp_tal" name="tissue/plasma partition coefficient tal" value="6.62140199045977" units="dimensionless" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Kp_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_IVDOSE_tal" sboTerm="SBO:0000002" id="IVDOSE_tal" name="IV bolus dose tal [mg]" value="0" units="mg" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_IVDOSE_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_ti_tal" sboTerm="SBO:0000002" id="ti_tal" name="injection time tal [s]" value="10" units="second" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_ti_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_Ki_tal" sboTerm="SBO:0000002" id="Ki_tal" name="Ki [1/min] injection tal" value="0.02" units="per_min" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Ki_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_Ri_tal" sboTerm="SBO:0000002" id="Ri_tal" name="Ri [mg/min] rate of infusion tal" value="0" units="mg_per_min" constant="true">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Ri_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_cum_dose_tal" sboTerm="SBO:0000002" id="cum_dose_tal" name="Cumulative dose due to infusion tal" value="0" units="mg" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_cum_dose_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter metaid="meta_cum_dose_intestine_tal" sboTerm="SBO:0000002" id="cum_dose_intestine_tal" name="Cumulative dose due to infusion intestine tal" value="0" units="mg" constant="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_cum_dose_intestine_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000002"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+      </parameter>
+      <parameter id="Qgu" name="gut blood flow" value="NaN" units="l_per_min" constant="false"/>
+      <parameter id="Qki" name="kidney blood flow" value="NaN" units="l_per_min" constant="false"/>
+      <parameter id="Qh" name="hepatic (venous side) blood flow" value="NaN" units="l_per_min" constant="false"/>
+      <parameter id="Qha" name="hepatic artery blood flow" value="NaN" units="l_per_min" constant="false"/>
+      <parameter id="Qlu" name="lung blood flow" value="NaN" units="l_per_min" constant="false"/>
+      <parameter id="Qre" name="rest of body blood flow" value="NaN" units="l_per_min" constant="false"/>
+      <parameter id="Qpo" name="portal blood flow" value="NaN" units="l_per_min" constant="false"/>
+      <parameter id="Qfo" name="forearm blood flow" value="NaN" units="l_per_min" constant="false"/>
+      <parameter id="Aki_plasma_tal" name="talinolol amount (kidney) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xki_plasma_tal" name="talinolol amount (kidney) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mki_plasma_tal" name="talinolol concentration (kidney) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Ali_plasma_tal" name="talinolol amount (liver) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xli_plasma_tal" name="talinolol amount (liver) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mli_plasma_tal" name="talinolol concentration (liver) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Alu_plasma_tal" name="talinolol amount (lung) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xlu_plasma_tal" name="talinolol amount (lung) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mlu_plasma_tal" name="talinolol concentration (lung) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Agu_plasma_tal" name="talinolol amount (gut) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xgu_plasma_tal" name="talinolol amount (gut) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mgu_plasma_tal" name="talinolol concentration (gut) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Are_plasma_tal" name="talinolol amount (rest) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xre_plasma_tal" name="talinolol amount (rest) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mre_plasma_tal" name="talinolol concentration (rest) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Afo_plasma_tal" name="talinolol amount (forearm) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xfo_plasma_tal" name="talinolol amount (forearm) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mfo_plasma_tal" name="talinolol concentration (forearm) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Aar_tal" name="talinolol amount (arterial blood) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xar_tal" name="talinolol amount (arterial blood) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mar_tal" name="talinolol concentration (arterial blood) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Ave_tal" name="talinolol amount (venous blood) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xve_tal" name="talinolol amount (venous blood) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mve_tal" name="talinolol concentration (venous blood) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Apo_tal" name="talinolol amount (portal vein) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xpo_tal" name="talinolol amount (portal vein) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mpo_tal" name="talinolol concentration (portal vein) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Ahv_tal" name="talinolol amount (hepatic vein) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xhv_tal" name="talinolol amount (hepatic vein) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mhv_tal" name="talinolol concentration (hepatic vein) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Afov_tal" name="talinolol amount (forearm vein) [mmole]" value="NaN" units="mmole" constant="false"/>
+      <parameter id="Xfov_tal" name="talinolol amount (forearm vein) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Mfov_tal" name="talinolol concentration (forearm vein) [mg/l]" value="NaN" units="mg_per_l" constant="false"/>
+      <parameter id="Xurine_tal" name="talinolol amount (urine) [mg]" value="NaN" units="mg" constant="false"/>
+      <parameter id="Xfeces_tal" name="talinolol amount (feces)" value="NaN" units="mg" constant="false"/>
+    </listOfParameters>
+    <listOfRules>
+      <assignmentRule variable="f_shunts">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <ci> f_cirrhosis </ci>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="f_tissue_loss">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <ci> f_cirrhosis </ci>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="FVre">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <minus/>
+            <cn sbml:units="l_per_kg"> 1 </cn>
+            <apply>
+              <plus/>
+              <ci> FVgu </ci>
+              <ci> FVki </ci>
+              <ci> FVli </ci>
+              <ci> FVlu </ci>
+              <ci> FVve </ci>
+              <ci> FVar </ci>
+              <ci> FVfo </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="FQre">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <minus/>
+            <cn sbml:units="dimensionless"> 1 </cn>
+            <apply>
+              <plus/>
+              <ci> FQki </ci>
+              <ci> FQh </ci>
+              <ci> FQfo </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="BSA">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <cn sbml:units="m2"> 0.024265 </cn>
+            <apply>
+              <power/>
+              <apply>
+                <divide/>
+                <ci> BW </ci>
+                <cn sbml:units="kg" type="integer"> 1 </cn>
+              </apply>
+              <cn> 0.5378 </cn>
+            </apply>
+            <apply>
+              <power/>
+              <apply>
+                <divide/>
+                <ci> HEIGHT </ci>
+                <cn sbml:units="cm" type="integer"> 1 </cn>
+              </apply>
+              <cn> 0.3964 </cn>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="CO">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <plus/>
+            <apply>
+              <times/>
+              <ci> BW </ci>
+              <ci> COBW </ci>
+            </apply>
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <apply>
+                  <minus/>
+                  <ci> HR </ci>
+                  <ci> HRrest </ci>
+                </apply>
+                <ci> COHRI </ci>
+              </apply>
+              <cn sbml:units="s_per_min" type="integer"> 60 </cn>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="QC">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> CO </ci>
+              <cn sbml:units="ml_per_l" type="integer"> 1000 </cn>
+            </apply>
+            <cn sbml:units="s_per_min" type="integer"> 60 </cn>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Vgu">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BW </ci>
+            <ci> FVgu </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Vki">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BW </ci>
+            <ci> FVki </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Vli">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BW </ci>
+            <ci> FVli </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Vlu">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BW </ci>
+            <ci> FVlu </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Vre">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> BW </ci>
+            <ci> FVre </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Vve">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <minus/>
+            <apply>
+              <times/>
+              <ci> BW </ci>
+              <ci> FVve </ci>
+            </apply>
+            <apply>
+              <times/>
+              <apply>
+                <divide/>
+                <ci> FVve </ci>
+                <apply>
+                  <plus/>
+                  <ci> FVar </ci>
+                  <ci> FVve </ci>
+                </apply>
+              </apply>
+              <ci> BW </ci>
+              <ci> Fblood </ci>
+              <apply>
+                <minus/>
+                <apply>
+                  <minus/>
+                  <cn sbml:units="l_per_kg" type="integer"> 1 </cn>
+                  <ci> FVve </ci>
+                </apply>
+                <ci> FVar </ci>
+              </apply>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Var">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <minus/>
+            <apply>
+              <times/>
+              <ci> BW </ci>
+              <ci> FVar </ci>
+            </apply>
+            <apply>
+              <times/>
+              <apply>
+                <divide/>
+                <ci> FVar </ci>
+                <apply>
+                  <plus/>
+                  <ci> FVar </ci>
+                  <ci> FVve </ci>
+                </apply>
+              </apply>
+              <ci> BW </ci>
+              <ci> Fblood </ci>
+              <apply>
+                <minus/>
+                <apply>
+                  <minus/>
+                  <cn sbml:units="l_per_kg" type="integer"> 1 </cn>
+                  <ci> FVve </ci>
+                </apply>
+                <ci> FVar </ci>
+              </apply>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Vpo">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> HCT </ci>
+            </apply>
+            <apply>
+              <minus/>
+              <apply>
+                <times/>
+                <ci> BW </ci>
+                <ci> FVpo </ci>
+              </apply>
+              <apply>
+                <times/>
+                <apply>
+                  <divide/>
+                  <ci> FVpo </ci>
+                  <apply>
+                    <plus/>
+                    <ci> FVar </ci>
+                    <ci> FVve </ci>
+                    <ci> FVpo </ci>
+                    <ci> FVhv </ci>
+                    <ci> FVfo </ci>
+                  </apply>
+                </apply>
+                <ci> BW </ci>
+                <ci> Fblood </ci>
+                <apply>
+                  <minus/>
+                  <cn sbml:units="l_per_kg" type="integer"> 1 </cn>
+                  <apply>
+                    <plus/>
+                    <ci> FVar </ci>
+                    <ci> FVve </ci>
+                    <ci> FVpo </ci>
+                    <ci> FVhv </ci>
+                    <ci> FVfo </ci>
+                  </apply>
+                </apply>
+              </apply>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule variable="Vhv">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> HCT </ci>
+            </apply>
+            <apply>
+              <minus/>
+              <apply>
+                <times/>
+                <ci> BW </ci>
+                <ci> FVhv </ci>
+              </apply>
+              <apply>
+                <times/>
+                <apply>
+                  <divide/>
+                  <ci> FVhv </ci>
+                  <apply>
+                    <plus/>
+                    <ci> FVar </ci>
+                    <ci> FVve </ci>
+                    <ci> FVpo </ci>
+                    <ci> FVhv </ci>
+                    <ci> FVfo </ci>
+                  </apply>
+                </apply>
+                <ci> BW </ci>
+                <ci> Fblood </ci>
+                <apply>
+                  <minus/>
+                  <cn sbml:units="l_per_kg" type="integer"> 1 </cn>
+                  <apply>
+                    <plus/>
+                    <ci> FVar </ci>
+                    <ci> FVve </ci>
+                    <ci> FVpo </ci>
+                    <ci> FVhv </ci>
+                    <ci> FVfo </ci>
+                  </apply>
+                </apply>
+              </apply>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="gut blood flow" variable="Qgu">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> QC </ci>
+            <ci> FQgu </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="kidney blood flow" variable="Qki">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> QC </ci>
+            <ci> FQki </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="hepatic (venous side) blood flow" variable="Qh">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> QC </ci>
+            <ci> FQh </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="hepatic artery blood flow" variable="Qha">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <minus/>
+            <ci> Qh </ci>
+            <ci> Qgu </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="lung blood flow" variable="Qlu">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> QC </ci>
+            <ci> FQlu </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="rest of body blood flow" variable="Qre">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> QC </ci>
+            <ci> FQre </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="portal blood flow" variable="Qpo">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <ci> Qgu </ci>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="forearm blood flow" variable="Qfo">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> QC </ci>
+            <ci> FQfo </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="plasma volume of kidney" variable="Vki_plasma">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vki </ci>
+            <ci> Fblood </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> HCT </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="tissue volume of kidney" variable="Vki_tissue">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vki </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> Fblood </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="plasma volume of liver" variable="Vli_plasma">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vli </ci>
+            <ci> Fblood </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> HCT </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="tissue volume of liver" variable="Vli_tissue">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vli </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> f_tissue_loss </ci>
+            </apply>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> Fblood </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="plasma volume of lung" variable="Vlu_plasma">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vlu </ci>
+            <ci> Fblood </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> HCT </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="tissue volume of lung" variable="Vlu_tissue">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vlu </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> Fblood </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="plasma volume of gut" variable="Vgu_plasma">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vgu </ci>
+            <ci> Fblood </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> HCT </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="tissue volume of gut" variable="Vgu_tissue">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vgu </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> Fblood </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="plasma volume of rest" variable="Vre_plasma">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vre </ci>
+            <ci> Fblood </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> HCT </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="tissue volume of rest" variable="Vre_tissue">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vre </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> Fblood </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="plasma volume of forearm" variable="Vfo_plasma">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vfo </ci>
+            <ci> Fblood </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> HCT </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="tissue volume of forearm" variable="Vfo_tissue">
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <ci> Vfo </ci>
+            <apply>
+              <minus/>
+              <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+              <ci> Fblood </ci>
+            </apply>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="injection rate IV" variable="Ki_tal">
+        <notes>
+          <body xmlns="http://www.w3.org/1999/xhtml">
+            <p>log(2) ~ 0.693 used for conversion of half-life injection time to
+injection rate.</p>
+          </body>
+        </notes>
+        <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <cn sbml:units="dimensionless"> 0.693 </cn>
+              <ci> ti_tal </ci>
+            </apply>
+            <cn sbml:units="s_per_min" type="integer"> 60 </cn>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (kidney) [mmole]" variable="Aki_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Cki_plasma_tal </ci>
+            <ci> Vki_plasma </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (kidney) [mg]" variable="Xki_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Aki_plasma_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (kidney) [mg/l]" variable="Mki_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Aki_plasma_tal </ci>
+              <ci> Vki_plasma </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (liver) [mmole]" variable="Ali_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Cli_plasma_tal </ci>
+            <ci> Vli_plasma </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (liver) [mg]" variable="Xli_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Ali_plasma_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (liver) [mg/l]" variable="Mli_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Ali_plasma_tal </ci>
+              <ci> Vli_plasma </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (lung) [mmole]" variable="Alu_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Clu_plasma_tal </ci>
+            <ci> Vlu_plasma </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (lung) [mg]" variable="Xlu_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Alu_plasma_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (lung) [mg/l]" variable="Mlu_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Alu_plasma_tal </ci>
+              <ci> Vlu_plasma </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (gut) [mmole]" variable="Agu_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Cgu_plasma_tal </ci>
+            <ci> Vgu_plasma </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (gut) [mg]" variable="Xgu_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Agu_plasma_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (gut) [mg/l]" variable="Mgu_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Agu_plasma_tal </ci>
+              <ci> Vgu_plasma </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (rest) [mmole]" variable="Are_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Cre_plasma_tal </ci>
+            <ci> Vre_plasma </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (rest) [mg]" variable="Xre_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Are_plasma_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (rest) [mg/l]" variable="Mre_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Are_plasma_tal </ci>
+              <ci> Vre_plasma </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (forearm) [mmole]" variable="Afo_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Cfo_plasma_tal </ci>
+            <ci> Vfo_plasma </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (forearm) [mg]" variable="Xfo_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Afo_plasma_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (forearm) [mg/l]" variable="Mfo_plasma_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Afo_plasma_tal </ci>
+              <ci> Vfo_plasma </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (arterial blood) [mmole]" variable="Aar_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Car_tal </ci>
+            <ci> Var </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (arterial blood) [mg]" variable="Xar_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Aar_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (arterial blood) [mg/l]" variable="Mar_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Aar_tal </ci>
+              <ci> Var </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (venous blood) [mmole]" variable="Ave_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Cve_tal </ci>
+            <ci> Vve </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (venous blood) [mg]" variable="Xve_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Ave_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (venous blood) [mg/l]" variable="Mve_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Ave_tal </ci>
+              <ci> Vve </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (portal vein) [mmole]" variable="Apo_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Cpo_tal </ci>
+            <ci> Vpo </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (portal vein) [mg]" variable="Xpo_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Apo_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (portal vein) [mg/l]" variable="Mpo_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Apo_tal </ci>
+              <ci> Vpo </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (hepatic vein) [mmole]" variable="Ahv_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Chv_tal </ci>
+            <ci> Vhv </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (hepatic vein) [mg]" variable="Xhv_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Ahv_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (hepatic vein) [mg/l]" variable="Mhv_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Ahv_tal </ci>
+              <ci> Vhv </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (forearm vein) [mmole]" variable="Afov_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Cfov_tal </ci>
+            <ci> Vfov </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (forearm vein) [mg]" variable="Xfov_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Afov_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol concentration (forearm vein) [mg/l]" variable="Mfov_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <apply>
+              <divide/>
+              <ci> Afov_tal </ci>
+              <ci> Vfov </ci>
+            </apply>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (urine) [mg]" variable="Xurine_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Aurine_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <assignmentRule name="talinolol amount (feces)" variable="Xfeces_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <ci> Afeces_tal </ci>
+            <ci> Mr_tal </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+      <rateRule variable="IVDOSE_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <plus/>
+            <apply>
+              <times/>
+              <apply>
+                <minus/>
+                <ci> iv_tal </ci>
+              </apply>
+              <ci> Mr_tal </ci>
+            </apply>
+            <ci> Ri_tal </ci>
+          </apply>
+        </math>
+      </rateRule>
+      <rateRule variable="cum_dose_tal">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <ci> Ri_tal </ci>
+        </math>
+      </rateRule>
+    </listOfRules>
+    <listOfReactions>
+      <reaction metaid="meta_transport_lu_tal" sboTerm="SBO:0000655" id="transport_lu_tal" name="transport talinolol" reversible="true" compartment="Vlu_tissue">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_transport_lu_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Clu_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Clu_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> ftissue_tal </ci>
+              <apply>
+                <minus/>
+                <apply>
+                  <times/>
+                  <ci> Clu_plasma_tal </ci>
+                  <ci> fup_tal </ci>
+                  <ci> Kp_tal </ci>
+                </apply>
+                <ci> Clu_tal </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_transport_re_tal" sboTerm="SBO:0000655" id="transport_re_tal" name="transport talinolol" reversible="true" compartment="Vre_tissue">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_transport_re_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Cre_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cre_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> ftissue_tal </ci>
+              <apply>
+                <minus/>
+                <apply>
+                  <times/>
+                  <ci> Cre_plasma_tal </ci>
+                  <ci> fup_tal </ci>
+                  <ci> Kp_tal </ci>
+                </apply>
+                <ci> Cre_tal </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_iv_tal" sboTerm="SBO:0000655" id="iv_tal" name="iv talinolol" reversible="false" compartment="Vve">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_iv_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                  <rdf:li rdf:resource="http://identifiers.org/ncit/C38276"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfProducts>
+          <speciesReference species="Cve_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <divide/>
+              <apply>
+                <times/>
+                <ci> Ki_tal </ci>
+                <ci> IVDOSE_tal </ci>
+              </apply>
+              <ci> Mr_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_ar_ki_tal" sboTerm="SBO:0000655" id="Flow_ar_ki_tal" name="inflow kidney talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_ar_ki_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Car_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cki_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Qki </ci>
+              <ci> Car_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_ki_ve_tal" sboTerm="SBO:0000655" id="Flow_ki_ve_tal" name="outflow kidney talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_ki_ve_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Cki_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cve_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Qki </ci>
+              <ci> Cki_plasma_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_arli_li_tal" sboTerm="SBO:0000655" id="Flow_arli_li_tal" name="arterial inflow liver talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_arli_li_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Car_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cli_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+            <apply>
+              <times/>
+              <apply>
+                <minus/>
+                <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+                <ci> f_shunts </ci>
+              </apply>
+              <ci> Qha </ci>
+              <ci> Car_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_arli_hv_tal" sboTerm="SBO:0000655" id="Flow_arli_hv_tal" name="flow arterial shunts" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_arli_hv_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Car_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Chv_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> f_shunts </ci>
+              <ci> Qha </ci>
+              <ci> Car_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_po_li_tal" sboTerm="SBO:0000655" id="Flow_po_li_tal" name="outflow po talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_po_li_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Cpo_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cli_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+            <apply>
+              <times/>
+              <apply>
+                <minus/>
+                <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+                <ci> f_shunts </ci>
+              </apply>
+              <ci> Qpo </ci>
+              <ci> Cpo_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_po_hv_tal" sboTerm="SBO:0000655" id="Flow_po_hv_tal" name="flow portal shunts" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_po_hv_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Cpo_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Chv_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> f_shunts </ci>
+              <ci> Qpo </ci>
+              <ci> Cpo_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_li_hv_tal" sboTerm="SBO:0000655" id="Flow_li_hv_tal" name="outflow liver talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_li_hv_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Cli_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Chv_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+            <apply>
+              <times/>
+              <apply>
+                <minus/>
+                <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+                <ci> f_shunts </ci>
+              </apply>
+              <apply>
+                <plus/>
+                <ci> Qpo </ci>
+                <ci> Qha </ci>
+              </apply>
+              <ci> Cli_plasma_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_hv_ve_tal" sboTerm="SBO:0000655" id="Flow_hv_ve_tal" name="outflow hepatic vein talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_hv_ve_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Chv_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cve_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Qh </ci>
+              <ci> Chv_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_ve_lu_tal" sboTerm="SBO:0000655" id="Flow_ve_lu_tal" name="inflow lung talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_ve_lu_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Cve_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Clu_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Qlu </ci>
+              <ci> Cve_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_lu_ar_tal" sboTerm="SBO:0000655" id="Flow_lu_ar_tal" name="outflow lung talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_lu_ar_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Clu_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Car_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Qlu </ci>
+              <ci> Clu_plasma_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_ar_gu_tal" sboTerm="SBO:0000655" id="Flow_ar_gu_tal" name="inflow gut talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_ar_gu_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Car_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cgu_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Qgu </ci>
+              <ci> Car_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_gu_po_tal" sboTerm="SBO:0000655" id="Flow_gu_po_tal" name="outflow gut talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_gu_po_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Cgu_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cpo_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Qgu </ci>
+              <ci> Cgu_plasma_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_ar_re_tal" sboTerm="SBO:0000655" id="Flow_ar_re_tal" name="inflow rest talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_ar_re_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Car_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cre_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Qre </ci>
+              <ci> Car_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_re_ve_tal" sboTerm="SBO:0000655" id="Flow_re_ve_tal" name="outflow rest talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_re_ve_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Cre_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cve_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Qre </ci>
+              <ci> Cre_plasma_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_ar_fo_tal" sboTerm="SBO:0000655" id="Flow_ar_fo_tal" name="inflow forearm talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_ar_fo_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Car_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cfo_plasma_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+            <apply>
+              <times/>
+              <ci> Qfo </ci>
+              <apply>
+                <minus/>
+                <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+                <ci> f_shunting_forearm </ci>
+              </apply>
+              <ci> Car_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_fo_fov_tal" sboTerm="SBO:0000655" id="Flow_fo_fov_tal" name="outflow forearm talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_fo_fov_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Cfo_plasma_tal" stoichiometry="1" constant="true"/>
+          <speciesReference species="Car_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cfov_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML" xmlns:sbml="http://www.sbml.org/sbml/level3/version2/core">
+            <apply>
+              <plus/>
+              <apply>
+                <times/>
+                <ci> Qfo </ci>
+                <apply>
+                  <minus/>
+                  <cn sbml:units="dimensionless" type="integer"> 1 </cn>
+                  <ci> f_shunting_forearm </ci>
+                </apply>
+                <ci> Cfo_plasma_tal </ci>
+              </apply>
+              <apply>
+                <times/>
+                <ci> Qfo </ci>
+                <ci> f_shunting_forearm </ci>
+                <ci> Car_tal </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+      <reaction metaid="meta_Flow_fo_ve_tal" sboTerm="SBO:0000655" id="Flow_fo_ve_tal" name="outflow forearm vein talinolol" reversible="false">
+        <annotation>
+          <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:vCard="http://www.w3.org/2001/vcard-rdf/3.0#" xmlns:vCard4="http://www.w3.org/2006/vcard/ns#" xmlns:bqbiol="http://biomodels.net/biology-qualifiers/" xmlns:bqmodel="http://biomodels.net/model-qualifiers/">
+            <rdf:Description rdf:about="#meta_Flow_fo_ve_tal">
+              <bqbiol:is>
+                <rdf:Bag>
+                  <rdf:li rdf:resource="http://identifiers.org/SBO:0000655"/>
+                </rdf:Bag>
+              </bqbiol:is>
+            </rdf:Description>
+          </rdf:RDF>
+        </annotation>
+        <listOfReactants>
+          <speciesReference species="Cfov_tal" stoichiometry="1" constant="true"/>
+        </listOfReactants>
+        <listOfProducts>
+          <speciesReference species="Cve_tal" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <times/>
+              <ci> Qfo </ci>
+              <ci> Cfov_tal </ci>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+    </listOfReactions>
+    <comp:listOfSubmodels>
+      <comp:submodel comp:id="KI" comp:name="kidney submodel" comp:modelRef="kidney"/>
+      <comp:submodel comp:id="LI" comp:name="liver submodel" comp:modelRef="liver"/>
+      <comp:submodel comp:id="GU" comp:name="gut submodel" comp:modelRef="gut"/>
+    </comp:listOfSubmodels>
+    <comp:listOfPorts>
+      <comp:port metaid="Vki_tissue_port" sboTerm="SBO:0000599" comp:idRef="Vki_tissue" comp:id="Vki_tissue_port" comp:name="Port of Vki_tissue"/>
+      <comp:port metaid="Vki_plasma_port" sboTerm="SBO:0000599" comp:idRef="Vki_plasma" comp:id="Vki_plasma_port" comp:name="Port of Vki_plasma"/>
+      <comp:port metaid="Vli_tissue_port" sboTerm="SBO:0000599" comp:idRef="Vli_tissue" comp:id="Vli_tissue_port" comp:name="Port of Vli_tissue"/>
+      <comp:port metaid="Vli_plasma_port" sboTerm="SBO:0000599" comp:idRef="Vli_plasma" comp:id="Vli_plasma_port" comp:name="Port of Vli_plasma"/>
+      <comp:port metaid="Vlu_tissue_port" sboTerm="SBO:0000599" comp:idRef="Vlu_tissue" comp:id="Vlu_tissue_port" comp:name="Port of Vlu_tissue"/>
+      <comp:port metaid="Vlu_plasma_port" sboTerm="SBO:0000599" comp:idRef="Vlu_plasma" comp:id="Vlu_plasma_port" comp:name="Port of Vlu_plasma"/>
+      <comp:port metaid="Vgu_tissue_port" sboTerm="SBO:0000599" comp:idRef="Vgu_tissue" comp:id="Vgu_tissue_port" comp:name="Port of Vgu_tissue"/>
+      <comp:port metaid="Vgu_plasma_port" sboTerm="SBO:0000599" comp:idRef="Vgu_plasma" comp:id="Vgu_plasma_port" comp:name="Port of Vgu_plasma"/>
+      <comp:port metaid="Vre_tissue_port" sboTerm="SBO:0000599" comp:idRef="Vre_tissue" comp:id="Vre_tissue_port" comp:name="Port of Vre_tissue"/>
+      <comp:port metaid="Vre_plasma_port" sboTerm="SBO:0000599" comp:idRef="Vre_plasma" comp:id="Vre_plasma_port" comp:name="Port of Vre_plasma"/>
+      <comp:port metaid="Vfo_tissue_port" sboTerm="SBO:0000599" comp:idRef="Vfo_tissue" comp:id="Vfo_tissue_port" comp:name="Port of Vfo_tissue"/>
+      <comp:port metaid="Vfo_plasma_port" sboTerm="SBO:0000599" comp:idRef="Vfo_plasma" comp:id="Vfo_plasma_port" comp:name="Port of Vfo_plasma"/>
+      <comp:port metaid="Cki_plasma_tal_port" sboTerm="SBO:0000599" comp:idRef="Cki_plasma_tal" comp:id="Cki_plasma_tal_port" comp:name="Port of Cki_plasma_tal"/>
+      <comp:port metaid="Cli_plasma_tal_port" sboTerm="SBO:0000599" comp:idRef="Cli_plasma_tal" comp:id="Cli_plasma_tal_port" comp:name="Port of Cli_plasma_tal"/>
+      <comp:port metaid="Clu_plasma_tal_port" sboTerm="SBO:0000599" comp:idRef="Clu_plasma_tal" comp:id="Clu_plasma_tal_port" comp:name="Port of Clu_plasma_tal"/>
+      <comp:port metaid="Cgu_plasma_tal_port" sboTerm="SBO:0000599" comp:idRef="Cgu_plasma_tal" comp:id="Cgu_plasma_tal_port" comp:name="Port of Cgu_plasma_tal"/>
+      <comp:port metaid="Cre_plasma_tal_port" sboTerm="SBO:0000599" comp:idRef="Cre_plasma_tal" comp:id="Cre_plasma_tal_port" comp:name="Port of Cre_plasma_tal"/>
+      <comp:port metaid="Cfo_plasma_tal_port" sboTerm="SBO:0000599" comp:idRef="Cfo_plasma_tal" comp:id="Cfo_plasma_tal_port" comp:name="Port of Cfo_plasma_tal"/>
+      <comp:port metaid="Car_tal_port" sboTerm="SBO:0000599" comp:idRef="Car_tal" comp:id="Car_tal_port" comp:name="Port of Car_tal"/>
+      <comp:port metaid="Cve_tal_port" sboTerm="SBO:0000599" comp:idRef="Cve_tal" comp:id="Cve_tal_port" comp:name="Port of Cve_tal"/>
+      <comp:port metaid="Cpo_tal_port" sboTerm="SBO:0000599" comp:idRef="Cpo_tal" comp:id="Cpo_tal_port" comp:name="Port of Cpo_tal"/>
+      <comp:port metaid="Chv_tal_port" sboTerm="SBO:0000599" comp:idRef="Chv_tal" comp:id="Chv_tal_port" comp:name="Port of Chv_tal"/>
+      <comp:port metaid="Cfov_tal_port" sboTerm="SBO:0000599" comp:idRef="Cfov_tal" comp:id="Cfov_tal_port" comp:name="Port of Cfov_tal"/>
+      <comp:port metaid="Clu_tal_port" sboTerm="SBO:0000599" comp:idRef="Clu_tal" comp:id="Clu_tal_port" comp:name="Port of Clu_tal"/>
+      <comp:port metaid="Cre_tal_port" sboTerm="SBO:0000599" comp:idRef="Cre_tal" comp:id="Cre_tal_port" comp:name="Port of Cre_tal"/>
+    </comp:listOfPorts>
+  </model>
+  <comp:listOfExternalModelDefinitions>
+    <comp:externalModelDefinition comp:id="kidney" comp:name="kidney model definition" comp:source="talinolol_kidney.xml" comp:modelRef="talinolol_kidney"/>
+    <comp:externalModelDefinition comp:id="liver" comp:name="liver model definition" comp:source="talinolol_liver.xml" comp:modelRef="talinolol_liver"/>
+    <comp:externalModelDefinition comp:id="gut" comp:name="gut model definition" comp:source="talinolol_intestine.xml" comp:modelRef="talinolol_intestine"/>
+  </comp:listOfExternalModelDefinitions>
+</sbml>"##;
+
+/// SBML document this module was generated from
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_model_sbml() -> Result<String, String> {
+    Ok(MODEL_SBML.to_string())
+}
+/// TypeScript definitions of the parameters, result and metadata of this model
+#[cfg(not(feature = "minimal"))]
+const TYPES_TS: &str = r#"// TypeScript definitions for talinolol_model, generated with the module
+
+export type SpeciesId = "Cki_plasma_tal" | "Cli_plasma_tal" | "Clu_plasma_tal" | "Cgu_plasma_tal" | "Cre_plasma_tal" | "Cfo_plasma_tal" | "Car_tal" | "Cve_tal" | "Cpo_tal" | "Chv_tal" | "Cfov_tal" | "Clu_tal" | "Cre_tal" | "Aurine_tal" | "Afeces_tal" | "Cduodenum_tal" | "IVDOSE_tal" | "cum_dose_tal";
+export type ObservableId = "f_shunts" | "f_tissue_loss" | "FVre" | "FQre" | "BSA" | "CO" | "Vgu" | "Vki" | "Vli" | "Vlu" | "Vve" | "Var" | "Vpo" | "Vhv" | "Vfo_plasma" | "Vfo_tissue" | "Ki_tal" | "Afov_tal" | "Xurine_tal" | "Xfeces_tal" | "Vre" | "QC" | "Vgu_plasma" | "Vgu_tissue" | "Vki_plasma" | "Vki_tissue" | "Vli_plasma" | "Vli_tissue" | "Vlu_plasma" | "Vlu_tissue" | "Ave_tal" | "Aar_tal" | "Apo_tal" | "Ahv_tal" | "Afo_plasma_tal" | "Xfov_tal" | "Mfov_tal" | "Vre_plasma" | "Vre_tissue" | "Qgu" | "Qki" | "Qh" | "Qlu" | "Qre" | "Qfo" | "Agu_plasma_tal" | "Aki_plasma_tal" | "Ali_plasma_tal" | "Alu_plasma_tal" | "Xve_tal" | "Mve_tal" | "Xar_tal" | "Mar_tal" | "Xpo_tal" | "Mpo_tal" | "Xhv_tal" | "Mhv_tal" | "Xfo_plasma_tal" | "Mfo_plasma_tal" | "Are_plasma_tal" | "Qpo" | "Qha" | "Xgu_plasma_tal" | "Mgu_plasma_tal" | "Xki_plasma_tal" | "Mki_plasma_tal" | "Xli_plasma_tal" | "Mli_plasma_tal" | "Xlu_plasma_tal" | "Mlu_plasma_tal" | "Xre_plasma_tal" | "Mre_plasma_tal";
+export type SolverMethod = "bdf" | "tr_bdf2";
+export type JacobianMode = "analytic" | "finite_difference";
+export type SimulationMode = "transient" | "steady_state";
+export type TimeUnit = "s" | "min" | "h" | "d";
+export type OutputKind = "amount" | "concentration" | "both";
+
+export interface Dose {
+  time: number;
+  amount: number;
+  target: SpeciesId;
+  units?: string | null;
+}
+
+/** Input of run_simulation; omitted fields take the model defaults */
+export interface SimulationParams {
+  BW?: number;
+  HEIGHT?: number;
+  HR?: number;
+  HRrest?: number;
+  COBW?: number;
+  COHRI?: number;
+  Fblood?: number;
+  HCT?: number;
+  f_shunting_forearm?: number;
+  FVgu?: number;
+  FVki?: number;
+  FVli?: number;
+  FVlu?: number;
+  FVfo?: number;
+  FVve?: number;
+  FVar?: number;
+  FVpo?: number;
+  FVhv?: number;
+  FVfov?: number;
+  FQgu?: number;
+  FQki?: number;
+  FQh?: number;
+  FQlu?: number;
+  FQfo?: number;
+  conversion_min_per_day?: number;
+  f_cirrhosis?: number;
+  PODOSE_tal?: number;
+  Ka_dis_tal?: number;
+  Mr_tal?: number;
+  fup_tal?: number;
+  ftissue_tal?: number;
+  Kp_tal?: number;
+  IVDOSE_tal?: number;
+  ti_tal?: number;
+  Ri_tal?: number;
+  cum_dose_tal?: number;
+  cum_dose_intestine_tal?: number;
+  Vurine?: number;
+  Vfeces?: number;
+  Vstomach?: number;
+  Vfo?: number;
+  Vfov?: number;
+  Vduodenum?: number;
+  init_Cki_plasma_tal?: number | null;
+  init_Cli_plasma_tal?: number | null;
+  init_Clu_plasma_tal?: number | null;
+  init_Cgu_plasma_tal?: number | null;
+  init_Cre_plasma_tal?: number | null;
+  init_Cfo_plasma_tal?: number | null;
+  init_Car_tal?: number | null;
+  init_Cve_tal?: number | null;
+  init_Cpo_tal?: number | null;
+  init_Chv_tal?: number | null;
+  init_Cfov_tal?: number | null;
+  init_Clu_tal?: number | null;
+  init_Cre_tal?: number | null;
+  init_Aurine_tal?: number | null;
+  init_Afeces_tal?: number | null;
+  init_Cduodenum_tal?: number | null;
+  init_IVDOSE_tal?: number | null;
+  init_cum_dose_tal?: number | null;
+  t_start?: number | null;
+  final_time?: number | null;
+  time_unit?: TimeUnit | null;
+  convert_output_time?: boolean | null;
+  output_times?: number[] | null;
+  output_dt?: number | null;
+  max_output_points?: number | null;
+  include_observables?: boolean | null;
+  output_kind?: OutputKind | null;
+  include_parameters?: boolean | null;
+  mass_balance?: boolean | null;
+  validate?: boolean | null;
+  doses?: Dose[] | null;
+  rtol?: number | null;
+  atol?: number | null;
+  max_steps?: number | null;
+  max_step?: number | null;
+  initial_step?: number | null;
+  check_finite?: boolean | null;
+  clamp_nonnegative?: boolean | null;
+  clamp_threshold?: number | null;
+  solver?: SolverMethod | null;
+  jacobian?: JacobianMode | null;
+  mode?: SimulationMode | null;
+  steady_state_tol?: number | null;
+  steady_state_exclude?: string[] | null;
+  steady_state_min_time?: number | null;
+}
+
+export interface SimulationResult {
+  species: Record<SpeciesId, number[]>;
+  time: number[];
+  /** null when the parameters were rejected or include_parameters is false */
+  parameters: SimulationParams | null;
+  /** Filled when include_observables is set */
+  observables: Partial<Record<ObservableId, number[]>>;
+  /** Unit of each species and observable series and of time; null if unknown */
+  units: Record<string, string | null>;
+  /** Filled when output_kind asks for them */
+  amounts: Partial<Record<SpeciesId, number[]>>;
+  concentrations: Partial<Record<SpeciesId, number[]>>;
+  /** Species left in their native representation, and why */
+  notes: string[];
+  steady_state: SteadyState | null;
+  applied_doses: Dose[];
+  events: EventRecord[];
+  /** Whether each event trigger held at the end; continuations start from it */
+  trigger_state: boolean[];
+  steps: number;
+  stats: SolverStats | null;
+  diagnostics: Diagnostics | null;
+  error: SimulationError | null;
+  timing: Timing;
+}
+
+/** Wall-clock milliseconds of the call */
+export interface Timing {
+  solve_ms: number;
+  serialize_ms: number;
+  total_ms: number;
+}
+
+export interface SteadyState {
+  reached: boolean;
+  time: number;
+  rms: number;
+  state: number[];
+}
 
-    serde_json::to_string(&result).unwrap()
+export interface EventRecord {
+  time: number;
+  id: string;
+  assignments: Partial<Record<SpeciesId, number>>;
+}
+
+export interface SolverStats {
+  steps: number;
+  rhs_evals: number;
+  jacobian_evals: number;
+  newton_iterations: number;
+  newton_failures: number;
+  final_step_size: number;
+}
+
+export interface Diagnostics {
+  total_mass: number[];
+  max_mass_drift: number;
+}
+
+export interface SimulationError {
+  kind: "invalid_params" | "setup" | "solver_failure" | "event_error" | "max_steps_exceeded" | "cancelled";
+  message: string;
+  field: string | null;
+  time: number | null;
+  state: number[] | null;
+  hint: string | null;
+}
+
+/** get_model_metadata */
+export interface ModelMetadata {
+  model_id: string;
+  num_species: number;
+  num_parameters: number;
+  time_units: string;
+  substance_units: string;
+  volume_units: string;
+  sbml: {
+    id: string | null;
+    name: string | null;
+    level: number | null;
+    version: number | null;
+    sha256: string | null;
+  };
+  generator: { name: string; version: string | null; generated_at: string | null };
+  diffsol_version: string;
+  features: ("events" | "sensitivities" | "embedded_sbml")[];
+  observables: { id: ObservableId; units: string | null }[];
+}
+
+/** One entry of get_parameters_info */
+export interface ParameterInfo {
+  id: keyof SimulationParams;
+  name: string | null;
+  units: string | null;
+  description: string | null;
+  default_value: number;
+  required: boolean;
+  is_compartment: boolean;
+  min: number | null;
+  max: number | null;
+}
+
+/** One entry of get_species_info */
+export interface SpeciesInfo {
+  id: SpeciesId;
+  initial_amount: number;
+  override: keyof SimulationParams;
+  units: string | null;
+  compartment: string | null;
+  compartment_volume: number | null;
+  is_amount: boolean;
+  is_boundary: boolean;
+}
+
+/** One entry of get_compartments_info */
+export interface CompartmentInfo {
+  id: string;
+  name: string | null;
+  size: number | null;
+  formula: string | null;
+  units: string | null;
+  species: SpeciesId[];
+}
+
+/** get_model_equations */
+export interface ModelEquations {
+  derivatives: Record<SpeciesId, string>;
+  assignment_rules: Record<string, string>;
+}
+"#;
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_types_ts() -> String {
+    TYPES_TS.to_string()
+}
+
+#[cfg(all(test, feature = "wasm", target_arch = "wasm32"))]
+mod browser_tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[cfg(not(feature = "node-tests"))]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn get(value: &JsValue, key: &str) -> JsValue {
+        js_sys::Reflect::get(value, &key.into()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn defaults_run_through_js() {
+        // Parsed, changed and serialized again in JS, as a page passes parameters
+        let params = js_sys::JSON::parse(&get_default_parameters()).unwrap();
+        js_sys::Reflect::set(&params, &"final_time".into(), &2.0.into()).unwrap();
+        let text = String::from(js_sys::JSON::stringify(&params).unwrap());
+        let result = js_sys::JSON::parse(&run_simulation(&text)).unwrap();
+        assert!(get(&result, "error").is_null());
+        let time = js_sys::Array::from(&get(&result, "time"));
+        assert_eq!(time.get(0).as_f64(), Some(0.0));
+        assert_eq!(time.get(time.length() - 1).as_f64(), Some(2.0));
+        assert_eq!(get(&get(&result, "parameters"), "final_time").as_f64(), Some(2.0));
+        // The first point is the model's initial state for the defaults
+        let p = TalinololModel::parameter_vector(&TalinololModel::default_params());
+        let mut y = vec![0.0; TalinololModel::N_STATES];
+        TalinololModel::init(&p, 0.0, &mut y);
+        let species = get(&result, "species");
+        for (name, y0) in SPECIES_IDS.iter().zip(&y) {
+            assert_eq!(js_sys::Array::from(&get(&species, name)).get(0).as_f64(), Some(*y0), "{}", name);
+        }
+        // The same run with JS objects in and out
+        let direct = run_simulation_js(params).unwrap();
+        let json = |value: &JsValue| String::from(js_sys::JSON::stringify(value).unwrap());
+        for key in ["time", "species"] {
+            assert_eq!(json(&get(&direct, key)), json(&get(&result, key)), "{}", key);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn malformed_params_are_errors_in_js() {
+        let result = js_sys::JSON::parse(&run_simulation(r#"{"final_time": 2,"#)).unwrap();
+        assert_eq!(get(&get(&result, "error"), "kind").as_string().as_deref(), Some("invalid_params"));
+        // Non-ASCII text survives both string conversions
+        let result = js_sys::JSON::parse(&run_simulation(r#"{"dosis_µg": 1}"#)).unwrap();
+        let message = get(&get(&result, "error"), "message").as_string().unwrap();
+        assert!(message.contains("dosis_µg"), "{}", message);
+        assert!(run_simulation_js(JsValue::from_str("{")).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn large_results_and_logging_reach_js() {
+        // Debug logging calls the console import on every step and event
+        set_log_level("debug").unwrap();
+        let logged = run_simulation(r#"{"final_time": 1}"#);
+        set_log_level(if cfg!(debug_assertions) { "info" } else { "error" }).unwrap();
+        assert!(get(&js_sys::JSON::parse(&logged).unwrap(), "error").is_null());
+        // Tens of thousands of points, megabytes of JSON
+        let output = run_simulation(r#"{"max_step": 0.001}"#);
+        let result = js_sys::JSON::parse(&output).unwrap();
+        assert!(get(&result, "error").is_null());
+        assert!(js_sys::Array::from(&get(&result, "time")).length() > 24_000);
+    }
+
+    #[wasm_bindgen_test]
+    async fn async_run_matches_sync_run() {
+        let params = js_sys::JSON::parse(&get_default_parameters()).unwrap();
+        let sync = run_simulation_js(params.clone()).unwrap();
+        // A small chunk so the run yields many times
+        let result = run_simulation_async(params, Some(7)).await.unwrap();
+        let json = |value: &JsValue| String::from(js_sys::JSON::stringify(value).unwrap());
+        assert_eq!(json(&result), json(&sync));
+    }
+
+    #[wasm_bindgen_test]
+    fn session_runs_match_stateless_runs() {
+        let mut session = ModelSession::new(js_sys::Object::new().into()).unwrap();
+        session.set_final_time(2.0).unwrap();
+        session.set_parameter("rtol", 1e-8).unwrap();
+        assert!(session.set_parameter("not_a_parameter", 1.0).is_err());
+        assert!(session.set_parameter("rtol", -1.0).is_err());
+        let params = js_sys::JSON::parse(r#"{"final_time": 2.0, "rtol": 1e-8}"#).unwrap();
+        let expected = run_simulation_typed(params).unwrap();
+        for _ in 0..2 {
+            let handle = session.run();
+            assert_eq!(handle.times(), expected.times());
+            for name in SPECIES_IDS {
+                assert_eq!(handle.species(name).unwrap(), expected.species(name).unwrap());
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn progress_callback_reports_fraction_and_steps() {
+        let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let record = calls.clone();
+        let callback = Closure::<dyn FnMut(f64, f64)>::new(move |fraction, steps| record.borrow_mut().push((fraction, steps)));
+        set_progress_callback(Some(callback.as_ref().unchecked_ref::<js_sys::Function>().clone()), None, Some(5));
+        let result = run_simulation_js(js_sys::Object::new().into());
+        set_progress_callback(None, None, None);
+        assert!(result.is_ok());
+        let calls = calls.borrow();
+        assert!(!calls.is_empty());
+        assert!(calls.windows(2).all(|pair| pair[0].0 <= pair[1].0 && pair[0].1 < pair[1].1));
+        assert!(calls.iter().all(|&(fraction, steps)| (0.0..=1.0).contains(&fraction) && steps % 5.0 == 0.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn throwing_progress_callback_is_ignored() {
+        let callback = js_sys::Function::new_with_args("fraction, steps", "throw new Error('progress bar failed')");
+        set_progress_callback(Some(callback), None, Some(1));
+        let result = run_simulation_js(js_sys::Object::new().into());
+        set_progress_callback(None, None, None);
+        assert!(js_sys::Reflect::get(&result.unwrap(), &"error".into()).unwrap().is_null());
+    }
 }
@@ -31,6 +31,9 @@ Usage:
     ...     f.write(rust_code)
 """
 
+# Set before the imports below; the facade reports it in get_model_metadata
+__version__ = '1.0.0'
+
 from .facade import SbmlToRustConverter
 from .models import SbmlModel, Species, Parameter, Compartment, Reaction, FunctionDefinition
 from .parsers import SbmlExpressionParser, FunctionInliner
@@ -38,7 +41,6 @@ from .symbolic import OdeSystemBuilder, JacobianBuilder, SymbolicOptimizer
 from .codegen import RustCodeGenerator, CustomRustCodePrinter, RustBlockGenerator
 from .utils import IdentifierValidator
 
-__version__ = '1.0.0'
 
 __all__ = [
     # Main facade
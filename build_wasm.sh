@@ -1,13 +1,16 @@
 #!/bin/bash
 set -e
 
-if [ "$#" -ne 2 ]; then
-    echo "Usage: build_wasm.sh <input_rust_file> <output_dir>"
+if [ "$#" -lt 2 ] || [ "$#" -gt 3 ] || { [ "$#" -eq 3 ] && [ "$3" != "--native" ] && [ "$3" != "--python" ] && [ "$3" != "--wasi" ] && [ "$3" != "--minimal" ]; }; then
+    echo "Usage: build_wasm.sh <input_rust_file> <output_dir> [--native | --python | --wasi | --minimal]"
     exit 1
 fi
 
 INPUT_FILE=$1
 OUTPUT_DIR=$2
+MODE=$3
+# Shared code of the generated models, next to this script unless given
+WASM_PK_CORE="${WASM_PK_CORE:-$(cd "$(dirname "$0")" && pwd)/wasm_pk_core}"
 
 # Create a temporary project directory in /tmp (not in mounted volume)
 TEMP_DIR="/tmp/sbml_wasm_build"
@@ -27,24 +30,110 @@ version = "0.1.0"
 edition = "2021"
 
 [lib]
-crate-type = ["cdylib"]
+crate-type = ["cdylib", "rlib"]
+
+[features]
+default = ["wasm"]
+# JS glue for the browser build
+wasm = ["dep:wasm-bindgen", "dep:wasm-bindgen-futures", "dep:js-sys", "dep:web-sys", "dep:serde-wasm-bindgen", "dep:getrandom", "dep:console_error_panic_hook", "wasm_pk_core/wasm"]
+# extern "C" entry points for native hosts
+c-api = []
+# Python module `sbml_model`, built with maturin
+python = ["dep:pyo3"]
+# plain exports of a wasm32-wasip1 module, for hosts such as wasmtime
+wasi = []
+# only the simulation entry points, without the metadata exports
+minimal = []
+# run the wasm-bindgen tests in Node rather than a headless browser
+node-tests = []
 
 [dependencies]
-wasm-bindgen = "0.2"
 serde = { version = "1.0", features = ["derive"] }
 serde_json = "1.0"
 diffsol = "0.6.3"
-getrandom = { version = "0.2", features = ["js"] }
+wasm_pk_core = { path = "$WASM_PK_CORE" }
+pyo3 = { version = "0.23", features = ["extension-module"], optional = true }
+
+# The JS glue is only compiled for wasm32; native builds with `wasm` on skip it
+[target.'cfg(target_arch = "wasm32")'.dependencies]
+wasm-bindgen = { version = "0.2", optional = true }
+wasm-bindgen-futures = { version = "0.4", optional = true }
+js-sys = { version = "0.3", optional = true }
+web-sys = { version = "0.3", features = ["Performance"], optional = true }
+serde-wasm-bindgen = { version = "0.6", optional = true }
+getrandom = { version = "0.2", features = ["js"], optional = true }
+console_error_panic_hook = { version = "0.1", optional = true }
+
+[target.'cfg(target_arch = "wasm32")'.dev-dependencies]
+wasm-bindgen-test = "0.3"
 EOF
 
 # Copy the input file to src/lib.rs
 cp "$INPUT_FILE" src/lib.rs
 
+# --native: a shared library with the C entry points and no JS glue
+if [ "$MODE" = "--native" ]; then
+    echo "Building native library..."
+    cargo build --release --no-default-features --features c-api
+    mkdir -p "$OUTPUT_DIR"
+    cp target/release/libsbml_wasm_project.so target/release/libsbml_wasm_project.dylib \
+        target/release/sbml_wasm_project.dll "$OUTPUT_DIR"/ 2>/dev/null || true
+    cat > "$OUTPUT_DIR/sbml_model.h" <<EOF
+/* C entry points of the generated model. Returned strings are JSON and must be
+   released with wasm_pk_free_string. */
+char *wasm_pk_run_simulation(const char *params_json);
+char *wasm_pk_get_default_parameters(void);
+void wasm_pk_free_string(char *s);
+EOF
+    echo "Build complete! Output in $OUTPUT_DIR"
+    exit 0
+fi
+
+# --python: a wheel of the sbml_model module (needs maturin)
+if [ "$MODE" = "--python" ]; then
+    cat > pyproject.toml <<EOF
+[build-system]
+requires = ["maturin>=1.0,<2.0"]
+build-backend = "maturin"
+
+[project]
+name = "sbml_model"
+version = "0.1.0"
+
+[tool.maturin]
+module-name = "sbml_model"
+no-default-features = true
+features = ["python"]
+EOF
+    echo "Building Python wheel..."
+    mkdir -p "$OUTPUT_DIR"
+    maturin build --release --out "$OUTPUT_DIR"
+    echo "Build complete! Output in $OUTPUT_DIR"
+    exit 0
+fi
+
+# --wasi: a wasm32-wasip1 module with plain exports and no JS glue
+if [ "$MODE" = "--wasi" ]; then
+    echo "Building WASI module..."
+    rustup target add wasm32-wasip1
+    cargo build --release --target wasm32-wasip1 --no-default-features --features wasi
+    mkdir -p "$OUTPUT_DIR"
+    cp target/wasm32-wasip1/release/sbml_wasm_project.wasm "$OUTPUT_DIR/sbml_model.wasm"
+    echo "Build complete! Output in $OUTPUT_DIR"
+    exit 0
+fi
+
 # Build with wasm-pack to a temporary output directory
 echo "Building WASM package..."
 TEMP_OUTPUT="/tmp/wasm_output"
 rm -rf $TEMP_OUTPUT
-wasm-pack build --target web --out-dir "$TEMP_OUTPUT" --out-name "sbml_model"
+# --minimal: the same package without the metadata exports
+FEATURES=""
+if [ "$MODE" = "--minimal" ]; then
+    FEATURES="--features minimal"
+fi
+wasm-pack build --target web --out-dir "$TEMP_OUTPUT" --out-name "sbml_model" -- $FEATURES
+echo "sbml_model_bg.wasm: $(wc -c < "$TEMP_OUTPUT/sbml_model_bg.wasm") bytes"
 
 # Copy the output to the final destination
 echo "Copying output to $OUTPUT_DIR..."
@@ -1,10 +1,230 @@
 # File: sbml_rust_generator/codegen/code_generator.py
 """Generates Rust code blocks from symbolic expressions"""
 
-from typing import List, Tuple, Dict
+import re
+from typing import Any, List, Tuple, Dict, Iterable, Optional, Set
 import sympy
 from codegen.rust_printer import RustCodeGenerator
 
+# diffsol release the generated code is written against (see build_wasm.sh)
+DIFFSOL_VERSION = "0.6.3"
+
+# Exports of WASM builds. The JS glue is behind the crate's `wasm` feature
+# (on by default) and only compiled for wasm32, so the same file also builds
+# as a plain native library or as a module of a native binary.
+JS_GLUE = 'all(feature = "wasm", target_arch = "wasm32")'
+WASM_EXPORT = f'#[cfg_attr({JS_GLUE}, wasm_bindgen)]\n'
+JS_ONLY = f'#[cfg({JS_GLUE})]\n'
+# Metadata exports and their tables, left out by the `minimal` feature of WASM builds
+METADATA_ONLY = '#[cfg(not(feature = "minimal"))]\n'
+
+
+def parameter_defaults(
+    params: Dict[str, float], compartments: Dict[str, float]
+) -> List[Tuple[str, float]]:
+    """Build the table of SimulationParams fields and their default values
+
+    The struct fields, get_parameters_info and get_default_parameters are all
+    generated from this table so they cannot drift apart.
+
+    Args:
+        params: Dictionary of parameters
+        compartments: Dictionary of compartments
+
+    Returns:
+        List of (id, default) pairs; unset values default to 0.0
+    """
+    table = [(p_id, value) for p_id, value in params.items()]
+    table += [(c_id, size) for c_id, size in compartments.items() if c_id not in params]
+    return [(f_id, 0.0 if value is None else float(value)) for f_id, value in table]
+
+
+def rust_literal(text: str) -> str:
+    """Write a string as a Rust string literal
+
+    Args:
+        text: String to embed in the generated code
+
+    Returns:
+        `"..."` with quotes, backslashes and control characters escaped
+    """
+    escaped = "".join(
+        ch if ch.isprintable() and ch not in '"\\' else
+        ("\\" + ch if ch in '"\\' else f"\\u{{{ord(ch):x}}}")
+        for ch in text
+    )
+    return f'"{escaped}"'
+
+
+def rust_raw_string(text: str) -> str:
+    """Write a string as a Rust raw string literal, kept verbatim
+
+    Args:
+        text: String to embed, such as a whole SBML document
+
+    Returns:
+        `r#"..."#` with enough hashes that no `"#...` inside ends it
+    """
+    hashes = "#" * (max((len(run) for run in re.findall(r'"(#*)', text)), default=0) + 1)
+    return f'r{hashes}"{text}"{hashes}'
+
+
+def rust_option(value: Optional[int]) -> str:
+    """Write an optional integer as a Rust `Option` literal
+
+    Args:
+        value: Integer, or None
+
+    Returns:
+        `None`, or `Some(value)`
+    """
+    return "None" if value is None else f"Some({int(value)})"
+
+
+def rust_string(text: Optional[str]) -> str:
+    """Write an optional string as a Rust `Option<&str>` literal
+
+    Args:
+        text: String from the SBML document, or None
+
+    Returns:
+        `None`, or `Some("...")` with quotes, backslashes and control characters escaped
+    """
+    if text is None:
+        return "None"
+    return f"Some({rust_literal(text)})"
+
+
+def model_type(model_name: str) -> str:
+    """Name of the zero-sized type implementing PkModel for a model
+
+    Args:
+        model_name: Name of the model, e.g. talinolol or PBPK_BPA_model
+
+    Returns:
+        Rust type name in UpperCamelCase ending in Model, e.g. TalinololModel
+    """
+    words = [word for word in re.split(r"[^0-9A-Za-z]+", model_name) if word]
+    name = "".join(word[0].upper() + word[1:] for word in words)
+    if not name.endswith("Model"):
+        name += "Model"
+    if name[0].isdigit():
+        name = "Model" + name
+    return name
+
+
+def equation_text(expr: sympy.Expr) -> str:
+    """Write an expression as readable text with its SBML identifiers
+
+    Whole-number floats such as stoichiometric 1.0 are written as integers,
+    so "-1.0*A*k1" reads "-A*k1". SymPy orders the terms itself, so the text
+    only changes when the expression does.
+
+    Args:
+        expr: SymPy expression
+
+    Returns:
+        Expression text
+    """
+    expr = sympy.sympify(expr)
+    whole = {f: sympy.Integer(int(f)) for f in expr.atoms(sympy.Float) if float(f).is_integer()}
+    return str(expr.xreplace(whole))
+
+
+def parameter_range(
+    param_id: str, name: Optional[str] = None, units: Optional[str] = None
+) -> Optional[str]:
+    """Infer the physical range of a parameter from its SBML id, name and units
+
+    The first match wins: hematocrit, fractions (named so, or ids like
+    fSA_exposed, fu_p, F_abs), body weight, then volumes, flows and
+    clearances (litre-based units, or ids like V_epi, QC, CL_renal).
+    "fat volume as fraction of total body weight" is thus a fraction.
+
+    Args:
+        param_id: SBML id
+        name: SBML name, if any
+        units: SBML units id, if any
+
+    Returns:
+        ParameterRange variant name, or None when no range is known
+    """
+    name = (name or "").lower()
+    if param_id.lower() in ("hct", "hematocrit") or "hematocrit" in name:
+        return "BelowOne"
+    if "fraction" in name or re.match(r"(f[A-Z_]|fu|F_)", param_id):
+        return "Fraction"
+    if param_id in ("BW", "BM", "Body_Weight", "BodyWeight") or "body weight" in name:
+        return "Positive"
+    if (
+        re.fullmatch(r"(m?l|litre|liter)(_per_.*)?", units or "", re.IGNORECASE)
+        or re.search(r"volume|flow|clearance", name)
+        or re.match(r"(V|Q|CL)($|_|[A-Z])", param_id)
+    ):
+        return "NonNegative"
+    return None
+
+
+def symbol_names(expressions: Iterable[sympy.Expr]) -> Set[str]:
+    """Collect the names of all free symbols read by some expressions
+
+    Args:
+        expressions: SymPy expressions
+
+    Returns:
+        Set of symbol names
+    """
+    names = set()
+    for expr in expressions:
+        names |= {str(s) for s in getattr(expr, "free_symbols", ())}
+    return names
+
+
+def accumulate(target: str, term: str, factor: str) -> str:
+    """Add term * factor to a Rust place, without a literal unit coefficient
+
+    Args:
+        target: Place to update, e.g. jv[0]
+        term: Rust expression of the coefficient
+        factor: Rust expression multiplied by it, e.g. v[1]
+
+    Returns:
+        Rust statement
+    """
+    if term == "1.0":
+        return f"{target} += {factor};"
+    if term == "-1.0":
+        return f"{target} -= {factor};"
+    return f"{target} += ({term}) * {factor};"
+
+
+def live_bindings(
+    bindings: List[Tuple[str, Optional[sympy.Expr]]], used: Set[str]
+) -> Tuple[List[Tuple[str, Optional[sympy.Expr]]], Set[str]]:
+    """Keep only the let bindings a closure body actually reads
+
+    Walks the bindings backwards so a binding is kept when the body or any
+    later kept binding reads it.
+
+    Args:
+        bindings: (name, expression) pairs in emission order; the expression is
+            None for plain lookups such as species read from y
+        used: Names read by the closure body
+
+    Returns:
+        Tuple of (kept bindings in emission order, all names read)
+    """
+    used = set(used)
+    kept = []
+    for name, expr in reversed(bindings):
+        if str(name) not in used:
+            continue
+        kept.append((name, expr))
+        if expr is not None:
+            used |= symbol_names([expr])
+    kept.reverse()
+    return kept, used
+
 
 class RustBlockGenerator:
     """Generates Rust code blocks for ODE solver"""
@@ -36,6 +256,31 @@ class RustBlockGenerator:
 
         return "\n".join(temp_vars_code)
 
+    def generate_window_terms(
+        self, window_terms: Dict[str, sympy.Expr], window_parameters: Tuple[str, str]
+    ) -> str:
+        """Generate the dose-window terms summed over a run's uptake windows
+
+        Args:
+            window_terms: Mapping of species ID to the terms moved out of its derivative
+            window_parameters: Names of the parameters bounding the window (start, end)
+
+        Returns:
+            Rust code block binding uptake_<species> for each species
+
+        Example:
+            let uptake_A = window_sum(windows, t0, t1, |t0, t1| k*(t1 - t0));
+        """
+        start, end = window_parameters
+        lines = []
+        for species_id, expr in window_terms.items():
+            rust_expr = self.code_gen.generate_code_with_formatting(expr)
+            lines.append(
+                f"        let uptake_{species_id} = window_sum(windows, {start}, {end}, "
+                f"|{start}, {end}| {rust_expr});"
+            )
+        return "\n".join(lines)
+
     def generate_derivatives(self, expressions: List[sympy.Expr]) -> str:
         """Generate derivative calculations (dy[i] = ...)
 
@@ -72,18 +317,50 @@ class RustBlockGenerator:
         for k, (row, col) in enumerate(indices):
             expr = jac_elements[k]
             term = self.code_gen.generate(expr)
-            jac_code.append(f"        jv[{row}] += ({term}) * v[{col}];")
+            jac_code.append(f"        {accumulate(f'jv[{row}]', term, f'v[{col}]')}")
 
         return "\n".join(jac_code)
 
+    def generate_jacobian_values(self, jac_elements: List[sympy.Expr]) -> str:
+        """Generate the nonzero Jacobian entries (values[k] = J[row, col])
+
+        Args:
+            jac_elements: List of non-zero Jacobian elements
+
+        Returns:
+            Rust code block filling values in the order of the elements
+        """
+        return "\n".join(
+            f"        values[{k}] = ({self.code_gen.generate(expr)});"
+            for k, expr in enumerate(jac_elements)
+        )
+
+    def generate_jacobian_pattern(self, indices: List[Tuple[int, int]]) -> str:
+        """Generate the JACOBIAN_PATTERN constant listing the nonzero entries
+
+        Args:
+            indices: List of (row, col) indices for each element
+
+        Returns:
+            Rust const declaration, eight entries per line
+        """
+        entries = [f"({row}, {col})" for row, col in indices]
+        lines = [", ".join(entries[i:i + 8]) + "," for i in range(0, len(entries), 8)]
+        code = ["const JACOBIAN_PATTERN: &[(usize, usize)] = &["]
+        code += [f"    {line}" for line in lines]
+        code.append("];")
+        return "\n".join(code)
+
     def generate_species_extraction(
         self,
-        species_map: Dict[str, int]
+        species_map: Dict[str, int],
+        used: Set[str] = None
     ) -> str:
         """Generate code to extract species from y vector
 
         Args:
             species_map: Dictionary mapping species IDs to indices
+            used: Names read by the closure; other species are not bound
 
         Returns:
             Rust code block with let statements
@@ -95,7 +372,8 @@ class RustBlockGenerator:
         species_extract = []
 
         for s_id, idx in species_map.items():
-            species_extract.append(f"        let {s_id} = y[{idx}];")
+            if used is None or s_id in used:
+                species_extract.append(f"        let {s_id} = y[{idx}];")
 
         return "\n".join(species_extract)
 
@@ -124,66 +402,103 @@ class RustBlockGenerator:
 
         return "\n".join(param_extract)
 
-    def generate_result_vectors_init(self, species_list: List[str]) -> str:
-        """Generate initialization of result vectors
+    def generate_parameter_lookups(
+        self,
+        parameter_index: Dict[str, int],
+        used: Set[str],
+        indent: str = "        ",
+        source: str = "p"
+    ) -> str:
+        """Generate code to read the parameters a closure uses from the parameter vector
 
         Args:
-            species_list: List of species IDs
+            parameter_index: Dictionary mapping parameter names to indices in p
+            used: Names read by the closure; other parameters are not bound
+            indent: Indentation string
+            source: Rust expression holding the parameter vector
 
         Returns:
-            Rust code block initializing Vec::new() for each species
-        """
-        from utils.validators import IdentifierValidator
+            Rust code block with let statements in index order
 
-        init_code = []
-        for species_id in species_list:
-            rust_id = IdentifierValidator.to_rust_identifier(species_id)
-            init_code.append(f"    let mut {rust_id} = Vec::new();")
+        Example:
+            let k1 = p[0];
+            let V = p[2];
+        """
+        lookups = []
+        for name, idx in sorted(parameter_index.items(), key=lambda item: item[1]):
+            if name in used:
+                lookups.append(f"{indent}let {name} = {source}[{idx}];")
 
-        return "\n".join(init_code)
+        return "\n".join(lookups)
 
-    def generate_result_pushes(
-        self,
-        species_list: List[str],
-        indent: str = "    "
-    ) -> str:
-        """Generate code to push current state to result vectors
+    def generate_parameter_values(self, values: List[str]) -> str:
+        """Generate the entries of the parameter vector literal
 
         Args:
-            species_list: List of species IDs
-            indent: Indentation string
+            values: Rust expressions in parameter order
 
         Returns:
-            Rust code block with push statements
+            Rust code block with one comma-terminated value per line
         """
-        from utils.validators import IdentifierValidator
+        return "\n".join(f"        {value}," for value in values)
 
-        pushes = []
-        for i, species_id in enumerate(species_list):
-            rust_id = IdentifierValidator.to_rust_identifier(species_id)
-            pushes.append(f"{indent}{rust_id}.push(solver.state().y[{i}]);")
+    def generate_result_keys(self, species_list: List[str]) -> str:
+        """Generate the species keys of the result in SBML order
 
-        return "\n".join(pushes)
+        Args:
+            species_list: List of species IDs
 
-    def generate_hashmap_inserts(self, species_list: List[str]) -> str:
-        """Generate code to insert species vectors into HashMap
+        Returns:
+            Comma-separated Rust string literals
+        """
+        return ", ".join(f'"{species_id}"' for species_id in species_list)
+
+    def generate_state_parameters(self, state_parameters: List[List[str]]) -> str:
+        """Generate the table of parameters each state's derivative reads
 
         Args:
-            species_list: List of species IDs
+            state_parameters: Parameter names per state, in state order
 
         Returns:
-            Rust code block with HashMap insert statements
+            Rust constant STATE_PARAMETERS
+
+        Example:
+            const STATE_PARAMETERS: [&[&str]; 2] = [&["k1"], &["k1", "k2"]];
         """
-        from utils.validators import IdentifierValidator
+        rows = ", ".join(
+            "&[" + ", ".join(f'"{name}"' for name in names) + "]" for names in state_parameters
+        )
+        return (
+            "/// Parameters read by the derivative of each state, for failure hints\n"
+            f"const STATE_PARAMETERS: [&[&str]; {len(state_parameters)}] = [{rows}];\n\n"
+        )
+
+    def generate_parameter_checks(
+        self, checks: List[Tuple[str, int, str, List[str]]]
+    ) -> str:
+        """Generate the table of physical range checks on the parameter vector
 
-        map_inserts = []
-        for species_id in species_list:
-            rust_id = IdentifierValidator.to_rust_identifier(species_id)
-            map_inserts.append(
-                f'        species_map.insert("{rust_id}".to_string(), {rust_id});'
-            )
+        Args:
+            checks: (name, index in p, ParameterRange variant, parameters the
+                value is computed from) per checked entry; the source list is
+                empty for the model parameters themselves
 
-        return "\n".join(map_inserts)
+        Returns:
+            Rust constant PARAMETER_CHECKS
+
+        Example:
+            const PARAMETER_CHECKS: &[ParameterCheck] = &[
+                ("fSA_exposed", 12, ParameterRange::Fraction, &[]),
+                ("Poor", 40, ParameterRange::NonNegative, &["BM", "scVFat"]),
+            ];
+        """
+        code = ["/// Range of each checked entry of p, with the parameters a derived value reads"]
+        code.append("const PARAMETER_CHECKS: &[ParameterCheck] = &[")
+        for name, idx, range_kind, sources in checks:
+            reads = ", ".join(f'"{source}"' for source in sources)
+            code.append(f'    ("{name}", {idx}, ParameterRange::{range_kind}, &[{reads}]),')
+        code.append("];\n")
+        return "\n".join(code) + "\n"
 
     def generate_initial_assignments(
         self,
@@ -224,12 +539,14 @@ class RustBlockGenerator:
 
     def generate_assignment_rules(
         self,
-        assignment_rules: List[Tuple[str, sympy.Expr]]
+        assignment_rules: List[Tuple[str, sympy.Expr]],
+        indent: str = "    "
     ) -> str:
         """Generate code for assignment rule calculations
 
         Args:
             assignment_rules: List of (variable, expression) tuples in dependency order
+            indent: Indentation string
 
         Returns:
             Rust code block with assignment calculations
@@ -246,42 +563,205 @@ class RustBlockGenerator:
         assignment_code = []
         for variable, expr in assignment_rules:
             rust_expr = self.code_gen.generate_code_with_formatting(expr)
-            assignment_code.append(f"    let {variable} = {rust_expr};")
+            assignment_code.append(f"{indent}let {variable} = {rust_expr};")
 
         return "\n".join(assignment_code)
 
-    def generate_init_function(
+    def generate_observables_function(
         self,
-        species_list: List[str],
         species_map: Dict[str, int],
-        initial_amounts: Dict[str, float]
+        assignment_rules: List[Tuple[str, sympy.Expr]],
+        state_dependent_rules: List[Tuple[str, sympy.Expr]],
+        parameter_index: Dict[str, int]
     ) -> str:
-        """Generate init function with species initial values
+        """Generate a closure evaluating all assignment rules at a given state
+
+        Parameter-only rules are read from the parameter vector; only the
+        state-dependent rules are recomputed from the state slice.
+
+        Args:
+            species_map: Dictionary mapping species IDs to indices
+            assignment_rules: All assignment rules in dependency order
+            state_dependent_rules: Rules that depend on state or time
+            parameter_index: Dictionary mapping parameter names to indices in p
+
+        Returns:
+            Rust code block defining eval_observables
+        """
+        values = ", ".join(variable for variable, _ in assignment_rules)
+
+        bindings = [(s_id, None) for s_id in species_map] + list(state_dependent_rules)
+        kept, used = live_bindings(bindings, {variable for variable, _ in assignment_rules})
+        rules = [(name, expr) for name, expr in kept if expr is not None]
+        state_arg = "y" if len(kept) > len(rules) else "_y"
+        time_arg = "t" if "t" in used else "_t"
+        lookups = self.generate_parameter_lookups(parameter_index, used - set(species_map))
+        param_arg = "p" if lookups else "_p"
+
+        code = []
+        code.append(f"    let eval_observables = |{state_arg}: &[f64], {time_arg}: f64, {param_arg}: &[f64]| -> Vec<f64> {{")
+        if lookups:
+            code.append(lookups)
+        for s_id, idx in species_map.items():
+            if s_id in used:
+                code.append(f"        let {s_id} = y[{idx}];")
+        if rules:
+            code.append(self.generate_assignment_rules(rules, indent="        "))
+        code.append(f"        vec![{values}]")
+        code.append("    };\n")
+
+        return "\n".join(code)
+
+    def generate_observable_ids(self, assignment_rules: List[Tuple[str, sympy.Expr]]) -> str:
+        """Generate the ids of the observables returned by eval_observables
+
+        Args:
+            assignment_rules: All assignment rules in dependency order
+
+        Returns:
+            Rust const declaration of OBSERVABLE_IDS
+        """
+        names = ", ".join(f'"{variable}"' for variable, _ in assignment_rules)
+        return (
+            "/// SBML ids of the assignment-rule observables, in evaluation order\n"
+            f"const OBSERVABLE_IDS: [&str; {len(assignment_rules)}] = [{names}];\n\n"
+        )
+
+    def generate_volume_function(
+        self,
+        species_map: Dict[str, int],
+        species_compartments: Dict[str, Optional[str]],
+        state_dependent_rules: List[Tuple[str, sympy.Expr]],
+        parameter_index: Dict[str, int]
+    ) -> str:
+        """Generate a closure giving the compartment size of each species at a given state
+
+        Sizes computed by state-dependent rules are recomputed from the state
+        slice; the others are read from the parameter vector.
+
+        Args:
+            species_map: Dictionary mapping species IDs to indices
+            species_compartments: Compartment converting each species between
+                amount and concentration, or None when it has none
+            state_dependent_rules: Rules that depend on state or time
+            parameter_index: Dictionary mapping parameter names to indices in p
+
+        Returns:
+            Rust code block defining eval_volumes; species without a
+            compartment of known size get NaN
+        """
+        rule_variables = {variable for variable, _ in state_dependent_rules}
+        values = []
+        for s_id in species_map:
+            compartment = species_compartments.get(s_id)
+            known = compartment in parameter_index or compartment in rule_variables
+            values.append(compartment if known else "f64::NAN")
+
+        bindings = [(s_id, None) for s_id in species_map] + list(state_dependent_rules)
+        kept, used = live_bindings(bindings, set(values))
+        rules = [(name, expr) for name, expr in kept if expr is not None]
+        state_arg = "y" if len(kept) > len(rules) else "_y"
+        time_arg = "t" if "t" in used else "_t"
+        lookups = self.generate_parameter_lookups(parameter_index, used - set(species_map))
+        param_arg = "p" if lookups else "_p"
+
+        code = []
+        code.append(f"    let eval_volumes = |{state_arg}: &[f64], {time_arg}: f64, {param_arg}: &[f64]| -> Vec<f64> {{")
+        if lookups:
+            code.append(lookups)
+        for s_id, idx in species_map.items():
+            if s_id in used:
+                code.append(f"        let {s_id} = y[{idx}];")
+        if rules:
+            code.append(self.generate_assignment_rules(rules, indent="        "))
+        code.append(f"        vec![{', '.join(values)}]")
+        code.append("    };\n")
+
+        return "\n".join(code)
+
+    def generate_mass_function(
+        self,
+        species_map: Dict[str, int],
+        coefficients: Dict[str, sympy.Expr],
+        parameter_index: Dict[str, int]
+    ) -> str:
+        """Generate a closure summing the mass held in a state vector
 
         Args:
-            species_list: List of species IDs
             species_map: Dictionary mapping species IDs to indices
+            coefficients: Mass factor of each state counted; other states are left out
+            parameter_index: Dictionary mapping parameter names to indices in p
+
+        Returns:
+            Rust code block defining eval_mass
+        """
+        terms = []
+        for s_id, factor in coefficients.items():
+            term = f"y[{species_map[s_id]}]"
+            if factor != 1:
+                term += f" * ({self.code_gen.generate(factor)})"
+            terms.append(term)
+
+        used = symbol_names(coefficients.values())
+        lookups = self.generate_parameter_lookups(parameter_index, used)
+        param_arg = "p" if lookups else "_p"
+        state_arg = "y" if terms else "_y"
+
+        code = []
+        code.append(f"    let eval_mass = |{state_arg}: &[f64], {param_arg}: &[f64]| -> f64 {{")
+        if lookups:
+            code.append(lookups)
+        code.append(f"        {' + '.join(terms) or '0.0'}")
+        code.append("    };\n")
+
+        return "\n".join(code)
+
+    def generate_initial_values(
+        self,
+        species_list: List[str],
+        initial_amounts: Dict[str, float],
+        initial_expressions: Dict[str, str] = None
+    ) -> List[str]:
+        """Generate the initial value of each state, honouring init_* overrides
+
+        Args:
+            species_list: List of species IDs
             initial_amounts: Dictionary of species initial amounts from SBML
+            initial_expressions: Rust expressions replacing the SBML default for
+                species set by an initial assignment or a parameter value
+
+        Returns:
+            Rust expressions in state order, e.g. "sim_params.init_QGut.unwrap_or(1.0)"
+        """
+        initial_expressions = initial_expressions or {}
+        values = []
+        for species_id in species_list:
+            default_value = initial_expressions.get(
+                species_id, initial_amounts.get(species_id) or 0.0
+            )
+            values.append(f"sim_params.init_{species_id}.unwrap_or({default_value})")
+
+        return values
+
+    def generate_init_function(self, n_states: int, offset: int) -> str:
+        """Generate the PkModel init function copying the initial state from the parameter vector
+
+        Args:
+            n_states: Number of states
+            offset: Index in p of the first initial value
 
         Returns:
             Rust code block for init function
 
         Example:
-            let init = |_y0: &diffsol::NalgebraVec<f64>, _t: f64, y: &mut diffsol::NalgebraVec<f64>| {
-                y[0] = sim_params.init_QFat.unwrap_or(0.0);
-                y[1] = sim_params.init_QGut.unwrap_or(1.0);
-                ...
-            };
+            fn init(p: &[f64], _t: f64, y: &mut [f64]) {
+                y.copy_from_slice(&p[12..15]);
+            }
         """
         init_code = []
-        init_code.append("    let init = |_y0: &diffsol::NalgebraVec<f64>, _t: f64, y: &mut diffsol::NalgebraVec<f64>| {")
-
-        for species_id in species_list:
-            idx = species_map[species_id]
-            default_value = initial_amounts.get(species_id, 0.0)
-            init_code.append(f"        y[{idx}] = sim_params.init_{species_id}.unwrap_or({default_value});")
-
-        init_code.append("    };\n")
+        init_code.append("    fn init(p: &[f64], _t: f64, y: &mut [f64]) {")
+        init_code.append(f"        y.copy_from_slice(&p[{offset}..{offset + n_states}]);")
+        init_code.append("    }\n")
 
         return "\n".join(init_code)
 
@@ -292,10 +772,31 @@ class RustBlockGenerator:
         species_initial_amounts: Dict[str, float],
         params: Dict[str, float],
         compartments: Dict[str, float],
-        wasm: bool = False
+        wasm: bool = False,
+        observable_units: Optional[Dict[str, Optional[str]]] = None,
+        parameter_info: Optional[Dict[str, Dict[str, Any]]] = None,
+        species_details: Optional[Dict[str, Dict[str, Any]]] = None,
+        compartment_info: Optional[Dict[str, Dict[str, Any]]] = None,
+        provenance: Optional[Dict[str, Any]] = None
     ) -> str:
         """Generate metadata exposure functions for UI/tools
 
+        get_model_metadata also reports where the code comes from: the SBML
+        id, name, level/version and SHA-256 of the source file, the generator
+        name, version and generation time, the diffsol release the code is
+        written against and the optional features compiled in.
+
+        get_parameters_info lists, per SimulationParams field, its SBML name,
+        units and notes (as description), whether it is a compartment size
+        and soft min/max bounds; absent values are null. get_species_info
+        lists each state's units, compartment and its default size, and whether
+        the SBML value is an amount and a boundary condition. The same units
+        and those of the observables fill SERIES_UNITS, which labels the
+        series of each result; SPECIES_IS_AMOUNT and SPECIES_VOLUMES tell
+        output_kind how to convert each species. get_compartments_info
+        lists each compartment with its default size, the rule computing it
+        and the species it contains.
+
         Args:
             model_name: Name of the model
             species_list: List of species IDs
@@ -303,6 +804,20 @@ class RustBlockGenerator:
             params: Dictionary of parameters
             compartments: Dictionary of compartments
             wasm: If True, add wasm_bindgen attribute
+            observable_units: Observable IDs in output order, mapped to their
+                SBML units (None when the SBML gives none)
+            parameter_info: Field IDs mapped to their "name", "units",
+                "description", "min" and "max" (any may be missing or None)
+            species_details: State IDs mapped to their "compartment",
+                "volume_index" (index of the compartment size in p),
+                "is_amount", "is_boundary" and "units"; states without these,
+                such as rate-rule parameters, are amounts with no compartment
+            compartment_info: Compartment IDs mapped to their "name", "units",
+                "size_index" (index of the size in p), "formula" and "species";
+                defaults to the compartment fields with no details
+            provenance: "sbml" (with "id", "name", "level", "version" and
+                "sha256"), "generator" (with "version" and "generated_at")
+                and "features"; anything missing is reported as null
 
         Returns:
             Rust code block with metadata functions
@@ -310,86 +825,339 @@ class RustBlockGenerator:
         code = []
 
         # Add decorator for WASM
-        decorator = "#[wasm_bindgen]\n" if wasm else ""
-
-        # get_model_metadata function
-        code.append(f"{decorator}pub fn get_model_metadata() -> String {{")
-        code.append('    let metadata = serde_json::json!({')
-        code.append(f'        "model_id": "{model_name}",')
-        code.append(f'        "num_species": {len(species_list)},')
-        code.append(f'        "num_parameters": {len(params) + len(compartments)},')
-        code.append('        "time_units": "HR",')
-        code.append('        "substance_units": "MilliMOL",')
-        code.append('        "volume_units": "L"')
-        code.append('    });')
-        code.append('    serde_json::to_string(&metadata).unwrap()')
+        decorator = WASM_EXPORT if wasm else ""
+        gate = METADATA_ONLY if wasm else ""
+
+        defaults = parameter_defaults(params, compartments)
+
+        # get_model_metadata function, built from structs rather than json!
+        # so models with many observables stay within the macro recursion limit
+        provenance = provenance or {}
+        sbml = provenance.get("sbml", {})
+        generator = provenance.get("generator", {})
+        code.append("/// One observable of get_model_metadata")
+        code.append(f"{gate}#[derive(Serialize)]")
+        code.append("struct ObservableInfo {")
+        code.append("    id: &'static str,")
+        code.append("    units: Option<&'static str>,")
+        code.append("}\n")
+        code.append("/// SBML document the model was generated from")
+        code.append(f"{gate}#[derive(Serialize)]")
+        code.append("struct SbmlSource {")
+        code.append("    id: Option<&'static str>,")
+        code.append("    name: Option<&'static str>,")
+        code.append("    level: Option<u32>,")
+        code.append("    version: Option<u32>,")
+        code.append("    /// SHA-256 of the source file")
+        code.append("    sha256: Option<&'static str>,")
+        code.append("}\n")
+        code.append("/// Generator that wrote this module")
+        code.append(f"{gate}#[derive(Serialize)]")
+        code.append("struct GeneratorInfo {")
+        code.append("    name: &'static str,")
+        code.append("    version: Option<&'static str>,")
+        code.append("    /// ISO 8601 UTC time of generation")
+        code.append("    generated_at: Option<&'static str>,")
+        code.append("}\n")
+        code.append(f"{gate}#[derive(Serialize)]")
+        code.append("struct ModelMetadata {")
+        code.append("    model_id: &'static str,")
+        code.append("    num_species: usize,")
+        code.append("    num_parameters: usize,")
+        code.append("    time_units: &'static str,")
+        code.append("    substance_units: &'static str,")
+        code.append("    volume_units: &'static str,")
+        code.append("    sbml: SbmlSource,")
+        code.append("    generator: GeneratorInfo,")
+        code.append("    diffsol_version: &'static str,")
+        code.append("    /// Optional features compiled in: \"events\", \"sensitivities\", \"embedded_sbml\"")
+        code.append("    features: &'static [&'static str],")
+        code.append("    observables: &'static [ObservableInfo],")
+        code.append("}\n")
+        code.append(f"{gate}{decorator}pub fn get_model_metadata() -> String {{")
+        code.append("    let metadata = ModelMetadata {")
+        code.append(f'        model_id: "{model_name}",')
+        code.append(f"        num_species: {len(species_list)},")
+        code.append(f"        num_parameters: {len(defaults)},")
+        code.append('        time_units: "HR",')
+        code.append('        substance_units: "MilliMOL",')
+        code.append('        volume_units: "L",')
+        code.append(
+            f'        sbml: SbmlSource {{ id: {rust_string(sbml.get("id"))}, name: {rust_string(sbml.get("name"))}, '
+            f'level: {rust_option(sbml.get("level"))}, version: {rust_option(sbml.get("version"))}, '
+            f'sha256: {rust_string(sbml.get("sha256"))} }},'
+        )
+        code.append(
+            f'        generator: GeneratorInfo {{ name: "sbml_rust_generator", '
+            f'version: {rust_string(generator.get("version"))}, '
+            f'generated_at: {rust_string(generator.get("generated_at"))} }},'
+        )
+        code.append(f'        diffsol_version: "{DIFFSOL_VERSION}",')
+        features = ", ".join(f'"{feature}"' for feature in provenance.get("features", []))
+        code.append(f"        features: &[{features}],")
+        code.append("        observables: &[")
+        for obs_id, units in (observable_units or {}).items():
+            code.append(f'            ObservableInfo {{ id: "{obs_id}", units: {rust_string(units)} }},')
+        code.append("        ],")
+        code.append("    };")
+        code.append("    serde_json::to_string(&metadata).unwrap()")
+        code.append("}\n")
+
+        # get_parameters_info function, from a table rather than json! so
+        # large models stay within the macro recursion limit
+        code.append("/// One entry of get_parameters_info")
+        code.append(f"{gate}#[derive(Serialize)]")
+        code.append("struct ParameterInfo {")
+        code.append("    id: &'static str,")
+        code.append("    name: Option<&'static str>,")
+        code.append("    units: Option<&'static str>,")
+        code.append("    /// SBML notes as plain text")
+        code.append("    description: Option<&'static str>,")
+        code.append("    default_value: f64,")
+        code.append("    required: bool,")
+        code.append("    is_compartment: bool,")
+        code.append("    /// Soft bounds for input forms; validate_parameters has the hard checks")
+        code.append("    min: Option<f64>,")
+        code.append("    max: Option<f64>,")
+        code.append("}\n")
+        code.append(f"{gate}const PARAMETER_INFO: &[ParameterInfo] = &[")
+        for param_id, default in defaults:
+            info = (parameter_info or {}).get(param_id, {})
+            bounds = [
+                "None" if info.get(key) is None else f"Some({float(info[key])!r})"
+                for key in ("min", "max")
+            ]
+            is_compartment = param_id in compartments and param_id not in params
+            code.append(
+                f'    ParameterInfo {{ id: "{param_id}", name: {rust_string(info.get("name"))}, '
+                f'units: {rust_string(info.get("units"))}, description: {rust_string(info.get("description"))}, '
+                f"default_value: {default!r}, required: true, is_compartment: {str(is_compartment).lower()}, "
+                f"min: {bounds[0]}, max: {bounds[1]} }},"
+            )
+        code.append("];\n")
+        code.append(f"{gate}{decorator}pub fn get_parameters_info() -> String {{")
+        code.append('    serde_json::to_string(PARAMETER_INFO).unwrap()')
         code.append('}\n')
 
-        # get_parameters_info function
-        code.append(f"{decorator}pub fn get_parameters_info() -> String {{")
-        code.append('    let params = serde_json::json!([')
-
-        for param_id, param_value in params.items():
-            # Handle None values
-            value_str = 'null' if param_value is None else str(param_value)
-            code.append('        {')
-            code.append(f'            "id": "{param_id}",')
-            code.append(f'            "default_value": {value_str},')
-            code.append('            "required": true')
-            code.append('        },')
-
-        for comp_id, comp_value in compartments.items():
-            # Handle None values
-            value_str = 'null' if comp_value is None else str(comp_value)
-            code.append('        {')
-            code.append(f'            "id": "{comp_id}",')
-            code.append(f'            "default_value": {value_str},')
-            code.append('            "required": true')
-            code.append('        },')
-
-        if code[-1].endswith(','):
-            code[-1] = code[-1][:-1]  # Remove trailing comma
-
-        code.append('    ]);')
-        code.append('    serde_json::to_string(&params).unwrap()')
-        code.append('}\n')
+        # get_species_info function; compartment sizes may be computed by
+        # rules, so they are read from the parameter vector at the defaults
+        code.append("/// One entry of get_species_info")
+        code.append(f"{gate}#[derive(Serialize)]")
+        code.append("struct SpeciesInfo {")
+        code.append("    id: &'static str,")
+        code.append("    initial_amount: f64,")
+        code.append('    #[serde(rename = "override")]')
+        code.append("    override_field: &'static str,")
+        code.append("    units: Option<&'static str>,")
+        code.append("    compartment: Option<&'static str>,")
+        code.append("    compartment_volume: Option<f64>,")
+        code.append("    /// False when the SBML value is a concentration (hasOnlySubstanceUnits=false)")
+        code.append("    is_amount: bool,")
+        code.append("    is_boundary: bool,")
+        code.append("}\n")
+        code.append(f"{gate}{decorator}pub fn get_species_info() -> String {{")
+        volumes = [
+            (species_details or {}).get(species_id, {}).get("volume_index")
+            for species_id in species_list
+        ]
+        if any(idx is not None for idx in volumes):
+            code.append(f"    let p = parameter_vector(&{model_type(model_name)}::default_params());")
+        # A slice, since serde only serializes arrays of up to 32 elements
+        code.append("    let species: &[SpeciesInfo] = &[")
+
+        for species_id, volume_index in zip(species_list, volumes):
+            details = (species_details or {}).get(species_id, {})
+            init_amount = species_initial_amounts.get(species_id) or 0.0
+            volume = "None" if volume_index is None else f"Some(p[{volume_index}])"
+            code.append(
+                f'        SpeciesInfo {{ id: "{species_id}", initial_amount: {float(init_amount)!r}, '
+                f'override_field: "init_{species_id}", units: {rust_string(details.get("units"))}, '
+                f'compartment: {rust_string(details.get("compartment"))}, compartment_volume: {volume}, '
+                f'is_amount: {str(details.get("is_amount", True)).lower()}, '
+                f'is_boundary: {str(details.get("is_boundary", False)).lower()} }},'
+            )
 
-        # get_species_info function
-        code.append(f"{decorator}pub fn get_species_info() -> String {{")
-        code.append('    let species = serde_json::json!([')
+        code.append('    ];')
+        code.append('    serde_json::to_string(&species).unwrap()')
+        code.append('}\n')
 
+        # Units of the result series, from the same details as get_species_info
+        series = [
+            (species_id, (species_details or {}).get(species_id, {}).get("units"))
+            for species_id in species_list
+        ] + list((observable_units or {}).items())
+        # Native representation of each species and what converts it, for output_kind
+        is_amount = []
+        volumes = []
         for species_id in species_list:
-            init_amount = species_initial_amounts.get(species_id, 0.0)
-            code.append('        {')
-            code.append(f'            "id": "{species_id}",')
-            code.append(f'            "initial_amount": {init_amount},')
-            code.append('            "units": "MilliMOL"')
-            code.append('        },')
+            details = (species_details or {}).get(species_id, {})
+            is_amount.append(str(details.get("is_amount", True)).lower())
+            if details.get("is_boundary"):
+                volumes.append('Err("a boundary species")')
+            elif details.get("compartment") is None:
+                volumes.append('Err("no compartment")')
+            else:
+                volumes.append(f'Ok({rust_literal(details["compartment"])})')
+        code.append("/// Whether each species' values are amounts rather than concentrations")
+        code.append(f"const SPECIES_IS_AMOUNT: [bool; {len(species_list)}] = [{', '.join(is_amount)}];")
+        code.append("/// Compartment converting each species between amount and concentration, or why none does")
+        code.append(f"const SPECIES_VOLUMES: [Result<&str, &str>; {len(species_list)}] = [{', '.join(volumes)}];\n")
+        code.append("/// Unit of each species and observable series; None where the SBML gives none")
+        code.append(f"const SERIES_UNITS: [(&str, Option<&str>); {len(series)}] = [")
+        for series_id, units in series:
+            code.append(f'    ("{series_id}", {rust_string(units)}),')
+        code.append("];\n")
+
+        # get_compartments_info function, sizes read from p as for species
+        if compartment_info is None:
+            compartment_info = {c_id: {} for c_id in compartments}
+        code.append("/// One entry of get_compartments_info")
+        code.append(f"{gate}#[derive(Serialize)]")
+        code.append("struct CompartmentInfo {")
+        code.append("    id: &'static str,")
+        code.append("    name: Option<&'static str>,")
+        code.append("    /// Size at the default parameters; None when it changes during a run")
+        code.append("    size: Option<f64>,")
+        code.append("    /// Rule or initial assignment computing the size, e.g. \"BM*scVLiver\"")
+        code.append("    formula: Option<&'static str>,")
+        code.append("    units: Option<&'static str>,")
+        code.append("    species: &'static [&'static str],")
+        code.append("}\n")
+        code.append(f"{gate}{decorator}pub fn get_compartments_info() -> String {{")
+        if any(info.get("size_index") is not None for info in compartment_info.values()):
+            code.append(f"    let p = parameter_vector(&{model_type(model_name)}::default_params());")
+        code.append("    let compartments: &[CompartmentInfo] = &[")
+        for c_id, info in compartment_info.items():
+            size_index = info.get("size_index")
+            size = "None" if size_index is None else f"Some(p[{size_index}])"
+            members = ", ".join(f'"{s_id}"' for s_id in info.get("species", []))
+            code.append(
+                f'        CompartmentInfo {{ id: "{c_id}", name: {rust_string(info.get("name"))}, size: {size}, '
+                f'formula: {rust_string(info.get("formula"))}, units: {rust_string(info.get("units"))}, '
+                f"species: &[{members}] }},"
+            )
+        code.append("    ];")
+        code.append("    serde_json::to_string(&compartments).unwrap()")
+        code.append("}\n")
+
+        # get_default_parameters function
+        code.append(f"{decorator}pub fn get_default_parameters() -> String {{")
+        # A table rather than json!, whose macro recursion grows with each entry
+        code.append('    let defaults: &[(&str, f64)] = &[')
 
-        if code[-1].endswith(','):
-            code[-1] = code[-1][:-1]  # Remove trailing comma
+        for param_id, default in defaults:
+            code.append(f'        ("{param_id}", {default!r}),')
 
-        code.append('    ]);')
-        code.append('    serde_json::to_string(&species).unwrap()')
+        code.append('        ("final_time", 24.0),')
+        code.append('    ];')
+        code.append('    let defaults: serde_json::Map<String, serde_json::Value> = defaults.iter().map(|&(id, value)| (id.to_string(), value.into())).collect();')
+        code.append('    serde_json::Value::Object(defaults).to_string()')
         code.append('}\n')
 
-        # get_default_parameters function
-        code.append(f"{decorator}pub fn get_default_parameters() -> String {{")
-        code.append('    let defaults = serde_json::json!({')
+        return "\n".join(code)
 
-        for param_id, param_value in params.items():
-            # Handle None values
-            value_str = 'null' if param_value is None else str(param_value)
-            code.append(f'        "{param_id}": {value_str},')
+    def generate_equations_function(
+        self,
+        species_list: List[str],
+        derivatives: List[sympy.Expr],
+        assignment_rules: List[Tuple[str, sympy.Expr]],
+        wasm: bool = False,
+        algebraic_states: Optional[List[str]] = None
+    ) -> str:
+        """Generate get_model_equations, listing the equations before CSE
 
-        for comp_id, comp_value in compartments.items():
-            # Handle None values
-            value_str = 'null' if comp_value is None else str(comp_value)
-            code.append(f'        "{comp_id}": {value_str},')
+        Each state maps to its derivative and each assigned variable to its
+        rule, written with the SBML identifiers. Entries keep model order and
+        the JSON is pretty-printed, so two model versions diff line by line.
+        States fixed by algebraic rules map to the expression held at zero,
+        under algebraic_rules.
 
-        code.append('        "final_time": 24.0')
-        code.append('    });')
-        code.append('    serde_json::to_string(&defaults).unwrap()')
-        code.append('}\n')
+        Args:
+            species_list: State IDs in solver order
+            derivatives: Derivative of each state, before rules are substituted
+            assignment_rules: List of (variable, expression) tuples in dependency order
+            wasm: If True, add wasm_bindgen attribute
+            algebraic_states: States whose entry in derivatives is an algebraic constraint
+
+        Returns:
+            Rust code block with get_model_equations
+
+        Example:
+            Input: ["A"], [-1.0*k1*A], [("V", BW*fV)]
+            Output JSON: {"derivatives": {"A": "-A*k1"}, "assignment_rules": {"V": "BW*fV"}}
+        """
+        decorator = WASM_EXPORT if wasm else ""
+        gate = METADATA_ONLY if wasm else ""
+        code = []
+        code.append("/// Equations in model order, serialized as a JSON object")
+        code.append(f"{gate}struct EquationTable(&'static [(&'static str, &'static str)]);\n")
+        code.append(f"{gate}impl Serialize for EquationTable {{")
+        code.append("    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {")
+        code.append("        use serde::ser::SerializeMap;")
+        code.append("        let mut map = serializer.serialize_map(Some(self.0.len()))?;")
+        code.append("        for (id, expression) in self.0 {")
+        code.append("            map.serialize_entry(id, expression)?;")
+        code.append("        }")
+        code.append("        map.end()")
+        code.append("    }")
+        code.append("}\n")
+        code.append(f"{gate}#[derive(Serialize)]")
+        code.append("struct ModelEquations {")
+        code.append("    derivatives: EquationTable,")
+        code.append("    assignment_rules: EquationTable,")
+        if algebraic_states:
+            code.append("    algebraic_rules: EquationTable,")
+        code.append("}\n")
+        code.append(f"{gate}{decorator}pub fn get_model_equations() -> String {{")
+        code.append("    let equations = ModelEquations {")
+        code.append("        derivatives: EquationTable(&[")
+        rows = list(zip(species_list, derivatives))
+        for s_id, expr in rows:
+            if s_id not in (algebraic_states or []):
+                code.append(f'            ("{s_id}", {rust_literal(equation_text(expr))}),')
+        code.append("        ]),")
+        code.append("        assignment_rules: EquationTable(&[")
+        for variable, expr in assignment_rules:
+            code.append(f'            ("{variable}", {rust_literal(equation_text(expr))}),')
+        code.append("        ]),")
+        if algebraic_states:
+            code.append("        algebraic_rules: EquationTable(&[")
+            for s_id, expr in rows:
+                if s_id in algebraic_states:
+                    code.append(f'            ("{s_id}", {rust_literal(equation_text(expr))}),')
+            code.append("        ]),")
+        code.append("    };")
+        code.append("    serde_json::to_string_pretty(&equations).unwrap()")
+        code.append("}\n")
+        return "\n".join(code)
+
+    def generate_sbml_function(
+        self, document: Optional[str], missing_reason: str, wasm: bool = False
+    ) -> str:
+        """Generate get_model_sbml, returning the SBML the model was generated from
+
+        The document is embedded verbatim as a raw string, so it hashes to
+        the sha256 in get_model_metadata.
 
+        Args:
+            document: Source SBML text, or None when it is not embedded
+            missing_reason: Why no document is embedded, returned as the error
+            wasm: If True, add wasm_bindgen attribute
+
+        Returns:
+            Rust code block with get_model_sbml
+        """
+        decorator = WASM_EXPORT if wasm else ""
+        gate = METADATA_ONLY if wasm else ""
+        code = []
+        if document is not None:
+            code.append("/// Source SBML document, verbatim")
+            code.append(f"{gate}const MODEL_SBML: &str = {rust_raw_string(document)};\n")
+        code.append("/// SBML document this module was generated from")
+        code.append(f"{gate}{decorator}pub fn get_model_sbml() -> Result<String, String> {{")
+        if document is not None:
+            code.append("    Ok(MODEL_SBML.to_string())")
+        else:
+            code.append(f"    Err({rust_literal(missing_reason)}.to_string())")
+        code.append("}\n")
         return "\n".join(code)
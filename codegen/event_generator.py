@@ -1,7 +1,9 @@
 # File: sbml_rust_generator/codegen/event_generator.py
 """Generates Rust code for SBML events using diffsol root finding"""
 
-from typing import Dict, List, Any
+import sympy
+from typing import Dict, List, Any, Tuple
+from codegen.code_generator import live_bindings, symbol_names
 
 
 class EventCodeGenerator:
@@ -11,106 +13,332 @@ class EventCodeGenerator:
         """Initialize with code generator and expression parser
         
         Args:
-            code_generator: RustBlockGenerator instance for expression generation
+            code_generator: RustCodeGenerator instance for expression generation
             expression_parser: SbmlExpressionParser for parsing MathML
         """
         self.code_gen = code_generator
         self.expression_parser = expression_parser
+        self.parameter_index = {}
     
     def generate_event_handling(
         self,
         events: Dict[str, Any],
-        species_map: Dict[str, int]
+        species_map: Dict[str, int],
+        state_dependent_rules: List[Tuple[str, sympy.Expr]] = None,
+        parameter_index: Dict[str, int] = None
     ) -> Dict[str, str]:
         """Generate comprehensive event handling code
         
+        The solver loop itself lives in the template; this produces the
+        closures it calls when a root is found.
+        
         Args:
             events: Dictionary of event data from SBML
             species_map: Mapping of species IDs to indices
+            state_dependent_rules: Assignment rules that must be recomputed from
+                the state before triggers and assignments are evaluated
+            parameter_index: Mapping of parameter names to indices in the
+                parameter vector the closures read them from
             
         Returns:
-            Dictionary with keys: root_fn, event_handling, root_registration
+            Dictionary with keys: root_fn, event_fns, event_consts
         """
         if not events:
             return {}
         
-        # Generate root function for triggers
-        root_fn = self._generate_root_function(events)
+        bindings = [(s_id, None) for s_id in species_map]
+        bindings += list(state_dependent_rules or [])
+        self.parameter_index = {
+            name: idx for name, idx in (parameter_index or {}).items()
+            if name not in species_map
+        }
         
-        # Generate event handling in main loop  
-        event_handling = self._generate_event_callback(events, species_map)
+        # Generate root function for triggers
+        triggers = self._parse_triggers(events)
+        root_fn = self._generate_root_function(triggers, species_map, bindings)
         
-        # Generate root registration for OdeBuilder
-        root_registration = f".root({len(events)}, root_fn)"
+        # Generate trigger evaluation and event assignment closures
+        event_fns = self._generate_trigger_function(triggers, species_map, bindings)
+        event_fns += self._generate_assignment_function(events, species_map, bindings)
         
         return {
             "root_fn": root_fn,
-            "event_handling": event_handling,
-            "root_registration": root_registration
+            "event_fns": event_fns,
+            "event_consts": self._generate_event_consts(events, species_map)
         }
     
-    def _generate_root_function(self, events: Dict[str, Any]) -> str:
-        """Generate root function for event triggers
+    def _generate_event_consts(self, events: Dict[str, Any], species_map: Dict[str, int]) -> str:
+        """Generate the event IDs, assigned species and trigger attributes
+        
+        The ids and targets are used by the event log; the trigger's
+        initialValue and persistent attributes (true when not given, as in
+        SBML Level 2) by the solver loop.
         
         Args:
             events: Dictionary of event data
+            species_map: Mapping of species IDs to indices
             
         Returns:
-            Rust code for root function
+            Rust constants EVENT_IDS, EVENT_TARGETS, EVENT_INITIAL_VALUES and
+            EVENT_PERSISTENT, in event order
         """
-        code = "    let root_fn = |y: &diffsol::NalgebraVec<f64>, _p: &diffsol::NalgebraVec<f64>, t: f64, roots: &mut diffsol::NalgebraVec<f64>| {\n"
+        ids = ", ".join(f'"{event_id}"' for event_id in events)
+        targets = []
+        for event_data in events.values():
+            indices = [
+                species_map[assignment.get("variable")]
+                for assignment in event_data.get("eventAssignments", [])
+                if assignment.get("variable") in species_map and assignment.get("math")
+            ]
+            targets.append("&[" + ", ".join(str(idx) for idx in indices) + "]")
         
-        for idx, (event_id, event_data) in enumerate(events.items()):
+        code = "/// SBML ids of the events, in root function order\n"
+        code += f"const EVENT_IDS: [&str; {len(events)}] = [{ids}];\n"
+        code += "/// State indices assigned by each event\n"
+        code += f"const EVENT_TARGETS: [&[usize]; {len(events)}] = [{', '.join(targets)}];\n"
+        initial_values = ", ".join(
+            str(bool(event_data.get("initialValue", True))).lower() for event_data in events.values()
+        )
+        persistent = ", ".join(
+            str(bool(event_data.get("persistent", True))).lower() for event_data in events.values()
+        )
+        code += "/// Value of each trigger before the start, so a false one fires there if it holds\n"
+        code += f"const EVENT_INITIAL_VALUES: [bool; {len(events)}] = [{initial_values}];\n"
+        code += "/// Whether each event still fires when events firing with it make its trigger false\n"
+        code += f"const EVENT_PERSISTENT: [bool; {len(events)}] = [{persistent}];\n\n"
+        return code
+    
+    def _generate_state_block(
+        self,
+        species_map: Dict[str, int],
+        bindings: List[Tuple[str, sympy.Expr]],
+        exprs: List[sympy.Expr],
+        indent: str,
+        source: str = "y"
+    ) -> Tuple[str, set]:
+        """Generate the parameter, species and state-dependent rule bindings a closure reads
+        
+        Parameters come first since the rules may read them.
+        
+        Args:
+            species_map: Mapping of species IDs to indices
+            bindings: Species (with no expression) followed by state-dependent rules
+            exprs: Expressions evaluated in the closure body
+            indent: Indentation string
+            source: Rust expression holding the state vector
+            
+        Returns:
+            Tuple of (Rust code block with let statements, names read)
+        """
+        kept, used = live_bindings(bindings, symbol_names(exprs))
+        code = ""
+        for name, idx in sorted(self.parameter_index.items(), key=lambda item: item[1]):
+            if name in used:
+                code += f"{indent}let {name} = p[{idx}];\n"
+        for name, expr in kept:
+            if expr is None:
+                code += f"{indent}let {name} = {source}[{species_map[name]}];\n"
+            else:
+                code += f"{indent}let {name} = {self.code_gen.generate(expr)};\n"
+        return code, used
+    
+    def _param_arg(self, used: set) -> str:
+        """Name the parameter vector argument, underscored when nothing is read from it
+        
+        Args:
+            used: Names read by the closure
+            
+        Returns:
+            "p" or "_p"
+        """
+        return "p" if any(name in used for name in self.parameter_index) else "_p"
+    
+    def _parse_triggers(self, events: Dict[str, Any]) -> List[Tuple[str, Any]]:
+        """Parse every event trigger
+        
+        Args:
+            events: Dictionary of event data
+            
+        Returns:
+            List of (event_id, trigger expression) in event order
+            
+        Raises:
+            ValueError: If an event has no trigger or its trigger cannot be parsed
+        """
+        triggers = []
+        for event_id, event_data in events.items():
             trigger = event_data.get("trigger")
             if not trigger:
-                # No trigger - shouldn't happen in valid SBML
-                code += f"        roots[{idx}] = 1.0; // Event {event_id}: no trigger\n"
-                continue
+                raise ValueError(f"Event {event_id} has no trigger")
             
             try:
-                # Parse trigger MathML to SymPy
-                trigger_expr = self.expression_parser.parse(trigger)
-                
-                # Generate Rust code
-                # For boolean expressions like "t > 5.0", we need to convert to root form
-                # Root finding detects sign changes, so we use: trigger - 0.5
-                # This means: when trigger goes from false (0) to true (1), root crosses zero
-                trigger_rust = self.code_gen.generate(trigger_expr)
-                
-                code += f"        // Event {event_id}: trigger\n"
-                code += f"        roots[{idx}] = ({trigger_rust}) as i32 as f64 - 0.5;\n"
-                
+                triggers.append((event_id, self.expression_parser.parse(trigger)))
+            except Exception as e:
+                raise ValueError(f"Could not translate trigger for event {event_id}: {e}") from e
+        return triggers
+    
+    @staticmethod
+    def _unwrap(code: str) -> str:
+        """Drop parentheses that enclose the whole of a Rust expression
+        
+        Args:
+            code: Rust expression
+            
+        Returns:
+            The expression without its enclosing parentheses, if any
+        """
+        if not code.startswith("("):
+            return code
+        depth = 0
+        for i, char in enumerate(code):
+            depth += {"(": 1, ")": -1}.get(char, 0)
+            if depth == 0:
+                return code[1:-1] if i == len(code) - 1 else code
+        return code
+    
+    def _trigger_to_root(self, expr) -> str:
+        """Convert a boolean trigger into a continuous root expression
+        
+        The result is positive exactly when the trigger is true, so the root
+        finder sees a sign change at the crossing instead of a jump: t >= T
+        gives t - T, a comparison of state and time the signed difference of
+        its sides, and/or the min/max of their roots, not the negated root and
+        xor the and/or combination it stands for. Other triggers (equalities,
+        piecewise) fall back to a step of the boolean.
+        
+        Args:
+            expr: SymPy boolean trigger expression
+            
+        Returns:
+            Rust expression for the root function
+        """
+        if isinstance(expr, (sympy.GreaterThan, sympy.StrictGreaterThan)):
+            return f"({self.code_gen.generate(expr.lhs - expr.rhs)})"
+        if isinstance(expr, (sympy.LessThan, sympy.StrictLessThan)):
+            return f"({self.code_gen.generate(expr.rhs - expr.lhs)})"
+        if isinstance(expr, sympy.And):
+            roots = [self._trigger_to_root(arg) for arg in expr.args]
+            return roots[0] + "".join(f".min({self._unwrap(r)})" for r in roots[1:])
+        if isinstance(expr, sympy.Or):
+            roots = [self._trigger_to_root(arg) for arg in expr.args]
+            return roots[0] + "".join(f".max({self._unwrap(r)})" for r in roots[1:])
+        if isinstance(expr, sympy.Not):
+            return f"-({self._trigger_to_root(expr.args[0])})"
+        if isinstance(expr, sympy.Xor):
+            first, rest = expr.args[0], sympy.Xor(*expr.args[1:])
+            return self._trigger_to_root(
+                sympy.Or(sympy.And(first, sympy.Not(rest)), sympy.And(sympy.Not(first), rest), evaluate=False)
+            )
+        if expr == sympy.true:
+            return "1.0"
+        if expr == sympy.false:
+            return "-1.0"
+        
+        # Fallback: step function crossing zero when the trigger becomes true
+        return f"(({self.code_gen.generate(expr)}) as i32 as f64 - 0.5)"
+    
+    def _generate_root_function(
+        self,
+        triggers: List[Tuple[str, Any]],
+        species_map: Dict[str, int],
+        bindings: List[Tuple[str, sympy.Expr]]
+    ) -> str:
+        """Generate root function for event triggers
+        
+        Args:
+            triggers: List of (event_id, trigger expression)
+            species_map: Mapping of species IDs to indices
+            bindings: Species followed by state-dependent rules
+            
+        Returns:
+            Rust code for root function
+            
+        Raises:
+            ValueError: If a trigger cannot be translated into a root expression
+        """
+        state_block, used = self._generate_state_block(
+            species_map, bindings, [expr for _, expr in triggers], "        "
+        )
+        state_arg = "y" if any(s_id in used for s_id in species_map) else "_y"
+        time_arg = "t" if "t" in used else "_t"
+        param_arg = self._param_arg(used)
+        
+        code = f"    let root_fn = |{state_arg}: &[f64], {param_arg}: &[f64], {time_arg}: f64, roots: &mut [f64]| {{\n"
+        code += state_block
+        
+        for idx, (event_id, trigger_expr) in enumerate(triggers):
+            try:
+                root_rust = self._trigger_to_root(trigger_expr)
             except Exception as e:
-                print(f"Warning: Could not parse trigger for event {event_id}: {e}")
-                code += f"        roots[{idx}] = 1.0; // {event_id}: parse error\n"
+                raise ValueError(f"Could not translate trigger for event {event_id}: {e}") from e
+            
+            code += f"        // Event {event_id}: {trigger_expr}\n"
+            code += f"        roots[{idx}] = {root_rust};\n"
+        
+        code += "    };\n\n"
+        return code
+    
+    def _generate_trigger_function(
+        self,
+        triggers: List[Tuple[str, Any]],
+        species_map: Dict[str, int],
+        bindings: List[Tuple[str, sympy.Expr]]
+    ) -> str:
+        """Generate a closure evaluating every trigger as a boolean
+        
+        Args:
+            triggers: List of (event_id, trigger expression)
+            species_map: Mapping of species IDs to indices
+            bindings: Species followed by state-dependent rules
+            
+        Returns:
+            Rust code defining eval_triggers
+        """
+        state_block, used = self._generate_state_block(
+            species_map, bindings, [expr for _, expr in triggers], "        "
+        )
+        state_arg = "y" if any(s_id in used for s_id in species_map) else "_y"
+        time_arg = "t" if "t" in used else "_t"
+        param_arg = self._param_arg(used)
+        values = [self.code_gen.generate(expr) for _, expr in triggers]
         
+        code = f"    let eval_triggers = |{state_arg}: &[f64], {time_arg}: f64, {param_arg}: &[f64]| -> Vec<bool> {{\n"
+        code += state_block
+        code += f"        vec![{', '.join(values)}]\n"
         code += "    };\n\n"
         return code
     
-    def _generate_event_callback(
+    def _generate_assignment_function(
         self,
         events: Dict[str, Any],
-        species_map: Dict[str, int]
+        species_map: Dict[str, int],
+        bindings: List[Tuple[str, sympy.Expr]]
     ) -> str:
-        """Generate event handling code for main solver loop
+        """Generate a closure applying the assignments of the events that fire
+        
+        All assignments are evaluated from the pre-event state, so events
+        firing together do not see each other's updates.
         
         Args:
             events: Dictionary of event data
             species_map: Mapping of species IDs to indices
+            bindings: Species followed by state-dependent rules
             
         Returns:
-            Rust code for event handling in match statement
+            Rust code defining apply_events
+            
+        Raises:
+            ValueError: If an event assignment cannot be parsed
         """
-        code = "            Ok(OdeSolverStopReason::RootFound(root_idx)) => {\n"
-        code += "                console_log!(\"Event triggered at t={}\", solver.state().t);\n"
-        code += "                match root_idx {\n"
-        
+        body = ""
+        exprs = []
         for idx, (event_id, event_data) in enumerate(events.items()):
-            code += f"                    {idx} => {{\n"
-            code += f"                        // Event: {event_id}\n"
-            
-            # Get event assignments
+            body += f"        // Event: {event_id}\n"
             assignments = event_data.get("eventAssignments", [])
+            if not assignments:
+                # Nothing to apply; the event only restarts the solver at its root
+                continue
+            body += f"        if fire[{idx}] {{\n"
             
             for assignment in assignments:
                 variable = assignment.get("variable")
@@ -120,31 +348,37 @@ class EventCodeGenerator:
                     continue
                 
                 try:
-                    # Parse assignment MathML
                     expr = self.expression_parser.parse(math_ml)
                     rust_expr = self.code_gen.generate(expr)
-                    
-                    # Determine if variable is a species or parameter
-                    if variable in species_map:
-                        idx_var = species_map[variable]
-                        code += f"                        solver.state_mut().y[{idx_var}] = {rust_expr};\n"
-                        code += f"                        console_log!(\"  {variable} = {{}}\", {rust_expr});\n"
-                    else:
-                        # It's a parameter - we can't modify parameters during simulation
-                        # This is a limitation, but valid in SBML
-                        code += f"                        // WARNING: Cannot modify parameter {variable} during simulation\n"
-                        code += f"                        console_log!(\"  Skipping parameter assignment: {variable}\");\n"
-                    
                 except Exception as e:
-                    print(f"Warning: Could not parse assignment for {variable}: {e}")
-                    code += f"                        // Parse error for {variable}\n"
+                    raise ValueError(
+                        f"Could not translate assignment to {variable} in event {event_id}: {e}"
+                    ) from e
+                
+                if variable in species_map:
+                    exprs.append(expr)
+                    body += f"            y_new[{species_map[variable]}] = {rust_expr};\n"
+                else:
+                    # Parameters are fixed for the whole simulation
+                    body += f"            // Skipped: assignment to parameter {variable} is not supported\n"
             
-            code += "                    },\n"
+            body += "        }\n"
         
-        code += "                    _ => console_log!(\"Unknown event index: {}\", root_idx),\n"
-        code += "                }\n"
-        code += "            },\n"
+        state_block, used = self._generate_state_block(species_map, bindings, exprs, "        ")
+        time_arg = "t" if "t" in used else "_t"
+        param_arg = self._param_arg(used)
         
+        fire_arg = "fire" if "if fire[" in body else "_fire"
+        code = f"    let apply_events = |y: &[f64], {time_arg}: f64, {fire_arg}: &[bool], {param_arg}: &[f64]| -> Vec<f64> {{\n"
+        code += state_block
+        if fire_arg == "fire":
+            code += "        let mut y_new = y.to_vec();\n"
+            code += body
+            code += "        y_new\n"
+        else:
+            code += body
+            code += "        y.to_vec()\n"
+        code += "    };\n\n"
         return code
     
     def will_implement_full_events(self):
@@ -3,10 +3,52 @@
 
 import re
 import sympy
-from sympy.printing.rust import RustCodePrinter
+from sympy.printing.rust import RustCodePrinter, TypeCast
 from core.base import CodeGenerator
 
 
+def fold_constants(expression):
+    """Evaluate numeric subexpressions ahead of code generation
+
+    Subtrees without symbols (e.g. 1.0**-1, 3229/25, 1/1000000*3229/25) are
+    replaced by a single number, so sympy merges them into the literal
+    coefficient of the surrounding product instead of emitting arithmetic
+    that runs on every RHS call.
+
+    Args:
+        expression: SymPy expression
+
+    Returns:
+        Expression with numeric subtrees folded
+    """
+    if not isinstance(expression, sympy.Basic):
+        return expression
+
+    def is_foldable(e):
+        return (
+            isinstance(e, sympy.Expr)
+            and not e.is_Atom
+            and e.is_number
+            and e.is_finite
+        )
+
+    def fold(e):
+        value = e.evalf(30)
+        if e.is_integer:
+            return sympy.Integer(int(value))
+        return value
+
+    def has_loose_numbers(e):
+        return (
+            isinstance(e, (sympy.Add, sympy.Mul))
+            and sum(arg.is_Number for arg in e.args) > 1
+        )
+
+    # Rebuilding an unevaluated sum or product merges its literal terms
+    folded = expression.replace(is_foldable, fold)
+    return folded.replace(has_loose_numbers, lambda e: e.func(*e.args))
+
+
 class CustomRustCodePrinter(RustCodePrinter):
     """Custom Rust code printer that handles Piecewise and ensures float literals"""
 
@@ -27,7 +69,7 @@ class CustomRustCodePrinter(RustCodePrinter):
 
         # Check if base needs parentheses (for Add, Mul, etc.)
         # This ensures correct operator precedence in Rust
-        needs_parens = isinstance(expr.base, (sympy.Add, sympy.Mul))
+        needs_parens = isinstance(expr.base, (sympy.Add, sympy.Mul, sympy.Piecewise))
         if needs_parens and not base.startswith('('):
             base = f"({base})"
 
@@ -54,7 +96,11 @@ class CustomRustCodePrinter(RustCodePrinter):
             return f"{base}.powf({exp_str})"
 
     def _print_Piecewise(self, expr):
-        """Print a Piecewise expression as nested if-else blocks
+        """Print a Piecewise expression as an if / else if / else chain
+
+        Branch values may be piecewise themselves, giving nested chains. SBML
+        leaves the value undefined when no condition holds and there is no
+        otherwise; the chain then ends in f64::NAN.
 
         Args:
             expr: SymPy Piecewise expression
@@ -62,13 +108,16 @@ class CustomRustCodePrinter(RustCodePrinter):
         Returns:
             Rust if-else chain as string
         """
+        args = list(expr.args)
+        if args[-1][1] != True:
+            args.append((sympy.nan, sympy.true))
         lines = []
-        for i, (e, c) in enumerate(expr.args):
+        for i, (e, c) in enumerate(args):
             if i == 0:
                 # Remove parentheses around condition
                 cond_str = self._print(c)
                 lines.append("if %s {" % cond_str)
-            elif i == len(expr.args) - 1 and c == True:
+            elif i == len(args) - 1 and c == True:
                 lines.append("} else {")
             else:
                 cond_str = self._print(c)
@@ -84,6 +133,102 @@ class CustomRustCodePrinter(RustCodePrinter):
         lines.append("}")
         return "\n".join(lines)
 
+    def _operand(self, expr):
+        """Print an operand, wrapping a piecewise chain in parentheses"""
+        code = self._print(expr)
+        return f"({code})" if isinstance(expr, sympy.Piecewise) else code
+
+    def _print_Relational(self, expr):
+        """Print a comparison of two f64 values
+
+        Integers already print as float literals, so unlike the base printer
+        no operand is cast; piecewise operands are parenthesized.
+        """
+        return f"{self._operand(expr.lhs)} {expr.rel_op} {self._operand(expr.rhs)}"
+
+    @staticmethod
+    def _bound(expr):
+        """Split a comparison into (operand, bound, side, inclusive), or None
+
+        side is "lower" when the bound is below the operand. Only comparisons
+        with a numeric side count, so x >= a stays a comparison of states.
+        """
+        if not isinstance(expr, sympy.core.relational.Relational) or expr.rel_op in ("==", "!="):
+            return None
+        operand, bound, op = expr.lhs, expr.rhs, expr.rel_op
+        if operand.is_Number and not bound.is_Number:
+            operand, bound = bound, operand
+            op = {">=": "<=", ">": "<", "<=": ">=", "<": ">"}[op]
+        if not bound.is_Number or operand.is_Number:
+            return None
+        return operand, bound, "lower" if op in (">=", ">") else "upper", "=" in op
+
+    def _print_And(self, expr):
+        """Print a conjunction, folding a closed-open interval into a range test
+
+        x >= a && x < b prints as (a..b).contains(&x), the form clippy asks
+        for; the remaining operands join with && as in the base printer.
+        """
+        args = sorted(expr.args, key=sympy.default_sort_key)
+        parts = []
+        while args:
+            arg = args.pop(0)
+            lower = self._bound(arg)
+            if lower and lower[2] == "lower" and lower[3]:
+                match = next(
+                    (
+                        other for other in args
+                        if (upper := self._bound(other))
+                        and upper[0] == lower[0]
+                        and upper[2] == "upper"
+                    ),
+                    None,
+                )
+                if match is not None:
+                    args.remove(match)
+                    upper = self._bound(match)
+                    op = "..=" if upper[3] else ".."
+                    operand = self._print(lower[0])
+                    if not lower[0].is_Atom:
+                        operand = f"({operand})"
+                    parts.append(
+                        f"({self._print(lower[1])}{op}{self._print(upper[1])}).contains(&{operand})"
+                    )
+                    continue
+            parts.append(self.parenthesize(arg, sympy.printing.precedence.precedence(expr)))
+        return " && ".join(parts)
+
+    # f64:: call syntax keeps float literal arguments unambiguous in Rust
+    def _print_floor(self, expr):
+        """Print floor as f64::floor"""
+        return f"f64::floor({self._print(expr.args[0])})"
+
+    def _print_ceiling(self, expr):
+        """Print ceiling as f64::ceil"""
+        return f"f64::ceil({self._print(expr.args[0])})"
+
+    # The parser's zero-derivative subclasses; sympy skips renamed subclasses
+    _print_Floor = _print_floor
+    _print_Ceiling = _print_ceiling
+
+    def _print_Max(self, expr):
+        """Print Max as nested f64::max calls"""
+        code = self._print(expr.args[0])
+        for arg in expr.args[1:]:
+            code = f"f64::max({code}, {self._print(arg)})"
+        return code
+
+    def _print_Min(self, expr):
+        """Print Min as nested f64::min calls"""
+        code = self._print(expr.args[0])
+        for arg in expr.args[1:]:
+            code = f"f64::min({code}, {self._print(arg)})"
+        return code
+
+    def _print_Heaviside(self, expr):
+        """Print Heaviside (the derivative of Max and Min) as a step"""
+        return f"(if {self._print(expr.args[0])} > 0.0 {{ 1.0 }} else {{ 0.0 }})"
+
     def _print_Mul(self, expr):
         """Handle multiplication with proper parenthesization
 
@@ -104,13 +249,6 @@ class CustomRustCodePrinter(RustCodePrinter):
         from functools import reduce
         import operator
 
-        # CRITICAL: Check which args are Add BEFORE float casting
-        # (float casting wraps them in TypeCast, hiding the Add)
-        needs_parens = set()
-        for i, arg in enumerate(expr.args):
-            if isinstance(arg, sympy.Add):
-                needs_parens.add(i)
-
         # Handle float casting (from parent implementation)
         contains_floats = any(arg.is_real and not arg.is_integer for arg in expr.args)
         if contains_floats:
@@ -118,17 +256,26 @@ class CustomRustCodePrinter(RustCodePrinter):
                          (self._cast_to_float(arg) if arg != -1 else arg
                           for arg in expr.args))
 
-        # Collect terms, wrapping Add expressions in parentheses
-        # Use the pre-casting check to determine which args need parens
-        terms = []
-        for i, arg in enumerate(expr.args):
-            if i in needs_parens or isinstance(arg, sympy.Add):
-                # Wrap Add/Sub expressions in parentheses to maintain correct precedence
-                terms.append(f"({self._print(arg)})")
-            else:
-                terms.append(self._print(arg))
+        def factor(arg):
+            """Print one factor, wrapping sums in parentheses
 
-        return "*".join(terms)
+            Float casting wraps factors in TypeCast, which hides an Add from
+            the printer, so the cast expression is checked as well.
+            """
+            code = self._print(arg)
+            if isinstance(arg, (sympy.Add, sympy.Piecewise)) or (isinstance(arg, TypeCast) and isinstance(arg.expr, (sympy.Add, sympy.Piecewise))):
+                return f"({code})"
+            return code
+
+        # Multiplying by +-1.0 is a no-op (the sign is emitted by the caller)
+        coeff = expr.args[0]
+        if coeff.is_Number and float(coeff) in (1.0, -1.0):
+            sign = "-" if float(coeff) == -1.0 else ""
+            if not sign and len(expr.args) == 2:
+                return self._print(expr.args[1])
+            return sign + "*".join(factor(arg) for arg in expr.args[1:])
+
+        return "*".join(factor(arg) for arg in expr.args)
 
     def _print_Integer(self, expr):
         """Always print integers as floats in our context
@@ -163,16 +310,36 @@ class CustomRustCodePrinter(RustCodePrinter):
         """
         return "0.0"
 
+    def _print_NaN(self, expr):
+        """Print NaN as f64::NAN"""
+        return "f64::NAN"
+
     def _print_Rational(self, expr):
-        """Print rational numbers as float division
+        """Print rational numbers as a single float literal
 
         Args:
             expr: SymPy Rational
 
         Returns:
-            Rust division expression
+            Float literal string
+        """
+        return self._print_Float(sympy.Float(expr, 30))
+
+    def _print_Float(self, expr, _type=False):
+        """Print floats as the shortest literal that round-trips to the f64
+
+        Args:
+            expr: SymPy Float
+
+        Returns:
+            Float literal string
         """
-        return f"{float(expr.p)}/{float(expr.q)}"
+        value = float(expr)
+        if value != value:
+            return "f64::NAN"
+        if value in (float("inf"), float("-inf")):
+            return "f64::INFINITY" if value > 0 else "f64::NEG_INFINITY"
+        return repr(value)
 
 
 class RustCodeGenerator(CodeGenerator):
@@ -191,7 +358,7 @@ class RustCodeGenerator(CodeGenerator):
         Returns:
             Rust code string
         """
-        return self.printer.doprint(expression)
+        return self.printer.doprint(fold_constants(expression))
 
     def generate_code_with_formatting(self, expression) -> str:
         """Generate Rust code and handle multiline formatting
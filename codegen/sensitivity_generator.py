@@ -0,0 +1,380 @@
+# File: sbml_rust_generator/codegen/sensitivity_generator.py
+"""Generates Rust code for forward sensitivity analysis with diffsol"""
+
+import sympy
+from typing import Dict, List, Tuple, Union
+from codegen.code_generator import WASM_EXPORT, accumulate, live_bindings, symbol_names
+
+
+class SensitivityCodeGenerator:
+    """Generates run_simulation_with_sensitivities for a model
+
+    The regular closures capture parameters as constants. Here every
+    parameter is read from diffsol's parameter vector `p` instead, so the
+    solver can integrate dY/dp alongside the states. Only the parameters named
+    in `wrt` are placed in `p`; the others keep their value from SimulationParams.
+    """
+
+    def __init__(self, code_generator, optimizer):
+        """Initialize with code generator and CSE optimizer
+
+        Args:
+            code_generator: RustCodeGenerator instance for expression generation
+            optimizer: SymbolicOptimizer shared with the main pipeline
+        """
+        self.code_gen = code_generator
+        self.optimizer = optimizer
+
+    def generate_sensitivity_function(
+        self,
+        ode_system: List[sympy.Expr],
+        jacobian_elements: List[sympy.Expr],
+        jac_indices: List[Tuple[int, int]],
+        species_map: Dict[str, int],
+        parameters: List[str],
+        parameter_bindings: List[Tuple[str, sympy.Expr]],
+        initial_values: Dict[str, Union[sympy.Expr, float]],
+        model_type: str,
+        wasm: bool = True,
+    ) -> Dict[str, str]:
+        """Generate the sensitivity entry point and its closures
+
+        Args:
+            ode_system: dy/dt expressions with state-dependent rules inlined
+            jacobian_elements: Non-zero elements of d(dy/dt)/dy
+            jac_indices: (row, col) index of each Jacobian element
+            species_map: Mapping of species IDs to indices
+            parameters: SimulationParams fields, in sensitivity parameter order
+            parameter_bindings: Parameter rules and initial assignments in
+                dependency order (the top-level `let` block of simulate)
+            initial_values: Initial value of every state, either a number or an
+                expression over the bindings
+            model_type: Name of the model's PkModel type, which parses the parameters
+            wasm: If True, export the entry point with wasm_bindgen
+
+        Returns:
+            Dictionary with key: sensitivity_fn
+
+        Raises:
+            ValueError: If the equations read a symbol that is not a parameter,
+                state or t
+        """
+        # Express everything in terms of the SimulationParams fields
+        expanded = {}
+        for variable, expr in parameter_bindings:
+            expanded[sympy.Symbol(variable)] = expr.xreplace(expanded)
+        non_state = {
+            sym: expr for sym, expr in expanded.items() if str(sym) not in species_map
+        }
+        rhs = [expr.xreplace(non_state) for expr in ode_system]
+        jac = [expr.xreplace(non_state) for expr in jacobian_elements]
+
+        unknown = symbol_names(rhs + jac) - set(parameters) - set(species_map) - {"t"}
+        if unknown:
+            raise ValueError(
+                "Sensitivities need the equations in terms of parameters; "
+                f"cannot resolve {sorted(unknown)}"
+            )
+
+        # A parameter that is also a state (rate-rule target) only seeds y0
+        sens_elements, sens_indices = [], []
+        for i, expr in enumerate(rhs):
+            for j, name in enumerate(parameters):
+                if name in species_map:
+                    continue
+                derivative = sympy.diff(expr, sympy.Symbol(name))
+                if derivative != 0:
+                    sens_elements.append(derivative)
+                    sens_indices.append((i, j))
+
+        init_exprs = []
+        for species_id in species_map:
+            value = initial_values.get(species_id, 0.0)
+            init_exprs.append(sympy.sympify(value).xreplace(expanded))
+        init_sens_elements, init_sens_indices = [], []
+        for i, expr in enumerate(init_exprs):
+            for j, name in enumerate(parameters):
+                derivative = sympy.diff(expr, sympy.Symbol(name))
+                if derivative != 0:
+                    init_sens_elements.append(derivative)
+                    init_sens_indices.append((i, j))
+
+        replacements, reduced = self.optimizer.optimize(rhs + jac + sens_elements)
+        reduced_rhs = reduced[:len(rhs)]
+        reduced_jac = reduced[len(rhs):len(rhs) + len(jac)]
+        reduced_sens = reduced[len(rhs) + len(jac):]
+        bindings = [(s_id, None) for s_id in species_map] + list(replacements)
+
+        code = self._generate_types(parameters)
+        code += self._generate_entry(
+            species_map, parameters, bindings,
+            reduced_rhs, reduced_jac, jac_indices, reduced_sens, sens_indices,
+            init_exprs, init_sens_elements, init_sens_indices, model_type, wasm
+        )
+        return {"sensitivity_fn": code}
+
+    def _generate_types(self, parameters: List[str]) -> str:
+        """Generate the parameter table and the result types"""
+        names = ", ".join(f'"{name}"' for name in parameters)
+        return (
+            "/// Parameters run_simulation_with_sensitivities can differentiate with respect to\n"
+            f"pub const SENSITIVITY_PARAMETERS: &[&str] = &[{names}];\n\n"
+            "#[derive(Serialize, Deserialize)]\n"
+            "pub struct ParameterSensitivity {\n"
+            "    pub parameter: String,\n"
+            "    pub species: OrderedSeries,\n"
+            "}\n\n"
+            "#[derive(Serialize, Deserialize)]\n"
+            "pub struct SensitivityResult {\n"
+            "    pub time: Vec<f64>,\n"
+            "    pub species: OrderedSeries,\n"
+            "    pub sensitivities: Vec<ParameterSensitivity>,\n"
+            "    pub error: Option<ErrorPayload>,\n"
+            "}\n\n"
+        )
+
+    def _generate_closure_body(
+        self,
+        species_map: Dict[str, int],
+        parameters: List[str],
+        bindings: List[Tuple[str, sympy.Expr]],
+        exprs: List[sympy.Expr],
+        indent: str,
+        reads_state: bool = True,
+    ) -> Tuple[str, set]:
+        """Generate the parameter, species and temporary bindings a closure reads
+
+        Args:
+            species_map: Mapping of species IDs to indices
+            parameters: Sensitivity parameters in p order
+            bindings: Species (with no expression) followed by CSE temporaries
+            exprs: Expressions evaluated in the closure body
+            indent: Indentation string
+            reads_state: If False (initial conditions), a parameter that is also
+                a state is read from p rather than y
+
+        Returns:
+            Tuple of (Rust code block with let statements, names read)
+        """
+        kept, used = live_bindings(bindings, symbol_names(exprs))
+        code = ""
+        for j, name in enumerate(parameters):
+            if name in used and not (reads_state and name in species_map):
+                code += f"{indent}let {name} = theta(p, {j});\n"
+        for name, expr in kept:
+            if expr is None:
+                code += f"{indent}let {name} = y[{species_map[name]}];\n"
+            else:
+                code += f"{indent}let {name} = {self.code_gen.generate(expr)};\n"
+        return code, used
+
+    def _closure_args(self, species_map, parameters, used, reads_state=True) -> Tuple[str, str, str]:
+        """Name the state, parameter and time arguments, prefixing unused ones"""
+        reads_y = reads_state and any(name in used for name in species_map)
+        reads_p = any(
+            name in used and not (reads_state and name in species_map)
+            for name in parameters
+        )
+        return (
+            "y" if reads_y else "_y",
+            "p" if reads_p else "_p",
+            "t" if "t" in used else "_t",
+        )
+
+    def _generate_entry(
+        self,
+        species_map, parameters, bindings,
+        rhs, jac, jac_indices, sens, sens_indices,
+        init_exprs, init_sens, init_sens_indices, model_type, wasm,
+    ) -> str:
+        """Generate run_simulation_with_sensitivities"""
+        n_states = len(species_map)
+        species_list = list(species_map)
+        code = []
+        code.append(
+            "/// Integrate the states together with dY/dp for the parameters named in `wrt`\n"
+        )
+        if wasm:
+            code.append(WASM_EXPORT)
+        code.append(
+            "pub fn run_simulation_with_sensitivities(params: &str, wrt: Vec<String>) -> Result<String, String> {\n"
+        )
+        code.append(f"    let sim_params = {model_type}::parse_params(params)?;\n")
+        code.append(
+            "    let output_scale = if sim_params.options.convert_output_time.unwrap_or(false) { time_scale(&sim_params) } else { 1.0 };\n"
+        )
+        code.append("    let sim_params = in_model_time(sim_params);\n")
+        base = ", ".join(f"sim_params.{name}" for name in parameters)
+        code.append(f"    let base: [f64; {len(parameters)}] = [{base}];\n")
+        code.append(
+            f"    let mut slot: [Option<usize>; {len(parameters)}] = [None; {len(parameters)}];\n"
+        )
+        code.append("    let mut p0 = Vec::with_capacity(wrt.len());\n")
+        code.append("    for (k, name) in wrt.iter().enumerate() {\n")
+        code.append(
+            "        let j = SENSITIVITY_PARAMETERS.iter().position(|p| p == name)\n"
+        )
+        code.append(
+            '            .ok_or_else(|| format!("unknown sensitivity parameter {}", name))?;\n'
+        )
+        code.append("        if slot[j].replace(k).is_some() {\n")
+        code.append(
+            '            return Err(format!("duplicate sensitivity parameter {}", name));\n'
+        )
+        code.append("        }\n")
+        code.append("        p0.push(base[j]);\n")
+        code.append("    }\n")
+        code.append("    if wrt.is_empty() {\n")
+        code.append(
+            '        return Err("wrt must name at least one parameter".to_string());\n'
+        )
+        code.append("    }\n")
+        code.append(
+            f"    let theta = |p: &V, j: usize| -> f64 {{\n"
+        )
+        code.append("        match slot[j] {\n")
+        code.append("            Some(k) => p[k],\n")
+        code.append("            None => base[j],\n")
+        code.append("        }\n")
+        code.append("    };\n\n")
+
+        # RHS
+        body, used = self._generate_closure_body(
+            species_map, parameters, bindings, rhs, "        "
+        )
+        y, p, t = self._closure_args(species_map, parameters, used)
+        code.append(
+            f"    let rhs = |{y}: &V, {p}: &V, {t}: f64, dy: &mut V| {{\n"
+        )
+        code.append(body)
+        for i, expr in enumerate(rhs):
+            code.append(f"        dy[{i}] = {self.code_gen.generate(expr)};\n")
+        code.append("    };\n\n")
+
+        # Jacobian-vector product
+        body, used = self._generate_closure_body(
+            species_map, parameters, bindings, jac, "        "
+        )
+        y, p, t = self._closure_args(species_map, parameters, used)
+        v = "v" if jac else "_v"
+        code.append(
+            f"    let jac = |{y}: &V, {p}: &V, {t}: f64, {v}: &V, jv: &mut V| {{\n"
+        )
+        code.append("        jv.fill(0.0);\n")
+        code.append(body)
+        for (i, j), expr in zip(jac_indices, jac):
+            code.append(f"        {accumulate(f'jv[{i}]', self.code_gen.generate(expr), f'v[{j}]')}\n")
+        code.append("    };\n\n")
+
+        # Parameter-derivative-vector product, restricted to the wrt parameters
+        body, used = self._generate_closure_body(
+            species_map, parameters, bindings, sens, "        "
+        )
+        y, p, t = self._closure_args(species_map, parameters, used)
+        v = "v" if sens else "_v"
+        code.append(
+            f"    let rhs_sens = |{y}: &V, {p}: &V, {t}: f64, {v}: &V, jv: &mut V| {{\n"
+        )
+        code.append("        jv.fill(0.0);\n")
+        code.append(body)
+        for (i, j), expr in zip(sens_indices, sens):
+            code.append(f"        if let Some(k) = slot[{j}] {{\n")
+            code.append(f"            {accumulate(f'jv[{i}]', self.code_gen.generate(expr), 'v[k]')}\n")
+            code.append("        }\n")
+        code.append("    };\n\n")
+
+        # Initial state and its parameter derivative; init_* overrides are constants
+        body, used = self._generate_closure_body(
+            species_map, parameters, [], init_exprs, "        ", reads_state=False
+        )
+        _, p, _ = self._closure_args(species_map, parameters, used, reads_state=False)
+        code.append(f"    let init = |{p}: &V, _t: f64, y: &mut V| {{\n")
+        code.append(body)
+        for i, expr in enumerate(init_exprs):
+            code.append(
+                f"        y[{i}] = sim_params.init_{species_list[i]}.unwrap_or({self.code_gen.generate(expr)});\n"
+            )
+        code.append("    };\n")
+        body, used = self._generate_closure_body(
+            species_map, parameters, [], init_sens, "        ", reads_state=False
+        )
+        _, p, _ = self._closure_args(species_map, parameters, used, reads_state=False)
+        v = "v" if init_sens else "_v"
+        code.append(
+            f"    let init_sens = |{p}: &V, _t: f64, {v}: &V, y: &mut V| {{\n"
+        )
+        code.append("        y.fill(0.0);\n")
+        code.append(body)
+        for (i, j), expr in zip(init_sens_indices, init_sens):
+            code.append(
+                f"        if let (None, Some(k)) = (sim_params.init_{species_list[i]}, slot[{j}]) {{\n"
+            )
+            code.append(f"            {accumulate(f'y[{i}]', self.code_gen.generate(expr), 'v[k]')}\n")
+            code.append("        }\n")
+        code.append("    };\n\n")
+
+        code.append("    let problem = OdeBuilder::<M>::new()\n")
+        code.append("        .p(p0)\n")
+        code.append("        .t0(sim_params.options.t_start.unwrap_or(0.0))\n")
+        code.append("        .rtol(sim_params.options.rtol.unwrap_or(1e-6))\n")
+        code.append("        .atol([sim_params.options.atol.unwrap_or(1e-6)])\n")
+        code.append("        .rhs_sens_implicit(rhs, jac, rhs_sens)\n")
+        code.append(f"        .init_sens(init, init_sens, {n_states})\n")
+        code.append("        .build()\n")
+        code.append("        .map_err(|e| e.to_string())?;\n")
+        code.append(
+            "    let mut solver = problem.bdf_sens::<LS>().map_err(|e| e.to_string())?;\n\n"
+        )
+
+        code.append("    let mut time = Vec::new();\n")
+        code.append(f"    let mut states: Vec<Vec<f64>> = vec![Vec::new(); {n_states}];\n")
+        code.append(
+            f"    let mut sens: Vec<Vec<Vec<f64>>> = vec![vec![Vec::new(); {n_states}]; wrt.len()];\n"
+        )
+        code.append("    let mut error = None;\n")
+        code.append("    let final_time = sim_params.options.final_time.unwrap_or(24.0);\n")
+        code.append("    solver.set_stop_time(final_time).map_err(|e| e.to_string())?;\n")
+        code.append("    loop {\n")
+        code.append("        let state = solver.state();\n")
+        code.append("        time.push(state.t / output_scale);\n")
+        code.append("        for (i, series) in states.iter_mut().enumerate() {\n")
+        code.append("            series.push(state.y[i]);\n")
+        code.append("        }\n")
+        code.append("        for (s, series) in state.s.iter().zip(sens.iter_mut()) {\n")
+        code.append("            for (i, values) in series.iter_mut().enumerate() {\n")
+        code.append("                values.push(s[i]);\n")
+        code.append("            }\n")
+        code.append("        }\n")
+        code.append("        if state.t >= final_time {\n")
+        code.append("            break;\n")
+        code.append("        }\n")
+        code.append("        if let Err(e) = solver.step() {\n")
+        code.append(
+            "            error = Some(MODEL.solver_error(e.to_string(), solver.state().t, solver.state().y.as_slice()).payload());\n"
+        )
+        code.append("            break;\n")
+        code.append("        }\n")
+        code.append("    }\n\n")
+
+        code.append("    let named = |series: Vec<Vec<f64>>| {\n")
+        code.append("        let mut map = OrderedSeries::default();\n")
+        code.append("        for (name, values) in SPECIES_IDS.iter().zip(series) {\n")
+        code.append("            map.insert(name.to_string(), values);\n")
+        code.append("        }\n")
+        code.append("        map\n")
+        code.append("    };\n")
+        code.append("    let result = SensitivityResult {\n")
+        code.append("        time,\n")
+        code.append("        species: named(states),\n")
+        code.append("        sensitivities: wrt\n")
+        code.append("            .into_iter()\n")
+        code.append("            .zip(sens)\n")
+        code.append(
+            "            .map(|(parameter, series)| ParameterSensitivity { parameter, species: named(series) })\n"
+        )
+        code.append("            .collect(),\n")
+        code.append("        error,\n")
+        code.append("    };\n")
+        code.append("    serde_json::to_string(&result).map_err(|e| e.to_string())\n")
+        code.append("}\n\n")
+        return "".join(code)
@@ -1,7 +1,89 @@
 # File: sbml_rust_generator/codegen/template_manager.py
 """Manages Rust code templates and file assembly"""
 
-from typing import Dict, List, Tuple
+from typing import Any, Dict, List, Optional, Tuple
+from codegen.code_generator import JS_GLUE, JS_ONLY, METADATA_ONLY, WASM_EXPORT, model_type, parameter_defaults, rust_raw_string, rust_string
+from utils.units import TIME_UNIT_SECONDS
+
+# Number fields also accept numeric strings such as "75" or "1e-3"
+LENIENT_F64 = '    #[serde(deserialize_with = "lenient_f64::deserialize")]\n'
+LENIENT_OPTION_F64 = '    #[serde(default, deserialize_with = "lenient_f64::option")]\n'
+
+# SimulationParams fields after the model's own, as (name, Rust type, doc
+# comment); a None name starts a commented group. The fields are those of
+# SimulationOptions in wasm_pk_core, plus uptake_windows for window models;
+# the TypeScript interface of get_types_ts is built from this table.
+SIMULATION_OPTIONS = [
+    ("t_start", "Option<f64>", "Start time; doses, events and output_times share its clock (default 0)"),
+    ("final_time", "Option<f64>", None),
+    (
+        "time_unit", "Option<TimeUnit>",
+        "Unit of the times and step sizes given here (default the model's time units)",
+    ),
+    ("convert_output_time", "Option<bool>", "Return times in time_unit as well (default false)"),
+    ("output_times", "Option<Vec<f64>>", None),
+    (
+        "output_dt", "Option<f64>",
+        "Record the state every output_dt from t_start, interpolated, instead of every step",
+    ),
+    ("max_output_points", "Option<usize>", None),
+    ("include_observables", "Option<bool>", None),
+    (
+        "output_kind", "Option<OutputKind>",
+        "Also return each species as amounts, concentrations or both (default neither)",
+    ),
+    (
+        "include_parameters", "Option<bool>",
+        "Echo the resolved parameters in the result (default true)",
+    ),
+    ("mass_balance", "Option<bool>", "Report the total mass at each output point (default false)"),
+    (
+        "validate", "Option<bool>",
+        "Check physical parameter ranges first and refuse to run on errors (default false)",
+    ),
+    ("doses", "Option<Vec<Dose>>", None),
+    ("uptake_windows", "Option<Vec<UptakeWindow>>", None),
+    (None, None, "Solver options (diffsol defaults when absent)"),
+    ("rtol", "Option<f64>", None),
+    ("atol", "Option<f64>", None),
+    ("max_steps", "Option<usize>", "Internal steps before a run is stopped (default 1e6)"),
+    ("max_step", "Option<f64>", "Upper bound on the internal step size"),
+    ("initial_step", "Option<f64>", "First step size instead of diffsol's estimate"),
+    ("check_finite", "Option<bool>", "Stop on a NaN or infinite state (default true)"),
+    ("clamp_nonnegative", "Option<bool>", "Project negative states to zero after each step (default false)"),
+    (
+        "clamp_threshold", "Option<f64>",
+        "Negative values beyond -clamp_threshold are errors instead (default none)",
+    ),
+    ("solver", "Option<SolverMethod>", "Integration method (default bdf)"),
+    (
+        "jacobian", "Option<JacobianMode>",
+        "J·v from the generated Jacobian or from finite differences (default analytic)",
+    ),
+    (None, None, "Steady-state mode: final_time becomes the maximum time"),
+    ("mode", "Option<SimulationMode>", None),
+    ("steady_state_tol", "Option<f64>", None),
+    ("steady_state_exclude", "Option<Vec<String>>", None),
+    ("steady_state_min_time", "Option<f64>", None),
+]
+
+
+def typescript_type(rust_type: str) -> str:
+    """Translate a SimulationParams field type to TypeScript
+
+    Args:
+        rust_type: Rust type such as "Option<Vec<f64>>"
+
+    Returns:
+        TypeScript type, e.g. "number[] | null"; other types keep their name
+    """
+    if rust_type.startswith("Option<"):
+        return f"{typescript_type(rust_type[7:-1])} | null"
+    if rust_type.startswith("Vec<"):
+        return f"{typescript_type(rust_type[4:-1])}[]"
+    return {"f64": "number", "usize": "number", "bool": "boolean", "String": "string"}.get(
+        rust_type, rust_type
+    )
 
 
 class RustTemplateManager:
@@ -24,33 +106,236 @@ class RustTemplateManager:
             species_list: List of species IDs
             params: Dictionary of parameters
             compartments: Dictionary of compartments
-            species_initial_amounts: Dictionary of species initial amounts (optional)
+            species_initial_amounts: Dictionary of species initial amounts (optional,
+                kept for compatibility; every species gets an init_* override)
 
         Returns:
             Tuple of (species_fields, param_fields)
         """
         # Species field (HashMap)
-        species_fields = "    pub species: std::collections::HashMap<String, Vec<f64>>,"
+        species_fields = "    pub species: OrderedSeries,"
 
-        # Parameter and compartment fields
+        # Parameter and compartment fields, from the same table as the defaults
         param_fields = ""
-        for p in params:
-            param_fields += f"    pub {p}: f64,\n"
-
-        for c in compartments:
-            if c not in params:  # Avoid duplicates
-                param_fields += f"    pub {c}: f64,\n"
+        for field_id, _ in parameter_defaults(params, compartments):
+            param_fields += LENIENT_F64
+            param_fields += f"    pub {field_id}: f64,\n"
 
         # Add initial amount fields for each species (optional, for runtime dosing)
-        if species_initial_amounts:
+        if species_list:
             param_fields += "\n    // Initial amounts (optional, for runtime dosing)\n"
             for species_id in species_list:
+                param_fields += LENIENT_OPTION_F64
                 param_fields += f"    pub init_{species_id}: Option<f64>,\n"
 
         return species_fields, param_fields
 
+    def generate_types_function(
+        self,
+        model_name: str,
+        species_list: List[str],
+        params: Dict[str, float],
+        compartments: Dict[str, float],
+        observable_ids: List[str],
+        has_windows: bool = False,
+        wasm: bool = False,
+        algebraic_states: Optional[List[str]] = None,
+    ) -> str:
+        """Generate get_types_ts, returning TypeScript definitions for the model
+
+        SimulationParams lists the same fields as the Rust struct, from the
+        same tables; species and observable keys are string literal unions, so
+        a misspelt name fails to compile in a TypeScript client. Generated
+        tests compare every interface with the JSON the module produces.
+
+        Args:
+            model_name: Name of the model
+            species_list: State IDs in solver order
+            params: Dictionary of parameters
+            compartments: Dictionary of compartments
+            observable_ids: Observable IDs in output order
+            has_windows: If True, the model takes uptake_windows
+            wasm: If True, add wasm_bindgen attribute
+            algebraic_states: States fixed by algebraic rules, listed by
+                get_model_equations under algebraic_rules
+
+        Returns:
+            Rust code block with get_types_ts
+        """
+        def union(names):
+            return " | ".join(f'"{name}"' for name in names) or "never"
+
+        ts = []
+        ts.append(f"// TypeScript definitions for {model_name}, generated with the module\n\n")
+        ts.append(f"export type SpeciesId = {union(species_list)};\n")
+        ts.append(f"export type ObservableId = {union(observable_ids)};\n")
+        ts.append('export type SolverMethod = "bdf" | "tr_bdf2";\n')
+        ts.append('export type JacobianMode = "analytic" | "finite_difference";\n')
+        ts.append('export type SimulationMode = "transient" | "steady_state";\n')
+        ts.append(f"export type TimeUnit = {union(TIME_UNIT_SECONDS)};\n")
+        ts.append('export type OutputKind = "amount" | "concentration" | "both";\n\n')
+        ts.append("export interface Dose {\n")
+        ts.append("  time: number;\n")
+        ts.append("  amount: number;\n")
+        ts.append("  target: SpeciesId;\n")
+        ts.append("  units?: string | null;\n")
+        ts.append("}\n\n")
+        if has_windows:
+            ts.append("export interface UptakeWindow {\n")
+            ts.append("  t0: number;\n")
+            ts.append("  t1: number;\n")
+            ts.append("  fraction: number;\n")
+            ts.append("}\n\n")
+        ts.append("/** Input of run_simulation; omitted fields take the model defaults */\n")
+        ts.append("export interface SimulationParams {\n")
+        for field_id, _ in parameter_defaults(params, compartments):
+            ts.append(f"  {field_id}?: number;\n")
+        for species_id in species_list:
+            ts.append(f"  init_{species_id}?: number | null;\n")
+        for name, rust_type, _ in SIMULATION_OPTIONS:
+            if name is None or (name.startswith("uptake_") and not has_windows):
+                continue
+            ts.append(f"  {name}?: {typescript_type(rust_type)};\n")
+        ts.append("}\n\n")
+        ts.append("export interface SimulationResult {\n")
+        ts.append("  species: Record<SpeciesId, number[]>;\n")
+        ts.append("  time: number[];\n")
+        ts.append("  /** null when the parameters were rejected or include_parameters is false */\n")
+        ts.append("  parameters: SimulationParams | null;\n")
+        ts.append("  /** Filled when include_observables is set */\n")
+        ts.append("  observables: Partial<Record<ObservableId, number[]>>;\n")
+        ts.append("  /** Unit of each species and observable series and of time; null if unknown */\n")
+        ts.append("  units: Record<string, string | null>;\n")
+        ts.append("  /** Filled when output_kind asks for them */\n")
+        ts.append("  amounts: Partial<Record<SpeciesId, number[]>>;\n")
+        ts.append("  concentrations: Partial<Record<SpeciesId, number[]>>;\n")
+        ts.append("  /** Species left in their native representation, and why */\n")
+        ts.append("  notes: string[];\n")
+        ts.append("  steady_state: SteadyState | null;\n")
+        ts.append("  applied_doses: Dose[];\n")
+        ts.append("  events: EventRecord[];\n")
+        ts.append("  /** Whether each event trigger held at the end; continuations start from it */\n")
+        ts.append("  trigger_state: boolean[];\n")
+        ts.append("  steps: number;\n")
+        ts.append("  stats: SolverStats | null;\n")
+        ts.append("  diagnostics: Diagnostics | null;\n")
+        ts.append("  error: SimulationError | null;\n")
+        ts.append("  timing: Timing;\n")
+        ts.append("}\n\n")
+        ts.append("/** Wall-clock milliseconds of the call */\n")
+        ts.append("export interface Timing {\n")
+        ts.append("  solve_ms: number;\n")
+        ts.append("  serialize_ms: number;\n")
+        ts.append("  total_ms: number;\n")
+        ts.append("}\n\n")
+        ts.append("export interface SteadyState {\n")
+        ts.append("  reached: boolean;\n")
+        ts.append("  time: number;\n")
+        ts.append("  rms: number;\n")
+        ts.append("  state: number[];\n")
+        ts.append("}\n\n")
+        ts.append("export interface EventRecord {\n")
+        ts.append("  time: number;\n")
+        ts.append("  id: string;\n")
+        ts.append("  assignments: Partial<Record<SpeciesId, number>>;\n")
+        ts.append("}\n\n")
+        ts.append("export interface SolverStats {\n")
+        ts.append("  steps: number;\n")
+        ts.append("  rhs_evals: number;\n")
+        ts.append("  jacobian_evals: number;\n")
+        ts.append("  newton_iterations: number;\n")
+        ts.append("  newton_failures: number;\n")
+        ts.append("  final_step_size: number;\n")
+        ts.append("}\n\n")
+        ts.append("export interface Diagnostics {\n")
+        ts.append("  total_mass: number[];\n")
+        ts.append("  max_mass_drift: number;\n")
+        ts.append("}\n\n")
+        ts.append("export interface SimulationError {\n")
+        ts.append(
+            '  kind: "invalid_params" | "setup" | "solver_failure" | "event_error" | "max_steps_exceeded" | "cancelled";\n'
+        )
+        ts.append("  message: string;\n")
+        ts.append("  field: string | null;\n")
+        ts.append("  time: number | null;\n")
+        ts.append("  state: number[] | null;\n")
+        ts.append("  hint: string | null;\n")
+        ts.append("}\n\n")
+        ts.append("/** get_model_metadata */\n")
+        ts.append("export interface ModelMetadata {\n")
+        ts.append("  model_id: string;\n")
+        ts.append("  num_species: number;\n")
+        ts.append("  num_parameters: number;\n")
+        ts.append("  time_units: string;\n")
+        ts.append("  substance_units: string;\n")
+        ts.append("  volume_units: string;\n")
+        ts.append("  sbml: {\n")
+        ts.append("    id: string | null;\n")
+        ts.append("    name: string | null;\n")
+        ts.append("    level: number | null;\n")
+        ts.append("    version: number | null;\n")
+        ts.append("    sha256: string | null;\n")
+        ts.append("  };\n")
+        ts.append("  generator: { name: string; version: string | null; generated_at: string | null };\n")
+        ts.append("  diffsol_version: string;\n")
+        ts.append('  features: ("events" | "sensitivities" | "embedded_sbml")[];\n')
+        ts.append("  observables: { id: ObservableId; units: string | null }[];\n")
+        ts.append("}\n\n")
+        ts.append("/** One entry of get_parameters_info */\n")
+        ts.append("export interface ParameterInfo {\n")
+        ts.append("  id: keyof SimulationParams;\n")
+        ts.append("  name: string | null;\n")
+        ts.append("  units: string | null;\n")
+        ts.append("  description: string | null;\n")
+        ts.append("  default_value: number;\n")
+        ts.append("  required: boolean;\n")
+        ts.append("  is_compartment: boolean;\n")
+        ts.append("  min: number | null;\n")
+        ts.append("  max: number | null;\n")
+        ts.append("}\n\n")
+        ts.append("/** One entry of get_species_info */\n")
+        ts.append("export interface SpeciesInfo {\n")
+        ts.append("  id: SpeciesId;\n")
+        ts.append("  initial_amount: number;\n")
+        ts.append("  override: keyof SimulationParams;\n")
+        ts.append("  units: string | null;\n")
+        ts.append("  compartment: string | null;\n")
+        ts.append("  compartment_volume: number | null;\n")
+        ts.append("  is_amount: boolean;\n")
+        ts.append("  is_boundary: boolean;\n")
+        ts.append("}\n\n")
+        ts.append("/** One entry of get_compartments_info */\n")
+        ts.append("export interface CompartmentInfo {\n")
+        ts.append("  id: string;\n")
+        ts.append("  name: string | null;\n")
+        ts.append("  size: number | null;\n")
+        ts.append("  formula: string | null;\n")
+        ts.append("  units: string | null;\n")
+        ts.append("  species: SpeciesId[];\n")
+        ts.append("}\n\n")
+        ts.append("/** get_model_equations */\n")
+        ts.append("export interface ModelEquations {\n")
+        ts.append("  derivatives: Record<SpeciesId, string>;\n")
+        ts.append("  assignment_rules: Record<string, string>;\n")
+        if algebraic_states:
+            ts.append("  algebraic_rules: Record<SpeciesId, string>;\n")
+        ts.append("}\n")
+
+        decorator = WASM_EXPORT if wasm else ""
+        gate = METADATA_ONLY if wasm else ""
+        code = []
+        code.append("/// TypeScript definitions of the parameters, result and metadata of this model\n")
+        code.append(f"{gate}const TYPES_TS: &str = {rust_raw_string(''.join(ts))};\n\n")
+        code.append(gate)
+        code.append(decorator)
+        code.append("pub fn get_types_ts() -> String {\n")
+        code.append("    TYPES_TS.to_string()\n")
+        code.append("}\n\n")
+        return "".join(code)
+
     def assemble_rust_file(
-        self, model_name: str, components: Dict[str, str], wasm: bool = True
+        self, model_name: str, components: Dict[str, str], wasm: bool = True,
+        sparse_jacobian: bool = False
     ) -> str:
         """Assemble complete Rust file from components
 
@@ -58,6 +343,7 @@ class RustTemplateManager:
             model_name: Name of the model
             components: Dictionary with component code blocks
             wasm: If True, generate WASM-compatible code. If False, generate native Rust code.
+            sparse_jacobian: If True, build a sparse problem (BuiltProblem::sparse)
 
         Returns:
             Complete Rust source code
@@ -78,198 +364,1356 @@ class RustTemplateManager:
             "// Uses SymPy CSE for optimized derivatives and Jacobian\n\n"
         )
 
-        # Imports
-        template_parts.append(
-            "use diffsol::{OdeBuilder, OdeSolverMethod, OdeSolverStopReason, Vector};\n"
-        )
+        # Imports; everything model-independent comes from wasm_pk_core
+        if components.get("sensitivity_fn"):
+            template_parts.append("use diffsol::{OdeBuilder, OdeSolverMethod, Vector, VectorHost};\n")
         if wasm:
+            template_parts.append(JS_ONLY)
             template_parts.append("use wasm_bindgen::prelude::*;\n")
         template_parts.append("use serde::{Deserialize, Serialize};\n")
-        template_parts.append("use std::collections::HashMap;\n\n")
-
-        template_parts.append("type M = diffsol::NalgebraMat<f64>;\n")
-        template_parts.append("type LS = diffsol::NalgebraLU<f64>;\n\n")
+        template_parts.append("use wasm_pk_core::*;\n\n")
 
-        # Structs
-        template_parts.append("#[derive(Serialize, Deserialize)]\n")
-        template_parts.append("pub struct SimulationResult {\n")
-        template_parts.append(components["species_fields"])
-        template_parts.append("\n")
-        template_parts.append("    pub time: Vec<f64>,\n")
-        template_parts.append("}\n\n")
-
-        template_parts.append("#[derive(Serialize, Deserialize)]\n")
+        has_events = bool(components.get("event_fns"))
+        has_windows = bool(components.get("window_terms"))
+        template_parts.append("#[derive(Serialize, Deserialize, Clone)]\n")
         template_parts.append("pub struct SimulationParams {\n")
         template_parts.append(components["param_fields"])
-        template_parts.append("    pub final_time: Option<f64>,\n")
+        if has_windows:
+            template_parts.append("    pub uptake_windows: Option<Vec<UptakeWindow>>,\n")
+        template_parts.append("    /// Times, outputs, doses and solver settings, the same for every model\n")
+        template_parts.append("    #[serde(flatten)]\n")
+        template_parts.append("    pub options: SimulationOptions,\n")
         template_parts.append("}\n\n")
 
-        # WASM-specific console logging setup
+        template_parts.append(
+            self._time_consts(components.get("model_time_seconds"), components.get("model_time_unit"))
+        )
+
+        # WASM-specific setup; console_log! comes from wasm_pk_core
         if wasm:
+            template_parts.append(JS_ONLY)
             template_parts.append("#[wasm_bindgen]\n")
             template_parts.append('extern "C" {\n')
-            template_parts.append("    #[wasm_bindgen(js_namespace = console)]\n")
-            template_parts.append("    fn log(s: &str);\n")
+            template_parts.append("    #[wasm_bindgen(js_name = setTimeout)]\n")
+            template_parts.append("    fn set_timeout(handler: &js_sys::Function, delay: i32);\n")
             template_parts.append("}\n\n")
-            template_parts.append("macro_rules! console_log {\n")
+            template_parts.append(self._log_level())
             template_parts.append(
-                "    ($($t:tt)*) => (log(&format_args!($($t)*).to_string()))\n"
+                "/// Runs when the module is instantiated, so a panic logs its message and location\n"
             )
+            template_parts.append('/// with console.error rather than only trapping as "unreachable"\n')
+            template_parts.append(JS_ONLY)
+            template_parts.append("#[wasm_bindgen(start)]\n")
+            template_parts.append("pub fn init_panic_hook() {\n")
+            template_parts.append("    console_error_panic_hook::set_once();\n")
             template_parts.append("}\n\n")
 
-        # Function signature
+        template_parts.append(self._option_wrappers(has_windows, components.get("window_repeat")))
+
+        # String entry point; parameter errors are reported in the result
+        model_type = self.model_type(model_name)
         if wasm:
-            template_parts.append("#[wasm_bindgen]\n")
+            template_parts.append(WASM_EXPORT)
         template_parts.append("pub fn run_simulation(params: &str) -> String {\n")
+        template_parts.append(f"    wasm_pk_core::simulate::<{model_type}>(params)\n")
+        template_parts.append("}\n\n")
 
-        # Logging statement
+        # Batch entry point: failures are per run and never abort the batch
+        template_parts.append(
+            "/// Run one simulation per parameter object; each result carries its own error\n"
+        )
         if wasm:
-            template_parts.append('    console_log!("Starting simulation...");\n\n')
-        else:
-            template_parts.append('    println!("Starting simulation...");\n\n')
-
-        # Parameter parsing
+            template_parts.append(WASM_EXPORT)
         template_parts.append(
-            "    let sim_params: SimulationParams = match serde_json::from_str(params) {\n"
+            "pub fn run_simulation_batch(params_list: &str) -> Result<String, String> {\n"
         )
-        template_parts.append("        Ok(p) => p,\n")
-        template_parts.append("        Err(e) => {\n")
+        template_parts.append(f"    wasm_pk_core::run_simulation_batch::<{model_type}>(params_list)\n")
+        template_parts.append("}\n\n")
+
+        # Object entry point: no JSON strings in either direction, and
+        # parameter errors are thrown as JS exceptions
         if wasm:
+            template_parts.append(JS_ONLY)
+            template_parts.append("#[wasm_bindgen]\n")
             template_parts.append(
-                '            console_log!("Error parsing params: {}", e);\n'
+                "pub fn run_simulation_js(params: JsValue) -> Result<JsValue, JsValue> {\n"
+            )
+            template_parts.append("    let call_start = now_ms();\n")
+            template_parts.append("    let sim_params = params_from_js(params)?;\n")
+            template_parts.append(f"    result_to_js({model_type}::simulate_run(sim_params), call_start)\n")
+            template_parts.append("}\n\n")
+            template_parts.append(JS_ONLY)
+            template_parts.append(
+                "fn params_from_js(params: JsValue) -> Result<SimulationParams, JsValue> {\n"
+            )
+            template_parts.append(
+                "    let overrides: serde_json::Map<String, serde_json::Value> =\n"
+            )
+            template_parts.append(
+                "        serde_wasm_bindgen::from_value(params).map_err(|e| JsError::new(&e.to_string()))?;\n"
             )
-        else:
             template_parts.append(
-                '            eprintln!("Error parsing params: {}", e);\n'
+                f"    resolve_params::<{model_type}>(overrides).map_err(JsValue::from)\n"
             )
+            template_parts.append("}\n\n")
+            template_parts.append(self._progress_callback())
+            template_parts.append(self._async_entry(model_type))
+            template_parts.append(self._result_handle(model_type))
+            template_parts.append(self._model_session(model_type))
+            template_parts.append(self._c_api())
+            template_parts.append(self._wasi_abi())
+            template_parts.append(self._python_module())
+
+        # Parameters live in the solver parameter vector so repeated runs only
+        # swap p instead of rebuilding the problem and its closures
         template_parts.append(
-            "            return serde_json::to_string(&SimulationResult {\n"
+            "/// Names of the solver parameter vector entries, in index order\n"
+        )
+        template_parts.append(
+            f"const PARAMETER_ORDER: &[&str] = &[{components['parameter_order']}];\n\n"
+        )
+        template_parts.append(
+            "/// Index mapping of the solver parameter vector: model parameters, derived\n"
+        )
+        template_parts.append(
+            "/// parameter values, then the initial state as init_<species> (JSON array)\n"
+        )
+        if wasm:
+            template_parts.append(WASM_EXPORT)
+        template_parts.append("pub fn get_parameter_order() -> String {\n")
+        template_parts.append("    serde_json::to_string(PARAMETER_ORDER).unwrap()\n")
+        template_parts.append("}\n\n")
+        template_parts.append(
+            "/// Resolve one run's parameters into the solver parameter vector\n"
+        )
+        template_parts.append(
+            "fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {\n"
         )
-        template_parts.append("                species: HashMap::new(),\n")
-        template_parts.append("                time: vec![],\n")
-        template_parts.append("            }).unwrap();\n")
-        template_parts.append("        }\n")
-        template_parts.append("    };\n\n")
-
         template_parts.append(components["param_extract"])
         template_parts.append("\n")
         template_parts.append(components.get("assignment_rules", ""))
-        template_parts.append("\n\n")
+        template_parts.append("\n")
         template_parts.append(components.get("initial_assignments", ""))
-        template_parts.append("\n\n")
-        template_parts.append(components.get("root_fn", ""))
-
-        template_parts.append("    // RHS Closure\n")
-        template_parts.append(
-            "    let rhs = |y: &diffsol::NalgebraVec<f64>, _p: &diffsol::NalgebraVec<f64>, t: f64, dy: &mut diffsol::NalgebraVec<f64>| {\n"
-        )
-        template_parts.append("        // Map species names to y indices\n")
-        template_parts.append(components["species_extract"])
-        template_parts.append("\n\n")
-        template_parts.append("        // Temporary variables (CSE)\n")
-        template_parts.append(components["temp_vars"])
-        template_parts.append("\n\n")
-        template_parts.append("        // Derivatives\n")
-        template_parts.append(components["rhs_block"])
         template_parts.append("\n")
-        template_parts.append("    };\n\n")
+        template_parts.append("    vec![\n")
+        template_parts.append(components["parameter_values"])
+        template_parts.append("\n")
+        template_parts.append("    ]\n")
+        template_parts.append("}\n\n")
+        template_parts.append(components["parameter_checks"])
+        template_parts.append(self._parameter_validation(model_type, wasm))
 
-        template_parts.append("    // Jacobian Closure (Matrix-Vector Product)\n")
+        template_parts.append(self._model_impl(model_name, components, sparse_jacobian))
+        if "jac_values_block" in components:
+            template_parts.append(self._jacobian_matrix(components))
         template_parts.append(
-            "    let jac = |y: &diffsol::NalgebraVec<f64>, _p: &diffsol::NalgebraVec<f64>, t: f64, v: &diffsol::NalgebraVec<f64>, jv: &mut diffsol::NalgebraVec<f64>| {\n"
-        )
-        template_parts.append("        for i in 0..jv.len() { jv[i] = 0.0; }\n\n")
-        template_parts.append("        // Map species names to y indices\n")
-        template_parts.append(components["species_extract"])
-        template_parts.append("\n\n")
-        template_parts.append("        // Temporary variables (CSE)\n")
-        template_parts.append(components["temp_vars"])
-        template_parts.append("\n\n")
-        template_parts.append("        // Jacobian-Vector Product\n")
-        template_parts.append(components["jac_block"])
+            "/// The run of checked parameters: the solver works in model time units, while\n"
+        )
+        template_parts.append("/// results and checkpoints keep the caller's\n")
+        template_parts.append(
+            "fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {\n"
+        )
+        template_parts.append(
+            "    let resolved = serde_json::to_value(with_defaults(sim_params.clone())).unwrap();\n"
+        )
+        template_parts.append("    let scale = time_scale(&sim_params);\n")
+        template_parts.append("    let sim_params = in_model_time(sim_params);\n")
+        template_parts.append("    let p = parameter_vector(&sim_params);\n")
+        template_parts.append("    Ok(Run {\n")
+        if has_windows:
+            template_parts.append("        uptake_windows: uptake_windows(&sim_params, &p)?,\n")
+        template_parts.append("        p,\n")
+        template_parts.append("        resolved,\n")
+        template_parts.append("        scale,\n")
+        if not has_windows:
+            template_parts.append("        uptake_windows: vec![],\n")
+        template_parts.append("        options: sim_params.options,\n")
+        template_parts.append("    })\n")
+        template_parts.append("}\n\n")
+        template_parts.append(
+            "/// The model's functions for the solver; the closures capture nothing, so they\n"
+        )
+        template_parts.append("/// are plain function pointers\n")
+        template_parts.append("fn equations() -> Equations<'static> {\n")
+        template_parts.append(components.get("root_fn", ""))
+        template_parts.append(components.get("event_fns", ""))
+
+        # Assignment-rule observables evaluated at a given state
+        template_parts.append(components["observables_fn"])
+        template_parts.append("\n")
+        template_parts.append(components["volumes_fn"])
+        template_parts.append("\n")
+        template_parts.append(components["mass_fn"])
         template_parts.append("\n")
-        template_parts.append("    };\n\n")
 
-        # Init function with species initial values
-        init_block = components.get("init_block", "")
-        if init_block:
-            template_parts.append(init_block)
+        template_parts.append("    Equations {\n")
+        template_parts.append(f"        rhs: {model_type}::rhs,\n")
+        template_parts.append(f"        jac: {model_type}::jac,\n")
+        template_parts.append(f"        init: {model_type}::init,\n")
+        if has_events:
+            template_parts.append("        roots: root_fn,\n")
+            template_parts.append("        triggers: eval_triggers,\n")
+            template_parts.append("        apply_events,\n")
         else:
-            # Fallback to old behavior
+            template_parts.append("        roots: no_roots,\n")
+            template_parts.append("        triggers: no_triggers,\n")
+            template_parts.append("        apply_events: no_events,\n")
+        template_parts.append("        observables: eval_observables,\n")
+        template_parts.append("        volumes: eval_volumes,\n")
+        template_parts.append("        mass: eval_mass,\n")
+        if "jac_values_block" in components:
             template_parts.append(
-                "    let init = |_y0: &diffsol::NalgebraVec<f64>, _t: f64, y: &mut diffsol::NalgebraVec<f64>| {\n"
+                "        jac_matrix: Some(JacobianMatrix { pattern: JACOBIAN_PATTERN, values: &jac_matrix }),\n"
             )
-            template_parts.append(
-                f"        for i in 0..{components['n_species']} {{ y[i] = 0.0; }}\n"
-            )
-            template_parts.append("    };\n\n")
-
-        template_parts.append("    let problem = OdeBuilder::<M>::new()\n")
-        template_parts.append("        .rhs_implicit(rhs, jac)\n")
-        template_parts.append(f"        .init(init, {components['n_species']})\n")
-        root_reg = components.get("root_registration", "")
-        if root_reg:
-            template_parts.append("        ")
-            template_parts.append(root_reg)
-            template_parts.append("\n")
-        template_parts.append("        .build()\n")
-        template_parts.append("        .unwrap();\n\n")
-
-        template_parts.append("    let mut solver = problem.bdf::<LS>().unwrap();\n")
-        template_parts.append("    let mut time = Vec::new();\n\n")
-
-        template_parts.append("    // Initialize result vectors\n")
-        template_parts.append(components["result_vectors_init"])
-        template_parts.append("\n\n")
-        template_parts.append(components["initial_pushes"])
-        template_parts.append("\n")
-        template_parts.append("    time.push(0.0);\n\n")
-
+        else:
+            template_parts.append("        jac_matrix: None,\n")
+        template_parts.append("    }\n")
+        template_parts.append("}\n\n")
         template_parts.append(
-            "    let final_time = sim_params.final_time.unwrap_or(24.0);\n"
+            "/// Solver stats of the last run of the last simulation call (JSON, null if it never started)\n"
         )
-        template_parts.append("    solver.set_stop_time(final_time).unwrap();\n")
-        template_parts.append("    loop {\n")
-        template_parts.append("        match solver.step() {\n")
+        if wasm:
+            template_parts.append(WASM_EXPORT)
+        template_parts.append("pub fn get_last_run_stats() -> String {\n")
         template_parts.append(
-            "            Ok(OdeSolverStopReason::InternalTimestep) => {\n"
+            "    LAST_RUN_STATS.with(|last| serde_json::to_string(&*last.borrow()).unwrap())\n"
         )
-        template_parts.append(components["loop_pushes"])
-        template_parts.append("\n")
-        template_parts.append("                time.push(solver.state().t);\n")
-        template_parts.append("            },\n")
-        event_handling = components.get("event_handling", "")
-        if event_handling:
-            template_parts.append(event_handling)
+        template_parts.append("}\n\n")
+
+        # Convenience entry point taking the output grid as a typed array
+        if wasm:
+            template_parts.append(WASM_EXPORT)
         template_parts.append(
-            "            Ok(OdeSolverStopReason::TstopReached) => break,\n"
+            "pub fn run_simulation_at_times(params: &str, times: &[f64]) -> String {\n"
         )
+        template_parts.append(f"    wasm_pk_core::run_simulation_at_times::<{model_type}>(params, times)\n")
+        template_parts.append("}\n\n")
+
+        # CSV export for spreadsheets: a time column, then species in SBML order
         template_parts.append(
-            "            Ok(OdeSolverStopReason::RootFound(_)) => break,\n"
+            "/// Run a simulation and return `time,<species...>` CSV, one row per stored time\n"
         )
-        template_parts.append('            Err(_) => panic!("Solver Error"),\n')
-        template_parts.append("        }\n")
-        template_parts.append("    }\n\n")
-
-        template_parts.append("    let mut species_map = HashMap::new();\n")
-        template_parts.append(components["map_inserts"])
-        template_parts.append("\n\n")
+        if wasm:
+            template_parts.append(WASM_EXPORT)
+        template_parts.append(
+            "pub fn run_simulation_csv(params: &str) -> Result<String, String> {\n"
+        )
+        template_parts.append(f"    wasm_pk_core::run_simulation_csv::<{model_type}>(params)\n")
+        template_parts.append("}\n\n")
 
-        template_parts.append("    let result = SimulationResult {\n")
-        template_parts.append("        time,\n")
-        template_parts.append("        species: species_map,\n")
-        template_parts.append("    };\n\n")
+        template_parts.append(self._pk_metrics(wasm))
+        template_parts.append(self._parameter_sweep(model_type, wasm))
+        template_parts.append(self._continuation(model_type, wasm))
+        template_parts.append(self._checkpoints(model_type, wasm))
 
-        template_parts.append("    serde_json::to_string(&result).unwrap()\n")
+        template_parts.append(
+            f"const SPECIES_IDS: &[&str] = &[{components['result_keys']}];\n\n"
+        )
+        template_parts.append(components.get("event_consts", ""))
+        template_parts.append(components["state_parameters"])
+        template_parts.append(components["observable_ids"])
+        template_parts.append("/// What the shared solver loop and option checks need to know about the model\n")
+        template_parts.append("const MODEL: ModelInfo = ModelInfo {\n")
+        template_parts.append("    species: SPECIES_IDS,\n")
+        template_parts.append("    state_parameters: &STATE_PARAMETERS,\n")
+        if has_events:
+            template_parts.append("    event_ids: &EVENT_IDS,\n")
+            template_parts.append("    event_targets: &EVENT_TARGETS,\n")
+            template_parts.append("    event_initial_values: &EVENT_INITIAL_VALUES,\n")
+            template_parts.append("    event_persistent: &EVENT_PERSISTENT,\n")
+        else:
+            template_parts.append("    event_ids: &[],\n")
+            template_parts.append("    event_targets: &[],\n")
+            template_parts.append("    event_initial_values: &[],\n")
+            template_parts.append("    event_persistent: &[],\n")
+        template_parts.append("    observables: &OBSERVABLE_IDS,\n")
+        template_parts.append("    species_is_amount: &SPECIES_IS_AMOUNT,\n")
+        template_parts.append("    species_volumes: &SPECIES_VOLUMES,\n")
+        template_parts.append("    series_units: &SERIES_UNITS,\n")
+        template_parts.append("    time_seconds: MODEL_TIME_SECONDS,\n")
+        template_parts.append("    time_unit: MODEL_TIME_UNIT,\n")
+        template_parts.append("    parameters: PARAMETER_ORDER,\n")
+        template_parts.append("    parameter_checks: PARAMETER_CHECKS,\n")
+        template_parts.append(f"    algebraic: {components.get('algebraic_states', '&[]')},\n")
+        template_parts.append("};\n\n")
+        # Compact binary export: little-endian f64 values laid out as
+        # [n_times, n_species, times..., species0..., species1...]
+        template_parts.append(
+            "/// Run a simulation and return the trajectory as little-endian f64 bytes\n"
+        )
+        if wasm:
+            template_parts.append(WASM_EXPORT)
+        template_parts.append(
+            "pub fn run_simulation_binary(params: &str) -> Result<Vec<u8>, String> {\n"
+        )
+        template_parts.append(f"    wasm_pk_core::run_simulation_binary::<{model_type}>(params)\n")
         template_parts.append("}\n\n")
+        template_parts.append(
+            "/// Describe the run_simulation_binary layout and its species order (JSON)\n"
+        )
+        if wasm:
+            template_parts.append(WASM_EXPORT)
+        template_parts.append("pub fn get_binary_layout() -> String {\n")
+        template_parts.append(f"    wasm_pk_core::get_binary_layout::<{model_type}>()\n")
+        template_parts.append("}\n\n")
+        template_parts.append(
+            "/// Decode run_simulation_binary output into times and named species series\n"
+        )
+        template_parts.append(
+            "pub fn decode_binary_result(bytes: &[u8]) -> Result<(Vec<f64>, OrderedSeries), String> {\n"
+        )
+        template_parts.append(f"    wasm_pk_core::decode_binary_result::<{model_type}>(bytes)\n")
+        template_parts.append("}\n\n")
+
+        template_parts.append(components.get("sensitivity_fn", ""))
 
-        # Add metadata functions
-        metadata_functions = components.get("metadata_functions", "")
-        if metadata_functions:
-            template_parts.append(metadata_functions)
+        # Metadata functions; PkModel::defaults reads get_default_parameters
+        template_parts.append(components["metadata_functions"])
+        template_parts.append(components.get("equations_fn", ""))
+        template_parts.append(components.get("sbml_fn", ""))
+        template_parts.append(components.get("types_fn", ""))
+        if wasm:
+            template_parts.append(self._browser_tests(model_type))
 
         return "".join(template_parts)
 
+    @staticmethod
+    def model_type(model_name: str) -> str:
+        """Name of the zero-sized type implementing PkModel for a model"""
+        return model_type(model_name)
+
+    def _model_impl(self, model_name: str, components: Dict[str, Any], sparse_jacobian: bool = False) -> str:
+        """Build the model's zero-sized type and its PkModel implementation
+
+        The right-hand side, Jacobian-vector product and initial state are
+        associated functions, so the solver and code generic over models share them.
+
+        Args:
+            model_name: Name of the model
+            components: Dictionary with component code blocks
+
+        Returns:
+            Rust struct and impl PkModel block
+        """
+        model_type = self.model_type(model_name)
+        code = []
+        code.append("/// The model as a type, for code generic over models; see PkModel\n")
+        code.append(f"pub struct {model_type};\n\n")
+        code.append(f"impl PkModel for {model_type} {{\n")
+        code.append("    type Params = SimulationParams;\n")
+        code.append(f"    const N_STATES: usize = {components['n_species']};\n")
+        code.append("    const INFO: ModelInfo = MODEL;\n\n")
+
+        code.append(
+            f"    fn rhs({components.get('rhs_state_arg', 'y')}: &[f64], {components.get('rhs_param_arg', '_p')}: &[f64], {components.get('rhs_time_arg', 't')}: f64, "
+            f"{'windows' if components.get('window_terms') else '_windows'}: &[UptakeWindow], dy: &mut [f64]) {{\n"
+        )
+        code.append("        // Parameters from p\n")
+        code.append(components.get("param_lookups", ""))
+        code.append("\n\n")
+        code.append("        // Map species names to y indices\n")
+        code.append(components["species_extract"])
+        code.append("\n\n")
+        if components.get("window_terms"):
+            code.append("        // Dose-window terms summed over the run's uptake windows\n")
+            code.append(components["window_terms"])
+            code.append("\n\n")
+        code.append("        // Temporary variables (CSE)\n")
+        code.append(components["temp_vars"])
+        code.append("\n\n")
+        code.append("        // Derivatives\n")
+        code.append(components["rhs_block"])
+        code.append("\n")
+        code.append("    }\n\n")
+
+        code.append(
+            f"    fn jac({components.get('jac_state_arg', 'y')}: &[f64], {components.get('jac_param_arg', '_p')}: &[f64], {components.get('jac_time_arg', 't')}: f64, {'v' if components['jac_block'].strip() else '_v'}: &[f64], jv: &mut [f64]) {{\n"
+        )
+        code.append("        jv.fill(0.0);\n\n")
+        code.append("        // Parameters from p\n")
+        code.append(components.get("jac_param_lookups", components.get("param_lookups", "")))
+        code.append("\n\n")
+        code.append("        // Map species names to y indices\n")
+        code.append(components.get("jac_species_extract", components["species_extract"]))
+        code.append("\n\n")
+        code.append("        // Temporary variables (CSE)\n")
+        code.append(components.get("jac_temp_vars", components["temp_vars"]))
+        code.append("\n\n")
+        code.append("        // Jacobian-Vector Product\n")
+        code.append(components["jac_block"])
+        code.append("\n")
+        code.append("    }\n\n")
+
+        # Init function copying the initial state from p
+        init_block = components.get("init_block", "")
+        if init_block:
+            code.append(init_block)
+        else:
+            # Fallback to old behavior
+            code.append(f"    fn init(_p: &[f64], _t: f64, y: &mut [f64]) {{\n")
+            code.append(f"        for i in 0..{components['n_species']} {{ y[i] = 0.0; }}\n")
+            code.append("    }\n\n")
+
+        code.append("    fn defaults() -> serde_json::Value {\n")
+        code.append("        serde_json::from_str(&get_default_parameters()).unwrap()\n")
+        code.append("    }\n\n")
+        code.append("    fn options(sim_params: &SimulationParams) -> &SimulationOptions {\n")
+        code.append("        &sim_params.options\n")
+        code.append("    }\n\n")
+        code.append("    fn options_mut(sim_params: &mut SimulationParams) -> &mut SimulationOptions {\n")
+        code.append("        &mut sim_params.options\n")
+        code.append("    }\n\n")
+        if components.get("window_terms"):
+            # The windows are the model's own fields, unknown to the shared options
+            code.append("    fn check(sim_params: &SimulationParams) -> Result<(), SimulationError> {\n")
+            code.append("        if let Some(ref windows) = sim_params.uptake_windows {\n")
+            code.append("            check_uptake_windows(windows)?;\n")
+            code.append("        }\n")
+            if components.get("window_repeat"):
+                code.append("        uptake_windows(sim_params, &parameter_vector(sim_params))?;\n")
+            code.append("        Ok(())\n")
+            code.append("    }\n\n")
+            code.append("    fn shift_schedule(sim_params: &mut SimulationParams, offset: f64) {\n")
+            code.append("        sim_params.options.shift_schedule(offset);\n")
+            code.append("        for window in sim_params.uptake_windows.iter_mut().flatten() {\n")
+            code.append("            window.t0 += offset;\n")
+            code.append("            window.t1 += offset;\n")
+            code.append("        }\n")
+            code.append("    }\n\n")
+        code.append(f"    fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {{\n")
+        code.append("        parameter_vector(sim_params)\n")
+        code.append("    }\n\n")
+        code.append("    fn build_problem() -> BuiltProblem<'static> {\n")
+        code.append(f"        BuiltProblem::{'sparse' if sparse_jacobian else 'dense'}(MODEL, equations())\n")
+        code.append("    }\n\n")
+        code.append("    fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {\n")
+        code.append("        prepare_run(sim_params)\n")
+        code.append("    }\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _jacobian_matrix(self, components: Dict[str, Any]) -> str:
+        """Build JACOBIAN_PATTERN and jac_matrix, the Jacobian as a matrix
+
+        jac_matrix binds the same parameters, species and temporaries as jac and
+        writes one value per pattern entry.
+
+        Args:
+            components: Dictionary with component code blocks
+
+        Returns:
+            Rust const and function
+        """
+        code = []
+        code.append("/// (row, column) of each nonzero Jacobian entry, in the order jac_matrix fills them\n")
+        code.append(components["jacobian_pattern"])
+        code.append("\n\n")
+        code.append("/// The nonzero Jacobian entries at (y, p, t), in JACOBIAN_PATTERN order; the\n")
+        code.append("/// solver fills its Newton matrix from them instead of from jac products\n")
+        code.append(
+            f"fn jac_matrix({components.get('jac_state_arg', 'y')}: &[f64], {components.get('jac_param_arg', '_p')}: &[f64], {components.get('jac_time_arg', 't')}: f64, {'values' if components['jac_values_block'].strip() else '_values'}: &mut [f64]) {{\n"
+        )
+        body = []
+        body.append("        // Parameters from p\n")
+        body.append(components.get("jac_param_lookups", components.get("param_lookups", "")))
+        body.append("\n\n")
+        body.append("        // Map species names to y indices\n")
+        body.append(components.get("jac_species_extract", components["species_extract"]))
+        body.append("\n\n")
+        body.append("        // Temporary variables (CSE)\n")
+        body.append(components.get("jac_temp_vars", components["temp_vars"]))
+        body.append("\n\n")
+        body.append("        // Jacobian entries\n")
+        body.append(components["jac_values_block"])
+        body.append("\n")
+        # The blocks are indented for the impl PkModel methods; this is a free function
+        code.append("".join(body).replace("\n    ", "\n")[4:])
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _time_consts(self, model_seconds, model_unit) -> str:
+        """Build the constants describing the model's time units
+
+        Args:
+            model_seconds: Seconds per model time unit, or None if unknown
+            model_unit: SBML id of the model's time units, or None
+
+        Returns:
+            Rust MODEL_TIME_SECONDS and MODEL_TIME_UNIT constants
+        """
+        model_seconds = "None" if model_seconds is None else f"Some({float(model_seconds)!r})"
+        code = []
+        code.append("/// Seconds per model time unit, if the SBML declares its time units\n")
+        code.append(f"const MODEL_TIME_SECONDS: Option<f64> = {model_seconds};\n")
+        code.append("/// SBML id of the model's time units\n")
+        code.append(f"const MODEL_TIME_UNIT: Option<&str> = {rust_string(model_unit)};\n\n")
+        return "".join(code)
+
+    def _option_wrappers(self, has_windows: bool, window_repeat: Optional[str]) -> str:
+        """Build the SimulationParams forms of the SimulationOptions helpers
+
+        The helpers of wasm_pk_core only see the shared options; these pass
+        the model in and also scale the uptake windows of window models. A model
+        whose window repeats derives its windows from its own parameters.
+
+        Args:
+            has_windows: If True, the model takes uptake_windows
+            window_repeat: Rust call building the model's repeated windows from
+                the parameter vector p; None when the model's window does not repeat
+
+        Returns:
+            Rust time_scale, in_model_time and with_defaults, plus
+            uptake_windows for window models
+        """
+        code = []
+        code.append("/// Model time units per time_unit (1 when it is not given)\n")
+        code.append("fn time_scale(sim_params: &SimulationParams) -> f64 {\n")
+        code.append("    sim_params.options.time_scale(&MODEL)\n")
+        code.append("}\n\n")
+        code.append("/// The parameters with every time and step size in model time units\n")
+        code.append("fn in_model_time(mut sim_params: SimulationParams) -> SimulationParams {\n")
+        code.append("    let scale = time_scale(&sim_params);\n")
+        code.append("    sim_params.options = sim_params.options.in_model_time(scale);\n")
+        if has_windows:
+            code.append("    for window in sim_params.uptake_windows.iter_mut().flatten() {\n")
+            code.append("        window.t0 *= scale;\n")
+            code.append("        window.t1 *= scale;\n")
+            code.append("    }\n")
+        code.append("    sim_params\n")
+        code.append("}\n\n")
+        code.append("/// The parameters with the defaults a run uses for its times and solver options\n")
+        code.append("fn with_defaults(mut sim_params: SimulationParams) -> SimulationParams {\n")
+        code.append("    sim_params.options = sim_params.options.with_defaults(&MODEL);\n")
+        code.append("    sim_params\n")
+        code.append("}\n\n")
+        if has_windows:
+            if window_repeat:
+                code.append("/// The uptake windows of a run in model time units: its uptake_windows, or the\n")
+                code.append("/// model's pulse repeated as its parameters ask\n")
+            else:
+                code.append("/// The uptake windows of a run in model time units: its uptake_windows, or none\n")
+                code.append("/// for the model's own window\n")
+            code.append(
+                f"fn uptake_windows(sim_params: &SimulationParams, {'p' if window_repeat else '_p'}: &[f64]) -> Result<Vec<UptakeWindow>, SimulationError> {{\n"
+            )
+            code.append("    match &sim_params.uptake_windows {\n")
+            code.append("        Some(windows) => Ok(windows.clone()),\n")
+            code.append(f"        None => {window_repeat or 'Ok(vec![])'},\n")
+            code.append("    }\n")
+            code.append("}\n\n")
+        return "".join(code)
+
+    def _parameter_validation(self, model_type: str, wasm: bool) -> str:
+        """Build validate_parameters, which checks a run without starting it
+
+        wasm_pk_core::validate_parameters checks the ranges in PARAMETER_CHECKS,
+        as runs with `validate` set do before they start.
+
+        Args:
+            model_type: Name of the model's PkModel type
+            wasm: If True, export validate_parameters to JavaScript
+
+        Returns:
+            Rust entry point
+        """
+        code = []
+        code.append(
+            "/// Check parameters without running: `{errors, warnings}`, each `{field, message}` (JSON)\n"
+        )
+        if wasm:
+            code.append(WASM_EXPORT)
+        code.append("pub fn validate_parameters(params: &str) -> String {\n")
+        code.append(f"    wasm_pk_core::validate_parameters::<{model_type}>(params)\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _pk_metrics(self, wasm: bool) -> str:
+        """Build the PK metrics entry points, computed from a stored result
+
+        The metrics do not depend on the model; wasm_pk_core computes them.
+
+        Args:
+            wasm: If True, export the entry points with wasm_bindgen
+
+        Returns:
+            Rust compute_pk_metrics and compute_partial_auc entry points
+        """
+        code = []
+        code.append(
+            "/// Cmax, Tmax, AUC, AUC(0-inf), terminal half-life and trough of one species (JSON)\n"
+        )
+        if wasm:
+            code.append(WASM_EXPORT)
+        code.append(
+            "pub fn compute_pk_metrics(result_json: &str, species: &str) -> Result<String, String> {\n"
+        )
+        code.append("    wasm_pk_core::compute_pk_metrics(result_json, species)\n")
+        code.append("}\n\n")
+        code.append("/// AUC of one species over each `[t1, t2]` window (JSON array, same order)\n")
+        if wasm:
+            code.append(WASM_EXPORT)
+        code.append(
+            "pub fn compute_partial_auc(result_json: &str, species: &str, windows: &str) -> Result<String, String> {\n"
+        )
+        code.append("    wasm_pk_core::compute_partial_auc(result_json, species, windows)\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _parameter_sweep(self, model_type: str, wasm: bool) -> str:
+        """Build the one-dimensional parameter sweep entry point
+
+        Args:
+            model_type: Name of the model's PkModel type
+            wasm: If True, export the entry point with wasm_bindgen
+
+        Returns:
+            Rust entry point; wasm_pk_core::run_parameter_sweep does the work
+        """
+        code = []
+        code.append(
+            "/// Run the model once per value of one parameter, e.g. body weight from 20 to 100 kg\n"
+        )
+        if wasm:
+            code.append(WASM_EXPORT)
+        code.append("pub fn run_parameter_sweep(base_params: &str, sweep: &str) -> Result<String, String> {\n")
+        code.append(f"    wasm_pk_core::run_parameter_sweep::<{model_type}>(base_params, sweep)\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _progress_callback(self) -> str:
+        """Build set_progress_callback, which registers the JS function that
+        wasm_pk_core's ProgressReporter calls while simulations run
+
+        Returns:
+            Rust wrapper exporting the setter
+        """
+        code = []
+        code.append(
+            "/// Call `callback(fraction, steps_taken)` while simulations run, `fraction` going from 0\n"
+        )
+        code.append(
+            "/// at t_start to 1 at final_time: at most once per `interval_ms` (default 100), or\n"
+        )
+        code.append(
+            "/// every `every_steps` solver steps if given. Returning `false` cancels the run, which\n"
+        )
+        code.append(
+            "/// then fails with a `cancelled` error at the time reached.\n"
+        )
+        code.append(
+            "/// Applies to every entry point, per run in batches; pass no callback to remove it.\n"
+        )
+        code.append(JS_ONLY)
+        code.append("#[wasm_bindgen]\n")
+        code.append(
+            "pub fn set_progress_callback(callback: Option<js_sys::Function>, interval_ms: Option<f64>, every_steps: Option<usize>) {\n"
+        )
+        code.append("    wasm_pk_core::set_progress_callback(callback, interval_ms, every_steps)\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _async_entry(self, model_type: str) -> str:
+        """Build run_simulation_async, the non-blocking object entry point
+
+        The run is the same as run_simulation_js, but every chunk of solver
+        steps it waits for a setTimeout(0) so the page can paint and handle
+        input in between. setTimeout rather than a resolved promise, as
+        microtasks run before rendering.
+
+        Args:
+            model_type: Name of the model's PkModel type
+
+        Returns:
+            Rust async function and its next_tick helper
+        """
+        code = []
+        code.append("/// Solver steps between yields of run_simulation_async\n")
+        code.append(JS_ONLY)
+        code.append("const DEFAULT_CHUNK_STEPS: usize = 1000;\n\n")
+        code.append(
+            "/// run_simulation_js without blocking the event loop: every `chunk_steps` solver\n"
+        )
+        code.append(
+            "/// steps (default 1000) the run waits for the next macrotask. Resolves to the\n"
+        )
+        code.append("/// result object; rejects on invalid parameters.\n")
+        code.append(JS_ONLY)
+        code.append("#[wasm_bindgen]\n")
+        code.append(
+            "pub async fn run_simulation_async(params: JsValue, chunk_steps: Option<usize>) -> Result<JsValue, JsValue> {\n"
+        )
+        code.append("    let call_start = now_ms();\n")
+        code.append("    let sim_params = params_from_js(params)?;\n")
+        code.append("    let chunk_steps = chunk_steps.unwrap_or(DEFAULT_CHUNK_STEPS).max(1);\n")
+        code.append("    let mut reporter = ProgressReporter::default();\n")
+        code.append("    let mut yielded_at = 0;\n")
+        code.append("    let pause = move |fraction: f64, steps: usize| {\n")
+        code.append("        let go_on = reporter.report(fraction, steps);\n")
+        code.append("        let due = steps >= yielded_at + chunk_steps;\n")
+        code.append("        if due {\n")
+        code.append("            yielded_at = steps;\n")
+        code.append("        }\n")
+        code.append("        async move {\n")
+        code.append("            if due {\n")
+        code.append("                next_tick().await;\n")
+        code.append("            }\n")
+        code.append("            go_on\n")
+        code.append("        }\n")
+        code.append("    };\n")
+        code.append(
+            f"    let result = {model_type}::build_problem().solve(vec![prepare_run(sim_params)], |_| {{}}, pause).await.pop().unwrap();\n"
+        )
+        code.append("    result_to_js(result, call_start)\n")
+        code.append("}\n\n")
+        code.append("/// Resolve on the next macrotask, after the browser has had a chance to render\n")
+        code.append(JS_ONLY)
+        code.append("async fn next_tick() {\n")
+        code.append(
+            "    let tick = js_sys::Promise::new(&mut |resolve, _| set_timeout(&resolve, 0));\n"
+        )
+        code.append("    // The promise only ever resolves\n")
+        code.append("    let _ = wasm_bindgen_futures::JsFuture::from(tick).await;\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _continuation(self, model_type: str, wasm: bool) -> str:
+        """Build continue_simulation, which resumes a run from its last state
+
+        Args:
+            model_type: Name of the model's PkModel type
+            wasm: If True, export the entry point with wasm_bindgen
+
+        Returns:
+            Rust entry point; wasm_pk_core::continue_simulation does the work
+        """
+        code = []
+        code.append(
+            "/// Continue a run_simulation result from its last recorded state for `additional_time`,\n"
+        )
+        code.append(
+            "/// with `new_params` merged over the parameters that run used. Its doses, output_times\n"
+        )
+        code.append(
+            "/// and uptake windows are not repeated; times in `new_params` count from the\n"
+        )
+        code.append(
+            "/// continuation start. The segment is returned on the combined time axis, after\n"
+        )
+        code.append("/// the earlier trajectory when `concatenate` is set.\n")
+        if wasm:
+            code.append(WASM_EXPORT)
+        code.append(
+            "pub fn continue_simulation(previous_result: &str, new_params: &str, additional_time: f64, concatenate: bool) -> Result<String, String> {\n"
+        )
+        code.append(
+            f"    wasm_pk_core::continue_simulation::<{model_type}>(previous_result, new_params, additional_time, concatenate)\n"
+        )
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _checkpoints(self, model_type: str, wasm: bool) -> str:
+        """Build export_checkpoint and run_from_checkpoint
+
+        Every run leaves the final solver time, state and step size, the
+        doses it has not applied yet and its parameters as the checkpoint of
+        its simulation call. Resuming restarts BDF from that state at
+        t_start = the checkpoint time, using the saved step size as the first
+        step. A checkpoint of another format version or of a model with other
+        species is refused.
+
+        Args:
+            model_type: Name of the model's PkModel type
+            wasm: If True, export the entry points with wasm_bindgen
+
+        Returns:
+            Rust entry points; wasm_pk_core::run_from_checkpoint does the work
+        """
+        code = []
+        code.append(
+            "/// Checkpoint of the last run of the last simulation call (JSON, null if it never\n"
+        )
+        code.append("/// started or its final state is not finite)\n")
+        if wasm:
+            code.append(WASM_EXPORT)
+        code.append("pub fn export_checkpoint() -> String {\n")
+        code.append(
+            "    LAST_CHECKPOINT.with(|last| serde_json::to_string(&*last.borrow()).unwrap())\n"
+        )
+        code.append("}\n\n")
+        code.append(
+            "/// Resume an exported checkpoint up to the absolute `final_time` in `params`, which are\n"
+        )
+        code.append(
+            "/// merged over the checkpoint's parameters. Its pending doses are applied unless\n"
+        )
+        code.append(
+            "/// `params` gives new doses. The result and the new checkpoint use absolute time.\n"
+        )
+        if wasm:
+            code.append(WASM_EXPORT)
+        code.append(
+            "pub fn run_from_checkpoint(checkpoint: &str, params: &str) -> Result<String, String> {\n"
+        )
+        code.append(f"    wasm_pk_core::run_from_checkpoint::<{model_type}>(checkpoint, params)\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _result_handle(self, model_type: str) -> str:
+        """Build the typed-array result handle exported to JavaScript
+
+        The trajectory stays in WASM memory and each series is copied into a
+        Float64Array on request, skipping JSON serialization entirely.
+
+        Args:
+            model_type: Name of the model's PkModel type
+
+        Returns:
+            Rust struct, its accessors and the run_simulation_typed entry point
+        """
+        code = []
+        code.append("/// Simulation result kept in WASM memory, read as typed arrays\n")
+        code.append(JS_ONLY)
+        code.append("#[wasm_bindgen]\n")
+        code.append("pub struct SimulationHandle {\n")
+        code.append("    result: SimulationResult,\n")
+        code.append("}\n\n")
+        code.append(JS_ONLY)
+        code.append("#[wasm_bindgen]\n")
+        code.append("impl SimulationHandle {\n")
+        code.append("    /// Stored output times (Float64Array)\n")
+        code.append("    pub fn times(&self) -> Vec<f64> {\n")
+        code.append("        self.result.time.clone()\n")
+        code.append("    }\n\n")
+        code.append("    /// Trajectory of one species by SBML id (Float64Array)\n")
+        code.append("    pub fn species(&self, name: &str) -> Result<Vec<f64>, JsError> {\n")
+        code.append("        self.result\n")
+        code.append("            .species\n")
+        code.append("            .get(name)\n")
+        code.append("            .cloned()\n")
+        code.append('            .ok_or_else(|| JsError::new(&format!("unknown species {}", name)))\n')
+        code.append("    }\n\n")
+        code.append("    /// Species ids in SBML order\n")
+        code.append("    pub fn species_names(&self) -> Vec<String> {\n")
+        code.append("        self.result.species.0.iter().map(|(name, _)| name.clone()).collect()\n")
+        code.append("    }\n\n")
+        code.append("    /// Solver failure message, if integration stopped early\n")
+        code.append("    pub fn error(&self) -> Option<String> {\n")
+        code.append("        self.result.error.as_ref().map(|e| e.message.clone())\n")
+        code.append("    }\n")
+        code.append("}\n\n")
+        code.append("/// Fast path for large trajectories: no JSON in either direction\n")
+        code.append(JS_ONLY)
+        code.append("#[wasm_bindgen]\n")
+        code.append(
+            "pub fn run_simulation_typed(params: JsValue) -> Result<SimulationHandle, JsValue> {\n"
+        )
+        code.append("    let sim_params = params_from_js(params)?;\n")
+        code.append(f"    Ok(SimulationHandle {{ result: {model_type}::simulate_run(sim_params) }})\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _log_level(self) -> str:
+        """Build the set_log_level export over the level wasm_pk_core keeps
+
+        Logging crosses the JS boundary on every call, so release builds
+        start at "error" and batch runs stay quiet; debug builds start at
+        "info". The level is one process-wide atomic, shared by every run.
+
+        Returns:
+            Rust set_log_level export
+        """
+        code = []
+        code.append('/// Set what the model logs: "off", "error", "info" (run starts, ignored doses) or\n')
+        code.append('/// "debug" (also batch progress, the steps of each run and every event firing)\n')
+        code.append(WASM_EXPORT)
+        code.append("pub fn set_log_level(level: &str) -> Result<(), String> {\n")
+        code.append("    wasm_pk_core::set_log_level(level)\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _c_api(self) -> str:
+        """Build the C ABI entry points of the crate's `c-api` feature
+
+        Native hosts such as a C++ framework load the model as a shared
+        library and call these instead of the wasm-bindgen exports. Strings
+        cross as NUL-terminated UTF-8; every string returned is allocated
+        here and must come back through wasm_pk_free_string. Bad input never
+        returns null but a result whose error says what was wrong.
+
+        Returns:
+            Rust extern "C" functions
+        """
+        code = []
+        code.append("/// run_simulation for C callers: `params_json` is a NUL-terminated JSON string and\n")
+        code.append("/// the result JSON is released with wasm_pk_free_string.\n")
+        code.append("///\n")
+        code.append("/// # Safety\n")
+        code.append("/// `params_json` must be null or point to a NUL-terminated string.\n")
+        code.append('#[cfg(feature = "c-api")]\n')
+        code.append("#[no_mangle]\n")
+        code.append(
+            'pub unsafe extern "C" fn wasm_pk_run_simulation(params_json: *const std::ffi::c_char) -> *mut std::ffi::c_char {\n'
+        )
+        code.append("    let call_start = now_ms();\n")
+        code.append("    let params = if params_json.is_null() {\n")
+        code.append('        Err("params_json is null".to_string())\n')
+        code.append("    } else {\n")
+        code.append(
+            '        unsafe { std::ffi::CStr::from_ptr(params_json) }.to_str().map_err(|e| format!("params_json: {}", e))\n'
+        )
+        code.append("    };\n")
+        code.append("    let json = match params {\n")
+        code.append("        Ok(params) => run_simulation(params),\n")
+        code.append("        Err(e) => result_to_json(failed_result(SimulationError::invalid(e)), call_start),\n")
+        code.append("    };\n")
+        code.append("    into_c_string(json)\n")
+        code.append("}\n\n")
+        code.append("/// get_default_parameters for C callers; release the string with wasm_pk_free_string\n")
+        code.append('#[cfg(feature = "c-api")]\n')
+        code.append("#[no_mangle]\n")
+        code.append('pub extern "C" fn wasm_pk_get_default_parameters() -> *mut std::ffi::c_char {\n')
+        code.append("    into_c_string(get_default_parameters())\n")
+        code.append("}\n\n")
+        code.append("/// Release a string returned by a wasm_pk_ function; null is ignored\n")
+        code.append("///\n")
+        code.append("/// # Safety\n")
+        code.append("/// `s` must be null or a string from this library that was not released yet.\n")
+        code.append('#[cfg(feature = "c-api")]\n')
+        code.append("#[no_mangle]\n")
+        code.append('pub unsafe extern "C" fn wasm_pk_free_string(s: *mut std::ffi::c_char) {\n')
+        code.append("    if !s.is_null() {\n")
+        code.append("        drop(unsafe { std::ffi::CString::from_raw(s) });\n")
+        code.append("    }\n")
+        code.append("}\n\n")
+        code.append('#[cfg(feature = "c-api")]\n')
+        code.append("fn into_c_string(json: String) -> *mut std::ffi::c_char {\n")
+        code.append("    // serde_json escapes NUL, so the JSON has no interior one\n")
+        code.append("    std::ffi::CString::new(json).unwrap().into_raw()\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _wasi_abi(self) -> str:
+        """Build the exports of the crate's `wasi` feature
+
+        A wasm32-wasip1 module has no JS glue, so a host such as wasmtime
+        calls plain exports under the same names as the JS functions. A
+        string crosses as a pointer and a byte length in the module's memory:
+        the host writes its input into a wasm_pk_alloc buffer, and each
+        function writes the length of its returned buffer to `out_len`. Both
+        buffers go back through wasm_pk_free. Logs reach the host on stderr.
+
+        Returns:
+            Rust module of extern "C" functions
+        """
+        getters = [
+            "get_default_parameters", "get_parameters_info", "get_species_info",
+            "get_compartments_info", "get_model_metadata", "get_model_equations",
+        ]
+        code = []
+        code.append('#[cfg(all(feature = "wasm", feature = "wasi"))]\n')
+        code.append('compile_error!("features `wasm` and `wasi` export the same names; build wasi with --no-default-features");\n\n')
+        code.append("/// Exports of the `wasi` feature: UTF-8 strings as pointer and length in linear memory.\n")
+        code.append("/// Every returned buffer, and every wasm_pk_alloc one, is released with wasm_pk_free.\n")
+        code.append('#[cfg(feature = "wasi")]\n')
+        code.append("mod wasi {\n")
+        code.append("    /// Buffer of `len` bytes for the host to write input into\n")
+        code.append("    #[no_mangle]\n")
+        code.append('    pub extern "C" fn wasm_pk_alloc(len: usize) -> *mut u8 {\n')
+        code.append("        Box::into_raw(vec![0u8; len].into_boxed_slice()) as *mut u8\n")
+        code.append("    }\n\n")
+        code.append("    /// Release a buffer from wasm_pk_alloc or a returned one; null is ignored\n")
+        code.append("    ///\n")
+        code.append("    /// # Safety\n")
+        code.append("    /// `ptr` must be null or a buffer of exactly `len` bytes from this module that was\n")
+        code.append("    /// not released yet.\n")
+        code.append("    #[no_mangle]\n")
+        code.append('    pub unsafe extern "C" fn wasm_pk_free(ptr: *mut u8, len: usize) {\n')
+        code.append("        if !ptr.is_null() {\n")
+        code.append("            drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) });\n")
+        code.append("        }\n")
+        code.append("    }\n\n")
+        code.append("    /// # Safety\n")
+        code.append("    /// `out_len` must point to writable memory.\n")
+        code.append("    unsafe fn into_buffer(json: String, out_len: *mut usize) -> *mut u8 {\n")
+        code.append("        let bytes = json.into_bytes().into_boxed_slice();\n")
+        code.append("        unsafe { *out_len = bytes.len() };\n")
+        code.append("        Box::into_raw(bytes) as *mut u8\n")
+        code.append("    }\n\n")
+        code.append("    /// # Safety\n")
+        code.append("    /// `ptr` must be null or point to `len` readable bytes.\n")
+        code.append("    unsafe fn read_str<'a>(ptr: *const u8, len: usize) -> Result<&'a str, String> {\n")
+        code.append("        if ptr.is_null() {\n")
+        code.append('            return Err("params is null".to_string());\n')
+        code.append("        }\n")
+        code.append('        std::str::from_utf8(unsafe { std::slice::from_raw_parts(ptr, len) }).map_err(|e| format!("params: {}", e))\n')
+        code.append("    }\n\n")
+        code.append("    /// run_simulation on the `params_len` bytes of JSON at `params`\n")
+        code.append("    ///\n")
+        code.append("    /// # Safety\n")
+        code.append("    /// `params` must be null or point to `params_len` readable bytes, and `out_len` to\n")
+        code.append("    /// writable memory.\n")
+        code.append("    #[no_mangle]\n")
+        code.append(
+            '    pub unsafe extern "C" fn run_simulation(params: *const u8, params_len: usize, out_len: *mut usize) -> *mut u8 {\n'
+        )
+        code.append("        let call_start = super::now_ms();\n")
+        code.append("        let json = match unsafe { read_str(params, params_len) } {\n")
+        code.append("            Ok(params) => super::run_simulation(params),\n")
+        code.append("            Err(e) => super::result_to_json(super::failed_result(super::SimulationError::invalid(e)), call_start),\n")
+        code.append("        };\n")
+        code.append("        unsafe { into_buffer(json, out_len) }\n")
+        code.append("    }\n\n")
+        code.append("    /// validate_parameters on the `params_len` bytes of JSON at `params`\n")
+        code.append("    ///\n")
+        code.append("    /// # Safety\n")
+        code.append("    /// As for run_simulation.\n")
+        code.append("    #[no_mangle]\n")
+        code.append(
+            '    pub unsafe extern "C" fn validate_parameters(params: *const u8, params_len: usize, out_len: *mut usize) -> *mut u8 {\n'
+        )
+        code.append("        let json = match unsafe { read_str(params, params_len) } {\n")
+        code.append("            Ok(params) => super::validate_parameters(params),\n")
+        code.append("            Err(message) => {\n")
+        code.append("                let issue = super::ParameterIssue { field: None, message };\n")
+        code.append("                serde_json::to_string(&super::ParameterReport { errors: vec![issue], warnings: vec![] }).unwrap()\n")
+        code.append("            }\n")
+        code.append("        };\n")
+        code.append("        unsafe { into_buffer(json, out_len) }\n")
+        code.append("    }\n\n")
+        code.append("    /// set_log_level on the `level_len` bytes at `level`; 0 on success, -1 for an\n")
+        code.append("    /// unknown level\n")
+        code.append("    ///\n")
+        code.append("    /// # Safety\n")
+        code.append("    /// `level` must be null or point to `level_len` readable bytes.\n")
+        code.append("    #[no_mangle]\n")
+        code.append('    pub unsafe extern "C" fn set_log_level(level: *const u8, level_len: usize) -> i32 {\n')
+        code.append("        match unsafe { read_str(level, level_len) }.and_then(super::set_log_level) {\n")
+        code.append("            Ok(()) => 0,\n")
+        code.append("            Err(_) => -1,\n")
+        code.append("        }\n")
+        code.append("    }\n\n")
+        for name in getters:
+            code.append("    /// # Safety\n")
+            code.append("    /// `out_len` must point to writable memory.\n")
+            if name != "get_default_parameters":
+                code.append("    " + METADATA_ONLY)
+            code.append("    #[no_mangle]\n")
+            code.append(f'    pub unsafe extern "C" fn {name}(out_len: *mut usize) -> *mut u8 {{\n')
+            code.append(f"        unsafe {{ into_buffer(super::{name}(), out_len) }}\n")
+            code.append("    }\n\n")
+        code[-1] = "    }\n"
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _python_module(self) -> str:
+        """Build the Python module of the crate's `python` feature
+
+        maturin builds it as `sbml_model`, whatever the model is called, as
+        wasm-pack names the JS package. Functions keep their Rust names and
+        arguments; JSON in either direction is a dict or list on the Python
+        side, and a JSON string is still accepted as input. Errors the Rust
+        function returns are raised as ValueError.
+
+        Returns:
+            Rust module of pyo3 functions
+        """
+        getters = [
+            ("get_default_parameters", "Default value of every parameter and option"),
+            ("get_parameters_info", "Name, default, units and range of each parameter"),
+            ("get_species_info", "Id, initial value, units and compartment of each species"),
+            ("get_compartments_info", "Size, formula, units and species of each compartment"),
+            ("get_model_metadata", "Model id, sizes, units and provenance"),
+            ("get_model_equations", "Derivatives and assignment rules as text"),
+        ]
+        code = []
+        code.append("/// Python module of the `python` feature, built with maturin as `sbml_model`.\n")
+        code.append("/// JSON arguments and results are dicts and lists on the Python side.\n")
+        code.append('#[cfg(feature = "python")]\n')
+        code.append("mod python {\n")
+        code.append("    use pyo3::exceptions::PyValueError;\n")
+        code.append("    use pyo3::prelude::*;\n\n")
+        code.append("    /// Python objects of the JSON the model returns\n")
+        code.append("    fn loads<'py>(py: Python<'py>, json: &str) -> PyResult<Bound<'py, PyAny>> {\n")
+        code.append('        py.import("json")?.call_method1("loads", (json,))\n')
+        code.append("    }\n\n")
+        code.append("    /// JSON of a dict or list argument; a string is taken as JSON already\n")
+        code.append("    fn dumps(value: &Bound<'_, PyAny>) -> PyResult<String> {\n")
+        code.append("        match value.extract::<String>() {\n")
+        code.append("            Ok(json) => Ok(json),\n")
+        code.append('            Err(_) => value.py().import("json")?.call_method1("dumps", (value,))?.extract(),\n')
+        code.append("        }\n")
+        code.append("    }\n\n")
+        code.append("    fn loads_ok<'py>(py: Python<'py>, result: Result<String, String>) -> PyResult<Bound<'py, PyAny>> {\n")
+        code.append("        loads(py, &result.map_err(PyValueError::new_err)?)\n")
+        code.append("    }\n\n")
+        code.append("    /// Run one simulation with `params` merged over the defaults; a failed run\n")
+        code.append("    /// has its message in the result's error\n")
+        code.append("    #[pyfunction]\n")
+        code.append("    #[pyo3(signature = (params = None))]\n")
+        code.append(
+            "    fn run_simulation<'py>(py: Python<'py>, params: Option<&Bound<'py, PyAny>>) -> PyResult<Bound<'py, PyAny>> {\n"
+        )
+        code.append('        let params = params.map(dumps).transpose()?.unwrap_or_else(|| "{}".to_string());\n')
+        code.append("        loads(py, &super::run_simulation(&params))\n")
+        code.append("    }\n\n")
+        code.append("    /// One result per parameter dict, each with its own error\n")
+        code.append("    #[pyfunction]\n")
+        code.append(
+            "    fn run_simulation_batch<'py>(py: Python<'py>, params_list: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {\n"
+        )
+        code.append("        loads_ok(py, super::run_simulation_batch(&dumps(params_list)?))\n")
+        code.append("    }\n\n")
+        code.append("    /// Every problem with `params`, without running\n")
+        code.append("    #[pyfunction]\n")
+        code.append(
+            "    fn validate_parameters<'py>(py: Python<'py>, params: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {\n"
+        )
+        code.append("        loads(py, &super::validate_parameters(&dumps(params)?))\n")
+        code.append("    }\n\n")
+        code.append("    /// Cmax, Tmax, AUC and half-life of one species of a result\n")
+        code.append("    #[pyfunction]\n")
+        code.append(
+            "    fn compute_pk_metrics<'py>(py: Python<'py>, result: &Bound<'py, PyAny>, species: &str) -> PyResult<Bound<'py, PyAny>> {\n"
+        )
+        code.append("        loads_ok(py, super::compute_pk_metrics(&dumps(result)?, species))\n")
+        code.append("    }\n\n")
+        for name, doc in getters:
+            code.append(f"    /// {doc}\n")
+            if name != "get_default_parameters":
+                code.append("    " + METADATA_ONLY)
+            code.append("    #[pyfunction]\n")
+            code.append(f"    fn {name}(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {{\n")
+            code.append(f"        loads(py, &super::{name}())\n")
+            code.append("    }\n\n")
+        code.append("    /// The SBML document the model was generated from\n")
+        code.append("    " + METADATA_ONLY)
+        code.append("    #[pyfunction]\n")
+        code.append("    fn get_model_sbml() -> PyResult<String> {\n")
+        code.append("        super::get_model_sbml().map_err(PyValueError::new_err)\n")
+        code.append("    }\n\n")
+        code.append('    /// Set what the model logs to stderr: "off", "error", "info" or "debug"\n')
+        code.append("    #[pyfunction]\n")
+        code.append("    fn set_log_level(level: &str) -> PyResult<()> {\n")
+        code.append("        super::set_log_level(level).map_err(PyValueError::new_err)\n")
+        code.append("    }\n\n")
+        code.append("    #[pymodule]\n")
+        code.append("    fn sbml_model(m: &Bound<'_, PyModule>) -> PyResult<()> {\n")
+        functions = [
+            "run_simulation", "run_simulation_batch", "validate_parameters", "compute_pk_metrics",
+        ] + [name for name, _ in getters] + ["get_model_sbml", "set_log_level"]
+        for name in functions:
+            if name.startswith("get_") and name != "get_default_parameters":
+                code.append("        " + METADATA_ONLY)
+            code.append(f"        m.add_function(wrap_pyfunction!({name}, m)?)?;\n")
+        code.append("        Ok(())\n")
+        code.append("    }\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _model_session(self, model_type: str) -> str:
+        """Build ModelSession, the entry point for repeated runs of one model
+
+        A session wraps wasm_pk_core's Session, which keeps the resolved
+        parameters, the run prepared from them and the built problem, so a
+        run parses no JSON and only re-initialises the solver. Only the
+        changed field goes through merge_params again, which keeps every
+        validation.
+
+        Args:
+            model_type: Name of the model's PkModel type
+
+        Returns:
+            Rust struct and its wasm_bindgen methods
+        """
+        code = []
+        code.append(
+            "/// Parameters resolved and a problem built once for repeated runs, e.g. behind a\n"
+        )
+        code.append("/// dashboard slider\n")
+        code.append(JS_ONLY)
+        code.append("#[wasm_bindgen]\n")
+        code.append("pub struct ModelSession {\n")
+        code.append(f"    session: Session<{model_type}>,\n")
+        code.append("}\n\n")
+        code.append(JS_ONLY)
+        code.append("#[wasm_bindgen]\n")
+        code.append("impl ModelSession {\n")
+        code.append(
+            "    /// Start from a parameter object merged over the defaults, as run_simulation_js does\n"
+        )
+        code.append("    #[wasm_bindgen(constructor)]\n")
+        code.append("    pub fn new(params: JsValue) -> Result<ModelSession, JsValue> {\n")
+        code.append("        Ok(ModelSession { session: Session::new(params_from_js(params)?)? })\n")
+        code.append("    }\n\n")
+        code.append(
+            "    /// Change one numeric parameter or option; rejected values leave the session unchanged\n"
+        )
+        code.append(
+            "    pub fn set_parameter(&mut self, name: &str, value: f64) -> Result<(), JsValue> {\n"
+        )
+        code.append("        Ok(self.session.set_parameter(name, value)?)\n")
+        code.append("    }\n\n")
+        code.append("    pub fn set_final_time(&mut self, final_time: f64) -> Result<(), JsValue> {\n")
+        code.append('        self.set_parameter("final_time", final_time)\n')
+        code.append("    }\n\n")
+        code.append("    /// Run with the current parameters; the session can be run again\n")
+        code.append("    pub fn run(&mut self) -> SimulationHandle {\n")
+        code.append("        SimulationHandle { result: self.session.run() }\n")
+        code.append("    }\n")
+        code.append("}\n\n")
+        return "".join(code)
+
+    def _browser_tests(self, model_type: str) -> str:
+        """Build the wasm-bindgen-test module of WASM builds
+
+        The defaults with final_time changed must go into JS and back as
+        strings, run, and come out with the new final time and the model's
+        initial state, the same through run_simulation_js; malformed JSON and
+        non-ASCII keys must come back as structured errors, and a large result
+        must parse in JS. run_simulation_async must resolve to exactly the
+        object the blocking run_simulation_js returns, even when it yields
+        every few steps, the session must run exactly like the stateless call
+        with the same parameters and refuse what merge_params refuses, the
+        progress callback must see a rising fraction every given number of
+        steps, and a callback that throws must not fail the run.
+        Run with `wasm-pack test --headless --chrome` (or --firefox), or in
+        Node with `wasm-pack test --node -- --features node-tests`.
+
+        Args:
+            model_type: Name of the model's PkModel type
+
+        Returns:
+            Rust test module compiled only for wasm32 test builds
+        """
+        test = []
+        test.append("#[cfg(all(test, feature = \"wasm\", target_arch = \"wasm32\"))]\n")
+        test.append("mod browser_tests {\n")
+        test.append("    use super::*;\n")
+        test.append("    use wasm_bindgen_test::*;\n\n")
+        test.append("    #[cfg(not(feature = \"node-tests\"))]\n")
+        test.append("    wasm_bindgen_test_configure!(run_in_browser);\n\n")
+        test.append("    fn get(value: &JsValue, key: &str) -> JsValue {\n")
+        test.append("        js_sys::Reflect::get(value, &key.into()).unwrap()\n")
+        test.append("    }\n\n")
+        test.append("    #[wasm_bindgen_test]\n")
+        test.append("    fn defaults_run_through_js() {\n")
+        test.append("        // Parsed, changed and serialized again in JS, as a page passes parameters\n")
+        test.append("        let params = js_sys::JSON::parse(&get_default_parameters()).unwrap();\n")
+        test.append('        js_sys::Reflect::set(&params, &"final_time".into(), &2.0.into()).unwrap();\n')
+        test.append("        let text = String::from(js_sys::JSON::stringify(&params).unwrap());\n")
+        test.append("        let result = js_sys::JSON::parse(&run_simulation(&text)).unwrap();\n")
+        test.append('        assert!(get(&result, "error").is_null());\n')
+        test.append('        let time = js_sys::Array::from(&get(&result, "time"));\n')
+        test.append("        assert_eq!(time.get(0).as_f64(), Some(0.0));\n")
+        test.append("        assert_eq!(time.get(time.length() - 1).as_f64(), Some(2.0));\n")
+        test.append('        assert_eq!(get(&get(&result, "parameters"), "final_time").as_f64(), Some(2.0));\n')
+        test.append("        // The first point is the model's initial state for the defaults\n")
+        test.append(f"        let p = {model_type}::parameter_vector(&{model_type}::default_params());\n")
+        test.append(f"        let mut y = vec![0.0; {model_type}::N_STATES];\n")
+        test.append(f"        {model_type}::init(&p, 0.0, &mut y);\n")
+        test.append('        let species = get(&result, "species");\n')
+        test.append("        for (name, y0) in SPECIES_IDS.iter().zip(&y) {\n")
+        test.append('            assert_eq!(js_sys::Array::from(&get(&species, name)).get(0).as_f64(), Some(*y0), "{}", name);\n')
+        test.append("        }\n")
+        test.append("        // The same run with JS objects in and out\n")
+        test.append("        let direct = run_simulation_js(params).unwrap();\n")
+        test.append("        let json = |value: &JsValue| String::from(js_sys::JSON::stringify(value).unwrap());\n")
+        test.append('        for key in ["time", "species"] {\n')
+        test.append("            assert_eq!(json(&get(&direct, key)), json(&get(&result, key)), \"{}\", key);\n")
+        test.append("        }\n")
+        test.append("    }\n\n")
+        test.append("    #[wasm_bindgen_test]\n")
+        test.append("    fn malformed_params_are_errors_in_js() {\n")
+        test.append('        let result = js_sys::JSON::parse(&run_simulation(r#"{"final_time": 2,"#)).unwrap();\n')
+        test.append('        assert_eq!(get(&get(&result, "error"), "kind").as_string().as_deref(), Some("invalid_params"));\n')
+        test.append("        // Non-ASCII text survives both string conversions\n")
+        test.append('        let result = js_sys::JSON::parse(&run_simulation(r#"{"dosis_µg": 1}"#)).unwrap();\n')
+        test.append('        let message = get(&get(&result, "error"), "message").as_string().unwrap();\n')
+        test.append('        assert!(message.contains("dosis_µg"), "{}", message);\n')
+        test.append('        assert!(run_simulation_js(JsValue::from_str("{")).is_err());\n')
+        test.append("    }\n\n")
+        test.append("    #[wasm_bindgen_test]\n")
+        test.append("    fn large_results_and_logging_reach_js() {\n")
+        test.append("        // Debug logging calls the console import on every step and event\n")
+        test.append('        set_log_level("debug").unwrap();\n')
+        test.append('        let logged = run_simulation(r#"{"final_time": 1}"#);\n')
+        test.append('        set_log_level(if cfg!(debug_assertions) { "info" } else { "error" }).unwrap();\n')
+        test.append('        assert!(get(&js_sys::JSON::parse(&logged).unwrap(), "error").is_null());\n')
+        test.append("        // Tens of thousands of points, megabytes of JSON\n")
+        test.append('        let output = run_simulation(r#"{"max_step": 0.001}"#);\n')
+        test.append("        let result = js_sys::JSON::parse(&output).unwrap();\n")
+        test.append('        assert!(get(&result, "error").is_null());\n')
+        test.append('        assert!(js_sys::Array::from(&get(&result, "time")).length() > 24_000);\n')
+        test.append("    }\n\n")
+        test.append("    #[wasm_bindgen_test]\n")
+        test.append("    async fn async_run_matches_sync_run() {\n")
+        test.append("        let params = js_sys::JSON::parse(&get_default_parameters()).unwrap();\n")
+        test.append("        let sync = run_simulation_js(params.clone()).unwrap();\n")
+        test.append("        // A small chunk so the run yields many times\n")
+        test.append("        let result = run_simulation_async(params, Some(7)).await.unwrap();\n")
+        test.append(
+            "        let json = |value: &JsValue| String::from(js_sys::JSON::stringify(value).unwrap());\n"
+        )
+        test.append("        assert_eq!(json(&result), json(&sync));\n")
+        test.append("    }\n\n")
+        test.append("    #[wasm_bindgen_test]\n")
+        test.append("    fn session_runs_match_stateless_runs() {\n")
+        test.append("        let mut session = ModelSession::new(js_sys::Object::new().into()).unwrap();\n")
+        test.append("        session.set_final_time(2.0).unwrap();\n")
+        test.append('        session.set_parameter("rtol", 1e-8).unwrap();\n')
+        test.append('        assert!(session.set_parameter("not_a_parameter", 1.0).is_err());\n')
+        test.append('        assert!(session.set_parameter("rtol", -1.0).is_err());\n')
+        test.append(
+            '        let params = js_sys::JSON::parse(r#"{"final_time": 2.0, "rtol": 1e-8}"#).unwrap();\n'
+        )
+        test.append("        let expected = run_simulation_typed(params).unwrap();\n")
+        test.append("        for _ in 0..2 {\n")
+        test.append("            let handle = session.run();\n")
+        test.append("            assert_eq!(handle.times(), expected.times());\n")
+        test.append("            for name in SPECIES_IDS {\n")
+        test.append(
+            "                assert_eq!(handle.species(name).unwrap(), expected.species(name).unwrap());\n"
+        )
+        test.append("            }\n")
+        test.append("        }\n")
+        test.append("    }\n\n")
+        test.append("    #[wasm_bindgen_test]\n")
+        test.append("    fn progress_callback_reports_fraction_and_steps() {\n")
+        test.append("        let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));\n")
+        test.append("        let record = calls.clone();\n")
+        test.append(
+            "        let callback = Closure::<dyn FnMut(f64, f64)>::new(move |fraction, steps| record.borrow_mut().push((fraction, steps)));\n"
+        )
+        test.append(
+            "        set_progress_callback(Some(callback.as_ref().unchecked_ref::<js_sys::Function>().clone()), None, Some(5));\n"
+        )
+        test.append("        let result = run_simulation_js(js_sys::Object::new().into());\n")
+        test.append("        set_progress_callback(None, None, None);\n")
+        test.append("        assert!(result.is_ok());\n")
+        test.append("        let calls = calls.borrow();\n")
+        test.append("        assert!(!calls.is_empty());\n")
+        test.append(
+            "        assert!(calls.windows(2).all(|pair| pair[0].0 <= pair[1].0 && pair[0].1 < pair[1].1));\n"
+        )
+        test.append(
+            "        assert!(calls.iter().all(|&(fraction, steps)| (0.0..=1.0).contains(&fraction) && steps % 5.0 == 0.0));\n"
+        )
+        test.append("    }\n\n")
+        test.append("    #[wasm_bindgen_test]\n")
+        test.append("    fn throwing_progress_callback_is_ignored() {\n")
+        test.append(
+            '        let callback = js_sys::Function::new_with_args("fraction, steps", "throw new Error(\'progress bar failed\')");\n'
+        )
+        test.append("        set_progress_callback(Some(callback), None, Some(1));\n")
+        test.append("        let result = run_simulation_js(js_sys::Object::new().into());\n")
+        test.append("        set_progress_callback(None, None, None);\n")
+        test.append(
+            '        assert!(js_sys::Reflect::get(&result.unwrap(), &"error".into()).unwrap().is_null());\n'
+        )
+        test.append("    }\n")
+        test.append("}\n")
+        return "".join(test)
+
     def create_minimal_template(self, model_name: str) -> str:
         """Create a minimal Rust template for testing
 
@@ -1,17 +1,23 @@
 # File: sbml_rust_generator/facade.py
 """Main facade class for SBML to Rust conversion"""
 
+import os
 import sympy
-from typing import Dict, Any
+from datetime import datetime, timezone
+from typing import Dict, Any, List, Optional, Tuple
+from . import __version__
 from .models.sbml_model import SbmlModel
-from .parsers.expression_parser import SbmlExpressionParser
+from .parsers.expression_parser import RateOf, SbmlExpressionParser
 from .symbolic.ode_builder import OdeSystemBuilder
 from .symbolic.jacobian_builder import JacobianBuilder
 from .symbolic.optimizer import SymbolicOptimizer
 from .symbolic.assignment_processor import AssignmentRuleProcessor
-from .codegen.code_generator import RustBlockGenerator
+from .codegen.code_generator import (
+    RustBlockGenerator, live_bindings, parameter_defaults, parameter_range, symbol_names
+)
 from .codegen.template_manager import RustTemplateManager
 from .codegen.event_generator import EventCodeGenerator
+from .codegen.sensitivity_generator import SensitivityCodeGenerator
 
 
 class SbmlToRustConverter:
@@ -48,6 +54,30 @@ class SbmlToRustConverter:
             c_id: c.size for c_id, c in self.model.compartments.items()
         }
 
+        # Parameters and compartments driven by rate rules, changed by events
+        # or determined by algebraic rules are extra states appended after the
+        # species. They stay in the parameter struct so their value (e.g.
+        # IVDOSE) seeds the state; for an algebraic rule it is the first guess
+        # of the solution. A state only events change has a zero derivative.
+        self.algebraic_variables = self._algebraic_variables(model_data)
+        self.parameter_states = {}
+        determined = [rule.get("variable") for rule in model_data.get("rateRules", {}).values()]
+        determined += [
+            assignment.get("variable") for event in model_data.get("events", {}).values()
+            for assignment in event.get("eventAssignments", [])
+        ]
+        for variable in determined + list(self.algebraic_variables.values()):
+            if variable in self.species_map or variable in self.parameter_states:
+                continue
+            if variable in self.params_map:
+                self.parameter_states[variable] = self.params_map[variable]
+            elif variable in self.compartments_map:
+                self.parameter_states[variable] = self.compartments_map[variable]
+            else:
+                continue
+            self.species_map[variable] = len(self.species_list)
+            self.species_list.append(variable)
+
         # Create symbols
         self.sym_species = {s: sympy.Symbol(s) for s in self.species_list}
         self.sym_params = {p: sympy.Symbol(p) for p in self.params_map}
@@ -56,6 +86,63 @@ class SbmlToRustConverter:
         # Setup components
         self._setup_components()
 
+    def _algebraic_variables(self, model_data: Dict[str, Any]) -> Dict[str, str]:
+        """Pick the variable each algebraic rule determines
+
+        SBML leaves it implicit. A rule that names one keeps it; otherwise it
+        takes the first variable its math reads, in model order, that nothing
+        else sets: a species no reaction changes or a non-constant parameter
+        or compartment, without an assignment or rate rule and not taken by
+        an earlier rule.
+
+        Args:
+            model_data: Dictionary containing SBML model
+
+        Returns:
+            Dictionary mapping algebraic rule IDs to their variables
+
+        Raises:
+            ValueError: If a rule reads no variable it could determine
+        """
+        rules = model_data.get("algebraicRules", {})
+        if not rules:
+            return {}
+        names = list(self.model.species) + list(self.model.parameters) + list(self.model.compartments)
+        context = {name: sympy.Symbol(name) for name in names}
+        context["time"] = context["t"] = sympy.Symbol("t")
+        parser = SbmlExpressionParser(context, {
+            f_id: {"arguments": f.arguments, "mathString": f.math_string}
+            for f_id, f in self.model.functions.items()
+        })
+        changed = {
+            s_id for rxn in model_data.get("reactions", {}).values()
+            for _, s_id in rxn.get("reactants", []) + rxn.get("products", [])
+        }
+        ruled = {
+            rule.get("variable") for kind in ("assignmentRules", "rateRules")
+            for rule in model_data.get(kind, {}).values()
+        }
+        free = [s_id for s_id in self.model.species if s_id not in changed]
+        free += [p_id for p_id, p in self.model.parameters.items() if not p.constant]
+        free += [c_id for c_id, c in self.model.compartments.items() if not c.constant]
+        free = [name for name in free if name not in ruled]
+
+        variables = {}
+        for rule_id, rule in rules.items():
+            variable = rule.get("variable")
+            if not variable:
+                read = {symbol.name for symbol in parser.parse(rule.get("math", "0")).free_symbols}
+                variable = next(
+                    (name for name in free if name in read and name not in variables.values()), None
+                )
+            if variable is None:
+                raise ValueError(
+                    f"Algebraic rule {rule_id} determines no variable: it reads no species, "
+                    "non-constant parameter or compartment that nothing else sets"
+                )
+            variables[rule_id] = variable
+        return variables
+
     def _setup_components(self):
         """Initialize all pipeline components"""
         # Create parsing context
@@ -66,6 +153,10 @@ class SbmlToRustConverter:
         context["time"] = sympy.Symbol("t")
         context["t"] = sympy.Symbol("t")
 
+        # Reaction IDs stand for their rates in SBML math
+        for r_id in self.model.reactions:
+            context.setdefault(r_id, sympy.Symbol(r_id))
+
         # Get functions as dict
         functions_dict = {
             f_id: {"arguments": f.arguments, "mathString": f.math_string}
@@ -75,33 +166,111 @@ class SbmlToRustConverter:
         # Initialize components
         self.expression_parser = SbmlExpressionParser(context, functions_dict)
         self.assignment_processor = AssignmentRuleProcessor(self.expression_parser)
-        self.ode_builder = OdeSystemBuilder(self.species_map, self.expression_parser)
+        self.ode_builder = OdeSystemBuilder(
+            self.species_map,
+            self.expression_parser,
+            {r_id: r.local_parameters for r_id, r in self.model.reactions.items()},
+        )
         self.jacobian_builder = JacobianBuilder(self.sym_species, self.species_list)
         self.optimizer = SymbolicOptimizer(optimization_level=2)
         self.code_generator = RustBlockGenerator()
         self.event_generator = EventCodeGenerator(
-            self.code_generator, self.expression_parser
+            self.code_generator.code_gen, self.expression_parser
+        )
+        self.sensitivity_generator = SensitivityCodeGenerator(
+            self.code_generator.code_gen, self.optimizer
         )
         self.template_manager = RustTemplateManager()
 
-    def convert(self, model_name: str = "sbml_model", wasm: bool = True) -> str:
+    def convert(
+        self, model_name: str = "sbml_model", wasm: bool = True, sensitivities: bool = False,
+        uptake_window: Optional[Tuple[str, str]] = None, embed_sbml: bool = True,
+        sparse_jacobian: bool = False, jacobian_matrix: bool = False,
+        pulse_sharpness: Optional[float] = None, uptake_repeat: Optional[Tuple[str, str]] = None
+    ) -> str:
         """Main conversion method
 
         Args:
             model_name: Name for the generated Rust module
             wasm: If True, generate WASM-compatible code (browser). If False, generate native Rust code.
+            sensitivities: If True, also generate run_simulation_with_sensitivities
+                (forward sensitivities; models with events are not supported)
+            uptake_window: Names of the parameters bounding the model's dose window,
+                e.g. ("t0", "t1"). The rate terms reading them are summed over the
+                uptake_windows simulation parameter when it is given, or over the
+                repeated windows of uptake_repeat.
+            embed_sbml: If True, embed the source SBML for get_model_sbml. Set it
+                to False for size-sensitive builds; get_model_sbml then returns an error.
+            sparse_jacobian: If True, the Newton iterations use a sparse Jacobian and
+                sparse LU (BuiltProblem::sparse). Worth it for large models whose species
+                each depend on a few others; results match the dense path.
+            jacobian_matrix: If True, also generate the Jacobian matrix itself (jac_matrix),
+                which the solver fills its Newton matrix from instead of assembling it
+                column by column from Jacobian-vector products.
+            pulse_sharpness: With uptake_window, rewrite each tanh edge of the window
+                terms, tanh(k*(t - t0)) or tanh(k*(t - t1)), to the steepness
+                pulse_sharpness/(t1 - t0), so the smoothed pulse keeps its shape
+                whatever the window length. None keeps the SBML steepness.
+            uptake_repeat: With uptake_window, names of the parameters giving the
+                number of uptakes and the time between their starts, e.g.
+                ("n_O", "period_O"). Each uptake is a copy of the window at the
+                parameter defaults, the first starting at the window start.
 
         Returns:
             Complete Rust source code as string
+
+        Raises:
+            ValueError: If uptake_window or uptake_repeat names unknown parameters,
+                no rate reads the window, the window length does not follow from
+                the parameter defaults, or sensitivities are requested as well;
+                or if sensitivities
+                are requested for a model with algebraic rules
         """
-        # 1. Process assignment rules
+        if sensitivities and self.algebraic_variables:
+            raise ValueError("Algebraic rules are not supported with sensitivities")
+
+        # 1. Process assignment rules (reaction IDs replaced by their rates)
         print("Processing assignment rules...")
-        assignment_rules = self.assignment_processor.process(self.model_data)
+        reaction_rates = self.ode_builder.parse_reaction_rates(self.model_data["reactions"])
+        assignment_rules = self.assignment_processor.process(
+            self.model_data, reaction_rates
+        )
         print(f"Found {len(assignment_rules)} assignment rules")
+        parameter_rules, state_dependent_rules = self.assignment_processor.split_by_state_dependence(
+            assignment_rules, set(self.species_list)
+        )
 
         # 2. Build ODE system
         print("Building ODE system...")
-        ode_system = self.ode_builder.build_ode_system(self.model_data["reactions"])
+        ode_system = self.ode_builder.build_ode_system(
+            self.model_data["reactions"], reaction_rates
+        )
+        ode_system = self.ode_builder.apply_rate_rules(
+            ode_system, self.model_data.get("rateRules", {}), reaction_rates
+        )
+        ode_system = self.ode_builder.apply_algebraic_rules(
+            ode_system, self.model_data.get("algebraicRules", {}), self.algebraic_variables,
+            reaction_rates
+        )
+        # get_model_equations lists the derivatives as written, rules unsubstituted
+        equations = ode_system
+        ode_system = self.assignment_processor.substitute_rules(
+            ode_system, state_dependent_rules
+        )
+        if any(expr.has(RateOf) for expr in ode_system + [expr for _, expr in assignment_rules]):
+            ode_system, assignment_rules = self._resolve_rate_of(
+                ode_system, assignment_rules, state_dependent_rules
+            )
+            parameter_rules, state_dependent_rules = self.assignment_processor.split_by_state_dependence(
+                assignment_rules, set(self.species_list)
+            )
+        window_terms = {}
+        if uptake_window:
+            if sensitivities:
+                raise ValueError("Uptake windows are not supported with sensitivities")
+            ode_system, window_terms = self.ode_builder.split_window_terms(
+                ode_system, uptake_window, pulse_sharpness
+            )
 
         # 3. Compute Jacobian
         jacobian_elements, jac_indices = self.jacobian_builder.compute_sparse_jacobian(
@@ -115,16 +284,62 @@ class SbmlToRustConverter:
 
         # 5. Generate code blocks
         code_blocks = self._generate_code_blocks(
-            replacements, reduced_ode, reduced_jac, jac_indices, assignment_rules, model_name, wasm
+            replacements, reduced_ode, reduced_jac, jac_indices, assignment_rules,
+            parameter_rules, state_dependent_rules, model_name, wasm,
+            window_terms, uptake_window, uptake_repeat,
+            self._provenance(sensitivities, embed_sbml, sparse_jacobian, jacobian_matrix)
+        )
+        if jacobian_matrix:
+            code_blocks["jacobian_pattern"] = self.code_generator.generate_jacobian_pattern(jac_indices)
+            code_blocks["jac_values_block"] = self.code_generator.generate_jacobian_values(reduced_jac)
+        code_blocks["equations_fn"] = self.code_generator.generate_equations_function(
+            self.species_list, equations, assignment_rules, wasm,
+            list(self.algebraic_variables.values())
+        )
+        document = self.model_data.get("model", {}).get("document")
+        code_blocks["sbml_fn"] = self.code_generator.generate_sbml_function(
+            document if embed_sbml else None,
+            "the source SBML was not embedded in this build (generated with embed_sbml=False)"
+            if document is not None else
+            "this model was not generated from an SBML file",
+            wasm
         )
+        if sensitivities:
+            code_blocks.update(self._generate_sensitivity_blocks(
+                ode_system, jacobian_elements, jac_indices, assignment_rules,
+                parameter_rules, self.template_manager.model_type(model_name), wasm
+            ))
 
         # 6. Assemble final Rust file
         return self.template_manager.assemble_rust_file(
-            model_name, code_blocks, wasm=wasm
+            model_name, code_blocks, wasm=wasm, sparse_jacobian=sparse_jacobian
+        )
+
+    def _resolve_rate_of(self, ode_system, assignment_rules, state_dependent_rules):
+        """Replace rateOf in the derivatives and assignment rules by the rates read
+
+        Args:
+            ode_system: dy/dt expressions, state-dependent rules inlined
+            assignment_rules: List of (variable, expression) tuples for assignment rules
+            state_dependent_rules: Subset of assignment_rules depending on state or time
+
+        Returns:
+            Tuple of (dy/dt expressions, assignment rules) free of rateOf
+        """
+        variables = [variable for variable, _ in assignment_rules]
+        rules = dict(zip(variables, self.assignment_processor.substitute_rules(
+            [expr for _, expr in assignment_rules], state_dependent_rules
+        )))
+        ode_system = self.ode_builder.resolve_rate_of(ode_system, rules)
+        expressions = self.ode_builder.substitute_rate_of(
+            [expr for _, expr in assignment_rules], ode_system, rules
         )
+        return ode_system, list(zip(variables, expressions))
 
     def _generate_code_blocks(
-        self, replacements, reduced_ode, reduced_jac, jac_indices, assignment_rules, model_name, wasm
+        self, replacements, reduced_ode, reduced_jac, jac_indices, assignment_rules,
+        parameter_rules, state_dependent_rules, model_name, wasm,
+        window_terms=None, uptake_window=None, uptake_repeat=None, provenance=None
     ) -> Dict[str, str]:
         """Generate all code blocks needed for the template
 
@@ -134,6 +349,12 @@ class SbmlToRustConverter:
             reduced_jac: Reduced Jacobian expressions
             jac_indices: Jacobian sparsity indices
             assignment_rules: List of (variable, expression) tuples for assignment rules
+            parameter_rules: Subset of assignment_rules depending only on parameters
+            state_dependent_rules: Subset of assignment_rules depending on state or time
+            window_terms: Rate terms moved out of the ODEs by split_window_terms
+            uptake_window: Names of the parameters bounding the dose window
+            uptake_repeat: Names of the uptake count and period parameters
+            provenance: Source and build details for get_model_metadata
 
         Returns:
             Dictionary with all code block components
@@ -159,6 +380,59 @@ class SbmlToRustConverter:
             s_id: species.initial_amount
             for s_id, species in self.model.species.items()
         }
+        species_initial_amounts.update(self.parameter_states)
+
+        # States with an initial assignment start from its top-level binding,
+        # rate-rule states from their parameter value
+        initial_expressions = {
+            var: var for var in initial_assigned_vars if var in self.species_map
+        }
+        initial_expressions.update(
+            {var: var for var in self.parameter_states if var not in all_assigned_vars}
+        )
+
+        # The solver parameter vector holds the struct fields, then the values
+        # of parameter rules and initial assignments, then the initial state;
+        # closures read their parameters from it so runs can swap it cheaply
+        parameter_bindings = self.assignment_processor.sort_with_initial_assignments(
+            parameter_rules, initial_assignments
+        )
+        parameter_names = [
+            p_id for p_id, _ in parameter_defaults(filtered_params, filtered_compartments)
+        ]
+        parameter_names += [var for var, _ in parameter_bindings if var not in self.species_map]
+        parameter_index = {
+            name: i for i, name in enumerate(parameter_names) if name not in self.species_map
+        }
+        parameter_order = parameter_names + [f"init_{s_id}" for s_id in self.species_list]
+
+        # Each closure binds only the species and CSE temporaries it reads
+        species_bindings = [(s_id, None) for s_id in self.species_list]
+        rhs_bindings, rhs_used = live_bindings(
+            species_bindings + list(replacements), symbol_names(reduced_ode)
+        )
+        jac_bindings, jac_used = live_bindings(
+            species_bindings + list(replacements), symbol_names(reduced_jac)
+        )
+        rhs_temps = [(sym, expr) for sym, expr in rhs_bindings if expr is not None]
+        if window_terms:
+            unknown = [
+                name for name in (*uptake_window, *(uptake_repeat or ())) if name not in parameter_index
+            ]
+            if unknown:
+                raise ValueError(f"Unknown uptake window parameters: {', '.join(unknown)}")
+            rhs_used = rhs_used | symbol_names(window_terms.values())
+        jac_temps = [(sym, expr) for sym, expr in jac_bindings if expr is not None]
+        # Parameters each derivative reads, through the CSE temporaries and window terms
+        state_parameters = []
+        for s_id, expr in zip(self.species_list, reduced_ode):
+            reads = symbol_names([expr, window_terms.get(s_id)] if window_terms else [expr])
+            _, reads = live_bindings(list(replacements), reads)
+            state_parameters.append(sorted(reads & set(parameter_index)))
+        rhs_lookups = self.code_generator.generate_parameter_lookups(parameter_index, rhs_used)
+        jac_lookups = self.code_generator.generate_parameter_lookups(parameter_index, jac_used)
+
+        species_details = self._species_details(parameter_index)
 
         # Generate struct fields (with initial amount options)
         species_fields, param_fields = self.template_manager.generate_struct_fields(
@@ -173,36 +447,79 @@ class SbmlToRustConverter:
                 filtered_params, filtered_compartments
             ),
             "assignment_rules": self.code_generator.generate_assignment_rules(
-                assignment_rules
+                parameter_bindings
             ),
-            "initial_assignments": self.code_generator.generate_initial_assignments(
-                initial_assignments, self.expression_parser
+            "parameter_order": ", ".join(f'"{name}"' for name in parameter_order),
+            "parameter_values": self.code_generator.generate_parameter_values(
+                parameter_names + self.code_generator.generate_initial_values(
+                    self.species_list, species_initial_amounts, initial_expressions
+                )
             ),
+            "param_lookups": rhs_lookups,
+            "jac_param_lookups": jac_lookups,
             "species_extract": self.code_generator.generate_species_extraction(
-                self.species_map
+                self.species_map, rhs_used
+            ),
+            "temp_vars": self.code_generator.generate_temp_vars(rhs_temps),
+            "jac_species_extract": self.code_generator.generate_species_extraction(
+                self.species_map, jac_used
             ),
-            "temp_vars": self.code_generator.generate_temp_vars(replacements),
+            "jac_temp_vars": self.code_generator.generate_temp_vars(jac_temps),
+            "rhs_time_arg": "t" if "t" in rhs_used else "_t",
+            "jac_time_arg": "t" if "t" in jac_used else "_t",
+            "rhs_param_arg": "p" if rhs_lookups else "_p",
+            "jac_param_arg": "p" if jac_lookups else "_p",
+            "rhs_state_arg": "y" if len(rhs_bindings) > len(rhs_temps) else "_y",
+            "jac_state_arg": "y" if len(jac_bindings) > len(jac_temps) else "_y",
             "rhs_block": self.code_generator.generate_derivatives(reduced_ode),
+            "window_terms": self.code_generator.generate_window_terms(
+                window_terms, uptake_window
+            ) if window_terms else "",
+            "window_repeat": self._window_repeat(
+                uptake_window, uptake_repeat, parameter_index,
+                parameter_defaults(filtered_params, filtered_compartments), parameter_bindings
+            ) if window_terms and uptake_repeat else "",
             "jac_block": self.code_generator.generate_jacobian(
                 reduced_jac, jac_indices
             ),
-            "init_block": self.code_generator.generate_init_function(
-                self.species_list, self.species_map, species_initial_amounts
+            "observables_fn": self.code_generator.generate_observables_function(
+                self.species_map, assignment_rules, state_dependent_rules, parameter_index
             ),
-            "result_vectors_init": self.code_generator.generate_result_vectors_init(
-                self.species_list
+            "observable_ids": self.code_generator.generate_observable_ids(assignment_rules),
+            "volumes_fn": self.code_generator.generate_volume_function(
+                self.species_map,
+                {
+                    s_id: details.get("compartment")
+                    for s_id, details in species_details.items()
+                    if not details.get("is_boundary")
+                },
+                state_dependent_rules,
+                parameter_index
             ),
-            "initial_pushes": self.code_generator.generate_result_pushes(
-                self.species_list, indent="    "
+            "mass_fn": self.code_generator.generate_mass_function(
+                self.species_map,
+                self._mass_coefficients(state_dependent_rules, parameter_index),
+                parameter_index
             ),
-            "loop_pushes": self.code_generator.generate_result_pushes(
-                self.species_list, indent="            "
+            # Rows of the system held at zero rather than integrated
+            "algebraic_states": "&[" + ", ".join(
+                str(self.species_map[variable]) for variable in self.algebraic_variables.values()
+            ) + "]",
+            "init_block": self.code_generator.generate_init_function(
+                len(self.species_list), len(parameter_names)
             ),
-            "map_inserts": self.code_generator.generate_hashmap_inserts(
-                self.species_list
+            "result_keys": self.code_generator.generate_result_keys(self.species_list),
+            "state_parameters": self.code_generator.generate_state_parameters(state_parameters),
+            "parameter_checks": self.code_generator.generate_parameter_checks(
+                self._parameter_checks(
+                    parameter_names, filtered_params, filtered_compartments, parameter_bindings
+                )
             ),
             "n_species": len(self.species_list),
             "gut_idx": self.species_map.get("QGut", 5),  # Default to 5 if not found
+            # Length and SBML id of the model time unit, None when the SBML does not declare it
+            "model_time_seconds": self.model_data.get("model", {}).get("time_units", {}).get("seconds"),
+            "model_time_unit": self.model_data.get("model", {}).get("time_units", {}).get("id"),
         }
 
         # Add metadata functions for UI/tools
@@ -212,7 +529,26 @@ class SbmlToRustConverter:
             species_initial_amounts,
             filtered_params,
             filtered_compartments,
-            wasm
+            wasm,
+            {
+                variable: getattr(self.model.parameters.get(variable), "units", None)
+                for variable, _ in assignment_rules
+            },
+            self._parameter_info(filtered_params, filtered_compartments),
+            species_details,
+            self._compartment_info(parameter_index, parameter_bindings + state_dependent_rules),
+            provenance,
+        )
+
+        code_blocks["types_fn"] = self.template_manager.generate_types_function(
+            model_name,
+            self.species_list,
+            filtered_params,
+            filtered_compartments,
+            [variable for variable, _ in assignment_rules],
+            bool(window_terms),
+            wasm,
+            list(self.algebraic_variables.values()),
         )
 
         # Add event handling if events exist
@@ -220,12 +556,326 @@ class SbmlToRustConverter:
         if events:
             print(f"Generating event handling for {len(events)} events...")
             event_components = self.event_generator.generate_event_handling(
-                events, self.species_map
+                events, self.species_map, state_dependent_rules, parameter_index
             )
             code_blocks.update(event_components)
 
         return code_blocks
 
+    def _parameter_checks(
+        self, parameter_names, params, compartments, parameter_bindings
+    ) -> List[Tuple[str, int, str, List[str]]]:
+        """Pick the physical range check of each entry of the parameter vector
+
+        Every model parameter is checked, against its inferred range or, when
+        none is known, for negative values (a warning only). Values computed
+        by parameter rules are checked when their range is known, e.g.
+        euromix's Poor volume goes negative when the volume fractions sum
+        past one.
+
+        Args:
+            parameter_names: Names of the parameter vector entries before the initial state
+            params: Parameters that are SimulationParams fields
+            compartments: Compartments that are SimulationParams fields
+            parameter_bindings: Parameter rules and initial assignments in dependency order
+
+        Returns:
+            List of (name, index in p, ParameterRange variant, source parameters)
+        """
+        index = {name: i for i, name in enumerate(parameter_names)}
+
+        checks = []
+        fields = [name for name, _ in parameter_defaults(params, compartments)]
+        for name in fields:
+            checks.append((name, index[name], self._parameter_range(name) or "Unknown", []))
+
+        # Derived values name the model parameters they are computed from
+        sources = {name: {name} for name in fields}
+        for variable, expr in parameter_bindings:
+            reads = set()
+            for name in symbol_names([expr]):
+                reads |= sources.get(name, set())
+            sources[variable] = reads
+            range_kind = self._parameter_range(variable)
+            if range_kind and variable in index:
+                ordered = [name for name in fields if name in reads]
+                checks.append((variable, index[variable], range_kind, ordered))
+
+        return checks
+
+    def _parameter_range(self, name: str) -> Optional[str]:
+        """Infer the physical range of a parameter or compartment
+
+        Args:
+            name: Parameter or compartment id
+
+        Returns:
+            ParameterRange variant name, or None when no range is known
+        """
+        if name in self.compartments_map:
+            return "NonNegative"
+        parameter = self.model.parameters.get(name)
+        return parameter_range(name, parameter.name, parameter.units) if parameter else None
+
+    def _parameter_info(self, params, compartments) -> Dict[str, Dict[str, Any]]:
+        """Collect the SBML name, units, notes and soft bounds of each field
+
+        Soft bounds follow the inferred physical range: fractions and
+        hematocrit lie in [0, 1], volumes, flows and body weight are at
+        least 0.
+
+        Args:
+            params: Parameters that are SimulationParams fields
+            compartments: Compartments that are SimulationParams fields
+
+        Returns:
+            Dictionary mapping field ids to their get_parameters_info entries
+        """
+        bounds = {
+            "Fraction": (0.0, 1.0),
+            "BelowOne": (0.0, 1.0),
+            "Positive": (0.0, None),
+            "NonNegative": (0.0, None),
+        }
+        info = {}
+        for name, _ in parameter_defaults(params, compartments):
+            component = self.model.parameters.get(name) or self.model.compartments.get(name)
+            low, high = bounds.get(self._parameter_range(name), (None, None))
+            info[name] = {
+                "name": getattr(component, "name", None) or None,
+                "units": getattr(component, "units", None) or None,
+                "description": getattr(component, "notes", None) or None,
+                "min": low,
+                "max": high,
+            }
+        return info
+
+    def _species_details(self, parameter_index) -> Dict[str, Dict[str, Any]]:
+        """Collect the get_species_info entry of each state
+
+        A species' unit is its substance unit, divided by the units of its
+        compartment when the SBML value is a concentration. States driven by
+        rate rules on parameters take the parameter's units.
+
+        Args:
+            parameter_index: Index in p of each parameter, compartment and derived value
+
+        Returns:
+            Dictionary mapping state ids to their compartment, index of the
+            compartment size in p, "is_amount", "is_boundary" and "units"
+            (None when the SBML does not give them)
+        """
+        details = {}
+        for s_id, species in self.model.species.items():
+            compartment = self.model.compartments.get(species.compartment)
+            units = species.units or None
+            if units and not species.has_only_substance_units:
+                volume_units = getattr(compartment, "units", None)
+                units = f"{units}/{volume_units}" if volume_units else None
+            details[s_id] = {
+                "compartment": species.compartment if compartment is not None else None,
+                "volume_index": parameter_index.get(species.compartment),
+                "is_amount": species.has_only_substance_units,
+                "is_boundary": species.boundary_condition,
+                "units": units,
+            }
+        for s_id in self.species_list:
+            if s_id not in details:
+                details[s_id] = {"units": getattr(self.model.parameters.get(s_id), "units", None)}
+        return details
+
+    def _compartment_info(self, parameter_index, rules) -> Dict[str, Dict[str, Any]]:
+        """Collect the get_compartments_info entry of each SBML compartment
+
+        Args:
+            parameter_index: Index in p of each parameter, compartment and derived value
+            rules: Parameter bindings and state-dependent rules as (variable, expression)
+
+        Returns:
+            Dictionary mapping compartment ids to their name, units, index of
+            the size in p (None when it changes during a run), formula and species
+        """
+        formulas = {variable: str(expr) for variable, expr in rules}
+        return {
+            c_id: {
+                "name": compartment.name or None,
+                "units": compartment.units or None,
+                "size_index": parameter_index.get(c_id),
+                "formula": formulas.get(c_id),
+                "species": [
+                    s_id for s_id, species in self.model.species.items()
+                    if species.compartment == c_id
+                ],
+            }
+            for c_id, compartment in self.model.compartments.items()
+        }
+
+    @staticmethod
+    def _window_repeat(
+        uptake_window: Tuple[str, str], uptake_repeat: Tuple[str, str],
+        parameter_index: Dict[str, int], defaults: List[Tuple[str, float]],
+        parameter_bindings: List[Tuple[str, sympy.Expr]]
+    ) -> str:
+        """Build the Rust call that repeats the dose window as its parameters ask
+
+        Each uptake keeps the length of the window at the parameter defaults,
+        so the period parameter only spaces the uptakes, even where the SBML
+        also derives the window end from it.
+
+        Args:
+            uptake_window: Names of the parameters bounding the dose window
+            uptake_repeat: Names of the uptake count and period parameters
+            parameter_index: Index of each parameter in the solver parameter vector
+            defaults: (id, default) pairs of the SimulationParams fields
+            parameter_bindings: Derived parameters and their expressions, in
+                dependency order
+
+        Returns:
+            Rust expression of type Result<Vec<UptakeWindow>, SimulationError>
+
+        Raises:
+            ValueError: If the window length does not follow from the defaults
+        """
+        values = {sympy.Symbol(name): value for name, value in defaults}
+        for name, expr in parameter_bindings:
+            values[sympy.Symbol(str(name))] = sympy.sympify(expr).subs(values)
+        start, end = (sympy.Symbol(name) for name in uptake_window)
+        width = sympy.sympify(values.get(end, end) - values.get(start, start))
+        if not width.is_number or not width > 0:
+            raise ValueError(f"The uptake window length is not a positive number at the defaults: {width}")
+        count, period = uptake_repeat
+        return (
+            f"repeated_windows(p[{parameter_index[uptake_window[0]]}], {float(width)!r}, "
+            f'("{count}", p[{parameter_index[count]}]), ("{period}", p[{parameter_index[period]}]))'
+        )
+
+    def _provenance(
+        self, sensitivities: bool, embed_sbml: bool, sparse_jacobian: bool,
+        jacobian_matrix: bool = False
+    ) -> Dict[str, Any]:
+        """Describe the source and build of the generated code for get_model_metadata
+
+        The generation time honours SOURCE_DATE_EPOCH, so regenerating a model
+        for comparison can give byte-identical output.
+
+        Args:
+            sensitivities: Whether run_simulation_with_sensitivities is generated
+            embed_sbml: Whether the source SBML is embedded for get_model_sbml
+            sparse_jacobian: Whether the solver factorises a sparse Jacobian
+            jacobian_matrix: Whether the Jacobian matrix is generated alongside J·v
+
+        Returns:
+            Dictionary with "sbml" (id, name, level, version and sha256 of the
+            source file, None for models not read from a file), "generator"
+            (version and ISO 8601 UTC time) and the list of "features"
+        """
+        source = self.model_data.get("model", {})
+        epoch = os.environ.get("SOURCE_DATE_EPOCH")
+        generated_at = (
+            datetime.fromtimestamp(int(epoch), timezone.utc) if epoch else datetime.now(timezone.utc)
+        )
+        features = [
+            feature for feature, present in (
+                ("events", bool(self.model_data.get("events"))),
+                ("sensitivities", sensitivities),
+                ("embedded_sbml", embed_sbml and source.get("document") is not None),
+                ("sparse_jacobian", sparse_jacobian),
+                ("jacobian_matrix", jacobian_matrix),
+            )
+            if present
+        ]
+        return {
+            "sbml": {key: source.get(key) for key in ("id", "name", "level", "version", "sha256")},
+            "generator": {
+                "version": __version__,
+                "generated_at": generated_at.strftime("%Y-%m-%dT%H:%M:%SZ"),
+            },
+            "features": features,
+        }
+
+    def _mass_coefficients(self, state_dependent_rules, parameter_index) -> Dict[str, sympy.Expr]:
+        """Pick the mass factor of each state for the mass balance diagnostic
+
+        States converted to mg by an assignment rule are counted with that
+        conversion. A model without such rules counts the amounts of its
+        species as they are.
+
+        Args:
+            state_dependent_rules: Assignment rules depending on state or time
+            parameter_index: Dictionary mapping parameter names to indices in p
+
+        Returns:
+            Dictionary mapping state names to their mass factor
+        """
+        mass_variables = {
+            variable for variable, _ in state_dependent_rules
+            if getattr(self.model.parameters.get(variable), "units", None) == "mg"
+        }
+        coefficients = {
+            s_id: factor for s_id, factor in self.assignment_processor.mass_coefficients(
+                state_dependent_rules, mass_variables, self.species_list
+            ).items()
+            if {str(sym) for sym in factor.free_symbols} <= set(parameter_index)
+        }
+        if not coefficients:
+            return {s_id: sympy.Integer(1) for s_id in self.model.species}
+
+        left_out = [s_id for s_id in self.model.species if s_id not in coefficients]
+        if left_out:
+            print(f"Mass balance leaves out states without an mg rule: {', '.join(left_out)}")
+        return coefficients
+
+    def _generate_sensitivity_blocks(
+        self, ode_system, jacobian_elements, jac_indices, assignment_rules,
+        parameter_rules, model_type, wasm
+    ) -> Dict[str, str]:
+        """Generate the forward sensitivity entry point
+
+        Args:
+            ode_system: dy/dt expressions before CSE
+            jacobian_elements: Non-zero Jacobian elements before CSE
+            jac_indices: Jacobian sparsity indices
+            assignment_rules: All assignment rules (their variables are not parameters)
+            parameter_rules: Assignment rules depending only on parameters
+            model_type: Name of the model's PkModel type
+            wasm: If True, export the entry point with wasm_bindgen
+
+        Returns:
+            Dictionary with the sensitivity_fn code block
+
+        Raises:
+            ValueError: If the model has events, whose state resets the
+                sensitivity equations do not account for
+        """
+        if self.model_data.get("events"):
+            raise ValueError("Sensitivities are not supported for models with events")
+
+        print("Generating forward sensitivity equations...")
+        initial_assignments = self.model_data.get("initialAssignments", {})
+        bindings = self.assignment_processor.sort_with_initial_assignments(
+            parameter_rules, initial_assignments
+        )
+        bound = {var for var, _ in bindings}
+        assigned = bound | {var for var, _ in assignment_rules}
+        parameters = [
+            p_id for p_id, _ in parameter_defaults(self.params_map, self.compartments_map)
+            if p_id not in assigned
+        ]
+
+        # States with an initial assignment or a rate-rule parameter start from
+        # that expression; the rest from their constant amount
+        initial_values = {}
+        for s_id in self.species_list:
+            if s_id in bound or s_id in self.parameter_states:
+                initial_values[s_id] = sympy.Symbol(s_id)
+            else:
+                initial_values[s_id] = self.model.species[s_id].initial_amount or 0.0
+
+        return self.sensitivity_generator.generate_sensitivity_function(
+            ode_system, jacobian_elements, jac_indices, self.species_map,
+            parameters, bindings, initial_values, model_type, wasm
+        )
+
     def get_model_info(self) -> Dict[str, Any]:
         """Get information about the loaded model
 
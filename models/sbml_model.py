@@ -1,7 +1,7 @@
 # File: sbml_rust_generator/models/sbml_model.py
 """Data models for SBML components"""
 
-from dataclasses import dataclass
+from dataclasses import dataclass, field
 from typing import Dict, List, Any, Tuple, Optional
 
 
@@ -14,6 +14,7 @@ class Species:
     initial_amount: float
     boundary_condition: bool = False
     has_only_substance_units: bool = False
+    units: Optional[str] = None
 
 
 @dataclass
@@ -23,6 +24,8 @@ class Parameter:
     value: float
     constant: bool = True
     name: Optional[str] = None
+    units: Optional[str] = None
+    notes: Optional[str] = None
 
 
 @dataclass
@@ -32,6 +35,9 @@ class Compartment:
     size: float
     constant: bool = True
     spatial_dimensions: int = 3
+    name: Optional[str] = None
+    units: Optional[str] = None
+    notes: Optional[str] = None
 
 
 @dataclass
@@ -43,6 +49,7 @@ class Reaction:
     rate_law: str
     reversible: bool = False
     name: Optional[str] = None
+    local_parameters: Dict[str, str] = field(default_factory=dict)  # Local ID -> promoted parameter ID
 
 
 @dataclass
@@ -82,9 +89,14 @@ class SbmlModel:
                 id=species_id,
                 name=species_data.get("name", species_id),
                 compartment=species_data.get("compartment", "default"),
-                initial_amount=species_data.get("value", 0.0),  # Parser stores as "value"
-                boundary_condition=species_data.get("boundaryCondition", False),
-                has_only_substance_units=species_data.get("hasOnlySubstanceUnits", False)
+                # Parser stores as "value"; unset initial values start empty
+                initial_amount=species_data.get("value") or 0.0,
+                # The parser writes isBoundarySpecies, to_dict boundaryCondition
+                boundary_condition=bool(
+                    species_data.get("boundaryCondition", species_data.get("isBoundarySpecies"))
+                ),
+                has_only_substance_units=species_data.get("hasOnlySubstanceUnits", False),
+                units=species_data.get("units")
             )
 
         # Parse parameters
@@ -93,7 +105,9 @@ class SbmlModel:
                 id=param_id,
                 value=param_data.get("value", 0.0),
                 constant=param_data.get("constant", True),
-                name=param_data.get("name")
+                name=param_data.get("name"),
+                units=param_data.get("units"),
+                notes=param_data.get("notes")
             )
 
         # Parse compartments
@@ -102,7 +116,10 @@ class SbmlModel:
                 id=comp_id,
                 size=comp_data.get("size", 1.0),
                 constant=comp_data.get("constant", True),
-                spatial_dimensions=comp_data.get("spatialDimensions", 3)
+                spatial_dimensions=comp_data.get("spatialDimensions", 3),
+                name=comp_data.get("name"),
+                units=comp_data.get("units"),
+                notes=comp_data.get("notes")
             )
 
         # Parse reactions
@@ -115,6 +132,9 @@ class SbmlModel:
                 reversible=rxn_data.get("reversible", False),
                 name=rxn_data.get("name")
             )
+            model._promote_local_parameters(
+                model.reactions[rxn_id], rxn_data.get("rxnParameters") or []
+            )
 
         # Parse functions
         for func_id, func_data in data.get("functions", {}).items():
@@ -126,6 +146,29 @@ class SbmlModel:
 
         return model
 
+    def _promote_local_parameters(
+        self, reaction: Reaction, local_parameters: List[Tuple[str, float]]
+    ) -> None:
+        """Promote kinetic-law local parameters to model parameters
+
+        Local parameters keep their ID unless it is already taken by another
+        model component, in which case they are prefixed with the reaction ID.
+
+        Args:
+            reaction: Reaction owning the kinetic law
+            local_parameters: List of (parameter_id, value) pairs
+        """
+        for param_id, value in local_parameters:
+            promoted_id = param_id
+            existing = self.parameters.get(param_id)
+            taken = param_id in self.species or param_id in self.compartments
+            if taken or (existing is not None and existing.value != value):
+                promoted_id = f"{reaction.id}_{param_id}"
+
+            if promoted_id not in self.parameters:
+                self.parameters[promoted_id] = Parameter(id=promoted_id, value=value)
+            reaction.local_parameters[param_id] = promoted_id
+
     def to_dict(self) -> Dict[str, Any]:
         """Convert model back to dictionary format
 
@@ -139,7 +182,8 @@ class SbmlModel:
                     "compartment": s.compartment,
                     "initialAmount": s.initial_amount,
                     "boundaryCondition": s.boundary_condition,
-                    "hasOnlySubstanceUnits": s.has_only_substance_units
+                    "hasOnlySubstanceUnits": s.has_only_substance_units,
+                    "units": s.units
                 }
                 for s_id, s in self.species.items()
             },
@@ -147,7 +191,9 @@ class SbmlModel:
                 p_id: {
                     "value": p.value,
                     "constant": p.constant,
-                    "name": p.name
+                    "name": p.name,
+                    "units": p.units,
+                    "notes": p.notes
                 }
                 for p_id, p in self.parameters.items()
             },
@@ -155,7 +201,10 @@ class SbmlModel:
                 c_id: {
                     "size": c.size,
                     "constant": c.constant,
-                    "spatialDimensions": c.spatial_dimensions
+                    "spatialDimensions": c.spatial_dimensions,
+                    "name": c.name,
+                    "units": c.units,
+                    "notes": c.notes
                 }
                 for c_id, c in self.compartments.items()
             },
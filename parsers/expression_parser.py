@@ -21,6 +21,32 @@ except ImportError:
     SBMLMATH_AVAILABLE = False
     print("Warning: sbmlmath not installed. Using fallback formula string parsing.")
 
+try:
+    import libsbml
+
+    LIBSBML_AVAILABLE = True
+except ImportError:
+    LIBSBML_AVAILABLE = False
+
+
+class Floor(sympy.floor):
+    """SBML floor; piecewise constant, so its derivative is taken as zero"""
+
+    def fdiff(self, argindex=1):
+        return sympy.S.Zero
+
+
+class Ceiling(sympy.ceiling):
+    """SBML ceiling; piecewise constant, so its derivative is taken as zero"""
+
+    def fdiff(self, argindex=1):
+        return sympy.S.Zero
+
+
+# SBML rateOf(x), the time derivative of x; OdeSystemBuilder.resolve_rate_of
+# replaces it once the derivatives are known
+RateOf = sympy.Function("rateOf")
+
 
 class SbmlExpressionParser(Parser):
     """Handles parsing of SBML mathematical expressions to SymPy expressions"""
@@ -57,17 +83,32 @@ class SbmlExpressionParser(Parser):
         self.context["tan"] = sympy.tan
         self.context["abs"] = sympy.Abs
 
+        # Rounding and extrema (libsbml writes ceiling as ceil)
+        self.context["floor"] = Floor
+        self.context["ceil"] = Ceiling
+        self.context["ceiling"] = Ceiling
+        self.context["max"] = sympy.Max
+        self.context["min"] = sympy.Min
+
         # Logic / Comparison Mappings
         self.context["gt"] = sympy.Gt
         self.context["lt"] = sympy.Lt
         self.context["ge"] = sympy.Ge
         self.context["le"] = sympy.Le
+        self.context["geq"] = sympy.Ge
+        self.context["leq"] = sympy.Le
         self.context["eq"] = sympy.Eq
         self.context["neq"] = sympy.Ne
         # Note: 'and' and 'or' are Python keywords, so we use 'And' and 'Or' in context
         # The _transform_logical_keywords method converts 'and(...)' to 'And(...)'
         self.context["And"] = sympy.And
         self.context["Or"] = sympy.Or
+        self.context["Not"] = sympy.Not
+        self.context["Xor"] = sympy.Xor
+        self.context["true"] = sympy.true
+        self.context["false"] = sympy.false
+
+        self.context["rateOf"] = RateOf
 
         # Helper for transformed piecewise
         self.context["sbml_piecewise"] = self._create_piecewise_function()
@@ -126,7 +167,9 @@ class SbmlExpressionParser(Parser):
             Exception: If sbmlmath not available or parsing fails
         """
         if not SBMLMATH_AVAILABLE or self.mathml_parser is None:
-            # Fallback: try to extract formula string and parse that
+            # Fallback: let libsbml render the MathML as a formula string
+            if LIBSBML_AVAILABLE:
+                return self._parse_formula_string(self._mathml_to_formula(mathml))
             print("Warning: sbmlmath not available, cannot parse MathML directly")
             raise Exception("sbmlmath required for MathML parsing")
 
@@ -154,6 +197,42 @@ class SbmlExpressionParser(Parser):
 
         return expr
 
+    def _mathml_to_formula(self, mathml: str) -> str:
+        """Convert MathML to a formula string using libsbml
+
+        Relations and logical operators are rendered in function form
+        (e.g. geq(time, 0.001), and(a, b)), which the formula parser understands.
+
+        Args:
+            mathml: MathML expression string
+
+        Returns:
+            Formula string
+
+        Raises:
+            Exception: If libsbml cannot read the MathML
+        """
+        ast = libsbml.readMathMLFromString(mathml)
+        if ast is None:
+            raise Exception("libsbml could not read MathML")
+        self._rename_time(ast)
+        return libsbml.formulaToString(ast).replace("^", "**")
+
+    def _rename_time(self, ast) -> None:
+        """Name every time csymbol in a libsbml AST "time"
+
+        The csymbol's text is free (often "t" or "time", but any name), and
+        formulaToString writes that text, which could then read as a model
+        symbol. "time" always maps to the closures' t argument.
+
+        Args:
+            ast: libsbml ASTNode, renamed in place
+        """
+        if ast.getType() == libsbml.AST_NAME_TIME:
+            ast.setName("time")
+        for i in range(ast.getNumChildren()):
+            self._rename_time(ast.getChild(i))
+
     def _preprocess_mathml_for_sbmlmath(self, mathml: str) -> tuple[str, list[str]]:
         """Preprocess MathML to remove attributes that cause issues with sbmlmath
 
@@ -214,7 +293,7 @@ class SbmlExpressionParser(Parser):
         # Replace TimeSymbol with 't'
         time_symbols = [
             s
-            for s in expr.atoms(sympy.Dummy)
+            for s in expr.atoms(sympy.Symbol)
             if hasattr(s, "definition_url")
             and "time" in getattr(s, "definition_url", "")
         ]
@@ -224,7 +303,7 @@ class SbmlExpressionParser(Parser):
         # Replace avogadro constant if present
         avogadro_symbols = [
             s
-            for s in expr.atoms(sympy.Dummy)
+            for s in expr.atoms(sympy.Symbol)
             if hasattr(s, "definition_url")
             and "avogadro" in getattr(s, "definition_url", "")
         ]
@@ -232,6 +311,15 @@ class SbmlExpressionParser(Parser):
             # SBML L3V2 defines avogadro as 6.02214179e23
             expr = expr.subs(av, sympy.Float(6.02214179e23))
 
+        # Rounding functions get the zero-derivative versions
+        expr = expr.replace(sympy.floor, Floor).replace(sympy.ceiling, Ceiling)
+
+        # The rateOf csymbol becomes the parser's rateOf function
+        expr = expr.replace(
+            lambda e: isinstance(e, sympy.Function) and type(e).__name__.lower() == "rateof",
+            lambda e: RateOf(*e.args),
+        )
+
         return expr
 
     def _inline_custom_functions(self, expr: sympy.Expr) -> sympy.Expr:
@@ -418,8 +506,9 @@ class SbmlExpressionParser(Parser):
         # Remove trailing operators (with optional whitespace)
         expr = re.sub(r"\s*[\*\+\-\/]\s*$", "", expr)
 
-        # Remove leading operators (with optional whitespace)
-        expr = re.sub(r"^\s*[\*\+\-\/]\s*", "", expr)
+        # Remove leading operators (with optional whitespace); a leading minus
+        # is a unary negation and must be kept
+        expr = re.sub(r"^\s*[\*\+\/]\s*", "", expr)
 
         # Remove duplicate operators with spaces (e.g., " * * " -> " * ")
         expr = re.sub(r"\s*([\*\+\-\/])\s*\1\s*", r" \1 ", expr)
@@ -453,17 +542,19 @@ class SbmlExpressionParser(Parser):
         return expr.replace("piecewise", "sbml_piecewise")
 
     def _transform_logical_keywords(self, expr: str) -> str:
-        """Transform Python keywords (and, or) to SymPy function names (And, Or)
+        """Transform Python keywords (and, or, not) to SymPy function names (And, Or, Not)
 
         Python keywords like 'and' and 'or' cannot be used as function names in
         SymPy's parse_expr. This function replaces them with their capitalized
-        versions which are valid function names.
+        versions which are valid function names. xor is not a keyword but is
+        capitalized too, as SymPy's Xor.
 
         Args:
             expr: Expression string
 
         Returns:
-            Transformed expression with 'and' -> 'And' and 'or' -> 'Or'
+            Transformed expression with 'and' -> 'And', 'or' -> 'Or', 'not' -> 'Not'
+            and 'xor' -> 'Xor'
         """
         # Use word boundaries to match complete identifiers only
         # This prevents replacing 'and' inside 'stand' or 'or' inside 'for'
@@ -473,6 +564,8 @@ class SbmlExpressionParser(Parser):
         expr = re.sub(r"\band\s*\(", "And(", expr)
         # Replace 'or(' with 'Or(' (function call)
         expr = re.sub(r"\bor\s*\(", "Or(", expr)
+        expr = re.sub(r"\bnot\s*\(", "Not(", expr)
+        expr = re.sub(r"\bxor\s*\(", "Xor(", expr)
 
         return expr
 
@@ -1,10 +1,77 @@
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
 mod pbpk_bpa_model;  // This will use your generated model
 
 use std::fs;
 
+/// Parameter file formats accepted by `--params`
+enum Format {
+    Json,
+    Yaml,
+}
+
+/// Read `--params <file>` (or a bare path), `--rerun <result file>` and `--format json|yaml`
+fn parse_args() -> Result<(Option<String>, Option<Format>, bool), String> {
+    let mut args = std::env::args().skip(1);
+    let (mut path, mut format, mut rerun) = (None, None, false);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--params" => path = Some(args.next().ok_or("--params needs a file")?),
+            "--rerun" => {
+                path = Some(args.next().ok_or("--rerun needs a result file")?);
+                rerun = true;
+            }
+            "--format" => {
+                format = Some(match args.next().as_deref() {
+                    Some("json") => Format::Json,
+                    Some("yaml") => Format::Yaml,
+                    other => return Err(format!("--format must be json or yaml, got {:?}", other)),
+                })
+            }
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+    }
+    Ok((path, format, rerun))
+}
+
+/// The parameters a result file echoes, to run the same simulation again
+fn embedded_parameters(path: &str, text: &str) -> Result<String, String> {
+    let result: serde_json::Value = serde_json::from_str(text).map_err(|e| format!("{}: {}", path, e))?;
+    match &result["parameters"] {
+        serde_json::Value::Object(parameters) => Ok(serde_json::to_string(parameters).unwrap()),
+        _ => Err(format!(
+            "{}: no embedded parameters (rejected run, or run with include_parameters false)",
+            path
+        )),
+    }
+}
+
+/// Read a parameter file as the JSON text the generated model expects
+///
+/// Without `--format`, `.yaml`/`.yml` files are read as YAML and anything
+/// else as JSON. YAML errors carry the parser's line and column.
+fn read_params(path: &str, format: Option<Format>) -> Result<String, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let format = format.unwrap_or(if path.ends_with(".yaml") || path.ends_with(".yml") {
+        Format::Yaml
+    } else {
+        Format::Json
+    });
+    match format {
+        Format::Json => Ok(text),
+        Format::Yaml => {
+            let mut value: serde_yaml::Value =
+                serde_yaml::from_str(&text).map_err(|e| format!("{}: {}", path, e))?;
+            // Anchors are resolved by the parser, `<<: *base` merge keys are not
+            value.apply_merge().map_err(|e| format!("{}: {}", path, e))?;
+            serde_json::to_string(&value).map_err(|e| format!("{}: {}", path, e))
+        }
+    }
+}
+
 fn main() {
     // Create JSON parameters matching SimulationParams struct for euromix model
-    let params_json = r#"
+    let _params_json = r#"
     {
         "BM": 70.0,
         "BSA": 190.0,
@@ -51,8 +118,36 @@ fn main() {
      //std::fs::write("result.json", &result)
      //.expect("Failed to write result.json");
 
-     let params = pbpk_bpa_model::get_default_parameters();
-     println!("Default parameters: {}", params);
+     // A parameter file may be given instead, e.g. `cargo run -- --params scenario.yaml`.
+     // Results are written as JSON whichever format the parameters were in.
+     // `cargo run -- --rerun result_baseline.json` repeats the run a result came from.
+     let (path, format, rerun) = parse_args().unwrap_or_else(|e| {
+         eprintln!("{}", e);
+         std::process::exit(2);
+     });
+     if let Some(path) = path {
+         let params_file = read_params(&path, format)
+             .and_then(|text| if rerun { embedded_parameters(&path, &text) } else { Ok(text) })
+             .unwrap_or_else(|e| {
+                 eprintln!("Failed to read parameters: {}", e);
+                 std::process::exit(1);
+             });
+         let result = pbpk_bpa_model::run_simulation(&params_file);
+         fs::write("result.json", &result).expect("Failed to write result.json");
+         // A failed run still writes its partial trajectory, but exits non-zero
+         let result: serde_json::Value = serde_json::from_str(&result).expect("Failed to read the result");
+         if let Some(error) = result["error"].as_object() {
+             let message = error["message"].as_str().unwrap_or_default();
+             match error["time"].as_f64() {
+                 Some(t) => eprintln!("Simulation failed at t = {}: {}", t, message),
+                 None => eprintln!("Simulation failed: {}", message),
+             }
+             std::process::exit(1);
+         }
+     } else {
+         let params = pbpk_bpa_model::get_default_parameters();
+         println!("Default parameters: {}", params);
+     }
  
     println!("Simulation completed!");
     println!("Result saved to result.json");
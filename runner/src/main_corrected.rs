@@ -26,6 +26,9 @@ fn main() {
     println!("  koa = 1.2e8 * 1.0 * 1.3381102 = {}", 1.2e8 * 1.0 * 1.3381102);
     println!("  t1 = 0.0 + 0.0003 = {}", 0.0 + 0.0003);
     println!("  uptake_O = 1.0 * 1.3381102 / 1.0 = {}", 1.0 * 1.3381102 / 1.0);
+    // Generated with pulse_sharpness 100, the tanh edges are 100 / (t1 - t0) steep;
+    // the part of the smoothed pulse before t0, ln(2) / 200 of it, is not absorbed
+    let expected_peak = 0.4 * 1.2e8 * 1.3381102 * 0.0003 * (1.0 - 2f64.ln() / 200.0);
     println!();
 
     let result = pbpk_bpa_model::run_simulation(params_json);
@@ -34,17 +37,27 @@ fn main() {
     std::fs::write("result_corrected.json", &result)
         .expect("Failed to write result_corrected.json");
 
+    // A failed solve still returns the trajectory up to the failure
+    let result_data: serde_json::Value = serde_json::from_str(&result).unwrap();
+    if let Some(error) = result_data["error"].as_object() {
+        eprintln!(
+            "Simulation failed at t = {}: {}",
+            error["time"], error["message"].as_str().unwrap_or("unknown error")
+        );
+        eprintln!("Partial result saved to result_corrected.json");
+        std::process::exit(1);
+    }
+
     println!("Simulation completed!");
     println!("Result saved to result_corrected.json");
 
     // Parse and show first few data points
-    let result_data: serde_json::Value = serde_json::from_str(&result).unwrap();
-    if let Some(aplasma) = result_data["species"]["aplasma"].as_array() {
+    if let Some(aplasma) = result_data["species"]["Aplasma"].as_array() {
         println!("\nFirst 5 Aplasma concentrations:");
         for (i, val) in aplasma.iter().take(5).enumerate() {
             println!("  [{}] {}", i, val);
         }
         println!("  ...");
-        println!("  Peak should be around 0.3-0.4 (not 1e-12!)");
+        println!("  Peak should be close to Kabs * koa * (t1 - t0) = {}", expected_peak);
     }
 }
@@ -1,50 +1,95 @@
-// Generated native Rust code from SBML model: PBPK_BPA_model
+// Generated native Rust code from SBML model: pbpk_bpa_model
 // Uses SymPy CSE for optimized derivatives and Jacobian
 
-use diffsol::{OdeBuilder, OdeSolverMethod, OdeSolverStopReason, Vector};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use wasm_pk_core::*;
 
-type M = diffsol::NalgebraMat<f64>;
-type LS = diffsol::NalgebraLU<f64>;
-
-#[derive(Serialize, Deserialize)]
-pub struct SimulationResult {
-    pub species: std::collections::HashMap<String, Vec<f64>>,
-    pub time: Vec<f64>,
-}
-
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct SimulationParams {
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Kabs: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub t0: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub Kelm: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub EoA_O: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub D_o: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub vplasma: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub period_O: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub n_O: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
     pub comp1: f64,
 
     // Initial amounts (optional, for runtime dosing)
+    #[serde(default, deserialize_with = "lenient_f64::option")]
     pub init_Aplasma: Option<f64>,
-    pub final_time: Option<f64>,
+    pub uptake_windows: Option<Vec<UptakeWindow>>,
+    /// Times, outputs, doses and solver settings, the same for every model
+    #[serde(flatten)]
+    pub options: SimulationOptions,
+}
+
+/// Seconds per model time unit, if the SBML declares its time units
+const MODEL_TIME_SECONDS: Option<f64> = None;
+/// SBML id of the model's time units
+const MODEL_TIME_UNIT: Option<&str> = None;
+
+/// Model time units per time_unit (1 when it is not given)
+fn time_scale(sim_params: &SimulationParams) -> f64 {
+    sim_params.options.time_scale(&MODEL)
+}
+
+/// The parameters with every time and step size in model time units
+fn in_model_time(mut sim_params: SimulationParams) -> SimulationParams {
+    let scale = time_scale(&sim_params);
+    sim_params.options = sim_params.options.in_model_time(scale);
+    for window in sim_params.uptake_windows.iter_mut().flatten() {
+        window.t0 *= scale;
+        window.t1 *= scale;
+    }
+    sim_params
+}
+
+/// The parameters with the defaults a run uses for its times and solver options
+fn with_defaults(mut sim_params: SimulationParams) -> SimulationParams {
+    sim_params.options = sim_params.options.with_defaults(&MODEL);
+    sim_params
+}
+
+/// The uptake windows of a run in model time units: its uptake_windows, or the
+/// model's pulse repeated as its parameters ask
+fn uptake_windows(sim_params: &SimulationParams, p: &[f64]) -> Result<Vec<UptakeWindow>, SimulationError> {
+    match &sim_params.uptake_windows {
+        Some(windows) => Ok(windows.clone()),
+        None => repeated_windows(p[1], 0.0003, ("n_O", p[7]), ("period_O", p[6])),
+    }
 }
 
 pub fn run_simulation(params: &str) -> String {
-    println!("Starting simulation...");
-
-    let sim_params: SimulationParams = match serde_json::from_str(params) {
-        Ok(p) => p,
-        Err(e) => {
-            eprintln!("Error parsing params: {}", e);
-            return serde_json::to_string(&SimulationResult {
-                species: HashMap::new(),
-                time: vec![],
-            }).unwrap();
-        }
-    };
+    wasm_pk_core::simulate::<PbpkBpaModel>(params)
+}
+
+/// Run one simulation per parameter object; each result carries its own error
+pub fn run_simulation_batch(params_list: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_batch::<PbpkBpaModel>(params_list)
+}
+
+/// Names of the solver parameter vector entries, in index order
+const PARAMETER_ORDER: &[&str] = &["Kabs", "t0", "Kelm", "EoA_O", "D_o", "vplasma", "period_O", "n_O", "comp1", "koa", "t1", "uptake_O", "init_Aplasma"];
 
+/// Index mapping of the solver parameter vector: model parameters, derived
+/// parameter values, then the initial state as init_<species> (JSON array)
+pub fn get_parameter_order() -> String {
+    serde_json::to_string(PARAMETER_ORDER).unwrap()
+}
+
+/// Resolve one run's parameters into the solver parameter vector
+fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
     let Kabs = sim_params.Kabs;
     let t0 = sim_params.t0;
     let Kelm = sim_params.Kelm;
@@ -54,167 +99,814 @@ pub fn run_simulation(params: &str) -> String {
     let period_O = sim_params.period_O;
     let n_O = sim_params.n_O;
     let comp1 = sim_params.comp1;
+    let koa = 120000000.0*D_o*EoA_O;
+    let t1 = period_O + t0;
+    let uptake_O = D_o*EoA_O*n_O.powi(-1);
 
+    vec![
+        Kabs,
+        t0,
+        Kelm,
+        EoA_O,
+        D_o,
+        vplasma,
+        period_O,
+        n_O,
+        comp1,
+        koa,
+        t1,
+        uptake_O,
+        sim_params.init_Aplasma.unwrap_or(0.0),
+    ]
+}
 
-    let koa = 1.2e-8*EoA_O*D_o;
-    let t1 = period_O + t0;
-    let uptake_O = EoA_O*D_o*n_O.powi(-1);
+/// Range of each checked entry of p, with the parameters a derived value reads
+const PARAMETER_CHECKS: &[ParameterCheck] = &[
+    ("Kabs", 0, ParameterRange::Unknown, &[]),
+    ("t0", 1, ParameterRange::Unknown, &[]),
+    ("Kelm", 2, ParameterRange::Unknown, &[]),
+    ("EoA_O", 3, ParameterRange::Unknown, &[]),
+    ("D_o", 4, ParameterRange::Unknown, &[]),
+    ("vplasma", 5, ParameterRange::Unknown, &[]),
+    ("period_O", 6, ParameterRange::Unknown, &[]),
+    ("n_O", 7, ParameterRange::Unknown, &[]),
+    ("comp1", 8, ParameterRange::NonNegative, &[]),
+];
+
+/// Check parameters without running: `{errors, warnings}`, each `{field, message}` (JSON)
+pub fn validate_parameters(params: &str) -> String {
+    wasm_pk_core::validate_parameters::<PbpkBpaModel>(params)
+}
+
+/// The model as a type, for code generic over models; see PkModel
+pub struct PbpkBpaModel;
+
+impl PkModel for PbpkBpaModel {
+    type Params = SimulationParams;
+    const N_STATES: usize = 1;
+    const INFO: ModelInfo = MODEL;
+
+    fn rhs(y: &[f64], p: &[f64], t: f64, windows: &[UptakeWindow], dy: &mut [f64]) {
+        // Parameters from p
+        let Kabs = p[0];
+        let t0 = p[1];
+        let Kelm = p[2];
+        let koa = p[9];
+        let t1 = p[10];
 
-    // RHS Closure
-    let rhs = |y: &diffsol::NalgebraVec<f64>, _p: &diffsol::NalgebraVec<f64>, t: f64, dy: &mut diffsol::NalgebraVec<f64>| {
         // Map species names to y indices
         let Aplasma = y[0];
 
+        // Dose-window terms summed over the run's uptake windows
+        let uptake_Aplasma = window_sum(windows, t0, t1, |t0, t1| 0.5*Kabs*koa*((100.0*(t - t0)*(-t0 + t1).powi(-1)).tanh() - (100.0*(t - t1)*(-t0 + t1).powi(-1)).tanh()));
+
         // Temporary variables (CSE)
-        let x0 = 1.0*Kelm;
+        let x0 = Kelm;
 
         // Derivatives
-        dy[0] = -1.0*Aplasma*x0 + 0.5*Kabs*koa*((100.0*t - 100.0*t0).tanh() - 1.0*(100.0*t - 100.0*t1).tanh());
-    };
+        dy[0] = -Aplasma*x0 + uptake_Aplasma;
+    }
 
-    // Jacobian Closure (Matrix-Vector Product)
-    let jac = |y: &diffsol::NalgebraVec<f64>, _p: &diffsol::NalgebraVec<f64>, t: f64, v: &diffsol::NalgebraVec<f64>, jv: &mut diffsol::NalgebraVec<f64>| {
-        for i in 0..jv.len() { jv[i] = 0.0; }
+    fn jac(_y: &[f64], p: &[f64], _t: f64, v: &[f64], jv: &mut [f64]) {
+        jv.fill(0.0);
+
+        // Parameters from p
+        let Kelm = p[2];
 
         // Map species names to y indices
-        let Aplasma = y[0];
+
 
         // Temporary variables (CSE)
-        let x0 = 1.0*Kelm;
+        let x0 = Kelm;
 
         // Jacobian-Vector Product
-        jv[0] += (-1.0*x0) * v[0];
-    };
+        jv[0] += (-x0) * v[0];
+    }
 
-    let init = |_y0: &diffsol::NalgebraVec<f64>, _t: f64, y: &mut diffsol::NalgebraVec<f64>| {
-        y[0] = sim_params.init_Aplasma.unwrap_or(0.0);
-    };
-    let problem = OdeBuilder::<M>::new()
-        .rhs_implicit(rhs, jac)
-        .init(init, 1)
-        .build()
-        .unwrap();
-
-    let mut solver = problem.bdf::<LS>().unwrap();
-    let mut time = Vec::new();
-
-    // Initialize result vectors
-    let mut aplasma = Vec::new();
-
-    aplasma.push(solver.state().y[0]);
-    time.push(0.0);
-
-    let final_time = sim_params.final_time.unwrap_or(24.0);
-    solver.set_stop_time(final_time).unwrap();
-    loop {
-        match solver.step() {
-            Ok(OdeSolverStopReason::InternalTimestep) => {
-            aplasma.push(solver.state().y[0]);
-                time.push(solver.state().t);
-            },
-            Ok(OdeSolverStopReason::TstopReached) => break,
-            Ok(OdeSolverStopReason::RootFound(_)) => break,
-            Err(_) => panic!("Solver Error"),
+    fn init(p: &[f64], _t: f64, y: &mut [f64]) {
+        y.copy_from_slice(&p[12..13]);
+    }
+    fn defaults() -> serde_json::Value {
+        serde_json::from_str(&get_default_parameters()).unwrap()
+    }
+
+    fn options(sim_params: &SimulationParams) -> &SimulationOptions {
+        &sim_params.options
+    }
+
+    fn options_mut(sim_params: &mut SimulationParams) -> &mut SimulationOptions {
+        &mut sim_params.options
+    }
+
+    fn check(sim_params: &SimulationParams) -> Result<(), SimulationError> {
+        if let Some(ref windows) = sim_params.uptake_windows {
+            check_uptake_windows(windows)?;
         }
+        uptake_windows(sim_params, &parameter_vector(sim_params))?;
+        Ok(())
+    }
+
+    fn shift_schedule(sim_params: &mut SimulationParams, offset: f64) {
+        sim_params.options.shift_schedule(offset);
+        for window in sim_params.uptake_windows.iter_mut().flatten() {
+            window.t0 += offset;
+            window.t1 += offset;
+        }
+    }
+
+    fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+        parameter_vector(sim_params)
+    }
+
+    fn build_problem() -> BuiltProblem<'static> {
+        BuiltProblem::dense(MODEL, equations())
+    }
+
+    fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+        prepare_run(sim_params)
     }
+}
+
+/// The run of checked parameters: the solver works in model time units, while
+/// results and checkpoints keep the caller's
+fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+    let resolved = serde_json::to_value(with_defaults(sim_params.clone())).unwrap();
+    let scale = time_scale(&sim_params);
+    let sim_params = in_model_time(sim_params);
+    let p = parameter_vector(&sim_params);
+    Ok(Run {
+        uptake_windows: uptake_windows(&sim_params, &p)?,
+        p,
+        resolved,
+        scale,
+        options: sim_params.options,
+    })
+}
 
-    let mut species_map = HashMap::new();
-        species_map.insert("aplasma".to_string(), aplasma);
+/// The model's functions for the solver; the closures capture nothing, so they
+/// are plain function pointers
+fn equations() -> Equations<'static> {
+    let eval_observables = |y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let vplasma = p[5];
+        let Aplasma = y[0];
+        let cplasma = Aplasma*vplasma.powi(-1);
+        vec![cplasma]
+    };
 
-    let result = SimulationResult {
-        time,
-        species: species_map,
+    let eval_volumes = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let comp1 = p[8];
+        vec![comp1]
     };
 
-    serde_json::to_string(&result).unwrap()
+    let eval_mass = |y: &[f64], _p: &[f64]| -> f64 {
+        y[0]
+    };
+
+    Equations {
+        rhs: PbpkBpaModel::rhs,
+        jac: PbpkBpaModel::jac,
+        init: PbpkBpaModel::init,
+        roots: no_roots,
+        triggers: no_triggers,
+        apply_events: no_events,
+        observables: eval_observables,
+        volumes: eval_volumes,
+        mass: eval_mass,
+        jac_matrix: None,
+    }
+}
+
+/// Solver stats of the last run of the last simulation call (JSON, null if it never started)
+pub fn get_last_run_stats() -> String {
+    LAST_RUN_STATS.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+pub fn run_simulation_at_times(params: &str, times: &[f64]) -> String {
+    wasm_pk_core::run_simulation_at_times::<PbpkBpaModel>(params, times)
+}
+
+/// Run a simulation and return `time,<species...>` CSV, one row per stored time
+pub fn run_simulation_csv(params: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_csv::<PbpkBpaModel>(params)
+}
+
+/// Cmax, Tmax, AUC, AUC(0-inf), terminal half-life and trough of one species (JSON)
+pub fn compute_pk_metrics(result_json: &str, species: &str) -> Result<String, String> {
+    wasm_pk_core::compute_pk_metrics(result_json, species)
+}
+
+/// AUC of one species over each `[t1, t2]` window (JSON array, same order)
+pub fn compute_partial_auc(result_json: &str, species: &str, windows: &str) -> Result<String, String> {
+    wasm_pk_core::compute_partial_auc(result_json, species, windows)
+}
+
+/// Run the model once per value of one parameter, e.g. body weight from 20 to 100 kg
+pub fn run_parameter_sweep(base_params: &str, sweep: &str) -> Result<String, String> {
+    wasm_pk_core::run_parameter_sweep::<PbpkBpaModel>(base_params, sweep)
+}
+
+/// Continue a run_simulation result from its last recorded state for `additional_time`,
+/// with `new_params` merged over the parameters that run used. Its doses, output_times
+/// and uptake windows are not repeated; times in `new_params` count from the
+/// continuation start. The segment is returned on the combined time axis, after
+/// the earlier trajectory when `concatenate` is set.
+pub fn continue_simulation(previous_result: &str, new_params: &str, additional_time: f64, concatenate: bool) -> Result<String, String> {
+    wasm_pk_core::continue_simulation::<PbpkBpaModel>(previous_result, new_params, additional_time, concatenate)
+}
+
+/// Checkpoint of the last run of the last simulation call (JSON, null if it never
+/// started or its final state is not finite)
+pub fn export_checkpoint() -> String {
+    LAST_CHECKPOINT.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+/// Resume an exported checkpoint up to the absolute `final_time` in `params`, which are
+/// merged over the checkpoint's parameters. Its pending doses are applied unless
+/// `params` gives new doses. The result and the new checkpoint use absolute time.
+pub fn run_from_checkpoint(checkpoint: &str, params: &str) -> Result<String, String> {
+    wasm_pk_core::run_from_checkpoint::<PbpkBpaModel>(checkpoint, params)
+}
+
+const SPECIES_IDS: &[&str] = &["Aplasma"];
+
+/// Parameters read by the derivative of each state, for failure hints
+const STATE_PARAMETERS: [&[&str]; 1] = [&["Kabs", "Kelm", "koa", "t0", "t1"]];
+
+/// SBML ids of the assignment-rule observables, in evaluation order
+const OBSERVABLE_IDS: [&str; 1] = ["cplasma"];
+
+/// What the shared solver loop and option checks need to know about the model
+const MODEL: ModelInfo = ModelInfo {
+    species: SPECIES_IDS,
+    state_parameters: &STATE_PARAMETERS,
+    event_ids: &[],
+    event_targets: &[],
+    event_initial_values: &[],
+    event_persistent: &[],
+    observables: &OBSERVABLE_IDS,
+    species_is_amount: &SPECIES_IS_AMOUNT,
+    species_volumes: &SPECIES_VOLUMES,
+    series_units: &SERIES_UNITS,
+    time_seconds: MODEL_TIME_SECONDS,
+    time_unit: MODEL_TIME_UNIT,
+    parameters: PARAMETER_ORDER,
+    parameter_checks: PARAMETER_CHECKS,
+    algebraic: &[],
+};
+
+/// Run a simulation and return the trajectory as little-endian f64 bytes
+pub fn run_simulation_binary(params: &str) -> Result<Vec<u8>, String> {
+    wasm_pk_core::run_simulation_binary::<PbpkBpaModel>(params)
+}
+
+/// Describe the run_simulation_binary layout and its species order (JSON)
+pub fn get_binary_layout() -> String {
+    wasm_pk_core::get_binary_layout::<PbpkBpaModel>()
+}
+
+/// Decode run_simulation_binary output into times and named species series
+pub fn decode_binary_result(bytes: &[u8]) -> Result<(Vec<f64>, OrderedSeries), String> {
+    wasm_pk_core::decode_binary_result::<PbpkBpaModel>(bytes)
+}
+
+/// One observable of get_model_metadata
+#[derive(Serialize)]
+struct ObservableInfo {
+    id: &'static str,
+    units: Option<&'static str>,
+}
+
+/// SBML document the model was generated from
+#[derive(Serialize)]
+struct SbmlSource {
+    id: Option<&'static str>,
+    name: Option<&'static str>,
+    level: Option<u32>,
+    version: Option<u32>,
+    /// SHA-256 of the source file
+    sha256: Option<&'static str>,
+}
+
+/// Generator that wrote this module
+#[derive(Serialize)]
+struct GeneratorInfo {
+    name: &'static str,
+    version: Option<&'static str>,
+    /// ISO 8601 UTC time of generation
+    generated_at: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct ModelMetadata {
+    model_id: &'static str,
+    num_species: usize,
+    num_parameters: usize,
+    time_units: &'static str,
+    substance_units: &'static str,
+    volume_units: &'static str,
+    sbml: SbmlSource,
+    generator: GeneratorInfo,
+    diffsol_version: &'static str,
+    /// Optional features compiled in: "events", "sensitivities", "embedded_sbml"
+    features: &'static [&'static str],
+    observables: &'static [ObservableInfo],
 }
 
 pub fn get_model_metadata() -> String {
-    let metadata = serde_json::json!({
-        "model_id": "PBPK_BPA_model",
-        "num_species": 1,
-        "num_parameters": 9,
-        "time_units": "HR",
-        "substance_units": "MilliMOL",
-        "volume_units": "L"
-    });
+    let metadata = ModelMetadata {
+        model_id: "pbpk_bpa_model",
+        num_species: 1,
+        num_parameters: 9,
+        time_units: "HR",
+        substance_units: "MilliMOL",
+        volume_units: "L",
+        sbml: SbmlSource { id: Some("PBPK_BPA"), name: None, level: Some(3), version: Some(2), sha256: Some("d1ad96a0009c64f920240b193efbbfea7f508b463134163d5e4539c856dac7ad") },
+        generator: GeneratorInfo { name: "sbml_rust_generator", version: Some("1.0.0"), generated_at: Some("2026-10-17T00:28:39Z") },
+        diffsol_version: "0.6.3",
+        features: &["embedded_sbml"],
+        observables: &[
+            ObservableInfo { id: "cplasma", units: Some("MilliMOL_per_L") },
+        ],
+    };
     serde_json::to_string(&metadata).unwrap()
 }
 
+/// One entry of get_parameters_info
+#[derive(Serialize)]
+struct ParameterInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    units: Option<&'static str>,
+    /// SBML notes as plain text
+    description: Option<&'static str>,
+    default_value: f64,
+    required: bool,
+    is_compartment: bool,
+    /// Soft bounds for input forms; validate_parameters has the hard checks
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+const PARAMETER_INFO: &[ParameterInfo] = &[
+    ParameterInfo { id: "Kabs", name: None, units: None, description: None, default_value: 0.4, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "t0", name: None, units: None, description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Kelm", name: None, units: None, description: None, default_value: 0.13, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "EoA_O", name: None, units: None, description: None, default_value: 1.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "D_o", name: None, units: None, description: None, default_value: 1.3381102, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "vplasma", name: None, units: None, description: None, default_value: 3.6, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "period_O", name: None, units: None, description: None, default_value: 0.0003, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "n_O", name: None, units: None, description: None, default_value: 1.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "comp1", name: None, units: None, description: None, default_value: 3.6, required: true, is_compartment: true, min: Some(0.0), max: None },
+];
+
 pub fn get_parameters_info() -> String {
-    let params = serde_json::json!([
-        {
-            "id": "Kabs",
-            "default_value": 0.4,
-            "required": true
-        },
-        {
-            "id": "t0",
-            "default_value": 0.0,
-            "required": true
-        },
-        {
-            "id": "Kelm",
-            "default_value": 0.13,
-            "required": true
-        },
-        {
-            "id": "EoA_O",
-            "default_value": 1.0,
-            "required": true
-        },
-        {
-            "id": "D_o",
-            "default_value": 1.3381102,
-            "required": true
-        },
-        {
-            "id": "vplasma",
-            "default_value": 3.6,
-            "required": true
-        },
-        {
-            "id": "period_O",
-            "default_value": 0.0003,
-            "required": true
-        },
-        {
-            "id": "n_O",
-            "default_value": 1.0,
-            "required": true
-        },
-        {
-            "id": "comp1",
-            "default_value": null,
-            "required": true
-        }
-    ]);
-    serde_json::to_string(&params).unwrap()
+    serde_json::to_string(PARAMETER_INFO).unwrap()
+}
+
+/// One entry of get_species_info
+#[derive(Serialize)]
+struct SpeciesInfo {
+    id: &'static str,
+    initial_amount: f64,
+    #[serde(rename = "override")]
+    override_field: &'static str,
+    units: Option<&'static str>,
+    compartment: Option<&'static str>,
+    compartment_volume: Option<f64>,
+    /// False when the SBML value is a concentration (hasOnlySubstanceUnits=false)
+    is_amount: bool,
+    is_boundary: bool,
 }
 
 pub fn get_species_info() -> String {
-    let species = serde_json::json!([
-        {
-            "id": "Aplasma",
-            "initial_amount": 0.0,
-            "units": "MilliMOL"
-        }
-    ]);
+    let p = parameter_vector(&PbpkBpaModel::default_params());
+    let species: &[SpeciesInfo] = &[
+        SpeciesInfo { id: "Aplasma", initial_amount: 0.0, override_field: "init_Aplasma", units: None, compartment: Some("comp1"), compartment_volume: Some(p[8]), is_amount: false, is_boundary: false },
+    ];
     serde_json::to_string(&species).unwrap()
 }
 
+/// Whether each species' values are amounts rather than concentrations
+const SPECIES_IS_AMOUNT: [bool; 1] = [false];
+/// Compartment converting each species between amount and concentration, or why none does
+const SPECIES_VOLUMES: [Result<&str, &str>; 1] = [Ok("comp1")];
+
+/// Unit of each species and observable series; None where the SBML gives none
+const SERIES_UNITS: [(&str, Option<&str>); 2] = [
+    ("Aplasma", None),
+    ("cplasma", Some("MilliMOL_per_L")),
+];
+
+/// One entry of get_compartments_info
+#[derive(Serialize)]
+struct CompartmentInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    /// Size at the default parameters; None when it changes during a run
+    size: Option<f64>,
+    /// Rule or initial assignment computing the size, e.g. "BM*scVLiver"
+    formula: Option<&'static str>,
+    units: Option<&'static str>,
+    species: &'static [&'static str],
+}
+
+pub fn get_compartments_info() -> String {
+    let p = parameter_vector(&PbpkBpaModel::default_params());
+    let compartments: &[CompartmentInfo] = &[
+        CompartmentInfo { id: "comp1", name: None, size: Some(p[8]), formula: None, units: None, species: &["Aplasma"] },
+    ];
+    serde_json::to_string(&compartments).unwrap()
+}
+
 pub fn get_default_parameters() -> String {
-    let defaults = serde_json::json!({
-        "Kabs": 0.4,
-        "t0": 0.0,
-        "Kelm": 0.13,
-        "EoA_O": 1.0,
-        "D_o": 1.3381102,
-        "vplasma": 3.6,
-        "period_O": 0.0003,
-        "n_O": 1.0,
-        "comp1": null,
-        "final_time": 24.0
-    });
-    serde_json::to_string(&defaults).unwrap()
+    let defaults: &[(&str, f64)] = &[
+        ("Kabs", 0.4),
+        ("t0", 0.0),
+        ("Kelm", 0.13),
+        ("EoA_O", 1.0),
+        ("D_o", 1.3381102),
+        ("vplasma", 3.6),
+        ("period_O", 0.0003),
+        ("n_O", 1.0),
+        ("comp1", 3.6),
+        ("final_time", 24.0),
+    ];
+    let defaults: serde_json::Map<String, serde_json::Value> = defaults.iter().map(|&(id, value)| (id.to_string(), value.into())).collect();
+    serde_json::Value::Object(defaults).to_string()
+}
+/// Equations in model order, serialized as a JSON object
+struct EquationTable(&'static [(&'static str, &'static str)]);
+
+impl Serialize for EquationTable {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (id, expression) in self.0 {
+            map.serialize_entry(id, expression)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Serialize)]
+struct ModelEquations {
+    derivatives: EquationTable,
+    assignment_rules: EquationTable,
 }
+
+pub fn get_model_equations() -> String {
+    let equations = ModelEquations {
+        derivatives: EquationTable(&[
+            ("Aplasma", "-Aplasma*Kelm + 0.5*Kabs*koa*(tanh(100*t - 100*t0) - tanh(100*t - 100*t1))"),
+        ]),
+        assignment_rules: EquationTable(&[
+            ("cplasma", "Aplasma/vplasma"),
+        ]),
+    };
+    serde_json::to_string_pretty(&equations).unwrap()
+}
+/// Source SBML document, verbatim
+const MODEL_SBML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<sbml xmlns="http://www.sbml.org/sbml/level3/version2/core" level="3" version="2">
+  <model metaid="PBPK_BPA" id="PBPK_BPA">
+    <listOfUnitDefinitions>
+      <unitDefinition id="MilliMOL_per_L">
+        <listOfUnits>
+          <unit kind="mole" exponent="1" scale="-3" multiplier="1"/>
+          <unit kind="litre" exponent="-1" scale="0" multiplier="1"/>
+        </listOfUnits>
+      </unitDefinition>
+    </listOfUnitDefinitions>
+    <listOfCompartments>
+      <compartment id="comp1" size="3.6" spatialDimensions="3" constant="true"/>
+    </listOfCompartments>
+    <listOfSpecies>
+      <species id="Aplasma" compartment="comp1" initialConcentration="0" hasOnlySubstanceUnits="false" boundaryCondition="false" constant="false"/>
+    </listOfSpecies>
+    <listOfParameters>
+      <parameter id="Kabs" value="0.4" constant="true"/>
+      <parameter id="koa" constant="true"/>
+      <parameter id="t0" value="0" constant="true"/>
+      <parameter id="t1" constant="true"/>
+      <parameter id="Kelm" value="0.13" constant="true"/>
+      <parameter id="EoA_O" value="1" constant="true"/>
+      <parameter id="D_o" value="1.3381102" constant="true"/>
+      <parameter id="vplasma" value="3.6" constant="true"/>
+      <parameter id="period_O" value="0.0003" constant="true"/>
+      <parameter id="n_O" value="1" constant="true"/>
+      <parameter id="uptake_O" constant="true"/>
+      <parameter id="cplasma" units="MilliMOL_per_L" constant="false"/>
+    </listOfParameters>
+    <listOfInitialAssignments>
+      <initialAssignment symbol="koa">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <times/>
+            <cn type="e-notation"> 1.2 <sep/> 8 </cn>
+            <ci> EoA_O </ci>
+            <ci> D_o </ci>
+          </apply>
+        </math>
+      </initialAssignment>
+      <initialAssignment symbol="t1">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <plus/>
+            <ci> t0 </ci>
+            <ci> period_O </ci>
+          </apply>
+        </math>
+      </initialAssignment>
+      <initialAssignment symbol="uptake_O">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <divide/>
+            <apply>
+              <times/>
+              <ci> EoA_O </ci>
+              <ci> D_o </ci>
+            </apply>
+            <ci> n_O </ci>
+          </apply>
+        </math>
+      </initialAssignment>
+    </listOfInitialAssignments>
+    <listOfRules>
+      <assignmentRule variable="cplasma">
+        <math xmlns="http://www.w3.org/1998/Math/MathML">
+          <apply>
+            <divide/>
+            <ci> Aplasma </ci>
+            <ci> vplasma </ci>
+          </apply>
+        </math>
+      </assignmentRule>
+    </listOfRules>
+    <listOfReactions>
+      <reaction id="J_absorption" reversible="true">
+        <listOfProducts>
+          <speciesReference species="Aplasma" stoichiometry="1" constant="true"/>
+        </listOfProducts>
+        <kineticLaw>
+          <math xmlns="http://www.w3.org/1998/Math/MathML">
+            <apply>
+              <minus/>
+              <apply>
+                <times/>
+                <ci> Kabs </ci>
+                <apply>
+                  <divide/>
+                  <apply>
+                    <times/>
+                    <ci> koa </ci>
+                    <apply>
+                      <minus/>
+                      <apply>
+                        <tanh/>
+                        <apply>
+                          <times/>
+                          <cn type="integer"> 100 </cn>
+                          <apply>
+                            <minus/>
+                            <csymbol encoding="text" definitionURL="http://www.sbml.org/sbml/symbols/time"> time </csymbol>
+                            <ci> t0 </ci>
+                          </apply>
+                        </apply>
+                      </apply>
+                      <apply>
+                        <tanh/>
+                        <apply>
+                          <times/>
+                          <cn type="integer"> 100 </cn>
+                          <apply>
+                            <minus/>
+                            <csymbol encoding="text" definitionURL="http://www.sbml.org/sbml/symbols/time"> time </csymbol>
+                            <ci> t1 </ci>
+                          </apply>
+                        </apply>
+                      </apply>
+                    </apply>
+                  </apply>
+                  <cn type="integer"> 2 </cn>
+                </apply>
+              </apply>
+              <apply>
+                <times/>
+                <ci> Kelm </ci>
+                <ci> Aplasma </ci>
+              </apply>
+            </apply>
+          </math>
+        </kineticLaw>
+      </reaction>
+    </listOfReactions>
+  </model>
+</sbml>
+"#;
+
+/// SBML document this module was generated from
+pub fn get_model_sbml() -> Result<String, String> {
+    Ok(MODEL_SBML.to_string())
+}
+/// TypeScript definitions of the parameters, result and metadata of this model
+const TYPES_TS: &str = r#"// TypeScript definitions for pbpk_bpa_model, generated with the module
+
+export type SpeciesId = "Aplasma";
+export type ObservableId = "cplasma";
+export type SolverMethod = "bdf" | "tr_bdf2";
+export type JacobianMode = "analytic" | "finite_difference";
+export type SimulationMode = "transient" | "steady_state";
+export type TimeUnit = "s" | "min" | "h" | "d";
+export type OutputKind = "amount" | "concentration" | "both";
+
+export interface Dose {
+  time: number;
+  amount: number;
+  target: SpeciesId;
+  units?: string | null;
+}
+
+export interface UptakeWindow {
+  t0: number;
+  t1: number;
+  fraction: number;
+}
+
+/** Input of run_simulation; omitted fields take the model defaults */
+export interface SimulationParams {
+  Kabs?: number;
+  t0?: number;
+  Kelm?: number;
+  EoA_O?: number;
+  D_o?: number;
+  vplasma?: number;
+  period_O?: number;
+  n_O?: number;
+  comp1?: number;
+  init_Aplasma?: number | null;
+  t_start?: number | null;
+  final_time?: number | null;
+  time_unit?: TimeUnit | null;
+  convert_output_time?: boolean | null;
+  output_times?: number[] | null;
+  output_dt?: number | null;
+  max_output_points?: number | null;
+  include_observables?: boolean | null;
+  output_kind?: OutputKind | null;
+  include_parameters?: boolean | null;
+  mass_balance?: boolean | null;
+  validate?: boolean | null;
+  doses?: Dose[] | null;
+  uptake_windows?: UptakeWindow[] | null;
+  rtol?: number | null;
+  atol?: number | null;
+  max_steps?: number | null;
+  max_step?: number | null;
+  initial_step?: number | null;
+  check_finite?: boolean | null;
+  clamp_nonnegative?: boolean | null;
+  clamp_threshold?: number | null;
+  solver?: SolverMethod | null;
+  jacobian?: JacobianMode | null;
+  mode?: SimulationMode | null;
+  steady_state_tol?: number | null;
+  steady_state_exclude?: string[] | null;
+  steady_state_min_time?: number | null;
+}
+
+export interface SimulationResult {
+  species: Record<SpeciesId, number[]>;
+  time: number[];
+  /** null when the parameters were rejected or include_parameters is false */
+  parameters: SimulationParams | null;
+  /** Filled when include_observables is set */
+  observables: Partial<Record<ObservableId, number[]>>;
+  /** Unit of each species and observable series and of time; null if unknown */
+  units: Record<string, string | null>;
+  /** Filled when output_kind asks for them */
+  amounts: Partial<Record<SpeciesId, number[]>>;
+  concentrations: Partial<Record<SpeciesId, number[]>>;
+  /** Species left in their native representation, and why */
+  notes: string[];
+  steady_state: SteadyState | null;
+  applied_doses: Dose[];
+  events: EventRecord[];
+  /** Whether each event trigger held at the end; continuations start from it */
+  trigger_state: boolean[];
+  steps: number;
+  stats: SolverStats | null;
+  diagnostics: Diagnostics | null;
+  error: SimulationError | null;
+  timing: Timing;
+}
+
+/** Wall-clock milliseconds of the call */
+export interface Timing {
+  solve_ms: number;
+  serialize_ms: number;
+  total_ms: number;
+}
+
+export interface SteadyState {
+  reached: boolean;
+  time: number;
+  rms: number;
+  state: number[];
+}
+
+export interface EventRecord {
+  time: number;
+  id: string;
+  assignments: Partial<Record<SpeciesId, number>>;
+}
+
+export interface SolverStats {
+  steps: number;
+  rhs_evals: number;
+  jacobian_evals: number;
+  newton_iterations: number;
+  newton_failures: number;
+  final_step_size: number;
+}
+
+export interface Diagnostics {
+  total_mass: number[];
+  max_mass_drift: number;
+}
+
+export interface SimulationError {
+  kind: "invalid_params" | "setup" | "solver_failure" | "event_error" | "max_steps_exceeded" | "cancelled";
+  message: string;
+  field: string | null;
+  time: number | null;
+  state: number[] | null;
+  hint: string | null;
+}
+
+/** get_model_metadata */
+export interface ModelMetadata {
+  model_id: string;
+  num_species: number;
+  num_parameters: number;
+  time_units: string;
+  substance_units: string;
+  volume_units: string;
+  sbml: {
+    id: string | null;
+    name: string | null;
+    level: number | null;
+    version: number | null;
+    sha256: string | null;
+  };
+  generator: { name: string; version: string | null; generated_at: string | null };
+  diffsol_version: string;
+  features: ("events" | "sensitivities" | "embedded_sbml")[];
+  observables: { id: ObservableId; units: string | null }[];
+}
+
+/** One entry of get_parameters_info */
+export interface ParameterInfo {
+  id: keyof SimulationParams;
+  name: string | null;
+  units: string | null;
+  description: string | null;
+  default_value: number;
+  required: boolean;
+  is_compartment: boolean;
+  min: number | null;
+  max: number | null;
+}
+
+/** One entry of get_species_info */
+export interface SpeciesInfo {
+  id: SpeciesId;
+  initial_amount: number;
+  override: keyof SimulationParams;
+  units: string | null;
+  compartment: string | null;
+  compartment_volume: number | null;
+  is_amount: boolean;
+  is_boundary: boolean;
+}
+
+/** One entry of get_compartments_info */
+export interface CompartmentInfo {
+  id: string;
+  name: string | null;
+  size: number | null;
+  formula: string | null;
+  units: string | null;
+  species: SpeciesId[];
+}
+
+/** get_model_equations */
+export interface ModelEquations {
+  derivatives: Record<SpeciesId, string>;
+  assignment_rules: Record<string, string>;
+}
+"#;
+
+pub fn get_types_ts() -> String {
+    TYPES_TS.to_string()
+}
+
@@ -32,6 +32,8 @@ class ModelData:
         Each value is an instance of ParameterData
     rateRules : dict
         Each value is an instance of RateRuleData
+    algebraicRules : dict
+        Each value is an instance of AlgebraicRuleData
     reactions : dict
         Each value is an instance of ReactionData
     species : dict
@@ -57,6 +59,7 @@ class ModelData:
 
         self.assignmentRules = {}
         self.rateRules = {}
+        self.algebraicRules = {}
 
         self.initialAssignments = {}
         self.events = {}
@@ -72,6 +75,7 @@ class ModelData:
             "functions": {},
             "assignmentRules": {},
             "rateRules": {},
+            "algebraicRules": {},
             "initialAssignments": {},
             "events": {},
         }
@@ -97,6 +101,9 @@ class ModelData:
         for key, component in self.rateRules.items():
             modelDictionary["rateRules"][key] = component.ToDictionary()
 
+        for key, component in self.algebraicRules.items():
+            modelDictionary["algebraicRules"][key] = component.ToDictionary()
+
         for key, component in self.initialAssignments.items():
             modelDictionary["initialAssignments"][key] = component.ToDictionary()
 
@@ -139,6 +146,11 @@ class ModelData:
         for key, componentDict in modelData["rateRules"].items():
             newModel.rateRules[key] = RateRuleData.ConstructFromDict(componentDict)
 
+        for key, componentDict in modelData.get("algebraicRules", {}).items():
+            newModel.algebraicRules[key] = AlgebraicRuleData.ConstructFromDict(
+                componentDict
+            )
+
         for key, componentDict in modelData["initialAssignments"].items():
             newModel.initialAssignments[key] = InitialAssignmentData.ConstructFromDict(
                 componentDict
@@ -160,6 +172,8 @@ class ParameterData:
     isConstant : str
     name : str
     value : str
+    units : str
+    notes : str
     """
 
     def __init__(self):
@@ -167,6 +181,8 @@ class ParameterData:
         self.value = None
         self.isConstant = None
         self.name = None
+        self.units = None
+        self.notes = None
 
     def ToDictionary(self):
         # This function turns this class into a dictionary to prep dumping to JSON
@@ -175,6 +191,8 @@ class ParameterData:
             "name": self.name,
             "value": self.value,
             "isConstant": self.isConstant,
+            "units": self.units,
+            "notes": self.notes,
         }
         return returnDict
 
@@ -186,6 +204,8 @@ class ParameterData:
         newComponent.name = dataDict["name"]
         newComponent.value = dataDict["value"]
         newComponent.isConstant = dataDict["isConstant"]
+        newComponent.units = dataDict.get("units")
+        newComponent.notes = dataDict.get("notes")
 
         return newComponent
 
@@ -230,6 +250,49 @@ class RateRuleData:
         return newComponent
 
 
+class AlgebraicRuleData:
+    """
+    This class holds all of the necessary data from an SBML model for an algebraic rule.
+
+    The rule constrains its math to zero. SBML does not name the variable it
+    determines, so variable stays None unless it is set by hand.
+
+    Attributes
+    ----------
+    Id : str
+    math : str
+    name : str
+    variable : str
+    """
+
+    def __init__(self):
+        self.Id = None
+        self.variable = None
+        self.math = None
+        self.name = None
+
+    def ToDictionary(self):
+        # This function turns this class into a dictionary to prep dumping to JSON
+        returnDict = {
+            "Id": self.Id,
+            "name": self.name,
+            "variable": self.variable,
+            "math": self.math,
+        }
+        return returnDict
+
+    @classmethod
+    def ConstructFromDict(cls, dataDict):
+        newComponent = cls()
+
+        newComponent.Id = dataDict["Id"]
+        newComponent.name = dataDict["name"]
+        newComponent.variable = dataDict.get("variable")
+        newComponent.math = dataDict["math"]
+
+        return newComponent
+
+
 class AssignmentRuleData:
     """
     This class holds all of the necessary data from an SBML model for an assignement rule.
@@ -281,6 +344,8 @@ class CompartmentData:
     isConstant : str
     name : str
     size : str
+    units : str
+    notes : str
     """
 
     def __init__(self):
@@ -289,6 +354,8 @@ class CompartmentData:
         self.dimensionality = None
         self.name = None
         self.isConstant = None
+        self.units = None
+        self.notes = None
 
     def ToDictionary(self):
         # This function turns this class into a dictionary to prep dumping to JSON
@@ -298,6 +365,8 @@ class CompartmentData:
             "size": self.size,
             "dimensionality": self.dimensionality,
             "isConstant": self.isConstant,
+            "units": self.units,
+            "notes": self.notes,
         }
         return returnDict
 
@@ -310,6 +379,8 @@ class CompartmentData:
         newComponent.size = dataDict["size"]
         newComponent.dimensionality = dataDict["dimensionality"]
         newComponent.isConstant = dataDict["isConstant"]
+        newComponent.units = dataDict.get("units")
+        newComponent.notes = dataDict.get("notes")
 
         return newComponent
 
@@ -322,10 +393,11 @@ class SpeciesData:
     ----------
     compartment : str
     Id : str
-    isBoudarySpecies : str
+    isBoundarySpecies : str
     isConstant : str
     hasOnlySubstanceUnits : str
     name : str
+    units : str
     value : str
     valueType : str
     """
@@ -337,8 +409,9 @@ class SpeciesData:
         self.compartment = None
         self.value = None
         self.isConstant = None
-        self.isBoudarySpecies = None
+        self.isBoundarySpecies = None
         self.name = None
+        self.units = None
 
     def ToDictionary(self):
         # This function turns this class into a dictionary to prep dumping to JSON
@@ -349,8 +422,9 @@ class SpeciesData:
             "valueType": self.valueType,
             "compartment": self.compartment,
             "isConstant": self.isConstant,
-            "isBoundarySpecies": self.isBoudarySpecies,
+            "isBoundarySpecies": self.isBoundarySpecies,
             "hasOnlySubstanceUnits": self.hasOnlySubstanceUnits,
+            "units": self.units,
         }
         return returnDict
 
@@ -366,6 +440,7 @@ class SpeciesData:
         newComponent.isConstant = dataDict["isConstant"]
         newComponent.isBoundarySpecies = dataDict["isBoundarySpecies"]
         newComponent.hasOnlySubstanceUnits = dataDict["hasOnlySubstanceUnits"]
+        newComponent.units = dataDict.get("units")
 
         return newComponent
 
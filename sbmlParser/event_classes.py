@@ -47,6 +47,12 @@ class EventData:
     delay : str
         MathML expression for delay (optional)
     useValuesFromTriggerTime : bool
+    initialValue : bool
+        Value of the trigger before the simulation starts; when False, a
+        trigger already true at the start fires the event there
+    persistent : bool
+        Whether the event still fires if its trigger turns false again
+        before it is executed
     eventAssignments : list of EventAssignmentData
     """
 
@@ -56,6 +62,8 @@ class EventData:
         self.trigger = None
         self.delay = None
         self.useValuesFromTriggerTime = True
+        self.initialValue = True
+        self.persistent = True
         self.eventAssignments = []
 
     def ToDictionary(self):
@@ -65,6 +73,8 @@ class EventData:
             "trigger": self.trigger,
             "delay": self.delay,
             "useValuesFromTriggerTime": self.useValuesFromTriggerTime,
+            "initialValue": self.initialValue,
+            "persistent": self.persistent,
             "eventAssignments": [ea.ToDictionary() for ea in self.eventAssignments],
         }
 
@@ -76,6 +86,8 @@ class EventData:
         newComponent.trigger = dataDict["trigger"]
         newComponent.delay = dataDict.get("delay")
         newComponent.useValuesFromTriggerTime = dataDict.get("useValuesFromTriggerTime", True)
+        newComponent.initialValue = dataDict.get("initialValue", True)
+        newComponent.persistent = dataDict.get("persistent", True)
         newComponent.eventAssignments = [
             EventAssignmentData.ConstructFromDict(ea)
             for ea in dataDict.get("eventAssignments", [])
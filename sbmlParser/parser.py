@@ -5,10 +5,21 @@ Created on Tue Jul  3 12:12:05 2018
 @author: Steve
 """
 
+import hashlib
+import html
 import libsbml
 import re
 import sys
 from sbmlParser import dataclasses
+from utils.units import unit_seconds
+
+
+def NotesText(component):
+    # SBML notes are XHTML; keep the text only, on one line
+    if not component.isSetNotes():
+        return None
+    text = html.unescape(re.sub(r"<[^>]+>", " ", component.getNotesString()))
+    return " ".join(text.split()) or None
 
 
 def ParseParameterAssignment(parameterIndex, parameter):
@@ -37,6 +48,11 @@ def ParseParameterAssignment(parameterIndex, parameter):
             newParameter.isConstant = parameter.getConstant()
         else:
             newParameter.isConstant = False
+
+        if parameter.isSetUnits():
+            newParameter.units = parameter.getUnits()
+
+        newParameter.notes = NotesText(parameter)
         # print("Parameter," + str(parameterIndex + 1) + "\n" + str(parameterId) + "\nValue\n" + str(parameterValue))
     #        outputFile.write(str(parameterId) + "; " + str(parameterValue) + "; " + str(parameterConst) + "; " + parameterName + "\n")
     else:
@@ -47,8 +63,7 @@ def ParseParameterAssignment(parameterIndex, parameter):
 
 def ParseRule(ruleIndex, rule):
     if rule.isAlgebraic():
-        raise Exception("Algebraic rules are currently not supported")
-
+        newRule = dataclasses.AlgebraicRuleData()
     #        outputFile.write("Rule; " + ruleId + "; Algebraic; " + ruleName + "\n")
     if rule.isAssignment():
         newRule = dataclasses.AssignmentRuleData()
@@ -67,13 +82,18 @@ def ParseRule(ruleIndex, rule):
     else:
         newRule.Id = str(ruleIndex + 1)
 
+    if rule.isAlgebraic():
+        # 0 = math; the generator picks the variable it determines
+        newRule.math = libsbml.writeMathMLToString(rule.getMath())
+        return newRule
+
     newRule.variable = rule.getVariable()
     # Use MathML for better parsing with sbmlmath (improved operator precedence)
     newRule.math = libsbml.writeMathMLToString(rule.getMath())
     ## Using MathML instead of formulaToString for direct parsing with sbmlmath
 
     if rule.getVariable() == "":
-        raise Exception("Algebraic rules are currently not supported")
+        raise Exception(f"Rule {newRule.Id} has no variable")
 
     else:
         return newRule
@@ -97,6 +117,12 @@ def ParseSpecies(speciesIndex, species):
     newSpecies.isConstant = species.getConstant()
     newSpecies.isBoundarySpecies = species.getBoundaryCondition()
     newSpecies.hasOnlySubstanceUnits = species.getHasOnlySubstanceUnits()
+    # Level 3 species without substanceUnits use the model's
+    model = species.getModel()
+    if species.isSetSubstanceUnits():
+        newSpecies.units = species.getSubstanceUnits()
+    elif model is not None and model.isSetSubstanceUnits():
+        newSpecies.units = model.getSubstanceUnits()
     if species.isSetInitialAmount():
         newSpecies.valueType = "Amount"
         newSpecies.value = species.getInitialAmount()
@@ -202,6 +228,15 @@ def ParseCompartment(compartmentIndex, compartment):
     else:
         newCompartment.isConstant = False
 
+    # Level 3 volumes without units use the model's volumeUnits
+    model = compartment.getModel()
+    if compartment.isSetUnits():
+        newCompartment.units = compartment.getUnits()
+    elif newCompartment.dimensionality == 3 and model is not None and model.isSetVolumeUnits():
+        newCompartment.units = model.getVolumeUnits()
+
+    newCompartment.notes = NotesText(compartment)
+
     #    outputFile.write("Compartment; " + str(compartmentIndex + 1) + "\nSize; " + str(size) + "\nDimensionality; " + str(dimensions) + "\n")
     #    outputFile.write("Compartment; " + compartment.getId() + "; " + compartmentName + "\nSize; " + str(size) + "\nDimensionality; " + str(dimensions) + "\n")
     return newCompartment
@@ -291,6 +326,11 @@ def ParseEvent(eventIndex, event):
             newEvent.trigger = libsbml.writeMathMLToString(trigger.getMath())
         else:
             newEvent.trigger = None
+        # Level 2 triggers have neither attribute and behave as if both were true
+        if trigger.isSetInitialValue():
+            newEvent.initialValue = trigger.getInitialValue()
+        if trigger.isSetPersistent():
+            newEvent.persistent = trigger.getPersistent()
     else:
         newEvent.trigger = None
     
@@ -325,6 +365,87 @@ def ParseEvent(eventIndex, event):
     return newEvent
 
 
+def FlattenCompModel(doc):
+    """
+    Parameters
+    ----------
+    doc : libsbml.SBMLDocument
+        Document that may use the hierarchical model composition (comp) package
+
+    Notes
+    -----
+    Reactions, rules and species of comp submodels only exist in the model
+    definitions they are instantiated from, so the top-level model on its own
+    is missing them. The document is flattened in place; external model
+    definitions are resolved relative to the location of the file.
+
+    """
+    if not doc.isPackageEnabled("comp"):
+        return
+
+    compPlugin = doc.getModel().getPlugin("comp")
+    if compPlugin is None or compPlugin.getNumSubmodels() == 0:
+        return
+
+    properties = libsbml.ConversionProperties()
+    properties.addOption("flatten comp", True)
+    properties.addOption("leavePorts", False)
+
+    if doc.convert(properties) != libsbml.LIBSBML_OPERATION_SUCCESS:
+        messages = [
+            doc.getError(i).getMessage()
+            for i in range(doc.getNumErrors())
+            if doc.getError(i).getSeverity() >= 2
+        ]
+        raise Exception(
+            "Could not flatten comp submodels: " + "; ".join(messages)
+        )
+
+
+def ParseTimeUnits(model):
+    """
+    Parameters
+    ----------
+    model : libsbml.Model
+        Model whose time units are read
+
+    Returns
+    -------
+    dict
+        "id" of the model's time units and their length in "seconds"; both
+        are None when the model does not declare them, and "seconds" is None
+        when the unit is not a plain time
+
+    Notes
+    -----
+    Level 2 models may redefine the built-in "time" unit instead of setting
+    timeUnits. Models that do neither are left undeclared rather than taken
+    to be in seconds, since many are written in hours without saying so.
+
+    """
+    unitId = model.getTimeUnits() if model.isSetTimeUnits() else ""
+    if not unitId and model.getUnitDefinition("time") is not None:
+        unitId = "time"
+    if not unitId:
+        return {"id": None, "seconds": None}
+
+    definition = model.getUnitDefinition(unitId)
+    if definition is None:
+        # A base unit such as "second"
+        units = [(unitId, 1, 0, 1.0)]
+    else:
+        units = [
+            (
+                libsbml.UnitKind_toString(unit.getKind()),
+                unit.getExponentAsDouble(),
+                unit.getScale(),
+                unit.getMultiplier(),
+            )
+            for unit in definition.getListOfUnits()
+        ]
+    return {"id": unitId, "seconds": unit_seconds(units)}
+
+
 def ParseSBMLFile(filePath):
     """
     Parameters
@@ -364,6 +485,9 @@ def ParseSBMLFile(filePath):
         else:
             print(f"SBML file has {num_errors} warnings but no errors. Proceeding...")
 
+    # Pull submodel reactions and rules into the top-level model
+    FlattenCompModel(doc)
+
     model = doc.getModel()
 
     modelData = dataclasses.ModelData()
@@ -392,6 +516,8 @@ def ParseSBMLFile(filePath):
             modelData.assignmentRules[newRule.Id] = newRule
         elif type(newRule) == dataclasses.RateRuleData:
             modelData.rateRules[newRule.Id] = newRule
+        elif type(newRule) == dataclasses.AlgebraicRuleData:
+            modelData.algebraicRules[newRule.Id] = newRule
     #    outputFile.write("Reactions"+ "\n")
     for i in range(model.getNumReactions()):
         newReaction = ParseReaction(i, model.getReaction(i))
@@ -407,6 +533,9 @@ def ParseSBMLFile(filePath):
         modelData.events[newEvent.Id] = newEvent
     # print(model.getNumSpecies())
 
+    with open(filePath, "rb") as source:
+        sourceBytes = source.read()
+
     # Convert ModelData to dictionary format for JSON serialization
     modelDictionary = {
         "parameters": {},
@@ -416,8 +545,21 @@ def ParseSBMLFile(filePath):
         "functions": {},
         "assignmentRules": {},
         "rateRules": {},
+        "algebraicRules": {},
         "initialAssignments": {},
         "events": {},
+        # Provenance reported by get_model_metadata
+        "model": {
+            "id": model.getId() or None,
+            "name": model.getName() or None,
+            "level": doc.getLevel(),
+            "version": doc.getVersion(),
+            "sha256": hashlib.sha256(sourceBytes).hexdigest(),
+            # Converts the time_unit option to model time
+            "time_units": ParseTimeUnits(model),
+            # Embedded by the generator so get_model_sbml can return it
+            "document": sourceBytes.decode("utf-8"),
+        },
     }
 
     for key, component in modelData.parameters.items():
@@ -441,6 +583,9 @@ def ParseSBMLFile(filePath):
     for key, component in modelData.rateRules.items():
         modelDictionary["rateRules"][key] = component.ToDictionary()
 
+    for key, component in modelData.algebraicRules.items():
+        modelDictionary["algebraicRules"][key] = component.ToDictionary()
+
     for key, component in modelData.initialAssignments.items():
         modelDictionary["initialAssignments"][key] = component.ToDictionary()
 
@@ -0,0 +1,30 @@
+//! Generate the Rust of every case in cases/ into OUT_DIR, so that only the
+//! cases' settings, results and SBML are checked in and the models always come
+//! from the current generator. It runs `python -m tests.semantic_cases generate`
+//! from the repository root, which needs sympy, and libsbml for cases imported
+//! from the SBML Test Suite.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let repo = Path::new(&manifest_dir).parent().unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    for input in ["__init__.py", "codegen", "core", "models", "parsers", "sbmlParser", "symbolic", "utils", "facade.py", "tests/fixture_models.py", "tests/semantic_cases.py", "semantic_cases/cases"] {
+        println!("cargo:rerun-if-changed={}", repo.join(input).display());
+    }
+    println!("cargo:rerun-if-env-changed=PYTHON");
+
+    let python = std::env::var("PYTHON").unwrap_or_else(|_| "python3".to_string());
+    let output = Command::new(&python)
+        .args(["-m", "tests.semantic_cases", "generate", &out_dir])
+        .current_dir(repo)
+        // Keep __pycache__ out of the inputs watched above
+        .env("PYTHONDONTWRITEBYTECODE", "1")
+        .output()
+        .unwrap_or_else(|e| panic!("could not run {} (set PYTHON to the generator's interpreter): {}", python, e));
+    if !output.status.success() {
+        panic!("generating the semantic cases failed:\n{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
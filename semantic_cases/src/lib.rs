@@ -0,0 +1,4 @@
+//! The generated models of the semantic cases, one module per case directory
+//! in cases/, and [`CASES`] to run them all; build.rs generates them.
+
+include!(concat!(env!("OUT_DIR"), "/mod.rs"));
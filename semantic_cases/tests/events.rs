@@ -0,0 +1,84 @@
+//! Event semantics of generated models, across run lengths, solvers and resumes
+//!
+//! A threshold event must fire at every crossing whatever final_time is and
+//! whichever method solves the model, and a continued or checkpointed run
+//! must start its triggers where the earlier run left them, so events that
+//! already fired do not fire again at its start. A root that fires nothing
+//! is recorded once, even when it falls on a solver stop.
+
+use semantic_cases::{case_threshold_event as threshold_event, case_trigger_semantics as trigger_semantics};
+use serde_json::{json, Value};
+
+fn run(run_simulation: fn(&str) -> String, params: Value) -> Value {
+    let result: Value = serde_json::from_str(&run_simulation(&params.to_string())).unwrap();
+    assert!(result["error"].is_null(), "{} with {}", result["error"], params);
+    result
+}
+
+fn event_ids(result: &Value) -> Vec<&str> {
+    result["events"].as_array().unwrap().iter().map(|event| event["id"].as_str().unwrap()).collect()
+}
+
+fn last(result: &Value, id: &str) -> f64 {
+    let values = result["species"][id].as_array().unwrap_or_else(|| panic!("no {} in {}", id, result["species"]));
+    values.last().unwrap().as_f64().unwrap()
+}
+
+#[test]
+fn threshold_event_fires_at_every_crossing() {
+    // S1 decays at rate 0.5 from 1 and is refilled to 1 when it falls below
+    // 0.5, so every 2 ln 2
+    let period = 2.0 * std::f64::consts::LN_2;
+    for solver in ["bdf", "tr_bdf2"] {
+        for final_time in [2.0, 3.0, 5.0, 10.0, 24.0] {
+            let result = run(threshold_event::run_simulation, json!({ "final_time": final_time, "solver": solver, "rtol": 1e-8, "atol": 1e-10 }));
+            let crossings = (final_time / period).floor();
+            assert_eq!(event_ids(&result).len() as f64, crossings, "{} to {}: {}", solver, final_time, result["events"]);
+            let expected = (-0.5 * (final_time - crossings * period)).exp();
+            let s1 = last(&result, "S1");
+            assert!((s1 - expected).abs() < 1e-4, "{} to {}: S1 = {}, expected {}", solver, final_time, s1, expected);
+        }
+    }
+}
+
+#[test]
+fn continuations_do_not_fire_events_again() {
+    // at_start fires at t = 0 and flicker at 1.05, whose trigger still holds at 1.5
+    let first = run(trigger_semantics::run_simulation, json!({ "final_time": 1.5 }));
+    assert_eq!(event_ids(&first), ["at_start", "flicker"]);
+    assert_eq!(first["trigger_state"], json!([true, true, true]));
+    let segment = trigger_semantics::continue_simulation(&first.to_string(), "{}", 2.5, false).unwrap();
+    let segment: Value = serde_json::from_str(&segment).unwrap();
+    assert!(segment["error"].is_null(), "{}", segment["error"]);
+    // Only flicker's second rise at 3.05
+    assert_eq!(event_ids(&segment), ["flicker"]);
+    assert_eq!(last(&segment, "flickers"), 2.0);
+    assert_eq!(last(&segment, "repeats"), 0.0);
+}
+
+#[test]
+fn checkpoints_do_not_fire_events_again() {
+    run(trigger_semantics::run_simulation, json!({ "final_time": 1.5 }));
+    let checkpoint = trigger_semantics::export_checkpoint();
+    let saved: Value = serde_json::from_str(&checkpoint).unwrap();
+    assert_eq!(saved["trigger_state"], json!([true, true, true]));
+    let resumed: Value = serde_json::from_str(&trigger_semantics::run_from_checkpoint(&checkpoint, r#"{"final_time": 4.0}"#).unwrap()).unwrap();
+    assert!(resumed["error"].is_null(), "{}", resumed["error"]);
+    assert_eq!(event_ids(&resumed), ["flicker"]);
+    let s1 = last(&resumed, "S1");
+    assert!((s1 - 2.0 * (-2.0f64).exp()).abs() < 1e-4, "S1 = {}", s1);
+    // A checkpoint without trigger values starts them from their initialValue
+    let mut old = saved.clone();
+    old.as_object_mut().unwrap().remove("trigger_state");
+    let resumed: Value = serde_json::from_str(&trigger_semantics::run_from_checkpoint(&old.to_string(), r#"{"final_time": 4.0}"#).unwrap()).unwrap();
+    assert_eq!(event_ids(&resumed), ["at_start", "flicker"]);
+}
+
+#[test]
+fn roots_on_a_stop_are_recorded_once() {
+    // flicker's trigger falls, firing nothing, at 2.05, where the run stops
+    let result = run(trigger_semantics::run_simulation, json!({ "final_time": 2.05 }));
+    let time: Vec<f64> = serde_json::from_value(result["time"].clone()).unwrap();
+    assert_eq!(time.iter().filter(|&&t| t == 2.05).count(), 1, "time {:?}", &time[time.len() - 3..]);
+    assert_eq!(time.last(), Some(&2.05));
+}
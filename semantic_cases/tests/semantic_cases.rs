@@ -0,0 +1,124 @@
+//! Generated models against semantic cases in the SBML Test Suite's layout
+//!
+//! Every case in cases/<id> has the suite's <id>-settings.txt (start,
+//! duration, steps, the variables checked and the absolute and relative
+//! tolerances) and <id>-results.csv; build.rs generates its model. A case is
+//! run on the times of its results and passes when every value is within
+//! absolute + relative * |expected| of them, as in the suite. Variables listed
+//! under `amount` or `concentration` are read in that form, others from the
+//! species, observables or parameters. All cases run and the failures are
+//! reported together. To add cases, see tests/semantic_cases.py in the
+//! repository root.
+
+use semantic_cases::{RunSimulation, CASES};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// The fields of <id>-settings.txt this harness uses
+struct Settings {
+    start: f64,
+    duration: f64,
+    variables: Vec<String>,
+    absolute: f64,
+    relative: f64,
+    amount: Vec<String>,
+    concentration: Vec<String>,
+}
+
+fn case_file(case: &str, suffix: &str) -> String {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "cases", case, &format!("{}-{}", case, suffix)].iter().collect();
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+/// Read the `key: value` lines of a case's settings
+fn read_settings(case: &str) -> Settings {
+    let text = case_file(case, "settings.txt");
+    let fields: HashMap<&str, &str> = text.lines().filter_map(|line| line.split_once(':')).map(|(k, v)| (k.trim(), v.trim())).collect();
+    let number = |key: &str| -> f64 {
+        let value = fields.get(key).unwrap_or_else(|| panic!("{}: no {} in settings", case, key));
+        value.parse().unwrap_or_else(|e| panic!("{}: {}: {:?}: {}", case, key, value, e))
+    };
+    let ids = |key: &str| -> Vec<String> {
+        fields.get(key).map_or(Vec::new(), |value| value.split(',').map(str::trim).filter(|id| !id.is_empty()).map(String::from).collect())
+    };
+    Settings {
+        start: number("start"),
+        duration: number("duration"),
+        variables: ids("variables"),
+        absolute: number("absolute"),
+        relative: number("relative"),
+        amount: ids("amount"),
+        concentration: ids("concentration"),
+    }
+}
+
+/// Read a case's results: the header ids and one row of values per output time
+fn read_results(case: &str) -> (Vec<String>, Vec<Vec<f64>>) {
+    let text = case_file(case, "results.csv");
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().unwrap_or_else(|| panic!("{}: empty results", case));
+    let ids = header.split(',').map(|id| id.trim().to_string()).collect();
+    let rows = lines
+        .map(|line| line.split(',').map(|value| value.trim().parse().unwrap_or(f64::NAN)).collect::<Vec<f64>>())
+        .collect();
+    (ids, rows)
+}
+
+/// The series of `id` in a result, a constant parameter repeated at every time
+fn series(result: &Value, settings: &Settings, id: &str, points: usize) -> Option<Vec<f64>> {
+    let kinds: &[&str] = if settings.amount.iter().any(|v| v == id) {
+        &["amounts", "species"]
+    } else if settings.concentration.iter().any(|v| v == id) {
+        &["concentrations", "species"]
+    } else {
+        &["species", "observables"]
+    };
+    kinds
+        .iter()
+        .find_map(|kind| serde_json::from_value(result[*kind][id].clone()).ok())
+        .or_else(|| result["parameters"][id].as_f64().map(|value| vec![value; points]))
+}
+
+/// Run `case` and describe where it fails the suite's tolerances, if it does
+fn check_case(case: &str, run_simulation: RunSimulation) -> Result<(), String> {
+    let settings = read_settings(case);
+    let (ids, rows) = read_results(case);
+    let times: Vec<f64> = rows.iter().map(|row| row[0]).collect();
+    let params = json!({
+        "t_start": settings.start, "final_time": settings.start + settings.duration, "output_times": times,
+        "rtol": 1e-10, "atol": 1e-12, "output_kind": "both", "include_observables": true,
+    });
+    let result: Value = serde_json::from_str(&run_simulation(&params.to_string())).unwrap();
+    if !result["error"].is_null() {
+        return Err(result["error"].to_string());
+    }
+    let time: Vec<f64> = serde_json::from_value(result["time"].clone()).unwrap();
+    if time.len() != times.len() {
+        return Err(format!("{} output times for {} result rows", time.len(), times.len()));
+    }
+    for id in &settings.variables {
+        let column = ids.iter().position(|c| c == id).ok_or_else(|| format!("no column {} in the results", id))?;
+        let values = series(&result, &settings, id, time.len()).ok_or_else(|| format!("{} is not in the result", id))?;
+        for ((t, row), actual) in times.iter().zip(&rows).zip(values) {
+            let expected = row[column];
+            if expected.is_nan() {
+                continue;
+            }
+            let error = (actual - expected).abs();
+            if error.is_nan() || error > settings.absolute + settings.relative * expected.abs() {
+                return Err(format!("{} is {} at t = {}, expected {}", id, actual, t, expected));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn semantic_cases_match_results() {
+    let failures: Vec<String> = CASES
+        .iter()
+        .filter_map(|(case, run_simulation)| check_case(case, *run_simulation).err().map(|e| format!("{}: {}", case, e)))
+        .collect();
+    assert!(failures.is_empty(), "{} of {} cases failed:\n{}", failures.len(), CASES.len(), failures.join("\n"));
+}
@@ -18,11 +18,16 @@ class AssignmentRuleProcessor(ModelProcessor):
         """
         self.parser = parser
 
-    def process(self, model_data: Dict[str, Any]) -> List[Tuple[str, sympy.Expr]]:
+    def process(
+        self,
+        model_data: Dict[str, Any],
+        reaction_rates: Dict[str, sympy.Expr] = None,
+    ) -> List[Tuple[str, sympy.Expr]]:
         """Process assignment rules from model data
 
         Args:
             model_data: Dictionary containing model data with 'assignmentRules' key
+            reaction_rates: Rate expressions substituted for reaction IDs (optional)
 
         Returns:
             List of (variable, expression) tuples in dependency order
@@ -31,10 +36,12 @@ class AssignmentRuleProcessor(ModelProcessor):
         if not assignment_rules:
             return []
 
-        return self.sort_assignment_rules(assignment_rules)
+        return self.sort_assignment_rules(assignment_rules, reaction_rates)
 
     def sort_assignment_rules(
-        self, assignment_rules: Dict[str, Any]
+        self,
+        assignment_rules: Dict[str, Any],
+        reaction_rates: Dict[str, sympy.Expr] = None,
     ) -> List[Tuple[str, sympy.Expr]]:
         """Sort assignment rules in topological order based on dependencies
 
@@ -44,6 +51,7 @@ class AssignmentRuleProcessor(ModelProcessor):
 
         Args:
             assignment_rules: Dictionary of assignment rule data
+            reaction_rates: Rate expressions substituted for reaction IDs (optional)
 
         Returns:
             List of (variable, expression) tuples in dependency order
@@ -51,6 +59,10 @@ class AssignmentRuleProcessor(ModelProcessor):
         Raises:
             ValueError: If circular dependency detected
         """
+        rate_subs = {
+            sympy.Symbol(r_id): rate for r_id, rate in (reaction_rates or {}).items()
+        }
+
         # Parse all expressions first
         parsed_rules = {}
         for rule_id, rule in assignment_rules.items():
@@ -59,7 +71,7 @@ class AssignmentRuleProcessor(ModelProcessor):
             
             try:
                 expr = self.parser.parse(math_expr)
-                parsed_rules[variable] = expr
+                parsed_rules[variable] = expr.xreplace(rate_subs)
             except Exception as e:
                 print(f"Warning: Failed to parse assignment rule for {variable}: {e}")
                 # Use a default value if parsing fails
@@ -74,6 +86,68 @@ class AssignmentRuleProcessor(ModelProcessor):
         # Return in sorted order
         return [(var, parsed_rules[var]) for var in sorted_vars if var in parsed_rules]
 
+    def substitute_rules(
+        self,
+        expressions: List[sympy.Expr],
+        sorted_rules: List[Tuple[str, sympy.Expr]],
+    ) -> List[sympy.Expr]:
+        """Inline assignment rules into expressions
+
+        Used for state-dependent rules so that derivatives and the Jacobian see
+        the full dependence on the state (including the chain rule through the
+        rule variable). Parameter-only rules are left as symbols and computed
+        once outside the RHS closures.
+
+        Args:
+            expressions: Expressions referencing rule variables
+            sorted_rules: List of (variable, expression) tuples in dependency order
+
+        Returns:
+            Expressions with the rule variables replaced by their definitions
+        """
+        expanded = {}
+        for variable, expr in sorted_rules:
+            expanded[sympy.Symbol(variable)] = expr.xreplace(expanded)
+
+        return [expr.xreplace(expanded) for expr in expressions]
+
+    def sort_with_initial_assignments(
+        self,
+        sorted_rules: List[Tuple[str, sympy.Expr]],
+        initial_assignments: Dict[str, Any],
+    ) -> List[Tuple[str, sympy.Expr]]:
+        """Order assignment rules and initial assignments as a single block
+
+        Both are emitted as sequential `let` bindings, so a rule that reads an
+        initially-assigned variable (or vice versa) must come after it.
+
+        Args:
+            sorted_rules: List of (variable, expression) tuples for assignment rules
+            initial_assignments: Dictionary of initial assignment data from SBML
+
+        Returns:
+            Combined list of (variable, expression) tuples in dependency order
+
+        Raises:
+            ValueError: If circular dependency detected
+        """
+        parsed = {}
+        for assignment in initial_assignments.values():
+            variable = assignment.get("variable")
+            math_expr = assignment.get("math")
+            if not variable or not math_expr:
+                continue
+
+            try:
+                parsed[variable] = self.parser.parse(math_expr)
+            except Exception as e:
+                print(f"Warning: Could not parse initial assignment for {variable}: {e}")
+
+        parsed.update(sorted_rules)
+
+        sorted_vars = self._topological_sort(self._build_dependency_graph(parsed))
+        return [(var, parsed[var]) for var in sorted_vars]
+
     def _build_dependency_graph(
         self, parsed_rules: Dict[str, sympy.Expr]
     ) -> Dict[str, Set[str]]:
@@ -143,6 +217,80 @@ class AssignmentRuleProcessor(ModelProcessor):
 
         return sorted_vars
 
+    def split_by_state_dependence(
+        self,
+        sorted_rules: List[Tuple[str, sympy.Expr]],
+        state_variables: Set[str],
+    ) -> Tuple[List[Tuple[str, sympy.Expr]], List[Tuple[str, sympy.Expr]]]:
+        """Split sorted assignment rules into parameter-only and state-dependent rules
+
+        A rule is state-dependent if it references a state variable, time, or
+        another state-dependent rule. Dependency order is preserved in both lists.
+
+        Args:
+            sorted_rules: List of (variable, expression) tuples in dependency order
+            state_variables: Names of the ODE state variables
+
+        Returns:
+            Tuple of (parameter_only_rules, state_dependent_rules)
+        """
+        dynamic = set(state_variables) | {"t"}
+        parameter_only = []
+        state_dependent = []
+
+        for variable, expr in sorted_rules:
+            if {str(sym) for sym in expr.free_symbols} & dynamic:
+                dynamic.add(variable)
+                state_dependent.append((variable, expr))
+            else:
+                parameter_only.append((variable, expr))
+
+        return parameter_only, state_dependent
+
+    def mass_coefficients(
+        self,
+        state_dependent_rules: List[Tuple[str, sympy.Expr]],
+        mass_variables: Set[str],
+        state_variables: List[str],
+    ) -> Dict[str, sympy.Expr]:
+        """Find the factor converting each state to a mass from rules such as X = A*Mr
+
+        A mass rule is used when, with the other state-dependent rules
+        inlined, it is a single state times a factor free of state and time
+        (e.g. Xve_tal = Cve_tal*Vve*Mr_tal). Other mass rules are skipped,
+        and the first rule found for a state wins.
+
+        Args:
+            state_dependent_rules: Rules that depend on state or time, in dependency order
+            mass_variables: Rule variables holding a mass, e.g. those in mg
+            state_variables: Names of the ODE state variables
+
+        Returns:
+            Dictionary mapping state names to their mass factor, in state order
+        """
+        states = {sympy.Symbol(s) for s in state_variables}
+        t = sympy.Symbol("t")
+        expanded = dict(zip(
+            [sympy.Symbol(variable) for variable, _ in state_dependent_rules],
+            self.substitute_rules([expr for _, expr in state_dependent_rules], state_dependent_rules),
+        ))
+
+        coefficients = {}
+        for variable, _ in state_dependent_rules:
+            if variable not in mass_variables:
+                continue
+            expr = expanded[sympy.Symbol(variable)]
+            read = expr.free_symbols & states
+            if len(read) != 1:
+                continue
+            state = read.pop()
+            factor = sympy.simplify(expr / state)
+            if factor.free_symbols & (states | {t}) or str(state) in coefficients:
+                continue
+            coefficients[str(state)] = factor
+
+        return {s: coefficients[s] for s in state_variables if s in coefficients}
+
     def get_assigned_variables(
         self, assignment_rules: Dict[str, Any]
     ) -> Set[str]:
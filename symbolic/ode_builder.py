@@ -2,23 +2,31 @@
 """Builds ODE system from SBML model reactions"""
 
 import sympy
-from typing import Dict, List, Tuple, Any
+from typing import Dict, List, Tuple, Any, Optional
 from core.base import ModelProcessor
-from parsers.expression_parser import SbmlExpressionParser
+from parsers.expression_parser import RateOf, SbmlExpressionParser
 
 
 class OdeSystemBuilder(ModelProcessor):
     """Builds ODE system from SBML model reactions"""
 
-    def __init__(self, species_map: Dict[str, int], parser: SbmlExpressionParser):
+    def __init__(
+        self,
+        species_map: Dict[str, int],
+        parser: SbmlExpressionParser,
+        local_parameters: Optional[Dict[str, Dict[str, str]]] = None,
+    ):
         """Initialize ODE system builder
 
         Args:
             species_map: Dictionary mapping species IDs to indices
             parser: Expression parser for rate laws
+            local_parameters: Per-reaction mapping of local parameter IDs to
+                their promoted model parameter IDs (optional)
         """
         self.species_map = species_map
         self.parser = parser
+        self.local_parameters = local_parameters or {}
         self.n_species = len(species_map)
 
     def process(self, model_data: Dict[str, Any]) -> List[sympy.Expr]:
@@ -32,7 +40,11 @@ class OdeSystemBuilder(ModelProcessor):
         """
         return self.build_ode_system(model_data.get("reactions", {}))
 
-    def build_ode_system(self, reactions: Dict[str, Any]) -> List[sympy.Expr]:
+    def build_ode_system(
+        self,
+        reactions: Dict[str, Any],
+        reaction_rates: Optional[Dict[str, sympy.Expr]] = None,
+    ) -> List[sympy.Expr]:
         """Build differential equations from reactions
 
         For each reaction, adds contributions to species rates based on
@@ -41,6 +53,7 @@ class OdeSystemBuilder(ModelProcessor):
 
         Args:
             reactions: Dictionary of reactions with rate laws
+            reaction_rates: Already parsed rates from parse_reaction_rates (optional)
 
         Returns:
             List of dy/dt expressions for each species
@@ -54,30 +67,273 @@ class OdeSystemBuilder(ModelProcessor):
         # Initialize all rates to zero
         dy_dt = [sympy.Float(0.0)] * self.n_species
 
+        rates = reaction_rates or self.parse_reaction_rates(reactions)
+
+        for rxn_id, rxn in reactions.items():
+            rate_expr = rates[rxn_id]
+
+            # Add contributions from reactants (negative stoichiometry)
+            for stoich, species_id in rxn.get("reactants", []):
+                if species_id in self.species_map:
+                    idx = self.species_map[species_id]
+                    dy_dt[idx] -= stoich * rate_expr
+
+            # Add contributions from products (positive stoichiometry)
+            for stoich, species_id in rxn.get("products", []):
+                if species_id in self.species_map:
+                    idx = self.species_map[species_id]
+                    dy_dt[idx] += stoich * rate_expr
+
+        return dy_dt
+
+    def parse_reaction_rates(self, reactions: Dict[str, Any]) -> Dict[str, sympy.Expr]:
+        """Parse the rate law of every reaction
+
+        Args:
+            reactions: Dictionary of reactions with rate laws
+
+        Returns:
+            Dictionary mapping reaction IDs to rate expressions
+        """
+        rates = {}
+
         print("Parsing reactions...")
         for rxn_id, rxn in reactions.items():
             try:
-                # Parse the rate law expression
                 rate_expr = self.parser.parse(rxn.get("rateLaw", "0"))
+            except Exception as e:
+                print(f"Failed to process reaction {rxn_id}: {e}")
+                raise e
+
+            # Rename local parameters that were promoted under a prefixed ID
+            renames = {
+                sympy.Symbol(local_id): sympy.Symbol(promoted_id)
+                for local_id, promoted_id in self.local_parameters.get(rxn_id, {}).items()
+                if local_id != promoted_id
+            }
+            if renames:
+                rate_expr = rate_expr.xreplace(renames)
+
+            rates[rxn_id] = rate_expr
 
-                # Add contributions from reactants (negative stoichiometry)
-                for stoich, species_id in rxn.get("reactants", []):
-                    if species_id in self.species_map:
-                        idx = self.species_map[species_id]
-                        dy_dt[idx] -= stoich * rate_expr
+        return rates
 
-                # Add contributions from products (positive stoichiometry)
-                for stoich, species_id in rxn.get("products", []):
-                    if species_id in self.species_map:
-                        idx = self.species_map[species_id]
-                        dy_dt[idx] += stoich * rate_expr
+    def apply_rate_rules(
+        self,
+        dy_dt: List[sympy.Expr],
+        rate_rules: Dict[str, Any],
+        reaction_rates: Dict[str, sympy.Expr] = None,
+    ) -> List[sympy.Expr]:
+        """Set the derivative of every rate-rule variable in the state vector
 
+        A rate rule replaces any reaction contributions for its variable.
+        Reaction IDs in the rule are replaced by the reaction rates.
+
+        Args:
+            dy_dt: List of dy/dt expressions for each state
+            rate_rules: Dictionary of rate rule data from SBML
+            reaction_rates: Dictionary mapping reaction IDs to rate expressions
+
+        Returns:
+            Updated list of dy/dt expressions
+        """
+        dy_dt = list(dy_dt)
+        rate_subs = {
+            sympy.Symbol(r_id): rate for r_id, rate in (reaction_rates or {}).items()
+        }
+
+        for rule_id, rule in rate_rules.items():
+            variable = rule.get("variable")
+            if variable not in self.species_map:
+                continue
+
+            try:
+                expr = self.parser.parse(rule.get("math", "0"))
             except Exception as e:
-                print(f"Failed to process reaction {rxn_id}: {e}")
+                print(f"Failed to process rate rule {rule_id}: {e}")
                 raise e
 
+            dy_dt[self.species_map[variable]] = expr.xreplace(rate_subs)
+
         return dy_dt
 
+    def apply_algebraic_rules(
+        self,
+        dy_dt: List[sympy.Expr],
+        algebraic_rules: Dict[str, Any],
+        variables: Dict[str, str],
+        reaction_rates: Dict[str, sympy.Expr] = None,
+    ) -> List[sympy.Expr]:
+        """Replace the row of each variable an algebraic rule determines by the rule
+
+        The solver holds these rows at zero (zero rows of the mass matrix)
+        instead of integrating them.
+
+        Args:
+            dy_dt: List of dy/dt expressions for each state
+            algebraic_rules: Dictionary of algebraic rule data from SBML
+            variables: The variable each rule determines, by rule ID
+            reaction_rates: Dictionary mapping reaction IDs to rate expressions
+
+        Returns:
+            Updated list of expressions
+        """
+        rules = {
+            rule_id: dict(rule, variable=variables[rule_id])
+            for rule_id, rule in algebraic_rules.items()
+        }
+        return self.apply_rate_rules(dy_dt, rules, reaction_rates)
+
+    def resolve_rate_of(
+        self,
+        dy_dt: List[sympy.Expr],
+        rules: Optional[Dict[str, sympy.Expr]] = None,
+    ) -> List[sympy.Expr]:
+        """Replace every rateOf in the derivatives by the rate it reads
+
+        Derivatives may read the rates of other states, so this repeats until
+        none is left.
+
+        Args:
+            dy_dt: List of dy/dt expressions for each state
+            rules: Assignment rule expressions by variable, state-dependent
+                rules inlined
+
+        Returns:
+            Updated list of dy/dt expressions
+
+        Raises:
+            ValueError: If derivatives read each other's rates in a cycle
+        """
+        dy_dt = list(dy_dt)
+        for _ in range(len(dy_dt) + 1):
+            if not any(expr.has(RateOf) for expr in dy_dt):
+                return dy_dt
+            dy_dt = self.substitute_rate_of(dy_dt, dy_dt, rules)
+        raise ValueError("Circular rateOf references between the derivatives")
+
+    def substitute_rate_of(
+        self,
+        expressions: List[sympy.Expr],
+        dy_dt: List[sympy.Expr],
+        rules: Optional[Dict[str, sympy.Expr]] = None,
+    ) -> List[sympy.Expr]:
+        """Replace rateOf(x) in expressions by the time derivative of x
+
+        The rate of a state is its entry of dy_dt; that of an assignment rule
+        variable is its rule differentiated along dy_dt (chain rule, plus the
+        explicit dependence on t); anything else is constant, with rate zero.
+
+        Args:
+            expressions: Expressions that may read rateOf
+            dy_dt: List of dy/dt expressions for each state
+            rules: Assignment rule expressions by variable, state-dependent
+                rules inlined
+
+        Returns:
+            Expressions with rateOf replaced
+        """
+        rules = rules or {}
+        t = sympy.Symbol("t")
+
+        def rate(arg):
+            name = str(arg)
+            if name in self.species_map:
+                return dy_dt[self.species_map[name]]
+            if name in rules:
+                expr = rules[name]
+                return sympy.Add(
+                    sympy.diff(expr, t),
+                    *(sympy.diff(expr, sympy.Symbol(s_id)) * dy_dt[idx] for s_id, idx in self.species_map.items()),
+                )
+            return sympy.S.Zero
+
+        return [expr.replace(RateOf, rate) for expr in expressions]
+
+    def split_window_terms(
+        self, dy_dt: List[sympy.Expr], window_parameters: Tuple[str, str],
+        pulse_sharpness: Optional[float] = None
+    ) -> Tuple[List[sympy.Expr], Dict[str, sympy.Expr]]:
+        """Move the terms that read the dose-window parameters out of the ODEs
+
+        Each derivative with such terms gets the symbol uptake_<species> in
+        their place, so the generated code can evaluate them once per uptake
+        window and sum the results.
+
+        Args:
+            dy_dt: List of dy/dt expressions for each state
+            window_parameters: Names of the parameters bounding the window (start, end)
+            pulse_sharpness: If given, tanh edges at the window start or end get
+                the steepness pulse_sharpness/(end - start)
+
+        Returns:
+            Tuple of (updated dy/dt expressions, mapping of species ID to the
+            moved terms in state order)
+
+        Raises:
+            ValueError: If no derivative reads the window parameters, or a
+                windowed term also reads a state
+        """
+        window_symbols = {sympy.Symbol(name) for name in window_parameters}
+        state_symbols = {sympy.Symbol(s_id) for s_id in self.species_map}
+        species_ids = sorted(self.species_map, key=self.species_map.get)
+
+        dy_dt = list(dy_dt)
+        window_terms = {}
+        for idx, expr in enumerate(dy_dt):
+            terms = sympy.Add.make_args(sympy.expand_mul(expr, deep=False))
+            windowed = [term for term in terms if term.free_symbols & window_symbols]
+            if not windowed:
+                continue
+            species_id = species_ids[idx]
+            moved = sympy.Add(*windowed)
+            if moved.free_symbols & state_symbols:
+                raise ValueError(
+                    f"Uptake window terms of {species_id} depend on the state: {moved}"
+                )
+            if pulse_sharpness is not None:
+                moved = self._sharpen_window_edges(moved, window_parameters, pulse_sharpness)
+            window_terms[species_id] = moved
+            rest = [term for term in terms if not term.free_symbols & window_symbols]
+            dy_dt[idx] = sympy.Add(*rest, sympy.Symbol(f"uptake_{species_id}"))
+
+        if not window_terms:
+            raise ValueError(
+                f"No rate reads the uptake window parameters {', '.join(window_parameters)}"
+            )
+        return dy_dt, window_terms
+
+    @staticmethod
+    def _sharpen_window_edges(
+        expr: sympy.Expr, window_parameters: Tuple[str, str], pulse_sharpness: float
+    ) -> sympy.Expr:
+        """Rewrite tanh(k*(t - edge)) at either window edge to the steepness
+        pulse_sharpness/(end - start); other tanh terms are left as they are
+
+        Args:
+            expr: Window terms of one derivative
+            window_parameters: Names of the parameters bounding the window (start, end)
+            pulse_sharpness: Edge steepness times the window length
+
+        Returns:
+            Expression with the window edges rescaled
+        """
+        t = sympy.Symbol("t")
+        start, end = (sympy.Symbol(name) for name in window_parameters)
+        steepness = sympy.Float(pulse_sharpness) / (end - start)
+
+        def edge(arg):
+            k = sympy.diff(arg, t)
+            if k == 0 or k.has(t):
+                return None
+            at = sympy.simplify(t - arg / k)
+            return at if at in (start, end) else None
+
+        return expr.replace(
+            lambda e: isinstance(e, sympy.tanh) and edge(e.args[0]) is not None,
+            lambda e: sympy.tanh(steepness * (t - edge(e.args[0]))),
+        )
+
     def get_species_count(self) -> int:
         """Get number of species in the system
 
@@ -0,0 +1,247 @@
+"""Small models whose generated Rust is checked into wasm_pk_core/tests/models
+
+The core crate's regression tests run these generated files against golden
+results, so a change to the generator or the core crate that alters any
+output shows up there. The euromix and BPA models from data/ are checked in
+alongside them for the property tests, which need libsbml to regenerate. Run this module to regenerate the files after a
+generator change:
+
+    python -m tests.fixture_models
+"""
+
+import os
+import sys
+from pathlib import Path
+
+REPO_DIR = Path(__file__).parent.parent
+MODELS_DIR = REPO_DIR / "wasm_pk_core" / "tests" / "models"
+
+# Fixed generation time, so regenerating an unchanged fixture leaves it as is
+SOURCE_DATE_EPOCH = "1767225600"
+
+# Two species with assignment rules, an initial assignment and local parameters
+TWO_COMPARTMENT = {
+    "species": {
+        "A": {"value": 1.0, "compartment": "c"},
+        "B": {"value": 0.0, "compartment": "c"},
+    },
+    "parameters": {
+        "k1": {"value": 0.5}, "k2": {"value": 0.1}, "V": {"value": 2.0}, "Vb": {"value": 0.0},
+        "Conc": {"value": 0.0}, "W": {"value": 0.0}, "Wb": {"value": 0.0},
+    },
+    "compartments": {"c": {"size": 1.0}},
+    "reactions": {
+        "R1": {"reactants": [[1.0, "A"]], "products": [[1.0, "B"]], "rateLaw": "k1*A/V + 0.01*Conc"},
+        "R2": {
+            "reactants": [[1.0, "B"]], "products": [], "rateLaw": "Vmax*B/(Km+B) + k1*0",
+            "rxnParameters": [["Vmax", 0.2], ["Km", 0.5], ["k1", 9.0]],
+        },
+    },
+    "functions": {},
+    "assignmentRules": {
+        "r1": {"variable": "W", "math": "V*2"},
+        "r2": {"variable": "Conc", "math": "B/W + t*0"},
+        "r3": {"variable": "Wb", "math": "Vb*2"},
+    },
+    "initialAssignments": {"ia1": {"variable": "Vb", "math": "V*3"}},
+}
+
+# Oral absorption with a time-triggered bolus and model time in hours
+EVENT_DOSE = {
+    "species": {
+        "Gut": {"value": 0.0, "compartment": "c"},
+        "Cp": {"value": 0.0, "compartment": "c"},
+        "Urine": {"value": 0.0, "compartment": "c"},
+    },
+    "parameters": {
+        "ka": {"value": 1.0}, "ke": {"value": 0.2}, "Dose": {"value": 10.0},
+        "Vd": {"value": 5.0}, "Mr": {"value": 300.0},
+    },
+    "compartments": {"c": {"size": 1.0}},
+    "reactions": {
+        "abs": {"reactants": [[1.0, "Gut"]], "products": [[1.0, "Cp"]], "rateLaw": "ka*Gut"},
+        "el": {"reactants": [[1.0, "Cp"]], "products": [[1.0, "Urine"]], "rateLaw": "ke*Cp"},
+    },
+    "functions": {},
+    "assignmentRules": {"r1": {"variable": "Vtot", "math": "Vd*2"}},
+    "events": {
+        "dose1": {"trigger": "t >= 2", "eventAssignments": [{"variable": "Gut", "math": "Gut + Dose"}]},
+    },
+    "model": {"id": "event_dose", "document": None, "sha256": None, "time_units": {"id": "HR", "seconds": 3600.0}},
+}
+
+# Infusion through rate rules on parameters
+INFUSION = {
+    "species": {
+        "Cve": {"value": 0.0, "compartment": "c"},
+        "Ast": {"value": 0.0, "compartment": "c"},
+    },
+    "parameters": {
+        "IVDOSE": {"value": 0.0}, "cum_dose": {"value": 0.0}, "Ri": {"value": 0.0}, "ti": {"value": 10.0},
+        "Ki": {"value": 0.0}, "Mr": {"value": 2.0}, "PODOSE": {"value": 0.0}, "ke": {"value": 1.0},
+    },
+    "compartments": {"c": {"size": 1.0}},
+    "reactions": {
+        "iv": {"reactants": [], "products": [[1.0, "Cve"]], "rateLaw": "Ki*IVDOSE/Mr"},
+        "abs": {"reactants": [[1.0, "Ast"]], "products": [[1.0, "Cve"]], "rateLaw": "ke*Ast"},
+        "el": {"reactants": [[1.0, "Cve"]], "products": [], "rateLaw": "ke*Cve"},
+    },
+    "functions": {},
+    "initialAssignments": {"ia1": {"variable": "Ast", "math": "PODOSE/Mr"}},
+    "assignmentRules": {"r1": {"variable": "Ki", "math": "0.693/ti*60"}},
+    "rateRules": {
+        "rr1": {"variable": "IVDOSE", "math": "-iv*Mr + Ri"},
+        "rr2": {"variable": "cum_dose", "math": "Ri"},
+    },
+}
+
+# An oral uptake pulse between t0 and t1 = t0 + period_O, as in the BPA model
+ORAL_WINDOW = {
+    "species": {"Aplasma": {"value": 0.0, "compartment": "comp1"}},
+    "parameters": {
+        "Kabs": {"value": 0.4}, "koa": {"value": 0.0}, "t0": {"value": 0.0}, "t1": {"value": 0.0},
+        "Kelm": {"value": 0.13}, "EoA_O": {"value": 1.0}, "D_o": {"value": 1.3381102},
+        "vplasma": {"value": 3.6}, "period_O": {"value": 0.0003}, "n_O": {"value": 1.0},
+        "uptake_O": {"value": 0.0},
+        "cplasma": {"value": None, "constant": False, "units": "MilliMOL_per_L"},
+    },
+    "compartments": {"comp1": {"size": 3.6}},
+    "reactions": {
+        "J_absorption": {
+            "reactants": [], "products": [[1.0, "Aplasma"]],
+            "rateLaw": "Kabs*(koa*(tanh(100*(time - t0)) - tanh(100*(time - t1)))/2) - Kelm*Aplasma",
+        },
+    },
+    "functions": {},
+    "assignmentRules": {"rule0": {"variable": "cplasma", "math": "Aplasma/vplasma"}},
+    "initialAssignments": {
+        "ia1": {"variable": "koa", "math": "1.2e8*EoA_O*D_o"},
+        "ia2": {"variable": "t1", "math": "t0 + period_O"},
+        "ia3": {"variable": "uptake_O", "math": "EoA_O*D_o/n_O"},
+    },
+}
+
+# A chain of transit compartments ending in saturable clearance: a large
+# model whose Jacobian has two entries per row, for the sparse solver
+_TRANSIT = 80
+TRANSIT_CHAIN = {
+    "species": {f"T{i}": {"value": 10.0 if i == 0 else 0.0, "compartment": "c"} for i in range(_TRANSIT)},
+    "parameters": {"ktr": {"value": 4.0}, "Vmax": {"value": 0.5}, "Km": {"value": 0.2}},
+    "compartments": {"c": {"size": 1.0}},
+    "reactions": {
+        **{
+            f"tr{i}": {"reactants": [[1.0, f"T{i}"]], "products": [[1.0, f"T{i + 1}"]], "rateLaw": f"ktr*T{i}"}
+            for i in range(_TRANSIT - 1)
+        },
+        "cl": {
+            "reactants": [[1.0, f"T{_TRANSIT - 1}"]], "products": [],
+            "rateLaw": f"Vmax*T{_TRANSIT - 1}/(Km + T{_TRANSIT - 1})",
+        },
+    },
+    "functions": {},
+    "assignmentRules": {},
+}
+
+# Absorption into a central compartment whose drug binds a saturable protein
+# in fast equilibrium: the free concentration, which is eliminated, is fixed
+# by an algebraic rule, so the model is a DAE
+PROTEIN_BINDING = {
+    "species": {
+        "Gut": {"value": 10.0, "compartment": "c"},
+        "Cent": {"value": 0.0, "compartment": "c"},
+    },
+    "parameters": {
+        "ka": {"value": 1.0}, "ke": {"value": 0.5}, "Kd": {"value": 0.5}, "Ptot": {"value": 2.0},
+        "Cfree": {"value": 0.0, "constant": False},
+    },
+    "compartments": {"c": {"size": 1.0}},
+    "reactions": {
+        "abs": {"reactants": [[1.0, "Gut"]], "products": [[1.0, "Cent"]], "rateLaw": "ka*Gut"},
+        "el": {"reactants": [[1.0, "Cent"]], "products": [], "rateLaw": "ke*Cfree"},
+    },
+    "functions": {},
+    "assignmentRules": {},
+    "algebraicRules": {"binding": {"math": "Cfree + Ptot*Cfree/(Kd + Cfree) - Cent"}},
+}
+
+# File name, model data and SbmlToRustConverter.convert arguments of each fixture
+FIXTURES = [
+    ("two_compartment.rs", TWO_COMPARTMENT, {"model_name": "two_compartment", "sensitivities": True}),
+    ("event_dose.rs", EVENT_DOSE, {"model_name": "event_dose", "wasm": False, "embed_sbml": False}),
+    ("infusion.rs", INFUSION, {"model_name": "infusion"}),
+    ("oral_window.rs", ORAL_WINDOW, {"model_name": "oral_window", "uptake_window": ("t0", "t1"), "pulse_sharpness": 100.0, "uptake_repeat": ("n_O", "period_O")}),
+    ("transit_chain.rs", TRANSIT_CHAIN, {"model_name": "transit_chain", "wasm": False, "embed_sbml": False}),
+    (
+        "transit_chain_sparse.rs", TRANSIT_CHAIN,
+        {"model_name": "transit_chain", "wasm": False, "embed_sbml": False, "sparse_jacobian": True},
+    ),
+    ("protein_binding.rs", PROTEIN_BINDING, {"model_name": "protein_binding", "wasm": False, "embed_sbml": False}),
+    (
+        "event_dose_jacobian_matrix.rs", EVENT_DOSE,
+        {"model_name": "event_dose", "wasm": False, "embed_sbml": False, "jacobian_matrix": True},
+    ),
+    (
+        "transit_chain_jacobian_matrix.rs", TRANSIT_CHAIN,
+        {
+            "model_name": "transit_chain", "wasm": False, "embed_sbml": False,
+            "sparse_jacobian": True, "jacobian_matrix": True,
+        },
+    ),
+]
+
+
+# File name, SBML file in data/ and convert arguments of each published model
+# checked over its real parameter ranges by the core crate's property tests
+SBML_FIXTURES = [
+    ("euromix.rs", "euromix.sbml", {"model_name": "euromix", "wasm": False, "embed_sbml": False}),
+    ("pbpk_bpa.rs", "PBPK_BPA_model.sbml", {"model_name": "pbpk_bpa", "wasm": False, "embed_sbml": False}),
+]
+
+
+def sbml_fixtures():
+    """SBML_FIXTURES with their SBML files parsed with libsbml
+
+    Returns:
+        (file name, model data, convert arguments) of each file
+    """
+    from sbmlParser.parser import ParseSBMLFile
+
+    return [
+        (file_name, ParseSBMLFile(str(REPO_DIR / "data" / sbml_file)), options)
+        for file_name, sbml_file, options in SBML_FIXTURES
+    ]
+
+
+def generate(converter_class, fixtures=None):
+    """Generate the Rust source of every fixture
+
+    Args:
+        converter_class: SbmlToRustConverter
+        fixtures: (file name, model data, convert arguments) of each file;
+            FIXTURES and the parsed SBML_FIXTURES if not given
+
+    Returns:
+        Dictionary of file name to generated Rust code
+    """
+    if fixtures is None:
+        fixtures = FIXTURES + sbml_fixtures()
+    previous = os.environ.get("SOURCE_DATE_EPOCH")
+    os.environ["SOURCE_DATE_EPOCH"] = SOURCE_DATE_EPOCH
+    try:
+        return {
+            file_name: converter_class(model_data).convert(**options)
+            for file_name, model_data, options in fixtures
+        }
+    finally:
+        if previous is None:
+            del os.environ["SOURCE_DATE_EPOCH"]
+        else:
+            os.environ["SOURCE_DATE_EPOCH"] = previous
+
+
+if __name__ == "__main__":
+    sys.path.insert(0, str(REPO_DIR.parent))
+    converter_class = __import__(REPO_DIR.name + ".facade", fromlist=["SbmlToRustConverter"]).SbmlToRustConverter
+    for file_name, code in generate(converter_class).items():
+        (MODELS_DIR / file_name).write_text(code)
+        print(f"wrote {MODELS_DIR / file_name}")
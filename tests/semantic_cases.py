@@ -0,0 +1,600 @@
+"""Semantic test cases, run by the semantic_cases crate in the repository root
+
+semantic_cases/tests/semantic_cases.rs runs every case on the grid of its
+settings and compares the result with its results CSV, using the case's
+tolerances. Each case lives in semantic_cases/cases/<id> in the layout of
+the SBML Test Suite: <id>-settings.txt, <id>-results.csv and, for cases
+imported from the suite, the SBML itself as <id>-sbml.xml. Only these files
+are checked in: semantic_cases/build.rs generates the Rust of every case into
+its OUT_DIR with `generate` below, so the models always come from the
+current generator.
+
+The curated cases below are written for this repository, with model data
+instead of SBML and results from closed-form solutions; none of them come
+from the SBML Test Suite. Rewrite their settings and results after changing
+them:
+
+    python -m tests.semantic_cases
+
+Import cases from a checkout of the SBML Test Suite (cases/semantic), either
+by number or, without numbers, a selection covering the features in
+SUITE_FEATURES:
+
+    python -m tests.semantic_cases import path/to/cases/semantic 00001 00026
+    python -m tests.semantic_cases import path/to/cases/semantic
+
+Imported cases are parsed with libsbml when they are generated.
+"""
+
+import argparse
+import math
+import shutil
+import sys
+from pathlib import Path
+
+from tests.fixture_models import REPO_DIR, generate
+
+CASES_DIR = REPO_DIR / "semantic_cases" / "cases"
+
+# SBML levels and versions to import, most recent first
+SBML_VERSIONS = ["l3v2", "l3v1", "l2v5", "l2v4"]
+
+# Features the suite selection covers, as the componentTags of a case's
+# <id>-model.m, or the MathML element its SBML must contain
+SUITE_FEATURES = {
+    "rate rules": ("tag", "RateRule"),
+    "assignment rules": ("tag", "AssignmentRule"),
+    "events": ("tag", "EventNoDelay"),
+    "initial assignments": ("tag", "InitialAssignment"),
+    "piecewise": ("math", "<piecewise>"),
+}
+# Cases selected per feature; a case counts for every feature it has
+SUITE_CASES_PER_FEATURE = 8
+# Suite tags of what the generator does not support
+UNSUPPORTED_TAGS = {
+    "AlgebraicRule", "CSymbolDelay", "EventWithDelay", "EventPriority", "FastReaction",
+    "RandomEventExecution", "StoichiometryMath",
+}
+
+# Species and parameters of the curated cases are read as amounts, so
+# everything lives in unit compartments
+_UNIT = {"c": {"size": 1.0}}
+_SETTINGS = {"start": 0.0, "duration": 5.0, "steps": 50, "absolute": 1e-7, "relative": 1e-4}
+
+# Case id, model data, the variables checked and their values at time t
+CURATED = [
+    (
+        "first_order",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}, "S2": {"value": 0.0, "compartment": "c"}},
+            "parameters": {"k1": {"value": 0.8}},
+            "compartments": _UNIT,
+            "reactions": {"R1": {"reactants": [[1.0, "S1"]], "products": [[1.0, "S2"]], "rateLaw": "k1*S1"}},
+            "functions": {},
+            "assignmentRules": {},
+        },
+        ["S1", "S2"],
+        lambda t: {"S1": math.exp(-0.8*t), "S2": 1 - math.exp(-0.8*t)},
+    ),
+    (
+        "reversible",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}, "S2": {"value": 0.5, "compartment": "c"}},
+            "parameters": {"kf": {"value": 1.2}, "kr": {"value": 0.4}},
+            "compartments": _UNIT,
+            "reactions": {
+                "R1": {
+                    "reactants": [[1.0, "S1"]], "products": [[1.0, "S2"]], "rateLaw": "kf*S1 - kr*S2",
+                    "reversible": True,
+                },
+            },
+            "functions": {},
+            "assignmentRules": {},
+        },
+        ["S1", "S2"],
+        # Equilibrium S1 = 1.5*kr/(kf + kr), approached at rate kf + kr
+        lambda t: {
+            "S1": 0.375 + 0.625*math.exp(-1.6*t),
+            "S2": 1.125 - 0.625*math.exp(-1.6*t),
+        },
+    ),
+    (
+        "bimolecular",
+        {
+            "species": {
+                "S1": {"value": 1.0, "compartment": "c"}, "S2": {"value": 1.0, "compartment": "c"},
+                "S3": {"value": 0.0, "compartment": "c"},
+            },
+            "parameters": {"k1": {"value": 2.0}},
+            "compartments": _UNIT,
+            "reactions": {
+                "R1": {"reactants": [[1.0, "S1"], [1.0, "S2"]], "products": [[1.0, "S3"]], "rateLaw": "k1*S1*S2"},
+            },
+            "functions": {},
+            "assignmentRules": {},
+        },
+        ["S1", "S2", "S3"],
+        lambda t: {"S1": 1/(1 + 2*t), "S2": 1/(1 + 2*t), "S3": 1 - 1/(1 + 2*t)},
+    ),
+    (
+        "source_and_decay",
+        {
+            "species": {"S1": {"value": 2.0, "compartment": "c"}},
+            "parameters": {"k0": {"value": 0.3}, "k1": {"value": 0.6}},
+            "compartments": _UNIT,
+            "reactions": {
+                "R1": {"reactants": [], "products": [[1.0, "S1"]], "rateLaw": "k0"},
+                "R2": {"reactants": [[1.0, "S1"]], "products": [], "rateLaw": "k1*S1"},
+            },
+            "functions": {},
+            "assignmentRules": {},
+        },
+        ["S1"],
+        lambda t: {"S1": 0.5 + 1.5*math.exp(-0.6*t)},
+    ),
+    (
+        "function_definition",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}, "S2": {"value": 0.0, "compartment": "c"}},
+            "parameters": {"k1": {"value": 0.5}},
+            "compartments": _UNIT,
+            "reactions": {
+                "R1": {"reactants": [[1.0, "S1"]], "products": [[1.0, "S2"]], "rateLaw": "mass_action(k1, S1)"},
+            },
+            "functions": {"mass_action": {"arguments": ["k", "x"], "mathString": "k*x"}},
+            "assignmentRules": {},
+        },
+        ["S1", "S2"],
+        lambda t: {"S1": math.exp(-0.5*t), "S2": 1 - math.exp(-0.5*t)},
+    ),
+    (
+        "local_parameter",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}, "S2": {"value": 0.0, "compartment": "c"}},
+            "parameters": {"k1": {"value": 9.0}},
+            "compartments": _UNIT,
+            "reactions": {
+                "R1": {
+                    "reactants": [[1.0, "S1"]], "products": [[1.0, "S2"]], "rateLaw": "k1*S1",
+                    "rxnParameters": [["k1", 0.4]],
+                },
+            },
+            "functions": {},
+            "assignmentRules": {},
+        },
+        ["S1", "S2"],
+        lambda t: {"S1": math.exp(-0.4*t), "S2": 1 - math.exp(-0.4*t)},
+    ),
+    (
+        "assignment_rule",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}, "S2": {"value": 0.0, "compartment": "c"}},
+            "parameters": {"k1": {"value": 0.3}, "P": {"value": 0.0, "constant": False}},
+            "compartments": _UNIT,
+            "reactions": {"R1": {"reactants": [[1.0, "S1"]], "products": [[1.0, "S2"]], "rateLaw": "k1*P/3"}},
+            "functions": {},
+            "assignmentRules": {"rule_P": {"variable": "P", "math": "3*S1"}},
+        },
+        ["S1", "S2", "P"],
+        lambda t: {"S1": math.exp(-0.3*t), "S2": 1 - math.exp(-0.3*t), "P": 3*math.exp(-0.3*t)},
+    ),
+    (
+        "initial_assignment",
+        {
+            "species": {"S1": {"value": 0.0, "compartment": "c"}},
+            "parameters": {"k1": {"value": 0.7}},
+            "compartments": _UNIT,
+            "reactions": {"R1": {"reactants": [[1.0, "S1"]], "products": [], "rateLaw": "k1*S1"}},
+            "functions": {},
+            "assignmentRules": {},
+            "initialAssignments": {"ia_S1": {"variable": "S1", "math": "2*k1"}},
+        },
+        ["S1"],
+        lambda t: {"S1": 1.4*math.exp(-0.7*t)},
+    ),
+    (
+        "rate_rules",
+        {
+            "species": {"S1": {"value": 2.0, "compartment": "c"}},
+            "parameters": {"k1": {"value": 0.3}, "r": {"value": 0.25}, "p": {"value": 1.0, "constant": False}},
+            "compartments": _UNIT,
+            "reactions": {},
+            "functions": {},
+            "assignmentRules": {},
+            "rateRules": {
+                "rr_S1": {"variable": "S1", "math": "-k1*S1"},
+                "rr_p": {"variable": "p", "math": "r"},
+            },
+        },
+        ["S1", "p"],
+        lambda t: {"S1": 2*math.exp(-0.3*t), "p": 1 + 0.25*t},
+    ),
+    (
+        "rate_of",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}, "S2": {"value": 0.0, "compartment": "c"}},
+            "parameters": {
+                "k1": {"value": 0.5},
+                "r1": {"value": 0.0, "constant": False},
+                "r2": {"value": 0.0, "constant": False},
+                "r3": {"value": 0.0, "constant": False},
+                "total": {"value": 0.0, "constant": False},
+            },
+            "compartments": _UNIT,
+            "reactions": {"R1": {"reactants": [[1.0, "S1"]], "products": [[1.0, "S2"]], "rateLaw": "k1*S1"}},
+            "functions": {},
+            "assignmentRules": {
+                "rule1": {"variable": "r1", "math": "rateOf(S1)"},
+                "rule2": {"variable": "r2", "math": "rateOf(S2)*time"},
+                "rule3": {"variable": "total", "math": "S1 + S2"},
+                # Conserved, so its rate is zero
+                "rule4": {"variable": "r3", "math": "rateOf(total)"},
+            },
+        },
+        ["S1", "r1", "r2", "r3"],
+        lambda t: {"S1": math.exp(-0.5*t), "r1": -0.5*math.exp(-0.5*t), "r2": 0.5*math.exp(-0.5*t)*t, "r3": 0.0},
+    ),
+    (
+        "piecewise_rate",
+        {
+            "species": {"S1": {"value": 0.0, "compartment": "c"}},
+            "parameters": {"t_switch": {"value": 2.0}},
+            "compartments": _UNIT,
+            "reactions": {
+                "R1": {"reactants": [], "products": [[1.0, "S1"]], "rateLaw": "piecewise(1, lt(time, t_switch), 0.5)"},
+            },
+            "functions": {},
+            "assignmentRules": {},
+        },
+        ["S1"],
+        lambda t: {"S1": t if t < 2 else 2 + 0.5*(t - 2)},
+    ),
+    (
+        "piecewise_regimes",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}},
+            "parameters": {},
+            "compartments": _UNIT,
+            "reactions": {
+                # Held until t = 1, then zero-order down to 0.5 and first-order
+                # below it, and slower first-order from t = 4
+                "R1": {
+                    "reactants": [[1.0, "S1"]], "products": [],
+                    "rateLaw": "piecewise(0, lt(time, 1), piecewise(0.25, gt(S1, 0.5), 0.5*S1), lt(time, 4), 0.1*S1)",
+                },
+            },
+            "functions": {},
+            "assignmentRules": {},
+        },
+        ["S1"],
+        lambda t: {
+            "S1": 1.0 if t < 1 else 1 - 0.25*(t - 1) if t < 3
+            else 0.5*math.exp(-0.5*(t - 3)) if t < 4 else 0.5*math.exp(-0.5)*math.exp(-0.1*(t - 4))
+        },
+    ),
+    (
+        "time_event",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}, "S2": {"value": 0.0, "compartment": "c"}},
+            "parameters": {"k1": {"value": 0.5}},
+            "compartments": _UNIT,
+            "reactions": {"R1": {"reactants": [[1.0, "S1"]], "products": [[1.0, "S2"]], "rateLaw": "k1*S1"}},
+            "functions": {},
+            "assignmentRules": {},
+            "events": {
+                "bolus": {"trigger": "t >= 2.55", "eventAssignments": [{"variable": "S1", "math": "S1 + 1"}]},
+            },
+        },
+        ["S1", "S2"],
+        # A bolus between two output times, so no row sits on the event
+        lambda t: {
+            "S1": math.exp(-0.5*t) + (math.exp(-0.5*(t - 2.55)) if t > 2.55 else 0.0),
+            "S2": 1 - math.exp(-0.5*t) + (1 - math.exp(-0.5*(t - 2.55)) if t > 2.55 else 0.0),
+        },
+    ),
+    (
+        "threshold_event",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}},
+            "parameters": {"k1": {"value": 0.5}},
+            "compartments": _UNIT,
+            "reactions": {"R1": {"reactants": [[1.0, "S1"]], "products": [], "rateLaw": "k1*S1"}},
+            "functions": {},
+            "assignmentRules": {},
+            "events": {"refill": {"trigger": "S1 < 0.5", "eventAssignments": [{"variable": "S1", "math": "1"}]}},
+        },
+        ["S1"],
+        # Refilled each time it halves, every log(2)/k1
+        lambda t: {"S1": math.exp(-0.5*math.fmod(t, math.log(2)/0.5))},
+    ),
+    (
+        "event_cumulative_dose",
+        {
+            "species": {"S1": {"value": 0.0, "compartment": "c"}},
+            "parameters": {
+                "k1": {"value": 0.5}, "ri": {"value": 0.1}, "dose": {"value": 1.0},
+                "cum_dose": {"value": 0.0, "constant": False},
+                "boluses": {"value": 0.0, "constant": False},
+            },
+            "compartments": _UNIT,
+            "reactions": {
+                "infusion": {"reactants": [], "products": [[1.0, "S1"]], "rateLaw": "ri"},
+                "R1": {"reactants": [[1.0, "S1"]], "products": [], "rateLaw": "k1*S1"},
+            },
+            "functions": {},
+            "assignmentRules": {},
+            # cum_dose counts the infusion through a rate rule and the
+            # boluses through events; boluses only changes at events
+            "rateRules": {"rr1": {"variable": "cum_dose", "math": "ri"}},
+            "events": {
+                event_id: {
+                    "trigger": f"t >= {time}",
+                    "eventAssignments": [
+                        {"variable": "S1", "math": "S1 + dose"},
+                        {"variable": "cum_dose", "math": "cum_dose + dose"},
+                        {"variable": "boluses", "math": "boluses + 1"},
+                    ],
+                }
+                for event_id, time in (("bolus1", 1.55), ("bolus2", 3.55))
+            },
+        },
+        ["S1", "cum_dose", "boluses"],
+        lambda t: {
+            "S1": 0.2*(1 - math.exp(-0.5*t)) + sum(math.exp(-0.5*(t - b)) for b in (1.55, 3.55) if t > b),
+            "cum_dose": 0.1*t + sum(1.0 for b in (1.55, 3.55) if t > b),
+            "boluses": sum(1.0 for b in (1.55, 3.55) if t > b),
+        },
+    ),
+    (
+        "staggered_doses",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}},
+            "parameters": {"k1": {"value": 0.5}},
+            "compartments": _UNIT,
+            "reactions": {"R1": {"reactants": [[1.0, "S1"]], "products": [], "rateLaw": "k1*S1"}},
+            "functions": {},
+            "assignmentRules": {},
+            "rateRules": {},
+            # Three doses, each with its own root; the last also needs S1
+            # below a bound it never reaches
+            "events": {
+                "dose1": {"trigger": "t >= 0.55", "eventAssignments": [{"variable": "S1", "math": "S1 + 1"}]},
+                "dose2": {"trigger": "t >= 1.55", "eventAssignments": [{"variable": "S1", "math": "S1 + 1"}]},
+                "dose3": {"trigger": "(t >= 3.05) & (S1 < 5)", "eventAssignments": [{"variable": "S1", "math": "S1 + 1"}]},
+            },
+        },
+        ["S1"],
+        lambda t: {"S1": sum(math.exp(-0.5*(t - b)) for b in (0.0, 0.55, 1.55, 3.05) if t >= b)},
+    ),
+    (
+        "trigger_semantics",
+        {
+            "species": {"S1": {"value": 1.0, "compartment": "c"}},
+            "parameters": {
+                "k1": {"value": 0.5},
+                "repeats": {"value": 0.0, "constant": False},
+                "flickers": {"value": 0.0, "constant": False},
+            },
+            "compartments": _UNIT,
+            "reactions": {"R1": {"reactants": [[1.0, "S1"]], "products": [], "rateLaw": "k1*S1"}},
+            "functions": {},
+            "assignmentRules": {},
+            "rateRules": {},
+            # Both start events hold at t = 0, where only the one whose
+            # trigger was false before fires; the non-persistent flicker
+            # trigger holds from 1.05 to 2.05 and again from 3.05
+            "events": {
+                "at_start": {
+                    "trigger": "t >= 0", "initialValue": False,
+                    "eventAssignments": [{"variable": "S1", "math": "S1 + 1"}],
+                },
+                "already_true": {
+                    "trigger": "t >= 0",
+                    "eventAssignments": [{"variable": "repeats", "math": "repeats + 1"}],
+                },
+                "flicker": {
+                    "trigger": "or(and(geq(t, 1.05), lt(t, 2.05)), geq(t, 3.05))", "persistent": False,
+                    "eventAssignments": [{"variable": "flickers", "math": "flickers + 1"}],
+                },
+            },
+        },
+        ["S1", "repeats", "flickers"],
+        lambda t: {
+            "S1": 2*math.exp(-0.5*t),
+            "repeats": 0.0,
+            "flickers": sum(1.0 for b in (1.05, 3.05) if t > b),
+        },
+    ),
+]
+
+
+def _format(value):
+    """Format a number as the suite's files do, integers without a fraction"""
+    return str(int(value)) if float(value).is_integer() and abs(value) < 1e15 else repr(float(value))
+
+
+def write_curated_case(case_id, model_data, variables, solution, settings=_SETTINGS):
+    """Write the settings and the closed-form results of a curated case
+
+    Args:
+        case_id: Directory and file name prefix of the case
+        model_data: Model data of the case; its species are checked as amounts
+        variables: Ids checked
+        solution: Function of time returning the value of every variable
+        settings: start, duration, steps and tolerances of the case
+    """
+    case_dir = CASES_DIR / case_id
+    case_dir.mkdir(parents=True, exist_ok=True)
+    lines = [
+        f"start: {_format(settings['start'])}",
+        f"duration: {_format(settings['duration'])}",
+        f"steps: {settings['steps']}",
+        f"variables: {', '.join(variables)}",
+        f"absolute: {settings['absolute']}",
+        f"relative: {settings['relative']}",
+        f"amount: {', '.join(v for v in variables if v in model_data['species'])}",
+        "concentration: ",
+    ]
+    (case_dir / f"{case_id}-settings.txt").write_text("\n".join(lines) + "\n")
+    rows = [",".join(["time"] + variables)]
+    for i in range(settings["steps"] + 1):
+        t = settings["start"] + i*settings["duration"]/settings["steps"]
+        values = solution(t)
+        rows.append(",".join(_format(x) for x in [t] + [values[v] for v in variables]))
+    (case_dir / f"{case_id}-results.csv").write_text("\n".join(rows) + "\n")
+
+
+def import_suite_case(semantic_dir, case_id):
+    """Copy a case of the SBML Test Suite into the cases directory
+
+    Args:
+        semantic_dir: The suite's cases/semantic directory
+        case_id: Five-digit case number, e.g. "00001"
+
+    Raises:
+        FileNotFoundError: If the case has no SBML in a supported level and version
+    """
+    source = Path(semantic_dir) / case_id
+    sbml = _suite_sbml(source, case_id)
+    if sbml is None:
+        raise FileNotFoundError(f"{source}: no SBML in {', '.join(SBML_VERSIONS)}")
+    case_dir = CASES_DIR / case_id
+    case_dir.mkdir(parents=True, exist_ok=True)
+    shutil.copyfile(sbml, case_dir / f"{case_id}-sbml.xml")
+    for suffix in ["settings.txt", "results.csv"]:
+        shutil.copyfile(source / f"{case_id}-{suffix}", case_dir / f"{case_id}-{suffix}")
+
+
+def _suite_sbml(source, case_id):
+    """The case's SBML in the most recent supported level and version, or None"""
+    return next((source / f"{case_id}-sbml-{v}.xml" for v in SBML_VERSIONS
+                 if (source / f"{case_id}-sbml-{v}.xml").exists()), None)
+
+
+def _suite_tags(source, case_id):
+    """componentTags, testTags and testType of a case, from its <id>-model.m"""
+    fields = {}
+    for line in (source / f"{case_id}-model.m").read_text().splitlines():
+        key, _, value = line.partition(":")
+        if key.strip() in ("componentTags", "testTags", "testType"):
+            fields[key.strip()] = {tag.strip() for tag in value.split(",") if tag.strip()}
+    return fields.get("componentTags", set()) | fields.get("testTags", set()), fields.get("testType", set())
+
+
+def select_suite_cases(semantic_dir, per_feature=SUITE_CASES_PER_FEATURE):
+    """Time-course cases of the suite covering SUITE_FEATURES, lowest numbers first
+
+    Cases with an unsupported tag or without SBML in a supported version are
+    skipped.
+
+    Args:
+        semantic_dir: The suite's cases/semantic directory
+        per_feature: Cases to take for each feature
+
+    Returns:
+        Sorted case numbers
+    """
+    chosen = set()
+    counts = dict.fromkeys(SUITE_FEATURES, 0)
+    for source in sorted(p for p in Path(semantic_dir).iterdir() if p.is_dir() and p.name.isdigit()):
+        case_id = source.name
+        tags, test_type = _suite_tags(source, case_id)
+        sbml = _suite_sbml(source, case_id)
+        if "TimeCourse" not in test_type or tags & UNSUPPORTED_TAGS or sbml is None:
+            continue
+        text = sbml.read_text()
+        for feature, (kind, name) in SUITE_FEATURES.items():
+            has = name in tags if kind == "tag" else name in text
+            if has and counts[feature] < per_feature:
+                counts[feature] += 1
+                chosen.add(case_id)
+        if all(count == per_feature for count in counts.values()):
+            break
+    return sorted(chosen)
+
+
+def _module_name(case_id):
+    return f"case_{case_id}"
+
+
+def case_models():
+    """Model data of every case in the cases directory, curated ones first
+
+    Returns:
+        List of (case id, model data)
+    """
+    models = [(case_id, model_data) for case_id, model_data, _, _ in CURATED]
+    curated = {case_id for case_id, _ in models}
+    imported = sorted(d.name for d in CASES_DIR.iterdir() if d.is_dir() and d.name not in curated)
+    if imported:
+        from sbmlParser.parser import ParseSBMLFile
+        for case_id in imported:
+            models.append((case_id, ParseSBMLFile(str(CASES_DIR / case_id / f"{case_id}-sbml.xml"))))
+    return models
+
+
+def module_index(case_ids, directory):
+    """Rust source of the semantic_cases crate's root, declaring every case
+
+    Args:
+        case_ids: Case ids, in the order they are run
+        directory: Directory of the generated case modules
+
+    Returns:
+        Contents of mod.rs, included by semantic_cases/src/lib.rs
+    """
+    code = "// Generated by semantic_cases/build.rs; do not edit\n\n"
+    for case_id in case_ids:
+        code += "#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]\n"
+        code += f'#[path = "{(Path(directory) / _module_name(case_id)).as_posix()}.rs"]\n'
+        code += f"pub mod {_module_name(case_id)};\n"
+    code += "\n/// The run_simulation of a case\n"
+    code += "pub type RunSimulation = fn(&str) -> String;\n"
+    code += "\n/// Id and run_simulation of every case\n"
+    code += "pub const CASES: &[(&str, RunSimulation)] = &[\n"
+    for case_id in case_ids:
+        code += f'    ("{case_id}", {_module_name(case_id)}::run_simulation),\n'
+    code += "];\n"
+    return code
+
+
+def generate_cases(out_dir):
+    """Write the Rust module of every case and their index, mod.rs, into `out_dir`
+
+    Args:
+        out_dir: Output directory, the OUT_DIR of semantic_cases/build.rs
+    """
+    sys.path.insert(0, str(REPO_DIR.parent))
+    converter_class = __import__(REPO_DIR.name + ".facade", fromlist=["SbmlToRustConverter"]).SbmlToRustConverter
+    models = case_models()
+    fixtures = [
+        (f"{_module_name(case_id)}.rs", model_data,
+         {"model_name": _module_name(case_id), "wasm": False, "embed_sbml": False})
+        for case_id, model_data in models
+    ]
+    out_dir = Path(out_dir).resolve()
+    out_dir.mkdir(parents=True, exist_ok=True)
+    for file_name, code in generate(converter_class, fixtures).items():
+        (out_dir / file_name).write_text(code)
+    (out_dir / "mod.rs").write_text(module_index([case_id for case_id, _ in models], out_dir))
+
+
+if __name__ == "__main__":
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    commands = parser.add_subparsers(dest="command")
+    importing = commands.add_parser("import", help="copy cases of the SBML Test Suite")
+    importing.add_argument("semantic_dir", help="the suite's cases/semantic directory")
+    importing.add_argument("case_ids", nargs="*", help="case numbers; a selection by feature if none")
+    generating = commands.add_parser("generate", help="write the Rust of every case, as build.rs does")
+    generating.add_argument("out_dir")
+    args = parser.parse_args()
+    if args.command == "import":
+        for case_id in args.case_ids or select_suite_cases(args.semantic_dir):
+            import_suite_case(args.semantic_dir, case_id)
+            print(f"imported {CASES_DIR / case_id}")
+    elif args.command == "generate":
+        generate_cases(args.out_dir)
+    else:
+        for case_id, model_data, variables, solution in CURATED:
+            write_curated_case(case_id, model_data, variables, solution)
+            print(f"wrote {CASES_DIR / case_id}")
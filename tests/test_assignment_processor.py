@@ -196,6 +196,80 @@ class TestAssignmentRuleProcessor:
         with pytest.raises(ValueError, match="Circular dependency"):
             processor._topological_sort(dependencies)
 
+    def test_split_by_state_dependence(self, processor):
+        """Test that rules depending on state or time are separated transitively"""
+        V1, V2, k1, k2, x, t = sympy.symbols("V1 V2 k1 k2 x t")
+        sorted_rules = [
+            ("V1", k1 * 2),
+            ("V2", x / V1),
+            ("V3", V2 + k2),
+            ("V4", k2 * t),
+        ]
+
+        parameter_only, state_dependent = processor.split_by_state_dependence(
+            sorted_rules, {"x"}
+        )
+
+        assert [var for var, _ in parameter_only] == ["V1"]
+        assert [var for var, _ in state_dependent] == ["V2", "V3", "V4"]
+
+    def test_substitute_rules(self, processor):
+        """Test that state-dependent rules are inlined transitively"""
+        V1, V2, k1, x = sympy.symbols("V1 V2 k1 x")
+        rules = [("V1", x / k1), ("V2", V1 * 2)]
+
+        result = processor.substitute_rules([V2 + x], rules)
+
+        assert sympy.simplify(result[0] - (2 * x / k1 + x)) == 0
+        # The Jacobian now sees the dependence on x through V2
+        assert sympy.diff(result[0], x) != 1
+
+    def test_mass_coefficients(self, processor):
+        """Test that mass rules linear in one state give that state's factor"""
+        Cve, Cli, Aurine, Vve, Mr, k1, t = sympy.symbols("Cve Cli Aurine Vve Mr k1 t")
+        Ave = sympy.Symbol("Ave")
+        rules = [
+            ("Ave", Cve * Vve),
+            ("Xve", Ave * Mr),
+            ("Xurine", Aurine * Mr),
+            ("Xsum", (Cve + Cli) * Mr),
+            ("Xtime", Cli * Mr * t),
+            ("Xoffset", Cli * Mr + k1),
+        ]
+
+        result = processor.mass_coefficients(
+            rules, {"Xve", "Xurine", "Xsum", "Xtime", "Xoffset"}, ["Cve", "Cli", "Aurine"]
+        )
+
+        # Mass rules reading two states, time or a constant term are skipped
+        assert list(result) == ["Cve", "Aurine"]
+        assert sympy.simplify(result["Cve"] - Vve * Mr) == 0
+        assert result["Aurine"] == Mr
+
+    def test_sort_with_initial_assignments(self, processor):
+        """Test that rules reading initially-assigned variables are ordered after them"""
+        V1, V2, k1 = sympy.symbols("V1 V2 k1")
+        initial_assignments = {
+            "ia1": {"variable": "V1", "math": "k1 * 3"}
+        }
+
+        result = processor.sort_with_initial_assignments(
+            [("V2", V1 * 2)], initial_assignments
+        )
+
+        variables = [var for var, _ in result]
+        assert variables == ["V1", "V2"]
+
+    def test_sort_with_initial_assignments_circular(self, processor):
+        """Test that cycles through initial assignments are rejected"""
+        V1, V2 = sympy.symbols("V1 V2")
+        initial_assignments = {
+            "ia1": {"variable": "V1", "math": "V2 + 1"}
+        }
+
+        with pytest.raises(ValueError, match="Circular dependency"):
+            processor.sort_with_initial_assignments([("V2", V1 * 2)], initial_assignments)
+
     def test_get_assigned_variables(self, processor):
         """Test getting set of assigned variables"""
         assignment_rules = {
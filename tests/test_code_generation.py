@@ -1,8 +1,71 @@
 """Tests for Rust code generation functionality"""
 
+import pytest
 import sympy
-from codegen.code_generator import RustBlockGenerator
-from codegen.rust_printer import RustCodeGenerator, CustomRustCodePrinter
+from codegen.code_generator import (
+    DIFFSOL_VERSION, METADATA_ONLY, WASM_EXPORT, RustBlockGenerator, equation_text, live_bindings, parameter_defaults, parameter_range,
+    rust_literal, rust_raw_string, rust_string, symbol_names
+)
+from codegen.rust_printer import RustCodeGenerator, CustomRustCodePrinter, fold_constants
+from parsers import expression_parser
+from parsers.expression_parser import SbmlExpressionParser
+
+TIME = '<csymbol encoding="text" definitionURL="http://www.sbml.org/sbml/symbols/time"> time </csymbol>'
+
+
+def mathml(body):
+    """Wrap a MathML body in its math element"""
+    return f'<math xmlns="http://www.w3.org/1998/Math/MathML">{body}</math>'
+
+
+# Piecewise trees as MathML, as the same formula and as the Rust they generate
+PIECEWISE_FIXTURES = [
+    (
+        mathml(
+            f"<piecewise><piece><ci>k1</ci><apply><lt/>{TIME}<cn>1</cn></apply></piece>"
+            f"<piece><ci>k2</ci><apply><lt/>{TIME}<cn>4</cn></apply></piece>"
+            "<otherwise><ci>k3</ci></otherwise></piecewise>"
+        ),
+        "piecewise(k1, lt(time, 1), k2, lt(time, 4), k3)",
+        "if t < 1.0 {\n    k1\n} else if t < 4.0 {\n    k2\n} else {\n    k3\n}",
+    ),
+    (
+        mathml(
+            "<piecewise><piece><piecewise><piece><ci>k1</ci><apply><gt/><ci>A</ci><cn>0.5</cn></apply></piece>"
+            f"<otherwise><ci>k2</ci></otherwise></piecewise><apply><lt/>{TIME}<cn>4</cn></apply></piece>"
+            "<otherwise><ci>k3</ci></otherwise></piecewise>"
+        ),
+        "piecewise(piecewise(k1, gt(A, 0.5), k2), lt(time, 4), k3)",
+        "if t < 4.0 {\n    if A > 0.5 {\n        k1\n    } else {\n        k2\n    }\n} else {\n    k3\n}",
+    ),
+    (
+        # No otherwise: undefined, so NaN, when no condition holds
+        mathml(f"<piecewise><piece><ci>k1</ci><apply><and/><apply><geq/>{TIME}<cn>1</cn></apply>"
+               "<apply><lt/><ci>A</ci><cn>2</cn></apply></apply></piece></piecewise>"),
+        "piecewise(k1, and(geq(time, 1), lt(A, 2)))",
+        "if t >= 1.0 && A < 2.0 {\n    k1\n} else {\n    f64::NAN\n}",
+    ),
+    (
+        mathml(f"<apply><times/><ci>k1</ci><piecewise><piece><cn>1</cn><apply><lt/>{TIME}<cn>2</cn></apply></piece>"
+               "<otherwise><cn>0</cn></otherwise></piecewise></apply>"),
+        "k1*piecewise(1, lt(time, 2), 0)",
+        "k1*(if t < 2.0 {\n    1.0\n} else {\n    0.0\n})",
+    ),
+    (
+        mathml("<piecewise><piece><ci>k1</ci><apply><not/><apply><leq/><ci>A</ci><cn>1</cn></apply></apply></piece>"
+               f"<piece><ci>k2</ci><apply><xor/><apply><lt/>{TIME}<cn>2</cn></apply>"
+               "<apply><gt/><ci>A</ci><cn>3</cn></apply></apply></piece><otherwise><ci>k3</ci></otherwise></piecewise>"),
+        "piecewise(k1, not(leq(A, 1)), k2, xor(lt(time, 2), gt(A, 3)), k3)",
+        "if A > 1.0 {\n    k1\n} else if (t >= 2.0 || A <= 3.0) && (A > 3.0 || t < 2.0) {\n    k2\n} else {\n    k3\n}",
+    ),
+]
+
+
+def piecewise_parser():
+    """An expression parser over the symbols of the piecewise fixtures"""
+    context = {name: sympy.Symbol(name) for name in ("A", "k1", "k2", "k3", "t")}
+    context["time"] = context["t"]
+    return SbmlExpressionParser(context, {})
 
 
 class TestRustCodeGenerator:
@@ -55,6 +118,20 @@ class TestRustCodeGenerator:
         assert "if" in result
         assert "else" in result
 
+    @pytest.mark.parametrize("_, formula, code", PIECEWISE_FIXTURES)
+    def test_generate_piecewise_tree(self, _, formula, code):
+        """Test that piecewise trees become if / else if / else chains"""
+        assert RustCodeGenerator().generate(piecewise_parser().parse(formula)) == code
+
+    @pytest.mark.parametrize("document, formula, _", PIECEWISE_FIXTURES)
+    def test_generate_piecewise_tree_from_mathml(self, document, formula, _):
+        """Test that piecewise MathML generates the same Rust as its formula"""
+        if not (expression_parser.SBMLMATH_AVAILABLE or expression_parser.LIBSBML_AVAILABLE):
+            pytest.skip("MathML parsing needs sbmlmath or libsbml")
+        generator = RustCodeGenerator()
+        parser = piecewise_parser()
+        assert generator.generate(parser.parse(document)) == generator.generate(parser.parse(formula))
+
     def test_generate_integer_as_float(self):
         """Test that integers are generated as floats"""
         generator = RustCodeGenerator()
@@ -64,14 +141,39 @@ class TestRustCodeGenerator:
         assert result == "5.0"
 
     def test_generate_rational(self):
-        """Test generating Rust code for rational numbers"""
+        """Test that rational numbers are emitted as a single literal"""
         generator = RustCodeGenerator()
         expr = sympy.Rational(1, 2)
 
         result = generator.generate(expr)
-        assert "/" in result
-        assert "1" in result
-        assert "2" in result
+        assert result == "0.5"
+
+    def test_fold_numeric_subexpressions(self):
+        """Test that literal arithmetic is folded and unit factors dropped"""
+        generator = RustCodeGenerator()
+        bw, a, b = sympy.symbols("BW a b")
+        unit = sympy.Pow(sympy.Float(1.0), -1, evaluate=False)
+        coeff = sympy.Mul(sympy.Rational(1, 1000000), sympy.Rational(3229, 25), evaluate=False)
+
+        assert generator.generate((bw * unit) ** sympy.Float(0.75)) == "BW.powf(0.75)"
+        assert generator.generate(coeff * a) == "0.00012916*a"
+        assert generator.generate(sympy.Float(1.0) * a - sympy.Float(1.0) * b) == "a - b"
+        assert generator.generate(sympy.Mul(2, 3, a, evaluate=False)) == "6.0*a"
+
+    def test_folded_code_matches_original_values(self):
+        """Golden check: folded expressions agree with the originals within 1e-12"""
+        bw, a = sympy.symbols("BW a")
+        exprs = [
+            (bw * sympy.Pow(sympy.Float(1.0), -1, evaluate=False)) ** sympy.Float(0.75),
+            sympy.Mul(sympy.Rational(1, 1000000), sympy.Rational(3229, 25), a, evaluate=False),
+            a - sympy.Rational(1, 10) + sympy.sqrt(2) * bw,
+        ]
+        values = {bw: 70.0, a: 3.5}
+
+        for expr in exprs:
+            folded = fold_constants(expr)
+            original = float(expr.subs(values))
+            assert abs(float(folded.subs(values)) - original) <= 1e-12 * max(1.0, abs(original))
 
     def test_generate_complex_expression(self):
         """Test generating Rust code for complex expression"""
@@ -143,6 +245,24 @@ class TestCustomRustCodePrinter:
         assert "if" in result
         assert "else" in result
 
+    def test_print_piecewise_operands(self):
+        """Test that piecewise operands are parenthesized and compared without casts"""
+        printer = CustomRustCodePrinter()
+        x, t = sympy.symbols("x t")
+        pw = sympy.Piecewise((1, t < 2), (3, True))
+        assert printer.doprint(pw > x) == "(if t < 2.0 {\n    1.0\n} else {\n    3.0\n}) > x"
+        assert printer.doprint(pw * x).startswith("x*(if t < 2.0 {")
+        assert printer.doprint(sympy.Pow(pw, sympy.Float(0.5), evaluate=False)).endswith("}).powf(0.5)")
+
+    def test_print_rounding_and_extrema(self):
+        """Test that floor, ceil, max and min print as f64 calls"""
+        printer = CustomRustCodePrinter()
+        x, y = sympy.symbols("x y")
+
+        assert printer.doprint(sympy.floor(x)) == "f64::floor(x)"
+        assert printer.doprint(sympy.ceiling(x)) == "f64::ceil(x)"
+        assert printer.doprint(sympy.Min(sympy.Max(x, 0), y)) == "f64::min(y, f64::max(0.0, x))"
+
     def test_print_mul_with_add_parentheses(self):
         """Test that Add expressions in Mul get parentheses"""
         printer = CustomRustCodePrinter()
@@ -153,6 +273,15 @@ class TestCustomRustCodePrinter:
         # The addition should be in parentheses
         assert "(" in result and ")" in result
 
+    def test_print_mul_with_unit_float_coefficient(self):
+        """Test that dropping a 1.0 or -1.0 coefficient keeps a sum factor in parentheses"""
+        printer = CustomRustCodePrinter()
+        vmax, x2, x3 = sympy.symbols("Vmax x2 x3")
+        # Michaelis-Menten clearance dV/dT after CSE, with the 1.0 of a float stoichiometry
+        expr = sympy.Float(1.0) * vmax * x2 * (x3 - 1)
+        assert printer.doprint(expr) == "Vmax*x2*(x3 - 1.0)"
+        assert printer.doprint(sympy.Float(-1.0) * vmax * (x2 + x3)) == "-Vmax*(x2 + x3)"
+
 
 class TestRustBlockGenerator:
     """Tests for RustBlockGenerator class"""
@@ -197,6 +326,17 @@ class TestRustBlockGenerator:
         assert "jv[1]" in result
         assert "+=" in result
 
+    def test_generate_jacobian_values(self):
+        """Test generating the Jacobian entries and their pattern"""
+        generator = RustBlockGenerator()
+        jac_elements = [sympy.Symbol("k1"), -sympy.Symbol("k2")]
+
+        result = generator.generate_jacobian_values(jac_elements)
+        assert "        values[0] = (k1);" in result
+        assert "        values[1] = (-k2);" in result
+        pattern = generator.generate_jacobian_pattern([(0, 0), (1, 1)])
+        assert pattern == "const JACOBIAN_PATTERN: &[(usize, usize)] = &[\n    (0, 0), (1, 1),\n];"
+
     def test_generate_species_extraction(self):
         """Test generating species extraction code"""
         generator = RustBlockGenerator()
@@ -219,32 +359,394 @@ class TestRustBlockGenerator:
         assert "comp1" in result
         assert "sim_params" in result
 
-    def test_generate_result_vectors_init(self):
-        """Test generating result vector initialization"""
+    def test_parameter_range(self):
+        """Test inferring physical ranges from SBML ids, names and units"""
+        assert parameter_range("HCT") == "BelowOne"
+        assert parameter_range("fSA_exposed", None, "UNITLESS") == "Fraction"
+        assert parameter_range("fu_p") == "Fraction"
+        assert parameter_range("F_abs") == "Fraction"
+        # A volume named as a fraction of body weight is a fraction, not a weight
+        assert parameter_range(
+            "scVFat", "fat volume as fraction of total body weight", "L_PER_KiloGM"
+        ) == "Fraction"
+        assert parameter_range("BM", "body weight", "KiloGM") == "Positive"
+        assert parameter_range("Body_Weight") == "Positive"
+        assert parameter_range("CLH", "hepatic clearance rate", "L_PER_HR") == "NonNegative"
+        assert parameter_range("Q_ad") == "NonNegative"
+        assert parameter_range("V_epi") == "NonNegative"
+        assert parameter_range("Vmax") is None
+        assert parameter_range("PCFat", "partition coefficient fat over blood", "UNITLESS") is None
+
+    def test_generate_parameter_checks(self):
+        """Test generating the table of parameter range checks"""
+        generator = RustBlockGenerator()
+        result = generator.generate_parameter_checks([
+            ("fSA_exposed", 3, "Fraction", []),
+            ("Poor", 12, "NonNegative", ["BM", "scVFat"]),
+        ])
+        assert "const PARAMETER_CHECKS: &[ParameterCheck] = &[" in result
+        assert '    ("fSA_exposed", 3, ParameterRange::Fraction, &[]),' in result
+        assert '    ("Poor", 12, ParameterRange::NonNegative, &["BM", "scVFat"]),' in result
+
+    def test_generate_mass_function(self):
+        """Test generating the closure summing the mass of the states"""
+        generator = RustBlockGenerator()
+        V, Mr = sympy.symbols("V Mr")
+
+        result = generator.generate_mass_function(
+            {"A": 0, "B": 1, "C": 2}, {"A": V * Mr, "C": sympy.Integer(1)}, {"V": 0, "Mr": 1, "k": 2}
+        )
+        assert "let eval_mass = |y: &[f64], p: &[f64]| -> f64 {" in result
+        assert "        let V = p[0];" in result
+        assert "        let Mr = p[1];" in result
+        assert "let k = " not in result
+        assert "y[0] * (" in result
+        assert "y[1]" not in result
+        assert result.count(" + y[2]") == 1
+
+        empty = generator.generate_mass_function({"A": 0}, {}, {})
+        assert "|_y: &[f64], _p: &[f64]| -> f64 {" in empty
+        assert "        0.0" in empty
+
+    def test_generate_observables_function(self):
+        """Test generating the assignment-rule observables closure"""
+        generator = RustBlockGenerator()
+        W, B, V = sympy.symbols("W B V")
+        rules = [("W", V * 2), ("Conc", B / W)]
+
+        result = generator.generate_observables_function(
+            {"A": 0, "B": 1}, rules, rules[1:], {"V": 0, "W": 1}
+        )
+        assert "let eval_observables = |y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {" in result
+        assert "        let B = y[1];" in result
+        assert "let A = " not in result
+        assert "        let Conc = " in result
+        # Parameter-only rules are read from the parameter vector
+        assert "        let W = p[1];" in result
+        assert "let V = " not in result
+        assert "vec![W, Conc]" in result
+
+    def test_generate_observable_ids(self):
+        """Test that the observable ids follow the order of the closure's values"""
+        generator = RustBlockGenerator()
+        W, B = sympy.symbols("W B")
+        result = generator.generate_observable_ids([("W", B * 2), ("Conc", B / W)])
+        assert 'const OBSERVABLE_IDS: [&str; 2] = ["W", "Conc"];' in result
+        assert "const OBSERVABLE_IDS: [&str; 0] = [];" in generator.generate_observable_ids([])
+
+    def test_generate_volume_function(self):
+        """Test generating the closure giving each species' compartment size"""
+        generator = RustBlockGenerator()
+        BM, scV = sympy.symbols("BM scV")
+
+        result = generator.generate_volume_function(
+            {"A": 0, "B": 1, "C": 2}, {"A": "plasma", "B": "Fat", "C": None},
+            [("Fat", BM * scV)], {"plasma": 0, "BM": 1, "scV": 2}
+        )
+        assert "let eval_volumes = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {" in result
+        assert "        let plasma = p[0];" in result
+        assert "        let BM = p[1];" in result
+        assert "        let Fat = " in result
+        assert "vec![plasma, Fat, f64::NAN]" in result
+
+    def test_live_bindings(self):
+        """Test that only bindings read by the body (directly or not) are kept"""
+        x0, x1, x2, a = sympy.symbols("x0 x1 x2 A")
+        bindings = [("A", None), ("B", None), (x0, a * 2), (x1, x0 + 1), (x2, a * 3)]
+
+        kept, used = live_bindings(bindings, symbol_names([x1 * 4]))
+        assert kept == [("A", None), (x0, a * 2), (x1, x0 + 1)]
+        assert "B" not in used
+
+    def test_generate_species_extraction_used_only(self):
+        """Test that species not read by the closure are not bound"""
+        generator = RustBlockGenerator()
+        result = generator.generate_species_extraction({"A": 0, "B": 1}, {"B"})
+        assert result == "        let B = y[1];"
+
+    def test_generate_initial_values_initial_expressions(self):
+        """Test that initially-assigned states start from their binding"""
+        generator = RustBlockGenerator()
+
+        result = generator.generate_initial_values(
+            ["Ast", "Cve", "IVDOSE"], {"Ast": 0.0, "Cve": 1.5, "IVDOSE": 0.0},
+            {"Ast": "Ast", "IVDOSE": "IVDOSE"}
+        )
+        assert result == [
+            "sim_params.init_Ast.unwrap_or(Ast)",
+            "sim_params.init_Cve.unwrap_or(1.5)",
+            "sim_params.init_IVDOSE.unwrap_or(IVDOSE)",
+        ]
+
+    def test_generate_init_function_reads_parameter_vector(self):
+        """Test that the initial state is copied from the tail of p"""
+        generator = RustBlockGenerator()
+        result = generator.generate_init_function(3, 5)
+        assert "    fn init(p: &[f64], _t: f64," in result
+        assert "y.copy_from_slice(&p[5..8]);" in result
+
+    def test_generate_parameter_lookups(self):
+        """Test that closures bind only the parameters they read, in index order"""
+        generator = RustBlockGenerator()
+        result = generator.generate_parameter_lookups({"k1": 0, "V": 1, "k2": 2}, {"k2", "k1", "A"})
+        assert result == "        let k1 = p[0];\n        let k2 = p[2];"
+
+    def test_parameter_defaults_table(self):
+        """Test that the defaults table skips duplicates and fills unset values"""
+        table = parameter_defaults({"k1": 1, "V": None}, {"V": 2.0, "comp1": 1.5})
+        assert table == [("k1", 1.0), ("V", 0.0), ("comp1", 1.5)]
+
+    def test_metadata_functions_share_defaults(self):
+        """Test that parameter info and defaults are generated from one table"""
+        generator = RustBlockGenerator()
+        result = generator.generate_metadata_functions(
+            "test_model", ["A"], {"A": 1.0}, {"k1": 2, "k2": None}, {"comp1": 1.0}
+        )
+        assert 'id: "k1", name: None, units: None, description: None, default_value: 2.0,' in result
+        assert '("k1", 2.0),' in result
+        assert '("k2", 0.0),' in result
+        assert "num_parameters: 3," in result
+        assert "null" not in result
+
+    def test_minimal_feature_gates_metadata(self):
+        """Test that WASM metadata exports and their tables are left out by the minimal feature"""
+        generator = RustBlockGenerator()
+        result = generator.generate_metadata_functions(
+            "test_model", ["A"], {"A": 1.0}, {"k1": 2}, {"comp1": 1.0}, True
+        )
+        for name in ["get_model_metadata", "get_parameters_info", "get_species_info", "get_compartments_info"]:
+            assert f'{METADATA_ONLY}{WASM_EXPORT}pub fn {name}() -> String {{' in result
+        assert f"{METADATA_ONLY}const PARAMETER_INFO" in result
+        assert f"{METADATA_ONLY}#[derive(Serialize)]\nstruct SpeciesInfo {{" in result
+        # run_simulation merges parameters over the defaults, so they stay
+        assert f"\n{WASM_EXPORT}pub fn get_default_parameters() -> String {{" in result
+        sbml = generator.generate_sbml_function("<sbml/>", "not embedded", wasm=True)
+        assert f"{METADATA_ONLY}const MODEL_SBML" in sbml
+        assert f"{METADATA_ONLY}{WASM_EXPORT}pub fn get_model_sbml()" in sbml
+        native = generator.generate_metadata_functions("test_model", ["A"], {"A": 1.0}, {"k1": 2}, {"comp1": 1.0})
+        assert "minimal" not in native
+
+    def test_parameters_info_lists_sbml_metadata(self):
+        """Test that get_parameters_info carries names, units, notes and soft bounds"""
         generator = RustBlockGenerator()
-        species_list = ["A", "B", "C"]
+        result = generator.generate_metadata_functions(
+            "test_model", ["A"], {"A": 1.0}, {"fA": 0.5}, {"comp1": 1.0}, False, None,
+            {
+                "fA": {"name": "absorbed fraction", "units": "dimensionless",
+                       "description": 'the "oral" fraction', "min": 0.0, "max": 1.0},
+                "comp1": {"min": 0.0},
+            }
+        )
+        assert "struct ParameterInfo {" in result
+        assert (
+            'ParameterInfo { id: "fA", name: Some("absorbed fraction"), units: Some("dimensionless"), '
+            'description: Some("the \\"oral\\" fraction"), default_value: 0.5, required: true, '
+            "is_compartment: false, min: Some(0.0), max: Some(1.0) }," in result
+        )
+        assert (
+            'ParameterInfo { id: "comp1", name: None, units: None, description: None, default_value: 1.0, '
+            "required: true, is_compartment: true, min: Some(0.0), max: None }," in result
+        )
+        assert "serde_json::to_string(PARAMETER_INFO).unwrap()" in result
 
-        result = generator.generate_result_vectors_init(species_list)
-        assert "Vec::new()" in result
-        assert "mut" in result
+    def test_species_info_lists_compartments(self):
+        """Test that get_species_info reads compartment sizes from the default parameter vector"""
+        generator = RustBlockGenerator()
+        result = generator.generate_metadata_functions(
+            "test_model", ["Cplasma", "Aurine", "Dose"], {"Cplasma": 0.0, "Aurine": 0.0, "Dose": 5.0},
+            {"k1": 1.0}, {"plasma": 3.0}, False, None, None,
+            {
+                "Cplasma": {"compartment": "plasma", "volume_index": 1, "is_amount": False, "is_boundary": False},
+                "Aurine": {"compartment": "urine", "volume_index": None, "is_amount": True, "is_boundary": True},
+            }
+        )
+        species_fn = result.split("pub fn get_species_info()")[1]
+        assert "let p = parameter_vector(&TestModel::default_params());" in species_fn
+        assert (
+            'compartment: Some("plasma"), compartment_volume: Some(p[1]), is_amount: false, is_boundary: false },'
+            in species_fn
+        )
+        assert 'compartment: Some("urine"), compartment_volume: None, is_amount: true, is_boundary: true },' in species_fn
+        # A rate-rule parameter state has no compartment
+        assert 'id: "Dose", initial_amount: 5.0, override_field: "init_Dose", units: None, compartment: None,' in species_fn
+        assert '#[serde(rename = "override")]' in result
 
-    def test_generate_result_pushes(self):
-        """Test generating result push statements"""
+    def test_series_units_follow_species_info(self):
+        """Test that SERIES_UNITS lists the species units of get_species_info, then the observables"""
         generator = RustBlockGenerator()
-        species_list = ["A", "B"]
+        result = generator.generate_metadata_functions(
+            "test_model", ["Cplasma", "Dose"], {"Cplasma": 0.0, "Dose": 5.0},
+            {"k1": 1.0}, {"plasma": 3.0}, False, {"cplasma_mg": "mg"}, None,
+            {"Cplasma": {"compartment": "plasma", "volume_index": 1, "units": "mmole/litre"}}
+        )
+        assert 'override_field: "init_Cplasma", units: Some("mmole/litre"),' in result
+        assert "const SERIES_UNITS: [(&str, Option<&str>); 3] = [" in result
+        series = result.split("const SERIES_UNITS")[1].split("];")[0]
+        assert series.index('("Cplasma", Some("mmole/litre")),') < series.index('("Dose", None),')
+        assert '("cplasma_mg", Some("mg")),' in series
+
+    def test_species_volumes_say_why_not(self):
+        """Test that boundary species and species without a compartment are not converted"""
+        generator = RustBlockGenerator()
+        result = generator.generate_metadata_functions(
+            "test_model", ["Cplasma", "Cin", "Dose"], {}, {"k1": 1.0}, {"plasma": 3.0}, False, None, None,
+            {
+                "Cplasma": {"compartment": "plasma", "is_amount": False},
+                "Cin": {"compartment": "plasma", "is_amount": False, "is_boundary": True},
+            }
+        )
+        assert "const SPECIES_IS_AMOUNT: [bool; 3] = [false, false, true];" in result
+        assert (
+            'const SPECIES_VOLUMES: [Result<&str, &str>; 3] = '
+            '[Ok("plasma"), Err("a boundary species"), Err("no compartment")];' in result
+        )
+
+    def test_compartments_info_lists_sizes_and_species(self):
+        """Test that get_compartments_info reads sizes from p and lists each compartment's species"""
+        generator = RustBlockGenerator()
+        result = generator.generate_metadata_functions(
+            "test_model", ["Agut", "Cplasma"], {"Agut": 1.0, "Cplasma": 0.0},
+            {"k1": 1.0}, {"gut": 0.5, "plasma": 3.0}, False, None, None, None,
+            {
+                "gut": {"name": "gut lumen", "units": "litre", "size_index": 1, "species": ["Agut"]},
+                "plasma": {"size_index": 2, "formula": "BW*fV", "species": ["Cplasma"]},
+            }
+        )
+        compartments_fn = result.split("pub fn get_compartments_info()")[1]
+        assert "let p = parameter_vector(&TestModel::default_params());" in compartments_fn
+        assert (
+            'CompartmentInfo { id: "gut", name: Some("gut lumen"), size: Some(p[1]), formula: None, '
+            'units: Some("litre"), species: &["Agut"] },' in compartments_fn
+        )
+        assert (
+            'CompartmentInfo { id: "plasma", name: None, size: Some(p[2]), formula: Some("BW*fV"), '
+            'units: None, species: &["Cplasma"] },' in compartments_fn
+        )
 
-        result = generator.generate_result_pushes(species_list)
-        assert ".push" in result
-        assert "solver.state().y" in result
+    def test_compartments_info_defaults_to_compartment_fields(self):
+        """Test that get_compartments_info lists compartments even without details"""
+        generator = RustBlockGenerator()
+        result = generator.generate_metadata_functions(
+            "test_model", ["A"], {"A": 1.0}, {"k1": 1.0}, {"V": 2.0}
+        )
+        assert (
+            'CompartmentInfo { id: "V", name: None, size: None, formula: None, units: None, species: &[] },'
+            in result
+        )
 
-    def test_generate_hashmap_inserts(self):
-        """Test generating HashMap insert statements"""
+    def test_equations_function_lists_derivatives_and_rules(self):
+        """Test that get_model_equations lists each derivative and rule in model order"""
+        A, B, k1, V, BW, fV = sympy.symbols("A B k1 V BW fV")
+        generator = RustBlockGenerator()
+        result = generator.generate_equations_function(
+            ["B", "A"], [1.0 * k1 * A / V, -1.0 * k1 * A / V], [("V", BW * fV)], wasm=True
+        )
+        assert WASM_EXPORT + "pub fn get_model_equations() -> String {" in result
+        derivatives = result.split("derivatives: EquationTable(&[")[1].split("]),")[0]
+        assert [line.strip() for line in derivatives.strip().splitlines()] == ['("B", "A*k1/V"),', '("A", "-A*k1/V"),']
+        assert '("V", "BW*fV"),' in result.split("assignment_rules: EquationTable(&[")[1]
+        assert "serde_json::to_string_pretty(&equations).unwrap()" in result
+
+    def test_equations_function_lists_algebraic_rules(self):
+        """Test that algebraic rows are listed apart from the derivatives"""
+        A, B, C, T, k1 = sympy.symbols("A B C T k1")
+        generator = RustBlockGenerator()
+        result = generator.generate_equations_function(
+            ["A", "B", "C"], [-k1 * A, k1 * A, A + B + C - T], [], algebraic_states=["C"]
+        )
+        derivatives = result.split("derivatives: EquationTable(&[")[1].split("]),")[0]
+        assert '("C"' not in derivatives
+        assert '("C", "A + B + C - T"),' in result.split("algebraic_rules: EquationTable(&[")[1]
+        # Models without algebraic rules keep the table out
+        assert "algebraic_rules" not in generator.generate_equations_function(["A"], [-k1 * A], [])
+
+    def test_equation_text(self):
+        """Test that equation text drops whole-number float coefficients only"""
+        A, k1, Vd = sympy.symbols("A k1 Vd")
+        assert equation_text(-1.0 * k1 * A + 2.0 * A) == "-A*k1 + 2*A"
+        assert equation_text(0.5 * A / Vd) == "0.5*A/Vd"
+        # Equal expressions give the same text whatever order they were built in
+        assert equation_text(k1 * A - Vd) == equation_text(-Vd + A * k1)
+
+    def test_sbml_function_embeds_document(self):
+        """Test that get_model_sbml returns the document verbatim, or the reason it is missing"""
+        generator = RustBlockGenerator()
+        document = '<?xml version="1.0"?>\n<sbml level="3"><!-- "# --></sbml>\n'
+        result = generator.generate_sbml_function(document, "unused", wasm=True)
+        assert f'const MODEL_SBML: &str = r##"{document}"##;' in result
+        assert WASM_EXPORT + "pub fn get_model_sbml() -> Result<String, String> {" in result
+        assert "    Ok(MODEL_SBML.to_string())" in result
+
+        result = generator.generate_sbml_function(None, "not embedded")
+        assert "MODEL_SBML" not in result
+        assert '    Err("not embedded".to_string())' in result
+
+    def test_rust_raw_string(self):
+        """Test that raw strings get more hashes than any quote-hash run inside"""
+        assert rust_raw_string("plain") == 'r#"plain"#'
+        assert rust_raw_string('a "b" "## c') == 'r###"a "b" "## c"###'
+
+    def test_rust_literal(self):
+        """Test that text is written as an escaped Rust string literal"""
+        assert rust_literal('Piecewise((0, t < "t0"))') == '"Piecewise((0, t < \\"t0\\"))"'
+
+    def test_rust_string(self):
+        """Test that SBML text is written as an escaped Rust Option<&str>"""
+        assert rust_string(None) == "None"
+        assert rust_string('a "b" \\ c') == 'Some("a \\"b\\" \\\\ c")'
+        assert rust_string("line\nbreak, µg") == 'Some("line\\u{a}break, µg")'
+
+    def test_metadata_lists_observable_units(self):
+        """Test that get_model_metadata lists each observable with its SBML units"""
         generator = RustBlockGenerator()
-        species_list = ["A", "B"]
+        result = generator.generate_metadata_functions(
+            "test_model", ["A"], {"A": 1.0}, {"V": 2.0}, {}, False,
+            {"conc": "MilliMOL_per_L", "ratio": None}
+        )
+        assert 'ObservableInfo { id: "conc", units: Some("MilliMOL_per_L") },' in result
+        assert 'ObservableInfo { id: "ratio", units: None },' in result
 
-        result = generator.generate_hashmap_inserts(species_list)
-        assert "species_map.insert" in result
-        assert ".to_string()" in result
+    def test_metadata_reports_provenance(self):
+        """Test the get_model_metadata body against a snapshot"""
+        generator = RustBlockGenerator()
+        result = generator.generate_metadata_functions(
+            "test_model", ["A"], {"A": 1.0}, {"k1": 2.0}, {"V": 1.0}, True,
+            {"conc": None}, None, None, None,
+            {
+                "sbml": {"id": "pk", "name": "One compartment", "level": 3, "version": 2, "sha256": "ab12"},
+                "generator": {"version": "1.0.0", "generated_at": "2026-01-02T03:04:05Z"},
+                "features": ["events"],
+            }
+        )
+        metadata_fn = result.split(WASM_EXPORT + "pub fn get_model_metadata() -> String {\n")[1].split("\n}\n")[0]
+        assert metadata_fn == "\n".join([
+            "    let metadata = ModelMetadata {",
+            '        model_id: "test_model",',
+            "        num_species: 1,",
+            "        num_parameters: 2,",
+            '        time_units: "HR",',
+            '        substance_units: "MilliMOL",',
+            '        volume_units: "L",',
+            '        sbml: SbmlSource { id: Some("pk"), name: Some("One compartment"), level: Some(3), version: Some(2), '
+            'sha256: Some("ab12") },',
+            '        generator: GeneratorInfo { name: "sbml_rust_generator", version: Some("1.0.0"), '
+            'generated_at: Some("2026-01-02T03:04:05Z") },',
+            f'        diffsol_version: "{DIFFSOL_VERSION}",',
+            '        features: &["events"],',
+            "        observables: &[",
+            '            ObservableInfo { id: "conc", units: None },',
+            "        ],",
+            "    };",
+            "    serde_json::to_string(&metadata).unwrap()",
+        ])
+
+    def test_metadata_provenance_defaults_to_null(self):
+        """Test that a model without provenance still gets every metadata field"""
+        generator = RustBlockGenerator()
+        result = generator.generate_metadata_functions("test_model", ["A"], {"A": 1.0}, {}, {})
+        assert "sbml: SbmlSource { id: None, name: None, level: None, version: None, sha256: None }," in result
+        assert 'generator: GeneratorInfo { name: "sbml_rust_generator", version: None, generated_at: None },' in result
+        assert "features: &[]," in result
 
     def test_generate_assignment_rules(self):
         """Test generating assignment rule code"""
@@ -259,6 +761,20 @@ class TestRustBlockGenerator:
         assert "let V2" in result
         assert "k1" in result or "k2" in result
 
+    def test_generate_window_terms(self):
+        """Test that window terms are summed over the uptake windows"""
+        generator = RustBlockGenerator()
+        k, t0, t1 = sympy.symbols("k t0 t1")
+
+        result = generator.generate_window_terms({"A": k * (t1 - t0)}, ("t0", "t1"))
+        assert result.startswith("        let uptake_A = window_sum(windows, t0, t1, |t0, t1| ")
+
+    def test_generate_state_parameters(self):
+        """Test the table of parameters read by each derivative"""
+        generator = RustBlockGenerator()
+        result = generator.generate_state_parameters([["k1"], [], ["k1", "k2"]])
+        assert 'const STATE_PARAMETERS: [&[&str]; 3] = [&["k1"], &[], &["k1", "k2"]];' in result
+
     def test_generate_empty_assignment_rules(self):
         """Test generating code for empty assignment rules"""
         generator = RustBlockGenerator()
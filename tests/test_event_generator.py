@@ -0,0 +1,139 @@
+"""Tests for SBML event code generation"""
+
+import pytest
+import sympy
+from codegen.code_generator import RustBlockGenerator
+from codegen.event_generator import EventCodeGenerator
+from parsers.expression_parser import SbmlExpressionParser
+
+
+class TestEventCodeGenerator:
+    """Tests for EventCodeGenerator class"""
+
+    @pytest.fixture
+    def event_generator(self):
+        """Create an event generator for testing"""
+        context = {
+            "Gut": sympy.Symbol("Gut"),
+            "Dose": sympy.Symbol("Dose"),
+            "t": sympy.Symbol("t"),
+        }
+        parser = SbmlExpressionParser(context, {})
+        return EventCodeGenerator(RustBlockGenerator().code_gen, parser)
+
+    @pytest.fixture
+    def dose_event(self):
+        """Create a time-triggered dosing event"""
+        return {
+            "dose": {
+                "trigger": "t >= 0.001",
+                "eventAssignments": [{"variable": "Gut", "math": "Gut + Dose"}],
+            }
+        }
+
+    def test_time_trigger_becomes_continuous_root(self, event_generator, dose_event):
+        """Test that t >= t0 is translated into the root t - t0"""
+        result = event_generator.generate_event_handling(dose_event, {"Gut": 0})
+        assert "roots[0] = (t - 0.001);" in result["root_fn"]
+        assert "parse error" not in result["root_fn"]
+
+    def test_compound_trigger_root(self, event_generator):
+        """Test that and/or triggers combine their roots with min/max"""
+        events = {"e1": {"trigger": "(t >= 2) & (Gut < 1)", "eventAssignments": []}}
+        result = event_generator.generate_event_handling(events, {"Gut": 0})
+        assert "let Gut = y[0];" in result["root_fn"]
+        assert ".min(" in result["root_fn"]
+
+    def test_negated_and_exclusive_trigger_roots(self, event_generator):
+        """Test that not negates a root and xor becomes its and/or combination"""
+        events = {
+            "e1": {"trigger": "not(and(geq(t, 2), lt(Gut, 1)))", "eventAssignments": []},
+            "e2": {"trigger": "xor(geq(t, 2), lt(Gut, 1))", "eventAssignments": []},
+        }
+        result = event_generator.generate_event_handling(events, {"Gut": 0})
+        assert "roots[0] = -((t - 2.0).min(1.0 - Gut));" in result["root_fn"]
+        assert "roots[1] = (Gut - 1.0).min(t - 2.0).max((1.0 - Gut).min(2.0 - t));" in result["root_fn"]
+        assert " as i32 as f64" not in result["root_fn"]
+
+    def test_staggered_time_triggers_get_distinct_roots(self, event_generator):
+        """Test that each time-based dose gets its own root, in event order"""
+        events = {
+            f"dose{i}": {"trigger": f"t >= {time}", "eventAssignments": [{"variable": "Gut", "math": "Gut + Dose"}]}
+            for i, time in enumerate((1, 4, 8))
+        }
+        result = event_generator.generate_event_handling(events, {"Gut": 0})
+        for i, time in enumerate((1, 4, 8)):
+            assert f"        roots[{i}] = (t - {time}.0);" in result["root_fn"]
+            assert f"        if fire[{i}] {{" in result["event_fns"]
+        assert 'const EVENT_IDS: [&str; 3] = ["dose0", "dose1", "dose2"];' in result["event_consts"]
+
+    def test_species_assignment_closure(self, event_generator, dose_event):
+        """Test that species assignments are applied by apply_events"""
+        result = event_generator.generate_event_handling(dose_event, {"Gut": 0})
+        event_fns = result["event_fns"]
+        assert "let apply_events = |y: &[f64], _t: f64, fire: &[bool], _p: &[f64]| -> Vec<f64> {" in event_fns
+        assert "        if fire[0] {" in event_fns
+        assert "            y_new[0] = Dose + Gut;" in event_fns
+        assert "console_log!" not in event_fns
+
+    def test_event_without_assignments(self, event_generator):
+        """Test that an event with no assignments leaves the state unchanged"""
+        events = {"restart": {"trigger": "t >= 2", "eventAssignments": []}}
+        result = event_generator.generate_event_handling(events, {"Gut": 0})
+        event_fns = result["event_fns"]
+        assert "_fire: &[bool]" in event_fns
+        assert "if fire[" not in event_fns
+        assert "        y.to_vec()\n" in event_fns
+
+    def test_event_log_constants(self, event_generator, dose_event):
+        """Test that the event IDs and assigned states are listed for the event log"""
+        events = dict(dose_event, restart={"trigger": "t >= 2", "eventAssignments": []})
+        result = event_generator.generate_event_handling(events, {"Gut": 0})
+        assert 'const EVENT_IDS: [&str; 2] = ["dose", "restart"];' in result["event_consts"]
+        assert "const EVENT_TARGETS: [&[usize]; 2] = [&[0], &[]];" in result["event_consts"]
+
+    def test_trigger_attributes(self, event_generator, dose_event):
+        """Test that initialValue and persistent are listed, true when not given"""
+        events = dict(
+            dose_event,
+            flicker={"trigger": "t >= 2", "initialValue": False, "persistent": False, "eventAssignments": []},
+        )
+        result = event_generator.generate_event_handling(events, {"Gut": 0})
+        assert "const EVENT_INITIAL_VALUES: [bool; 2] = [true, false];" in result["event_consts"]
+        assert "const EVENT_PERSISTENT: [bool; 2] = [true, false];" in result["event_consts"]
+
+    def test_trigger_closure(self, event_generator, dose_event):
+        """Test that triggers are also emitted as booleans for edge detection"""
+        result = event_generator.generate_event_handling(dose_event, {"Gut": 0})
+        assert "let eval_triggers = |_y: &[f64], t: f64, _p: &[f64]| -> Vec<bool> {" in result["event_fns"]
+        assert "vec![t >= 0.001]" in result["event_fns"]
+
+    def test_closures_bind_only_what_they_read(self, event_generator, dose_event):
+        """Test that unused species and rules are not bound in event closures"""
+        rules = [("Conc", sympy.Symbol("Gut") * 2)]
+        result = event_generator.generate_event_handling(
+            dose_event, {"Gut": 0, "Urine": 1}, rules
+        )
+        assert "let Gut" not in result["root_fn"]
+        assert "let Conc" not in result["event_fns"]
+        assert "let Urine" not in result["event_fns"]
+        assert "        let Gut = y[0];" in result["event_fns"]
+
+    def test_parameters_read_from_parameter_vector(self, event_generator, dose_event):
+        """Test that event closures read parameters from p instead of capturing them"""
+        result = event_generator.generate_event_handling(
+            dose_event, {"Gut": 0}, [], {"ka": 0, "Dose": 1, "Gut": 2}
+        )
+        event_fns = result["event_fns"]
+        assert "fire: &[bool], p: &[f64]|" in event_fns
+        assert "        let Dose = p[1];" in event_fns
+        assert "let ka" not in event_fns
+        # States are never shadowed by a parameter of the same name
+        assert "p[2]" not in event_fns
+        assert "_p: &[f64]" in result["root_fn"]
+
+    def test_untranslatable_trigger_raises(self, event_generator):
+        """Test that a broken trigger fails generation instead of emitting a stub"""
+        events = {"bad": {"trigger": "t >= (", "eventAssignments": []}}
+        with pytest.raises(ValueError, match="bad"):
+            event_generator.generate_event_handling(events, {"Gut": 0})
@@ -2,7 +2,7 @@
 
 import pytest
 import sympy
-from parsers.expression_parser import SbmlExpressionParser
+from parsers.expression_parser import RateOf, SbmlExpressionParser
 
 
 class TestSbmlExpressionParser:
@@ -31,6 +31,12 @@ class TestSbmlExpressionParser:
         # Check that it's equivalent to x/y
         assert expr == sympy.Symbol("x") / sympy.Symbol("y")
 
+    def test_parse_leading_unary_minus(self, expression_parser):
+        """Test that a leading minus sign is kept"""
+        expr = expression_parser.parse("-k1 * x + y")
+        x, y, k1 = sympy.symbols("x y k1")
+        assert expr == -k1 * x + y
+
     def test_parse_power(self, expression_parser):
         """Test parsing power expressions"""
         expr = expression_parser.parse("x**2")
@@ -118,6 +124,39 @@ class TestSbmlExpressionParser:
         expr = expression_parser.parse("and(gt(x, 0), lt(x, 10))")
         assert isinstance(expr, sympy.And)
 
+    def test_parse_not_xor_and_booleans(self, expression_parser):
+        """Test parsing not, xor and the boolean constants in conditions"""
+        x = sympy.Symbol("x")
+        assert expression_parser.parse("not(gt(x, 0))") == (x <= 0)
+        assert isinstance(expression_parser.parse("xor(gt(x, 0), lt(x, 10))"), sympy.Xor)
+        expr = expression_parser.parse("piecewise(1, and(true, gt(x, 0)), 2)")
+        assert expr == sympy.Piecewise((1, x > 0), (2, True))
+
+    def test_parse_rate_of(self, expression_parser):
+        """Test that rateOf parses to the function the ODE builder resolves"""
+        expr = expression_parser.parse("2 * rateOf(x)")
+        assert expr == 2 * RateOf(sympy.Symbol("x"))
+
+    def test_parse_rounding_and_extrema(self, expression_parser):
+        """Test parsing floor, ceil, max and min"""
+        x, y = sympy.symbols("x y")
+        expr = expression_parser.parse("floor(x / y)")
+        assert isinstance(expr, sympy.floor)
+        assert expr.args == (x / y,)
+
+        assert isinstance(expression_parser.parse("ceil(x)"), sympy.ceiling)
+        assert expression_parser.parse("max(x, y)") == sympy.Max(x, y)
+
+        expr = expression_parser.parse("min(floor(x), y)")
+        assert isinstance(expr, sympy.Min)
+        assert y in expr.args
+
+    def test_rounding_derivative_is_zero(self, expression_parser):
+        """Test that floor and ceil differentiate to zero"""
+        x = sympy.Symbol("x")
+        expr = expression_parser.parse("x * floor(x) + ceil(x)")
+        assert sympy.diff(expr, x) == expression_parser.parse("floor(x)")
+
     def test_parse_with_custom_functions(self):
         """Test parsing expressions with custom function definitions"""
         context = {
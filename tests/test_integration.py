@@ -2,10 +2,16 @@
 
 """Integration tests for the complete SBML to Rust conversion pipeline"""
 
+import ctypes
+import hashlib
+import json
 import pytest
 import sympy
 import tempfile
 import os
+import re
+import shutil
+import subprocess
 import sys
 from pathlib import Path
 
@@ -15,6 +21,7 @@ sys.path.insert(0, str(parent_dir))
 
 import libsbml
 from sbmlParser.parser import ParseSBMLFile
+from codegen.code_generator import METADATA_ONLY, WASM_EXPORT, rust_raw_string
 
 # Import facade as a package module to handle relative imports
 # We need to treat the parent directory as a package
@@ -39,6 +46,79 @@ except (ImportError, ModuleNotFoundError):
     pytest.skip("Cannot import SbmlToRustConverter due to package structure")
 
 
+DATA_DIR = parent_dir / "data"
+
+# Dependencies of the scratch crate used to compile generated modules
+CHECK_CRATE_MANIFEST = f"""[package]
+name = "generated_model_check"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+diffsol = "0.6.3"
+serde = {{ version = "1.0", features = ["derive"] }}
+serde_json = "1.0"
+wasm_pk_core = {{ path = "{(parent_dir / 'wasm_pk_core').as_posix()}" }}
+"""
+
+# Native shared library or WASI module of a WASM build: the c-api or wasi
+# feature without the JS glue. `wasm` is declared so the code's
+# cfg(feature = "wasm") is known, but stays off
+C_API_CRATE_MANIFEST = CHECK_CRATE_MANIFEST + """
+[lib]
+crate-type = ["cdylib", "rlib"]
+
+[features]
+wasm = []
+c-api = []
+python = []
+wasi = []
+minimal = []
+"""
+
+# Python extension of a WASM build, loaded without maturin
+PYTHON_CRATE_MANIFEST = CHECK_CRATE_MANIFEST + """
+[dependencies.pyo3]
+version = "0.23"
+features = ["extension-module"]
+optional = true
+
+[lib]
+crate-type = ["cdylib", "rlib"]
+
+[features]
+wasm = []
+c-api = []
+python = ["dep:pyo3"]
+wasi = []
+minimal = []
+"""
+
+# Default WASM build as build_wasm.sh sets it up, plus a native runner binary:
+# the JS glue and its dependencies only exist on wasm32
+RUNNER_CRATE_MANIFEST = CHECK_CRATE_MANIFEST + """
+[target.'cfg(target_arch = "wasm32")'.dependencies]
+wasm-bindgen = { version = "0.2", optional = true }
+wasm-bindgen-futures = { version = "0.4", optional = true }
+js-sys = { version = "0.3", optional = true }
+web-sys = { version = "0.3", features = ["Performance"], optional = true }
+serde-wasm-bindgen = { version = "0.6", optional = true }
+getrandom = { version = "0.2", features = ["js"], optional = true }
+console_error_panic_hook = { version = "0.1", optional = true }
+
+[lib]
+crate-type = ["cdylib", "rlib"]
+
+[features]
+default = ["wasm"]
+wasm = ["dep:wasm-bindgen", "dep:wasm-bindgen-futures", "dep:js-sys", "dep:web-sys", "dep:serde-wasm-bindgen", "dep:getrandom", "dep:console_error_panic_hook", "wasm_pk_core/wasm"]
+c-api = []
+python = []
+wasi = []
+minimal = []
+"""
+
+
 class TestIntegration:
     """Integration tests for the full conversion pipeline"""
 
@@ -118,6 +198,263 @@ class TestIntegration:
             finally:
                 os.unlink(f.name)
 
+    def test_parameters_info_carries_sbml_metadata(self):
+        """Test that SBML names, units and notes reach get_parameters_info"""
+        doc = self.create_simple_sbml_model()
+        model = doc.getModel()
+        k1 = model.getParameter("k1")
+        k1.setName("absorption rate")
+        k1.setUnits("per_h")
+        k1.setNotes(
+            '<body xmlns="http://www.w3.org/1999/xhtml"><p>First-order\n  uptake &amp; transfer</p></body>'
+        )
+        model.getCompartment("comp1").setUnits("litre")
+
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+            try:
+                model_data = ParseSBMLFile(f.name)
+            finally:
+                os.unlink(f.name)
+
+        assert model_data["parameters"]["k1"]["notes"] == "First-order uptake & transfer"
+        rust_code = SbmlToRustConverter(model_data).convert("test_model", wasm=False)
+        assert (
+            'ParameterInfo { id: "k1", name: Some("absorption rate"), units: Some("per_h"), '
+            'description: Some("First-order uptake & transfer"), default_value: 0.5, required: true, '
+            "is_compartment: false, min: None, max: None }," in rust_code
+        )
+        assert (
+            'ParameterInfo { id: "comp1", name: None, units: Some("litre"), description: None, '
+            "default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None }," in rust_code
+        )
+
+    def test_species_info_reports_compartments(self):
+        """Test that get_species_info tells amounts from concentrations, with their compartment"""
+        euromix = SbmlToRustConverter(
+            ParseSBMLFile(str(DATA_DIR / "euromix.sbml"))
+        ).convert("euromix_model", wasm=False)
+        # Euromix compartment sizes are assignment rules, read from p at the defaults
+        assert re.search(
+            r'SpeciesInfo \{ id: "QFat", .*compartment: Some\("Fat"\), compartment_volume: Some\(p\[\d+\]\), '
+            r"is_amount: true, is_boundary: false \},",
+            euromix,
+        )
+
+        bpa = SbmlToRustConverter(
+            ParseSBMLFile(str(DATA_DIR / "PBPK_BPA_model.sbml"))
+        ).convert("PBPK_BPA_model", wasm=False)
+        assert "pub fn get_species_info() -> String" in bpa
+        assert re.search(
+            r'SpeciesInfo \{ id: "Aplasma", .*compartment: Some\("comp1"\), compartment_volume: Some\(p\[\d+\]\), '
+            r"is_amount: false, is_boundary: false \},",
+            bpa,
+        )
+
+    def test_series_units_from_sbml(self):
+        """Test that amounts carry their substance units and concentrations a per-volume unit"""
+        talinolol = SbmlToRustConverter(
+            ParseSBMLFile(str(DATA_DIR / "talinolol_body.xml"))
+        ).convert("talinolol_model", wasm=False)
+        assert '("Aurine_tal", Some("mmole")),' in talinolol
+        assert '("Cki_plasma_tal", Some("mmole/litre")),' in talinolol
+        assert 'const MODEL_TIME_UNIT: Option<&str> = Some("min");' in talinolol
+
+    def test_compartments_info_matches_rate_laws(self):
+        """Test that get_compartments_info places each species where its rate laws read its volume"""
+        doc = self.create_simple_sbml_model()
+        model = doc.getModel()
+        plasma = model.createCompartment()
+        plasma.setId("plasma")
+        plasma.setName("blood plasma")
+        plasma.setUnits("litre")
+        plasma.setSize(3.0)
+        model.getSpecies("B").setCompartment("plasma")
+        model.getReaction("R1").getKineticLaw().setMath(libsbml.parseL3Formula("k1 * A * comp1"))
+        elimination = model.createReaction()
+        elimination.setId("R2")
+        reactant = elimination.createReactant()
+        reactant.setSpecies("B")
+        reactant.setStoichiometry(1.0)
+        elimination.createKineticLaw().setMath(libsbml.parseL3Formula("k2 * B * plasma"))
+
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+
+            try:
+                model_data = ParseSBMLFile(f.name)
+                converter = SbmlToRustConverter(model_data)
+                rust_code = converter.convert("test_model", wasm=False)
+            finally:
+                os.unlink(f.name)
+
+        assert "pub fn get_compartments_info() -> String" in rust_code
+        assert re.search(
+            r'CompartmentInfo \{ id: "plasma", name: Some\("blood plasma"\), size: Some\(p\[\d+\]\), '
+            r'formula: None, units: Some\("litre"\), species: &\["B"\] \},',
+            rust_code,
+        )
+        listed = {
+            species: compartment
+            for compartment, names in re.findall(
+                r'CompartmentInfo \{ id: "(\w+)", .*species: &\[([^\]]*)\] \}', rust_code
+            )
+            for species in re.findall(r'"(\w+)"', names)
+        }
+
+        rates = converter.ode_builder.parse_reaction_rates(model_data["reactions"])
+        ode_system = converter.ode_builder.build_ode_system(model_data["reactions"], rates)
+        volumes = {sympy.Symbol(c) for c in model_data["compartments"]}
+        for species, rhs in zip(converter.species_list, ode_system):
+            read = {str(v) for v in sympy.diff(rhs, sympy.Symbol(species)).free_symbols & volumes}
+            assert read == {listed[species]}, species
+
+    def test_metadata_identifies_source_sbml(self, monkeypatch):
+        """Test that get_model_metadata carries the SBML provenance and a reproducible timestamp"""
+        monkeypatch.setenv("SOURCE_DATE_EPOCH", "1700000000")
+        sbml_path = DATA_DIR / "PBPK_BPA_model.sbml"
+        model_data = ParseSBMLFile(str(sbml_path))
+        rust_code = SbmlToRustConverter(model_data).convert("PBPK_BPA_model", wasm=False)
+
+        sha256 = hashlib.sha256(sbml_path.read_bytes()).hexdigest()
+        assert (
+            'sbml: SbmlSource { id: Some("PBPK_BPA"), name: None, level: Some(3), version: Some(2), '
+            f'sha256: Some("{sha256}") }},'
+        ) in rust_code
+        assert 'generated_at: Some("2023-11-14T22:13:20Z")' in rust_code
+        assert 'features: &["events", "embedded_sbml"],' in rust_code
+
+    def test_model_sbml_embeds_source_document(self):
+        """Test that get_model_sbml returns the parsed file verbatim unless embedding is turned off"""
+        sbml_path = DATA_DIR / "PBPK_BPA_model.sbml"
+        converter = SbmlToRustConverter(ParseSBMLFile(str(sbml_path)))
+        rust_code = converter.convert("PBPK_BPA_model", wasm=False)
+        document = sbml_path.read_bytes().decode("utf-8")
+        assert f"const MODEL_SBML: &str = {rust_raw_string(document)};" in rust_code
+        assert "    Ok(MODEL_SBML.to_string())" in rust_code
+
+        lean = converter.convert("PBPK_BPA_model", wasm=False, embed_sbml=False)
+        assert "MODEL_SBML" not in lean
+        assert "generated with embed_sbml=False" in lean
+        assert 'features: &["events"],' in lean
+
+    def test_types_ts_names_model_keys(self):
+        """Test that get_types_ts spells out the species, observables and parameters of the model"""
+        rust_code = SbmlToRustConverter(
+            ParseSBMLFile(str(DATA_DIR / "PBPK_BPA_model.sbml"))
+        ).convert("PBPK_BPA_model", wasm=True, uptake_window=("t0", "t1"))
+        types_ts = rust_code.split("const TYPES_TS: &str = ")[1].split("\n" + METADATA_ONLY + WASM_EXPORT + "pub fn get_types_ts()")[0]
+        assert 'export type SpeciesId = "Aplasma";' in types_ts
+        assert 'export type ObservableId = "cplasma";' in types_ts
+        assert "  Kelm?: number;" in types_ts
+        assert "  init_Aplasma?: number | null;" in types_ts
+        assert "  uptake_windows?: UptakeWindow[] | null;" in types_ts
+        assert "uptake_schedule" not in types_ts
+
+    def test_model_equations_use_sbml_identifiers(self):
+        """Test that get_model_equations lists the derivatives and rules before CSE"""
+        doc = self.create_simple_sbml_model()
+        model = doc.getModel()
+        k_eff = model.createParameter()
+        k_eff.setId("k_eff")
+        k_eff.setConstant(False)
+        rule = model.createAssignmentRule()
+        rule.setVariable("k_eff")
+        rule.setMath(libsbml.parseL3Formula("k1 * k2"))
+        model.getReaction("R1").getKineticLaw().setMath(libsbml.parseL3Formula("k_eff * A"))
+
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+
+            try:
+                rust_code = SbmlToRustConverter(ParseSBMLFile(f.name)).convert("test_model", wasm=False)
+            finally:
+                os.unlink(f.name)
+
+        equations = rust_code.split("pub fn get_model_equations() -> String {")[1]
+        assert '("A", "-A*k_eff"),' in equations
+        assert '("B", "A*k_eff"),' in equations
+        assert '("k_eff", "k1*k2"),' in equations.split("assignment_rules:")[1]
+
+    def test_algebraic_rule_determines_a_free_variable(self):
+        """Test that an algebraic rule replaces the row of the variable it determines"""
+        doc = self.create_simple_sbml_model()
+        model = doc.getModel()
+        total = model.createParameter()
+        total.setId("total")
+        total.setValue(1.0)
+        total.setConstant(False)
+        rule = model.createAlgebraicRule()
+        rule.setMath(libsbml.parseL3Formula("A + B - total"))
+
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+            try:
+                model_data = ParseSBMLFile(f.name)
+            finally:
+                os.unlink(f.name)
+
+        # A and B are changed by R1 and k1, k2 are constant, so the rule fixes total
+        converter = SbmlToRustConverter(model_data)
+        assert converter.algebraic_variables == {"1": "total"}
+        rust_code = converter.convert("test_model", wasm=False)
+        assert "algebraic: &[2]," in rust_code
+        assert '("total", "A + B - total"),' in rust_code.split("algebraic_rules: EquationTable(&[")[1]
+        with pytest.raises(ValueError, match="sensitivities"):
+            SbmlToRustConverter(model_data).convert("test_model", wasm=False, sensitivities=True)
+
+    def test_rate_of_and_time_csymbol_in_observables(self):
+        """Test that rateOf reads the derivative and any time csymbol reads t"""
+        doc = self.create_simple_sbml_model()
+        model = doc.getModel()
+        for rule_id, formula in (("net", "rateOf(B)"), ("elapsed", "time + k2")):
+            parameter = model.createParameter()
+            parameter.setId(rule_id)
+            parameter.setConstant(False)
+            rule = model.createAssignmentRule()
+            rule.setVariable(rule_id)
+            rule.setMath(libsbml.parseL3Formula(formula))
+        # The time csymbol's text is free; name it like a model symbol
+        model.getAssignmentRule("elapsed").getMath().getChild(0).setName("T")
+
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+            try:
+                rust_code = SbmlToRustConverter(ParseSBMLFile(f.name)).convert("test_model", wasm=False)
+            finally:
+                os.unlink(f.name)
+
+        observables = rust_code.split("let eval_observables =")[1].split("};")[0]
+        assert "let net = A*k1;" in observables
+        assert "let elapsed = k2 + t;" in observables
+        assert "rateOf" not in rust_code.split("fn rhs(")[1].split("fn jac(")[0]
+
+    def test_event_assigned_parameter_becomes_state(self):
+        """Test that a parameter only events change is a state with a zero derivative"""
+        model_data = {
+            "species": {"Gut": {"value": 0.0, "compartment": "c"}},
+            "parameters": {"dose": {"value": 5.0}, "cum_dose": {"value": 0.0, "constant": False}},
+            "compartments": {"c": {"size": 1.0}},
+            "reactions": {},
+            "functions": {},
+            "assignmentRules": {},
+            "events": {
+                "bolus": {
+                    "trigger": "t >= 2",
+                    "eventAssignments": [
+                        {"variable": "Gut", "math": "Gut + dose"},
+                        {"variable": "cum_dose", "math": "cum_dose + dose"},
+                    ],
+                },
+            },
+        }
+        converter = SbmlToRustConverter(model_data)
+        assert converter.species_list == ["Gut", "cum_dose"]
+        rust_code = converter.convert("test_model", wasm=False)
+        assert "y_new[1] = cum_dose + dose;" in rust_code
+        assert "dy[1] = 0.0;" in rust_code
+        assert "Skipped: assignment to parameter" not in rust_code
+
     def test_full_pipeline_with_assignment_rules(self):
         """Test pipeline with assignment rules"""
         doc = self.create_simple_sbml_model()
@@ -195,3 +532,1030 @@ class TestIntegration:
             finally:
                 os.unlink(f.name)
 
+
+    def test_full_pipeline_with_local_parameters(self):
+        """Test that kinetic-law local parameters become SimulationParams fields"""
+        doc = self.create_simple_sbml_model()
+        model = doc.getModel()
+
+        reaction = model.getReaction(0)
+        kl = reaction.getKineticLaw()
+        kl.setMath(libsbml.parseL3Formula("Vmax * A / (Km + A)"))
+        for param_id, value in [("Vmax", 2.0), ("Km", 0.1)]:
+            local = kl.createLocalParameter()
+            local.setId(param_id)
+            local.setValue(value)
+
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+
+            try:
+                model_data = ParseSBMLFile(f.name)
+                converter = SbmlToRustConverter(model_data)
+                rust_code = converter.convert("test_model")
+
+                assert "pub Vmax: f64," in rust_code
+                assert "pub Km: f64," in rust_code
+                assert "let Km = sim_params.Km;" in rust_code
+
+            finally:
+                os.unlink(f.name)
+
+    def test_full_pipeline_flattens_comp_submodels(self):
+        """Test that submodel reactions reach the ODEs and conserve the dose"""
+        sbmlns = libsbml.SBMLNamespaces(3, 1, "comp", 1)
+        doc = libsbml.SBMLDocument(sbmlns)
+        doc.setPackageRequired("comp", True)
+
+        # Kidney submodel: renal excretion from plasma into urine
+        kidney = doc.getPlugin("comp").createModelDefinition()
+        kidney.setId("kidney")
+        comp = kidney.createCompartment()
+        comp.setId("plasma")
+        comp.setSize(1.0)
+        comp.setConstant(True)
+        for species_id, amount in [("A", 0.0), ("Aurine", 0.0)]:
+            species = kidney.createSpecies()
+            species.setId(species_id)
+            species.setCompartment("plasma")
+            species.setInitialAmount(amount)
+            species.setHasOnlySubstanceUnits(True)
+            species.setBoundaryCondition(False)
+            species.setConstant(False)
+        reaction = kidney.createReaction()
+        reaction.setId("EX")
+        reaction.setReversible(False)
+        reactant = reaction.createReactant()
+        reactant.setSpecies("A")
+        reactant.setStoichiometry(1.0)
+        reactant.setConstant(True)
+        product = reaction.createProduct()
+        product.setSpecies("Aurine")
+        product.setStoichiometry(1.0)
+        product.setConstant(True)
+        reaction.createKineticLaw().setMath(libsbml.parseL3Formula("0.1 * A"))
+
+        # Body model: plasma amount shared with the kidney submodel
+        model = doc.createModel()
+        model.setId("body")
+        comp = model.createCompartment()
+        comp.setId("plasma")
+        comp.setSize(1.0)
+        comp.setConstant(True)
+        comp.getPlugin("comp").createReplacedElement().setIdRef("plasma")
+        comp.getPlugin("comp").getReplacedElement(0).setSubmodelRef("KI")
+        species = model.createSpecies()
+        species.setId("A")
+        species.setCompartment("plasma")
+        species.setInitialAmount(10.0)
+        species.setHasOnlySubstanceUnits(True)
+        species.setBoundaryCondition(False)
+        species.setConstant(False)
+        replaced = species.getPlugin("comp").createReplacedElement()
+        replaced.setIdRef("A")
+        replaced.setSubmodelRef("KI")
+        submodel = model.getPlugin("comp").createSubmodel()
+        submodel.setId("KI")
+        submodel.setModelRef("kidney")
+
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+
+            try:
+                model_data = ParseSBMLFile(f.name)
+                assert len(model_data["reactions"]) == 1
+
+                converter = SbmlToRustConverter(model_data)
+                ode_system = converter.ode_builder.build_ode_system(
+                    model_data["reactions"]
+                )
+
+                # Excreted amount accumulates what leaves plasma
+                assert all(expr != 0 for expr in ode_system)
+                assert sympy.simplify(sum(ode_system)) == 0
+
+            finally:
+                os.unlink(f.name)
+
+    def test_state_dependent_rules_stay_inside_closures(self):
+        """Test that rules reading species are not bound before the closures"""
+        doc = self.create_simple_sbml_model()
+        model = doc.getModel()
+
+        rule = model.createAssignmentRule()
+        rule.setVariable("Atot")
+        rule.setMath(libsbml.parseL3Formula("A * k2"))
+
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+
+            try:
+                model_data = ParseSBMLFile(f.name)
+                converter = SbmlToRustConverter(model_data)
+                rust_code = converter.convert("test_model", wasm=False)
+
+                top_level = rust_code.split("// RHS Closure")[0]
+                assert "let Atot" not in top_level
+                assert "let Atot = " in rust_code.split("let eval_observables")[1]
+
+            finally:
+                os.unlink(f.name)
+
+    def test_core_fixtures_match_generator(self):
+        """Test that the models checked into wasm_pk_core are what the generator emits"""
+        from tests.fixture_models import MODELS_DIR, generate
+
+        for file_name, code in generate(SbmlToRustConverter).items():
+            assert (MODELS_DIR / file_name).read_text() == code, (
+                f"{file_name} is stale: run python -m tests.fixture_models"
+            )
+
+    @pytest.mark.slow
+    def test_talinolol_module_compiles(self, tmp_path):
+        """Test that the regenerated talinolol module passes cargo check"""
+        submodels = ["talinolol_kidney.xml", "talinolol_liver.xml", "talinolol_intestine.xml"]
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+        if not all((DATA_DIR / name).exists() for name in submodels):
+            pytest.skip("talinolol comp submodel files are not available")
+
+        model_data = ParseSBMLFile(str(DATA_DIR / "talinolol_body.xml"))
+        rust_code = SbmlToRustConverter(model_data).convert("talinolol_model", wasm=False)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod talinolol_model;\n"
+        )
+        (tmp_path / "src" / "talinolol_model.rs").write_text(rust_code)
+
+        result = subprocess.run(
+            ["cargo", "check", "--quiet"], cwd=tmp_path,
+            capture_output=True, text=True, timeout=1800
+        )
+        assert result.returncode == 0, result.stderr
+
+    @pytest.mark.slow
+    def test_euromix_sensitivities_match_finite_differences(self, tmp_path):
+        """Test the euromix forward sensitivities against central finite differences"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        model_data = ParseSBMLFile(str(DATA_DIR / "euromix.sbml"))
+        rust_code = SbmlToRustConverter(model_data).convert(
+            "euromix_model", wasm=False, sensitivities=True
+        )
+        assert "pub fn run_simulation_with_sensitivities(" in rust_code
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod euromix_model;\n"
+        )
+        (tmp_path / "src" / "euromix_model.rs").write_text(rust_code)
+        (tmp_path / "tests" / "sensitivities.rs").write_text(EUROMIX_SENSITIVITY_CHECKS)
+
+        result = subprocess.run(
+            ["cargo", "test", "--quiet", "--release", "--test", "sensitivities"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stdout + result.stderr
+
+    @pytest.mark.slow
+    @pytest.mark.parametrize("file_name", ["euromix.sbml", "talinolol_body.xml"])
+    def test_jacobian_matrix_matches_jacobian_products(self, tmp_path, file_name):
+        """Test that jacobian_matrix gives the results of the default J·v solver"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+        if file_name == "talinolol_body.xml" and not (DATA_DIR / "talinolol_liver.xml").exists():
+            pytest.skip("talinolol comp submodel files are not available")
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod products;\npub mod matrix;\n"
+        )
+        for module, jacobian_matrix in [("products", False), ("matrix", True)]:
+            model_data = ParseSBMLFile(str(DATA_DIR / file_name))
+            rust_code = SbmlToRustConverter(model_data).convert(
+                "check_model", wasm=False, jacobian_matrix=jacobian_matrix
+            )
+            (tmp_path / "src" / f"{module}.rs").write_text(rust_code)
+        (tmp_path / "tests" / "jacobian_matrix.rs").write_text(JACOBIAN_MATRIX_CHECKS)
+
+        result = subprocess.run(
+            ["cargo", "test", "--quiet", "--release", "--test", "jacobian_matrix"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stdout + result.stderr
+
+    @pytest.mark.slow
+    def test_euromix_reports_solver_stats(self, tmp_path):
+        """Test that the euromix default run reports nonzero solver stats"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        model_data = ParseSBMLFile(str(DATA_DIR / "euromix.sbml"))
+        rust_code = SbmlToRustConverter(model_data).convert("euromix_model", wasm=False)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod euromix_model;\n"
+        )
+        (tmp_path / "src" / "euromix_model.rs").write_text(rust_code)
+
+        result = subprocess.run(
+            ["cargo", "test", "--quiet", "solver_stats_are_reported"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stdout + result.stderr
+
+    @pytest.mark.slow
+    def test_euromix_parameter_validation(self, tmp_path):
+        """Test that out-of-range euromix parameters are reported and refused"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        model_data = ParseSBMLFile(str(DATA_DIR / "euromix.sbml"))
+        rust_code = SbmlToRustConverter(model_data).convert("euromix_model", wasm=False)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod euromix_model;\n"
+        )
+        (tmp_path / "src" / "euromix_model.rs").write_text(rust_code)
+        (tmp_path / "tests" / "validation.rs").write_text(EUROMIX_VALIDATION_CHECKS)
+
+        result = subprocess.run(
+            ["cargo", "test", "--quiet", "--test", "validation"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stdout + result.stderr
+
+    @pytest.mark.slow
+    def test_fast_absorption_clamped_nonnegative(self, tmp_path):
+        """Test that clamp_nonnegative removes the overshoot of a fast absorption"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        doc = self.create_simple_sbml_model()
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+            try:
+                model_data = ParseSBMLFile(f.name)
+            finally:
+                os.unlink(f.name)
+        rust_code = SbmlToRustConverter(model_data).convert("test_model", wasm=False)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod test_model;\n"
+        )
+        (tmp_path / "src" / "test_model.rs").write_text(rust_code)
+        (tmp_path / "tests" / "nonnegative.rs").write_text(NONNEGATIVE_CHECKS)
+
+        result = subprocess.run(
+            ["cargo", "test", "--quiet", "--test", "nonnegative"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stdout + result.stderr
+
+    @pytest.mark.slow
+    def test_closed_model_conserves_mass(self, tmp_path):
+        """Test that the mass balance diagnostic finds no drift in a closed model with doses"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        doc = self.create_simple_sbml_model()
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+            try:
+                model_data = ParseSBMLFile(f.name)
+            finally:
+                os.unlink(f.name)
+        rust_code = SbmlToRustConverter(model_data).convert("test_model", wasm=False)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod test_model;\n"
+        )
+        (tmp_path / "src" / "test_model.rs").write_text(rust_code)
+        (tmp_path / "tests" / "mass_balance.rs").write_text(MASS_BALANCE_CHECKS)
+
+        result = subprocess.run(
+            ["cargo", "test", "--quiet", "--test", "mass_balance"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stdout + result.stderr
+
+    @pytest.mark.slow
+    def test_continuation_matches_single_run(self, tmp_path):
+        """Test that a run continued from its last state or checkpoint ends where one longer run does"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        doc = self.create_simple_sbml_model()
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+            try:
+                model_data = ParseSBMLFile(f.name)
+            finally:
+                os.unlink(f.name)
+        rust_code = SbmlToRustConverter(model_data).convert("test_model", wasm=False)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod test_model;\n"
+        )
+        (tmp_path / "src" / "test_model.rs").write_text(rust_code)
+        (tmp_path / "tests" / "continuation.rs").write_text(CONTINUATION_CHECKS)
+
+        result = subprocess.run(
+            ["cargo", "test", "--quiet", "--test", "continuation"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stdout + result.stderr
+
+    @pytest.mark.slow
+    def test_failed_run_keeps_partial_results(self, tmp_path):
+        """Test that a run the solver cannot finish returns its trajectory and diagnostics"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        doc = self.create_simple_sbml_model()
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+            try:
+                model_data = ParseSBMLFile(f.name)
+            finally:
+                os.unlink(f.name)
+        rust_code = SbmlToRustConverter(model_data).convert("test_model", wasm=False)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod test_model;\n"
+        )
+        (tmp_path / "src" / "test_model.rs").write_text(rust_code)
+        (tmp_path / "tests" / "failure.rs").write_text(FAILURE_DIAGNOSTICS_CHECKS)
+
+        result = subprocess.run(
+            ["cargo", "test", "--quiet", "--test", "failure"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stdout + result.stderr
+
+    @pytest.mark.slow
+    def test_model_without_species_reports_build_error(self, tmp_path):
+        """Test that a problem diffsol cannot solve fails with a message instead of a panic"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        doc = libsbml.SBMLDocument(3, 2)
+        model = doc.createModel()
+        model.setId("test_model")
+        param = model.createParameter()
+        param.setId("k1")
+        param.setValue(0.5)
+        with tempfile.NamedTemporaryFile(mode='w', suffix='.xml', delete=False) as f:
+            libsbml.writeSBMLToFile(doc, f.name)
+            try:
+                model_data = ParseSBMLFile(f.name)
+            finally:
+                os.unlink(f.name)
+        rust_code = SbmlToRustConverter(model_data).convert("test_model", wasm=False)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod test_model;\n"
+        )
+        (tmp_path / "src" / "test_model.rs").write_text(rust_code)
+        (tmp_path / "tests" / "no_species.rs").write_text(NO_SPECIES_CHECKS)
+
+        result = subprocess.run(
+            ["cargo", "test", "--quiet", "--test", "no_species"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stdout + result.stderr
+
+    @pytest.mark.slow
+    def test_bpa_oral_uptake(self, tmp_path):
+        """Test the BPA oral uptake windows with the checks in BPA_ORAL_UPTAKE_CHECKS"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        model_data = ParseSBMLFile(str(DATA_DIR / "PBPK_BPA_model_corrected.sbml"))
+        rust_code = SbmlToRustConverter(model_data).convert(
+            "pbpk_bpa_model", wasm=False, uptake_window=("t0", "t1"), pulse_sharpness=100,
+            uptake_repeat=("n_O", "period_O"),
+        )
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "Cargo.toml").write_text(CHECK_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod pbpk_bpa_model;\n"
+        )
+        (tmp_path / "src" / "pbpk_bpa_model.rs").write_text(rust_code)
+        (tmp_path / "tests" / "oral_uptake.rs").write_text(BPA_ORAL_UPTAKE_CHECKS)
+
+        result = subprocess.run(
+            ["cargo", "test", "--quiet", "--release", "--test", "oral_uptake"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stdout + result.stderr
+
+    @pytest.mark.slow
+    def test_same_source_builds_for_native_runner_and_wasm32(self, tmp_path):
+        """Test that one WASM build runs in a native binary with `wasm` on and also targets wasm32"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        model_data = ParseSBMLFile(str(DATA_DIR / "euromix.sbml"))
+        rust_code = SbmlToRustConverter(model_data).convert("euromix_model", wasm=True)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "Cargo.toml").write_text(RUNNER_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod euromix_model;\n"
+        )
+        (tmp_path / "src" / "euromix_model.rs").write_text(rust_code)
+        (tmp_path / "src" / "main.rs").write_text(
+            "use generated_model_check::euromix_model;\n\n"
+            "fn main() {\n"
+            '    println!("{}", euromix_model::run_simulation("{}"));\n'
+            "}\n"
+        )
+
+        # Host: default features, so `wasm` is on, yet nothing of wasm-bindgen is linked
+        result = subprocess.run(
+            ["cargo", "run", "--quiet", "--release"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stderr
+        output = json.loads(result.stdout)
+        assert output["error"] is None
+        assert output["time"][-1] == pytest.approx(output["parameters"]["final_time"])
+        tree = subprocess.run(
+            ["cargo", "tree", "--edges", "normal"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=600
+        )
+        assert tree.returncode == 0, tree.stderr
+        assert "wasm-bindgen" not in tree.stdout
+
+        sysroot = subprocess.run(["rustc", "--print", "sysroot"], capture_output=True, text=True).stdout.strip()
+        if not (Path(sysroot) / "lib" / "rustlib" / "wasm32-unknown-unknown").is_dir():
+            pytest.skip("the wasm32-unknown-unknown target is not installed")
+        result = subprocess.run(
+            ["cargo", "build", "--quiet", "--release", "--lib", "--target", "wasm32-unknown-unknown"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stderr
+        wasm = tmp_path / "target" / "wasm32-unknown-unknown" / "release" / "generated_model_check.wasm"
+        assert b"run_simulation_js" in wasm.read_bytes()
+
+    @pytest.mark.slow
+    def test_euromix_c_api(self, tmp_path):
+        """Test that the native C ABI library runs the euromix defaults when loaded with dlopen"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        model_data = ParseSBMLFile(str(DATA_DIR / "euromix.sbml"))
+        rust_code = SbmlToRustConverter(model_data).convert("euromix_model", wasm=True)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "Cargo.toml").write_text(C_API_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod euromix_model;\n"
+        )
+        (tmp_path / "src" / "euromix_model.rs").write_text(rust_code)
+
+        result = subprocess.run(
+            ["cargo", "build", "--quiet", "--release", "--features", "c-api"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stderr
+
+        library = next(
+            path for path in (tmp_path / "target" / "release").iterdir()
+            if path.suffix in (".so", ".dylib", ".dll")
+        )
+        lib = ctypes.CDLL(str(library))
+        lib.wasm_pk_run_simulation.argtypes = [ctypes.c_char_p]
+        lib.wasm_pk_run_simulation.restype = ctypes.c_void_p
+        lib.wasm_pk_get_default_parameters.restype = ctypes.c_void_p
+        lib.wasm_pk_free_string.argtypes = [ctypes.c_void_p]
+
+        def take_json(pointer):
+            text = ctypes.string_at(pointer).decode()
+            lib.wasm_pk_free_string(pointer)
+            return json.loads(text)
+
+        defaults = take_json(lib.wasm_pk_get_default_parameters())
+        result = take_json(lib.wasm_pk_run_simulation(json.dumps(defaults).encode()))
+        assert result["error"] is None
+        assert result["time"][-1] == pytest.approx(result["parameters"]["final_time"])
+        assert "QGut" in result["species"]
+        for values in result["species"].values():
+            assert len(values) == len(result["time"])
+
+        # Bad input comes back as a failed result, never as null
+        failed = take_json(lib.wasm_pk_run_simulation(None))
+        assert failed["error"]["message"] == "params_json is null"
+
+    @pytest.mark.slow
+    def test_euromix_wasi_module(self, tmp_path):
+        """Test that the wasi feature builds a wasm32-wasip1 module that runs under wasmtime"""
+        wasmtime = pytest.importorskip("wasmtime")
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+        sysroot = subprocess.run(["rustc", "--print", "sysroot"], capture_output=True, text=True).stdout.strip()
+        if not (Path(sysroot) / "lib" / "rustlib" / "wasm32-wasip1").is_dir():
+            pytest.skip("the wasm32-wasip1 target is not installed")
+
+        model_data = ParseSBMLFile(str(DATA_DIR / "euromix.sbml"))
+        rust_code = SbmlToRustConverter(model_data).convert("euromix_model", wasm=True)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "Cargo.toml").write_text(C_API_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod euromix_model;\n"
+        )
+        (tmp_path / "src" / "euromix_model.rs").write_text(rust_code)
+
+        result = subprocess.run(
+            ["cargo", "build", "--quiet", "--release", "--target", "wasm32-wasip1", "--features", "wasi"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stderr
+
+        engine = wasmtime.Engine()
+        store = wasmtime.Store(engine)
+        store.set_wasi(wasmtime.WasiConfig())
+        linker = wasmtime.Linker(engine)
+        linker.define_wasi()
+        module = wasmtime.Module.from_file(
+            engine, str(tmp_path / "target" / "wasm32-wasip1" / "release" / "generated_model_check.wasm")
+        )
+        exports = linker.instantiate(store, module).exports(store)
+        if "_initialize" in exports:
+            exports["_initialize"](store)
+        memory = exports["memory"]
+        alloc, free = exports["wasm_pk_alloc"], exports["wasm_pk_free"]
+        out_len = alloc(store, 4)
+
+        def take_json(pointer):
+            length = int.from_bytes(memory.read(store, out_len, out_len + 4), "little")
+            text = bytes(memory.read(store, pointer, pointer + length)).decode()
+            free(store, pointer, length)
+            return json.loads(text)
+
+        def call(name, params):
+            data = json.dumps(params).encode()
+            pointer = alloc(store, len(data))
+            memory.write(store, data, pointer)
+            try:
+                return take_json(exports[name](store, pointer, len(data), out_len))
+            finally:
+                free(store, pointer, len(data))
+
+        defaults = take_json(exports["get_default_parameters"](store, out_len))
+        result = call("run_simulation", defaults)
+        assert result["error"] is None
+        assert result["time"][-1] == pytest.approx(result["parameters"]["final_time"])
+        assert result["species"]["QGut"][0] == 1.0
+        for values in result["species"].values():
+            assert len(values) == len(result["time"])
+
+        report = call("validate_parameters", {"BM": "heavy"})
+        assert report["errors"][0]["field"] == "BM"
+
+    @pytest.mark.slow
+    def test_minimal_feature_strips_metadata(self, tmp_path):
+        """Test that the minimal feature drops at least the embedded SBML from the library"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+
+        document = (DATA_DIR / "euromix.sbml").read_bytes()
+        model_data = ParseSBMLFile(str(DATA_DIR / "euromix.sbml"))
+        rust_code = SbmlToRustConverter(model_data).convert("euromix_model", wasm=True)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "Cargo.toml").write_text(C_API_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod euromix_model;\n"
+        )
+        (tmp_path / "src" / "euromix_model.rs").write_text(rust_code)
+
+        # The wasi exports keep the getters in the library, as wasm-bindgen does
+        sizes = {}
+        for features in ["wasi", "wasi,minimal"]:
+            result = subprocess.run(
+                ["cargo", "build", "--quiet", "--release", "--features", features],
+                cwd=tmp_path, capture_output=True, text=True, timeout=3600
+            )
+            assert result.returncode == 0, result.stderr
+            library = next(
+                path for path in (tmp_path / "target" / "release").iterdir()
+                if path.suffix in (".so", ".dylib", ".dll")
+            )
+            sizes[features] = library.stat().st_size
+        saved = sizes["wasi"] - sizes["wasi,minimal"]
+        print(f"minimal saves {saved} of {sizes['wasi']} bytes")
+        assert saved >= len(document)
+
+    @pytest.mark.slow
+    def test_euromix_python_module(self, tmp_path):
+        """Test that the python feature imports as sbml_model and runs the euromix defaults"""
+        if shutil.which("cargo") is None:
+            pytest.skip("cargo is not available")
+        if sys.platform != "linux":
+            pytest.skip("extension modules built by plain cargo only load as is on Linux")
+
+        model_data = ParseSBMLFile(str(DATA_DIR / "euromix.sbml"))
+        rust_code = SbmlToRustConverter(model_data).convert("euromix_model", wasm=True)
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "Cargo.toml").write_text(PYTHON_CRATE_MANIFEST)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "#![allow(non_snake_case, unused)]\npub mod euromix_model;\n"
+        )
+        (tmp_path / "src" / "euromix_model.rs").write_text(rust_code)
+
+        result = subprocess.run(
+            ["cargo", "build", "--quiet", "--release", "--features", "python"],
+            cwd=tmp_path, capture_output=True, text=True, timeout=3600
+        )
+        assert result.returncode == 0, result.stderr
+
+        extension = tmp_path / "sbml_model.so"
+        shutil.copy(tmp_path / "target" / "release" / "libgenerated_model_check.so", extension)
+        spec = importlib.util.spec_from_file_location("sbml_model", extension)
+        sbml_model = importlib.util.module_from_spec(spec)
+        spec.loader.exec_module(sbml_model)
+
+        result = sbml_model.run_simulation({"output_times": list(range(25))})
+        assert result["error"] is None
+        assert result["time"] == [float(t) for t in range(25)]
+        assert all(len(values) == 25 for values in result["species"].values())
+        # The whole dose starts in the gut and the model is closed, so it is
+        # all still somewhere at the end
+        assert result["species"]["QGut"][0] == 1.0
+        total = sum(values[-1] for values in result["species"].values())
+        assert total == pytest.approx(1.0, rel=1e-4)
+
+        assert sbml_model.get_default_parameters()["BM"] == result["parameters"]["BM"]
+        with pytest.raises(ValueError, match="unknown species"):
+            sbml_model.compute_pk_metrics(result, "NotASpecies")
+
+
+# Three repeated uptakes (n_O = 3) give three Aplasma peaks; without elimination the
+# final amount is proportional to the number of uptakes. A single uptake with the defaults
+# absorbs Kabs * koa * (t1 - t0), less the ln(2) / (2 pulse_sharpness) fraction
+# of the smoothed pulse that falls before t0. Uptake windows absorb that
+# amount per unit of window length, scaled by their fraction
+BPA_ORAL_UPTAKE_CHECKS = """
+use generated_model_check::pbpk_bpa_model::run_simulation;
+
+fn aplasma(params: &str) -> (Vec<f64>, Vec<f64>) {
+    let result: serde_json::Value = serde_json::from_str(&run_simulation(params)).unwrap();
+    assert!(result["error"].is_null(), "{}", result["error"]);
+    let series = |v: &serde_json::Value| -> Vec<f64> {
+        v.as_array().unwrap().iter().map(|x| x.as_f64().unwrap()).collect()
+    };
+    (series(&result["time"]), series(&result["species"]["Aplasma"]))
+}
+
+#[test]
+fn three_uptakes_give_three_peaks() {
+    let (time, values) = aplasma(r#"{"n_O": 3, "period_O": 8, "final_time": 24}"#);
+    // Local maxima at least 10% above the lowest value since the previous
+    // peak; solver ripple around a plateau is far smaller
+    let mut peaks = Vec::new();
+    let mut trough = f64::MAX;
+    for i in 1..values.len() - 1 {
+        trough = trough.min(values[i]);
+        if values[i] >= values[i - 1] && values[i] >= values[i + 1] && values[i] > 1.1 * trough {
+            peaks.push(time[i]);
+            trough = values[i];
+        }
+    }
+    assert_eq!(peaks.len(), 3, "peaks at {:?}", peaks);
+    for (peak, start) in peaks.iter().zip([0.0, 8.0, 16.0]) {
+        assert!(*peak > start && *peak < start + 1.0, "peaks at {:?}", peaks);
+    }
+}
+
+#[test]
+fn absorbed_amount_scales_with_n_O() {
+    // Starting at t0 = 1 takes in the whole ramp of the first window too
+    let absorbed = |n: u32| {
+        let (_, values) = aplasma(&format!(r#"{{"t0": 1, "n_O": {}, "period_O": 8, "final_time": 24, "Kelm": 0}}"#, n));
+        *values.last().unwrap()
+    };
+    let single = absorbed(1);
+    assert!(single > 0.0);
+    for n in [2, 3] {
+        let ratio = absorbed(n) / single;
+        assert!((ratio - n as f64).abs() < 1e-3, "n_O {}: ratio {}", n, ratio);
+    }
+}
+
+#[test]
+fn single_uptake_peak_matches_square_pulse() {
+    // runner/src/main_corrected.rs parameters
+    let (_, values) = aplasma(
+        r#"{"Kabs": 0.4, "t0": 0.0, "Kelm": 0.13, "EoA_O": 1.0, "D_o": 1.3381102,
+            "vplasma": 3.6, "period_O": 0.0003, "n_O": 1.0, "final_time": 36.0}"#,
+    );
+    let peak = values.iter().cloned().fold(f64::MIN, f64::max);
+    let expected = 0.4 * 1.2e8 * 1.3381102 * 0.0003 * (1.0 - 2f64.ln() / 200.0);
+    assert!((peak / expected - 1.0).abs() < 1e-3, "peak {} vs {}", peak, expected);
+}
+
+#[test]
+fn uptake_windows_sum_their_fractions() {
+    let absorbed = |windows: &str| {
+        let (_, values) = aplasma(&format!(r#"{{"final_time": 24, "Kelm": 0{}}}"#, windows));
+        *values.last().unwrap()
+    };
+    let single = absorbed("");
+    let same = absorbed(r#", "uptake_windows": [{"t0": 0, "t1": 0.0003, "fraction": 1}]"#);
+    assert!((same / single - 1.0).abs() < 1e-5, "{} vs {}", same, single);
+    // 0.5 * 2 + 1 + 0.25 single-window amounts
+    let three = absorbed(
+        r#", "uptake_windows": [{"t0": 1, "t1": 1.0006, "fraction": 0.5},
+            {"t0": 5, "t1": 5.0003, "fraction": 1}, {"t0": 10, "t1": 10.0003, "fraction": 0.25}]"#,
+    );
+    assert!((three / single - 2.25).abs() < 1e-3, "ratio {}", three / single);
+}
+
+#[test]
+fn invalid_uptake_windows_are_rejected() {
+    for (windows, message) in [
+        (r#"[{"t0": 2, "t1": 3, "fraction": 1}, {"t0": 1, "t1": 1.5, "fraction": 1}]"#, "starts before"),
+        (r#"[{"t0": -1, "t1": 1, "fraction": 1}]"#, "invalid window 0"),
+        (r#"[{"t0": 1, "t1": 2, "fraction": -0.5}]"#, "invalid window 0"),
+        ("[]", "no windows"),
+    ] {
+        let params = format!(r#"{{"uptake_windows": {}}}"#, windows);
+        let result: serde_json::Value = serde_json::from_str(&run_simulation(&params)).unwrap();
+        let error = result["error"]["message"].as_str().unwrap_or_default();
+        assert!(error.contains(message), "{}: {}", windows, error);
+    }
+}
+"""
+
+
+# A negative k1 makes A grow like exp(50 t) until the state overflows and the
+# step size underflows; the run keeps everything up to the failure
+FAILURE_DIAGNOSTICS_CHECKS = """
+use generated_model_check::test_model::run_simulation;
+
+#[test]
+fn failed_run_keeps_partial_results() {
+    let result: serde_json::Value =
+        serde_json::from_str(&run_simulation(r#"{"k1": -50.0, "final_time": 100.0}"#)).unwrap();
+    let error = &result["error"];
+    let time = error["time"].as_f64().unwrap();
+    assert!(time > 1.0 && time < 100.0, "{}", error);
+    assert!(error["message"].as_str().unwrap().contains("Step size is too small"), "{}", error);
+
+    let times = result["time"].as_array().unwrap();
+    assert!(times.len() > 10);
+    assert!(times.last().unwrap().as_f64().unwrap() <= time);
+    assert_eq!(result["species"]["A"].as_array().unwrap().len(), times.len());
+
+    let state = error["state"].as_array().unwrap();
+    assert_eq!(state.len(), 2);
+    assert!(state[1].as_f64().unwrap() < -1e100, "{}", error);
+    let hint = error["hint"].as_str().unwrap();
+    assert!(hint.starts_with("state y[1] (B) = -"), "{}", hint);
+    assert!(hint.ends_with("consider checking k1"), "{}", hint);
+}
+"""
+
+NO_SPECIES_CHECKS = """
+use generated_model_check::test_model::run_simulation_batch;
+
+#[test]
+fn every_run_reports_the_build_error() {
+    let results: serde_json::Value = serde_json::from_str(&run_simulation_batch("[{}, {}]").unwrap()).unwrap();
+    for result in results.as_array().unwrap() {
+        assert_eq!(
+            result["error"]["message"],
+            "building the ODE problem failed: the model has no species to integrate"
+        );
+        assert!(result["time"].as_array().unwrap().is_empty());
+    }
+}
+"""
+
+NONNEGATIVE_CHECKS = """
+use generated_model_check::test_model::run_simulation;
+
+fn run(params: serde_json::Value) -> serde_json::Value {
+    serde_json::from_str(&run_simulation(&params.to_string())).unwrap()
+}
+
+fn series(result: &serde_json::Value, id: &str) -> Vec<f64> {
+    result["species"][id].as_array().unwrap().iter().map(|v| v.as_f64().unwrap()).collect()
+}
+
+#[test]
+fn fast_absorption_is_clamped_at_zero() {
+    let grid = [1.0, 2.0, 4.0, 8.0, 16.0, 24.0];
+    let free = run(serde_json::json!({ "k1": 50.0, "output_times": grid }));
+    assert!(series(&free, "A").iter().any(|&a| a < 0.0));
+
+    let clamped = run(serde_json::json!({ "k1": 50.0, "output_times": grid, "clamp_nonnegative": true }));
+    assert!(clamped["error"].is_null(), "{}", clamped["error"]);
+    assert!(series(&clamped, "A").iter().all(|&a| a >= 0.0));
+    for (b, expected) in series(&clamped, "B").iter().zip(series(&free, "B")) {
+        assert!((b - expected).abs() < 1e-4, "{} vs {}", b, expected);
+    }
+
+    let strict = run(serde_json::json!({ "k1": 50.0, "clamp_nonnegative": true, "clamp_threshold": 1e-12 }));
+    let message = strict["error"]["message"].as_str().unwrap();
+    assert!(message.starts_with("negative value of A (y[0])"), "{}", message);
+}
+"""
+
+# A -> B loses nothing, so the total mass is the initial amount plus the doses
+# at every point, including the pre- and post-dose points at each dose time
+MASS_BALANCE_CHECKS = """
+use generated_model_check::test_model::run_simulation;
+
+#[test]
+fn closed_model_conserves_mass() {
+    let doses = serde_json::json!([
+        { "time": 2.0, "amount": 0.5, "target": "A" },
+        { "time": 5.0, "amount": 0.25, "target": "B" }
+    ]);
+    for output_times in [serde_json::Value::Null, serde_json::json!([0.0, 1.0, 2.0, 3.0, 5.0, 8.0])] {
+        let params = serde_json::json!({
+            "final_time": 8.0, "doses": doses, "output_times": output_times, "mass_balance": true
+        });
+        let result: serde_json::Value = serde_json::from_str(&run_simulation(&params.to_string())).unwrap();
+        let diagnostics = &result["diagnostics"];
+        let total: Vec<f64> = diagnostics["total_mass"].as_array().unwrap().iter().map(|m| m.as_f64().unwrap()).collect();
+        assert_eq!(total.len(), result["time"].as_array().unwrap().len());
+        assert!((total[0] - 1.0).abs() < 1e-12);
+        assert!((total.last().unwrap() - 1.75).abs() < 1e-6, "{:?}", total);
+        assert!(diagnostics["max_mass_drift"].as_f64().unwrap() < 1e-6, "{}", diagnostics);
+    }
+
+    let plain: serde_json::Value = serde_json::from_str(&run_simulation("{}")).unwrap();
+    assert!(plain["diagnostics"].is_null());
+}
+"""
+
+# Continuations and checkpoints keep the clock, so only the BDF restart at
+# the joining point or checkpoint separates them from a single run
+JACOBIAN_MATRIX_CHECKS = """
+use generated_model_check::{matrix, products};
+use serde_json::Value;
+
+#[test]
+fn jacobian_matrix_matches_jacobian_products() {
+    for params in [r#"{"output_dt": 0.5}"#, r#"{"output_dt": 0.5, "rtol": 1e-8, "atol": 1e-10}"#] {
+        let expected: Value = serde_json::from_str(&products::run_simulation(params)).unwrap();
+        let actual: Value = serde_json::from_str(&matrix::run_simulation(params)).unwrap();
+        assert!(expected["error"].is_null(), "{}", expected["error"]);
+        assert!(actual["error"].is_null(), "{}", actual["error"]);
+        assert_eq!(expected["time"], actual["time"]);
+        for (id, values) in expected["species"].as_object().unwrap() {
+            for (a, b) in values.as_array().unwrap().iter().zip(actual["species"][id].as_array().unwrap()) {
+                let (a, b) = (a.as_f64().unwrap(), b.as_f64().unwrap());
+                assert!((a - b).abs() <= 1e-6 * a.abs().max(1e-6), "{} ({}): {} vs {}", id, params, a, b);
+            }
+        }
+    }
+}
+"""
+
+CONTINUATION_CHECKS = """
+use generated_model_check::test_model::{continue_simulation, export_checkpoint, run_from_checkpoint, run_simulation};
+
+fn last(result: &serde_json::Value, id: &str) -> f64 {
+    result["species"][id].as_array().unwrap().last().unwrap().as_f64().unwrap()
+}
+
+#[test]
+fn continuation_matches_single_run() {
+    let dose = serde_json::json!([{ "time": 1.0, "amount": 0.5, "target": "A" }]);
+    let first = run_simulation(&serde_json::json!({ "final_time": 4.0, "doses": dose }).to_string());
+    let joined: serde_json::Value = serde_json::from_str(&continue_simulation(&first, "{}", 4.0, true).unwrap()).unwrap();
+    let single: serde_json::Value =
+        serde_json::from_str(&run_simulation(&serde_json::json!({ "final_time": 8.0, "doses": dose }).to_string())).unwrap();
+    assert_eq!(joined["time"].as_array().unwrap().last().unwrap().as_f64(), Some(8.0));
+    // The earlier dose is kept in the record but not applied again
+    assert_eq!(joined["applied_doses"].as_array().unwrap().len(), 1);
+    for id in ["A", "B"] {
+        let (continued, direct) = (last(&joined, id), last(&single, id));
+        assert!((continued - direct).abs() < 1e-4 * direct.abs().max(1e-3), "{}: {} vs {}", id, continued, direct);
+    }
+
+    // A washout: the rate changes from the joining point on
+    let washout: serde_json::Value = serde_json::from_str(&continue_simulation(&first, r#"{"k1": 0.0}"#, 4.0, false).unwrap()).unwrap();
+    assert_eq!(washout["parameters"]["k1"], 0.0);
+    assert!((last(&washout, "A") - last(&serde_json::from_str(&first).unwrap(), "A")).abs() < 1e-12);
+}
+
+#[test]
+fn checkpoint_resumes_pending_doses() {
+    let dose = serde_json::json!([{ "time": 3.0, "amount": 0.5, "target": "A" }]);
+    run_simulation(&serde_json::json!({ "final_time": 2.0, "doses": dose }).to_string());
+    let checkpoint = export_checkpoint();
+    let resumed: serde_json::Value =
+        serde_json::from_str(&run_from_checkpoint(&checkpoint, r#"{"final_time": 6.0}"#).unwrap()).unwrap();
+    let single: serde_json::Value =
+        serde_json::from_str(&run_simulation(&serde_json::json!({ "final_time": 6.0, "doses": dose }).to_string())).unwrap();
+    // The dose after the checkpoint is applied at its absolute time
+    assert_eq!(resumed["applied_doses"][0]["time"], 3.0);
+    assert_eq!(resumed["time"][0], 2.0);
+    for id in ["A", "B"] {
+        let (continued, direct) = (last(&resumed, id), last(&single, id));
+        assert!((continued - direct).abs() < 1e-4 * direct.abs().max(1e-3), "{}: {} vs {}", id, continued, direct);
+    }
+}
+"""
+
+# The euromix defaults are physical; a skin fraction of 10, a negative
+# clearance and volume fractions leaving no room for Poor are not
+EUROMIX_SENSITIVITY_CHECKS = """
+use generated_model_check::euromix_model::{get_default_parameters, run_simulation, run_simulation_with_sensitivities, SENSITIVITY_PARAMETERS};
+use serde_json::{json, Value};
+
+/// Final state of a one-hour run with `parameter` set to `value`
+fn final_states(parameter: &str, value: f64) -> Vec<f64> {
+    let params = json!({ parameter: value, "final_time": 1.0, "rtol": 1e-10, "atol": 1e-12 });
+    let result: Value = serde_json::from_str(&run_simulation(&params.to_string())).unwrap();
+    result["species"].as_object().unwrap().values().map(|v| v.as_array().unwrap().last().unwrap().as_f64().unwrap()).collect()
+}
+
+#[test]
+fn sensitivities_match_finite_differences() {
+    let wrt: Vec<String> = SENSITIVITY_PARAMETERS.iter().map(|name| name.to_string()).collect();
+    let params = r#"{"final_time": 1.0, "rtol": 1e-10, "atol": 1e-12}"#;
+    let result: Value = serde_json::from_str(&run_simulation_with_sensitivities(params, wrt).unwrap()).unwrap();
+    assert!(result["error"].is_null(), "{}", result["error"]);
+    let defaults: Value = serde_json::from_str(&get_default_parameters()).unwrap();
+    for entry in result["sensitivities"].as_array().unwrap() {
+        let parameter = entry["parameter"].as_str().unwrap();
+        let value = defaults[parameter].as_f64().unwrap();
+        let h = 1e-4 * value.abs().max(1e-2);
+        let (up, down) = (final_states(parameter, value + h), final_states(parameter, value - h));
+        for (i, (name, series)) in entry["species"].as_object().unwrap().iter().enumerate() {
+            let forward = series.as_array().unwrap().last().unwrap().as_f64().unwrap();
+            let fd = (up[i] - down[i]) / (2.0 * h);
+            assert!(
+                (forward - fd).abs() <= 1e-4 * (1.0 + forward.abs().max(fd.abs())),
+                "d{}/d{}: forward {} vs finite difference {}",
+                name, parameter, forward, fd
+            );
+        }
+    }
+}
+"""
+
+EUROMIX_VALIDATION_CHECKS = """
+use generated_model_check::euromix_model::{run_simulation, validate_parameters};
+
+fn fields(params: &str, kind: &str) -> Vec<String> {
+    let report: serde_json::Value = serde_json::from_str(&validate_parameters(params)).unwrap();
+    report[kind].as_array().unwrap().iter().map(|issue| issue["field"].as_str().unwrap_or("").to_string()).collect()
+}
+
+#[test]
+fn out_of_range_parameters_are_refused() {
+    assert!(fields("{}", "errors").is_empty());
+    assert_eq!(fields(r#"{"fSA_exposed": 10.0}"#, "errors"), ["fSA_exposed"]);
+    assert_eq!(fields(r#"{"CLH": -1.0}"#, "errors"), ["CLH"]);
+    assert_eq!(fields(r#"{"BM": "70"}"#, "errors"), ["BM"]);
+
+    let report = validate_parameters(r#"{"scVFat": 0.9}"#);
+    assert!(report.contains("Poor must not be negative"), "{}", report);
+    assert!(report.contains("scVFat"), "{}", report);
+
+    let refused: serde_json::Value =
+        serde_json::from_str(&run_simulation(r#"{"validate": true, "fSA_exposed": 10.0}"#)).unwrap();
+    let message = refused["error"]["message"].as_str().unwrap();
+    assert!(message.starts_with("invalid parameters: fSA_exposed must be a fraction"), "{}", message);
+    assert!(refused["time"].as_array().unwrap().is_empty());
+}
+"""
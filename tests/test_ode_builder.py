@@ -3,7 +3,7 @@
 import pytest
 import sympy
 from symbolic.ode_builder import OdeSystemBuilder
-from parsers.expression_parser import SbmlExpressionParser
+from parsers.expression_parser import RateOf, SbmlExpressionParser
 
 
 class TestOdeSystemBuilder:
@@ -155,6 +155,118 @@ class TestOdeSystemBuilder:
         # A should still get positive contribution
         assert ode_system[0] != sympy.Float(0.0)
 
+    def test_build_reaction_with_promoted_local_parameter(self, species_map, parser):
+        """Test that renamed local parameters are substituted in the rate law"""
+        ode_builder = OdeSystemBuilder(species_map, parser, {"R1": {"k1": "R1_k1"}})
+        reactions = {
+            "R1": {
+                "reactants": [[1.0, "A"]],
+                "products": [[1.0, "B"]],
+                "rateLaw": "k1 * A"
+            }
+        }
+
+        result = ode_builder.build_ode_system(reactions)
+
+        R1_k1, A = sympy.Symbol("R1_k1"), sympy.Symbol("A")
+        assert sympy.simplify(result[0] + R1_k1 * A) == 0
+        assert sympy.Symbol("k1") not in result[1].free_symbols
+
+    def test_apply_rate_rules(self, species_map, parser):
+        """Test that a rate rule sets its state derivative and resolves reaction IDs"""
+        parser.context["R1"] = sympy.Symbol("R1")
+        ode_builder = OdeSystemBuilder(species_map, parser)
+        reactions = {
+            "R1": {
+                "reactants": [[1.0, "A"]],
+                "products": [[1.0, "B"]],
+                "rateLaw": "k1 * A"
+            }
+        }
+        rate_rules = {"rr1": {"variable": "C", "math": "k2 - R1"}}
+
+        rates = ode_builder.parse_reaction_rates(reactions)
+        dy_dt = ode_builder.build_ode_system(reactions, rates)
+        result = ode_builder.apply_rate_rules(dy_dt, rate_rules, rates)
+
+        A, k1, k2 = sympy.symbols("A k1 k2")
+        assert sympy.simplify(result[2] - (k2 - k1 * A)) == 0
+        # Reaction contributions for other states are untouched
+        assert result[0] == dy_dt[0]
+
+    def test_apply_algebraic_rules(self, species_map, parser):
+        """Test that an algebraic rule replaces the row of the variable it determines"""
+        ode_builder = OdeSystemBuilder(species_map, parser)
+        reactions = {
+            "R1": {
+                "reactants": [[1.0, "A"]],
+                "products": [[1.0, "B"]],
+                "rateLaw": "k1 * A"
+            }
+        }
+        algebraic_rules = {"ar1": {"math": "A + B + C - k2"}}
+
+        rates = ode_builder.parse_reaction_rates(reactions)
+        dy_dt = ode_builder.build_ode_system(reactions, rates)
+        result = ode_builder.apply_algebraic_rules(dy_dt, algebraic_rules, {"ar1": "C"}, rates)
+
+        A, B, C, k2 = sympy.symbols("A B C k2")
+        assert sympy.simplify(result[2] - (A + B + C - k2)) == 0
+        assert result[:2] == dy_dt[:2]
+
+    def test_resolve_rate_of(self, species_map, parser):
+        """Test that rateOf reads derivatives, rules along them, and zero otherwise"""
+        ode_builder = OdeSystemBuilder(species_map, parser)
+        A, B, C, k1, total = sympy.symbols("A B C k1 total")
+        # C grows at the rate A falls
+        dy_dt = [-k1 * A, k1 * A, -RateOf(A)]
+        result = ode_builder.resolve_rate_of(dy_dt)
+        assert result == [-k1 * A, k1 * A, k1 * A]
+
+        rules = {"total": A + B}
+        rates = ode_builder.substitute_rate_of(
+            [RateOf(B), RateOf(total), RateOf(k1)], result, rules
+        )
+        assert rates == [k1 * A, 0, 0]
+
+        with pytest.raises(ValueError, match="Circular"):
+            ode_builder.resolve_rate_of([RateOf(B), RateOf(A), sympy.S.Zero])
+
+    def test_split_window_terms(self, ode_builder):
+        """Test that window-dependent terms are moved out of the derivatives"""
+        A, B, k1, t0, t1 = sympy.symbols("A B k1 t0 t1")
+        dy_dt = [k1 * (t1 - t0) - k1 * A, k1 * A, sympy.Float(0.0)]
+
+        result, window_terms = ode_builder.split_window_terms(dy_dt, ("t0", "t1"))
+
+        assert list(window_terms) == ["A"]
+        assert sympy.simplify(window_terms["A"] - k1 * (t1 - t0)) == 0
+        assert sympy.simplify(result[0] - (sympy.Symbol("uptake_A") - k1 * A)) == 0
+        assert result[1:] == dy_dt[1:]
+
+    def test_split_window_terms_sharpens_edges(self, ode_builder):
+        """Test that pulse_sharpness rescales the tanh edges at the window bounds only"""
+        k1, t, t0, t1 = sympy.symbols("k1 t t0 t1")
+        pulse = k1 * (sympy.tanh(100 * (t - t0)) - sympy.tanh(100 * t - 100 * t1)) / 2
+        other = k1 * t0 * sympy.tanh(3 * t)
+        _, window_terms = ode_builder.split_window_terms(
+            [pulse + other, k1, k1], ("t0", "t1"), pulse_sharpness=50
+        )
+        steepness = sympy.Float(50) / (t1 - t0)
+        expected = k1 * (sympy.tanh(steepness * (t - t0)) - sympy.tanh(steepness * (t - t1))) / 2 + other
+        assert sympy.simplify(window_terms["A"] - expected) == 0
+        # Without it the SBML steepness is kept
+        _, window_terms = ode_builder.split_window_terms([pulse, k1, k1], ("t0", "t1"))
+        assert sympy.simplify(window_terms["A"] - pulse) == 0
+
+    def test_split_window_terms_errors(self, ode_builder):
+        """Test that state-dependent or missing window terms are rejected"""
+        A, k1, t0 = sympy.symbols("A k1 t0")
+        with pytest.raises(ValueError, match="depend on the state"):
+            ode_builder.split_window_terms([k1 * A * t0, k1, k1], ("t0", "t1"))
+        with pytest.raises(ValueError, match="No rate reads"):
+            ode_builder.split_window_terms([k1 * A, k1, k1], ("t0", "t1"))
+
     def test_get_species_count(self, ode_builder):
         """Test getting species count"""
         assert ode_builder.get_species_count() == 3
@@ -14,6 +14,7 @@ from sbmlParser.parser import (
     ParseRule,
     ParseEvent,
     ParseInitialAssignment,
+    ParseTimeUnits,
 )
 from sbmlParser import dataclasses
 
@@ -30,6 +31,7 @@ class TestParseParameterAssignment:
         param.setName("rate_constant")
         param.setValue(0.5)
         param.setConstant(True)
+        param.setUnits("per_hour")
 
         result = ParseParameterAssignment(0, param)
 
@@ -37,6 +39,7 @@ class TestParseParameterAssignment:
         assert result.name == "rate_constant"
         assert result.value == 0.5
         assert result.isConstant is True
+        assert result.units == "per_hour"
 
     def test_parse_parameter_without_name(self):
         """Test parsing a parameter without a name"""
@@ -51,6 +54,7 @@ class TestParseParameterAssignment:
         assert result.Id == "k2"
         assert result.name == ""
         assert result.value == 1.0
+        assert result.units is None
 
     def test_parse_parameter_without_id_raises_exception(self):
         """Test that parameter without ID raises exception"""
@@ -144,6 +148,20 @@ class TestParseSpecies:
 
         assert result.isBoundarySpecies is True
 
+    def test_species_units_default_to_model_substance_units(self):
+        """Test that species without substanceUnits take the model's"""
+        doc = libsbml.SBMLDocument(3, 2)
+        model = doc.createModel()
+        model.setSubstanceUnits("mmole")
+        plain = model.createSpecies()
+        plain.setId("S5")
+        own = model.createSpecies()
+        own.setId("S6")
+        own.setSubstanceUnits("mole")
+
+        assert ParseSpecies(0, plain).units == "mmole"
+        assert ParseSpecies(1, own).units == "mole"
+
 
 class TestParseCompartment:
     """Tests for ParseCompartment function"""
@@ -180,6 +198,17 @@ class TestParseCompartment:
         assert result.Id == "comp2"
         assert result.size is None
 
+    def test_compartment_units_default_to_model_volume_units(self):
+        """Test that volumes without units take the model's volumeUnits"""
+        doc = libsbml.SBMLDocument(3, 2)
+        model = doc.createModel()
+        model.setVolumeUnits("litre")
+        comp = model.createCompartment()
+        comp.setId("comp3")
+        comp.setSpatialDimensions(3)
+
+        assert ParseCompartment(0, comp).units == "litre"
+
 
 class TestParseFunction:
     """Tests for ParseFunction function"""
@@ -308,19 +337,21 @@ class TestParseRule:
         assert result.Id == "rule2"
         assert result.variable == "x"
 
-    def test_parse_algebraic_rule_raises_exception(self):
-        """Test that algebraic rules raise exception"""
+    def test_parse_algebraic_rule(self):
+        """Test parsing an algebraic rule, which names no variable"""
         doc = libsbml.SBMLDocument(3, 2)
         model = doc.createModel()
         rule = model.createAlgebraicRule()
-        rule.setId("rule3")
-        math_ast = libsbml.parseL3Formula("x + y = 0")
+        rule.setIdAttribute("rule3")
+        math_ast = libsbml.parseL3Formula("x + y - T")
         rule.setMath(math_ast)
 
-        with pytest.raises(
-            Exception, match="Algebraic rules are currently not supported"
-        ):
-            ParseRule(0, rule)
+        result = ParseRule(0, rule)
+
+        assert isinstance(result, dataclasses.AlgebraicRuleData)
+        assert result.Id == "rule3"
+        assert result.variable is None
+        assert "<apply>" in result.math
 
 
 class TestParseEvent:
@@ -376,6 +407,40 @@ class TestParseInitialAssignment:
         assert result.math is not None
 
 
+class TestParseTimeUnits:
+    """Tests for ParseTimeUnits function"""
+
+    def test_parse_declared_time_units(self):
+        """Test that a unit definition of 3600 s is read as an hour"""
+        doc = libsbml.SBMLDocument(3, 2)
+        model = doc.createModel()
+        definition = model.createUnitDefinition()
+        definition.setId("HR")
+        unit = definition.createUnit()
+        unit.setKind(libsbml.UNIT_KIND_SECOND)
+        unit.setExponent(1)
+        unit.setScale(0)
+        unit.setMultiplier(3600)
+        model.setTimeUnits("HR")
+
+        assert ParseTimeUnits(model) == {"id": "HR", "seconds": 3600.0}
+
+    def test_parse_base_time_unit(self):
+        """Test that the base unit second needs no definition"""
+        doc = libsbml.SBMLDocument(3, 2)
+        model = doc.createModel()
+        model.setTimeUnits("second")
+
+        assert ParseTimeUnits(model) == {"id": "second", "seconds": 1.0}
+
+    def test_undeclared_time_units_are_unknown(self):
+        """Test that a model without time units is not taken to be in seconds"""
+        doc = libsbml.SBMLDocument(3, 2)
+        model = doc.createModel()
+
+        assert ParseTimeUnits(model) == {"id": None, "seconds": None}
+
+
 class TestParseSBMLFile:
     """Tests for ParseSBMLFile function"""
 
@@ -0,0 +1,73 @@
+"""Tests for forward sensitivity code generation"""
+
+import pytest
+import sympy
+from codegen.code_generator import WASM_EXPORT, RustBlockGenerator
+from codegen.sensitivity_generator import SensitivityCodeGenerator
+from symbolic.optimizer import SymbolicOptimizer
+
+
+class TestSensitivityCodeGenerator:
+    """Tests for SensitivityCodeGenerator class"""
+
+    @pytest.fixture
+    def generator(self):
+        """Create a sensitivity generator for testing"""
+        return SensitivityCodeGenerator(RustBlockGenerator().code_gen, SymbolicOptimizer())
+
+    @pytest.fixture
+    def decay_model(self):
+        """Create A -> B with rate k*A/V, where V = BM*scV is a parameter rule"""
+        a, b, k, v = sympy.symbols("A B k V")
+        return {
+            "ode_system": [-k * a / v, k * a / v],
+            "jacobian_elements": [-k / v, k / v],
+            "jac_indices": [(0, 0), (1, 0)],
+            "species_map": {"A": 0, "B": 1},
+            "parameters": ["k", "BM", "scV", "Dose"],
+            "parameter_bindings": [
+                ("V", sympy.Symbol("BM") * sympy.Symbol("scV")),
+                ("A", sympy.Symbol("Dose")),
+            ],
+            "initial_values": {"A": sympy.Symbol("A"), "B": 0.0},
+            "model_type": "DecayModel",
+        }
+
+    def test_closures_read_parameters_from_p(self, generator, decay_model):
+        """Test that parameter rules are inlined and parameters read through theta"""
+        code = generator.generate_sensitivity_function(**decay_model)["sensitivity_fn"]
+        rhs = code.split("let rhs = ")[1].split("};")[0]
+        assert "let k = theta(p, 0);" in rhs
+        assert "let BM = theta(p, 1);" in rhs
+        assert "let V = " not in rhs
+        assert 'pub const SENSITIVITY_PARAMETERS: &[&str] = &["k", "BM", "scV", "Dose"];' in code
+
+    def test_parameter_derivatives_restricted_to_wrt(self, generator, decay_model):
+        """Test that df/dp columns only contribute for parameters placed in p"""
+        code = generator.generate_sensitivity_function(**decay_model)["sensitivity_fn"]
+        rhs_sens = code.split("let rhs_sens = ")[1].split("    };")[0]
+        for j in range(3):
+            assert f"if let Some(k) = slot[{j}] {{" in rhs_sens
+        # Dose only enters through the initial condition
+        assert "slot[3]" not in rhs_sens
+
+    def test_initial_condition_sensitivity(self, generator, decay_model):
+        """Test that initial assignments seed y0 and its derivative unless overridden"""
+        code = generator.generate_sensitivity_function(**decay_model)["sensitivity_fn"]
+        assert "y[0] = sim_params.init_A.unwrap_or(Dose);" in code
+        assert "if let (None, Some(k)) = (sim_params.init_A, slot[3]) {" in code
+        assert ".rhs_sens_implicit(rhs, jac, rhs_sens)" in code
+        assert ".init_sens(init, init_sens, 2)" in code
+
+    def test_unresolved_symbol_rejected(self, generator, decay_model):
+        """Test that equations reading an unknown symbol raise ValueError"""
+        decay_model["parameter_bindings"] = []
+        with pytest.raises(ValueError, match="V"):
+            generator.generate_sensitivity_function(**decay_model)
+
+    def test_wasm_export(self, generator, decay_model):
+        """Test that the entry point is exported for WASM builds only"""
+        wasm = generator.generate_sensitivity_function(**decay_model)["sensitivity_fn"]
+        native = generator.generate_sensitivity_function(**decay_model, wasm=False)["sensitivity_fn"]
+        assert WASM_EXPORT + "pub fn run_simulation_with_sensitivities(" in wasm
+        assert "wasm_bindgen" not in native
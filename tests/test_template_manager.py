@@ -0,0 +1,775 @@
+"""Tests for Rust file assembly"""
+
+import re
+from pathlib import Path
+
+import sympy
+from codegen.code_generator import WASM_EXPORT, RustBlockGenerator
+from codegen.template_manager import SIMULATION_OPTIONS, RustTemplateManager, typescript_type
+
+def build_components(species_list=None, params=None, compartments=None):
+    """Build a minimal set of code blocks for a two-species model"""
+    species_list = species_list or ["A", "B"]
+    params = params if params is not None else {"k1": 0.5}
+    compartments = compartments if compartments is not None else {"comp1": 1.0}
+    species_map = {s: i for i, s in enumerate(species_list)}
+    initial_amounts = {s: 1.0 if i == 0 else 0.0 for i, s in enumerate(species_list)}
+
+    generator = RustBlockGenerator()
+    manager = RustTemplateManager()
+    species_fields, param_fields = manager.generate_struct_fields(
+        species_list, params, compartments, initial_amounts
+    )
+    k1, a = sympy.Symbol("k1"), sympy.Symbol(species_list[0])
+    parameter_names = list(params) + [c for c in compartments if c not in params]
+    parameter_index = {name: i for i, name in enumerate(parameter_names)}
+
+    return {
+        "species_fields": species_fields,
+        "param_fields": param_fields,
+        "param_extract": generator.generate_parameter_extraction(params, compartments),
+        "parameter_order": ", ".join(
+            f'"{name}"' for name in parameter_names + [f"init_{s}" for s in species_list]
+        ),
+        "parameter_values": generator.generate_parameter_values(
+            parameter_names + generator.generate_initial_values(species_list, initial_amounts)
+        ),
+        "param_lookups": generator.generate_parameter_lookups(parameter_index, {"k1"}),
+        "rhs_param_arg": "p" if "k1" in parameter_index else "_p",
+        "species_extract": generator.generate_species_extraction(species_map),
+        "temp_vars": "",
+        "rhs_block": generator.generate_derivatives([-k1 * a, k1 * a]),
+        "jac_block": generator.generate_jacobian([-k1, k1], [(0, 0), (1, 0)]),
+        "observables_fn": generator.generate_observables_function(species_map, [], [], {}),
+        "observable_ids": generator.generate_observable_ids([]),
+        "init_block": generator.generate_init_function(
+            len(species_list), len(parameter_names)
+        ),
+        "parameter_checks": generator.generate_parameter_checks(
+            [(name, i, "NonNegative", []) for i, name in enumerate(parameter_names)]
+        ),
+        "volumes_fn": generator.generate_volume_function(species_map, {}, [], parameter_index),
+        "mass_fn": generator.generate_mass_function(
+            species_map, {s: sympy.Integer(1) for s in species_list}, parameter_index
+        ),
+        "result_keys": generator.generate_result_keys(species_list),
+        "state_parameters": generator.generate_state_parameters(
+            [["k1"]] * len(species_list)
+        ),
+        "n_species": len(species_list),
+        "metadata_functions": generator.generate_metadata_functions(
+            "test_model", species_list, initial_amounts, params, compartments
+        ),
+        "equations_fn": generator.generate_equations_function(
+            species_list, [-k1 * sympy.Symbol(s) for s in species_list], []
+        ),
+        "sbml_fn": generator.generate_sbml_function(None, "this model was not generated from an SBML file"),
+        "types_fn": RustTemplateManager().generate_types_function(
+            "test_model", species_list, params, compartments, []
+        ),
+    }
+
+
+class TestRustTemplateManager:
+    """Tests for RustTemplateManager class"""
+
+    def test_assemble_contains_entry_point(self):
+        """Test that the assembled file exposes run_simulation"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "pub fn run_simulation(params: &str) -> String" in code
+        assert "pub struct SimulationParams" in code
+
+    def test_native_build_has_no_wasm_bindgen(self):
+        """Test that native output does not depend on wasm_bindgen"""
+        code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert "wasm_bindgen" not in code
+
+    def test_wasm_glue_behind_feature(self):
+        """Test that WASM output builds without its JS glue and exports a C ABI behind c-api"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        lines = code.split("\n")
+        # Only wasm32 gets the glue, so a native binary can use the file with `wasm` on
+        glue = '#[cfg(all(feature = "wasm", target_arch = "wasm32"))]'
+        assert lines[lines.index("use wasm_bindgen::prelude::*;") - 1] == glue
+        for i, line in enumerate(lines):
+            if line == "#[wasm_bindgen]":
+                assert lines[i - 1] == glue, lines[i + 1]
+        assert WASM_EXPORT + "pub fn run_simulation(params: &str) -> String {" in code
+        assert WASM_EXPORT == '#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]\n'
+        # Progress reporting is the core's, so no generated item depends on the glue
+        assert "fn simulate_runs(" not in code
+        c_api = code.split("fn wasm_pk_run_simulation")[1].split("\n}\n")[0]
+        assert "Err(e) => result_to_json(failed_result(SimulationError::invalid(e)), call_start)," in c_api
+        for name in ["wasm_pk_run_simulation", "wasm_pk_get_default_parameters", "wasm_pk_free_string"]:
+            assert f'#[cfg(feature = "c-api")]\n#[no_mangle]\npub ' in code.split(f'extern "C" fn {name}(')[0][-80:]
+        native = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=False)
+        assert "fn wasm_pk_" not in native
+
+    def test_minimal_feature_gates_metadata(self):
+        """Test that the minimal feature compiles out the metadata exports but not the defaults"""
+        types = RustTemplateManager().generate_types_function("test_model", ["A"], {}, {}, [], wasm=True)
+        assert f'#[cfg(not(feature = "minimal"))]\n{WASM_EXPORT}pub fn get_types_ts() -> String {{' in types
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        module = code.split('#[cfg(feature = "python")]\nmod python {')[1]
+        assert '        #[cfg(not(feature = "minimal"))]\n        m.add_function(wrap_pyfunction!(get_species_info, m)?)?;' in module
+        native = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=False)
+        assert "minimal" not in native
+
+    def test_failures_are_errors_not_panics(self):
+        """Test that the problem builder and solver calls report errors instead of unwrapping"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert '#[cfg(all(feature = "wasm", target_arch = "wasm32"))]\n#[wasm_bindgen(start)]\npub fn init_panic_hook() {' in code
+        assert "console_error_panic_hook::set_once();" in code
+        native = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=False)
+        assert "console_error_panic_hook" not in native
+
+    def test_log_level(self):
+        """Test that console_log! checks a level set_log_level changes, quiet by default in release"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert WASM_EXPORT + "pub fn set_log_level(level: &str) -> Result<(), String> {" in code
+        assert "console_log!(" not in code.replace("console_log!(Error,", "").replace("console_log!(Info,", "").replace(
+            "console_log!(Debug,", ""
+        )
+        assert "super::set_log_level(level).map_err(PyValueError::new_err)" in code
+        assert 'pub unsafe extern "C" fn set_log_level(level: *const u8, level_len: usize) -> i32 {' in code
+        native = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=False)
+        assert "set_log_level" not in native
+        # The shared loop logs through the core, to stderr in native builds
+        assert "Starting simulation" not in native
+
+    def test_wasi_exports(self):
+        """Test that WASM output exports pointer/length functions behind the wasi feature"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        module = code.split('#[cfg(feature = "wasi")]\nmod wasi {')[1].split("\n}\n")[0]
+        assert (
+            '#[no_mangle]\n    pub unsafe extern "C" fn run_simulation(params: *const u8, params_len: usize, out_len: *mut usize) -> *mut u8 {'
+            in module
+        )
+        assert '#[no_mangle]\n    pub unsafe extern "C" fn get_default_parameters(out_len: *mut usize) -> *mut u8 {' in module
+        assert 'pub extern "C" fn wasm_pk_alloc(len: usize) -> *mut u8 {' in module
+        assert 'pub unsafe extern "C" fn wasm_pk_free(ptr: *mut u8, len: usize) {' in module
+        assert 'compile_error!("features `wasm` and `wasi`' in code
+        native = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=False)
+        assert "mod wasi" not in native
+
+    def test_python_module(self):
+        """Test that WASM output carries a PyO3 module behind the python feature"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        module = code.split('#[cfg(feature = "python")]\nmod python {')[1].split("\n}\n")[0]
+        assert "fn sbml_model(m: &Bound<'_, PyModule>) -> PyResult<()> {" in module
+        for name in ["run_simulation", "run_simulation_batch", "get_default_parameters", "get_model_sbml"]:
+            assert f"m.add_function(wrap_pyfunction!({name}, m)?)?;" in module
+        assert "loads(py, &super::run_simulation(&params))" in module
+        native = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=False)
+        assert "pyo3" not in native
+
+    def test_partial_params_merged_with_defaults(self):
+        """Test that user parameters are applied over get_default_parameters()"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        impl = code.split("impl PkModel for TestModel {")[1].split("\n}\n")[0]
+        assert "        serde_json::from_str(&get_default_parameters()).unwrap()\n" in impl
+        # The merge itself is the core's resolve_params, generic over the model
+        assert "resolve_params::<TestModel>(overrides)" in code
+        assert "fn merge_params" not in code
+
+    def test_result_echoes_resolved_parameters(self):
+        """Test that the result carries the parameters actually used"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "let resolved = serde_json::to_value(with_defaults(sim_params.clone())).unwrap();" in code
+
+    def test_include_parameters(self):
+        """Test that defaults are filled in and the echo can be turned off"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        defaults = code.split("fn with_defaults")[1].split("\n}\n")[0]
+        assert "sim_params.options = sim_params.options.with_defaults(&MODEL);" in defaults
+
+    def test_result_units(self):
+        """Test that results label their series and time with units"""
+        components = build_components()
+        components["model_time_unit"] = "HR"
+        code = RustTemplateManager().assemble_rust_file("test_model", components)
+        assert 'const MODEL_TIME_UNIT: Option<&str> = Some("HR");' in code
+        assert "series_units: &SERIES_UNITS," in code
+        types = RustTemplateManager().generate_types_function("test_model", ["A"], {}, {}, [])
+        assert "  units: Record<string, string | null>;" in types
+        native = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=False)
+        assert "const MODEL_TIME_UNIT: Option<&str> = None;" in native
+
+    def test_output_kind(self):
+        """Test that amounts and concentrations are computed only on request"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "species_volumes: &SPECIES_VOLUMES," in code
+        types = RustTemplateManager().generate_types_function("test_model", ["A"], {}, {}, [])
+        assert 'export type OutputKind = "amount" | "concentration" | "both";' in types
+        assert "  output_kind?: OutputKind | null;" in types
+
+    def test_timing(self):
+        """Test that results time the call with performance.now() on wasm32 and Instant natively"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "result_to_js(TestModel::simulate_run(sim_params), call_start)" in code
+        assert "result_to_js(result, call_start)" in code
+        types = RustTemplateManager().generate_types_function("test_model", ["A"], {}, {}, [])
+        assert "  timing: Timing;" in types
+        native = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=False)
+        assert "web_sys" not in native
+        # The string entry points are timed inside the core
+        assert "now_ms()" not in native
+
+    def test_output_times_grid(self):
+        """Test that a caller-supplied output grid is validated and interpolated"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "pub fn run_simulation_at_times(params: &str, times: &[f64]) -> String" in code
+
+    def test_output_dt_grid(self):
+        """Test that output_dt is a generated option recorded on an interpolated grid"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "output_dt?: number | null;" in code
+
+    def test_observables_opt_in(self):
+        """Test that assignment-rule observables are only evaluated on request"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "observables: &OBSERVABLE_IDS," in code
+
+    def test_events_reinitialise_solver_at_root(self):
+        """Test that a found root rewinds to the root, applies events and restarts"""
+        components = build_components()
+        components["event_fns"] = "    let eval_triggers = |_y: &[f64], _t: f64, _p: &[f64]| vec![false];\n"
+        code = RustTemplateManager().assemble_rust_file("test_model", components)
+        assert "        apply_events,\n" in code
+
+    def test_events_read_triggers_at_root(self):
+        """Test that triggers are read at the root and a hidden stop time is replayed"""
+        components = build_components()
+        components["event_fns"] = "    let eval_triggers = |_y: &[f64], _t: f64, _p: &[f64]| vec![false];\n"
+        code = RustTemplateManager().assemble_rust_file("test_model", components)
+        assert "triggers: eval_triggers," in code
+
+    def test_solver_failure_diagnostics(self):
+        """Test that a failed step reports the state and a hint, and setup failures do not panic"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "state_parameters: &STATE_PARAMETERS," in code
+
+    def test_solver_stats(self):
+        """Test that each run reports its solver stats and the last ones stay readable"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "pub fn get_last_run_stats() -> String {" in code
+
+    def test_last_run_stats_exported_to_wasm(self):
+        """Test that the last run stats accessor is exported in WASM builds"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=True)
+        assert WASM_EXPORT + "pub fn get_last_run_stats() -> String {" in code
+
+    def test_sparse_jacobian(self):
+        """Test that sparse_jacobian solves with the sparse matrix type of the core crate"""
+        dense = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        sparse = RustTemplateManager().assemble_rust_file("test_model", build_components(), sparse_jacobian=True)
+        assert "        BuiltProblem::dense(MODEL, equations())\n" in dense
+        assert "        BuiltProblem::sparse(MODEL, equations())\n" in sparse
+        # The closures take slices, so one model body serves both matrix types
+        assert "&V" not in sparse
+
+    def test_jacobian_matrix(self):
+        """Test that a model with the Jacobian entries hands the solver its matrix"""
+        components = build_components()
+        products = RustTemplateManager().assemble_rust_file("test_model", components)
+        components["jacobian_pattern"] = "const JACOBIAN_PATTERN: &[(usize, usize)] = &[\n    (0, 0),\n];"
+        components["jac_values_block"] = "        values[0] = (-k1);"
+        matrix = RustTemplateManager().assemble_rust_file("test_model", components)
+        assert "        jac_matrix: None,\n" in products
+        assert "fn jac_matrix(" not in products
+        assert "        jac_matrix: Some(JacobianMatrix { pattern: JACOBIAN_PATTERN, values: &jac_matrix }),\n" in matrix
+        assert "fn jac_matrix(y: &[f64], _p: &[f64], t: f64, values: &mut [f64]) {\n" in matrix
+        assert "\n    values[0] = (-k1);\n}\n" in matrix
+        # jac stays, for the products the solver still takes
+        assert "    fn jac(" in matrix
+
+    def test_jacobian_mode(self):
+        """Test that the jacobian option switches J·v to finite differences of the rhs"""
+        ts = RustTemplateManager().generate_types_function("test_model", ["A"], {}, {}, [])
+        assert 'export type JacobianMode = "analytic" | "finite_difference";' in ts
+        assert "  jacobian?: JacobianMode | null;" in ts
+
+    def test_generated_models_carry_no_native_tests(self):
+        """Test that the per-model checks live in the core's tests, not in every generated file"""
+        for wasm in (True, False):
+            code = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=wasm)
+            assert "mod generated_tests" not in code
+            assert "sha2::" not in code
+
+    def test_mass_balance(self):
+        """Test that the mass balance is computed on request and reported in diagnostics"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "let eval_mass = |y: &[f64], _p: &[f64]| -> f64 {" in code
+        assert "mass: eval_mass," in code
+
+    def test_parameter_validation(self):
+        """Test that parameter ranges are checked on request and refuse the run on errors"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "const PARAMETER_CHECKS: &[ParameterCheck] = &[" in code
+        assert '    ("k1", 0, ParameterRange::NonNegative, &[]),' in code
+        assert "    parameter_checks: PARAMETER_CHECKS," in code
+        # The ranges are checked by the core, generic over the model
+        assert "    wasm_pk_core::validate_parameters::<TestModel>(params)\n" in code
+        assert "fn check_parameters" not in code
+
+        wasm_code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=True
+        )
+        assert WASM_EXPORT + "pub fn validate_parameters(params: &str) -> String {" in wasm_code
+
+    def test_event_log(self):
+        """Test that fired events and applied doses are logged in the result"""
+        components = build_components()
+        components["event_fns"] = "    let eval_triggers = |_y: &[f64], _t: f64, _p: &[f64]| vec![false];\n"
+        code = RustTemplateManager().assemble_rust_file("test_model", components)
+        assert "event_ids: &EVENT_IDS," in code
+        assert "event_initial_values: &EVENT_INITIAL_VALUES," in code
+        assert "event_persistent: &EVENT_PERSISTENT," in code
+
+    def test_roots_are_recorded_and_the_run_continues(self):
+        """Test that a root that fires nothing is recorded and events do not end the run"""
+        components = build_components()
+        components["event_fns"] = "    let eval_triggers = |_y: &[f64], _t: f64, _p: &[f64]| vec![false];\n"
+        code = RustTemplateManager().assemble_rust_file("test_model", components, wasm=False)
+        # A root on the stop would record that time twice
+        assert "eval_triggers" in code
+
+    def test_no_event_arm_without_events(self):
+        """Test that models without events keep the plain root arm"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "roots: no_roots," in code
+        assert "triggers: no_triggers," in code
+        assert "eval_triggers" not in code
+
+    def test_uptake_windows(self):
+        """Test that uptake windows are validated, read by the RHS and stop the solver"""
+        components = build_components()
+        components["window_terms"] = (
+            "        let uptake_A = window_sum(windows, k1, k1, |k1, _| k1);"
+        )
+        components["window_repeat"] = 'repeated_windows(p[0], 0.5, ("k2", p[1]), ("V", p[2]))'
+        code = RustTemplateManager().assemble_rust_file("test_model", components)
+        assert "pub uptake_windows: Option<Vec<UptakeWindow>>," in code
+        assert "let uptake_A = window_sum(windows, " in code.split("    fn rhs(")[1].split("\n    }\n")[0]
+        assert ": f64, windows: &[UptakeWindow], dy: &mut [f64]) {" in code
+        # Without explicit windows the model's own window repeats from its parameters
+        assert 'None => repeated_windows(p[0], 0.5, ("k2", p[1]), ("V", p[2])),' in code
+        assert "uptake_windows: uptake_windows(&sim_params, &p)?," in code
+        # and is checked before a run starts
+        assert "        uptake_windows(sim_params, &parameter_vector(sim_params))?;\n" in code
+        assert "uptake_schedule" not in code
+        # Each run hands its windows to the rhs, with no state shared across runs
+
+    def test_no_uptake_windows_by_default(self):
+        """Test that models without a window keep the plain dose stops"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "Option<Vec<UptakeWindow>>" not in code
+        assert "window_sum" not in code
+        assert ": f64, _windows: &[UptakeWindow], dy: &mut [f64]) {" in code
+        assert "uptake_windows: vec![]," in code
+        assert "fn uptake_windows(" not in code
+        assert "repeated_windows" not in code
+
+    def test_init_overrides_for_every_state(self):
+        """Test that each state gets an init_* field even without SBML amounts"""
+        _, param_fields = RustTemplateManager().generate_struct_fields(
+            ["Cve", "Aurine"], {"k1": 0.5}, {}
+        )
+        assert "pub init_Cve: Option<f64>," in param_fields
+        assert "pub init_Aurine: Option<f64>," in param_fields
+
+    def test_species_info_lists_overrides(self):
+        """Test that get_species_info exposes defaults and their override field"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        species_fn = code.split("pub fn get_species_info()")[1].split("\n}\n")[0]
+        assert 'SpeciesInfo { id: "A", initial_amount: 1.0, override_field: "init_A",' in species_fn
+
+    def test_compartments_info_generated(self):
+        """Test that get_compartments_info is generated"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "pub fn get_compartments_info() -> String" in code
+
+    def test_types_ts_lists_model_fields(self):
+        """Test that get_types_ts declares every SimulationParams field and the species keys"""
+        code = RustTemplateManager().generate_types_function(
+            "test_model", ["Agut", "Cplasma"], {"ka": 0.5}, {"plasma": 3.0}, ["conc"], has_windows=False, wasm=True
+        )
+        assert WASM_EXPORT + "pub fn get_types_ts() -> String {" in code
+        assert 'export type SpeciesId = "Agut" | "Cplasma";' in code
+        assert 'export type ObservableId = "conc";' in code
+        params = code.split("export interface SimulationParams {\n")[1].split("\n}")[0]
+        assert "  ka?: number;\n  plasma?: number;\n  init_Agut?: number | null;" in params
+        assert "  doses?: Dose[] | null;" in params
+        assert "  solver?: SolverMethod | null;" in params
+        assert "uptake_windows" not in code
+        assert "species: Record<SpeciesId, number[]>;" in code
+
+    def test_types_ts_follows_struct_options(self):
+        """Test that SimulationParams in TypeScript lists the model's fields, then the option table"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components(), wasm=False)
+        struct = code.split("pub struct SimulationParams {\n")[1].split("\n}\n")[0]
+        rust_fields = re.findall(r"^    pub (\w+):", struct, re.M)
+        assert rust_fields.pop() == "options"
+        rust_fields += [name for name, _, _ in SIMULATION_OPTIONS if name and name != "uptake_windows"]
+        ts = code.split("export interface SimulationParams {\n")[1].split("\n}")[0]
+        # The core's types_ts_match_json checks the table against SimulationOptions
+        assert re.findall(r"^  (\w+)\?:", ts, re.M) == rust_fields
+
+    def test_typescript_type(self):
+        """Test translating option field types to TypeScript"""
+        assert typescript_type("Option<f64>") == "number | null"
+        assert typescript_type("Option<Vec<String>>") == "string[] | null"
+        assert typescript_type("Option<SolverMethod>") == "SolverMethod | null"
+        assert typescript_type("bool") == "boolean"
+
+    def test_types_ts_without_observables(self):
+        """Test that a model without observables gets an empty ObservableId union"""
+        code = RustTemplateManager().generate_types_function("test_model", ["A"], {}, {}, [], has_windows=True)
+        assert "export type ObservableId = never;" in code
+        assert "  uptake_windows?: UptakeWindow[] | null;" in code
+        assert "export interface UptakeWindow {" in code
+        assert "uptake_schedule" not in code
+
+    def test_embedded_sbml_export(self):
+        """Test that get_model_sbml is generated; the core's tests hash it against get_model_metadata"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "pub fn get_model_sbml() -> Result<String, String>" in code
+        assert "fn sha256_hex" not in code
+
+    def test_model_equations_when_generated(self):
+        """Test that get_model_equations is emitted only when the block is present"""
+        components = build_components()
+        code = RustTemplateManager().assemble_rust_file("test_model", components)
+        assert "pub fn get_model_equations() -> String" in code
+        del components["equations_fn"]
+        code = RustTemplateManager().assemble_rust_file("test_model", components)
+        assert "fn get_model_equations()" not in code
+
+    def test_metadata_always_emitted(self):
+        """Test that the metadata accessors are generated"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        for name in ["get_model_metadata", "get_parameters_info", "get_species_info",
+                     "get_compartments_info", "get_default_parameters"]:
+            assert f"pub fn {name}() -> String" in code
+
+    def test_species_serialized_in_sbml_order(self):
+        """Test that species are kept in SBML order and the order is pinned"""
+        code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(species_list=["Z", "A"]), wasm=False
+        )
+        assert 'const SPECIES_IDS: &[&str] = &["Z", "A"];' in code
+        assert code.index('SpeciesInfo { id: "Z"') < code.index('SpeciesInfo { id: "A"')
+
+    def test_result_keys_are_sbml_ids(self):
+        """Test that result keys keep the exact (mixed-case) SBML ids"""
+        code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(species_list=["QSkin_sc_u", "Aplasma"]),
+            wasm=False
+        )
+        assert 'const SPECIES_IDS: &[&str] = &["QSkin_sc_u", "Aplasma"];' in code
+        assert 'SpeciesInfo { id: "QSkin_sc_u", initial_amount: 1.0, override_field: "init_QSkin_sc_u",' in code
+
+    def test_js_object_entry_point(self):
+        """Test that WASM builds accept and return JS objects without JSON strings"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "pub fn run_simulation_js(params: JsValue) -> Result<JsValue, JsValue>" in code
+        assert "serde_wasm_bindgen::from_value(params)" in code
+        assert "resolve_params::<TestModel>(overrides).map_err(JsValue::from)" in code
+        # The string API shares parsing and the solver loop
+        assert "wasm_pk_core::simulate::<TestModel>(params)" in code
+        assert "result_to_js(TestModel::simulate_run(sim_params), call_start)" in code
+
+    def test_js_object_entry_point_wasm_only(self):
+        """Test that native builds do not get the JsValue entry point"""
+        code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert "run_simulation_js" not in code
+        assert "serde_wasm_bindgen" not in code
+
+    def test_async_entry_point(self):
+        """Test that run_simulation_async shares the solver loop and yields every chunk"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert (
+            "pub async fn run_simulation_async(params: JsValue, chunk_steps: Option<usize>)"
+            " -> Result<JsValue, JsValue> {"
+        ) in code
+        assert "let chunk_steps = chunk_steps.unwrap_or(DEFAULT_CHUNK_STEPS).max(1);" in code
+        assert "TestModel::build_problem().solve(vec![prepare_run(sim_params)], |_| {}, pause).await" in code
+        assert "fn set_timeout(handler: &js_sys::Function, delay: i32);" in code
+        assert "wasm_bindgen_futures::JsFuture::from(tick).await;" in code
+        # The blocking paths are PkModel's, which run the same loop with pauses that never wait
+        assert "fn simulate_runs(" not in code
+        assert '#[cfg(all(test, feature = "wasm", target_arch = "wasm32"))]\nmod browser_tests {' in code
+        assert "let result = run_simulation_async(params, Some(7)).await.unwrap();" in code
+
+    def test_browser_tests_cross_the_js_boundary(self):
+        """Test that WASM builds test the exports through JS strings and objects, in a browser or Node"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert '    #[cfg(not(feature = "node-tests"))]\n    wasm_bindgen_test_configure!(run_in_browser);' in code
+        assert "        let params = js_sys::JSON::parse(&get_default_parameters()).unwrap();\n" in code
+        assert "        TestModel::init(&p, 0.0, &mut y);\n" in code
+        assert 'assert!(run_simulation_js(JsValue::from_str("{")).is_err());' in code
+        assert 'let output = run_simulation(r#"{"max_step": 0.001}"#);' in code
+        assert "node-tests = []" in (Path(__file__).parent.parent / "build_wasm.sh").read_text()
+
+    def test_async_entry_point_wasm_only(self):
+        """Test that native builds keep the synchronous loop without JS dependencies"""
+        code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert "run_simulation_async" not in code
+        assert "js_sys" not in code
+        assert "browser_tests" not in code
+        assert "fn equations() -> Equations<'static> {" in code
+        assert "fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {" in code
+
+    def test_progress_callback(self):
+        """Test that every WASM entry point reports progress through the core's callback"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert (
+            "pub fn set_progress_callback(callback: Option<js_sys::Function>,"
+            " interval_ms: Option<f64>, every_steps: Option<usize>) {\n"
+            "    wasm_pk_core::set_progress_callback(callback, interval_ms, every_steps)\n}"
+        ) in code
+        # The async loop reports through the core's reporter, as the core's blocking loop does
+        assert code.count("let mut reporter = ProgressReporter::default();") == 1
+        assert "let go_on = reporter.report(fraction, steps);" in code
+        assert "fn throwing_progress_callback_is_ignored() {" in code
+        assert "PROGRESS_CALLBACK" not in code
+
+        native = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert "ProgressReporter" not in native
+
+    def test_parameter_errors_name_the_field(self):
+        """Test that parameters are parsed by the core, which names the offending field"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "fn parse_params" not in code
+        assert "fn deserialize_params" not in code
+        assert "    wasm_pk_core::simulate::<TestModel>(params)\n" in code
+
+    def test_errors_go_through_simulation_error(self):
+        """Test that entry points report failures as the core's SimulationError"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        # The C API turns unreadable text into a failed result
+        assert "Err(e) => result_to_json(failed_result(SimulationError::invalid(e)), call_start)," in code
+        # The TS error type names the kind
+        assert "  field: string | null;\n" in code
+        assert '"max_steps_exceeded" | "cancelled"' in code
+        assert "JsError::new(&e)" not in code
+
+    def test_numeric_strings_are_accepted(self):
+        """Test that number fields deserialize JSON numbers and numeric strings"""
+        manager = RustTemplateManager()
+        _, param_fields = manager.generate_struct_fields(["A"], {"k1": 0.5}, {})
+        assert '#[serde(deserialize_with = "lenient_f64::deserialize")]\n    pub k1: f64,' in param_fields
+        assert '#[serde(default, deserialize_with = "lenient_f64::option")]\n    pub init_A: Option<f64>,' in param_fields
+
+    def test_annotated_parameter_files(self):
+        """Test that parameter text reaches the core's comment and trailing comma stripper"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        # Every string entry point hands its text to the core unparsed
+        assert "from_json(" not in code
+        assert "    wasm_pk_core::run_simulation_batch::<TestModel>(params_list)\n" in code
+
+    def test_padded_parameter_text(self):
+        """Test that native builds check run_simulation on BOM- and space-padded text"""
+        manager = RustTemplateManager()
+        code = manager.assemble_rust_file("test_model", build_components(), wasm=False)
+        assert "fn padded_parameter_text_parses() {" not in code
+
+    def test_typed_array_result_handle(self):
+        """Test that WASM builds expose a handle returning typed arrays"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "pub struct SimulationHandle {" in code
+        assert "pub fn times(&self) -> Vec<f64> {" in code
+        assert "pub fn species(&self, name: &str) -> Result<Vec<f64>, JsError> {" in code
+        assert "pub fn species_names(&self) -> Vec<String> {" in code
+        assert "pub fn run_simulation_typed(params: JsValue) -> Result<SimulationHandle, JsValue> {" in code
+
+        native = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert "SimulationHandle" not in native
+
+    def test_model_session(self):
+        """Test that WASM builds expose the core's session, which keeps its parameters and problem"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "pub struct ModelSession {\n    session: Session<TestModel>,\n}" in code
+        assert "pub fn new(params: JsValue) -> Result<ModelSession, JsValue> {" in code
+        assert "pub fn set_parameter(&mut self, name: &str, value: f64) -> Result<(), JsValue> {" in code
+        assert "Ok(ModelSession { session: Session::new(params_from_js(params)?)? })" in code
+        assert "Ok(self.session.set_parameter(name, value)?)" in code
+        assert 'self.set_parameter("final_time", final_time)' in code
+        assert "pub fn run(&mut self) -> SimulationHandle {" in code
+        assert "SimulationHandle { result: self.session.run() }" in code
+        assert "fn session_runs_match_stateless_runs() {" in code
+
+        native = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert "ModelSession" not in native
+
+    def test_continue_simulation(self):
+        """Test that continuations are the core's, with the model's schedule shift"""
+        code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert (
+            "pub fn continue_simulation(previous_result: &str, new_params: &str,"
+            " additional_time: f64, concatenate: bool) -> Result<String, String> {\n"
+            "    wasm_pk_core::continue_simulation::<TestModel>(previous_result, new_params, additional_time, concatenate)\n}"
+        ) in code
+        assert "struct Trajectory" not in code
+
+        wasm_code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert WASM_EXPORT + "pub fn continue_simulation(" in wasm_code
+
+    def test_checkpoints(self):
+        """Test that every run leaves a checkpoint the core resumes on absolute time"""
+        code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert "pub fn export_checkpoint() -> String {" in code
+        assert (
+            "pub fn run_from_checkpoint(checkpoint: &str, params: &str) -> Result<String, String> {\n"
+            "    wasm_pk_core::run_from_checkpoint::<TestModel>(checkpoint, params)\n}"
+        ) in code
+        assert "CHECKPOINT_VERSION" not in code
+
+        wasm_code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert WASM_EXPORT + "pub fn export_checkpoint()" in wasm_code
+        assert WASM_EXPORT + "pub fn run_from_checkpoint(" in wasm_code
+
+    def test_time_unit(self):
+        """Test that time_unit converts each run to model time from the shared unit table"""
+        code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert "const MODEL_TIME_SECONDS: Option<f64> = None;" in code
+        assert "\n    let sim_params = in_model_time(sim_params);\n" in code
+
+        components = build_components()
+        components["model_time_seconds"] = 3600.0
+        code = RustTemplateManager().assemble_rust_file("test_model", components, wasm=False)
+        assert "const MODEL_TIME_SECONDS: Option<f64> = Some(3600.0);" in code
+
+    def test_csv_export(self):
+        """Test that run_simulation_csv is the core's CSV writer for the model"""
+        code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert "pub fn run_simulation_csv(params: &str) -> Result<String, String> {" in code
+        assert "    wasm_pk_core::run_simulation_csv::<TestModel>(params)\n" in code
+        assert "fn csv_has_one_row_per_time_point()" not in code
+
+    def test_binary_export(self):
+        """Test the little-endian f64 export, its layout header and decoder"""
+        code = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert 'const SPECIES_IDS: &[&str] = &["A", "B"];' in code
+        assert "pub fn run_simulation_binary(params: &str) -> Result<Vec<u8>, String> {" in code
+        assert "    wasm_pk_core::run_simulation_binary::<TestModel>(params)\n" in code
+        assert "pub fn get_binary_layout() -> String {" in code
+        assert "pub fn decode_binary_result(bytes: &[u8]) -> Result<(Vec<f64>, OrderedSeries), String> {" in code
+        assert "to_le_bytes" not in code
+
+    def test_batch_entry_point(self):
+        """Test that batch runs go through the core and the model's shared solver loop"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        batch_fn = code.split("pub fn run_simulation_batch")[1].split("\n}\n")[0]
+        assert "(params_list: &str) -> Result<String, String> {" in batch_fn
+        assert "    wasm_pk_core::run_simulation_batch::<TestModel>(params_list)" in batch_fn
+        # All runs share the problem of build_problem; rejected parameter sets become failed results
+        assert "    fn build_problem() -> BuiltProblem<'static> {" in code
+        assert "    fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {" in code
+        assert "println!" not in code
+
+    def test_pk_model_impl(self):
+        """Test that each model is a zero-sized type implementing PkModel with its own closures"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "pub struct TestModel;\n\nimpl PkModel for TestModel {" in code
+        impl = code.split("impl PkModel for TestModel {")[1].split("\n}\n")[0]
+        assert "    const N_STATES: usize = 2;" in impl
+        assert "    const INFO: ModelInfo = MODEL;" in impl
+        assert "        // Derivatives\n" in impl
+        assert "        // Jacobian-Vector Product\n" in impl
+        assert "    fn init(p: &[f64], _t: f64, y: &mut [f64]) {" in impl
+        assert "        serde_json::from_str(&get_default_parameters()).unwrap()" in impl
+        assert "        &mut sim_params.options\n" in impl
+        assert "rhs: TestModel::rhs," in code
+        assert "let rhs = |" not in code
+        assert RustTemplateManager.model_type("PBPK_BPA_model") == "PBPKBPAModel"
+        assert RustTemplateManager.model_type("talinolol") == "TalinololModel"
+        assert RustTemplateManager.model_type("2cmt") == "Model2cmtModel"
+
+    def test_algebraic_rules_in_types_and_tests(self):
+        """Test that models with algebraic rules get DAE-aware types and core tests"""
+        manager = RustTemplateManager()
+        types = manager.generate_types_function("test_model", ["A", "C"], {}, {}, [], algebraic_states=["C"])
+        assert "  algebraic_rules: Record<SpeciesId, string>;\n" in types
+        assert "algebraic_rules" not in manager.generate_types_function("test_model", ["A"], {}, {}, [])
+        components = build_components()
+        components["algebraic_states"] = "&[1]"
+        code = manager.assemble_rust_file("test_model", components, wasm=False)
+        assert "fn tr_bdf2_matches_bdf() {" not in code
+        # Algebraic states are listed apart, solved again after doses and resumes
+
+    def test_parameter_vector(self):
+        """Test that parameters reach the closures through the solver parameter vector"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert 'const PARAMETER_ORDER: &[&str] = &["k1", "comp1", "init_A", "init_B"];' in code
+        assert "pub fn get_parameter_order() -> String {" in code
+        assert "fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {" in code
+        assert "        sim_params.init_A.unwrap_or(1.0)," in code
+        assert "    fn rhs(y: &[f64], p: &[f64]," in code
+        assert "        let k1 = p[0];" in code
+        assert "parameters: PARAMETER_ORDER," in code
+        assert "    algebraic: &[],\n" in code
+        # Only the problem is shared: each prepared run carries its own p and tolerances
+        assert "    let p = parameter_vector(&sim_params);\n    Ok(Run {\n        p,\n" in code
+        assert "        parameter_vector(sim_params)\n" in code.split("impl PkModel for TestModel {")[1]
+
+    def test_parameter_sweep(self):
+        """Test that sweeps are the core's, run through the model's PkModel impl"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert "pub fn run_parameter_sweep(base_params: &str, sweep: &str) -> Result<String, String> {" in code
+        sweep_fn = code.split("pub fn run_parameter_sweep")[1].split("\n}\n")[0]
+        assert "    wasm_pk_core::run_parameter_sweep::<TestModel>(base_params, sweep)" in sweep_fn
+        assert "struct SweepSpec" not in code
+
+    def test_pk_metrics(self):
+        """Test that PK metrics are exported from the core"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert WASM_EXPORT + "pub fn compute_pk_metrics(result_json: &str, species: &str)" in code
+        assert "    wasm_pk_core::compute_pk_metrics(result_json, species)\n" in code
+        assert "struct PkMetrics" not in code
+        assert "fn pk_metrics" not in code
+
+    def test_partial_auc(self):
+        """Test that partial AUCs are exported from the core"""
+        code = RustTemplateManager().assemble_rust_file("test_model", build_components())
+        assert (
+            WASM_EXPORT + "pub fn compute_partial_auc(result_json: &str, species: &str, windows: &str)"
+            in code
+        )
+        assert "    wasm_pk_core::compute_partial_auc(result_json, species, windows)\n" in code
+        assert "fn interpolate_at" not in code
+        assert "fn partial_auc" not in code
+
+    def test_sensitivity_block(self):
+        """Test that a sensitivity block is emitted only when generated"""
+        components = build_components()
+        components["sensitivity_fn"] = "pub fn run_simulation_with_sensitivities() {}\n"
+        code = RustTemplateManager().assemble_rust_file("test_model", components, wasm=False)
+        assert "pub fn run_simulation_with_sensitivities() {}" in code
+
+        plain = RustTemplateManager().assemble_rust_file(
+            "test_model", build_components(), wasm=False
+        )
+        assert "run_simulation_with_sensitivities" not in plain
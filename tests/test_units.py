@@ -0,0 +1,44 @@
+"""Tests for the shared time unit conversions"""
+
+import pytest
+from utils.units import TIME_UNIT_SECONDS, convert_time, unit_seconds
+
+
+class TestUnitSeconds:
+    """Tests for unit_seconds"""
+
+    def test_time_definitions(self):
+        """Test that plain time definitions give their length in seconds"""
+        assert unit_seconds([("second", 1, 0, 3600.0)]) == 3600.0
+        assert unit_seconds([("second", 1, 0, 60.0)]) == 60.0
+        assert unit_seconds([("second", 1, -3, 1.0)]) == pytest.approx(1e-3)
+
+    def test_other_definitions(self):
+        """Test that rates, products and other kinds are not times"""
+        assert unit_seconds([("second", -1, 0, 1.0)]) is None
+        assert unit_seconds([("litre", 1, 0, 1.0)]) is None
+        assert unit_seconds([("second", 1, 0, 1.0), ("mole", 1, 0, 1.0)]) is None
+        assert unit_seconds([]) is None
+
+
+class TestConvertTime:
+    """Tests for convert_time"""
+
+    def test_known_factors(self):
+        """Test the conversions between the accepted units"""
+        assert convert_time(1.0, "d", "h") == 24.0
+        assert convert_time(90.0, "min", "h") == 1.5
+        assert convert_time(2.0, "h", "s") == 7200.0
+
+    def test_round_trips(self):
+        """Test that converting there and back returns the value"""
+        for from_unit in TIME_UNIT_SECONDS:
+            for to_unit in TIME_UNIT_SECONDS:
+                for value in [0.0, 0.1, 1.0, 7.0, 1234.5]:
+                    there = convert_time(value, from_unit, to_unit)
+                    assert convert_time(there, to_unit, from_unit) == pytest.approx(value)
+
+    def test_unknown_unit(self):
+        """Test that units outside the table are refused"""
+        with pytest.raises(KeyError):
+            convert_time(1.0, "week", "h")
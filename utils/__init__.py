@@ -2,5 +2,6 @@
 """Utility functions and validators"""
 
 from .validators import IdentifierValidator
+from .units import TIME_UNIT_SECONDS, convert_time, unit_seconds
 
-__all__ = ['IdentifierValidator']
+__all__ = ['IdentifierValidator', 'TIME_UNIT_SECONDS', 'convert_time', 'unit_seconds']
@@ -0,0 +1,49 @@
+# File: sbml_rust_generator/utils/units.py
+"""Time unit conversion shared by the SBML parser and the generated code"""
+
+from typing import Optional, Sequence, Tuple
+
+# Length of each unit accepted by the time_unit option, in seconds. The
+# generated TimeUnit enum is built from this table.
+TIME_UNIT_SECONDS = {"s": 1.0, "min": 60.0, "h": 3600.0, "d": 86400.0}
+
+
+def unit_seconds(units: Sequence[Tuple[str, float, int, float]]) -> Optional[float]:
+    """Length in seconds of an SBML unit definition that is a time
+
+    Args:
+        units: (kind, exponent, scale, multiplier) of each unit in the
+            definition, e.g. [("second", 1, 0, 3600)] for an hour
+
+    Returns:
+        Seconds per unit, or None if the definition is not a plain time
+
+    Examples:
+        >>> unit_seconds([("second", 1, 0, 60.0)])
+        60.0
+        >>> unit_seconds([("litre", 1, 0, 1.0)]) is None
+        True
+    """
+    if len(units) != 1:
+        return None
+    kind, exponent, scale, multiplier = units[0]
+    if kind != "second" or exponent != 1:
+        return None
+    return multiplier * 10.0 ** scale
+
+
+def convert_time(value: float, from_unit: str, to_unit: str) -> float:
+    """Convert a time between two units of TIME_UNIT_SECONDS
+
+    Args:
+        value: Time in from_unit
+        from_unit: Unit of value, e.g. "d"
+        to_unit: Unit to convert to, e.g. "h"
+
+    Returns:
+        The time in to_unit
+
+    Raises:
+        KeyError: If either unit is not in TIME_UNIT_SECONDS
+    """
+    return value * TIME_UNIT_SECONDS[from_unit] / TIME_UNIT_SECONDS[to_unit]
@@ -1,4 +1,7 @@
-import init, { run_simulation } from 'sbml_wasm_project';
+import init, {
+    run_simulation, run_simulation_js, run_simulation_typed, run_simulation_binary, get_binary_layout,
+    run_simulation_async, set_progress_callback
+} from 'sbml_wasm_project';
 import { readFileSync } from 'fs';
 import { fileURLToPath } from 'url';
 import { dirname, join } from 'path';
@@ -6,6 +9,18 @@ import { dirname, join } from 'path';
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = dirname(__filename);
 
+// Decode run_simulation_binary output: little-endian f64 values laid out as
+// [n_times, n_species, times..., species0..., species1...]
+function decodeBinaryResult(bytes, layout) {
+    const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+    const read = (i) => view.getFloat64(i * 8, true);
+    const nTimes = read(0);
+    const series = (k) => Float64Array.from({ length: nTimes }, (_, i) => read(2 + k * nTimes + i));
+    const species = {};
+    layout.species.forEach((name, k) => { species[name] = series(k + 1); });
+    return { time: series(0), species };
+}
+
 async function testWASM() {
     console.log("🧪 Starting WASM Test Suite...\n");
 
@@ -127,6 +142,81 @@ async function testWASM() {
         console.log(`   Time points: ${output2.time.length}`);
         console.log(`   Time range: ${output2.time[0].toFixed(2)} - ${output2.time[output2.time.length-1].toFixed(2)} hours\n`);
 
+        // Test 3: Parameter object in, result object out
+        console.log("Test 3: Object API (run_simulation_js)");
+        console.log("─".repeat(50));
+
+        const output3 = run_simulation_js(testParams1);
+
+        console.log(`✅ Simulation completed`);
+        console.log(`   Time points: ${output3.time.length}\n`);
+
+        let invalidParamError = null;
+        try {
+            run_simulation_js({ ...testParams1, BM: "seventy" });
+        } catch (e) {
+            invalidParamError = e;
+        }
+
+        // Test 4: Typed-array result handle
+        console.log("Test 4: Typed-array API (run_simulation_typed)");
+        console.log("─".repeat(50));
+
+        let start = performance.now();
+        const jsonOutput = JSON.parse(run_simulation(JSON.stringify(testParams1)));
+        const jsonMs = performance.now() - start;
+
+        start = performance.now();
+        const handle = run_simulation_typed(testParams1);
+        const typedTime = handle.times();
+        const typedSpecies = {};
+        for (const name of handle.species_names()) {
+            typedSpecies[name] = handle.species(name);
+        }
+        const typedMs = performance.now() - start;
+        handle.free();
+
+        console.log(`✅ Simulation completed`);
+        console.log(`   JSON path: ${jsonMs.toFixed(1)} ms, typed path: ${typedMs.toFixed(1)} ms\n`);
+
+        // Test 5: Compact binary output
+        console.log("Test 5: Binary API (run_simulation_binary)");
+        console.log("─".repeat(50));
+
+        const bytes = run_simulation_binary(JSON.stringify(testParams1));
+        const layout = JSON.parse(get_binary_layout());
+        const binaryOutput = decodeBinaryResult(bytes, layout);
+        const jsonText = run_simulation(JSON.stringify(testParams1));
+
+        console.log(`✅ Simulation completed`);
+        console.log(`   Binary: ${bytes.byteLength} bytes, JSON: ${jsonText.length} bytes\n`);
+
+        // Test 6: Non-blocking run
+        console.log("Test 6: Async API (run_simulation_async)");
+        console.log("─".repeat(50));
+
+        let ticks = 0;
+        const ticker = setInterval(() => ticks++, 0);
+        const asyncOutput = await run_simulation_async(testParams1, 10);
+        clearInterval(ticker);
+
+        console.log(`✅ Simulation completed`);
+        console.log(`   Event loop ran ${ticks} timer callbacks during the run\n`);
+
+        // Test 7: Progress callback, including one that throws
+        console.log("Test 7: Progress callback (set_progress_callback)");
+        console.log("─".repeat(50));
+
+        const progress = [];
+        set_progress_callback((fraction, steps) => progress.push([fraction, steps]), undefined, 5);
+        run_simulation_js(testParams1);
+        set_progress_callback(() => { throw new Error("progress bar failed"); }, undefined, 1);
+        const throwingOutput = run_simulation_js(testParams1);
+        set_progress_callback();
+
+        console.log(`✅ Simulation completed`);
+        console.log(`   Progress callback called ${progress.length} times\n`);
+
         // Validation checks
         console.log("Validation Checks");
         console.log("─".repeat(50));
@@ -186,6 +276,68 @@ async function testWASM() {
             allPassed = false;
         }
 
+        // Check 6: Object API matches the string API
+        if (JSON.stringify(output3.time) === JSON.stringify(output1.time) &&
+            JSON.stringify(output3.species) === JSON.stringify(output1.species)) {
+            console.log("✅ Object API matches the string API");
+        } else {
+            console.log("❌ Object API result differs from the string API");
+            allPassed = false;
+        }
+
+        // Check 7: Invalid parameters throw with the field name
+        if (invalidParamError && String(invalidParamError.message).startsWith("BM:")) {
+            console.log(`✅ Invalid parameter rejected: ${invalidParamError.message}`);
+        } else {
+            console.log("❌ Invalid parameter was not reported with its field name");
+            allPassed = false;
+        }
+
+        // Check 8: Typed arrays hold the same values as the JSON output
+        const sameSeries = (a, b) => a.length === b.length && a.every((v, i) => v === b[i]);
+        let typedMatch = typedTime instanceof Float64Array && sameSeries(typedTime, jsonOutput.time);
+        for (const [name, values] of Object.entries(jsonOutput.species)) {
+            typedMatch = typedMatch && typedSpecies[name] instanceof Float64Array
+                && sameSeries(typedSpecies[name], values);
+        }
+        if (typedMatch) {
+            console.log("✅ Typed-array API matches the JSON output");
+        } else {
+            console.log("❌ Typed-array API result differs from the JSON output");
+            allPassed = false;
+        }
+
+        // Check 9: Binary output decodes to the JSON values bit for bit
+        let binaryMatch = sameSeries(binaryOutput.time, jsonOutput.time);
+        for (const [name, values] of Object.entries(jsonOutput.species)) {
+            binaryMatch = binaryMatch && sameSeries(binaryOutput.species[name], values);
+        }
+        if (binaryMatch) {
+            console.log("✅ Binary API matches the JSON output");
+        } else {
+            console.log("❌ Binary API result differs from the JSON output");
+            allPassed = false;
+        }
+
+        // Check 10: The async run resolves to the object API result
+        if (JSON.stringify(asyncOutput) === JSON.stringify(output3)) {
+            console.log("✅ Async API matches the object API");
+        } else {
+            console.log("❌ Async API result differs from the object API");
+            allPassed = false;
+        }
+
+        // Check 11: Progress rises within [0, 1] and a throwing callback is ignored
+        const progressRises = progress.length > 0 && progress.every(([fraction, steps], i) =>
+            fraction >= 0 && fraction <= 1 && steps % 5 === 0 &&
+            (i === 0 || (fraction >= progress[i - 1][0] && steps > progress[i - 1][1])));
+        if (progressRises && JSON.stringify(throwingOutput) === JSON.stringify(output3)) {
+            console.log("✅ Progress callback reports rising fractions and its exceptions are ignored");
+        } else {
+            console.log("❌ Progress callback misreported or its exception affected the run");
+            allPassed = false;
+        }
+
         console.log("\n" + "═".repeat(50));
         if (allPassed) {
             console.log("🎉 All tests PASSED!");
@@ -0,0 +1,83 @@
+//! One rhs call, one J·v product and a whole default run of each fixture model
+//!
+//! Run with `cargo bench --bench models`; criterion keeps the previous results
+//! under target/criterion and reports the change against them, so running it
+//! before and after a generator change shows what the change costs. rhs and
+//! jac are timed at a fixed pseudo-random state, direction and time with the
+//! default parameters, so every run times the same work. To add a model,
+//! declare its module and add a line to `models`.
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/two_compartment.rs"]
+mod two_compartment;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/event_dose.rs"]
+mod event_dose;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/infusion.rs"]
+mod infusion;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/oral_window.rs"]
+mod oral_window;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/transit_chain.rs"]
+mod transit_chain;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/transit_chain_sparse.rs"]
+mod transit_chain_sparse;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use wasm_pk_core::{simulate, PkModel};
+
+/// Values in [0, 1) from a fixed xorshift64 seed, the same on every run
+fn uniform(count: usize) -> Vec<f64> {
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    (0..count)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        })
+        .collect()
+}
+
+/// Time rhs, jac and a default run of `Model` in a group called `name`
+fn bench_model<Model: PkModel>(c: &mut Criterion, name: &str) {
+    let n = Model::N_STATES;
+    let p = Model::parameter_vector(&Model::default_params());
+    let mut y = vec![0.0; n];
+    Model::init(&p, 0.0, &mut y);
+    // Positive states on the scale of the initial ones, and a direction in [-1, 1)
+    let scale = y.iter().fold(1.0_f64, |m, y| m.max(y.abs()));
+    let random = uniform(2 * n);
+    let y: Vec<f64> = random[..n].iter().map(|u| scale * (0.1 + 0.9 * u)).collect();
+    let v: Vec<f64> = random[n..].iter().map(|u| 2.0 * u - 1.0).collect();
+    let t = 1.0;
+    let mut out = vec![0.0; n];
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function("rhs", |b| b.iter(|| Model::rhs(black_box(&y), black_box(&p), black_box(t), &[], &mut out)));
+    group.bench_function("jac", |b| b.iter(|| Model::jac(black_box(&y), black_box(&p), black_box(t), black_box(&v), &mut out)));
+    group.sample_size(20);
+    group.bench_function("solve", |b| b.iter(|| simulate::<Model>(black_box("{}"))));
+    group.finish();
+}
+
+fn models(c: &mut Criterion) {
+    bench_model::<two_compartment::TwoCompartmentModel>(c, "two_compartment");
+    bench_model::<event_dose::EventDoseModel>(c, "event_dose");
+    bench_model::<infusion::InfusionModel>(c, "infusion");
+    bench_model::<oral_window::OralWindowModel>(c, "oral_window");
+    bench_model::<transit_chain::TransitChainModel>(c, "transit_chain");
+    bench_model::<transit_chain_sparse::TransitChainModel>(c, "transit_chain_sparse");
+}
+
+criterion_group!(benches, models);
+criterion_main!(benches);
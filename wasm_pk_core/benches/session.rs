@@ -0,0 +1,84 @@
+//! Repeated runs after a one-parameter change, through a Session and statelessly
+//!
+//! Run with `cargo bench --bench session`. Both paths change the same parameter
+//! by a small step on every iteration, as a dashboard slider does, and solve
+//! the default run. The stateless path merges the change over the previous
+//! parameters and calls `simulate_run`, which prepares the run and builds the
+//! problem anew; the session only prepares the run and re-initialises the
+//! solver of the problem it keeps. Their difference is the cost of building
+//! the problem, largest for the sparse model, whose sparsity pattern is found
+//! from `jac` on every build.
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/two_compartment.rs"]
+mod two_compartment;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/event_dose.rs"]
+mod event_dose;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/oral_window.rs"]
+mod oral_window;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/transit_chain.rs"]
+mod transit_chain;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/transit_chain_sparse.rs"]
+mod transit_chain_sparse;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use wasm_pk_core::{merge_params, PkModel, Session};
+
+/// The default value of `name` scaled by 1 ± 1e-3, alternating between calls
+fn slider(name: &str, defaults: &serde_json::Value) -> impl FnMut() -> f64 {
+    let value = defaults[name].as_f64().unwrap();
+    let mut up = false;
+    move || {
+        up = !up;
+        value * if up { 1.001 } else { 0.999 }
+    }
+}
+
+/// Time both paths for `Model` in a group called `name`, moving `parameter`
+fn bench_model<Model: PkModel>(c: &mut Criterion, name: &str, parameter: &str) {
+    let defaults = Model::defaults();
+    let mut group = c.benchmark_group(name);
+    group.sample_size(20);
+
+    let mut params = Model::default_params();
+    let mut next = slider(parameter, &defaults);
+    group.bench_function("stateless", |b| {
+        b.iter(|| {
+            let current = serde_json::to_value(&params).unwrap();
+            let mut overrides = serde_json::Map::new();
+            overrides.insert(parameter.to_string(), serde_json::json!(next()));
+            params = merge_params::<Model>(current, overrides).unwrap();
+            black_box(Model::simulate_run(params.clone()))
+        })
+    });
+
+    let mut session = Session::<Model>::new(Model::default_params()).unwrap();
+    let mut next = slider(parameter, &defaults);
+    group.bench_function("session", |b| {
+        b.iter(|| {
+            session.set_parameter(parameter, next()).unwrap();
+            black_box(session.run())
+        })
+    });
+    group.finish();
+}
+
+fn sessions(c: &mut Criterion) {
+    bench_model::<two_compartment::TwoCompartmentModel>(c, "two_compartment", "k1");
+    bench_model::<event_dose::EventDoseModel>(c, "event_dose", "ke");
+    bench_model::<oral_window::OralWindowModel>(c, "oral_window", "Kabs");
+    bench_model::<transit_chain::TransitChainModel>(c, "transit_chain", "ktr");
+    bench_model::<transit_chain_sparse::TransitChainModel>(c, "transit_chain_sparse", "ktr");
+}
+
+criterion_group!(benches, sessions);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+//! Whole runs of the 80-compartment transit chain, generated with the dense
+//! and the sparse Jacobian
+//!
+//! Run with `cargo bench --bench sparse_jacobian`. Each row of the chain's
+//! Jacobian has at most two entries, so the sparse LU factorises in linear
+//! time where the dense one takes cubic time in the number of species.
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/transit_chain.rs"]
+mod transit_chain;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/transit_chain_sparse.rs"]
+mod transit_chain_sparse;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Best time of a few repetitions
+fn measure<T>(run: impl Fn() -> T) -> Duration {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(run());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    println!("transit_chain, 80 species");
+    for (name, params) in [
+        ("defaults", r#"{}"#),
+        ("tight tolerances", r#"{"rtol": 1e-10, "atol": 1e-12}"#),
+        ("tr_bdf2", r#"{"solver": "tr_bdf2"}"#),
+    ] {
+        let dense = measure(|| transit_chain::run_simulation(black_box(params)));
+        let sparse = measure(|| transit_chain_sparse::run_simulation(black_box(params)));
+        println!(
+            "{:>18}: dense {:>9.3} ms, sparse {:>9.3} ms ({:.1}x)",
+            name,
+            dense.as_secs_f64() * 1e3,
+            sparse.as_secs_f64() * 1e3,
+            dense.as_secs_f64() / sparse.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Recording a 100k-step run: one Vec per species against the row-major
+//! Trajectory, growing or preallocated; then whole runs of a fixture model
+//!
+//! Run with `cargo bench --bench trajectory`. Each layout records the same
+//! states, then splits them into the species map of a result; the counting
+//! allocator reports the allocations each one makes on the way.
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../tests/models/two_compartment.rs"]
+mod two_compartment;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use wasm_pk_core::{OrderedSeries, Trajectory};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const STEPS: usize = 100_000;
+const SPECIES: &[&str] = &[
+    "Cgut", "Cliver", "Ckidney", "Cfat", "Cmuscle", "Cbrain", "Cskin", "Cplasma",
+    "Cart", "Cven", "Clung", "Cheart", "Cspleen", "Cbone", "Curine", "Cfeces",
+];
+
+/// The layout before Trajectory: a time vector and one series per species
+fn per_species(states: &[Vec<f64>]) -> (Vec<f64>, OrderedSeries) {
+    let mut time = Vec::new();
+    let mut series: Vec<Vec<f64>> = vec![Vec::new(); SPECIES.len()];
+    for (i, y) in states.iter().enumerate() {
+        for (k, values) in series.iter_mut().enumerate() {
+            values.push(y[k]);
+        }
+        time.push(i as f64);
+    }
+    let mut species = OrderedSeries::default();
+    for (id, values) in SPECIES.iter().zip(series) {
+        species.insert(id.to_string(), values);
+    }
+    (time, species)
+}
+
+fn row_major(states: &[Vec<f64>]) -> (Vec<f64>, OrderedSeries) {
+    let mut trajectory = Trajectory::new(SPECIES.len());
+    for (i, y) in states.iter().enumerate() {
+        trajectory.push(i as f64, y);
+    }
+    trajectory.into_series(SPECIES)
+}
+
+fn preallocated(states: &[Vec<f64>]) -> (Vec<f64>, OrderedSeries) {
+    let mut trajectory = Trajectory::with_capacity(SPECIES.len(), states.len());
+    for (i, y) in states.iter().enumerate() {
+        trajectory.push(i as f64, y);
+    }
+    trajectory.into_series(SPECIES)
+}
+
+/// Best time of a few repetitions and the allocations of one
+fn measure<T>(run: impl Fn() -> T) -> (Duration, usize) {
+    let mut best = Duration::MAX;
+    let mut allocations = 0;
+    for _ in 0..10 {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        black_box(run());
+        best = best.min(start.elapsed());
+        allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    }
+    (best, allocations)
+}
+
+fn main() {
+    let states: Vec<Vec<f64>> = (0..STEPS).map(|i| (0..SPECIES.len()).map(|k| (i * k) as f64).collect()).collect();
+    println!("{} steps x {} species", STEPS, SPECIES.len());
+    let layouts = [
+        ("per-species Vecs", per_species as fn(&[Vec<f64>]) -> _),
+        ("row-major Trajectory", row_major),
+        ("preallocated", preallocated),
+    ];
+    for (name, record) in layouts {
+        let (time, allocations) = measure(|| record(black_box(&states)));
+        println!("{:>22}: {:>9.3} ms, {:>6} allocations", name, time.as_secs_f64() * 1e3, allocations);
+    }
+
+    // A dose a day for 240 h, with and without a bound on the output points
+    let doses: Vec<String> = (0..10).map(|day| format!(r#"{{"time": {}, "amount": 1, "target": "A"}}"#, 24 * day)).collect();
+    println!("two_compartment, 240 h");
+    for (name, extra) in [("every step", ""), ("max_output_points 200", r#", "max_output_points": 200"#)] {
+        let params = format!(r#"{{"final_time": 240, "doses": [{}]{}}}"#, doses.join(", "), extra);
+        let (time, allocations) = measure(|| two_compartment::run_simulation(black_box(&params)));
+        println!("{:>22}: {:>9.3} ms, {:>6} allocations", name, time.as_secs_f64() * 1e3, allocations);
+    }
+}
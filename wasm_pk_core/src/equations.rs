@@ -0,0 +1,399 @@
+use std::cell::RefCell;
+
+use diffsol::matrix::sparsity::MatrixSparsity;
+use diffsol::op::OpStatistics;
+use diffsol::{
+    find_jacobian_non_zeros, ConstantOp, JacobianColoring, LinearOp, Matrix, NonLinearOp, NonLinearOpJacobian, OdeEquations, OdeEquationsRef,
+    Op, UnitCallable, Vector, VectorHost, VectorIndex,
+};
+
+use crate::options::UptakeWindow;
+use crate::solver::JacobianMode;
+
+/// Nonzero entries of the Jacobian at (state, parameter vector, time), in the
+/// order of the pattern they come with
+pub type JacobianValuesFn<'a> = &'a dyn Fn(&[f64], &[f64], f64, &mut [f64]);
+
+/// The Jacobian matrix of a model generated with jacobian_matrix: the (row,
+/// column) of each nonzero entry and the function filling in their values
+#[derive(Clone, Copy)]
+pub struct JacobianMatrix<'a> {
+    pub pattern: &'a [(usize, usize)],
+    pub values: JacobianValuesFn<'a>,
+}
+
+/// Where the pattern entries go in the matrix data, and room for their values
+struct MatrixFill<'a, Mat: Matrix> {
+    values: JacobianValuesFn<'a>,
+    dst_indices: <Mat::V as Vector>::Index,
+    src_indices: <Mat::V as Vector>::Index,
+    scratch: RefCell<Mat::V>,
+}
+
+/// The diagonal mass matrix of a model with algebraic states: one for the
+/// integrated states, zero for the algebraic ones
+struct MassDiagonal<Mat: Matrix> {
+    values: Mat::V,
+    dst_indices: <Mat::V as Vector>::Index,
+    src_indices: <Mat::V as Vector>::Index,
+    sparsity: Option<Mat::Sparsity>,
+}
+
+/// The generated closures of a model as one diffsol system. It reproduces what
+/// OdeBuilder makes of them, except that a model with a [`JacobianMatrix`]
+/// fills the Newton matrix directly instead of through `jac` products,
+/// [`JacobianMode::FiniteDifference`] replaces both with differences of `rhs`,
+/// and a model with algebraic states gets a singular mass matrix, making it a DAE.
+pub(crate) struct ModelEquations<'a, Mat: Matrix, F, J, I, R> {
+    rhs: F,
+    jac: J,
+    init: I,
+    roots: R,
+    nstates: usize,
+    nroots: usize,
+    p: Mat::V,
+    sparsity: Option<Mat::Sparsity>,
+    coloring: Option<JacobianColoring<Mat>>,
+    matrix: Option<MatrixFill<'a, Mat>>,
+    mass: Option<MassDiagonal<Mat>>,
+    finite_difference: bool,
+    /// Uptake windows of the run being solved, passed to every `rhs` call
+    windows: Vec<UptakeWindow>,
+    /// The shifted state and the rhs at the unshifted one of a difference
+    scratch: RefCell<Vec<f64>>,
+    statistics: RefCell<OpStatistics>,
+    ctx: Mat::C,
+}
+
+impl<'a, Mat, F, J, I, R> ModelEquations<'a, Mat, F, J, I, R>
+where
+    Mat: Matrix<T = f64>,
+    Mat::V: VectorHost,
+    F: Fn(&[f64], &[f64], f64, &[UptakeWindow], &mut [f64]),
+    J: Fn(&[f64], &[f64], f64, &[f64], &mut [f64]),
+    I: Fn(&[f64], f64, &mut [f64]),
+    R: Fn(&[f64], &[f64], f64, &mut [f64]),
+{
+    /// The system of `nstates` states and `nroots` roots for `nparams` parameters,
+    /// with zero rows of the mass matrix for the `algebraic` states. Like
+    /// OdeBuilder, sparse matrices find their pattern from `jac` at the initial
+    /// state of a zero parameter vector.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        rhs: F,
+        jac: J,
+        init: I,
+        roots: R,
+        nstates: usize,
+        nroots: usize,
+        nparams: usize,
+        matrix: Option<JacobianMatrix<'a>>,
+        algebraic: &[usize],
+    ) -> Self {
+        let ctx = Mat::C::default();
+        let mut equations = ModelEquations {
+            rhs,
+            jac,
+            init,
+            roots,
+            nstates,
+            nroots,
+            p: Mat::V::zeros(nparams, ctx.clone()),
+            sparsity: None,
+            coloring: None,
+            matrix: None,
+            mass: None,
+            finite_difference: false,
+            windows: Vec::new(),
+            scratch: RefCell::new(vec![0.0; 2 * nstates]),
+            statistics: RefCell::new(OpStatistics::default()),
+            ctx: ctx.clone(),
+        };
+        if !algebraic.is_empty() {
+            let diagonal: Vec<(usize, usize)> = (0..nstates).map(|i| (i, i)).collect();
+            let sparsity = Mat::Sparsity::try_from_indices(nstates, nstates, diagonal.clone()).expect("invalid mass matrix pattern");
+            let values: Vec<f64> = (0..nstates).map(|i| if algebraic.contains(&i) { 0.0 } else { 1.0 }).collect();
+            equations.mass = Some(MassDiagonal {
+                values: Mat::V::from_slice(&values, ctx.clone()),
+                dst_indices: sparsity.get_index(&diagonal, ctx.clone()),
+                src_indices: <Mat::V as Vector>::Index::from_vec((0..nstates).collect(), ctx.clone()),
+                sparsity: Mat::is_sparse().then_some(sparsity),
+            });
+        }
+        if let Some(JacobianMatrix { pattern, values }) = matrix {
+            let sparsity = Mat::Sparsity::try_from_indices(nstates, nstates, pattern.to_vec()).expect("invalid Jacobian pattern");
+            equations.matrix = Some(MatrixFill {
+                values,
+                dst_indices: sparsity.get_index(pattern, ctx.clone()),
+                src_indices: <Mat::V as Vector>::Index::from_vec((0..pattern.len()).collect(), ctx.clone()),
+                scratch: RefCell::new(Mat::V::zeros(pattern.len(), ctx.clone())),
+            });
+            if Mat::is_sparse() {
+                // For finite differences, which assemble the matrix like jac products
+                equations.coloring = Some(JacobianColoring::new(&sparsity, pattern, ctx));
+                equations.sparsity = Some(sparsity);
+            }
+        } else if Mat::is_sparse() {
+            let y0 = equations.init().call(0.0);
+            let non_zeros = find_jacobian_non_zeros(&equations.rhs(), &y0, 0.0);
+            let sparsity = Mat::Sparsity::try_from_indices(nstates, nstates, non_zeros.clone()).expect("invalid sparsity pattern");
+            equations.coloring = Some(JacobianColoring::new(&sparsity, &non_zeros, ctx));
+            equations.sparsity = Some(sparsity);
+        }
+        equations
+    }
+}
+
+impl<Mat, F, J, I, R> ModelEquations<'_, Mat, F, J, I, R>
+where
+    Mat: Matrix<T = f64>,
+    Mat::V: VectorHost,
+    F: Fn(&[f64], &[f64], f64, &[UptakeWindow], &mut [f64]),
+{
+    /// Take J·v, and the matrix built from it, from `jac` or from differences of `rhs`
+    pub fn set_jacobian(&mut self, mode: JacobianMode) {
+        self.finite_difference = mode == JacobianMode::FiniteDifference;
+    }
+
+    /// Solve the next run with these uptake windows; empty for the model's own window
+    pub fn set_uptake_windows(&mut self, windows: Vec<UptakeWindow>) {
+        self.windows = windows;
+    }
+
+    /// Forward-difference J·v: (rhs(x + h v) - rhs(x)) / h, with h scaled to x and v
+    fn finite_difference_mul(&self, x: &[f64], t: f64, v: &[f64], jv: &mut [f64]) {
+        let v_norm = v.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+        if v_norm == 0.0 {
+            jv.fill(0.0);
+            return;
+        }
+        let x_norm = x.iter().fold(0.0_f64, |m, x| m.max(x.abs()));
+        let h = f64::EPSILON.sqrt() * (1.0 + x_norm) / v_norm;
+        let mut scratch = self.scratch.borrow_mut();
+        let (shifted, f0) = scratch.split_at_mut(self.nstates);
+        for ((s, x), v) in shifted.iter_mut().zip(x).zip(v) {
+            *s = x + h * v;
+        }
+        let p = self.p.as_slice();
+        (self.rhs)(x, p, t, &self.windows, f0);
+        (self.rhs)(shifted, p, t, &self.windows, jv);
+        for (jv, f0) in jv.iter_mut().zip(f0.iter()) {
+            *jv = (*jv - f0) / h;
+        }
+        let mut statistics = self.statistics.borrow_mut();
+        statistics.increment_call();
+        statistics.increment_call();
+    }
+}
+
+/// Right-hand side of [`ModelEquations`] with its Jacobian
+pub(crate) struct ModelRhs<'b, E>(&'b E);
+/// Initial state of [`ModelEquations`]
+pub(crate) struct ModelInit<'b, E>(&'b E);
+/// Event roots of [`ModelEquations`]
+pub(crate) struct ModelRoots<'b, E>(&'b E);
+/// Mass matrix of [`ModelEquations`] with algebraic states
+pub(crate) struct ModelMass<'b, E>(&'b E);
+
+macro_rules! impl_op {
+    ($op:ident, $nout:ident) => {
+        impl<Mat: Matrix, F, J, I, R> Op for $op<'_, ModelEquations<'_, Mat, F, J, I, R>> {
+            type T = Mat::T;
+            type V = Mat::V;
+            type M = Mat;
+            type C = Mat::C;
+
+            fn context(&self) -> &Self::C {
+                &self.0.ctx
+            }
+
+            fn nstates(&self) -> usize {
+                self.0.nstates
+            }
+
+            fn nout(&self) -> usize {
+                self.0.$nout
+            }
+
+            fn nparams(&self) -> usize {
+                self.0.p.len()
+            }
+
+            fn statistics(&self) -> OpStatistics {
+                self.0.statistics.borrow().clone()
+            }
+        }
+    };
+}
+
+impl_op!(ModelRhs, nstates);
+impl_op!(ModelInit, nstates);
+impl_op!(ModelRoots, nroots);
+impl_op!(ModelMass, nstates);
+
+impl<Mat, F, J, I, R> NonLinearOp for ModelRhs<'_, ModelEquations<'_, Mat, F, J, I, R>>
+where
+    Mat: Matrix<T = f64>,
+    Mat::V: VectorHost,
+    F: Fn(&[f64], &[f64], f64, &[UptakeWindow], &mut [f64]),
+{
+    fn call_inplace(&self, x: &Mat::V, t: f64, y: &mut Mat::V) {
+        self.0.statistics.borrow_mut().increment_call();
+        (self.0.rhs)(x.as_slice(), self.0.p.as_slice(), t, &self.0.windows, y.as_mut_slice());
+    }
+}
+
+impl<Mat, F, J, I, R> NonLinearOpJacobian for ModelRhs<'_, ModelEquations<'_, Mat, F, J, I, R>>
+where
+    Mat: Matrix<T = f64>,
+    Mat::V: VectorHost,
+    F: Fn(&[f64], &[f64], f64, &[UptakeWindow], &mut [f64]),
+    J: Fn(&[f64], &[f64], f64, &[f64], &mut [f64]),
+{
+    fn jac_mul_inplace(&self, x: &Mat::V, t: f64, v: &Mat::V, y: &mut Mat::V) {
+        self.0.statistics.borrow_mut().increment_jac_mul();
+        if self.0.finite_difference {
+            self.0.finite_difference_mul(x.as_slice(), t, v.as_slice(), y.as_mut_slice());
+        } else {
+            (self.0.jac)(x.as_slice(), self.0.p.as_slice(), t, v.as_slice(), y.as_mut_slice());
+        }
+    }
+
+    fn jacobian_inplace(&self, x: &Mat::V, t: f64, y: &mut Mat) {
+        self.0.statistics.borrow_mut().increment_matrix();
+        if let Some(fill) = self.0.matrix.as_ref().filter(|_| !self.0.finite_difference) {
+            let mut values = fill.scratch.borrow_mut();
+            (fill.values)(x.as_slice(), self.0.p.as_slice(), t, values.as_mut_slice());
+            y.set_data_with_indices(&fill.dst_indices, &fill.src_indices, &values);
+        } else if let Some(coloring) = self.0.coloring.as_ref() {
+            coloring.jacobian_inplace(self, x, t, y);
+        } else {
+            self._default_jacobian_inplace(x, t, y);
+        }
+    }
+
+    fn jacobian_sparsity(&self) -> Option<Mat::Sparsity> {
+        self.0.sparsity.clone()
+    }
+}
+
+impl<Mat, F, J, I, R> ConstantOp for ModelInit<'_, ModelEquations<'_, Mat, F, J, I, R>>
+where
+    Mat: Matrix<T = f64>,
+    Mat::V: VectorHost,
+    I: Fn(&[f64], f64, &mut [f64]),
+{
+    fn call_inplace(&self, t: f64, y: &mut Mat::V) {
+        (self.0.init)(self.0.p.as_slice(), t, y.as_mut_slice());
+    }
+}
+
+impl<Mat, F, J, I, R> NonLinearOp for ModelRoots<'_, ModelEquations<'_, Mat, F, J, I, R>>
+where
+    Mat: Matrix<T = f64>,
+    Mat::V: VectorHost,
+    R: Fn(&[f64], &[f64], f64, &mut [f64]),
+{
+    fn call_inplace(&self, x: &Mat::V, t: f64, y: &mut Mat::V) {
+        (self.0.roots)(x.as_slice(), self.0.p.as_slice(), t, y.as_mut_slice());
+    }
+}
+
+impl<Mat, F, J, I, R> LinearOp for ModelMass<'_, ModelEquations<'_, Mat, F, J, I, R>>
+where
+    Mat: Matrix<T = f64>,
+    Mat::V: VectorHost,
+{
+    fn gemv_inplace(&self, x: &Mat::V, _t: f64, beta: f64, y: &mut Mat::V) {
+        let mass = self.0.mass.as_ref().expect("a mass matrix without algebraic states");
+        for ((y, x), m) in y.as_mut_slice().iter_mut().zip(x.as_slice()).zip(mass.values.as_slice()) {
+            *y = m * x + beta * *y;
+        }
+    }
+
+    fn matrix_inplace(&self, _t: f64, y: &mut Mat) {
+        let mass = self.0.mass.as_ref().expect("a mass matrix without algebraic states");
+        y.set_data_with_indices(&mass.dst_indices, &mass.src_indices, &mass.values);
+    }
+
+    fn sparsity(&self) -> Option<Mat::Sparsity> {
+        self.0.mass.as_ref().and_then(|mass| mass.sparsity.clone())
+    }
+}
+
+impl<Mat: Matrix, F, J, I, R> Op for ModelEquations<'_, Mat, F, J, I, R> {
+    type T = Mat::T;
+    type V = Mat::V;
+    type M = Mat;
+    type C = Mat::C;
+
+    fn context(&self) -> &Self::C {
+        &self.ctx
+    }
+
+    fn nstates(&self) -> usize {
+        self.nstates
+    }
+
+    fn nout(&self) -> usize {
+        self.nstates
+    }
+
+    fn nparams(&self) -> usize {
+        self.p.len()
+    }
+
+    fn statistics(&self) -> OpStatistics {
+        self.statistics.borrow().clone()
+    }
+}
+
+impl<'b, 'a, Mat, F, J, I, R> OdeEquationsRef<'b> for ModelEquations<'a, Mat, F, J, I, R>
+where
+    Mat: Matrix<T = f64>,
+    Mat::V: VectorHost,
+    F: Fn(&[f64], &[f64], f64, &[UptakeWindow], &mut [f64]),
+    J: Fn(&[f64], &[f64], f64, &[f64], &mut [f64]),
+    I: Fn(&[f64], f64, &mut [f64]),
+    R: Fn(&[f64], &[f64], f64, &mut [f64]),
+{
+    type Mass = ModelMass<'b, Self>;
+    type Rhs = ModelRhs<'b, Self>;
+    type Root = ModelRoots<'b, Self>;
+    type Init = ModelInit<'b, Self>;
+    type Out = UnitCallable<Mat>;
+}
+
+impl<Mat, F, J, I, R> OdeEquations for ModelEquations<'_, Mat, F, J, I, R>
+where
+    Mat: Matrix<T = f64>,
+    Mat::V: VectorHost,
+    F: Fn(&[f64], &[f64], f64, &[UptakeWindow], &mut [f64]),
+    J: Fn(&[f64], &[f64], f64, &[f64], &mut [f64]),
+    I: Fn(&[f64], f64, &mut [f64]),
+    R: Fn(&[f64], &[f64], f64, &mut [f64]),
+{
+    fn rhs(&self) -> ModelRhs<'_, Self> {
+        ModelRhs(self)
+    }
+
+    fn mass(&self) -> Option<ModelMass<'_, Self>> {
+        self.mass.as_ref().map(|_| ModelMass(self))
+    }
+
+    fn root(&self) -> Option<ModelRoots<'_, Self>> {
+        Some(ModelRoots(self))
+    }
+
+    fn init(&self) -> ModelInit<'_, Self> {
+        ModelInit(self)
+    }
+
+    fn set_params(&mut self, p: &Mat::V) {
+        self.p.copy_from(p);
+    }
+
+    fn get_params(&self, p: &mut Mat::V) {
+        p.copy_from(&self.p);
+    }
+}
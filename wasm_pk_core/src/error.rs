@@ -0,0 +1,103 @@
+use crate::result::ErrorPayload;
+
+/// Why a simulation call failed. Entry points report it in the result as an
+/// [`ErrorPayload`], throw it as a JS `Error`, or return its message.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SimulationError {
+    /// Parameters or options a run cannot start from; `field` is the key to blame, if one is
+    #[error("{message}")]
+    InvalidParams { field: Option<String>, message: String },
+    /// The ODE problem or its solver could not be set up
+    #[error("{0}")]
+    Setup(String),
+    /// The solver failed at `t` in the state `state`
+    #[error("{message}")]
+    SolverFailure { t: f64, message: String, state: Vec<f64>, hint: Option<String> },
+    /// An event root at `t` could not be resolved
+    #[error("{message}")]
+    EventError { t: f64, message: String, state: Vec<f64>, hint: Option<String> },
+    /// The run took `limit` (max_steps) solver steps without reaching final_time
+    #[error("solver exceeded max_steps ({limit}) at t = {t}")]
+    MaxStepsExceeded { t: f64, limit: usize, state: Vec<f64>, hint: Option<String> },
+    /// The progress callback stopped the run at `t`
+    #[error("cancelled at t = {t}")]
+    Cancelled { t: f64 },
+}
+
+impl SimulationError {
+    /// Invalid parameters no single field is to blame for
+    pub fn invalid(message: impl Into<String>) -> Self {
+        SimulationError::InvalidParams { field: None, message: message.into() }
+    }
+
+    /// Invalid value of `field`
+    pub fn invalid_field(field: &str, message: impl Into<String>) -> Self {
+        SimulationError::InvalidParams { field: Some(field.to_string()), message: message.into() }
+    }
+
+    /// Name of the variant in snake case, as in the payload's `kind`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SimulationError::InvalidParams { .. } => "invalid_params",
+            SimulationError::Setup(_) => "setup",
+            SimulationError::SolverFailure { .. } => "solver_failure",
+            SimulationError::EventError { .. } => "event_error",
+            SimulationError::MaxStepsExceeded { .. } => "max_steps_exceeded",
+            SimulationError::Cancelled { .. } => "cancelled",
+        }
+    }
+
+    /// The error as a result reports it
+    pub fn payload(&self) -> ErrorPayload {
+        let (field, time, state, hint) = match self {
+            SimulationError::InvalidParams { field, .. } => (field.clone(), None, None, None),
+            SimulationError::Setup(_) => (None, None, None, None),
+            SimulationError::SolverFailure { t, state, hint, .. }
+            | SimulationError::EventError { t, state, hint, .. }
+            | SimulationError::MaxStepsExceeded { t, state, hint, .. } => (None, Some(*t), Some(state.clone()), hint.clone()),
+            SimulationError::Cancelled { t } => (None, Some(*t), None, None),
+        };
+        ErrorPayload { kind: self.kind().to_string(), message: self.to_string(), field, time, state, hint }
+    }
+}
+
+/// For the entry points that return `Result<_, String>`
+impl From<SimulationError> for String {
+    fn from(error: SimulationError) -> String {
+        error.to_string()
+    }
+}
+
+/// A JS `Error` with the message, plus `kind` and `field` properties
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+impl From<SimulationError> for wasm_bindgen::JsValue {
+    fn from(error: SimulationError) -> wasm_bindgen::JsValue {
+        let js_error = js_sys::Error::new(&error.to_string());
+        let _ = js_sys::Reflect::set(&js_error, &"kind".into(), &error.kind().into());
+        if let SimulationError::InvalidParams { field: Some(field), .. } = &error {
+            let _ = js_sys::Reflect::set(&js_error, &"field".into(), &field.as_str().into());
+        }
+        js_error.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payloads_keep_the_message_and_name_the_kind() {
+        let error = SimulationError::invalid_field("rtol", "rtol must be a positive number, got -1");
+        let payload = error.payload();
+        assert_eq!((payload.kind.as_str(), payload.field.as_deref()), ("invalid_params", Some("rtol")));
+        assert_eq!(payload.message, "rtol must be a positive number, got -1");
+        let error = SimulationError::MaxStepsExceeded { t: 2.0, limit: 500, state: vec![1.0], hint: None };
+        let payload = error.payload();
+        assert_eq!((payload.kind.as_str(), payload.time), ("max_steps_exceeded", Some(2.0)));
+        assert_eq!(payload.message, "solver exceeded max_steps (500) at t = 2");
+        let error = SimulationError::EventError { t: 3.25, message: "interpolating at the root t = 3.25 failed".into(), state: vec![0.5], hint: None };
+        let payload = error.payload();
+        assert_eq!((payload.kind.as_str(), payload.time, payload.state), ("event_error", Some(3.25), Some(vec![0.5])));
+        assert_eq!(String::from(SimulationError::Cancelled { t: 1.5 }), "cancelled at t = 1.5");
+    }
+}
@@ -0,0 +1,74 @@
+/// Blank out BOMs, Unicode spaces, comments and trailing commas so annotated files parse as JSON
+pub fn strip_json_extras(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(chars.len());
+    // Byte offset in `out` of a comma seen since the last value
+    let mut trailing_comma: Option<usize> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c == '"' {
+            trailing_comma = None;
+            out.push(c);
+            i += 1;
+            while i < chars.len() {
+                out.push(chars[i]);
+                i += 1;
+                match chars[i - 1] {
+                    '\\' if i < chars.len() => {
+                        out.push(chars[i]);
+                        i += 1;
+                    }
+                    '"' => break,
+                    _ => {}
+                }
+            }
+        } else if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                out.push(' ');
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            let end = (i + 2..chars.len()).find(|&j| chars[j] == '*' && chars.get(j + 1) == Some(&'/'));
+            let end = end.map_or(chars.len(), |j| j + 2);
+            out.extend(chars[i..end].iter().map(|&c| if c == '\n' { '\n' } else { ' ' }));
+            i = end;
+        } else if c == '\u{feff}' || (c.is_whitespace() && !c.is_ascii()) {
+            out.push(' ');
+            i += 1;
+        } else {
+            match c {
+                ',' => trailing_comma = Some(out.len()),
+                '}' | ']' => {
+                    if let Some(at) = trailing_comma.take() {
+                        out.replace_range(at..at + 1, " ");
+                    }
+                }
+                c if !c.is_whitespace() => trailing_comma = None,
+                _ => {}
+            }
+            out.push(c);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Parse JSON that may carry comments, trailing commas or a BOM
+pub fn from_json<T: serde::de::DeserializeOwned>(text: &str) -> serde_json::Result<T> {
+    serde_json::from_str(&strip_json_extras(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotations_are_blanked() {
+        let value: serde_json::Value = from_json("\u{feff}{\"a\": 1, // one\n \"b\": [2, 3,], /* end */}").unwrap();
+        assert_eq!(value, serde_json::json!({"a": 1, "b": [2, 3]}));
+        // Blanked, not removed, so error positions match the text
+        assert_eq!(strip_json_extras("{/* x\n*/}"), "{    \n  }");
+    }
+}
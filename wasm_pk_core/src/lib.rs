@@ -0,0 +1,69 @@
+//! Model-independent part of the Rust generated from SBML models
+//!
+//! A generated model keeps its parameters, its closures (right-hand side,
+//! Jacobian-vector product, initial state, events and observables) and its
+//! exported entry points. Everything else lives here: the simulation options
+//! and their checks, the result types and their serialization, logging, and
+//! the solver loop of [`BuiltProblem`]. Generated files `use wasm_pk_core::*` and
+//! implement [`PkModel`]; their parameter checks, PK metrics, sweeps,
+//! continuations and checkpoint restarts are the generic functions here, which
+//! the models' exports call with their own type.
+
+#[macro_use]
+mod log;
+mod equations;
+mod error;
+mod json;
+mod metrics;
+mod model;
+mod options;
+mod params;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod progress;
+mod result;
+mod runs;
+mod series;
+mod session;
+mod solver;
+mod trajectory;
+
+pub use equations::{JacobianMatrix, JacobianValuesFn};
+pub use error::SimulationError;
+pub use json::{from_json, strip_json_extras};
+pub use log::{log_enabled, log_message, set_log_level, LogLevel, DEFAULT_LOG_LEVEL};
+pub use metrics::{compute_partial_auc, compute_pk_metrics, partial_auc, pk_metrics, PkMetrics};
+pub use model::{mass_diagnostics, simulate, steady_state_rms, ModelInfo, PkModel};
+pub use options::{
+    apply_doses, check_unknown_keys, check_uptake_windows, deserialize_params, edit_distance, lenient_f64, repeated_windows,
+    suggest_field, window_sum, Dose, OutputKind, SimulationMode, SimulationOptions, TimeUnit, UptakeWindow,
+};
+pub use params::{
+    check_parameters, merge_params, resolve_params, validate_parameters, ParameterCheck, ParameterIssue, ParameterRange,
+    ParameterReport,
+};
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub use progress::{set_progress_callback, ProgressReporter};
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub use result::result_to_js;
+pub use result::{
+    failed_result, join_results, now_ms, result_to_csv, result_to_json, scale_result_times, Checkpoint, Diagnostics,
+    ErrorPayload, EventRecord, SimulationResult, SolverStats, SteadyState, Timing, CHECKPOINT_VERSION,
+};
+pub use runs::{
+    continue_simulation, decode_binary_result, get_binary_layout, run_from_checkpoint, run_parameter_sweep,
+    run_simulation_at_times, run_simulation_batch, run_simulation_binary, run_simulation_csv,
+};
+pub use series::OrderedSeries;
+pub use session::Session;
+pub use solver::{
+    clamp_output, no_events, no_roots, no_triggers, run_ready, thin_output, BuiltProblem, Equations, EventFn, InitFn, JacFn, JacobianMode, MassFn,
+    RhsFn, RootFn, Run, SeriesFn, SolverMethod, TriggerFn, LAST_CHECKPOINT, LAST_RUN_STATS,
+};
+pub use trajectory::Trajectory;
+
+/// Matrix, linear solver and vector types of the solver
+pub type M = diffsol::NalgebraMat<f64>;
+pub type LS = diffsol::NalgebraLU<f64>;
+pub type V = diffsol::NalgebraVec<f64>;
+/// Sparse Jacobian of [`BuiltProblem::sparse`]; it factorises with diffsol's FaerSparseLU
+pub type SparseM = diffsol::FaerSparseMat<f64>;
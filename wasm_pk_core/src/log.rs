@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much the models log, quietest first
+#[derive(Clone, Copy)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Info,
+    Debug,
+}
+
+/// Release builds only report errors until set_log_level raises the level
+pub const DEFAULT_LOG_LEVEL: LogLevel = if cfg!(debug_assertions) { LogLevel::Info } else { LogLevel::Error };
+
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(DEFAULT_LOG_LEVEL as u8);
+
+pub fn log_enabled(level: LogLevel) -> bool {
+    level as u8 <= LOG_LEVEL.load(Ordering::Relaxed)
+}
+
+/// Set what the models log: "off", "error", "info" (run starts, ignored doses) or
+/// "debug" (also batch progress, the steps of each run and every event firing)
+pub fn set_log_level(level: &str) -> Result<(), String> {
+    let level = match level.trim().to_ascii_lowercase().as_str() {
+        "off" => LogLevel::Off,
+        "error" => LogLevel::Error,
+        "info" => LogLevel::Info,
+        "debug" => LogLevel::Debug,
+        _ => return Err(format!("unknown log level {:?}: expected off, error, info or debug", level)),
+    };
+    LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+    Ok(())
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen::prelude::wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    fn log(s: &str);
+}
+
+/// Write one log line: the browser console, or stderr in builds without the JS glue
+pub fn log_message(message: &str) {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    log(message);
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    eprintln!("{}", message);
+}
+
+/// Log at a level of [`LogLevel`], formatting only when that level is enabled
+#[macro_export]
+macro_rules! console_log {
+    ($level:ident, $($t:tt)*) => {
+        if $crate::log_enabled($crate::LogLevel::$level) {
+            $crate::log_message(&format!($($t)*))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_level_is_set_by_name() {
+        assert!(set_log_level(" Debug ").is_ok());
+        assert!(log_enabled(LogLevel::Debug));
+        assert!(set_log_level("off").is_ok());
+        assert!(!log_enabled(LogLevel::Error));
+        assert!(set_log_level("verbose").unwrap_err().contains("\"verbose\""));
+        // An unknown name leaves the level as it was
+        assert!(!log_enabled(LogLevel::Error));
+        set_log_level("error").unwrap();
+        assert!(log_enabled(LogLevel::Error) && !log_enabled(LogLevel::Info));
+    }
+}
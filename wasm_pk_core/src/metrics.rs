@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+
+use crate::series::OrderedSeries;
+
+/// Pharmacokinetic summary of one species trajectory
+#[derive(Serialize, Deserialize)]
+pub struct PkMetrics {
+    pub cmax: f64,
+    pub tmax: f64,
+    /// AUC from the first to the last sample (linear trapezoidal)
+    pub auc: f64,
+    /// AUC extrapolated to infinity with the terminal slope
+    pub auc_inf: Option<f64>,
+    /// Terminal half-life from the log-linear regression
+    pub half_life: Option<f64>,
+    /// Lowest value after Tmax
+    pub ctrough: f64,
+    /// Last sampled value
+    #[serde(rename = "final")]
+    pub clast: f64,
+}
+
+/// Samples used for the terminal log-linear regression
+const TERMINAL_POINTS: usize = 3;
+
+/// PK metrics of one series on the (non-uniform) solver grid `time`. The terminal
+/// slope is a log-linear least-squares fit on the last TERMINAL_POINTS positive
+/// samples after Tmax; half-life and AUC(0-inf) are null when there are too few
+/// of them or the fit does not decay.
+pub fn pk_metrics(time: &[f64], values: &[f64]) -> Result<PkMetrics, String> {
+    if time.is_empty() || values.len() != time.len() {
+        return Err("trajectory has no samples".to_string());
+    }
+    let mut imax = 0;
+    for (i, v) in values.iter().enumerate() {
+        if *v > values[imax] {
+            imax = i;
+        }
+    }
+    let auc: f64 = time
+        .windows(2)
+        .zip(values.windows(2))
+        .map(|(t, v)| 0.5 * (t[1] - t[0]) * (v[0] + v[1]))
+        .sum();
+    let clast = values[values.len() - 1];
+
+    // Terminal phase: the last positive samples after Tmax
+    let terminal: Vec<(f64, f64)> = time[imax + 1..]
+        .iter()
+        .zip(&values[imax + 1..])
+        .filter(|(_, v)| **v > 0.0)
+        .map(|(t, v)| (*t, v.ln()))
+        .collect();
+    let lambda_z = if terminal.len() >= TERMINAL_POINTS {
+        let tail = &terminal[terminal.len() - TERMINAL_POINTS..];
+        let n = tail.len() as f64;
+        let t_mean = tail.iter().map(|(t, _)| t).sum::<f64>() / n;
+        let y_mean = tail.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let sxy: f64 = tail.iter().map(|(t, y)| (t - t_mean) * (y - y_mean)).sum();
+        let sxx: f64 = tail.iter().map(|(t, _)| (t - t_mean).powi(2)).sum();
+        let slope = sxy / sxx;
+        (sxx > 0.0 && slope < 0.0).then(|| -slope)
+    } else {
+        None
+    };
+
+    Ok(PkMetrics {
+        cmax: values[imax],
+        tmax: time[imax],
+        auc,
+        auc_inf: lambda_z.map(|lambda| auc + clast / lambda),
+        half_life: lambda_z.map(|lambda| std::f64::consts::LN_2 / lambda),
+        ctrough: values[imax..].iter().cloned().fold(f64::INFINITY, f64::min),
+        clast,
+    })
+}
+
+/// Value at `t` by linear interpolation; `t` must lie within the grid
+fn interpolate_at(time: &[f64], values: &[f64], t: f64) -> f64 {
+    let i = time.partition_point(|s| *s <= t).clamp(1, time.len() - 1);
+    let dt = time[i] - time[i - 1];
+    if dt <= 0.0 {
+        return values[i];
+    }
+    values[i - 1] + (t - time[i - 1]) / dt * (values[i] - values[i - 1])
+}
+
+/// Trapezoidal AUC between t1 and t2 with interpolated endpoints, so the window
+/// need not fall on the grid but must lie within it
+pub fn partial_auc(time: &[f64], values: &[f64], t1: f64, t2: f64) -> Result<f64, String> {
+    let (first, last) = (time[0], time[time.len() - 1]);
+    if t1 > t2 {
+        return Err(format!("window [{}, {}] ends before it starts", t1, t2));
+    }
+    if !(t1 >= first && t2 <= last) {
+        return Err(format!(
+            "window [{}, {}] is outside the simulated range [{}, {}]",
+            t1, t2, first, last
+        ));
+    }
+    let mut points = vec![(t1, interpolate_at(time, values, t1))];
+    points.extend(
+        time.iter()
+            .zip(values)
+            .filter(|(t, _)| **t > t1 && **t < t2)
+            .map(|(t, v)| (*t, *v)),
+    );
+    points.push((t2, interpolate_at(time, values, t2)));
+    Ok(points.windows(2).map(|p| 0.5 * (p[1].0 - p[0].0) * (p[0].1 + p[1].1)).sum())
+}
+
+/// The parts of a result JSON the metrics need; other fields are ignored
+#[derive(Deserialize)]
+struct StoredResult {
+    time: Vec<f64>,
+    species: OrderedSeries,
+}
+
+impl StoredResult {
+    fn parse(result_json: &str) -> Result<StoredResult, String> {
+        let result: StoredResult = serde_json::from_str(result_json).map_err(|e| format!("result_json: {}", e))?;
+        if result.time.is_empty() {
+            return Err("result_json: trajectory has no samples".to_string());
+        }
+        Ok(result)
+    }
+
+    fn series(&self, species: &str) -> Result<&[f64], String> {
+        let values = self
+            .species
+            .get(species)
+            .ok_or_else(|| format!("unknown species {}", species))?;
+        if values.len() != self.time.len() {
+            return Err(format!("result_json: {} has {} values for {} times", species, values.len(), self.time.len()));
+        }
+        Ok(values)
+    }
+}
+
+/// Cmax, Tmax, AUC, AUC(0-inf), terminal half-life and trough of one species of a
+/// result (JSON)
+pub fn compute_pk_metrics(result_json: &str, species: &str) -> Result<String, String> {
+    let result = StoredResult::parse(result_json)?;
+    let metrics = pk_metrics(&result.time, result.series(species)?)?;
+    serde_json::to_string(&metrics).map_err(|e| e.to_string())
+}
+
+/// AUC of one species of a result over each `[t1, t2]` window (JSON array, same order)
+pub fn compute_partial_auc(result_json: &str, species: &str, windows: &str) -> Result<String, String> {
+    let result = StoredResult::parse(result_json)?;
+    let values = result.series(species)?;
+    let windows: Vec<(f64, f64)> =
+        serde_json::from_str(windows).map_err(|e| format!("windows: {}", e))?;
+    let aucs = windows
+        .iter()
+        .map(|(t1, t2)| partial_auc(&result.time, values, *t1, *t2))
+        .collect::<Result<Vec<f64>, String>>()?;
+    serde_json::to_string(&aucs).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolation_is_linear_between_samples() {
+        let (time, values) = ([0.0, 1.0, 3.0], [0.0, 2.0, 6.0]);
+        assert_eq!(interpolate_at(&time, &values, 0.5), 1.0);
+        assert_eq!(interpolate_at(&time, &values, 2.0), 4.0);
+        assert_eq!(interpolate_at(&time, &values, 3.0), 6.0);
+        // A repeated time (a dose jump) takes the value after it
+        assert_eq!(interpolate_at(&[0.0, 1.0, 1.0, 2.0], &[0.0, 1.0, 5.0, 5.0], 1.0), 5.0);
+    }
+
+    #[test]
+    fn partial_auc_interpolates_its_endpoints() {
+        let (time, values) = ([0.0, 2.0, 4.0], [0.0, 2.0, 0.0]);
+        assert!((partial_auc(&time, &values, 1.0, 3.0).unwrap() - 3.0).abs() < 1e-12);
+        assert_eq!(partial_auc(&time, &values, 1.0, 1.0).unwrap(), 0.0);
+        assert_eq!(partial_auc(&time, &values, 3.0, 1.0).unwrap_err(), "window [3, 1] ends before it starts");
+        assert_eq!(
+            partial_auc(&time, &values, 1.0, 5.0).unwrap_err(),
+            "window [1, 5] is outside the simulated range [0, 4]"
+        );
+    }
+
+    #[test]
+    fn terminal_phase_needs_decaying_samples() {
+        let time: Vec<f64> = (0..6).map(f64::from).collect();
+        let values: Vec<f64> = time.iter().map(|t| if *t < 1.0 { 0.0 } else { (-0.5 * (t - 1.0)).exp() }).collect();
+        let metrics = pk_metrics(&time, &values).unwrap();
+        assert_eq!((metrics.cmax, metrics.tmax), (1.0, 1.0));
+        assert!((metrics.half_life.unwrap() - 2f64.ln() / 0.5).abs() < 1e-9);
+        assert!((metrics.auc_inf.unwrap() - metrics.auc - metrics.clast / 0.5).abs() < 1e-9);
+        // A rising or flat tail has no terminal slope
+        let metrics = pk_metrics(&[0.0, 1.0, 2.0, 3.0], &[1.0, 2.0, 2.0, 2.0]).unwrap();
+        assert_eq!((metrics.half_life, metrics.auc_inf), (None, None));
+        assert!(pk_metrics(&[], &[]).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::options::{OutputKind, SimulationOptions, UptakeWindow};
+use crate::error::SimulationError;
+use crate::json::from_json;
+use crate::params::{resolve_params, ParameterCheck};
+use crate::result::{failed_result, now_ms, result_to_json, Diagnostics, SimulationResult};
+use crate::series::OrderedSeries;
+use crate::solver::{BuiltProblem, Run};
+
+/// What the shared code needs to know about a generated model, as a `MODEL` const
+pub struct ModelInfo {
+    /// Species ids in state order
+    pub species: &'static [&'static str],
+    /// Parameters read by the derivative of each state, for failure hints
+    pub state_parameters: &'static [&'static [&'static str]],
+    /// Event ids in root order; empty for models without events
+    pub event_ids: &'static [&'static str],
+    /// State indices assigned by each event
+    pub event_targets: &'static [&'static [usize]],
+    /// Value of each trigger before the start: a trigger that starts true
+    /// fires its event at the start only when this is false
+    pub event_initial_values: &'static [bool],
+    /// Whether each event fires even when the events firing with it make its
+    /// trigger false; a non-persistent one is then cancelled
+    pub event_persistent: &'static [bool],
+    /// Ids of the assignment-rule observables, in the order the model evaluates them
+    pub observables: &'static [&'static str],
+    /// Whether each species is an amount (otherwise a concentration)
+    pub species_is_amount: &'static [bool],
+    /// Compartment of each species, or why its size cannot convert it
+    pub species_volumes: &'static [Result<&'static str, &'static str>],
+    /// Unit of each species and observable
+    pub series_units: &'static [(&'static str, Option<&'static str>)],
+    /// Seconds per model time unit, if the SBML declares its time units
+    pub time_seconds: Option<f64>,
+    /// SBML id of the model's time units
+    pub time_unit: Option<&'static str>,
+    /// Entries of the solver parameter vector
+    pub parameters: &'static [&'static str],
+    /// Range of each checked entry of the parameter vector, for check_parameters
+    pub parameter_checks: &'static [ParameterCheck],
+    /// States fixed by algebraic rules: their rows of `rhs` are constraints the
+    /// solver holds at zero (zero rows of the mass matrix)
+    pub algebraic: &'static [usize],
+}
+
+impl ModelInfo {
+    pub fn species_index(&self, id: &str) -> Option<usize> {
+        self.species.iter().position(|species| *species == id)
+    }
+
+    /// Error for a failed solver step at `t`, with the state and a hint at the culprit
+    pub fn solver_error(&self, message: String, t: f64, y: &[f64]) -> SimulationError {
+        let (state, hint) = self.blame(y);
+        SimulationError::SolverFailure { t, message, state, hint }
+    }
+
+    /// The state `y` as a vector, and a hint at the state most likely to blame for a failure
+    pub fn blame(&self, y: &[f64]) -> (Vec<f64>, Option<String>) {
+        let state = y.to_vec();
+        let suspicion = |v: f64| (!v.is_finite(), v < 0.0, v.abs());
+        let hint = (0..state.len())
+            .max_by(|&a, &b| suspicion(state[a]).partial_cmp(&suspicion(state[b])).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|i| {
+                let mut hint = format!("state y[{}] ({}) = {:e}", i, self.species[i], state[i]);
+                if !self.state_parameters[i].is_empty() {
+                    hint += &format!(", consider checking {}", self.state_parameters[i].join(", "));
+                }
+                hint
+            });
+        (state, hint)
+    }
+
+    /// Unit of each series of a result, and of its times under "time"
+    pub fn series_units(
+        &self,
+        species: &OrderedSeries,
+        observables: &OrderedSeries,
+        options: &SimulationOptions,
+    ) -> std::collections::BTreeMap<String, Option<String>> {
+        let mut units: std::collections::BTreeMap<_, _> = self
+            .series_units
+            .iter()
+            .filter(|(id, _)| species.get(id).is_some() || observables.get(id).is_some())
+            .map(|(id, unit)| (id.to_string(), unit.map(str::to_string)))
+            .collect();
+        let time_unit = match options.time_unit {
+            Some(unit) if options.convert_output_time.unwrap_or(false) => Some(unit.name()),
+            _ => self.time_unit,
+        };
+        units.insert("time".to_string(), time_unit.map(str::to_string));
+        units
+    }
+
+    /// Amounts and concentrations of each species for `kind`, from the compartment
+    /// sizes at each output point (`volumes[i][k]` for species k)
+    pub fn species_representations(
+        &self,
+        species: &OrderedSeries,
+        volumes: &[Vec<f64>],
+        kind: OutputKind,
+    ) -> (OrderedSeries, OrderedSeries, Vec<String>) {
+        let mut amounts = OrderedSeries::default();
+        let mut concentrations = OrderedSeries::default();
+        let mut notes = vec![];
+        for (k, (id, values)) in species.0.iter().enumerate() {
+            let is_amount = self.species_is_amount[k];
+            let converted = match kind {
+                OutputKind::Amount if is_amount => None,
+                OutputKind::Concentration if !is_amount => None,
+                _ => Some(self.convert_species(k, values, volumes)),
+            };
+            let other = match converted {
+                Some(Ok(other)) => other,
+                Some(Err(reason)) => {
+                    let native = if is_amount { "amounts" } else { "concentrations" };
+                    notes.push(format!("{} left as {}: {}", id, native, reason));
+                    values.clone()
+                }
+                None => vec![],
+            };
+            let (amount, concentration) = if is_amount {
+                (values.clone(), other)
+            } else {
+                (other, values.clone())
+            };
+            if kind != OutputKind::Concentration {
+                amounts.insert(id.clone(), amount);
+            }
+            if kind != OutputKind::Amount {
+                concentrations.insert(id.clone(), concentration);
+            }
+        }
+        (amounts, concentrations, notes)
+    }
+
+    /// Species k in the representation it is not stored in, or why it cannot be converted
+    fn convert_species(&self, k: usize, values: &[f64], volumes: &[Vec<f64>]) -> Result<Vec<f64>, String> {
+        let compartment = self.species_volumes[k].map_err(str::to_string)?;
+        if let Some(size) = volumes.iter().map(|v| v[k]).find(|v| !(v.is_finite() && *v > 0.0)) {
+            return Err(format!("compartment {} has size {}", compartment, size));
+        }
+        let sizes = volumes.iter().map(|v| v[k]);
+        Ok(if self.species_is_amount[k] {
+            values.iter().zip(sizes).map(|(amount, size)| amount / size).collect()
+        } else {
+            values.iter().zip(sizes).map(|(concentration, size)| concentration * size).collect()
+        })
+    }
+}
+
+/// A generated model as a type, so that sweeps, fitting and batch runners can be
+/// written once for every model. Generated files implement it on a zero-sized
+/// type named after the model.
+pub trait PkModel: Sized {
+    /// Parameters of one run: the model's own and the shared options
+    type Params: Clone + Serialize + DeserializeOwned;
+    /// Number of species in the state vector
+    const N_STATES: usize;
+    /// Species, events and units of the model
+    const INFO: ModelInfo;
+
+    /// Derivative `dy` of the state `y` at `t` for the parameter vector `p`, with
+    /// the run's uptake `windows` (empty for the model's own window; models
+    /// without one ignore them)
+    fn rhs(y: &[f64], p: &[f64], t: f64, windows: &[UptakeWindow], dy: &mut [f64]);
+    /// Jacobian-vector product `jv` of the derivative at (`y`, `p`, `t`) with `v`
+    fn jac(y: &[f64], p: &[f64], t: f64, v: &[f64], jv: &mut [f64]);
+    /// Initial state `y` for the parameter vector `p`
+    fn init(p: &[f64], t: f64, y: &mut [f64]);
+    /// Default parameter values as a JSON object, as get_default_parameters lists them
+    fn defaults() -> serde_json::Value;
+    /// The shared options of a run
+    fn options(params: &Self::Params) -> &SimulationOptions;
+    fn options_mut(params: &mut Self::Params) -> &mut SimulationOptions;
+    /// Solver parameter vector of a run, in the order of `INFO.parameters`
+    fn parameter_vector(params: &Self::Params) -> Vec<f64>;
+    /// The model's equations as an ODE problem, built once for any number of runs
+    fn build_problem() -> BuiltProblem<'static>;
+    /// A run of checked parameters, in model time units and ready to solve
+    fn prepare_run(params: Self::Params) -> Result<Run, SimulationError>;
+
+    /// Checks of the model's own fields, e.g. its uptake windows, after the shared options
+    fn check(_params: &Self::Params) -> Result<(), SimulationError> {
+        Ok(())
+    }
+
+    /// Move the start, the end and every scheduled time of a run by `offset`
+    fn shift_schedule(params: &mut Self::Params, offset: f64) {
+        Self::options_mut(params).shift_schedule(offset);
+    }
+
+    /// Parameters of a run without overrides
+    fn default_params() -> Self::Params {
+        resolve_params::<Self>(serde_json::Map::new()).unwrap()
+    }
+
+    /// Parameters from a JSON object of overrides, checked as every entry point checks them
+    fn parse_params(params: &str) -> Result<Self::Params, SimulationError> {
+        let overrides = from_json(params).map_err(|e| SimulationError::invalid(e.to_string()))?;
+        resolve_params::<Self>(overrides)
+    }
+
+    /// Solve several parameter sets with one problem; see [`BuiltProblem::solve`].
+    /// Rejected sets become failed results and `on_progress` receives the number
+    /// of finished runs.
+    fn simulate_runs(runs: Vec<Result<Self::Params, SimulationError>>, on_progress: impl FnMut(usize)) -> Vec<SimulationResult> {
+        let runs = runs.into_iter().map(|run| run.and_then(Self::prepare_run)).collect();
+        Self::build_problem().solve_blocking(runs, on_progress)
+    }
+
+    /// Solve one run
+    fn simulate_run(params: Self::Params) -> SimulationResult {
+        Self::simulate_runs(vec![Ok(params)], |_| {}).pop().unwrap()
+    }
+
+    /// Species ids in state order
+    fn species_names() -> &'static [&'static str] {
+        Self::INFO.species
+    }
+}
+
+/// Run one simulation of `Model` from a JSON object of parameter overrides, as a
+/// model's run_simulation does; parameter errors are reported in the result
+pub fn simulate<Model: PkModel>(params: &str) -> String {
+    let call_start = now_ms();
+    let result = match Model::parse_params(params) {
+        Ok(params) => Model::simulate_runs(vec![Ok(params)], |_| {}).pop().unwrap(),
+        Err(e) => {
+            console_log!(Error, "Error parsing params: {}", e);
+            failed_result(e)
+        }
+    };
+    result_to_json(result, call_start)
+}
+
+/// Weighted RMS of dy/dt over the included states (relative rate of change)
+pub fn steady_state_rms(y: &[f64], dydt: &[f64], include: &[bool], atol: f64) -> f64 {
+    let (mut sum, mut n) = (0.0, 0);
+    for (i, &included) in include.iter().enumerate() {
+        if included {
+            sum += (dydt[i] / (y[i].abs() + atol)).powi(2);
+            n += 1;
+        }
+    }
+    if n == 0 { 0.0 } else { (sum / n as f64).sqrt() }
+}
+
+/// Compare the total mass with the initial mass plus the (time, mass) of each dose
+pub fn mass_diagnostics(time: &[f64], total_mass: Vec<f64>, doses: &[(f64, f64)]) -> Diagnostics {
+    let dosed = |due: &dyn Fn(f64) -> bool| -> f64 {
+        doses.iter().filter(|(t, _)| due(*t)).map(|(_, mass)| mass).sum()
+    };
+    // Doses up to the first point are already in the initial state
+    let start = time.first().copied().unwrap_or(0.0);
+    let initial = total_mass.first().copied().unwrap_or(0.0) - dosed(&|t| t <= start);
+    let (mut gap, mut scale) = (0.0f64, 0.0f64);
+    for (&t, &mass) in time.iter().zip(&total_mass) {
+        let before = initial + dosed(&|td| td < t);
+        let after = before + dosed(&|td| td == t);
+        gap = gap.max((mass - before).abs().min((mass - after).abs()));
+        scale = scale.max(before.abs()).max(after.abs()).max(mass.abs());
+    }
+    let max_mass_drift = if gap > 0.0 { gap / scale } else { 0.0 };
+    Diagnostics { total_mass, max_mass_drift }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODEL: ModelInfo = ModelInfo {
+        species: &["A", "B"],
+        state_parameters: &[&["k"], &[]],
+        event_ids: &[],
+        event_targets: &[],
+        event_initial_values: &[],
+        event_persistent: &[],
+        observables: &[],
+        species_is_amount: &[true, false],
+        species_volumes: &[Ok("c"), Err("no compartment")],
+        series_units: &[("A", Some("mg")), ("B", None)],
+        time_seconds: Some(3600.0),
+        time_unit: Some("hr"),
+        parameters: &["k"],
+        parameter_checks: &[],
+        algebraic: &[],
+    };
+
+    #[test]
+    fn hints_blame_the_worst_state() {
+        let error = MODEL.solver_error("failed".to_string(), 2.0, &[1.0, f64::NAN]).payload();
+        assert_eq!(error.hint.unwrap(), "state y[1] (B) = NaN");
+        let error = MODEL.solver_error("failed".to_string(), 2.0, &[-1.0, 0.5]).payload();
+        assert_eq!((error.kind.as_str(), error.time), ("solver_failure", Some(2.0)));
+        assert_eq!(error.hint.unwrap(), "state y[0] (A) = -1e0, consider checking k");
+    }
+
+    #[test]
+    fn species_convert_through_their_compartment() {
+        let mut species = OrderedSeries::default();
+        species.insert("A".to_string(), vec![4.0, 2.0]);
+        species.insert("B".to_string(), vec![1.0, 1.0]);
+        let volumes = vec![vec![2.0, 2.0], vec![4.0, 4.0]];
+        let (amounts, concentrations, notes) = MODEL.species_representations(&species, &volumes, OutputKind::Both);
+        assert_eq!(concentrations.get("A"), Some(&vec![2.0, 0.5]));
+        assert_eq!(amounts.get("B"), Some(&vec![1.0, 1.0]));
+        assert_eq!(notes, ["B left as concentrations: no compartment"]);
+    }
+
+    #[test]
+    fn mass_drift_allows_for_doses() {
+        let diagnostics = mass_diagnostics(&[0.0, 1.0, 2.0], vec![1.0, 3.0, 3.03], &[(1.0, 2.0)]);
+        assert!((diagnostics.max_mass_drift - 0.03 / 3.03).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,608 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::SimulationError;
+use crate::model::ModelInfo;
+use crate::result::EventRecord;
+use crate::solver::{JacobianMode, SolverMethod};
+
+/// Number fields also accept numeric strings such as "75" or "1e-3"
+pub mod lenient_f64 {
+    use serde::de::{self, Deserialize, Deserializer, Visitor};
+
+    struct LenientF64;
+
+    impl Visitor<'_> for LenientF64 {
+        type Value = f64;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a number or a numeric string")
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<f64, E> {
+            Ok(v)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<f64, E> {
+            Ok(v as f64)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<f64, E> {
+            Ok(v as f64)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<f64, E> {
+            match v.trim().parse::<f64>() {
+                Ok(x) if x.is_finite() => Ok(x),
+                _ if v.contains(',') => Err(E::custom(format!("expected a number, got \"{}\" (use '.' as the decimal separator)", v))),
+                _ => Err(E::custom(format!("expected a number, got \"{}\"", v))),
+            }
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        deserializer.deserialize_any(LenientF64)
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Lenient(#[serde(deserialize_with = "deserialize")] f64);
+
+    pub fn option<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+        Ok(Option::<Lenient>::deserialize(deserializer)?.map(|Lenient(v)| v))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationMode {
+    Transient,
+    SteadyState,
+}
+
+/// Units accepted by the time_unit option
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum TimeUnit {
+    #[serde(rename = "s")]
+    S,
+    #[serde(rename = "min")]
+    Min,
+    #[serde(rename = "h")]
+    H,
+    #[serde(rename = "d")]
+    D,
+}
+
+impl TimeUnit {
+    pub fn seconds(self) -> f64 {
+        match self {
+            TimeUnit::S => 1.0,
+            TimeUnit::Min => 60.0,
+            TimeUnit::H => 3600.0,
+            TimeUnit::D => 86400.0,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TimeUnit::S => "s",
+            TimeUnit::Min => "min",
+            TimeUnit::H => "h",
+            TimeUnit::D => "d",
+        }
+    }
+}
+
+/// One scheduled dose: `amount` is added to species `target` at `time`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Dose {
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub time: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub amount: f64,
+    pub target: String,
+    /// Label only: amounts are in the units of the target species
+    pub units: Option<String>,
+}
+
+/// Add every dose due by `t` to `y`, recording it and advancing `next_dose`
+pub fn apply_doses(
+    model: &ModelInfo,
+    doses: &[Dose],
+    next_dose: &mut usize,
+    t: f64,
+    y: &mut [f64],
+    applied: &mut Vec<Dose>,
+    event_log: &mut Vec<EventRecord>,
+) {
+    while *next_dose < doses.len() && doses[*next_dose].time <= t {
+        let dose = &doses[*next_dose];
+        let i = model.species_index(&dose.target).unwrap();
+        y[i] += dose.amount;
+        applied.push(dose.clone());
+        event_log.push(EventRecord {
+            time: t,
+            id: "dose".to_string(),
+            assignments: [(dose.target.clone(), dose.amount)].into(),
+        });
+        *next_dose += 1;
+    }
+}
+
+/// Representations of the species returned besides the native one
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputKind {
+    Amount,
+    Concentration,
+    Both,
+}
+
+/// One uptake window from `t0` to `t1`; its forcing is scaled by `fraction`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UptakeWindow {
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub t0: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub t1: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub fraction: f64,
+}
+
+/// Most uptakes a repeated pulse accepts, so a huge count fails instead of
+/// exhausting memory; daily uptakes for over 27 years
+const MAX_UPTAKES: f64 = 10_000.0;
+
+/// The uptake pulse from `t0` to `t0 + width`, repeated `count` times with each
+/// copy `period` after the last. `count` and `period` are model parameters; the
+/// errors name them by the field given with each value.
+pub fn repeated_windows(t0: f64, width: f64, (count_field, count): (&str, f64), (period_field, period): (&str, f64)) -> Result<Vec<UptakeWindow>, SimulationError> {
+    if !(count >= 1.0 && count.fract() == 0.0) {
+        return Err(SimulationError::invalid_field(count_field, format!("{} must be a whole number of uptakes, at least 1, got {}", count_field, count)));
+    }
+    if count > MAX_UPTAKES {
+        return Err(SimulationError::invalid_field(count_field, format!("{} must be at most {} uptakes, got {}", count_field, MAX_UPTAKES, count)));
+    }
+    if count > 1.0 && !(period >= width && period.is_finite()) {
+        return Err(SimulationError::invalid_field(period_field, format!("{} must be at least the uptake pulse of {}, got {}", period_field, width, period)));
+    }
+    Ok((0..count as usize)
+        .map(|k| {
+            let start = t0 + k as f64 * period;
+            UptakeWindow { t0: start, t1: start + width, fraction: 1.0 }
+        })
+        .collect())
+}
+
+/// Sum `term` over a run's uptake windows, or evaluate it on the model's own
+/// window from `t0` to `t1` when there are none
+pub fn window_sum(windows: &[UptakeWindow], t0: f64, t1: f64, term: impl Fn(f64, f64) -> f64) -> f64 {
+    if windows.is_empty() {
+        return term(t0, t1);
+    }
+    windows.iter().map(|window| window.fraction * term(window.t0, window.t1)).sum()
+}
+
+/// Check a run's uptake windows: in time order, without overlaps
+pub fn check_uptake_windows(windows: &[UptakeWindow]) -> Result<(), SimulationError> {
+    if windows.is_empty() {
+        return Err(SimulationError::invalid_field("uptake_windows", "uptake_windows: no windows"));
+    }
+    for (i, window) in windows.iter().enumerate() {
+        if !(window.t0 >= 0.0 && window.t1 > window.t0 && window.t1.is_finite() && window.fraction >= 0.0 && window.fraction.is_finite()) {
+            return Err(SimulationError::invalid_field("uptake_windows", format!("uptake_windows: invalid window {} from {} to {} with fraction {}", i, window.t0, window.t1, window.fraction)));
+        }
+        if i > 0 && window.t0 < windows[i - 1].t1 {
+            return Err(SimulationError::invalid_field("uptake_windows", format!("uptake_windows: window {} starts before window {} ends", i, i - 1)));
+        }
+    }
+    Ok(())
+}
+
+/// The options every model accepts besides its own parameters; the generated
+/// SimulationParams flattens them in after the model's fields
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct SimulationOptions {
+    /// Start time; doses, events and output_times share its clock (default 0)
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub t_start: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub final_time: Option<f64>,
+    /// Unit of the times and step sizes given here (default the model's time units)
+    pub time_unit: Option<TimeUnit>,
+    /// Return times in time_unit as well (default false)
+    pub convert_output_time: Option<bool>,
+    pub output_times: Option<Vec<f64>>,
+    /// Record the state every output_dt from t_start, interpolated, instead of every step
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub output_dt: Option<f64>,
+    pub max_output_points: Option<usize>,
+    pub include_observables: Option<bool>,
+    /// Also return each species as amounts, concentrations or both (default neither)
+    pub output_kind: Option<OutputKind>,
+    /// Echo the resolved parameters in the result (default true)
+    pub include_parameters: Option<bool>,
+    /// Report the total mass at each output point (default false)
+    pub mass_balance: Option<bool>,
+    /// Check physical parameter ranges first and refuse to run on errors (default false)
+    pub validate: Option<bool>,
+    pub doses: Option<Vec<Dose>>,
+
+    // Solver options (diffsol defaults when absent)
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub rtol: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub atol: Option<f64>,
+    /// Internal steps before a run is stopped (default 1e6)
+    pub max_steps: Option<usize>,
+    /// Upper bound on the internal step size
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub max_step: Option<f64>,
+    /// First step size instead of diffsol's estimate
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub initial_step: Option<f64>,
+    /// Stop on a NaN or infinite state (default true)
+    pub check_finite: Option<bool>,
+    /// Project negative states to zero after each step (default false)
+    pub clamp_nonnegative: Option<bool>,
+    /// Negative values beyond -clamp_threshold are errors instead (default none)
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub clamp_threshold: Option<f64>,
+    /// Integration method (default bdf)
+    pub solver: Option<SolverMethod>,
+    /// J·v from the generated Jacobian or from finite differences (default analytic)
+    pub jacobian: Option<JacobianMode>,
+
+    // Steady-state mode: final_time becomes the maximum time
+    pub mode: Option<SimulationMode>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub steady_state_tol: Option<f64>,
+    pub steady_state_exclude: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub steady_state_min_time: Option<f64>,
+
+    /// Trigger values a resumed run starts from instead of their initialValue;
+    /// set by continue_simulation and run_from_checkpoint, never parsed
+    #[serde(skip)]
+    pub trigger_state: Option<Vec<bool>>,
+}
+
+impl SimulationOptions {
+    /// Check the options against each other and against the model
+    pub fn validate(&self, model: &ModelInfo) -> Result<(), SimulationError> {
+        for (name, value) in [("rtol", self.rtol), ("atol", self.atol), ("steady_state_tol", self.steady_state_tol), ("max_step", self.max_step), ("initial_step", self.initial_step), ("clamp_threshold", self.clamp_threshold)] {
+            if let Some(v) = value {
+                if !(v.is_finite() && v > 0.0) {
+                    return Err(SimulationError::invalid_field(name, format!("{} must be a positive number, got {}", name, v)));
+                }
+            }
+        }
+        if self.time_unit.is_some() && model.time_seconds.is_none() {
+            return Err(SimulationError::invalid_field("time_unit", "time_unit: the model does not declare its time units"));
+        }
+        let t_start = self.t_start.unwrap_or(0.0);
+        let final_time = self.final_time_or_default(model);
+        if !(t_start.is_finite() && t_start >= 0.0) {
+            return Err(SimulationError::invalid_field("t_start", format!("t_start must be a non-negative number, got {}", t_start)));
+        }
+        if final_time.is_nan() || final_time <= t_start {
+            return Err(SimulationError::invalid_field("final_time", format!("final_time {} must be after t_start {}", final_time, t_start)));
+        }
+        // The solver runs in model time units, where a huge final_time may overflow
+        // or a tiny span round to nothing
+        let scale = self.time_scale(model);
+        if !((final_time * scale).is_finite() && final_time * scale > t_start * scale) {
+            return Err(SimulationError::invalid_field("final_time", format!("final_time {} is out of range in the model's time units", final_time)));
+        }
+        if let Some(ref output_times) = self.output_times {
+            if output_times.is_empty() {
+                return Err(SimulationError::invalid_field("output_times", "output_times must not be empty"));
+            }
+            if output_times.iter().any(|&t| !(t >= t_start && t <= final_time)) {
+                return Err(SimulationError::invalid_field("output_times", format!("output_times must lie within [{}, {}]", t_start, final_time)));
+            }
+            if output_times.windows(2).any(|w| w[1] <= w[0]) {
+                return Err(SimulationError::invalid_field("output_times", "output_times must be strictly increasing"));
+            }
+        }
+        if let Some(output_dt) = self.output_dt {
+            if !(output_dt.is_finite() && output_dt > 0.0) {
+                return Err(SimulationError::invalid_field("output_dt", format!("output_dt must be a positive number, got {}", output_dt)));
+            }
+            if self.output_times.is_some() {
+                return Err(SimulationError::invalid_field("output_dt", "output_dt and output_times cannot both be given"));
+            }
+            if dt_grid_len(t_start, final_time, output_dt).is_some_and(|len| len > MAX_GRID_POINTS) {
+                return Err(SimulationError::invalid_field("output_dt", format!("output_dt {} gives more than {} output points", output_dt, MAX_GRID_POINTS)));
+            }
+        }
+        if let (Some(max_step), Some(initial_step)) = (self.max_step, self.initial_step) {
+            if max_step < initial_step {
+                return Err(SimulationError::invalid_field("max_step", format!("max_step {} is smaller than initial_step {}", max_step, initial_step)));
+            }
+        }
+        if self.max_steps == Some(0) {
+            return Err(SimulationError::invalid_field("max_steps", "max_steps must be positive"));
+        }
+        if !model.algebraic.is_empty() && self.solver == Some(SolverMethod::TrBdf2) {
+            return Err(SimulationError::invalid_field("solver", "tr_bdf2 cannot solve the algebraic rules of this model; use bdf"));
+        }
+        if let Some(max_output_points) = self.max_output_points {
+            if max_output_points < 2 {
+                return Err(SimulationError::invalid_field("max_output_points", "max_output_points must be at least 2"));
+            }
+            if self.output_times.as_ref().is_some_and(|ts| ts.len() > max_output_points) {
+                return Err(SimulationError::invalid_field("output_times", "output_times has more points than max_output_points"));
+            }
+            if let Some(grid) = self.output_dt.and_then(|dt| dt_grid_len(t_start, final_time, dt)) {
+                if grid > max_output_points {
+                    return Err(SimulationError::invalid_field("output_dt", format!("output_dt gives {} points, more than max_output_points", grid)));
+                }
+            }
+        }
+        if let Some(ref exclude) = self.steady_state_exclude {
+            if let Some(id) = exclude.iter().find(|id| model.species_index(id).is_none()) {
+                return Err(SimulationError::invalid_field("steady_state_exclude", format!("steady_state_exclude: unknown species {}", id)));
+            }
+        }
+        for dose in self.doses.iter().flatten() {
+            if model.species_index(&dose.target).is_none() {
+                return Err(SimulationError::invalid_field("doses", format!("doses: unknown species {}", dose.target)));
+            }
+            if !(dose.time >= t_start && dose.time.is_finite() && dose.amount.is_finite()) {
+                return Err(SimulationError::invalid_field("doses", format!("doses: invalid dose of {} to {} at t = {}", dose.amount, dose.target, dose.time)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Model time units per time_unit (1 when it is not given)
+    pub fn time_scale(&self, model: &ModelInfo) -> f64 {
+        match (self.time_unit, model.time_seconds) {
+            (Some(unit), Some(model)) => unit.seconds() / model,
+            _ => 1.0,
+        }
+    }
+
+    /// final_time, or 24 model time units in time_unit
+    pub fn final_time_or_default(&self, model: &ModelInfo) -> f64 {
+        self.final_time.unwrap_or(24.0 / self.time_scale(model))
+    }
+
+    /// The options with every time and step size in model time units
+    pub fn in_model_time(mut self, scale: f64) -> Self {
+        let times = [
+            &mut self.t_start,
+            &mut self.final_time,
+            &mut self.output_dt,
+            &mut self.max_step,
+            &mut self.initial_step,
+            &mut self.steady_state_min_time,
+        ];
+        for t in times.into_iter().flatten() {
+            *t *= scale;
+        }
+        for dose in self.doses.iter_mut().flatten() {
+            dose.time *= scale;
+        }
+        for t in self.output_times.iter_mut().flatten() {
+            *t *= scale;
+        }
+        self
+    }
+
+    /// The options with the defaults a run uses for its times and solver settings
+    pub fn with_defaults(mut self, model: &ModelInfo) -> Self {
+        self.final_time = Some(self.final_time_or_default(model));
+        self.t_start.get_or_insert(0.0);
+        self.rtol.get_or_insert(1e-6);
+        self.atol.get_or_insert(1e-6);
+        self.max_steps.get_or_insert(1_000_000);
+        self.solver.get_or_insert(SolverMethod::Bdf);
+        self.jacobian.get_or_insert(JacobianMode::Analytic);
+        self.check_finite.get_or_insert(true);
+        self.clamp_nonnegative.get_or_insert(false);
+        self.mode.get_or_insert(SimulationMode::Transient);
+        self
+    }
+
+    /// The times a run records at: output_times, or the output_dt grid from
+    /// t_start ending at final_time. None records every internal step.
+    pub fn output_grid(&self) -> Option<Vec<f64>> {
+        if let Some(ref output_times) = self.output_times {
+            return Some(output_times.clone());
+        }
+        let (t_start, final_time) = (self.t_start.unwrap_or(0.0), self.final_time.unwrap_or(24.0));
+        let dt = self.output_dt?;
+        let len = dt_grid_len(t_start, final_time, dt)?;
+        // Multiples of dt rather than a running sum, so the grid does not drift
+        let mut grid: Vec<f64> = (0..len - 1).map(|k| t_start + k as f64 * dt).collect();
+        grid.push(final_time);
+        Some(grid)
+    }
+
+    /// Move the start, the end and every scheduled time of a run by `offset`
+    pub fn shift_schedule(&mut self, offset: f64) {
+        self.t_start = Some(self.t_start.unwrap_or(0.0) + offset);
+        self.final_time = Some(self.final_time.unwrap_or(24.0) + offset);
+        for dose in self.doses.iter_mut().flatten() {
+            dose.time += offset;
+        }
+        for t in self.output_times.iter_mut().flatten() {
+            *t += offset;
+        }
+        if let Some(t) = self.steady_state_min_time.as_mut() {
+            *t += offset;
+        }
+    }
+}
+
+/// Longest output_dt grid a run accepts, so a tiny dt fails instead of exhausting memory
+const MAX_GRID_POINTS: usize = 10_000_000;
+
+/// Points of the output_dt grid over [t_start, final_time]: every multiple of dt
+/// plus final_time, which absorbs a last step shorter than a thousandth of dt.
+/// None when dt is not a positive number.
+fn dt_grid_len(t_start: f64, final_time: f64, dt: f64) -> Option<usize> {
+    if !(dt.is_finite() && dt > 0.0 && final_time > t_start) {
+        return None;
+    }
+    let steps = ((final_time - t_start) / dt - 1e-3).ceil().max(1.0);
+    Some((steps as usize).saturating_add(1))
+}
+
+/// Deserialize a model's parameters, naming the field of the first bad value
+pub fn deserialize_params<T: serde::de::DeserializeOwned>(params: serde_json::Value) -> Result<T, SimulationError> {
+    let invalid = |e: serde_path_to_error::Error<serde_json::Error>| {
+        let field = e.path().to_string();
+        let message = e.to_string();
+        SimulationError::InvalidParams { field: (field != ".").then_some(field), message }
+    };
+    // Errors inside a flattened struct lose their path, so the options are read on their own first
+    serde_path_to_error::deserialize::<_, SimulationOptions>(&params).map_err(invalid)?;
+    serde_path_to_error::deserialize(params).map_err(invalid)
+}
+
+/// Refuse the `keys` that are not fields of the serialized parameters
+pub fn check_unknown_keys(keys: &[String], fields: &serde_json::Value) -> Result<(), SimulationError> {
+    let unknown: Vec<&String> = keys.iter().filter(|key| fields.get(key.as_str()).is_none()).collect();
+    if !unknown.is_empty() {
+        let plural = if unknown.len() > 1 { "s" } else { "" };
+        let named: Vec<String> = unknown.iter().map(|key| suggest_field(key, fields)).collect();
+        let message = format!("unknown parameter{} {}", plural, named.join(", "));
+        return Err(SimulationError::InvalidParams { field: (unknown.len() == 1).then(|| unknown[0].clone()), message });
+    }
+    Ok(())
+}
+
+/// Name an unknown key, with the closest field when one is near, e.g. "Ktp_Lvier (did you mean Ktp_Liver?)"
+pub fn suggest_field(key: &str, fields: &serde_json::Value) -> String {
+    let key_lower = key.to_lowercase();
+    let closest = fields
+        .as_object()
+        .into_iter()
+        .flat_map(|fields| fields.keys())
+        .map(|field| (edit_distance(&key_lower, &field.to_lowercase()), field))
+        .min_by_key(|(distance, _)| *distance);
+    match closest {
+        Some((distance, field)) if distance <= (key.chars().count() / 3).max(1) => {
+            format!("{} (did you mean {}?)", key, field)
+        }
+        _ => key.to_string(),
+    }
+}
+
+/// Levenshtein distance between two strings, in characters
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn option_errors_name_their_field() {
+        let params = serde_json::json!({ "k": 1, "final_time": "1,5" });
+        let error = deserialize_params::<SimulationOptions>(params).err().unwrap();
+        let SimulationError::InvalidParams { field, message } = error else { panic!("{}", error) };
+        assert_eq!(field.as_deref(), Some("final_time"));
+        assert!(message.starts_with("final_time: expected a number"), "{}", message);
+        assert!(message.contains("decimal separator"));
+    }
+
+    #[test]
+    fn unknown_keys_get_suggestions() {
+        let fields = serde_json::to_value(SimulationOptions::default()).unwrap();
+        let error = check_unknown_keys(&["rtoll".to_string(), "zzz".to_string()], &fields).unwrap_err();
+        assert_eq!(error.to_string(), "unknown parameters rtoll (did you mean rtol?), zzz");
+        assert_eq!(error.payload().field, None);
+        assert!(check_unknown_keys(&["atol".to_string()], &fields).is_ok());
+    }
+
+    #[test]
+    fn schedules_shift_and_scale() {
+        let mut options = SimulationOptions { output_times: Some(vec![0.0, 2.0]), max_step: Some(0.5), ..Default::default() };
+        options.shift_schedule(10.0);
+        assert_eq!((options.t_start, options.final_time), (Some(10.0), Some(34.0)));
+        let options = options.in_model_time(60.0);
+        assert_eq!(options.output_times, Some(vec![600.0, 720.0]));
+        assert_eq!(options.max_step, Some(30.0));
+    }
+
+    #[test]
+    fn output_times_are_checked_against_the_default_final_time() {
+        let model = ModelInfo {
+            species: &["A"],
+            state_parameters: &[&[]],
+            event_ids: &[],
+            event_targets: &[],
+            event_initial_values: &[],
+            event_persistent: &[],
+            observables: &[],
+            species_is_amount: &[true],
+            species_volumes: &[Ok("c")],
+            series_units: &[("A", None)],
+            time_seconds: Some(3600.0),
+            time_unit: Some("hr"),
+            parameters: &[],
+            parameter_checks: &[],
+            algebraic: &[],
+        };
+        // 24 h of the model are 1440 min
+        let options = SimulationOptions { time_unit: Some(TimeUnit::Min), output_times: Some(vec![0.0, 1000.0, 1440.0]), ..Default::default() };
+        assert!(options.validate(&model).is_ok());
+        assert_eq!(options.clone().with_defaults(&model).final_time, Some(1440.0));
+        let options = SimulationOptions { output_times: Some(vec![1441.0]), ..options };
+        assert_eq!(options.validate(&model).unwrap_err().payload().field.as_deref(), Some("output_times"));
+    }
+
+    #[test]
+    fn output_dt_grid_ends_at_final_time() {
+        let options = SimulationOptions { t_start: Some(1.0), final_time: Some(2.0), output_dt: Some(0.3), ..Default::default() };
+        let grid = options.output_grid().unwrap();
+        assert_eq!(grid.len(), 5);
+        assert!((grid[3] - 1.9).abs() < 1e-12);
+        assert_eq!(grid[4], 2.0);
+        // A remainder within rounding of dt does not add a point
+        let options = SimulationOptions { final_time: Some(1.0), output_dt: Some(0.1), ..Default::default() };
+        assert_eq!(options.output_grid().unwrap().len(), 11);
+        assert_eq!(SimulationOptions::default().output_grid(), None);
+    }
+
+    #[test]
+    fn repeated_windows_follow_count_and_period() {
+        let windows = repeated_windows(1.0, 0.5, ("n", 3.0), ("period", 8.0)).unwrap();
+        let edges: Vec<(f64, f64)> = windows.iter().map(|window| (window.t0, window.t1)).collect();
+        assert_eq!(edges, [(1.0, 1.5), (9.0, 9.5), (17.0, 17.5)]);
+        assert!(check_uptake_windows(&windows).is_ok());
+        // One uptake needs no period
+        assert_eq!(repeated_windows(1.0, 0.5, ("n", 1.0), ("period", f64::NAN)).unwrap().len(), 1);
+        let error = repeated_windows(1.0, 0.5, ("n", 2.0), ("period", 0.25)).err();
+        assert_eq!(error.unwrap(), SimulationError::invalid_field("period", "period must be at least the uptake pulse of 0.5, got 0.25"));
+        for count in [0.0, 2.5, f64::NAN, f64::INFINITY] {
+            let error = repeated_windows(1.0, 0.5, ("n", count), ("period", 8.0)).err();
+            assert_eq!(error.unwrap().payload().field.as_deref(), Some("n"));
+        }
+        assert_eq!(repeated_windows(1.0, 0.5, ("n", 10_000.0), ("period", 8.0)).unwrap().len(), 10_000);
+        let error = repeated_windows(1.0, 0.5, ("n", 1e15), ("period", 8.0)).err();
+        assert_eq!(error.unwrap(), SimulationError::invalid_field("n", "n must be at most 10000 uptakes, got 1000000000000000"));
+    }
+
+    #[test]
+    fn overlapping_windows_are_refused() {
+        let window = |t0, t1| UptakeWindow { t0, t1, fraction: 1.0 };
+        assert!(check_uptake_windows(&[window(0.0, 1.0), window(1.0, 2.0)]).is_ok());
+        let error = check_uptake_windows(&[window(0.0, 2.0), window(1.0, 3.0)]).unwrap_err();
+        assert_eq!(error, SimulationError::invalid_field("uptake_windows", "uptake_windows: window 1 starts before window 0 ends"));
+    }
+}
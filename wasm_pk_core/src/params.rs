@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::SimulationError;
+use crate::json::from_json;
+use crate::model::PkModel;
+use crate::options::{check_unknown_keys, deserialize_params};
+
+/// Physical range of a parameter, inferred from its SBML id, name and units
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParameterRange {
+    /// In [0, 1]
+    Fraction,
+    /// In [0, 1), e.g. hematocrit
+    BelowOne,
+    /// Above zero, e.g. body weight
+    Positive,
+    /// Volumes, flows and clearances
+    NonNegative,
+    /// No known range; negative values are warnings
+    Unknown,
+}
+
+/// A checked entry of the parameter vector: its name, its index in p, its range
+/// and the parameters it is computed from (empty for a model parameter)
+pub type ParameterCheck = (&'static str, usize, ParameterRange, &'static [&'static str]);
+
+#[derive(Serialize, Deserialize)]
+pub struct ParameterIssue {
+    /// Parameter at fault; absent when the parameters could not be read
+    pub field: Option<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ParameterReport {
+    pub errors: Vec<ParameterIssue>,
+    pub warnings: Vec<ParameterIssue>,
+}
+
+/// Parameters of `Model` from `overrides` merged over the parameter object `merged`,
+/// with the checks every entry point applies: field types, unknown keys, the
+/// shared options, the model's own checks and, with `validate` set, the ranges
+pub fn merge_params<Model: PkModel>(
+    mut merged: serde_json::Value,
+    overrides: serde_json::Map<String, serde_json::Value>,
+) -> Result<Model::Params, SimulationError> {
+    let keys: Vec<String> = overrides.keys().cloned().collect();
+    if let Some(base) = merged.as_object_mut() {
+        for (key, value) in overrides {
+            base.insert(key, value);
+        }
+    }
+    // Errors name the offending field, e.g. "Ktp_Liver: invalid type: ..."
+    let params: Model::Params = deserialize_params(merged)?;
+    // serde drops unknown keys silently; the serialized struct lists every field
+    check_unknown_keys(&keys, &serde_json::to_value(&params).map_err(|e| SimulationError::Setup(e.to_string()))?)?;
+    Model::options(&params).validate(&Model::INFO)?;
+    Model::check(&params)?;
+    if Model::options(&params).validate.unwrap_or(false) {
+        let errors: Vec<String> = check_parameters::<Model>(&params).errors.into_iter().map(|e| e.message).collect();
+        if !errors.is_empty() {
+            return Err(SimulationError::invalid(format!("invalid parameters: {}", errors.join("; "))));
+        }
+    }
+    Ok(params)
+}
+
+/// Parameters of `Model` from `overrides` merged over its defaults
+pub fn resolve_params<Model: PkModel>(
+    overrides: serde_json::Map<String, serde_json::Value>,
+) -> Result<Model::Params, SimulationError> {
+    merge_params::<Model>(Model::defaults(), overrides)
+}
+
+/// Check each parameter, and each value derived from them, against its physical range
+pub fn check_parameters<Model: PkModel>(params: &Model::Params) -> ParameterReport {
+    check_ranges(&Model::parameter_vector(params), Model::INFO.parameter_checks)
+}
+
+/// Check the entries of the parameter vector `p` named by `checks`
+fn check_ranges(p: &[f64], checks: &[ParameterCheck]) -> ParameterReport {
+    let mut report = ParameterReport::default();
+    for &(name, i, range, sources) in checks {
+        let value = p[i];
+        let (outside, expected) = match range {
+            ParameterRange::Fraction => (!(0.0..=1.0).contains(&value), "must be a fraction in [0, 1]"),
+            ParameterRange::BelowOne => (!(0.0..1.0).contains(&value), "must be in [0, 1)"),
+            ParameterRange::Positive => (value <= 0.0, "must be positive"),
+            ParameterRange::NonNegative => (value < 0.0, "must not be negative"),
+            ParameterRange::Unknown => (value < 0.0, "is negative"),
+        };
+        if !(outside || value.is_nan()) {
+            continue;
+        }
+        let mut message = format!("{} {}, got {}", name, expected, value);
+        if !sources.is_empty() {
+            message += &format!(" (computed from {})", sources.join(", "));
+        }
+        let issue = ParameterIssue { field: Some(name.to_string()), message };
+        match range {
+            ParameterRange::Unknown if !value.is_nan() => report.warnings.push(issue),
+            _ => report.errors.push(issue),
+        }
+    }
+    report
+}
+
+/// Check the parameters of a `Model` run without running it: `{errors, warnings}`,
+/// each `{field, message}` (JSON). Values outside a known range are errors;
+/// negative values of parameters without one are warnings.
+pub fn validate_parameters<Model: PkModel>(params: &str) -> String {
+    let parsed = from_json::<serde_json::Map<String, serde_json::Value>>(params)
+        .map_err(|e| SimulationError::invalid(e.to_string()))
+        .and_then(|mut overrides| {
+            // The range errors are reported here rather than as one rejection
+            overrides.remove("validate");
+            resolve_params::<Model>(overrides)
+        });
+    let report = match parsed {
+        Ok(params) => check_parameters::<Model>(&params),
+        Err(error) => {
+            let field = error.payload().field;
+            ParameterReport { errors: vec![ParameterIssue { field, message: error.to_string() }], warnings: vec![] }
+        }
+    };
+    serde_json::to_string(&report).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(issues: &[ParameterIssue]) -> Vec<&str> {
+        issues.iter().map(|issue| issue.message.as_str()).collect()
+    }
+
+    #[test]
+    fn ranges_sort_values_into_errors_and_warnings() {
+        let checks: &[ParameterCheck] = &[
+            ("fu", 0, ParameterRange::Fraction, &[]),
+            ("Hct", 1, ParameterRange::BelowOne, &[]),
+            ("BW", 2, ParameterRange::Positive, &[]),
+            ("VLiver", 3, ParameterRange::NonNegative, &["BW", "scVLiver"]),
+            ("k", 4, ParameterRange::Unknown, &[]),
+        ];
+        let report = check_ranges(&[1.0, 0.45, 70.0, 0.0, 0.0], checks);
+        assert!(report.errors.is_empty() && report.warnings.is_empty());
+
+        let report = check_ranges(&[1.5, 1.0, 0.0, -1.0, -2.0], checks);
+        assert_eq!(
+            messages(&report.errors),
+            [
+                "fu must be a fraction in [0, 1], got 1.5",
+                "Hct must be in [0, 1), got 1",
+                "BW must be positive, got 0",
+                "VLiver must not be negative, got -1 (computed from BW, scVLiver)",
+            ]
+        );
+        // Without a known range a negative value is only suspicious
+        assert_eq!(messages(&report.warnings), ["k is negative, got -2"]);
+        assert_eq!(report.warnings[0].field.as_deref(), Some("k"));
+
+        // NaN is outside every range, known or not
+        let report = check_ranges(&[f64::NAN, 0.5, 1.0, 1.0, f64::NAN], checks);
+        assert_eq!(messages(&report.errors), ["fu must be a fraction in [0, 1], got NaN", "k is negative, got NaN"]);
+        assert!(report.warnings.is_empty());
+    }
+}
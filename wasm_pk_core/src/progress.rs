@@ -0,0 +1,76 @@
+use wasm_bindgen::JsValue;
+
+/// Progress callback registered with set_progress_callback
+struct ProgressCallback {
+    callback: js_sys::Function,
+    interval_ms: f64,
+    every_steps: Option<usize>,
+}
+
+thread_local! {
+    static PROGRESS_CALLBACK: std::cell::RefCell<Option<ProgressCallback>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Register `callback(fraction, steps_taken)`, called at most once per `interval_ms`
+/// (default 100) or every `every_steps` solver steps if given; None removes it
+pub fn set_progress_callback(callback: Option<js_sys::Function>, interval_ms: Option<f64>, every_steps: Option<usize>) {
+    let registered = callback.map(|callback| ProgressCallback {
+        callback,
+        interval_ms: interval_ms.unwrap_or(100.0),
+        every_steps: every_steps.map(|n| n.max(1)),
+    });
+    PROGRESS_CALLBACK.with(|progress| *progress.borrow_mut() = registered);
+}
+
+/// Throttles the registered progress callback within one simulation call. The
+/// solver loop reports before each step; an exception thrown by the callback is
+/// logged and silences it for the rest of the call.
+#[derive(Default)]
+pub struct ProgressReporter {
+    last_steps: usize,
+    last_ms: f64,
+    failed: bool,
+}
+
+impl ProgressReporter {
+    /// False once the callback has asked to cancel
+    pub fn report(&mut self, fraction: f64, steps: usize) -> bool {
+        if steps < self.last_steps {
+            // The next run of a batch
+            self.last_steps = 0;
+        }
+        // An event replays its stop without a new step, so steps can repeat
+        if self.failed || steps == 0 || steps == self.last_steps {
+            return true;
+        }
+        let due = PROGRESS_CALLBACK.with(|progress| {
+            let progress = progress.borrow();
+            let progress = progress.as_ref()?;
+            let due = match progress.every_steps {
+                Some(n) => steps >= self.last_steps + n,
+                None => {
+                    let now = js_sys::Date::now();
+                    let due = now - self.last_ms >= progress.interval_ms;
+                    if due {
+                        self.last_ms = now;
+                    }
+                    due
+                }
+            };
+            due.then(|| progress.callback.clone())
+        });
+        // Called outside the borrow, so the callback may replace itself
+        if let Some(callback) = due {
+            self.last_steps = steps;
+            let result = callback.call2(&JsValue::NULL, &fraction.min(1.0).into(), &(steps as f64).into());
+            match result {
+                Ok(value) => return value.as_bool() != Some(false),
+                Err(e) => {
+                    console_log!(Error, "Progress callback threw, ignoring it for this call: {:?}", e);
+                    self.failed = true;
+                }
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,325 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::SimulationError;
+use crate::options::Dose;
+use crate::series::OrderedSeries;
+
+#[derive(Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub species: OrderedSeries,
+    pub time: Vec<f64>,
+    pub parameters: serde_json::Value,
+    pub observables: OrderedSeries,
+    /// Unit of each series and of time; None where the SBML gives none
+    pub units: std::collections::BTreeMap<String, Option<String>>,
+    /// Species as amounts and as concentrations; filled when output_kind asks for them
+    pub amounts: OrderedSeries,
+    pub concentrations: OrderedSeries,
+    /// Species output_kind could not convert, and why
+    pub notes: Vec<String>,
+    pub steady_state: Option<SteadyState>,
+    pub applied_doses: Vec<Dose>,
+    pub events: Vec<EventRecord>,
+    /// Whether each event trigger held at the end of the run, where a
+    /// continuation starts its triggers from
+    #[serde(default)]
+    pub trigger_state: Vec<bool>,
+    /// Internal solver steps taken
+    pub steps: usize,
+    /// Solver work counters; absent when the run never started
+    pub stats: Option<SolverStats>,
+    /// Mass balance check; present when mass_balance is set
+    pub diagnostics: Option<Diagnostics>,
+    pub error: Option<ErrorPayload>,
+    // Last, so the entry points can fill in serialize_ms after serializing
+    /// Wall-clock milliseconds spent on the call
+    #[serde(default)]
+    pub timing: Timing,
+}
+
+/// Wall-clock time of a simulation call, in milliseconds
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Timing {
+    /// Solving the run, output conversions included
+    pub solve_ms: f64,
+    /// Serializing the result; 0 when it is not serialized
+    pub serialize_ms: f64,
+    /// The whole call, parameter parsing included
+    pub total_ms: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SolverStats {
+    pub steps: usize,
+    pub rhs_evals: usize,
+    pub jacobian_evals: usize,
+    pub newton_iterations: usize,
+    pub newton_failures: usize,
+    pub final_step_size: f64,
+}
+
+// Failures are reported in the result instead of panicking, so the
+// trajectory up to the failure is still returned
+/// A [`SimulationError`] as the result reports it
+#[derive(Serialize, Deserialize)]
+pub struct ErrorPayload {
+    /// Which SimulationError variant, e.g. "invalid_params" or "solver_failure"
+    #[serde(default)]
+    pub kind: String,
+    pub message: String,
+    /// Parameter to blame for invalid parameters, if one is
+    #[serde(default)]
+    pub field: Option<String>,
+    pub time: Option<f64>,
+    /// State vector when the solver failed, in SBML species order
+    pub state: Option<Vec<f64>>,
+    /// The state most likely to blame and the parameters its rate reads
+    pub hint: Option<String>,
+}
+
+/// One fired event or applied dose (id "dose") with the change it made to each species it sets
+#[derive(Serialize, Deserialize)]
+pub struct EventRecord {
+    pub time: f64,
+    pub id: String,
+    pub assignments: std::collections::BTreeMap<String, f64>,
+}
+
+/// Outcome of a steady-state run
+#[derive(Serialize, Deserialize)]
+pub struct SteadyState {
+    /// Whether the criterion was met before final_time
+    pub reached: bool,
+    /// Time steady state was declared, or final_time when it was not reached
+    pub time: f64,
+    /// Weighted RMS of dy/dt at that time
+    pub rms: f64,
+    /// State at that time, in SBML species order
+    pub state: Vec<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Diagnostics {
+    /// Total mass at each output point (mg when the model converts species to mg)
+    pub total_mass: Vec<f64>,
+    /// Largest gap to the initial mass plus doses, relative to the largest mass seen or expected
+    pub max_mass_drift: f64,
+}
+
+/// Format of exported checkpoints; older or newer ones are refused
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+/// Where a run stopped, with what it needs to go on
+#[derive(Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub version: u32,
+    /// Species ids in state order, to refuse checkpoints of another model
+    pub species: Vec<String>,
+    /// Times are in the time_unit of the parameters
+    pub time: f64,
+    pub state: Vec<f64>,
+    /// Last solver step size, the first step when resuming
+    pub step_size: f64,
+    /// Scheduled doses not applied yet, in absolute time
+    pub pending_doses: Vec<Dose>,
+    /// Whether each event trigger held, so events that fired do not fire again
+    #[serde(default)]
+    pub trigger_state: Vec<bool>,
+    pub parameters: serde_json::Value,
+}
+
+/// Result of a run that failed before it started
+pub fn failed_result(error: SimulationError) -> SimulationResult {
+    SimulationResult {
+        species: OrderedSeries::default(),
+        time: vec![],
+        parameters: serde_json::Value::Null,
+        observables: OrderedSeries::default(),
+        units: Default::default(),
+        amounts: OrderedSeries::default(),
+        concentrations: OrderedSeries::default(),
+        notes: vec![],
+        steady_state: None,
+        applied_doses: vec![],
+        events: vec![],
+        trigger_state: vec![],
+        steps: 0,
+        stats: None,
+        diagnostics: None,
+        error: Some(error.payload()),
+        timing: Timing::default(),
+    }
+}
+
+/// Milliseconds on a monotonic clock
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub fn now_ms() -> f64 {
+    use wasm_bindgen::JsCast;
+    // Windows and workers both have performance on their global object
+    js_sys::Reflect::get(&js_sys::global(), &"performance".into())
+        .ok()
+        .and_then(|performance| performance.dyn_into::<web_sys::Performance>().ok())
+        .map_or_else(js_sys::Date::now, |performance| performance.now())
+}
+
+/// Milliseconds on a monotonic clock
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+pub fn now_ms() -> f64 {
+    static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    START.get_or_init(std::time::Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+/// Serialize a result as JSON with its serialization and total time filled in
+pub fn result_to_json(mut result: SimulationResult, call_start: f64) -> String {
+    let serialize_start = now_ms();
+    let json = serde_json::to_string(&result).unwrap();
+    result.timing.serialize_ms = now_ms() - serialize_start;
+    result.timing.total_ms = now_ms() - call_start;
+    // timing is the last field, so only its object is replaced
+    let field = json.rfind("\"timing\":").unwrap();
+    format!("{}\"timing\":{}}}", &json[..field], serde_json::to_string(&result.timing).unwrap())
+}
+
+/// Serialize a result as a JS object, with its timing filled in as result_to_json does
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub fn result_to_js(mut result: SimulationResult, call_start: f64) -> Result<wasm_bindgen::JsValue, wasm_bindgen::JsValue> {
+    use wasm_bindgen::JsError;
+    let serialize_start = now_ms();
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    let value = result.serialize(&serializer).map_err(|e| JsError::new(&e.to_string()))?;
+    result.timing.serialize_ms = now_ms() - serialize_start;
+    result.timing.total_ms = now_ms() - call_start;
+    let timing = result.timing.serialize(&serializer).map_err(|e| JsError::new(&e.to_string()))?;
+    js_sys::Reflect::set(&value, &"timing".into(), &timing)?;
+    Ok(value)
+}
+
+/// Divide every time in a result by `scale`
+pub fn scale_result_times(result: &mut SimulationResult, scale: f64) {
+    for t in &mut result.time {
+        *t /= scale;
+    }
+    for dose in &mut result.applied_doses {
+        dose.time /= scale;
+    }
+    for event in &mut result.events {
+        event.time /= scale;
+    }
+    if let Some(steady_state) = &mut result.steady_state {
+        steady_state.time /= scale;
+    }
+    if let Some(t) = result.error.as_mut().and_then(|error| error.time.as_mut()) {
+        *t /= scale;
+    }
+}
+
+/// `time,<species...>` CSV of a result, one row per stored time
+pub fn result_to_csv(result: &SimulationResult) -> String {
+    let mut csv = String::from("time");
+    for (name, _) in &result.species.0 {
+        csv.push(',');
+        csv.push_str(name);
+    }
+    csv.push('\n');
+    for (i, t) in result.time.iter().enumerate() {
+        csv.push_str(&t.to_string());
+        for (_, values) in &result.species.0 {
+            csv.push(',');
+            csv.push_str(&values[i].to_string());
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Append a continuation to the result it continues; the segment's first point
+/// repeats the last one and is dropped. Series missing before are padded with NaN.
+pub fn join_results(previous: SimulationResult, segment: SimulationResult) -> SimulationResult {
+    let skip = usize::from(segment.time.first() == previous.time.last());
+    let join = |before: &OrderedSeries, after: OrderedSeries| {
+        let mut joined = OrderedSeries::default();
+        for (name, values) in after.0 {
+            let mut series = before.get(&name).cloned().unwrap_or_else(|| vec![f64::NAN; previous.time.len()]);
+            series.extend(&values[skip..]);
+            joined.insert(name, series);
+        }
+        joined
+    };
+    let species = join(&previous.species, segment.species);
+    let observables = join(&previous.observables, segment.observables);
+    let amounts = join(&previous.amounts, segment.amounts);
+    let concentrations = join(&previous.concentrations, segment.concentrations);
+    let mut notes = previous.notes;
+    for note in segment.notes {
+        if !notes.contains(&note) {
+            notes.push(note);
+        }
+    }
+    let diagnostics = previous.diagnostics.zip(segment.diagnostics).map(|(before, after)| Diagnostics {
+        total_mass: [&before.total_mass[..], &after.total_mass[skip..]].concat(),
+        max_mass_drift: before.max_mass_drift.max(after.max_mass_drift),
+    });
+    SimulationResult {
+        time: [&previous.time[..], &segment.time[skip..]].concat(),
+        species,
+        observables,
+        amounts,
+        concentrations,
+        notes,
+        applied_doses: previous.applied_doses.into_iter().chain(segment.applied_doses).collect(),
+        events: previous.events.into_iter().chain(segment.events).collect(),
+        steps: previous.steps + segment.steps,
+        diagnostics,
+        ..segment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trajectory(time: &[f64], values: &[f64]) -> SimulationResult {
+        let mut result = failed_result(SimulationError::invalid(""));
+        result.error = None;
+        result.time = time.to_vec();
+        result.species.insert("A".to_string(), values.to_vec());
+        result
+    }
+
+    #[test]
+    fn json_timing_is_filled_in_last() {
+        let json = result_to_json(trajectory(&[0.0, 1.0], &[1.0, 0.5]), now_ms());
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["species"]["A"], serde_json::json!([1.0, 0.5]));
+        assert!(value["timing"]["total_ms"].as_f64().unwrap() >= value["timing"]["serialize_ms"].as_f64().unwrap());
+    }
+
+    #[test]
+    fn csv_has_a_row_per_time() {
+        let csv = result_to_csv(&trajectory(&[0.0, 1.5], &[2.0, 0.25]));
+        assert_eq!(csv, "time,A\n0,2\n1.5,0.25\n");
+    }
+
+    #[test]
+    fn joined_results_drop_the_repeated_point() {
+        let mut segment = trajectory(&[1.0, 2.0], &[0.5, 0.25]);
+        segment.observables.insert("B".to_string(), vec![3.0, 4.0]);
+        segment.steps = 3;
+        let previous = SimulationResult { steps: 2, ..trajectory(&[0.0, 1.0], &[1.0, 0.5]) };
+        let joined = join_results(previous, segment);
+        assert_eq!(joined.time, [0.0, 1.0, 2.0]);
+        assert_eq!(joined.species.get("A"), Some(&vec![1.0, 0.5, 0.25]));
+        let observed = joined.observables.get("B").unwrap();
+        assert!(observed[0].is_nan() && observed[1].is_nan() && observed[2] == 4.0);
+        assert_eq!(joined.steps, 5);
+    }
+
+    #[test]
+    fn result_times_are_scaled() {
+        let mut result = trajectory(&[0.0, 60.0], &[1.0, 0.5]);
+        result.error = Some(SimulationError::Cancelled { t: 30.0 }.payload());
+        scale_result_times(&mut result, 60.0);
+        assert_eq!(result.time, [0.0, 1.0]);
+        assert_eq!(result.error.unwrap().time, Some(0.5));
+    }
+}
@@ -0,0 +1,291 @@
+use serde::Deserialize;
+
+use crate::json::from_json;
+use crate::metrics::{pk_metrics, PkMetrics};
+use crate::model::{simulate, PkModel};
+use crate::params::merge_params;
+use crate::result::{join_results, now_ms, result_to_csv, result_to_json, Checkpoint, SimulationResult, CHECKPOINT_VERSION};
+use crate::series::OrderedSeries;
+use crate::solver::LAST_CHECKPOINT;
+
+/// Run one simulation of `Model` per parameter object; each result carries its own error
+pub fn run_simulation_batch<Model: PkModel>(params_list: &str) -> Result<String, String> {
+    let runs: Vec<serde_json::Map<String, serde_json::Value>> =
+        from_json(params_list).map_err(|e| format!("params_list: {}", e))?;
+    let defaults = Model::defaults();
+    let total = runs.len();
+    let runs = runs
+        .into_iter()
+        .map(|overrides| merge_params::<Model>(defaults.clone(), overrides))
+        .collect();
+    let results = Model::simulate_runs(runs, |done| console_log!(Debug, "Batch progress: {}/{} runs complete", done, total));
+    // Each result's total covers its own solve and serialization
+    let results: Vec<String> = results.into_iter().map(|result| {
+        let run_start = now_ms() - result.timing.solve_ms;
+        result_to_json(result, run_start)
+    }).collect();
+    Ok(format!("[{}]", results.join(",")))
+}
+
+/// Run one simulation of `Model` with its output at `times`, instead of any
+/// output_times or output_dt in `params`
+pub fn run_simulation_at_times<Model: PkModel>(params: &str, times: &[f64]) -> String {
+    match from_json::<serde_json::Value>(params) {
+        Ok(serde_json::Value::Object(mut overrides)) => {
+            overrides.insert("output_times".to_string(), serde_json::json!(times));
+            overrides.remove("output_dt");
+            simulate::<Model>(&serde_json::Value::Object(overrides).to_string())
+        }
+        _ => simulate::<Model>(params),
+    }
+}
+
+/// Run one simulation of `Model` and return `time,<species...>` CSV, one row per stored time
+pub fn run_simulation_csv<Model: PkModel>(params: &str) -> Result<String, String> {
+    let result = Model::simulate_run(Model::parse_params(params)?);
+    if let Some(error) = result.error {
+        return Err(error.message);
+    }
+    Ok(result_to_csv(&result))
+}
+
+/// Run one simulation of `Model` and return the trajectory as little-endian f64
+/// values laid out as [n_times, n_species, times..., species0..., species1...]
+pub fn run_simulation_binary<Model: PkModel>(params: &str) -> Result<Vec<u8>, String> {
+    let result = Model::simulate_run(Model::parse_params(params)?);
+    if let Some(error) = result.error {
+        return Err(error.message);
+    }
+    let header = [result.time.len() as f64, result.species.0.len() as f64];
+    let series = result.species.0.iter().flat_map(|(_, values)| values);
+    let mut bytes = Vec::new();
+    for value in header.iter().chain(&result.time).chain(series) {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    Ok(bytes)
+}
+
+/// The run_simulation_binary layout and the species order of `Model` (JSON)
+pub fn get_binary_layout<Model: PkModel>() -> String {
+    serde_json::json!({
+        "dtype": "f64",
+        "endianness": "little",
+        "layout": ["n_times", "n_species", "times", "species"],
+        "species": Model::INFO.species,
+    })
+    .to_string()
+}
+
+/// Times and named species series of a `Model` run from run_simulation_binary output
+pub fn decode_binary_result<Model: PkModel>(bytes: &[u8]) -> Result<(Vec<f64>, OrderedSeries), String> {
+    let species_ids = Model::INFO.species;
+    if !bytes.len().is_multiple_of(8) || bytes.len() < 16 {
+        return Err(format!("invalid binary result of {} bytes", bytes.len()));
+    }
+    let values: Vec<f64> = bytes
+        .chunks_exact(8)
+        .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+        .collect();
+    // The header is untrusted: a count must be a whole number the lengths can hold
+    let count = |name: &str, value: f64| {
+        if value >= 0.0 && value.fract() == 0.0 && value <= usize::MAX as f64 / 2.0 {
+            Ok(value as usize)
+        } else {
+            Err(format!("invalid {} {} in the binary result header", name, value))
+        }
+    };
+    let (n_times, n_species) = (count("n_times", values[0])?, count("n_species", values[1])?);
+    if n_species != species_ids.len() {
+        return Err(format!("expected {} species, found {}", species_ids.len(), n_species));
+    }
+    let expected = n_times.checked_mul(1 + n_species).and_then(|n| n.checked_add(2));
+    if expected != Some(values.len()) {
+        let expected = expected.map_or_else(|| "more".to_string(), |n| n.to_string());
+        return Err(format!("expected {} values, found {}", expected, values.len()));
+    }
+    let mut series = values[2..].chunks_exact(n_times.max(1));
+    let times = series.next().unwrap_or(&[]).to_vec();
+    let mut species = OrderedSeries::default();
+    for name in species_ids {
+        species.insert(name.to_string(), series.next().unwrap_or(&[]).to_vec());
+    }
+    Ok((times, species))
+}
+
+/// One parameter and its values: an explicit list, or `n` evenly spaced values
+/// from `min` to `max`. With `species` set a sweep keeps only its metrics.
+#[derive(Deserialize)]
+struct SweepSpec {
+    parameter: String,
+    values: Option<Vec<f64>>,
+    min: Option<f64>,
+    max: Option<f64>,
+    n: Option<usize>,
+    species: Option<String>,
+}
+
+impl SweepSpec {
+    fn sweep_values(&self) -> Result<Vec<f64>, String> {
+        match (&self.values, self.min, self.max, self.n) {
+            (Some(values), None, None, None) => Ok(values.clone()),
+            (None, Some(min), Some(max), Some(n)) if n >= 2 => {
+                Ok((0..n).map(|i| min + (max - min) * i as f64 / (n - 1) as f64).collect())
+            }
+            _ => Err("sweep needs either values or min, max and n (n >= 2)".to_string()),
+        }
+    }
+}
+
+/// Shared PK metrics of one species; null for failed runs
+fn species_metrics(result: &SimulationResult, name: &str) -> Option<PkMetrics> {
+    pk_metrics(&result.time, result.species.get(name)?).ok()
+}
+
+/// Run `Model` once per value of one parameter, e.g. body weight from 20 to 100 kg.
+/// Each value is merged over the (partial) base parameters like a normal run.
+pub fn run_parameter_sweep<Model: PkModel>(base_params: &str, sweep: &str) -> Result<String, String> {
+    let base: serde_json::Map<String, serde_json::Value> =
+        from_json(base_params).map_err(|e| format!("base_params: {}", e))?;
+    let spec: SweepSpec = from_json(sweep).map_err(|e| format!("sweep: {}", e))?;
+    let defaults = Model::defaults();
+    let fields = serde_json::to_value(merge_params::<Model>(defaults.clone(), base.clone())?)
+        .map_err(|e| e.to_string())?;
+    if fields.get(&spec.parameter).is_none() {
+        return Err(format!("unknown parameter {}", spec.parameter));
+    }
+    if let Some(ref species) = spec.species {
+        if Model::INFO.species_index(species).is_none() {
+            return Err(format!("unknown species {}", species));
+        }
+    }
+    let values = spec.sweep_values()?;
+    let runs = values
+        .iter()
+        .map(|value| {
+            let mut overrides = base.clone();
+            overrides.insert(spec.parameter.clone(), serde_json::json!(value));
+            merge_params::<Model>(defaults.clone(), overrides)
+        })
+        .collect();
+    let mut entries = Vec::new();
+    for (value, result) in values.iter().zip(Model::simulate_runs(runs, |_| {})) {
+        entries.push(match spec.species {
+            Some(ref species) => serde_json::json!({
+                "value": value,
+                "metrics": species_metrics(&result, species),
+                "error": result.error,
+            }),
+            None => serde_json::json!({ "value": value, "result": result }),
+        });
+    }
+    serde_json::to_string(&entries).map_err(|e| e.to_string())
+}
+
+/// Continue a `Model` result from its last recorded state for `additional_time`,
+/// with `new_params` merged over the parameters that run used. Its doses,
+/// output_times and uptake windows are cleared so nothing is applied twice; times
+/// in `new_params` count from the continuation start. The segment is returned on
+/// the combined time axis, after the earlier trajectory when `concatenate` is set,
+/// summing the steps and keeping the segment's parameters, stats and error.
+pub fn continue_simulation<Model: PkModel>(
+    previous_result: &str,
+    new_params: &str,
+    additional_time: f64,
+    concatenate: bool,
+) -> Result<String, String> {
+    let call_start = now_ms();
+    let previous: SimulationResult = from_json(previous_result).map_err(|e| format!("previous_result: {}", e))?;
+    if let Some(error) = &previous.error {
+        return Err(format!("previous_result: cannot continue a failed run ({})", error.message));
+    }
+    let Some(&(mut t_end)) = previous.time.last() else {
+        return Err("previous_result: no trajectory to continue".to_string());
+    };
+    let serde_json::Value::Object(mut base) = previous.parameters.clone() else {
+        return Err("previous_result: no parameters to continue with (run with include_parameters false?)".to_string());
+    };
+    if !(additional_time.is_finite() && additional_time > 0.0) {
+        return Err(format!("additional_time must be a positive number, got {}", additional_time));
+    }
+    for key in ["t_start", "doses", "output_times", "uptake_windows"] {
+        if let Some(value) = base.get_mut(key) {
+            *value = serde_json::Value::Null;
+        }
+    }
+    for id in Model::INFO.species {
+        let last = previous.species.get(id).and_then(|values| values.last());
+        let last = last.ok_or_else(|| format!("previous_result: no values for {}", id))?;
+        base.insert(format!("init_{}", id), serde_json::json!(last));
+    }
+    let mut overrides: serde_json::Map<String, serde_json::Value> = from_json(new_params).map_err(|e| e.to_string())?;
+    overrides.insert("final_time".to_string(), serde_json::json!(additional_time));
+    let mut params = merge_params::<Model>(serde_json::Value::Object(base), overrides)?;
+    let options = Model::options(&params);
+    if !options.convert_output_time.unwrap_or(false) {
+        // The earlier times are in model time units, the parameters in time_unit
+        t_end /= options.time_scale(&Model::INFO);
+    }
+    Model::shift_schedule(&mut params, t_end);
+    // Events that already fired do not fire again at the continuation start
+    Model::options_mut(&mut params).trigger_state = Some(previous.trigger_state.clone());
+    let mut segment = Model::simulate_run(params);
+    if concatenate {
+        segment = join_results(previous, segment);
+    }
+    Ok(result_to_json(segment, call_start))
+}
+
+/// Resume a checkpoint exported after a `Model` run up to the absolute `final_time`
+/// in `params`, which are merged over the checkpoint's parameters. Its pending
+/// doses are applied unless `params` gives new doses, and the saved step size is
+/// the first step. The result and the new checkpoint use absolute time. A
+/// checkpoint of another format version or of a model with other species is refused.
+pub fn run_from_checkpoint<Model: PkModel>(checkpoint: &str, params: &str) -> Result<String, String> {
+    let call_start = now_ms();
+    let species_ids = Model::INFO.species;
+    let checkpoint: serde_json::Value = from_json(checkpoint).map_err(|e| format!("checkpoint: {}", e))?;
+    if checkpoint["version"] != CHECKPOINT_VERSION {
+        return Err(format!(
+            "checkpoint: format version {} is not supported, expected {}",
+            checkpoint["version"], CHECKPOINT_VERSION
+        ));
+    }
+    let checkpoint: Checkpoint = serde_json::from_value(checkpoint).map_err(|e| format!("checkpoint: {}", e))?;
+    if checkpoint.species != species_ids || checkpoint.state.len() != species_ids.len() {
+        return Err(format!("checkpoint: saved for species {:?}, not {:?}", checkpoint.species, species_ids));
+    }
+    let serde_json::Value::Object(mut base) = checkpoint.parameters else {
+        return Err("checkpoint: parameters must be an object".to_string());
+    };
+    let mut pending_doses = checkpoint.pending_doses;
+    for dose in &mut pending_doses {
+        dose.time = dose.time.max(checkpoint.time);
+    }
+    base.insert("doses".to_string(), serde_json::json!(pending_doses));
+    base.insert("output_times".to_string(), serde_json::Value::Null);
+    for (id, value) in species_ids.iter().zip(&checkpoint.state) {
+        base.insert(format!("init_{}", id), serde_json::json!(value));
+    }
+    let mut overrides: serde_json::Map<String, serde_json::Value> = from_json(params).map_err(|e| e.to_string())?;
+    overrides.insert("t_start".to_string(), serde_json::json!(checkpoint.time));
+    let mut params = merge_params::<Model>(serde_json::Value::Object(base), overrides)?;
+    let mut resolved = params.clone();
+    let options = Model::options_mut(&mut resolved);
+    *options = std::mem::take(options).with_defaults(&Model::INFO);
+    let parameters = serde_json::to_value(resolved).unwrap();
+    let options = Model::options_mut(&mut params);
+    let max_step = options.max_step.unwrap_or(f64::INFINITY);
+    options.initial_step = options.initial_step.or(Some(checkpoint.step_size.min(max_step)));
+    options.trigger_state = Some(checkpoint.trigger_state);
+    let mut result = Model::simulate_run(params);
+    if !result.parameters.is_null() {
+        result.parameters = parameters.clone();
+    }
+    LAST_CHECKPOINT.with(|last| {
+        if let Some(next) = last.borrow_mut().as_mut() {
+            next.parameters = parameters;
+        }
+    });
+    Ok(result_to_json(result, call_start))
+}
+
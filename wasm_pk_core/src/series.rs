@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+/// Named series serialized as a JSON object in insertion order
+#[derive(Default)]
+pub struct OrderedSeries(pub Vec<(String, Vec<f64>)>);
+
+impl OrderedSeries {
+    pub fn insert(&mut self, name: String, values: Vec<f64>) {
+        self.0.push((name, values));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Vec<f64>> {
+        self.0.iter().find(|(key, _)| key == name).map(|(_, values)| values)
+    }
+}
+
+impl Serialize for OrderedSeries {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, values) in &self.0 {
+            map.serialize_entry(name, values)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderedSeries {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SeriesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SeriesVisitor {
+            type Value = OrderedSeries;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a map of named series")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut access: A) -> Result<OrderedSeries, A::Error> {
+                let mut series = OrderedSeries::default();
+                while let Some((name, values)) = access.next_entry()? {
+                    series.insert(name, values);
+                }
+                Ok(series)
+            }
+        }
+
+        deserializer.deserialize_map(SeriesVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn series_keep_insertion_order() {
+        let mut series = OrderedSeries::default();
+        series.insert("b".to_string(), vec![1.0]);
+        series.insert("a".to_string(), vec![2.0, 3.0]);
+        let json = serde_json::to_string(&series).unwrap();
+        assert_eq!(json, r#"{"b":[1.0],"a":[2.0,3.0]}"#);
+        let back: OrderedSeries = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.0[0].0, "b");
+        assert_eq!(back.get("a"), Some(&vec![2.0, 3.0]));
+    }
+}
@@ -0,0 +1,42 @@
+use crate::error::SimulationError;
+use crate::model::PkModel;
+use crate::params::merge_params;
+use crate::result::SimulationResult;
+use crate::solver::{BuiltProblem, Run};
+
+/// Repeated runs of one model, e.g. behind a dashboard slider. The session keeps
+/// its checked parameters, the run prepared from them and the built problem, so
+/// a run only re-initialises the solver; a parameter change merges just the
+/// changed field, with every check of the other entry points.
+pub struct Session<Model: PkModel> {
+    params: Model::Params,
+    run: Run,
+    problem: BuiltProblem<'static>,
+}
+
+impl<Model: PkModel> Session<Model> {
+    pub fn new(params: Model::Params) -> Result<Self, SimulationError> {
+        let run = Model::prepare_run(params.clone())?;
+        Ok(Session { params, run, problem: Model::build_problem() })
+    }
+
+    /// Change one numeric parameter or option; rejected values leave the session unchanged
+    pub fn set_parameter(&mut self, name: &str, value: f64) -> Result<(), SimulationError> {
+        let current = serde_json::to_value(&self.params).map_err(|e| SimulationError::Setup(e.to_string()))?;
+        let mut overrides = serde_json::Map::new();
+        overrides.insert(name.to_string(), serde_json::json!(value));
+        let params = merge_params::<Model>(current, overrides)?;
+        self.run = Model::prepare_run(params.clone())?;
+        self.params = params;
+        Ok(())
+    }
+
+    pub fn params(&self) -> &Model::Params {
+        &self.params
+    }
+
+    /// Solve with the current parameters; the session can be run again
+    pub fn run(&mut self) -> SimulationResult {
+        self.problem.solve_blocking(vec![Ok(self.run.clone())], |_| {}).pop().unwrap()
+    }
+}
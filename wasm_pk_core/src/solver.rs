@@ -0,0 +1,1078 @@
+use std::cell::RefCell;
+
+use diffsol::{
+    BdfState, ConstantOp, DefaultDenseMatrix, DefaultSolver, Matrix, NonLinearOp, NonLinearOpJacobian, OdeBuilder, OdeEquations, OdeSolverMethod,
+    OdeSolverProblem, OdeSolverState, OdeSolverStopReason, Op, Vector, VectorHost, VectorRef,
+};
+use diffsol::matrix::MatrixRef;
+use serde::{Deserialize, Serialize};
+
+use crate::equations::{JacobianMatrix, ModelEquations};
+use crate::error::SimulationError;
+use crate::model::{mass_diagnostics, steady_state_rms, ModelInfo};
+use crate::options::{apply_doses, SimulationMode, SimulationOptions, UptakeWindow};
+use crate::result::{failed_result, now_ms, scale_result_times, Checkpoint, EventRecord, SimulationResult, SolverStats, SteadyState, Timing, CHECKPOINT_VERSION};
+use crate::series::OrderedSeries;
+use crate::trajectory::Trajectory;
+use crate::{SparseM, M};
+
+/// Integration method: BDF, or the L-stable TR-BDF2 SDIRK scheme
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SolverMethod {
+    Bdf,
+    TrBdf2,
+}
+
+/// Where the Newton iterations take J·v from: the generated `jac` (and
+/// `jac_matrix`), or forward differences of `rhs` to rule out an error in them
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JacobianMode {
+    Analytic,
+    FiniteDifference,
+}
+
+/// The solver of one run, whichever method was chosen
+enum Integrator<B, S> {
+    Bdf(B),
+    TrBdf2(S),
+}
+
+impl<B, S> Integrator<B, S> {
+    fn step<'a, Eqn>(&mut self) -> Result<OdeSolverStopReason<f64>, diffsol::error::DiffsolError>
+    where
+        Eqn: OdeEquations<T = f64> + 'a,
+        B: OdeSolverMethod<'a, Eqn>,
+        S: OdeSolverMethod<'a, Eqn>,
+    {
+        match self {
+            Integrator::Bdf(s) => s.step(),
+            Integrator::TrBdf2(s) => s.step(),
+        }
+    }
+
+    fn state<'a, Eqn>(&self) -> diffsol::StateRef<'_, Eqn::V>
+    where
+        Eqn: OdeEquations<T = f64> + 'a,
+        B: OdeSolverMethod<'a, Eqn>,
+        S: OdeSolverMethod<'a, Eqn>,
+    {
+        match self {
+            Integrator::Bdf(s) => s.state(),
+            Integrator::TrBdf2(s) => s.state(),
+        }
+    }
+
+    fn state_mut<'a, Eqn>(&mut self) -> diffsol::StateRefMut<'_, Eqn::V>
+    where
+        Eqn: OdeEquations<T = f64> + 'a,
+        B: OdeSolverMethod<'a, Eqn>,
+        S: OdeSolverMethod<'a, Eqn>,
+    {
+        match self {
+            Integrator::Bdf(s) => s.state_mut(),
+            Integrator::TrBdf2(s) => s.state_mut(),
+        }
+    }
+
+    fn set_stop_time<'a, Eqn>(&mut self, tstop: f64) -> Result<(), diffsol::error::DiffsolError>
+    where
+        Eqn: OdeEquations<T = f64> + 'a,
+        B: OdeSolverMethod<'a, Eqn>,
+        S: OdeSolverMethod<'a, Eqn>,
+    {
+        match self {
+            Integrator::Bdf(s) => s.set_stop_time(tstop),
+            Integrator::TrBdf2(s) => s.set_stop_time(tstop),
+        }
+    }
+
+    fn interpolate<'a, Eqn>(&self, t: f64) -> Result<Eqn::V, diffsol::error::DiffsolError>
+    where
+        Eqn: OdeEquations<T = f64> + 'a,
+        B: OdeSolverMethod<'a, Eqn>,
+        S: OdeSolverMethod<'a, Eqn>,
+    {
+        match self {
+            Integrator::Bdf(s) => s.interpolate(t),
+            Integrator::TrBdf2(s) => s.interpolate(t),
+        }
+    }
+}
+
+/// Derivative at (state, parameter vector, time) with the run's uptake windows
+pub type RhsFn = fn(&[f64], &[f64], f64, &[UptakeWindow], &mut [f64]);
+/// Jacobian-vector product at (state, parameter vector, time) with a direction
+pub type JacFn = fn(&[f64], &[f64], f64, &[f64], &mut [f64]);
+/// Initial state for a parameter vector and start time
+pub type InitFn = fn(&[f64], f64, &mut [f64]);
+/// Event roots at (state, parameter vector, time)
+pub type RootFn = fn(&[f64], &[f64], f64, &mut [f64]);
+/// The state after the events flagged in the slice fire together
+pub type EventFn = fn(&[f64], f64, &[bool], &[f64]) -> Vec<f64>;
+/// Whether each event trigger holds at a state and time
+pub type TriggerFn = fn(&[f64], f64, &[f64]) -> Vec<bool>;
+/// Values at a state, at a time, for a parameter vector
+pub type SeriesFn = fn(&[f64], f64, &[f64]) -> Vec<f64>;
+/// Total mass of a state for a parameter vector
+pub type MassFn = fn(&[f64], &[f64]) -> f64;
+
+/// The generated functions of a model, on plain slices so that one model works
+/// with either matrix type. `rhs`, `jac`, `init` and `roots` are handed to
+/// diffsol; the others are evaluated on (state, time, parameter vector). They
+/// are plain function pointers (the generated closures capture nothing), so a
+/// built problem can be kept between calls.
+#[derive(Clone, Copy)]
+pub struct Equations<'a> {
+    pub rhs: RhsFn,
+    pub jac: JacFn,
+    pub init: InitFn,
+    /// One root per event; [`no_roots`] for models without events
+    pub roots: RootFn,
+    /// Whether each event trigger holds
+    pub triggers: TriggerFn,
+    /// Applies the events flagged in the slice together
+    pub apply_events: EventFn,
+    /// Values of the observables, in the order of `ModelInfo::observables`
+    pub observables: SeriesFn,
+    /// Compartment size of each species
+    pub volumes: SeriesFn,
+    /// Total mass of a state
+    pub mass: MassFn,
+    /// The Jacobian matrix itself, for models generated with jacobian_matrix;
+    /// without it the solver assembles the matrix from `jac` products
+    pub jac_matrix: Option<JacobianMatrix<'a>>,
+}
+
+/// Root function of models without events
+pub fn no_roots(_y: &[f64], _p: &[f64], _t: f64, _roots: &mut [f64]) {}
+
+/// Trigger states of models without events
+pub fn no_triggers(_y: &[f64], _t: f64, _p: &[f64]) -> Vec<bool> {
+    vec![]
+}
+
+/// Event assignments of models without events
+pub fn no_events(y: &[f64], _t: f64, _fire: &[bool], _p: &[f64]) -> Vec<f64> {
+    y.to_vec()
+}
+
+/// One parameter set ready to solve
+#[derive(Clone)]
+pub struct Run {
+    /// Options with every time in model time units
+    pub options: SimulationOptions,
+    /// Solver parameter vector, ending with the initial state
+    pub p: Vec<f64>,
+    /// The parameters with their defaults, as echoed in the result and checkpoint
+    pub resolved: serde_json::Value,
+    /// Model time units per time unit of the parameters
+    pub scale: f64,
+    /// Uptake windows in model time units; empty for the model's own window
+    pub uptake_windows: Vec<UptakeWindow>,
+}
+
+/// Poll a future whose pauses are all ready, as the synchronous entry points' are
+pub fn run_ready<T>(future: impl std::future::Future<Output = T>) -> T {
+    let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    match std::future::Future::poll(std::pin::pin!(future), &mut context) {
+        std::task::Poll::Ready(value) => value,
+        std::task::Poll::Pending => unreachable!("a synchronous run paused"),
+    }
+}
+
+/// Time past a root, relative to the time and step size, at which triggers
+/// are read: well above the root finder's tolerance (100 ulp of the same)
+const ROOT_PROBE: f64 = 1e4 * f64::EPSILON;
+
+/// The state at an event time and just past it, where the triggers are read
+struct EventTime<'a> {
+    t: f64,
+    y: &'a [f64],
+    t_probe: f64,
+    y_probe: &'a [f64],
+}
+
+impl EventTime<'_> {
+    /// A state set at `t`, carried to `t_probe` with the change of the trajectory
+    fn carried(&self, y_new: &[f64]) -> Vec<f64> {
+        y_new.iter().zip(self.y_probe).zip(self.y).map(|((new, probe), y)| new + (probe - y)).collect()
+    }
+}
+
+/// The events that fire at `at`: those whose trigger holds `now` but not
+/// `before`. A non-persistent one is cancelled when the assignments of the
+/// others firing with it make its trigger false before it is applied.
+fn firing_events(model: &ModelInfo, eval_triggers: TriggerFn, apply_events: EventFn, at: &EventTime, p: &[f64], now: &[bool], before: &[bool]) -> Vec<bool> {
+    let fire: Vec<bool> = now.iter().zip(before).map(|(&now, &before)| now && !before).collect();
+    if fire.iter().filter(|&&f| f).count() < 2 {
+        return fire;
+    }
+    (0..fire.len())
+        .map(|i| {
+            if !fire[i] || model.event_persistent[i] {
+                return fire[i];
+            }
+            let others: Vec<bool> = (0..fire.len()).map(|j| fire[j] && j != i).collect();
+            eval_triggers(&at.carried(&apply_events(at.y, at.t, &others, p)), at.t_probe, p)[i]
+        })
+        .collect()
+}
+
+/// Log each firing event with the change it makes on its own
+fn log_events(model: &ModelInfo, apply_events: EventFn, y: &[f64], t: f64, fire: &[bool], p: &[f64], event_log: &mut Vec<EventRecord>) {
+    for i in (0..fire.len()).filter(|&i| fire[i]) {
+        let alone: Vec<bool> = (0..fire.len()).map(|j| j == i).collect();
+        let y_alone = apply_events(y, t, &alone, p);
+        console_log!(Debug, "Event {} fired at t = {}", model.event_ids[i], t);
+        event_log.push(EventRecord {
+            time: t,
+            id: model.event_ids[i].to_string(),
+            assignments: model.event_targets[i].iter().map(|&s| (model.species[s].to_string(), y_alone[s] - y[s])).collect(),
+        });
+    }
+}
+
+/// Record the solve time of the run just pushed and report progress
+fn finish_run(results: &mut [SimulationResult], run_start: f64, on_progress: &mut impl FnMut(usize)) {
+    let timing = &mut results.last_mut().unwrap().timing;
+    timing.solve_ms = now_ms() - run_start;
+    timing.total_ms = timing.solve_ms;
+    on_progress(results.len());
+}
+
+/// Drop the samples whose `keep` flag is false (used to bound the output size)
+pub fn thin_output<T>(values: &mut Vec<T>, keep: &[bool]) {
+    let mut flags = keep.iter();
+    values.retain(|_| *flags.next().unwrap());
+}
+
+/// Output points reserved when nothing better is known: BDF takes a few hundred
+/// steps for a typical run
+const TYPICAL_STEPS: usize = 256;
+/// Extra steps after each dose, event or window edge, where the step size restarts small
+const STEPS_PER_STOP: usize = 64;
+/// Upper bound of the reservation, whatever the estimate
+const MAX_RESERVED_POINTS: usize = 1 << 16;
+
+/// Output points to reserve for a run: the length of output_times, the output_dt
+/// grid and its stops, otherwise a step count guessed from the stops and max_step,
+/// at most max_output_points and MAX_RESERVED_POINTS. The trajectory grows past it
+/// if the guess is low.
+fn expected_output_points(options: &SimulationOptions, grid: Option<&[f64]>, t_start: f64, final_time: f64, stops: usize) -> usize {
+    match grid {
+        Some(grid) if options.output_times.is_some() => return grid.len().min(MAX_RESERVED_POINTS),
+        // Each stop inserts its pre- and post-stop states into the grid
+        Some(grid) => return (grid.len() + 2 * stops).min(MAX_RESERVED_POINTS),
+        None => {}
+    }
+    let mut steps = TYPICAL_STEPS + STEPS_PER_STOP * stops;
+    if let Some(max_step) = options.max_step {
+        steps = steps.max(((final_time - t_start) / max_step).ceil().min(MAX_RESERVED_POINTS as f64) as usize);
+    }
+    if options.solver == Some(SolverMethod::TrBdf2) {
+        // Its error estimate keeps steps several times shorter
+        steps *= 8;
+    }
+    // Every stop records its post-dose state as well
+    (steps + stops + 1).min(options.max_output_points.map_or(usize::MAX, |max| max.saturating_add(stops))).min(MAX_RESERVED_POINTS)
+}
+
+/// Newton iterations allowed to satisfy the algebraic rules
+const CONSISTENCY_ITERATIONS: usize = 50;
+
+/// Solve the m×m row-major system `a x = b` by Gaussian elimination with
+/// partial pivoting; None if it is singular
+fn solve_dense(mut a: Vec<f64>, mut b: Vec<f64>, m: usize) -> Option<Vec<f64>> {
+    for col in 0..m {
+        let pivot = (col..m).max_by(|&i, &j| a[i * m + col].abs().total_cmp(&a[j * m + col].abs()))?;
+        if a[pivot * m + col] == 0.0 || !a[pivot * m + col].is_finite() {
+            return None;
+        }
+        if pivot != col {
+            for k in 0..m {
+                a.swap(pivot * m + k, col * m + k);
+            }
+            b.swap(pivot, col);
+        }
+        for row in col + 1..m {
+            let factor = a[row * m + col] / a[col * m + col];
+            for k in col..m {
+                a[row * m + k] -= factor * a[col * m + k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    for row in (0..m).rev() {
+        let sum: f64 = (row + 1..m).map(|k| a[row * m + k] * b[k]).sum();
+        b[row] = (b[row] - sum) / a[row * m + row];
+    }
+    Some(b)
+}
+
+/// Make `y` consistent with the algebraic rules: Newton's method on the
+/// `algebraic` rows of `rhs` for the algebraic states, the others held fixed.
+/// Needed at the start and after every dose or event, which change the
+/// integrated states the constraints depend on.
+fn consistent_state<Rhs>(rhs: &Rhs, algebraic: &[usize], t: f64, y: &mut [f64]) -> Result<(), String>
+where
+    Rhs: NonLinearOpJacobian<T = f64>,
+    Rhs::V: VectorHost,
+{
+    if algebraic.is_empty() {
+        return Ok(());
+    }
+    let m = algebraic.len();
+    let mut x = Rhs::V::from_slice(y, rhs.context().clone());
+    let mut residual = x.clone();
+    let mut direction = x.clone();
+    let mut column = x.clone();
+    for _ in 0..CONSISTENCY_ITERATIONS {
+        rhs.call_inplace(&x, t, &mut residual);
+        let scale = x.as_slice().iter().fold(1.0_f64, |m, x| m.max(x.abs()));
+        if algebraic.iter().all(|&i| residual[i].abs() <= 1e-12 * scale) {
+            y.copy_from_slice(x.as_slice());
+            return Ok(());
+        }
+        // The constraints' derivatives with respect to the algebraic states
+        let mut jacobian = vec![0.0; m * m];
+        for (j, &k) in algebraic.iter().enumerate() {
+            direction.fill(0.0);
+            direction[k] = 1.0;
+            rhs.jac_mul_inplace(&x, t, &direction, &mut column);
+            for (i, &row) in algebraic.iter().enumerate() {
+                jacobian[i * m + j] = column[row];
+            }
+        }
+        let step = solve_dense(jacobian, algebraic.iter().map(|&i| -residual[i]).collect(), m)
+            .ok_or("the algebraic rules do not determine their states (singular Jacobian)")?;
+        for (&k, dx) in algebraic.iter().zip(step) {
+            x[k] += dx;
+        }
+    }
+    Err(format!("the algebraic rules were not satisfied after {} Newton iterations", CONSISTENCY_ITERATIONS))
+}
+
+/// Zero the negative samples of a series (clamp_nonnegative)
+pub fn clamp_output(values: &mut [f64]) {
+    for value in values.iter_mut().filter(|value| **value < 0.0) {
+        *value = 0.0;
+    }
+}
+
+thread_local! {
+    pub static LAST_RUN_STATS: RefCell<Option<SolverStats>> = const { RefCell::new(None) };
+    pub static LAST_CHECKPOINT: RefCell<Option<Checkpoint>> = const { RefCell::new(None) };
+}
+
+/// A model's equations as a diffsol system, with its parameter vector set by each run
+type System<'a, Mat> = ModelEquations<'a, Mat, RhsFn, JacFn, InitFn, RootFn>;
+
+/// A built problem, or why building it failed, for either matrix type; boxed, as
+/// they are large and differ in size
+enum Problem<'a> {
+    Dense(Box<Result<OdeSolverProblem<System<'a, M>>, String>>),
+    Sparse(Box<Result<OdeSolverProblem<System<'a, SparseM>>, String>>),
+}
+
+/// A model's ODE problem, built once and solved for any number of parameter
+/// sets: each run swaps the parameter vector, uptake windows and tolerances and
+/// re-initialises the solver.
+pub struct BuiltProblem<'a> {
+    model: ModelInfo,
+    equations: Equations<'a>,
+    problem: Problem<'a>,
+}
+
+impl<'a> BuiltProblem<'a> {
+    /// The problem whose Newton iterations factorise a dense Jacobian
+    pub fn dense(model: ModelInfo, equations: Equations<'a>) -> Self {
+        let problem = Problem::Dense(Box::new(build::<M>(&model, &equations)));
+        BuiltProblem { model, equations, problem }
+    }
+
+    /// The problem with a sparse Jacobian and LU, for models generated with
+    /// sparse_jacobian. diffsol finds the sparsity pattern by pushing NaN through
+    /// `jac` one column at a time, so it is the structural pattern of the
+    /// generated Jacobian.
+    pub fn sparse(model: ModelInfo, equations: Equations<'a>) -> Self {
+        let problem = Problem::Sparse(Box::new(build::<SparseM>(&model, &equations)));
+        BuiltProblem { model, equations, problem }
+    }
+
+    /// Solve several parameter sets. Rejected parameter sets become failed results;
+    /// `on_progress` receives the number of finished runs. `pause` is awaited before
+    /// every solver step with the fraction of the way from t_start to final_time and
+    /// the steps taken so far; false cancels the run.
+    pub async fn solve<P: std::future::Future<Output = bool>>(
+        &mut self,
+        runs: Vec<Result<Run, SimulationError>>,
+        on_progress: impl FnMut(usize),
+        pause: impl FnMut(f64, usize) -> P,
+    ) -> Vec<SimulationResult> {
+        match &mut self.problem {
+            Problem::Dense(problem) => solve(&self.model, &self.equations, &mut **problem, runs, on_progress, pause).await,
+            Problem::Sparse(problem) => solve(&self.model, &self.equations, &mut **problem, runs, on_progress, pause).await,
+        }
+    }
+
+    /// Solve without pausing, as the synchronous entry points do; in the browser
+    /// the steps report to the callback of set_progress_callback, which may cancel
+    pub fn solve_blocking(&mut self, runs: Vec<Result<Run, SimulationError>>, on_progress: impl FnMut(usize)) -> Vec<SimulationResult> {
+        #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+        {
+            let mut reporter = crate::progress::ProgressReporter::default();
+            run_ready(self.solve(runs, on_progress, |fraction, steps| std::future::ready(reporter.report(fraction, steps))))
+        }
+        #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+        run_ready(self.solve(runs, on_progress, |_, _| std::future::ready(true)))
+    }
+}
+
+/// Build the diffsol problem of a model's equations with a zero parameter vector
+fn build<'a, Mat>(model: &ModelInfo, equations: &Equations<'a>) -> Result<OdeSolverProblem<System<'a, Mat>>, String>
+where
+    Mat: Matrix<T = f64> + DefaultSolver,
+    Mat::V: VectorHost + DefaultDenseMatrix<T = f64, C = Mat::C>,
+    for<'b> &'b Mat::V: VectorRef<Mat::V>,
+    for<'b> &'b Mat: MatrixRef<Mat>,
+{
+    let system = ModelEquations::<Mat, _, _, _, _>::new(
+        equations.rhs,
+        equations.jac,
+        equations.init,
+        equations.roots,
+        model.species.len(),
+        model.event_ids.len(),
+        model.parameters.len(),
+        equations.jac_matrix,
+        model.algebraic,
+    );
+    OdeBuilder::<Mat>::new()
+        .p(vec![0.0; model.parameters.len()])
+        .build_from_eqn(system)
+        .map_err(|e| e.to_string())
+        // diffsol builds an empty system, but its solvers cannot step one
+        .and_then(|problem| if problem.eqn.rhs().nstates() == 0 { Err("the model has no species to integrate".to_string()) } else { Ok(problem) })
+}
+
+async fn solve<'a, Mat, P>(
+    model: &ModelInfo,
+    equations: &Equations<'a>,
+    problem: &mut Result<OdeSolverProblem<System<'a, Mat>>, String>,
+    runs: Vec<Result<Run, SimulationError>>,
+    mut on_progress: impl FnMut(usize),
+    mut pause: impl FnMut(f64, usize) -> P,
+) -> Vec<SimulationResult>
+where
+    Mat: Matrix<T = f64> + DefaultSolver,
+    Mat::V: VectorHost + DefaultDenseMatrix<T = f64, C = Mat::C>,
+    for<'b> &'b Mat::V: VectorRef<Mat::V>,
+    for<'b> &'b Mat: MatrixRef<Mat>,
+    P: std::future::Future<Output = bool>,
+{
+    let Equations { triggers: eval_triggers, apply_events, observables: eval_observables, volumes: eval_volumes, mass: eval_mass, .. } = *equations;
+    let n = model.species.len();
+    let mut results = Vec::with_capacity(runs.len());
+    let mut checkpoint = None;
+    for run in runs {
+        let run_start = now_ms();
+        checkpoint = None;
+        let problem = match problem.as_mut() {
+            Ok(problem) => problem,
+            Err(e) => {
+                results.push(failed_result(SimulationError::Setup(format!("building the ODE problem failed: {}", e))));
+                finish_run(&mut results, run_start, &mut on_progress);
+                continue;
+            }
+        };
+        let Run { options, p, resolved, scale, uptake_windows: windows } = match run {
+            Ok(run) => run,
+            Err(e) => {
+                results.push(failed_result(e));
+                finish_run(&mut results, run_start, &mut on_progress);
+                continue;
+            }
+        };
+        // The solver runs in model time units; results and checkpoints use the caller's
+        let parameters = if options.include_parameters.unwrap_or(true) { resolved.clone() } else { serde_json::Value::Null };
+        let output_scale = if options.convert_output_time.unwrap_or(false) { scale } else { 1.0 };
+        console_log!(Info, "Starting simulation...");
+        problem.eqn.set_params(&Mat::V::from_slice(&p, Default::default()));
+        problem.eqn.set_jacobian(options.jacobian.unwrap_or(JacobianMode::Analytic));
+        problem.eqn.set_uptake_windows(windows.clone());
+        problem.rtol = options.rtol.unwrap_or(1e-6);
+        problem.atol.fill(options.atol.unwrap_or(1e-6));
+
+        let rhs_before = problem.eqn.rhs().statistics();
+        // A non-finite initial derivative would hang the solver setup
+        let t_start = options.t_start.unwrap_or(0.0);
+        problem.t0 = t_start;
+        let mut y0 = problem.eqn.init().call(t_start);
+        if let Err(message) = consistent_state(&problem.eqn.rhs(), model.algebraic, t_start, y0.as_mut_slice()) {
+            let (state, hint) = model.blame(y0.as_slice());
+            let message = format!("no initial state satisfies the algebraic rules: {}", message);
+            results.push(SimulationResult {
+                parameters,
+                ..failed_result(SimulationError::SolverFailure { t: t_start, message, state, hint })
+            });
+            finish_run(&mut results, run_start, &mut on_progress);
+            continue;
+        }
+        let mut dy0 = y0.clone();
+        problem.eqn.rhs().call_inplace(&y0, t_start, &mut dy0);
+        if let Some(i) = (0..dy0.len()).find(|&i| !dy0[i].is_finite()) {
+            let (state, _) = model.blame(y0.as_slice());
+            let hint = (!model.state_parameters[i].is_empty()).then(|| format!("consider checking {}", model.state_parameters[i].join(", ")));
+            let message = format!("non-finite derivative of {} at t = {}", model.species[i], t_start);
+            results.push(SimulationResult {
+                parameters,
+                ..failed_result(SimulationError::SolverFailure { t: t_start, message, state, hint })
+            });
+            finish_run(&mut results, run_start, &mut on_progress);
+            continue;
+        }
+        let solver = match options.solver.unwrap_or(SolverMethod::Bdf) {
+            // diffsol's own consistency solve starts from the model's guess and may not
+            // converge, so the solver starts from the state found above instead
+            SolverMethod::Bdf if !model.algebraic.is_empty() => BdfState::new_without_initialise(problem)
+                .and_then(|mut state| {
+                    let common = state.as_mut();
+                    common.y.copy_from(&y0);
+                    common.dy.copy_from(&dy0);
+                    state.set_step_size(problem.h0, &problem.atol, problem.rtol, &problem.eqn, 1);
+                    problem.bdf_solver::<Mat::LS>(state)
+                })
+                .map(Integrator::Bdf),
+            SolverMethod::Bdf => problem.bdf::<Mat::LS>().map(Integrator::Bdf),
+            SolverMethod::TrBdf2 => {
+                let tableau = diffsol::Tableau::<<Mat::V as DefaultDenseMatrix>::M>::tr_bdf2(Default::default());
+                problem
+                    .rk_state(&tableau)
+                    .and_then(|state| problem.sdirk_solver::<Mat::LS, _>(state, tableau))
+                    .map(Integrator::TrBdf2)
+            }
+        };
+        let mut solver = match solver {
+            Ok(solver) => solver,
+            Err(e) => {
+                results.push(failed_result(SimulationError::Setup(format!("solver setup failed: {}", e))));
+                finish_run(&mut results, run_start, &mut on_progress);
+                continue;
+            }
+        };
+        if let Some(h) = options.initial_step {
+            *solver.state_mut().h = h;
+        }
+        let final_time = options.final_time.unwrap_or(24.0);
+
+        // Scheduled doses in time order (stable, so same-time doses keep their order)
+        let mut doses = options.doses.clone().unwrap_or_default();
+        doses.sort_by(|a, b| a.time.total_cmp(&b.time));
+        for dose in doses.iter().filter(|dose| dose.time >= final_time) {
+            console_log!(Info, "Ignoring dose to {} at t = {}: not before final_time {}", dose.target, dose.time, final_time);
+        }
+        doses.retain(|dose| dose.time < final_time);
+        let mut next_dose = 0;
+        let mut applied_doses = Vec::new();
+        let mut event_log = Vec::new();
+        // Uptake window edges are stops too, so the solver does not step over a window
+        let mut window_edges: Vec<f64> = windows.iter()
+            .flat_map(|window| [window.t0, window.t1])
+            .filter(|&edge| edge > t_start && edge < final_time)
+            .collect();
+        window_edges.dedup();
+        let mut next_edge = 0;
+        let next_stop = |next_dose: usize, next_edge: usize| {
+            doses.get(next_dose).map_or(final_time, |dose| dose.time).min(window_edges.get(next_edge).map_or(final_time, |&edge| edge))
+        };
+        if doses.first().is_some_and(|dose| dose.time <= t_start) {
+            let state = solver.state_mut();
+            apply_doses(model, &doses, &mut next_dose, t_start, state.y.as_mut_slice(), &mut applied_doses, &mut event_log);
+            if let Err(message) = consistent_state(&problem.eqn.rhs(), model.algebraic, t_start, state.y.as_mut_slice()) {
+                let message = format!("no state after the doses at t = {} satisfies the algebraic rules: {}", t_start, message);
+                results.push(SimulationResult { parameters, ..failed_result(model.solver_error(message, t_start, state.y.as_slice())) });
+                finish_run(&mut results, run_start, &mut on_progress);
+                continue;
+            }
+            problem.eqn.rhs().call_inplace(state.y, t_start, state.dy);
+        }
+        // Triggers start from their initialValue, or from where the run being
+        // resumed left them, so one that holds at t_start fires only if that is false
+        let y = solver.state().y.as_slice().to_vec();
+        let before = options.trigger_state.as_deref().filter(|state| state.len() == model.event_ids.len()).unwrap_or(model.event_initial_values);
+        let at = EventTime { t: t_start, y: &y, t_probe: t_start, y_probe: &y };
+        let fire = firing_events(model, eval_triggers, apply_events, &at, &p, &eval_triggers(&y, t_start, &p), before);
+        if fire.contains(&true) {
+            log_events(model, apply_events, &y, t_start, &fire, &p, &mut event_log);
+            let mut y_new = apply_events(&y, t_start, &fire, &p);
+            if let Err(message) = consistent_state(&problem.eqn.rhs(), model.algebraic, t_start, &mut y_new) {
+                let (state, hint) = model.blame(&y_new);
+                let message = format!("no state after the events at t = {} satisfies the algebraic rules: {}", t_start, message);
+                results.push(SimulationResult { parameters, ..failed_result(SimulationError::EventError { t: t_start, message, state, hint }) });
+                finish_run(&mut results, run_start, &mut on_progress);
+                continue;
+            }
+            let state = solver.state_mut();
+            state.y.as_mut_slice().copy_from_slice(&y_new);
+            problem.eqn.rhs().call_inplace(state.y, t_start, state.dy);
+        }
+        let mut trigger_state = eval_triggers(solver.state().y.as_slice(), solver.state().t, &p);
+        // Recorded at these times (interpolated) if given, otherwise at every internal step.
+        // The output_dt grid also keeps the states on both sides of each dose and event.
+        let grid = options.output_grid();
+        let record_stops = options.output_times.is_none();
+        let capacity = expected_output_points(&options, grid.as_deref(), t_start, final_time, doses.len() + window_edges.len() + model.event_ids.len());
+        let mut trajectory = Trajectory::with_capacity(n, capacity);
+
+        let mut next_output = 0;
+        if grid.as_ref().is_none_or(|ts| ts[0] == t_start) {
+            trajectory.push(t_start, solver.state().y.as_slice());
+            next_output = 1;
+        }
+
+        let max_output_points = options.max_output_points.unwrap_or(usize::MAX);
+        let mut pinned = Vec::with_capacity(capacity);
+        pinned.resize(trajectory.len(), true);
+        let mut output_stride = 1;
+        let mut steps_since_output = 0;
+
+        let mut error = solver.set_stop_time(next_stop(next_dose, next_edge)).err().map(|e| {
+            model.solver_error(format!("setting the stop time failed: {}", e), t_start, solver.state().y.as_slice())
+        });
+        let steady_state_tol = match options.mode {
+            Some(SimulationMode::SteadyState) => Some(options.steady_state_tol.unwrap_or(1e-6)),
+            _ => None,
+        };
+        let steady_state_include: Vec<bool> = model
+            .species
+            .iter()
+            .map(|id| !options.steady_state_exclude.iter().flatten().any(|ex| ex == id))
+            .collect();
+        let mut dydt = solver.state().y.clone();
+        let mut steady_state = None;
+        let mut stop_reached = false;
+        let max_steps = options.max_steps.unwrap_or(1_000_000);
+        let mut steps = 0;
+        let check_finite = options.check_finite.unwrap_or(true);
+        let clamp_nonnegative = options.clamp_nonnegative.unwrap_or(false);
+        let clamp_threshold = options.clamp_threshold.unwrap_or(f64::INFINITY);
+        // error is set only just before leaving the loop
+        'steps: while error.is_none() {
+            let t: f64 = solver.state().t;
+            let go_on = pause((t - t_start) / (final_time - t_start), steps).await;
+            if !go_on {
+                error = Some(SimulationError::Cancelled { t: solver.state().t });
+                break;
+            }
+            if steps >= max_steps {
+                let (state, hint) = model.blame(solver.state().y.as_slice());
+                error = Some(SimulationError::MaxStepsExceeded { t: solver.state().t, limit: max_steps, state, hint });
+                break;
+            }
+            if check_finite {
+                let (y, t) = (solver.state().y.as_slice(), solver.state().t);
+                if let Some(i) = (0..y.len()).find(|&i| !y[i].is_finite()) {
+                    error = Some(model.solver_error(format!("non-finite value of {} (y[{}]) at t = {}", model.species[i], i, t), t, y));
+                    break;
+                }
+            }
+            if clamp_nonnegative {
+                let (y, t) = (solver.state().y.as_slice(), solver.state().t);
+                if let Some(i) = (0..y.len()).find(|&i| y[i] < -clamp_threshold) {
+                    error = Some(model.solver_error(format!("negative value of {} (y[{}]) = {:e} at t = {}, beyond clamp_threshold", model.species[i], i, y[i], t), t, y));
+                    break;
+                }
+                if (0..y.len()).any(|i| y[i] < 0.0) {
+                    let state = solver.state_mut();
+                    for i in 0..state.y.len() {
+                        state.y[i] = state.y[i].max(0.0);
+                    }
+                    problem.eqn.rhs().call_inplace(state.y, *state.t, state.dy);
+                }
+            }
+            if let Some(max_step) = options.max_step {
+                if solver.state().h > max_step {
+                    *solver.state_mut().h = max_step;
+                }
+            }
+            // A root without a firing event may leave the stop time behind the solver
+            let step = if std::mem::take(&mut stop_reached) {
+                Ok(OdeSolverStopReason::TstopReached)
+            } else {
+                steps += 1;
+                solver.step()
+            };
+            let stop_reason = match step {
+                Ok(reason) => reason,
+                Err(e) => {
+                    let (y, t) = (solver.state().y, solver.state().t);
+                    let mut message = e.to_string();
+                    problem.eqn.rhs().call_inplace(y, t, &mut dydt);
+                    if let Some(i) = (0..dydt.len()).find(|&i| !dydt[i].is_finite()) {
+                        message += &format!(" (non-finite derivative of {} at t = {})", model.species[i], t);
+                    }
+                    error = Some(model.solver_error(message, t, y.as_slice()));
+                    break;
+                }
+            };
+            // Stops before final_time are scheduled doses and uptake window edges
+            let stop_due = stop_reason == OdeSolverStopReason::TstopReached && (next_dose < doses.len() || next_edge < window_edges.len());
+
+            // Record output: interpolated onto the requested grid, or every internal step
+            // Steady-state mode stops once the weighted RMS of dy/dt is below tolerance
+            let steady = match (steady_state_tol, &stop_reason) {
+                (Some(tol), OdeSolverStopReason::InternalTimestep | OdeSolverStopReason::TstopReached) => {
+                    let (y, t) = (solver.state().y, solver.state().t);
+                    problem.eqn.rhs().call_inplace(y, t, &mut dydt);
+                    let rms = steady_state_rms(y.as_slice(), dydt.as_slice(), &steady_state_include, options.atol.unwrap_or(1e-6));
+                    let reached = rms < tol && t >= options.steady_state_min_time.unwrap_or(0.0);
+                    if reached || (stop_reason == OdeSolverStopReason::TstopReached && !stop_due) {
+                        let state = y.as_slice().to_vec();
+                        steady_state = Some(SteadyState { reached, time: t, rms, state });
+                    }
+                    reached
+                }
+                _ => false,
+            };
+
+            // The solver may stop a rounding error short of the stop it was given
+            let t_reached = match stop_reason {
+                OdeSolverStopReason::RootFound(t_root) => t_root,
+                OdeSolverStopReason::TstopReached => solver.state().t.max(next_stop(next_dose, next_edge)),
+                OdeSolverStopReason::InternalTimestep => solver.state().t,
+            };
+            match grid {
+                Some(ref output_times) => {
+                    while next_output < output_times.len() && output_times[next_output] <= t_reached {
+                        // A grid point at a stop is the solver's own state, which a dose there starts from
+                        let t = output_times[next_output];
+                        let y = if t >= solver.state().t { Ok(solver.state().y.clone()) } else { solver.interpolate(t) };
+                        let y = match y {
+                            Ok(y) => y,
+                            Err(e) => {
+                                let (y, t) = (solver.state().y.as_slice(), solver.state().t);
+                                error = Some(model.solver_error(format!("interpolating at t = {} failed: {}", output_times[next_output], e), t, y));
+                                break 'steps;
+                            }
+                        };
+                        trajectory.push(output_times[next_output], y.as_slice());
+                        next_output += 1;
+                    }
+                }
+                None => {
+                    let record = match stop_reason {
+                        OdeSolverStopReason::InternalTimestep => {
+                            steps_since_output += 1;
+                            steady || steps_since_output >= output_stride
+                        }
+                        OdeSolverStopReason::TstopReached => true,
+                        OdeSolverStopReason::RootFound(_) => false,
+                    };
+                    if record {
+                        steps_since_output = 0;
+                        trajectory.push(solver.state().t, solver.state().y.as_slice());
+                        pinned.push(stop_due);
+                        if trajectory.len() >= max_output_points && stop_reason != OdeSolverStopReason::TstopReached && !steady {
+                            let keep: Vec<bool> = pinned.iter().enumerate().map(|(i, &p)| p || i % 2 == 0).collect();
+                            trajectory.retain(&keep);
+                            thin_output(&mut pinned, &keep);
+                            output_stride *= 2;
+                        }
+                    }
+                }
+            }
+
+            if steady {
+                break;
+            }
+            match stop_reason {
+                OdeSolverStopReason::InternalTimestep => {},
+                OdeSolverStopReason::TstopReached if stop_due => {
+                    // The solver may stop a rounding error short of the dose time
+                    let t_dose = solver.state().t.max(next_stop(next_dose, next_edge));
+                    let mut y_new = solver.state().y.as_slice().to_vec();
+                    let doses_before = next_dose;
+                    apply_doses(model, &doses, &mut next_dose, t_dose, &mut y_new, &mut applied_doses, &mut event_log);
+                    if let Err(message) = consistent_state(&problem.eqn.rhs(), model.algebraic, t_dose, &mut y_new) {
+                        let message = format!("no state after the dose at t = {} satisfies the algebraic rules: {}", t_dose, message);
+                        error = Some(model.solver_error(message, t_dose, &y_new));
+                        break;
+                    }
+                    next_edge += window_edges[next_edge..].iter().take_while(|&&edge| edge <= t_dose).count();
+                    if record_stops && next_dose > doses_before {
+                        if trajectory.time().last() != Some(&t_dose) {
+                            trajectory.push(t_dose, solver.state().y.as_slice());
+                        }
+                        trajectory.push(t_dose, &y_new);
+                        pinned.resize(trajectory.len(), true);
+                    }
+                    let state = solver.state_mut();
+                    state.y.as_mut_slice().copy_from_slice(&y_new);
+                    *state.t = t_dose;
+                    *state.h = 1e-6 * final_time.max(1.0);
+                    problem.eqn.rhs().call_inplace(state.y, t_dose, state.dy);
+                    if let Err(e) = solver.set_stop_time(next_stop(next_dose, next_edge)) {
+                        let (y, t) = (solver.state().y.as_slice(), solver.state().t);
+                        error = Some(model.solver_error(format!("setting the stop time failed: {}", e), t, y));
+                        break;
+                    }
+                }
+                OdeSolverStopReason::TstopReached => break,
+                OdeSolverStopReason::RootFound(t_root) => {
+                    let y = match solver.interpolate(t_root) {
+                        Ok(y) => y.as_slice().to_vec(),
+                        Err(e) => {
+                            let (state, hint) = model.blame(solver.state().y.as_slice());
+                            let message = format!("interpolating at the root t = {} failed: {}", t_root, e);
+                            error = Some(SimulationError::EventError { t: t_root, message, state, hint });
+                            break;
+                        }
+                    };
+                    // The roots have crossed at t_root, but a strict trigger such as
+                    // S1 < 0.5 may still be false there: triggers are read just past it
+                    let t_probe = (t_root + ROOT_PROBE * (t_root.abs() + solver.state().h.abs())).min(solver.state().t);
+                    let y_probe = solver.interpolate(t_probe).map_or_else(|_| y.clone(), |y| y.as_slice().to_vec());
+                    let at = EventTime { t: t_root, y: &y, t_probe, y_probe: &y_probe };
+                    let triggers = eval_triggers(&y_probe, t_probe, &p);
+                    let fire = firing_events(model, eval_triggers, apply_events, &at, &p, &triggers, &trigger_state);
+                    if fire.contains(&true) {
+                        log_events(model, apply_events, &y, t_root, &fire, &p, &mut event_log);
+                        let mut y_new = apply_events(&y, t_root, &fire, &p);
+                        // Doses due at the event time apply together with it
+                        apply_doses(model, &doses, &mut next_dose, t_root, &mut y_new, &mut applied_doses, &mut event_log);
+                        if let Err(message) = consistent_state(&problem.eqn.rhs(), model.algebraic, t_root, &mut y_new) {
+                            let (state, hint) = model.blame(&y_new);
+                            let message = format!("no state after the event satisfies the algebraic rules: {}", message);
+                            error = Some(SimulationError::EventError { t: t_root, message, state, hint });
+                            break;
+                        }
+                        // Triggers read after the events: one its own event made false is armed again
+                        trigger_state = eval_triggers(&at.carried(&y_new), t_probe, &p);
+                        next_edge += window_edges[next_edge..].iter().take_while(|&&edge| edge <= t_root).count();
+                        if record_stops {
+                            // Pre- and post-event states, kept through decimation
+                            if trajectory.time().last() != Some(&t_root) {
+                                trajectory.push(t_root, &y);
+                            }
+                            trajectory.push(t_root, &y_new);
+                            pinned.resize(trajectory.len(), true);
+                        }
+                        let state = solver.state_mut();
+                        state.y.as_mut_slice().copy_from_slice(&y_new);
+                        *state.t = t_root;
+                        *state.h = 1e-6 * final_time.max(1.0);
+                        problem.eqn.rhs().call_inplace(state.y, t_root, state.dy);
+                        // An event at final_time ends the run with its post-event state
+                        if t_root >= final_time {
+                            break;
+                        }
+                        if let Err(e) = solver.set_stop_time(next_stop(next_dose, next_edge)) {
+                            let (y, t) = (solver.state().y.as_slice(), solver.state().t);
+                            error = Some(model.solver_error(format!("setting the stop time failed: {}", e), t, y));
+                            break;
+                        }
+                    } else {
+                        trigger_state = triggers;
+                        // Nothing fired: the root state is kept like an internal step,
+                        // unless it falls on the stop, which records that time itself
+                        let stop = next_stop(next_dose, next_edge);
+                        if grid.is_none() && t_root < stop {
+                            trajectory.push(t_root, &y);
+                            pinned.push(false);
+                        }
+                        if solver.set_stop_time(stop).is_err() {
+                            stop_reached = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(SteadyState { reached: false, time, rms, .. }) = steady_state {
+            console_log!(Info, "Steady state not reached by t = {} (weighted RMS of dy/dt {})", time, rms);
+        }
+
+        if clamp_nonnegative {
+            trajectory.clamp_nonnegative();
+        }
+
+        let diagnostics = options.mass_balance.unwrap_or(false).then(|| {
+            let total_mass = (0..trajectory.len()).map(|i| eval_mass(trajectory.state(i), &p)).collect();
+            let dose_mass: Vec<(f64, f64)> = applied_doses.iter().map(|dose| {
+                let mut y = vec![0.0; n];
+                y[model.species_index(&dose.target).unwrap()] = dose.amount;
+                (dose.time, eval_mass(&y, &p))
+            }).collect();
+            mass_diagnostics(trajectory.time(), total_mass, &dose_mass)
+        });
+
+        let mut observables = OrderedSeries::default();
+        if options.include_observables.unwrap_or(false) {
+            let mut values_by_name: Vec<Vec<f64>> = vec![Vec::with_capacity(trajectory.len()); model.observables.len()];
+            for (i, &t) in trajectory.time().iter().enumerate() {
+                let values = eval_observables(trajectory.state(i), t, &p);
+                for (k, value) in values.into_iter().enumerate() {
+                    values_by_name[k].push(value);
+                }
+            }
+            for (name, values) in model.observables.iter().zip(values_by_name) {
+                observables.insert(name.to_string(), values);
+            }
+        }
+
+        let volumes: Vec<Vec<f64>> = match options.output_kind {
+            Some(_) => (0..trajectory.len()).map(|i| eval_volumes(trajectory.state(i), trajectory.time()[i], &p)).collect(),
+            None => vec![],
+        };
+
+        let (time, species_map) = trajectory.into_series(model.species);
+
+        let (amounts, concentrations, notes) = match options.output_kind {
+            Some(kind) => model.species_representations(&species_map, &volumes, kind),
+            None => Default::default(),
+        };
+
+        let rhs_stats = problem.eqn.rhs().statistics();
+        let bdf_stats = match &solver {
+            Integrator::Bdf(s) => s.get_statistics().clone(),
+            Integrator::TrBdf2(s) => s.get_statistics().clone(),
+        };
+        let stats = SolverStats {
+            steps,
+            rhs_evals: rhs_stats.number_of_calls - rhs_before.number_of_calls,
+            jacobian_evals: rhs_stats.number_of_matrix_evals - rhs_before.number_of_matrix_evals,
+            newton_iterations: bdf_stats.number_of_nonlinear_solver_iterations,
+            newton_failures: bdf_stats.number_of_nonlinear_solver_fails,
+            final_step_size: solver.state().h,
+        };
+        console_log!(Debug, "Run stopped at t = {} after {} steps, {} RHS evaluations", solver.state().t, steps, stats.rhs_evals);
+        let (y, t_end, h_end): (&[f64], f64, f64) = (solver.state().y.as_slice(), solver.state().t, solver.state().h);
+        checkpoint = (0..y.len()).all(|i| y[i].is_finite()).then(|| {
+            let mut pending_doses = options.doses.clone().unwrap_or_default();
+            pending_doses.sort_by(|a, b| a.time.total_cmp(&b.time));
+            pending_doses.drain(..applied_doses.len());
+            for dose in &mut pending_doses {
+                dose.time /= scale;
+            }
+            Checkpoint {
+                version: CHECKPOINT_VERSION,
+                species: model.species.iter().map(|id| id.to_string()).collect(),
+                time: t_end / scale,
+                state: y.to_vec(),
+                step_size: h_end / scale,
+                pending_doses,
+                trigger_state: trigger_state.clone(),
+                parameters: resolved,
+            }
+        });
+
+        let units = model.series_units(&species_map, &observables, &options);
+        results.push(SimulationResult {
+            time,
+            species: species_map,
+            parameters,
+            observables,
+            units,
+            amounts,
+            concentrations,
+            notes,
+            steady_state,
+            applied_doses,
+            events: event_log,
+            trigger_state,
+            steps,
+            stats: Some(stats),
+            diagnostics,
+            error: error.map(|error| error.payload()),
+            timing: Timing::default(),
+        });
+        scale_result_times(results.last_mut().unwrap(), output_scale);
+        finish_run(&mut results, run_start, &mut on_progress);
+    }
+    LAST_RUN_STATS.with(|last| *last.borrow_mut() = results.last().and_then(|result| result.stats.clone()));
+    LAST_CHECKPOINT.with(|last| *last.borrow_mut() = checkpoint);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_capacity_follows_the_options() {
+        let mut options = SimulationOptions::default();
+        assert_eq!(expected_output_points(&options, None, 0.0, 24.0, 0), TYPICAL_STEPS + 1);
+        assert_eq!(expected_output_points(&options, None, 0.0, 24.0, 2), TYPICAL_STEPS + 2 * STEPS_PER_STOP + 3);
+        options.max_step = Some(0.01);
+        assert_eq!(expected_output_points(&options, None, 0.0, 24.0, 0), 2401);
+        options.max_step = Some(1e-9);
+        assert_eq!(expected_output_points(&options, None, 0.0, 24.0, 0), MAX_RESERVED_POINTS);
+        options.max_output_points = Some(100);
+        assert_eq!(expected_output_points(&options, None, 0.0, 24.0, 2), 102);
+        options.max_output_points = Some(usize::MAX);
+        assert_eq!(expected_output_points(&options, None, 0.0, 24.0, 2), MAX_RESERVED_POINTS);
+        options.max_output_points = Some(100);
+        options.output_dt = Some(1.0);
+        let grid = options.output_grid().unwrap();
+        assert_eq!(expected_output_points(&options, Some(&grid), 0.0, 24.0, 2), 29);
+        // A long output_dt grid reserves no more than a guessed step count would
+        options.output_dt = Some(1e-5);
+        let grid = options.output_grid().unwrap();
+        assert_eq!(expected_output_points(&options, Some(&grid), 0.0, 24.0, 2), MAX_RESERVED_POINTS);
+        options.output_dt = None;
+        options.output_times = Some(vec![0.0, 12.0, 24.0]);
+        let grid = options.output_grid().unwrap();
+        assert_eq!(expected_output_points(&options, Some(&grid), 0.0, 24.0, 2), 3);
+    }
+
+    #[test]
+    fn dense_solve_pivots() {
+        // The first pivot is zero without row exchanges
+        let x = solve_dense(vec![0.0, 2.0, 1.0, 0.0, 3.0, 1.0, 1.0, 1.0, 1.0], vec![7.0, 9.0, 6.0], 3).unwrap();
+        for (x, expected) in x.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((x - expected).abs() < 1e-12, "{:?}", x);
+        }
+        assert!(solve_dense(vec![1.0, 2.0, 2.0, 4.0], vec![1.0, 2.0], 2).is_none());
+    }
+
+    #[test]
+    fn events_fire_on_rising_triggers_only() {
+        // "reset" (persistent) sets A to 0, "high" (not persistent) fires while A > 1
+        let model = ModelInfo {
+            species: &["A"],
+            state_parameters: &[&[]],
+            event_ids: &["reset", "high"],
+            event_targets: &[&[0], &[0]],
+            event_initial_values: &[true, false],
+            event_persistent: &[true, false],
+            observables: &[],
+            species_is_amount: &[true],
+            species_volumes: &[Ok("c")],
+            series_units: &[("A", None)],
+            time_seconds: None,
+            time_unit: None,
+            parameters: &[],
+            parameter_checks: &[],
+            algebraic: &[],
+        };
+        let triggers: TriggerFn = |y: &[f64], t: f64, _p: &[f64]| vec![t >= 1.0, y[0] > 1.0];
+        let apply: EventFn = |y: &[f64], _t: f64, fire: &[bool], _p: &[f64]| vec![if fire[0] { 0.0 } else if fire[1] { y[0] + 1.0 } else { y[0] }];
+        let fire = |y: f64, t: f64, before: &[bool]| {
+            let at = EventTime { t, y: &[y], t_probe: t, y_probe: &[y] };
+            firing_events(&model, triggers, apply, &at, &[], &triggers(&[y], t, &[]), before)
+        };
+        // At the start, a trigger that holds fires only if its initial value is false
+        assert_eq!(fire(2.0, 1.0, model.event_initial_values), [false, true]);
+        assert_eq!(fire(0.0, 1.0, model.event_initial_values), [false, false]);
+        // A trigger that turned false and true again fires again
+        assert_eq!(fire(2.0, 0.5, &[false, false]), [false, true]);
+        assert_eq!(fire(2.0, 0.5, &[false, true]), [false, false]);
+        // "reset" firing with "high" makes A 0, which cancels "high" but not the reverse
+        assert_eq!(fire(2.0, 1.0, &[false, false]), [true, false]);
+        // Past the root, a state set by an event keeps the trajectory's change
+        let at = EventTime { t: 1.0, y: &[0.5, 2.0], t_probe: 1.0 + 1e-9, y_probe: &[0.5 - 1e-10, 2.0] };
+        assert_eq!(at.carried(&[1.0, 2.0]), [1.0 - 1e-10, 2.0]);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::series::OrderedSeries;
+use crate::solver::{clamp_output, thin_output};
+
+/// Output points of a run: their times and, in one row-major buffer, the state
+/// at each of them (`len() × n_states`). The per-species series of a result are
+/// only split out at the end, by [`Trajectory::into_series`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trajectory {
+    n_states: usize,
+    time: Vec<f64>,
+    states: Vec<f64>,
+}
+
+impl Trajectory {
+    pub fn new(n_states: usize) -> Self {
+        Trajectory { n_states, time: Vec::new(), states: Vec::new() }
+    }
+
+    /// Room for `points` output points before the buffers grow
+    pub fn with_capacity(n_states: usize, points: usize) -> Self {
+        Trajectory { n_states, time: Vec::with_capacity(points), states: Vec::with_capacity(points * n_states) }
+    }
+
+    /// Append the state `y` at time `t`
+    pub fn push(&mut self, t: f64, y: &[f64]) {
+        self.time.push(t);
+        self.states.extend_from_slice(&y[..self.n_states]);
+    }
+
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+
+    pub fn n_states(&self) -> usize {
+        self.n_states
+    }
+
+    pub fn time(&self) -> &[f64] {
+        &self.time
+    }
+
+    /// All states, row after row
+    pub fn states(&self) -> &[f64] {
+        &self.states
+    }
+
+    /// The state at output point `i`, in state order
+    pub fn state(&self, i: usize) -> &[f64] {
+        &self.states[i * self.n_states..(i + 1) * self.n_states]
+    }
+
+    /// Keep the output points whose `keep` flag is set
+    pub fn retain(&mut self, keep: &[bool]) {
+        let n = self.n_states;
+        let mut kept = 0;
+        for (i, _) in keep.iter().enumerate().filter(|(_, &keep)| keep) {
+            self.states.copy_within(i * n..(i + 1) * n, kept * n);
+            kept += 1;
+        }
+        self.states.truncate(kept * n);
+        thin_output(&mut self.time, keep);
+    }
+
+    /// Zero every negative value (clamp_nonnegative)
+    pub fn clamp_nonnegative(&mut self) {
+        clamp_output(&mut self.states);
+    }
+
+    /// Split into the time axis and one series per state, named by `names`
+    pub fn into_series(self, names: &[&str]) -> (Vec<f64>, OrderedSeries) {
+        let mut columns: Vec<Vec<f64>> = (0..self.n_states).map(|_| Vec::with_capacity(self.len())).collect();
+        // Row by row, so the buffer is read in order
+        for row in self.states.chunks_exact(self.n_states.max(1)) {
+            for (column, &value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+        let mut series = OrderedSeries::default();
+        for (name, values) in names.iter().zip(columns) {
+            series.insert(name.to_string(), values);
+        }
+        (self.time, series)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_are_split_into_named_series() {
+        let mut trajectory = Trajectory::new(2);
+        for (t, y) in [(0.0, [1.0, -2.0]), (0.5, [3.0, 4.0]), (1.0, [5.0, -6.0]), (2.0, [7.0, 8.0])] {
+            trajectory.push(t, &y);
+        }
+        assert_eq!(trajectory.state(1), &[3.0, 4.0]);
+        trajectory.retain(&[true, false, true, true]);
+        trajectory.clamp_nonnegative();
+        assert_eq!(trajectory.states(), &[1.0, 0.0, 5.0, 0.0, 7.0, 8.0]);
+        let (time, series) = trajectory.into_series(&["A", "B"]);
+        assert_eq!(time, vec![0.0, 1.0, 2.0]);
+        assert_eq!(series.get("A"), Some(&vec![1.0, 5.0, 7.0]));
+        assert_eq!(series.get("B"), Some(&vec![0.0, 0.0, 8.0]));
+    }
+}
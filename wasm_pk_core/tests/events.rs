@@ -0,0 +1,47 @@
+//! Doses and output grids of generated models where the solver stops short
+//!
+//! A dose is applied at its stop even when the solver halts a rounding error
+//! short of it, and an output grid then still ends at final_time. Event
+//! semantics are tested on the semantic cases' models, in the semantic_cases
+//! crate of the repository.
+
+#![cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+
+use serde_json::{json, Value};
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/event_dose.rs"]
+mod event_dose;
+
+fn run(run_simulation: fn(&str) -> String, params: Value) -> Value {
+    let result: Value = serde_json::from_str(&run_simulation(&params.to_string())).unwrap();
+    assert!(result["error"].is_null(), "{} with {}", result["error"], params);
+    result
+}
+
+#[test]
+fn doses_are_applied_when_the_solver_stops_just_short() {
+    // TR-BDF2 stops one ulp before the first dose here
+    let params = json!({
+        "t_start": 4.446611823709614, "final_time": 95.57180339879577, "solver": "tr_bdf2", "rtol": 2.6504781958019493e-8, "atol": 2.5234447675176464e-7,
+        "ka": 2.830894500949651, "ke": 1.7860514237759426, "Dose": 93.50945013825874, "Vd": 2.9303681836260362,
+        "doses": [
+            { "time": 55.51100977469478, "amount": 9.098030924973335, "target": "Gut" },
+            { "time": 61.98385793282798, "amount": 4.73205313403252, "target": "Gut" },
+            { "time": 94.07597340790485, "amount": 7.843811699880133, "target": "Gut" },
+        ],
+    });
+    let result = run(event_dose::run_simulation, params);
+    let doses: Vec<f64> = result["events"].as_array().unwrap().iter().map(|event| event["time"].as_f64().unwrap()).collect();
+    assert_eq!(doses, [55.51100977469478, 61.98385793282798, 94.07597340790485], "{}", result["events"]);
+}
+
+#[test]
+fn output_grids_end_at_final_time_when_the_solver_stops_just_short() {
+    // TR-BDF2 stops one ulp before final_time here
+    let params = json!({ "t_start": 2.6302, "final_time": 29.3646, "output_dt": 0.05, "solver": "tr_bdf2", "rtol": 1e-4, "atol": 1e-5 });
+    let result = run(event_dose::run_simulation, params);
+    let time: Vec<f64> = serde_json::from_value(result["time"].clone()).unwrap();
+    assert_eq!(time.last(), Some(&29.3646), "time ends {:?}", &time[time.len() - 2..]);
+    assert_eq!(time.len(), result["species"]["Cp"].as_array().unwrap().len());
+}
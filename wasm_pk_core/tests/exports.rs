@@ -0,0 +1,892 @@
+//! Entry points and run options shared by every generated model
+//!
+//! Parameter parsing, PK metrics, the CSV, binary, sweep, batch,
+//! continuation and checkpoint exports and the run options (doses,
+//! steady-state mode, time units, t_start, step limits, solvers, output
+//! kinds) behave the same whatever the model, so they are checked here for
+//! each fixture, along with the schema of each model's metadata exports and
+//! its Jacobian; generated files carry no tests of their own. The models
+//! in Notebooks/output are checked where they are. To cover another
+//! generated model, add it to tests/models and an `exports_test!` line with
+//! its model type.
+
+#![cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+
+use serde_json::{json, Value};
+use wasm_pk_core::{from_json, PkMetrics, PkModel};
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/two_compartment.rs"]
+mod two_compartment;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/event_dose.rs"]
+mod event_dose;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/infusion.rs"]
+mod infusion;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/oral_window.rs"]
+mod oral_window;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/transit_chain.rs"]
+mod transit_chain;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/protein_binding.rs"]
+mod protein_binding;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../../Notebooks/output/euromix_model.rs"]
+mod euromix_model;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../../Notebooks/output/PBPK_BPA_model.rs"]
+mod pbpk_bpa_model;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "../../Notebooks/output/talinolol_model.rs"]
+mod talinolol_model;
+
+use two_compartment::{compute_partial_auc, compute_pk_metrics, TwoCompartmentModel};
+
+/// Top-level fields of one interface in a model's get_types_ts
+fn ts_fields(ts: &str, name: &str) -> Vec<String> {
+    let start = ts.find(&format!("export interface {} {{", name)).unwrap();
+    let mut fields: Vec<String> = ts[start..]
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.starts_with('}'))
+        .filter_map(|line| line.strip_prefix("  ").filter(|field| field.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')))
+        .map(|field| field.split(['?', ':']).next().unwrap().to_string())
+        .collect();
+    fields.sort();
+    fields
+}
+
+fn json_keys(value: &Value) -> Vec<String> {
+    let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn unknown_keys_are_rejected() {
+    let error = TwoCompartmentModel::parse_params(r#"{"final_tmie": 2.0}"#).err().unwrap();
+    assert_eq!(error.to_string(), "unknown parameter final_tmie (did you mean final_time?)");
+    let error = TwoCompartmentModel::parse_params(r#"{"zzzzzzzz": 1.0, "rtl": 1e-6}"#).err().unwrap();
+    assert_eq!(error.to_string(), "unknown parameters rtl (did you mean rtol?), zzzzzzzz");
+}
+
+#[test]
+fn numeric_strings_are_accepted() {
+    let sim_params = TwoCompartmentModel::parse_params(r#"{"final_time": " 2.5e1", "rtol": "1E-6", "max_steps": 100}"#).unwrap();
+    assert_eq!(sim_params.options.final_time, Some(25.0));
+    assert_eq!(sim_params.options.rtol, Some(1e-6));
+    let error = TwoCompartmentModel::parse_params(r#"{"final_time": "1,5"}"#).err().unwrap();
+    assert_eq!(error.to_string(), "final_time: expected a number, got \"1,5\" (use '.' as the decimal separator)");
+    let error = TwoCompartmentModel::parse_params(r#"{"atol": "NaN"}"#).err().unwrap();
+    assert_eq!(error.to_string(), "atol: expected a number, got \"NaN\"");
+}
+
+#[test]
+fn annotated_parameter_files_parse() {
+    let text = "\u{feff}{\n  // hours\n  \"final_time\": 2.5, /* short run */\n  \"rtol\": 1e-6,\n}\n";
+    assert_eq!(TwoCompartmentModel::parse_params(text).unwrap().options.final_time, Some(2.5));
+    let value: Value = from_json(r#"{"units": "mg // kg, /* */", "x": [1, 2,],}"#).unwrap();
+    assert_eq!(value, json!({"units": "mg // kg, /* */", "x": [1, 2]}));
+    // Comments are blanked, not removed, so error positions match the file
+    let error = TwoCompartmentModel::parse_params("{\n  /* a\n  b */\n  \"final_time\": }").err().unwrap();
+    assert!(error.to_string().contains("line 4"), "{}", error);
+}
+
+#[test]
+fn sensitivities_match_finite_differences() {
+    use two_compartment::{get_default_parameters, run_simulation, run_simulation_with_sensitivities, SensitivityResult, SENSITIVITY_PARAMETERS};
+    use wasm_pk_core::SimulationResult;
+
+    let final_states = |overrides: serde_json::Map<String, Value>| -> Vec<f64> {
+        let mut params = overrides;
+        params.insert("final_time".to_string(), json!(1.0));
+        params.insert("rtol".to_string(), json!(1e-10));
+        params.insert("atol".to_string(), json!(1e-12));
+        let json = Value::Object(params).to_string();
+        let result: SimulationResult = serde_json::from_str(&run_simulation(&json)).unwrap();
+        result.species.0.iter().map(|(_, v)| *v.last().unwrap()).collect()
+    };
+    let wrt: Vec<String> = SENSITIVITY_PARAMETERS.iter().map(|name| name.to_string()).collect();
+    let params = r#"{"final_time": 1.0, "rtol": 1e-10, "atol": 1e-12}"#;
+    let result: SensitivityResult = serde_json::from_str(&run_simulation_with_sensitivities(params, wrt).unwrap()).unwrap();
+    assert!(result.error.is_none());
+    let defaults: Value = serde_json::from_str(&get_default_parameters()).unwrap();
+    for entry in &result.sensitivities {
+        let value = defaults[&entry.parameter].as_f64().unwrap();
+        let h = 1e-4 * value.abs().max(1e-2);
+        let perturbed = |delta: f64| {
+            let mut overrides = serde_json::Map::new();
+            overrides.insert(entry.parameter.clone(), json!(value + delta));
+            final_states(overrides)
+        };
+        let (up, down) = (perturbed(h), perturbed(-h));
+        for (i, (name, series)) in entry.species.0.iter().enumerate() {
+            let forward = *series.last().unwrap();
+            let fd = (up[i] - down[i]) / (2.0 * h);
+            assert!(
+                (forward - fd).abs() <= 1e-4 * (1.0 + forward.abs().max(fd.abs())),
+                "d{}/d{}: forward {} vs finite difference {}",
+                name, entry.parameter, forward, fd
+            );
+        }
+    }
+}
+
+#[test]
+fn pk_metrics_match_analytic_values() {
+    // C(t) = 10 exp(-0.5 t) on a non-uniform grid
+    let time: Vec<f64> = (0..=400).map(|i| 40.0 * (i as f64 / 400.0).powi(2)).collect();
+    let decay: Vec<f64> = time.iter().map(|t| 10.0 * (-0.5 * t).exp()).collect();
+    let rising: Vec<f64> = time.iter().map(|t| 1.0 - (-0.5 * t).exp()).collect();
+    let result = json!({"time": time, "species": {"decay": decay, "rising": rising}, "success": true}).to_string();
+    let metrics: PkMetrics = serde_json::from_str(&compute_pk_metrics(&result, "decay").unwrap()).unwrap();
+    assert_eq!(metrics.cmax, 10.0);
+    assert_eq!(metrics.tmax, 0.0);
+    assert!((metrics.half_life.unwrap() - std::f64::consts::LN_2 / 0.5).abs() < 1e-9);
+    assert!((metrics.auc_inf.unwrap() - 20.0).abs() < 1e-2);
+    assert!((metrics.auc - 20.0).abs() < 1e-2);
+
+    let metrics: PkMetrics = serde_json::from_str(&compute_pk_metrics(&result, "rising").unwrap()).unwrap();
+    assert!(metrics.half_life.is_none());
+    assert!(metrics.auc_inf.is_none());
+    assert!(compute_pk_metrics(&result, "missing").unwrap_err().contains("missing"));
+
+    // Two peaks: Tmax is the higher one, the trough comes after it
+    let peaks = json!({"time": [0.0, 1.0, 2.0, 3.0, 4.0], "species": {"C": [0.0, 2.0, 1.0, 3.0, 0.5]}}).to_string();
+    let peaks: PkMetrics = serde_json::from_str(&compute_pk_metrics(&peaks, "C").unwrap()).unwrap();
+    assert_eq!((peaks.cmax, peaks.tmax, peaks.ctrough), (3.0, 3.0, 0.5));
+    assert_eq!(peaks.auc, 6.25);
+}
+
+#[test]
+fn partial_auc_matches_analytic_integral() {
+    // One-compartment elimination: AUC(t1, t2) = 20 (exp(-t1/2) - exp(-t2/2))
+    let time: Vec<f64> = (0..=2000).map(|i| 24.0 * i as f64 / 2000.0).collect();
+    let decay: Vec<f64> = time.iter().map(|t| 10.0 * (-0.5 * t).exp()).collect();
+    let result = json!({"time": time, "species": {"decay": decay}}).to_string();
+    let exact = |t1: f64, t2: f64| 20.0 * ((-0.5 * t1).exp() - (-0.5 * t2).exp());
+    let windows = r#"[[0, 12], [12, 24], [1.00123, 3.0071], [2, 5], [3.3, 3.3]]"#;
+    let aucs: Vec<f64> = serde_json::from_str(&compute_partial_auc(&result, "decay", windows).unwrap()).unwrap();
+    let expected = [exact(0.0, 12.0), exact(12.0, 24.0), exact(1.00123, 3.0071), exact(2.0, 5.0), 0.0];
+    for (auc, exact) in aucs.iter().zip(expected) {
+        assert!((auc - exact).abs() < 1e-4, "{} vs {}", auc, exact);
+    }
+    assert!((aucs[0] + aucs[1] - exact(0.0, 24.0)).abs() < 1e-4);
+    assert!(compute_partial_auc(&result, "decay", "[[20, 25]]").unwrap_err().contains("outside"));
+    assert!(compute_partial_auc(&result, "decay", "[[-1, 2]]").unwrap_err().contains("outside"));
+    assert!(compute_partial_auc(&result, "decay", "[[5, 2]]").unwrap_err().contains("before"));
+}
+
+macro_rules! exports_test {
+    ($name:ident, $model:ident, $model_type:ident) => {
+        mod $name {
+            use super::$model::*;
+            use super::{json_keys, ts_fields};
+            use serde_json::{json, Value};
+            use wasm_pk_core::{Checkpoint, ModelInfo, ParameterReport, PkModel, SimulationResult, SolverStats, TimeUnit, CHECKPOINT_VERSION};
+
+            const INFO: ModelInfo = <$model_type as PkModel>::INFO;
+
+            fn run(params: &str) -> SimulationResult {
+                serde_json::from_str(&run_simulation(params)).unwrap()
+            }
+
+            fn run_json(params: Value) -> SimulationResult {
+                run(&params.to_string())
+            }
+
+            /// Tolerance on a restarted state: algebraic states are solved
+            /// again at the start, to within the solver's tolerance
+            fn restart_tolerance(id: &str, end: f64) -> f64 {
+                if INFO.algebraic.iter().any(|&i| INFO.species[i] == id) {
+                    1e-6 * end.abs().max(1.0)
+                } else {
+                    0.0
+                }
+            }
+
+            #[test]
+            fn default_parameters_round_trip() {
+                let defaults: Value = serde_json::from_str(&get_default_parameters()).unwrap();
+                let sim_params: SimulationParams = serde_json::from_value(defaults.clone()).unwrap();
+                let order: Vec<String> = serde_json::from_str(&get_parameter_order()).unwrap();
+                assert_eq!($model_type::parameter_vector(&sim_params).len(), order.len());
+                let round_trip = serde_json::to_value(&sim_params).unwrap();
+                for (key, value) in defaults.as_object().unwrap() {
+                    assert_eq!(&round_trip[key], value, "default for {} does not round-trip", key);
+                }
+
+                let info: Value = serde_json::from_str(&get_parameters_info()).unwrap();
+                for param in info.as_array().unwrap() {
+                    let id = param["id"].as_str().unwrap();
+                    assert_eq!(&defaults[id], &param["default_value"], "{} drifted", id);
+                }
+            }
+
+            #[test]
+            fn model_metadata_reports_provenance() {
+                let metadata: Value = serde_json::from_str(&get_model_metadata()).unwrap();
+                assert_eq!(metadata["num_species"], INFO.species.len());
+                assert_eq!(metadata["generator"]["name"], "sbml_rust_generator");
+                assert!(metadata["diffsol_version"].is_string());
+                let sha256 = &metadata["sbml"]["sha256"];
+                assert!(sha256.is_null() || sha256.as_str().unwrap().len() == 64);
+                let features = metadata["features"].as_array().unwrap();
+                assert_eq!(features.contains(&"events".into()), !INFO.event_ids.is_empty());
+            }
+
+            #[test]
+            fn jacobian_matches_finite_differences() {
+                // xorshift64, so every run checks the same points
+                let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+                let mut uniform = move || {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    (seed >> 11) as f64 / (1u64 << 53) as f64
+                };
+                let norm = |x: &[f64]| x.iter().fold(0.0_f64, |m, x| m.max(x.abs()));
+                let defaults = $model_type::parameter_vector(&$model_type::default_params());
+                let n = $model_type::N_STATES;
+                for _ in 0..8 {
+                    let p: Vec<f64> = defaults.iter().map(|value| value * (0.5 + uniform())).collect();
+                    let mut y = vec![0.0; n];
+                    $model_type::init(&p, 0.0, &mut y);
+                    // Positive states on the scale of the initial ones, away from zero
+                    let scale = norm(&y).max(1.0);
+                    y.iter_mut().for_each(|y| *y = scale * (0.1 + 0.9 * uniform()));
+                    let t = 24.0 * uniform();
+                    let v: Vec<f64> = (0..n).map(|_| 2.0 * uniform() - 1.0).collect();
+                    let mut jv = vec![0.0; n];
+                    $model_type::jac(&y, &p, t, &v, &mut jv);
+                    let h = f64::EPSILON.cbrt() * norm(&y) / norm(&v).max(1e-300);
+                    let rhs_at = |sign: f64| {
+                        let shifted: Vec<f64> = y.iter().zip(&v).map(|(y, v)| y + sign * h * v).collect();
+                        let mut dy = vec![0.0; n];
+                        $model_type::rhs(&shifted, &p, t, &[], &mut dy);
+                        dy
+                    };
+                    let (up, down) = (rhs_at(1.0), rhs_at(-1.0));
+                    let fd: Vec<f64> = up.iter().zip(&down).map(|(u, d)| (u - d) / (2.0 * h)).collect();
+                    let error = jv.iter().zip(&fd).fold(0.0_f64, |m, (a, b)| m.max((a - b).abs()));
+                    let relative = error / norm(&jv).max(norm(&fd)).max(1e-12);
+                    assert!(relative < 1e-6, "J·v off by {:e} (relative) at t = {}: {:?} vs {:?}", relative, t, jv, fd);
+                }
+            }
+
+            #[test]
+            fn embedded_sbml_matches_metadata_hash() {
+                use sha2::{Digest, Sha256};
+
+                let metadata: Value = serde_json::from_str(&get_model_metadata()).unwrap();
+                let embedded = metadata["features"].as_array().unwrap().contains(&"embedded_sbml".into());
+                match get_model_sbml() {
+                    Ok(sbml) => {
+                        assert!(embedded);
+                        assert_eq!(metadata["sbml"]["sha256"], format!("{:x}", Sha256::digest(sbml.as_bytes())));
+                    }
+                    Err(message) => assert!(!embedded && !message.is_empty()),
+                }
+            }
+
+            #[test]
+            fn types_ts_match_json() {
+                let ts = get_types_ts();
+                let params = serde_json::to_value($model_type::parse_params("{}").unwrap()).unwrap();
+                assert_eq!(ts_fields(&ts, "SimulationParams"), json_keys(&params));
+                let metadata: Value = serde_json::from_str(&get_model_metadata()).unwrap();
+                assert_eq!(ts_fields(&ts, "ModelMetadata"), json_keys(&metadata));
+                for (name, json) in [("ParameterInfo", get_parameters_info()), ("SpeciesInfo", get_species_info()), ("CompartmentInfo", get_compartments_info())] {
+                    let entries: Value = serde_json::from_str(&json).unwrap();
+                    for entry in entries.as_array().unwrap() {
+                        assert_eq!(ts_fields(&ts, name), json_keys(entry), "{}", name);
+                    }
+                }
+                let equations: Value = serde_json::from_str(&get_model_equations()).unwrap();
+                assert_eq!(ts_fields(&ts, "ModelEquations"), json_keys(&equations));
+                for id in INFO.species {
+                    assert!(ts.contains(&format!("\"{}\"", id)), "{} missing from SpeciesId", id);
+                }
+            }
+
+            #[test]
+            fn parameters_info_schema() {
+                let order: Vec<String> = serde_json::from_str(&get_parameter_order()).unwrap();
+                let info: Value = serde_json::from_str(&get_parameters_info()).unwrap();
+                for entry in info.as_array().unwrap() {
+                    let keys: Vec<&str> = entry.as_object().unwrap().keys().map(|key| key.as_str()).collect();
+                    assert_eq!(keys, ["default_value", "description", "id", "is_compartment", "max", "min", "name", "required", "units"]);
+                    assert!(order.iter().any(|id| entry["id"] == id.as_str()));
+                    assert!(entry["default_value"].is_number());
+                    assert!(entry["required"].is_boolean() && entry["is_compartment"].is_boolean());
+                    for key in ["name", "units", "description"] {
+                        assert!(entry[key].is_string() || entry[key].is_null(), "{}: {}", key, entry);
+                    }
+                    for key in ["min", "max"] {
+                        assert!(entry[key].is_number() || entry[key].is_null(), "{}: {}", key, entry);
+                    }
+                    if let (Some(min), Some(max)) = (entry["min"].as_f64(), entry["max"].as_f64()) {
+                        assert!(min <= max, "{}", entry);
+                    }
+                }
+            }
+
+            #[test]
+            fn species_info_schema() {
+                let info: Value = serde_json::from_str(&get_species_info()).unwrap();
+                assert_eq!(info.as_array().unwrap().len(), INFO.species.len());
+                for entry in info.as_array().unwrap() {
+                    let keys: Vec<&str> = entry.as_object().unwrap().keys().map(|key| key.as_str()).collect();
+                    assert_eq!(keys, ["compartment", "compartment_volume", "id", "initial_amount", "is_amount", "is_boundary", "override", "units"]);
+                    assert!(entry["is_amount"].is_boolean() && entry["is_boundary"].is_boolean());
+                    assert!(entry["compartment"].is_string() || entry["compartment"].is_null());
+                    if !entry["compartment_volume"].is_null() {
+                        assert!(entry["compartment_volume"].is_number() && entry["compartment"].is_string(), "{}", entry);
+                    }
+                }
+            }
+
+            #[test]
+            fn compartments_match_species_info() {
+                let species: Value = serde_json::from_str(&get_species_info()).unwrap();
+                let compartments: Value = serde_json::from_str(&get_compartments_info()).unwrap();
+                let mut listed = 0;
+                for compartment in compartments.as_array().unwrap() {
+                    for id in compartment["species"].as_array().unwrap() {
+                        let entry = species.as_array().unwrap().iter().find(|entry| &entry["id"] == id).unwrap();
+                        assert_eq!(entry["compartment"], compartment["id"]);
+                        assert_eq!(entry["compartment_volume"], compartment["size"]);
+                        listed += 1;
+                    }
+                }
+                let placed = species.as_array().unwrap().iter().filter(|entry| !entry["compartment"].is_null()).count();
+                assert_eq!(listed, placed);
+            }
+
+            #[test]
+            fn model_equations_list_every_state() {
+                let equations: Value = serde_json::from_str(&get_model_equations()).unwrap();
+                let derivatives = equations["derivatives"].as_object().unwrap();
+                // A state fixed by an algebraic rule is listed under algebraic_rules instead
+                let none = serde_json::Map::new();
+                let algebraic = equations.get("algebraic_rules").map_or(&none, |rules| rules.as_object().unwrap());
+                assert_eq!(derivatives.len() + algebraic.len(), INFO.species.len());
+                for id in INFO.species {
+                    let equation = derivatives.get(*id).or_else(|| algebraic.get(*id)).unwrap();
+                    assert!(!equation.as_str().unwrap().is_empty(), "{}", id);
+                }
+                assert!(equations["assignment_rules"].is_object());
+            }
+
+            #[test]
+            fn species_keys_follow_sbml_order() {
+                let result = run(r#"{"final_time": 0.001}"#);
+                let keys: Vec<&str> = result.species.0.iter().map(|(name, _)| name.as_str()).collect();
+                assert_eq!(keys, INFO.species);
+
+                // Result keys are the SBML ids reported by get_species_info
+                let info: Value = serde_json::from_str(&get_species_info()).unwrap();
+                for species in info.as_array().unwrap() {
+                    let id = species["id"].as_str().unwrap();
+                    assert!(result.species.get(id).is_some(), "missing result key {}", id);
+                }
+            }
+
+            #[test]
+            fn events_do_not_end_the_run() {
+                if INFO.event_ids.is_empty() {
+                    return;
+                }
+                let result = run("{}");
+                assert!(result.error.is_none());
+                let final_time = result.parameters["final_time"].as_f64().unwrap();
+                assert_eq!(result.time.last(), Some(&final_time));
+                assert!(result.time.windows(2).all(|w| w[0] <= w[1]));
+                // Every logged event is on the time grid
+                for event in &result.events {
+                    assert!(result.time.contains(&event.time), "{} at {}", event.id, event.time);
+                }
+            }
+
+            #[test]
+            fn csv_has_one_row_per_time_point() {
+                let params = r#"{"final_time": 0.5}"#;
+                let result = run(params);
+                let csv = run_simulation_csv(params).unwrap();
+                let rows: Vec<&str> = csv.lines().collect();
+                let header: Vec<&str> = std::iter::once("time").chain(result.species.0.iter().map(|(id, _)| id.as_str())).collect();
+                assert_eq!(rows[0], header.join(","));
+                assert_eq!(rows.len(), result.time.len() + 1);
+                let last: Vec<f64> = rows[rows.len() - 1].split(',').map(|v| v.parse().unwrap()).collect();
+                assert_eq!(last[0], 0.5);
+            }
+
+            #[test]
+            fn binary_round_trip_matches_json() {
+                let params = r#"{"final_time": 0.5}"#;
+                let json = run_simulation(params);
+                let bytes = run_simulation_binary(params).unwrap();
+                let (times, species) = decode_binary_result(&bytes).unwrap();
+                // Shortest round-trip formatting is unique per f64, so equal text means equal bits
+                assert!(json.starts_with(&format!(
+                    r#"{{"species":{},"time":{},"#,
+                    serde_json::to_string(&species).unwrap(),
+                    serde_json::to_string(&times).unwrap()
+                )));
+            }
+
+            #[test]
+            fn binary_headers_are_checked() {
+                let header = |n_times: f64, n_species: f64| -> Vec<u8> {
+                    [n_times, n_species, 0.0, 0.0].iter().flat_map(|value| value.to_le_bytes()).collect()
+                };
+                let n_species = INFO.species.len() as f64;
+                for n_times in [f64::NAN, f64::INFINITY, -1.0, 1.5, 1e300] {
+                    let error = decode_binary_result(&header(n_times, n_species)).err().unwrap();
+                    assert_eq!(error, format!("invalid n_times {} in the binary result header", n_times));
+                }
+                // Fits in a usize, but its length overflows one
+                let error = decode_binary_result(&header(2f64.powi(63), n_species)).err().unwrap();
+                assert_eq!(error, "expected more values, found 4");
+                assert!(decode_binary_result(&header(1.0, f64::NAN)).err().unwrap().starts_with("invalid n_species"));
+            }
+
+            #[test]
+            fn sweep_validates_and_records_metrics() {
+                let defaults: Value = serde_json::from_str(&get_default_parameters()).unwrap();
+                let names: Vec<&String> = defaults.as_object().unwrap().keys().collect();
+                let name = names.iter().find(|key| key.as_str() != "final_time").unwrap_or(&names[0]);
+                let sweep = json!({"parameter": name, "min": 0.5, "max": 1.5, "n": 3, "species": INFO.species[0]});
+                let entries: Value =
+                    serde_json::from_str(&run_parameter_sweep(r#"{"final_time": 0.5}"#, &sweep.to_string()).unwrap()).unwrap();
+                let entries = entries.as_array().unwrap();
+                assert_eq!(entries.len(), 3);
+                assert_eq!(entries[2]["value"], 1.5);
+                assert!(entries[0]["metrics"]["cmax"].is_number());
+                assert!(run_parameter_sweep("{}", r#"{"parameter": "not_a_parameter", "values": [1.0]}"#).is_err());
+            }
+
+            #[test]
+            fn steady_state_mode_reports_outcome() {
+                // Any finite RMS meets a huge tolerance on the first step
+                let loose = run(r#"{"final_time": 1.0, "mode": "steady_state", "steady_state_tol": 1e300}"#);
+                let steady = loose.steady_state.unwrap();
+                assert!(steady.reached && steady.time < 1.0);
+                assert_eq!(loose.time.last(), Some(&steady.time));
+                assert_eq!(steady.state.len(), INFO.species.len());
+                // The maximum time is reached first and reported
+                let capped = run(r#"{"final_time": 0.5, "mode": "steady_state", "steady_state_tol": 1e300, "steady_state_min_time": 1.0}"#);
+                let steady = capped.steady_state.unwrap();
+                assert!(!steady.reached);
+                assert_eq!(steady.time, 0.5);
+                assert!(run(r#"{"final_time": 0.5}"#).steady_state.is_none());
+            }
+
+            #[test]
+            fn scheduled_doses_apply_and_are_recorded() {
+                // States fixed by algebraic rules are solved for again after a dose, so only others are dosed
+                let dosable: Vec<&str> =
+                    INFO.species.iter().enumerate().filter(|(i, _)| !INFO.algebraic.contains(i)).map(|(_, id)| *id).collect();
+                let (first, last) = (dosable[0], dosable[dosable.len() - 1]);
+                let result = run_json(json!({"final_time": 2.0, "doses": [
+                    {"time": 1.0, "amount": 1.0, "target": first},
+                    {"time": 1.0, "amount": 2.0, "target": last, "units": "mg"},
+                    {"time": 5.0, "amount": 4.0, "target": first},
+                ]}));
+                assert!(result.error.is_none());
+                // The dose after final_time is ignored, the simultaneous ones both apply
+                assert_eq!(result.applied_doses.len(), 2);
+                // Doses are in the event log too
+                let logged: Vec<(f64, &str)> = result
+                    .events
+                    .iter()
+                    .filter(|e| e.id == "dose")
+                    .map(|e| (e.time, e.assignments.keys().next().unwrap().as_str()))
+                    .collect();
+                assert_eq!(logged, vec![(1.0, first), (1.0, last)]);
+                let i = result.time.iter().position(|t| *t == 1.0).unwrap();
+                assert_eq!(result.time[i + 1], 1.0);
+                for id in &dosable {
+                    let values = result.species.get(id).unwrap();
+                    let dosed: f64 = result.applied_doses.iter().filter(|d| d.target == *id).map(|d| d.amount).sum();
+                    assert!((values[i + 1] - values[i] - dosed).abs() < 1e-12, "{}", id);
+                }
+                let unknown = run_simulation(r#"{"doses": [{"time": 1.0, "amount": 1.0, "target": "nope"}]}"#);
+                assert!(unknown.contains("doses: unknown species nope"));
+            }
+
+            #[test]
+            fn batch_failures_stay_per_run() {
+                let batch = run_simulation_batch(r#"[{"final_time": 0.5}, {"rtol": -1.0}, {}]"#).unwrap();
+                let results: Vec<SimulationResult> = serde_json::from_str(&batch).unwrap();
+                assert_eq!(results.len(), 3);
+                assert!(results[0].error.is_none());
+                assert_eq!(results[0].time.last(), Some(&0.5));
+                assert!(results[1].error.as_ref().unwrap().message.starts_with("rtol"));
+                assert!(results[2].error.is_none());
+                assert!(run_simulation_batch(r#"{"final_time": 1.0}"#).is_err());
+            }
+
+            #[test]
+            fn continuation_starts_from_the_final_state() {
+                let first = run_simulation(r#"{"final_time": 1.0}"#);
+                let previous: SimulationResult = serde_json::from_str(&first).unwrap();
+                let result = |concatenate| -> SimulationResult {
+                    serde_json::from_str(&continue_simulation(&first, "{}", 1.5, concatenate).unwrap()).unwrap()
+                };
+                let (segment, joined) = (result(false), result(true));
+                assert!(segment.error.is_none());
+                assert_eq!(segment.time.first(), Some(&1.0));
+                assert_eq!(segment.time.last(), Some(&2.5));
+                assert_eq!(joined.time.len(), previous.time.len() + segment.time.len() - 1);
+                assert!(joined.time.windows(2).all(|w| w[0] <= w[1]));
+                for (id, values) in &segment.species.0 {
+                    let (start, end) = (values[0], *previous.species.get(id).unwrap().last().unwrap());
+                    assert!((start - end).abs() <= restart_tolerance(id, end), "{}: {} vs {}", id, start, end);
+                    assert_eq!(joined.species.get(id).unwrap().len(), joined.time.len());
+                }
+                assert!(continue_simulation(&first, r#"{"not_a_parameter": 1}"#, 1.0, false).is_err());
+                assert!(continue_simulation(&first, "{}", 0.0, false).is_err());
+            }
+
+            #[test]
+            fn checkpoint_resumes_where_the_run_stopped() {
+                let first = run(r#"{"final_time": 1.0}"#);
+                let checkpoint = export_checkpoint();
+                let saved: Checkpoint = serde_json::from_str(&checkpoint).unwrap();
+                assert_eq!((saved.version, saved.time), (CHECKPOINT_VERSION, 1.0));
+                let resumed: SimulationResult =
+                    serde_json::from_str(&run_from_checkpoint(&checkpoint, r#"{"final_time": 2.5}"#).unwrap()).unwrap();
+                assert!(resumed.error.is_none());
+                assert_eq!(resumed.time.first(), Some(&1.0));
+                assert_eq!(resumed.time.last(), Some(&2.5));
+                for (id, values) in &resumed.species.0 {
+                    let (start, end) = (values[0], *first.species.get(id).unwrap().last().unwrap());
+                    assert!((start - end).abs() <= restart_tolerance(id, end), "{}: {} vs {}", id, start, end);
+                }
+                let next: Checkpoint = serde_json::from_str(&export_checkpoint()).unwrap();
+                assert_eq!(next.time, 2.5);
+                assert!(run_from_checkpoint(&checkpoint, r#"{"final_time": 0.5}"#).is_err());
+                let mut stale: Value = serde_json::from_str(&checkpoint).unwrap();
+                stale["version"] = json!(CHECKPOINT_VERSION + 1);
+                assert!(run_from_checkpoint(&stale.to_string(), "{}").unwrap_err().contains("version"));
+                stale["version"] = json!(CHECKPOINT_VERSION);
+                stale["species"] = json!(["other"]);
+                assert!(run_from_checkpoint(&stale.to_string(), "{}").unwrap_err().contains("species"));
+            }
+
+            #[test]
+            fn echoed_parameters_rerun_the_same_simulation() {
+                let first = run(r#"{"final_time": 1.5}"#);
+                assert_eq!(first.parameters["rtol"], 1e-6);
+                assert_eq!(first.parameters["solver"], "bdf");
+                let again = run(&first.parameters.to_string());
+                assert_eq!(again.parameters, first.parameters);
+                assert_eq!(again.time, first.time);
+                assert_eq!(again.species.0, first.species.0);
+                let hidden = run(r#"{"final_time": 1.5, "include_parameters": false}"#);
+                assert!(hidden.parameters.is_null() && hidden.error.is_none());
+                assert_eq!(hidden.time, first.time);
+            }
+
+            #[test]
+            fn units_label_every_series() {
+                let result = run(r#"{"final_time": 1.0, "include_observables": true}"#);
+                for (id, _) in result.species.0.iter().chain(&result.observables.0) {
+                    assert!(result.units.contains_key(id), "{}", id);
+                }
+                assert_eq!(result.units.len(), result.species.0.len() + result.observables.0.len() + 1);
+                assert_eq!(result.units["time"].as_deref(), INFO.time_unit);
+                // Species are labelled as in get_species_info
+                let info: Value = serde_json::from_str(&get_species_info()).unwrap();
+                for entry in info.as_array().unwrap() {
+                    assert_eq!(json!(result.units[entry["id"].as_str().unwrap()]), entry["units"]);
+                }
+            }
+
+            #[test]
+            fn output_kind_converts_with_compartment_sizes() {
+                let native = run_json(json!({"final_time": 1.0}));
+                assert!(native.amounts.0.is_empty() && native.concentrations.0.is_empty());
+                let both = run_json(json!({"final_time": 1.0, "output_kind": "both"}));
+                assert_eq!(both.species.0, native.species.0);
+                let info: Value = serde_json::from_str(&get_species_info()).unwrap();
+                for (k, entry) in info.as_array().unwrap().iter().enumerate() {
+                    let id = entry["id"].as_str().unwrap();
+                    let (amount, concentration) = (both.amounts.get(id).unwrap(), both.concentrations.get(id).unwrap());
+                    match (INFO.species_volumes[k], entry["compartment_volume"].as_f64()) {
+                        (Ok(_), Some(size)) if size > 0.0 => {
+                            for (a, c) in amount.iter().zip(concentration) {
+                                assert!((a - c * size).abs() <= 1e-12 * a.abs().max(1.0), "{}", id);
+                            }
+                        }
+                        (Err(_), _) => {
+                            assert_eq!(amount, concentration);
+                            assert!(both.notes.iter().any(|note| note.starts_with(id)));
+                        }
+                        // Sizes that change during the run
+                        _ => {}
+                    }
+                }
+                let amounts = run_json(json!({"final_time": 1.0, "output_kind": "amount"}));
+                assert_eq!(amounts.amounts.0, both.amounts.0);
+                assert!(amounts.concentrations.0.is_empty());
+            }
+
+            #[test]
+            fn timing_reports_solve_and_serialization() {
+                let json = run_simulation(r#"{"final_time": 48.0, "rtol": 1e-10, "atol": 1e-12, "include_observables": true}"#);
+                let timing = serde_json::from_str::<Value>(&json).unwrap()["timing"].clone();
+                for field in ["solve_ms", "serialize_ms", "total_ms"] {
+                    assert!(timing[field].is_f64(), "{}", field);
+                }
+                let result: SimulationResult = serde_json::from_str(&json).unwrap();
+                let timing = result.timing;
+                assert!(timing.solve_ms > 0.0 && timing.serialize_ms >= 0.0);
+                assert!(timing.total_ms >= timing.solve_ms + timing.serialize_ms);
+                // Rejected parameters are never solved
+                let failed = run(r#"{"no_such_option": 1}"#);
+                assert!(failed.error.is_some() && failed.timing.total_ms >= failed.timing.solve_ms);
+            }
+
+            #[test]
+            fn time_unit_converts_to_model_time() {
+                for (name, unit) in [("s", 1.0), ("min", 60.0), ("h", 3600.0), ("d", 86400.0)] {
+                    let parsed: TimeUnit = serde_json::from_value(json!(name)).unwrap();
+                    assert_eq!(parsed.seconds(), unit);
+                }
+                let Some(model_seconds) = INFO.time_seconds else {
+                    assert!(run_json(json!({"time_unit": "h"})).error.unwrap().message.starts_with("time_unit"));
+                    return;
+                };
+                // One model time unit, given in minutes
+                let minutes = model_seconds / 60.0;
+                let direct = run_json(json!({"final_time": 1.0, "output_times": [0.0, 0.5, 1.0]}));
+                let params = json!({"time_unit": "min", "final_time": minutes, "output_times": [0.0, minutes / 2.0, minutes]});
+                let converted = run_json(params.clone());
+                assert!(converted.error.is_none());
+                assert_eq!(converted.time, direct.time);
+                for (id, values) in &direct.species.0 {
+                    let other = converted.species.get(id).unwrap();
+                    assert!(values.iter().zip(other).all(|(a, b)| (a - b).abs() <= 1e-9 * a.abs().max(1.0)), "{}", id);
+                }
+                let mut back = params;
+                back["convert_output_time"] = json!(true);
+                let back = run_json(back);
+                assert!((back.time[2] - minutes).abs() <= 1e-12 * minutes);
+                assert_eq!(back.units["time"].as_deref(), Some("min"));
+                assert_eq!(converted.units["time"].as_deref(), INFO.time_unit);
+            }
+
+            #[test]
+            fn runs_start_at_t_start() {
+                let target = INFO.species[0];
+                let result = run_json(json!({"t_start": 8.0, "final_time": 10.0, "doses": [{"time": 9.0, "amount": 1.0, "target": target}]}));
+                assert!(result.error.is_none());
+                assert_eq!(result.time.first(), Some(&8.0));
+                assert_eq!(result.time.last(), Some(&10.0));
+                assert_eq!(result.applied_doses[0].time, 9.0);
+                let sampled = run_json(json!({"t_start": 8.0, "final_time": 10.0, "output_times": [8.0, 9.0, 10.0]}));
+                assert_eq!(sampled.time, [8.0, 9.0, 10.0]);
+                let error = |params: Value| run_json(params).error.unwrap().message;
+                assert_eq!(error(json!({"t_start": 8.0, "final_time": 8.0})), "final_time 8 must be after t_start 8");
+                assert!(error(json!({"t_start": 30.0})).contains("after t_start"));
+                assert!(error(json!({"t_start": -1.0})).starts_with("t_start"));
+                assert!(error(json!({"t_start": 8.0, "final_time": 10.0, "output_times": [7.0, 9.0]})).starts_with("output_times"));
+                assert!(error(json!({"t_start": 8.0, "final_time": 10.0, "doses": [{"time": 7.0, "amount": 1.0, "target": target}]}))
+                    .starts_with("doses"));
+            }
+
+            #[test]
+            fn max_steps_stops_the_run() {
+                let stopped = run(r#"{"max_steps": 5}"#);
+                let error = stopped.error.as_ref().unwrap();
+                assert_eq!(error.kind, "max_steps_exceeded");
+                assert_eq!(error.message, format!("solver exceeded max_steps (5) at t = {}", error.time.unwrap()));
+                assert_eq!(stopped.steps, 5);
+                assert!(stopped.time.len() > 1);
+                let full = run(r#"{"final_time": 0.5}"#);
+                assert!(full.error.is_none() && full.steps > 0);
+                assert!(run(r#"{"max_steps": 0}"#).error.unwrap().message.contains("max_steps"));
+            }
+
+            #[test]
+            fn types_ts_match_simulation_result() {
+                let ts = get_types_ts();
+                let result: Value = serde_json::from_str(&run_simulation(r#"{"final_time": 1.0, "mass_balance": true}"#)).unwrap();
+                assert_eq!(ts_fields(&ts, "SimulationResult"), json_keys(&result));
+                assert_eq!(ts_fields(&ts, "SimulationParams"), json_keys(&result["parameters"]));
+                assert_eq!(ts_fields(&ts, "SolverStats"), json_keys(&result["stats"]));
+                assert_eq!(ts_fields(&ts, "Diagnostics"), json_keys(&result["diagnostics"]));
+                let mut species: Vec<String> = INFO.species.iter().map(|id| id.to_string()).collect();
+                species.sort();
+                assert_eq!(json_keys(&result["species"]), species);
+            }
+
+            #[test]
+            fn solver_stats_are_reported() {
+                let result = run("{}");
+                let stats = result.stats.unwrap();
+                assert_eq!(stats.steps, result.steps);
+                assert!(stats.steps > 0 && stats.rhs_evals > 0 && stats.jacobian_evals > 0);
+                assert!(stats.newton_iterations > 0 && stats.final_step_size > 0.0);
+                let last: SolverStats = serde_json::from_str(&get_last_run_stats()).unwrap();
+                assert_eq!((last.steps, last.rhs_evals), (stats.steps, stats.rhs_evals));
+                assert!(run(r#"{"max_steps": 0}"#).stats.is_none());
+            }
+
+            #[test]
+            fn tr_bdf2_matches_bdf() {
+                // TR-BDF2 cannot hold algebraic rules, see tr_bdf2_is_refused_for_algebraic_rules
+                if !INFO.algebraic.is_empty() {
+                    return;
+                }
+                let run = |solver: &str| {
+                    run_json(json!({ "solver": solver, "rtol": 1e-8, "atol": 1e-10, "output_times": [1.0, 2.0, 4.0, 8.0, 16.0] }))
+                };
+                let (bdf, tr_bdf2) = (run("bdf"), run("tr_bdf2"));
+                assert!(bdf.error.is_none() && tr_bdf2.error.is_none());
+                for (name, expected) in &bdf.species.0 {
+                    let actual = tr_bdf2.species.get(name).unwrap();
+                    let scale = expected.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+                    // Relative to the largest value, and at least the absolute tolerance
+                    // for species that stay near zero
+                    for (a, e) in actual.iter().zip(expected) {
+                        assert!((a - e).abs() <= 1e-4 * scale + 1e-8, "{}: {} vs {}", name, a, e);
+                    }
+                }
+                // Both methods share the root finding and event code, so they fire the same events
+                assert_eq!(bdf.events.len(), tr_bdf2.events.len());
+                for (a, e) in tr_bdf2.events.iter().zip(&bdf.events) {
+                    assert!(
+                        a.id == e.id && (a.time - e.time).abs() <= 1e-4 * e.time.abs().max(1.0),
+                        "{} at {} vs {} at {}",
+                        a.id,
+                        a.time,
+                        e.id,
+                        e.time
+                    );
+                }
+                assert!(run_simulation(r#"{"solver": "rk4"}"#).contains("unknown variant"));
+            }
+
+            #[test]
+            fn tr_bdf2_is_refused_for_algebraic_rules() {
+                if INFO.algebraic.is_empty() {
+                    return;
+                }
+                let result = run(r#"{"solver": "tr_bdf2"}"#);
+                let error = result.error.unwrap();
+                assert_eq!((error.kind.as_str(), error.field.as_deref()), ("invalid_params", Some("solver")));
+                assert_eq!(result.steps, 0);
+                // Refused with the other options, so validate_parameters reports it without a run
+                let report: Value = serde_json::from_str(&validate_parameters(r#"{"solver": "tr_bdf2"}"#)).unwrap();
+                assert_eq!(report["errors"][0]["field"], "solver");
+                assert!(run_simulation(r#"{"solver": "rk4"}"#).contains("unknown variant"));
+            }
+
+            #[test]
+            fn finite_difference_jacobian_matches_analytic() {
+                let run = |jacobian: &str| run_json(json!({ "jacobian": jacobian, "output_times": [1.0, 2.0, 4.0, 8.0, 16.0] }));
+                let (analytic, finite_difference) = (run("analytic"), run("finite_difference"));
+                assert!(analytic.error.is_none() && finite_difference.error.is_none());
+                // The default scenario; within ten times the default rtol and atol of 1e-6
+                for (name, expected) in &analytic.species.0 {
+                    let actual = finite_difference.species.get(name).unwrap();
+                    for (a, e) in actual.iter().zip(expected) {
+                        assert!((a - e).abs() <= 1e-5 * e.abs() + 1e-5, "{}: {} vs {}", name, a, e);
+                    }
+                }
+            }
+
+            #[test]
+            fn max_step_bounds_the_steps() {
+                let result = run(r#"{"max_step": 0.05, "initial_step": 0.01, "final_time": 2.0}"#);
+                assert!(result.error.is_none());
+                assert!(result.time.windows(2).all(|w| w[1] - w[0] <= 0.05 + 1e-12));
+                assert!(run(r#"{"max_step": 0.0}"#).error.is_some());
+                assert!(run(r#"{"max_step": 0.01, "initial_step": 0.1}"#).error.is_some());
+            }
+
+            #[test]
+            fn non_finite_state_is_reported() {
+                // Two huge doses overflow the first species to infinity at t = 1
+                let dose = json!({ "target": INFO.species[0], "time": 1.0, "amount": 1e308 });
+                let params = json!({ "final_time": 2.0, "doses": [dose, dose] });
+                // JSON has no infinity, so the state comes back with nulls
+                let result: Value = serde_json::from_str(&run_simulation(&params.to_string())).unwrap();
+                let message = result["error"]["message"].as_str().unwrap();
+                assert!(message.starts_with(&format!("non-finite value of {} (y[0])", INFO.species[0])), "{}", message);
+                assert_eq!(result["error"]["time"], 1.0);
+                assert!(result["time"].as_array().unwrap().iter().all(|t| t.as_f64().unwrap() <= 1.0));
+            }
+
+            #[test]
+            fn clamped_run_stays_nonnegative() {
+                let result = run(r#"{"clamp_nonnegative": true}"#);
+                assert!(result.error.is_none());
+                assert!(result.species.0.iter().all(|(_, values)| values.iter().all(|&v| v >= 0.0)));
+            }
+
+            #[test]
+            fn mass_balance_is_reported() {
+                let result = run(r#"{"mass_balance": true}"#);
+                let diagnostics = result.diagnostics.unwrap();
+                assert_eq!(diagnostics.total_mass.len(), result.time.len());
+                assert!(diagnostics.max_mass_drift >= 0.0);
+                assert!(run("{}").diagnostics.is_none());
+            }
+
+            #[test]
+            fn out_of_range_parameters_are_refused() {
+                let report: ParameterReport = serde_json::from_str(&validate_parameters(r#"{"final_time": "x"}"#)).unwrap();
+                assert_eq!(report.errors.len(), 1);
+                // The first parameter with a physical range, set outside it
+                let info: Value = serde_json::from_str(&get_parameters_info()).unwrap();
+                let bounded = info.as_array().unwrap().iter().find(|entry| entry["min"].as_f64().is_some_and(|min| min > -1.0));
+                if let Some(entry) = bounded {
+                    let name = entry["id"].as_str().unwrap();
+                    let params = json!({ name: -1.0, "validate": true }).to_string();
+                    let report: ParameterReport = serde_json::from_str(&validate_parameters(&params)).unwrap();
+                    assert!(report.errors.iter().any(|issue| issue.field.as_deref() == Some(name)));
+                    let result = run(&params);
+                    assert_eq!(result.error.as_ref().map(|error| error.kind.as_str()), Some("invalid_params"));
+                    // Refused before any integration
+                    assert_eq!(result.steps, 0);
+                    assert!(result.time.is_empty());
+                }
+            }
+
+            #[test]
+            fn padded_parameter_text_parses() {
+                // As read from a Windows-exported file or a browser fetch
+                for text in ["\u{feff}{\"final_time\": 0.5}", "  \r\n{\"final_time\": 0.5}\n\t ", "\u{feff}\u{a0}{\"final_time\": 0.5}\u{a0}\r\n"] {
+                    let result = run(text);
+                    assert!(result.error.is_none(), "{:?}", text);
+                    assert_eq!(result.time.last(), Some(&0.5));
+                }
+            }
+        }
+    };
+}
+
+exports_test!(two_compartment_exports, two_compartment, TwoCompartmentModel);
+exports_test!(event_dose_exports, event_dose, EventDoseModel);
+exports_test!(infusion_exports, infusion, InfusionModel);
+exports_test!(oral_window_exports, oral_window, OralWindowModel);
+exports_test!(transit_chain_exports, transit_chain, TransitChainModel);
+exports_test!(protein_binding_exports, protein_binding, ProteinBindingModel);
+exports_test!(euromix_exports, euromix_model, EuromixModel);
+exports_test!(pbpk_bpa_exports, pbpk_bpa_model, PBPKBPAModel);
+exports_test!(talinolol_exports, talinolol_model, TalinololModel);
@@ -0,0 +1,135 @@
+//! Fuzzing of the parameter path: JSON text, defaults merge, validation and run
+//!
+//! Arbitrary text, and JSON objects built from the option and parameter names
+//! with odd values (huge, tiny and out-of-range numbers, NaN and infinity as
+//! literals and strings, deep nesting, duplicate keys, comments), go through a
+//! fixture's run_simulation. Each must come back as a result or a structured
+//! error, never a panic: in the browser a panic takes the whole WASM instance
+//! down. Inputs that once broke it are kept as plain tests at the end. Set
+//! PROPTEST_CASES to run more cases than the default 256.
+
+#![cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+
+use proptest::prelude::*;
+use serde_json::Value;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/event_dose.rs"]
+mod event_dose;
+
+/// Options, the fixture's parameters and the fields of doses, plus a few that are neither
+const KEYS: &[&str] = &[
+    "t_start", "final_time", "time_unit", "convert_output_time", "output_times", "output_dt",
+    "max_output_points", "include_observables", "output_kind", "include_parameters", "mass_balance",
+    "validate", "doses", "rtol", "atol", "max_steps", "max_step", "initial_step", "check_finite",
+    "clamp_nonnegative", "clamp_threshold", "solver", "jacobian", "mode", "steady_state_tol",
+    "steady_state_exclude", "steady_state_min_time", "ka", "ke", "Dose", "Vd", "Mr", "c",
+    "time", "amount", "target", "units", "", "__proto__", "Gut",
+];
+
+fn key() -> impl Strategy<Value = String> {
+    prop_oneof![4 => prop::sample::select(KEYS).prop_map(String::from), 1 => "\\PC{0,6}"]
+}
+
+fn number() -> impl Strategy<Value = String> {
+    prop_oneof![
+        prop::sample::select(&[
+            "0", "-0", "-1", "0.5", "2", "24", "1e-5", "1e-320", "1e300", "1e308", "1e999", "-1e999",
+            "18446744073709551615", "18446744073709551616", "-9223372036854775809", "1.", ".5", "01",
+        ][..])
+        .prop_map(String::from),
+        any::<f64>().prop_map(|v| format!("{:e}", v)),
+        any::<i64>().prop_map(|v| v.to_string()),
+    ]
+}
+
+/// A JSON value for any field: numbers, strings, enum names, arrays and objects,
+/// some of them invalid JSON
+fn value() -> impl Strategy<Value = String> {
+    let leaf = prop_oneof![
+        4 => number(),
+        2 => number().prop_map(|n| format!("\"{}\"", n)),
+        3 => prop::sample::select(&[
+            "\"NaN\"", "\"inf\"", "\"-infinity\"", "\"1,5\"", "\" 7 \"", "\"\"", "NaN", "Infinity", "null",
+            "true", "false", "\"Gut\"", "\"Cp\"", "\"d\"", "\"min\"", "\"s\"", "\"tr_bdf2\"", "\"finite_difference\"",
+            "\"steady_state\"", "\"both\"", "\"concentration\"",
+        ][..])
+        .prop_map(String::from),
+        1 => "\\PC{0,8}".prop_map(|s| serde_json::to_string(&s).unwrap()),
+        1 => (0usize..300).prop_map(|depth| "[".repeat(depth) + &"]".repeat(depth)),
+    ];
+    leaf.prop_recursive(3, 24, 6, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..6).prop_map(|values| format!("[{}]", values.join(", "))),
+            prop::collection::vec((key(), inner), 0..6).prop_map(|entries| object(&entries)),
+        ]
+    })
+}
+
+fn object(entries: &[(String, String)]) -> String {
+    let fields: Vec<String> = entries.iter().map(|(key, value)| format!("{}: {}", serde_json::to_string(key).unwrap(), value)).collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// Parameters as run_simulation receives them, sometimes annotated
+///
+/// A last max_steps, which a duplicate key overrides, bounds the length of any run.
+fn document() -> impl Strategy<Value = String> {
+    let entries = prop::collection::vec((key(), value()), 0..8);
+    let prefix = prop::sample::select(&["", "\u{feff}", "// params\n", "/* ", " \u{a0}"][..]);
+    (prefix, entries, any::<bool>()).prop_map(|(prefix, mut entries, trailing_comma)| {
+        entries.push(("max_steps".to_string(), "20000".to_string()));
+        let mut text = object(&entries);
+        if trailing_comma {
+            text.insert(text.len() - 1, ',');
+        }
+        format!("{}{}", prefix, text)
+    })
+}
+
+/// Run `params` and check that the output is a result or a structured error
+fn check_run(params: &str) -> Value {
+    let output = event_dose::run_simulation(params);
+    let result: Value = serde_json::from_str(&output).unwrap_or_else(|e| panic!("{}: not JSON ({}): {}", params, e, output));
+    let error = &result["error"];
+    if error.is_null() {
+        assert!(!result["time"].as_array().unwrap().is_empty(), "{}: no output and no error", params);
+    } else {
+        assert!(error["kind"].is_string() && error["message"].is_string(), "{}: unstructured error {}", params, error);
+    }
+    result
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_text_is_handled(text in "\\PC{0,64}") {
+        check_run(&text);
+    }
+
+    #[test]
+    fn json_like_text_is_handled(text in "[\\[\\]{}\",:0-9eE.+\\-a-z/* \n]{0,48}") {
+        check_run(&text);
+    }
+
+    #[test]
+    fn odd_parameters_are_handled(params in document()) {
+        check_run(&params);
+    }
+}
+
+#[test]
+fn huge_max_output_points_is_accepted() {
+    // Reserving the output once overflowed adding the stops to it
+    let result = check_run(r#"{"max_output_points": 18446744073709551615, "final_time": 6}"#);
+    assert!(result["error"].is_null(), "{}", result["error"]);
+}
+
+#[test]
+fn final_time_overflowing_model_time_is_refused() {
+    // 1e308 days is infinite in the fixture's hours
+    let result = check_run(r#"{"time_unit": "d", "final_time": 1e308}"#);
+    assert_eq!(result["error"]["field"], "final_time");
+    // And a span below the smallest float rounds to nothing
+    let result = check_run(r#"{"time_unit": "s", "final_time": 1e-321}"#);
+    assert_eq!(result["error"]["field"], "final_time");
+}
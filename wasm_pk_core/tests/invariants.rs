@@ -0,0 +1,179 @@
+//! Structural invariants of run_simulation results over random valid runs
+//!
+//! Each case draws model parameters from ranges given per model, and a start,
+//! a final time, solver settings, doses and an output grid shared by all
+//! models. The result must then have no error, times from t_start to
+//! final_time that only repeat at a dose or event (its pre- and post-dose
+//! states), a finite value per time for every species and observable, and
+//! cumulative states (excreted amounts and the like) that never decrease.
+//! A failure prints the parameters of the run, ready to pass back to
+//! run_simulation. To cover another generated model, add it to tests/models
+//! and an `invariants_test!` line with its parameter ranges and cumulative states.
+
+#![cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use serde_json::{json, Map, Value};
+use wasm_pk_core::PkModel;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/two_compartment.rs"]
+mod two_compartment;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/event_dose.rs"]
+mod event_dose;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/infusion.rs"]
+mod infusion;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/oral_window.rs"]
+mod oral_window;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/euromix.rs"]
+mod euromix;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/pbpk_bpa.rs"]
+mod pbpk_bpa;
+
+/// A number from 10^low to 10^high, uniform in its exponent
+fn log_uniform(low: f64, high: f64) -> impl Strategy<Value = f64> {
+    (low..high).prop_map(|exponent| 10f64.powf(exponent))
+}
+
+/// Parameters of a valid transient run: each of `ranges` (id, low, high) drawn
+/// uniformly, and doses to `species`
+fn run_params(species: &'static [&'static str], ranges: &'static [(&'static str, f64, f64)]) -> impl Strategy<Value = Value> {
+    let parameters: Vec<_> = ranges.iter().map(|&(id, low, high)| (low..=high).prop_map(move |value| (id, value))).collect();
+    let dose = (prop::sample::select(species), 0.0..1.0, 0.0..10.0);
+    // Every internal step, a bounded number of them or an output_dt grid
+    let output = prop_oneof![
+        Just(json!({})),
+        (2usize..400).prop_map(|points| json!({ "max_output_points": points })),
+        (0.05..5.0).prop_map(|dt: f64| json!({ "output_dt": dt })),
+    ];
+    let solver = (prop::sample::select(&["bdf", "tr_bdf2"][..]), log_uniform(-8.0, -4.0), log_uniform(-10.0, -5.0));
+    (parameters, 0.0..5.0, 1.0..100.0, solver, prop::collection::vec(dose, 0..4), output).prop_map(
+        |(parameters, t_start, span, (method, rtol, atol), doses, output)| {
+            let mut params: Map<String, Value> = parameters.into_iter().map(|(id, value)| (id.to_string(), value.into())).collect();
+            let doses: Vec<Value> = doses
+                .into_iter()
+                .map(|(target, at, amount)| json!({ "time": t_start + at * span, "amount": amount, "target": target }))
+                .collect();
+            params.extend(
+                json!({
+                    "t_start": t_start, "final_time": t_start + span, "solver": method, "rtol": rtol, "atol": atol,
+                    "doses": doses, "include_observables": true,
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            );
+            params.extend(output.as_object().unwrap().clone());
+            Value::Object(params)
+        },
+    )
+}
+
+/// Check the invariants on the result of `run_simulation` with `params`;
+/// `cumulative` are the species that must never decrease
+fn check_invariants(run_simulation: fn(&str) -> String, params: &Value, cumulative: &[&str]) -> Result<(), TestCaseError> {
+    let result: Value = serde_json::from_str(&run_simulation(&params.to_string())).unwrap();
+    prop_assert!(result["error"].is_null(), "{} with {}", result["error"], params);
+    let time: Vec<f64> = serde_json::from_value(result["time"].clone()).unwrap();
+    let (t_start, final_time) = (params["t_start"].as_f64().unwrap(), params["final_time"].as_f64().unwrap());
+    prop_assert_eq!(time.first(), Some(&t_start), "first time with {}", params);
+    let last = *time.last().unwrap();
+    prop_assert!((last - final_time).abs() <= 1e-12 * final_time, "last time {} with {}", last, params);
+    // A time repeats once, for the states before and after a dose or event there
+    let stops: Vec<f64> = result["events"].as_array().unwrap().iter().map(|event| event["time"].as_f64().unwrap()).collect();
+    for (i, w) in time.windows(2).enumerate() {
+        let repeat = w[1] == w[0] && stops.contains(&w[0]) && (i == 0 || time[i - 1] < w[0]);
+        prop_assert!(w[1] > w[0] || repeat, "time {} follows {} with {}", w[1], w[0], params);
+    }
+    let rtol = params["rtol"].as_f64().unwrap();
+    let atol = params["atol"].as_f64().unwrap();
+    for kind in ["species", "observables"] {
+        for (id, values) in result[kind].as_object().unwrap() {
+            let values: Vec<f64> = serde_json::from_value(values.clone()).unwrap();
+            prop_assert_eq!(values.len(), time.len(), "{} {} with {}", kind, id, params);
+            if let Some(k) = values.iter().position(|value| !value.is_finite()) {
+                return Err(TestCaseError::fail(format!("{} {} is {} at t = {} with {}", kind, id, values[k], time[k], params)));
+            }
+        }
+    }
+    for id in cumulative {
+        let values: Vec<f64> = serde_json::from_value(result["species"][id].clone()).unwrap();
+        for (k, w) in values.windows(2).enumerate() {
+            // Within the solver's error of no change
+            let drop = w[0] - w[1];
+            prop_assert!(drop <= 10.0 * (rtol * w[0].abs() + atol), "{} falls from {} to {} at t = {} with {}", id, w[0], w[1], time[k + 1], params);
+        }
+    }
+    Ok(())
+}
+
+/// A property test of `$model` over `$ranges`, with `$cumulative` never decreasing
+macro_rules! invariants_test {
+    ($name:ident, $model:ident, $species:expr, $ranges:expr, $cumulative:expr) => {
+        proptest! {
+            #[test]
+            fn $name(params in run_params($species, $ranges)) {
+                check_invariants($model::run_simulation, &params, $cumulative)?;
+            }
+        }
+    };
+}
+
+invariants_test!(
+    two_compartment_invariants, two_compartment, two_compartment::TwoCompartmentModel::species_names(),
+    &[("k1", 0.05, 5.0), ("k2", 0.01, 1.0), ("V", 0.5, 10.0), ("Vmax", 0.01, 2.0), ("Km", 0.05, 5.0)],
+    &[]
+);
+
+invariants_test!(
+    event_dose_invariants, event_dose, event_dose::EventDoseModel::species_names(),
+    &[("ka", 0.1, 5.0), ("ke", 0.01, 2.0), ("Dose", 0.0, 100.0), ("Vd", 0.5, 50.0)],
+    &["Urine"]
+);
+
+invariants_test!(
+    infusion_invariants, infusion, &["Cve", "Ast"],
+    &[("Ri", 0.0, 10.0), ("ti", 1.0, 100.0), ("ke", 0.1, 5.0), ("Mr", 0.5, 500.0), ("PODOSE", 0.0, 100.0)],
+    &["cum_dose"]
+);
+
+invariants_test!(
+    oral_window_invariants, oral_window, oral_window::OralWindowModel::species_names(),
+    &[("Kabs", 0.1, 1.0), ("Kelm", 0.01, 1.0), ("D_o", 0.1, 10.0), ("t0", 0.0, 6.0)],
+    &[]
+);
+
+// Adult physiology and chemical properties around the defaults, with flow and
+// volume fractions kept from summing past one; oral, dermal and venous doses
+invariants_test!(
+    euromix_invariants, euromix, &["QGut", "QSkin_sc_e", "QVen"],
+    &[
+        ("BM", 40.0, 120.0), ("BSA", 140.0, 230.0), ("scVFat", 0.1, 0.35), ("scVRich", 0.09, 0.12), ("scVLiver", 0.02, 0.03),
+        ("scVBlood", 0.06, 0.08), ("scFBlood", 3.5, 6.0), ("scFFat", 0.05, 0.1), ("scFPoor", 0.1, 0.2), ("scFLiver", 0.2, 0.3),
+        ("scFSkin", 0.03, 0.06), ("fSA_exposed", 0.01, 1.0), ("Falv", 1500.0, 3000.0), ("PCFat", 0.5, 10.0), ("PCLiver", 0.3, 3.0),
+        ("PCRich", 0.3, 3.0), ("PCPoor", 0.3, 3.0), ("PCSkin", 0.3, 3.0), ("kGut", 0.1, 5.0), ("Kp_sc_vs", 0.001, 0.1),
+        ("Michaelis", 0.0, 1.0), ("Km", 0.01, 1.0), ("Vmax", 0.0, 10.0), ("CLH", 10.0, 200.0), ("Ke", 0.0, 20.0), ("fub", 0.1, 1.0),
+    ],
+    &["QMetab", "QExcret"]
+);
+
+// Oral BPA absorbed over a short window after t0
+invariants_test!(
+    pbpk_bpa_invariants, pbpk_bpa, pbpk_bpa::PbpkBpaModel::species_names(),
+    &[
+        ("Kabs", 0.1, 2.0), ("Kelm", 0.05, 1.0), ("D_o", 0.1, 10.0), ("EoA_O", 0.5, 1.0), ("vplasma", 2.0, 6.0),
+        ("t0", 0.0, 6.0), ("period_O", 0.0001, 0.01),
+    ],
+    &[]
+);
@@ -0,0 +1,1469 @@
+// Generated native Rust code from SBML model: euromix
+// Uses SymPy CSE for optimized derivatives and Jacobian
+
+use serde::{Deserialize, Serialize};
+use wasm_pk_core::*;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulationParams {
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub BM: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub BSA: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub scVFat: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub scVRich: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub scVLiver: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub scVBlood: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub scVArt: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub scFBlood: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub scFFat: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub scFPoor: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub scFLiver: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub scFSkin: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub fSA_exposed: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Height_sc: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Height_vs: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Falv: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub PCFat: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub PCLiver: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub PCRich: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub PCPoor: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub PCSkin_sc: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub PCSkin: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub PCAir: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub kGut: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Kp_sc_vs: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Km: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Michaelis: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Vmax: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub CLH: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Ke: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub fub: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Air: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Urine: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Gut: f64,
+
+    // Initial amounts (optional, for runtime dosing)
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QFat: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QRich: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QPoor: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QLiver: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QMetab: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QGut: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QSkin_u: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QSkin_e: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QSkin_sc_u: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QSkin_sc_e: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QArt: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QVen: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QExcret: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_QAir: Option<f64>,
+    /// Times, outputs, doses and solver settings, the same for every model
+    #[serde(flatten)]
+    pub options: SimulationOptions,
+}
+
+/// Seconds per model time unit, if the SBML declares its time units
+const MODEL_TIME_SECONDS: Option<f64> = Some(3600.0);
+/// SBML id of the model's time units
+const MODEL_TIME_UNIT: Option<&str> = Some("HR");
+
+/// Model time units per time_unit (1 when it is not given)
+fn time_scale(sim_params: &SimulationParams) -> f64 {
+    sim_params.options.time_scale(&MODEL)
+}
+
+/// The parameters with every time and step size in model time units
+fn in_model_time(mut sim_params: SimulationParams) -> SimulationParams {
+    let scale = time_scale(&sim_params);
+    sim_params.options = sim_params.options.in_model_time(scale);
+    sim_params
+}
+
+/// The parameters with the defaults a run uses for its times and solver options
+fn with_defaults(mut sim_params: SimulationParams) -> SimulationParams {
+    sim_params.options = sim_params.options.with_defaults(&MODEL);
+    sim_params
+}
+
+pub fn run_simulation(params: &str) -> String {
+    wasm_pk_core::simulate::<EuromixModel>(params)
+}
+
+/// Run one simulation per parameter object; each result carries its own error
+pub fn run_simulation_batch(params_list: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_batch::<EuromixModel>(params_list)
+}
+
+/// Names of the solver parameter vector entries, in index order
+const PARAMETER_ORDER: &[&str] = &["BM", "BSA", "scVFat", "scVRich", "scVLiver", "scVBlood", "scVArt", "scFBlood", "scFFat", "scFPoor", "scFLiver", "scFSkin", "fSA_exposed", "Height_sc", "Height_vs", "Falv", "PCFat", "PCLiver", "PCRich", "PCPoor", "PCSkin_sc", "PCSkin", "PCAir", "kGut", "Kp_sc_vs", "Km", "Michaelis", "Vmax", "CLH", "Ke", "fub", "Air", "Urine", "Gut", "Fat", "Rich", "Liver", "Skin_e", "Skin_u", "Skin_sc_e", "Skin_sc_u", "f_su", "f_se", "VBlood", "FBlood", "Poor", "Art", "FFat", "FPoor", "FLiver", "FSkin", "Ven", "FRich", "FSkin_e", "FSkin_u", "init_QFat", "init_QRich", "init_QPoor", "init_QLiver", "init_QMetab", "init_QGut", "init_QSkin_u", "init_QSkin_e", "init_QSkin_sc_u", "init_QSkin_sc_e", "init_QArt", "init_QVen", "init_QExcret", "init_QAir"];
+
+/// Index mapping of the solver parameter vector: model parameters, derived
+/// parameter values, then the initial state as init_<species> (JSON array)
+pub fn get_parameter_order() -> String {
+    serde_json::to_string(PARAMETER_ORDER).unwrap()
+}
+
+/// Resolve one run's parameters into the solver parameter vector
+fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+    let BM = sim_params.BM;
+    let BSA = sim_params.BSA;
+    let scVFat = sim_params.scVFat;
+    let scVRich = sim_params.scVRich;
+    let scVLiver = sim_params.scVLiver;
+    let scVBlood = sim_params.scVBlood;
+    let scVArt = sim_params.scVArt;
+    let scFBlood = sim_params.scFBlood;
+    let scFFat = sim_params.scFFat;
+    let scFPoor = sim_params.scFPoor;
+    let scFLiver = sim_params.scFLiver;
+    let scFSkin = sim_params.scFSkin;
+    let fSA_exposed = sim_params.fSA_exposed;
+    let Height_sc = sim_params.Height_sc;
+    let Height_vs = sim_params.Height_vs;
+    let Falv = sim_params.Falv;
+    let PCFat = sim_params.PCFat;
+    let PCLiver = sim_params.PCLiver;
+    let PCRich = sim_params.PCRich;
+    let PCPoor = sim_params.PCPoor;
+    let PCSkin_sc = sim_params.PCSkin_sc;
+    let PCSkin = sim_params.PCSkin;
+    let PCAir = sim_params.PCAir;
+    let kGut = sim_params.kGut;
+    let Kp_sc_vs = sim_params.Kp_sc_vs;
+    let Km = sim_params.Km;
+    let Michaelis = sim_params.Michaelis;
+    let Vmax = sim_params.Vmax;
+    let CLH = sim_params.CLH;
+    let Ke = sim_params.Ke;
+    let fub = sim_params.fub;
+    let Air = sim_params.Air;
+    let Urine = sim_params.Urine;
+    let Gut = sim_params.Gut;
+    let Fat = BM*scVFat;
+    let Rich = BM*scVRich;
+    let Liver = BM*scVLiver;
+    let Skin_e = BSA*Height_vs*fSA_exposed;
+    let Skin_u = BSA*Height_vs*(1.0 - fSA_exposed);
+    let Skin_sc_e = BSA*Height_sc*fSA_exposed;
+    let Skin_sc_u = BSA*Height_sc*(1.0 - fSA_exposed);
+    let f_su = BSA*Kp_sc_vs*(1.0 - fSA_exposed);
+    let f_se = BSA*Kp_sc_vs*fSA_exposed;
+    let VBlood = BM*scVBlood;
+    let FBlood = BM*scFBlood;
+    let Poor = BM*(-scVBlood - scVFat - scVLiver - scVRich + 0.9) - Skin_e - Skin_sc_e - Skin_sc_u - Skin_u;
+    let Art = VBlood*scVArt;
+    let FFat = FBlood*scFFat;
+    let FPoor = FBlood*scFPoor;
+    let FLiver = FBlood*scFLiver;
+    let FSkin = FBlood*scFSkin;
+    let Ven = -Art + VBlood;
+    let FRich = FBlood - FFat - FLiver - FPoor - FSkin;
+    let FSkin_e = FSkin*fSA_exposed;
+    let FSkin_u = FSkin - FSkin_e;
+
+    vec![
+        BM,
+        BSA,
+        scVFat,
+        scVRich,
+        scVLiver,
+        scVBlood,
+        scVArt,
+        scFBlood,
+        scFFat,
+        scFPoor,
+        scFLiver,
+        scFSkin,
+        fSA_exposed,
+        Height_sc,
+        Height_vs,
+        Falv,
+        PCFat,
+        PCLiver,
+        PCRich,
+        PCPoor,
+        PCSkin_sc,
+        PCSkin,
+        PCAir,
+        kGut,
+        Kp_sc_vs,
+        Km,
+        Michaelis,
+        Vmax,
+        CLH,
+        Ke,
+        fub,
+        Air,
+        Urine,
+        Gut,
+        Fat,
+        Rich,
+        Liver,
+        Skin_e,
+        Skin_u,
+        Skin_sc_e,
+        Skin_sc_u,
+        f_su,
+        f_se,
+        VBlood,
+        FBlood,
+        Poor,
+        Art,
+        FFat,
+        FPoor,
+        FLiver,
+        FSkin,
+        Ven,
+        FRich,
+        FSkin_e,
+        FSkin_u,
+        sim_params.init_QFat.unwrap_or(0.0),
+        sim_params.init_QRich.unwrap_or(0.0),
+        sim_params.init_QPoor.unwrap_or(0.0),
+        sim_params.init_QLiver.unwrap_or(0.0),
+        sim_params.init_QMetab.unwrap_or(0.0),
+        sim_params.init_QGut.unwrap_or(1.0),
+        sim_params.init_QSkin_u.unwrap_or(0.0),
+        sim_params.init_QSkin_e.unwrap_or(0.0),
+        sim_params.init_QSkin_sc_u.unwrap_or(0.0),
+        sim_params.init_QSkin_sc_e.unwrap_or(0.0),
+        sim_params.init_QArt.unwrap_or(0.0),
+        sim_params.init_QVen.unwrap_or(0.0),
+        sim_params.init_QExcret.unwrap_or(0.0),
+        sim_params.init_QAir.unwrap_or(0.0),
+    ]
+}
+
+/// Range of each checked entry of p, with the parameters a derived value reads
+const PARAMETER_CHECKS: &[ParameterCheck] = &[
+    ("BM", 0, ParameterRange::Positive, &[]),
+    ("BSA", 1, ParameterRange::Unknown, &[]),
+    ("scVFat", 2, ParameterRange::Fraction, &[]),
+    ("scVRich", 3, ParameterRange::Fraction, &[]),
+    ("scVLiver", 4, ParameterRange::Fraction, &[]),
+    ("scVBlood", 5, ParameterRange::Fraction, &[]),
+    ("scVArt", 6, ParameterRange::Fraction, &[]),
+    ("scFBlood", 7, ParameterRange::NonNegative, &[]),
+    ("scFFat", 8, ParameterRange::Fraction, &[]),
+    ("scFPoor", 9, ParameterRange::Fraction, &[]),
+    ("scFLiver", 10, ParameterRange::Fraction, &[]),
+    ("scFSkin", 11, ParameterRange::Fraction, &[]),
+    ("fSA_exposed", 12, ParameterRange::Fraction, &[]),
+    ("Height_sc", 13, ParameterRange::Unknown, &[]),
+    ("Height_vs", 14, ParameterRange::Unknown, &[]),
+    ("Falv", 15, ParameterRange::NonNegative, &[]),
+    ("PCFat", 16, ParameterRange::Unknown, &[]),
+    ("PCLiver", 17, ParameterRange::Unknown, &[]),
+    ("PCRich", 18, ParameterRange::Unknown, &[]),
+    ("PCPoor", 19, ParameterRange::Unknown, &[]),
+    ("PCSkin_sc", 20, ParameterRange::Unknown, &[]),
+    ("PCSkin", 21, ParameterRange::Unknown, &[]),
+    ("PCAir", 22, ParameterRange::Unknown, &[]),
+    ("kGut", 23, ParameterRange::Unknown, &[]),
+    ("Kp_sc_vs", 24, ParameterRange::Unknown, &[]),
+    ("Km", 25, ParameterRange::Unknown, &[]),
+    ("Michaelis", 26, ParameterRange::Unknown, &[]),
+    ("Vmax", 27, ParameterRange::Unknown, &[]),
+    ("CLH", 28, ParameterRange::NonNegative, &[]),
+    ("Ke", 29, ParameterRange::NonNegative, &[]),
+    ("fub", 30, ParameterRange::Fraction, &[]),
+    ("Air", 31, ParameterRange::NonNegative, &[]),
+    ("Urine", 32, ParameterRange::NonNegative, &[]),
+    ("Gut", 33, ParameterRange::NonNegative, &[]),
+    ("Fat", 34, ParameterRange::NonNegative, &["BM", "scVFat"]),
+    ("Rich", 35, ParameterRange::NonNegative, &["BM", "scVRich"]),
+    ("Liver", 36, ParameterRange::NonNegative, &["BM", "scVLiver"]),
+    ("Skin_e", 37, ParameterRange::NonNegative, &["BSA", "fSA_exposed", "Height_vs"]),
+    ("Skin_u", 38, ParameterRange::NonNegative, &["BSA", "fSA_exposed", "Height_vs"]),
+    ("Skin_sc_e", 39, ParameterRange::NonNegative, &["BSA", "fSA_exposed", "Height_sc"]),
+    ("Skin_sc_u", 40, ParameterRange::NonNegative, &["BSA", "fSA_exposed", "Height_sc"]),
+    ("f_su", 41, ParameterRange::Fraction, &["BSA", "fSA_exposed", "Kp_sc_vs"]),
+    ("f_se", 42, ParameterRange::Fraction, &["BSA", "fSA_exposed", "Kp_sc_vs"]),
+    ("VBlood", 43, ParameterRange::NonNegative, &["BM", "scVBlood"]),
+    ("FBlood", 44, ParameterRange::NonNegative, &["BM", "scFBlood"]),
+    ("Poor", 45, ParameterRange::NonNegative, &["BM", "BSA", "scVFat", "scVRich", "scVLiver", "scVBlood", "fSA_exposed", "Height_sc", "Height_vs"]),
+    ("Art", 46, ParameterRange::NonNegative, &["BM", "scVBlood", "scVArt"]),
+    ("FFat", 47, ParameterRange::NonNegative, &["BM", "scFBlood", "scFFat"]),
+    ("FPoor", 48, ParameterRange::NonNegative, &["BM", "scFBlood", "scFPoor"]),
+    ("FLiver", 49, ParameterRange::NonNegative, &["BM", "scFBlood", "scFLiver"]),
+    ("FSkin", 50, ParameterRange::NonNegative, &["BM", "scFBlood", "scFSkin"]),
+    ("Ven", 51, ParameterRange::NonNegative, &["BM", "scVBlood", "scVArt"]),
+    ("FRich", 52, ParameterRange::NonNegative, &["BM", "scFBlood", "scFFat", "scFPoor", "scFLiver", "scFSkin"]),
+    ("FSkin_e", 53, ParameterRange::NonNegative, &["BM", "scFBlood", "scFSkin", "fSA_exposed"]),
+    ("FSkin_u", 54, ParameterRange::NonNegative, &["BM", "scFBlood", "scFSkin", "fSA_exposed"]),
+];
+
+/// Check parameters without running: `{errors, warnings}`, each `{field, message}` (JSON)
+pub fn validate_parameters(params: &str) -> String {
+    wasm_pk_core::validate_parameters::<EuromixModel>(params)
+}
+
+/// The model as a type, for code generic over models; see PkModel
+pub struct EuromixModel;
+
+impl PkModel for EuromixModel {
+    type Params = SimulationParams;
+    const N_STATES: usize = 14;
+    const INFO: ModelInfo = MODEL;
+
+    fn rhs(y: &[f64], p: &[f64], _t: f64, _windows: &[UptakeWindow], dy: &mut [f64]) {
+        // Parameters from p
+        let Falv = p[15];
+        let PCFat = p[16];
+        let PCLiver = p[17];
+        let PCRich = p[18];
+        let PCPoor = p[19];
+        let PCSkin_sc = p[20];
+        let PCSkin = p[21];
+        let PCAir = p[22];
+        let kGut = p[23];
+        let Km = p[25];
+        let Michaelis = p[26];
+        let Vmax = p[27];
+        let CLH = p[28];
+        let Ke = p[29];
+        let fub = p[30];
+        let Air = p[31];
+        let Fat = p[34];
+        let Rich = p[35];
+        let Liver = p[36];
+        let Skin_e = p[37];
+        let Skin_u = p[38];
+        let Skin_sc_e = p[39];
+        let Skin_sc_u = p[40];
+        let f_su = p[41];
+        let f_se = p[42];
+        let FBlood = p[44];
+        let Poor = p[45];
+        let Art = p[46];
+        let FFat = p[47];
+        let FPoor = p[48];
+        let FLiver = p[49];
+        let Ven = p[51];
+        let FRich = p[52];
+        let FSkin_e = p[53];
+        let FSkin_u = p[54];
+
+        // Map species names to y indices
+        let QFat = y[0];
+        let QRich = y[1];
+        let QPoor = y[2];
+        let QLiver = y[3];
+        let QGut = y[5];
+        let QSkin_u = y[6];
+        let QSkin_e = y[7];
+        let QSkin_sc_u = y[8];
+        let QSkin_sc_e = y[9];
+        let QArt = y[10];
+        let QVen = y[11];
+        let QAir = y[13];
+
+        // Temporary variables (CSE)
+        let x0 = Art.powi(-1);
+        let x1 = -QArt*x0;
+        let x2 = Fat.powi(-1)*PCFat.powi(-1);
+        let x3 = QFat*x2;
+        let x4 = FFat;
+        let x5 = PCRich.powi(-1)*Rich.powi(-1);
+        let x6 = QRich*x5;
+        let x7 = FRich;
+        let x8 = PCPoor.powi(-1)*Poor.powi(-1);
+        let x9 = QPoor*x8;
+        let x10 = FPoor;
+        let x11 = Liver.powi(-1);
+        let x12 = QLiver*x11;
+        let x13 = Liver > 0.0;
+        let x14 = if x13 {
+            x12
+        } else {
+            0.0
+        };
+        let x15 = if Km*PCLiver + x14 != 0.0 {
+            (Km*PCLiver + x14).powi(-1)
+        } else {
+            10000000000.0
+        };
+        let x16 = x14*x15;
+        let x17 = Liver*Vmax;
+        let x18 = Michaelis > 0.5;
+        let x19 = PCLiver.powi(-1);
+        let x20 = CLH*x19;
+        let x21 = fub*(if x18 {
+            x16*x17
+        } else {
+            x14*x20
+        });
+        let x22 = QArt*x0;
+        let x23 = FLiver*x22;
+        let x24 = FLiver*x19;
+        let x25 = x12*x24;
+        let x26 = Skin_sc_u.powi(-1);
+        let x27 = Skin_sc_u > 0.0;
+        let x28 = if x27 {
+            QSkin_sc_u*x26
+        } else {
+            0.0
+        };
+        let x29 = Skin_u.powi(-1);
+        let x30 = Skin_u > 0.0;
+        let x31 = if x30 {
+            QSkin_u*x29
+        } else {
+            0.0
+        };
+        let x32 = PCSkin.powi(-1);
+        let x33 = FSkin_u*x32;
+        let x34 = x31*x33;
+        let x35 = PCSkin_sc.powi(-1);
+        let x36 = FSkin_u*x22;
+        let x37 = Skin_sc_e.powi(-1);
+        let x38 = Skin_sc_e > 0.0;
+        let x39 = if x38 {
+            QSkin_sc_e*x37
+        } else {
+            0.0
+        };
+        let x40 = Skin_e.powi(-1);
+        let x41 = Skin_e > 0.0;
+        let x42 = if x41 {
+            QSkin_e*x40
+        } else {
+            0.0
+        };
+        let x43 = FSkin_e*x32;
+        let x44 = x42*x43;
+        let x45 = FSkin_e*x22;
+        let x46 = f_su;
+        let x47 = f_se;
+        let x48 = FBlood*Ven.powi(-1);
+        let x49 = -QVen*x48;
+        let x50 = Ke*fub;
+        let x51 = x22*x50;
+        let x52 = FBlood*Air.powi(-1);
+        let x53 = Falv*PCAir.powi(-1);
+        let x54 = -QAir*x52 + x22*x53;
+
+        // Derivatives
+        dy[0] = x4*(-x1 - x3);
+        dy[1] = x7*(-x1 - x6);
+        dy[2] = x10*(-x1 - x9);
+        dy[3] = QGut*kGut - x21 + x23 - x25;
+        dy[4] = x21;
+        dy[5] = -QGut*kGut;
+        dy[6] = f_su*x28 - f_su*x31*x35 - x34 + x36;
+        dy[7] = -f_se*x35*x42 + f_se*x39 - x44 + x45;
+        dy[8] = x46*(-x28 + x31*x35);
+        dy[9] = x47*(x35*x42 - x39);
+        dy[10] = -FFat*x22 - FPoor*x22 - FRich*x22 - x23 - x36 - x45 - x49 - x51 - x54;
+        dy[11] = FFat*x3 + FPoor*x9 + FRich*x6 + x25 + x34 + x44 + x49;
+        dy[12] = x51;
+        dy[13] = x54;
+    }
+
+    fn jac(y: &[f64], p: &[f64], _t: f64, v: &[f64], jv: &mut [f64]) {
+        jv.fill(0.0);
+
+        // Parameters from p
+        let Falv = p[15];
+        let PCFat = p[16];
+        let PCLiver = p[17];
+        let PCRich = p[18];
+        let PCPoor = p[19];
+        let PCSkin_sc = p[20];
+        let PCSkin = p[21];
+        let PCAir = p[22];
+        let kGut = p[23];
+        let Km = p[25];
+        let Michaelis = p[26];
+        let Vmax = p[27];
+        let CLH = p[28];
+        let Ke = p[29];
+        let fub = p[30];
+        let Air = p[31];
+        let Fat = p[34];
+        let Rich = p[35];
+        let Liver = p[36];
+        let Skin_e = p[37];
+        let Skin_u = p[38];
+        let Skin_sc_e = p[39];
+        let Skin_sc_u = p[40];
+        let f_su = p[41];
+        let f_se = p[42];
+        let FBlood = p[44];
+        let Poor = p[45];
+        let Art = p[46];
+        let FFat = p[47];
+        let FPoor = p[48];
+        let FLiver = p[49];
+        let Ven = p[51];
+        let FRich = p[52];
+        let FSkin_e = p[53];
+        let FSkin_u = p[54];
+
+        // Map species names to y indices
+        let QLiver = y[3];
+
+        // Temporary variables (CSE)
+        let x0 = Art.powi(-1);
+        let x2 = Fat.powi(-1)*PCFat.powi(-1);
+        let x4 = FFat;
+        let x5 = PCRich.powi(-1)*Rich.powi(-1);
+        let x7 = FRich;
+        let x8 = PCPoor.powi(-1)*Poor.powi(-1);
+        let x10 = FPoor;
+        let x11 = Liver.powi(-1);
+        let x12 = QLiver*x11;
+        let x13 = Liver > 0.0;
+        let x14 = if x13 {
+            x12
+        } else {
+            0.0
+        };
+        let x15 = if Km*PCLiver + x14 != 0.0 {
+            (Km*PCLiver + x14).powi(-1)
+        } else {
+            10000000000.0
+        };
+        let x16 = x14*x15;
+        let x17 = Liver*Vmax;
+        let x18 = Michaelis > 0.5;
+        let x19 = PCLiver.powi(-1);
+        let x20 = CLH*x19;
+        let x24 = FLiver*x19;
+        let x26 = Skin_sc_u.powi(-1);
+        let x27 = Skin_sc_u > 0.0;
+        let x29 = Skin_u.powi(-1);
+        let x30 = Skin_u > 0.0;
+        let x32 = PCSkin.powi(-1);
+        let x33 = FSkin_u*x32;
+        let x35 = PCSkin_sc.powi(-1);
+        let x37 = Skin_sc_e.powi(-1);
+        let x38 = Skin_sc_e > 0.0;
+        let x40 = Skin_e.powi(-1);
+        let x41 = Skin_e > 0.0;
+        let x43 = FSkin_e*x32;
+        let x46 = f_su;
+        let x47 = f_se;
+        let x48 = FBlood*Ven.powi(-1);
+        let x50 = Ke*fub;
+        let x52 = FBlood*Air.powi(-1);
+        let x53 = Falv*PCAir.powi(-1);
+        let x55 = x2*x4;
+        let x56 = x5*x7;
+        let x57 = x10*x8;
+        let x58 = if x13 {
+            x11
+        } else {
+            0.0
+        };
+        let x59 = fub*(if x18 {
+            x15*x17*x58*(1.0 - x16)
+        } else {
+            x20*x58
+        });
+        let x60 = x11*x24;
+        let x61 = kGut;
+        let x62 = x0;
+        let x63 = f_su*x35;
+        let x64 = if x30 {
+            x29
+        } else {
+            0.0
+        };
+        let x65 = x46*(if x27 {
+            x26
+        } else {
+            0.0
+        });
+        let x66 = f_se*x35;
+        let x67 = if x41 {
+            x40
+        } else {
+            0.0
+        };
+        let x68 = x47*(if x38 {
+            x37
+        } else {
+            0.0
+        });
+        let x69 = x48;
+        let x70 = x52;
+
+        // Jacobian-Vector Product
+        jv[0] += (-x55) * v[0];
+        jv[0] += (x0*x4) * v[10];
+        jv[1] += (-x56) * v[1];
+        jv[1] += (x0*x7) * v[10];
+        jv[2] += (-x57) * v[2];
+        jv[2] += (x0*x10) * v[10];
+        jv[3] += (-x59 - x60) * v[3];
+        jv[3] += (x61) * v[5];
+        jv[3] += (FLiver*x62) * v[10];
+        jv[4] += (x59) * v[3];
+        jv[5] += (-x61) * v[5];
+        jv[6] += (-x64*(x33 + x63)) * v[6];
+        jv[6] += (x65) * v[8];
+        jv[6] += (FSkin_u*x62) * v[10];
+        jv[7] += (-x67*(x43 + x66)) * v[7];
+        jv[7] += (x68) * v[9];
+        jv[7] += (FSkin_e*x62) * v[10];
+        jv[8] += (x63*x64) * v[6];
+        jv[8] += (-x65) * v[8];
+        jv[9] += (x66*x67) * v[7];
+        jv[9] += (-x68) * v[9];
+        jv[10] += (-x62*(FFat + FLiver + FPoor + FRich + FSkin_e + FSkin_u + x50 + x53)) * v[10];
+        jv[10] += (x69) * v[11];
+        jv[10] += (x70) * v[13];
+        jv[11] += (x55) * v[0];
+        jv[11] += (x56) * v[1];
+        jv[11] += (x57) * v[2];
+        jv[11] += (x60) * v[3];
+        jv[11] += (x33*x64) * v[6];
+        jv[11] += (x43*x67) * v[7];
+        jv[11] += (-x69) * v[11];
+        jv[12] += (x50*x62) * v[10];
+        jv[13] += (x53*x62) * v[10];
+        jv[13] += (-x70) * v[13];
+    }
+
+    fn init(p: &[f64], _t: f64, y: &mut [f64]) {
+        y.copy_from_slice(&p[55..69]);
+    }
+    fn defaults() -> serde_json::Value {
+        serde_json::from_str(&get_default_parameters()).unwrap()
+    }
+
+    fn options(sim_params: &SimulationParams) -> &SimulationOptions {
+        &sim_params.options
+    }
+
+    fn options_mut(sim_params: &mut SimulationParams) -> &mut SimulationOptions {
+        &mut sim_params.options
+    }
+
+    fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+        parameter_vector(sim_params)
+    }
+
+    fn build_problem() -> BuiltProblem<'static> {
+        BuiltProblem::dense(MODEL, equations())
+    }
+
+    fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+        prepare_run(sim_params)
+    }
+}
+
+/// The run of checked parameters: the solver works in model time units, while
+/// results and checkpoints keep the caller's
+fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+    let resolved = serde_json::to_value(with_defaults(sim_params.clone())).unwrap();
+    let scale = time_scale(&sim_params);
+    let sim_params = in_model_time(sim_params);
+    let p = parameter_vector(&sim_params);
+    Ok(Run {
+        p,
+        resolved,
+        scale,
+        uptake_windows: vec![],
+        options: sim_params.options,
+    })
+}
+
+/// The model's functions for the solver; the closures capture nothing, so they
+/// are plain function pointers
+fn equations() -> Equations<'static> {
+    let eval_observables = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let Fat = p[34];
+        let Rich = p[35];
+        let Liver = p[36];
+        let Skin_e = p[37];
+        let Skin_u = p[38];
+        let Skin_sc_e = p[39];
+        let Skin_sc_u = p[40];
+        let f_su = p[41];
+        let f_se = p[42];
+        let VBlood = p[43];
+        let FBlood = p[44];
+        let Poor = p[45];
+        let Art = p[46];
+        let FFat = p[47];
+        let FPoor = p[48];
+        let FLiver = p[49];
+        let FSkin = p[50];
+        let Ven = p[51];
+        let FRich = p[52];
+        let FSkin_e = p[53];
+        let FSkin_u = p[54];
+        vec![Fat, Rich, Liver, Skin_e, Skin_u, Skin_sc_e, Skin_sc_u, f_su, f_se, VBlood, FBlood, Poor, Art, FFat, FPoor, FLiver, FSkin, Ven, FRich, FSkin_e, FSkin_u]
+    };
+
+    let eval_volumes = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let Air = p[31];
+        let Urine = p[32];
+        let Gut = p[33];
+        let Fat = p[34];
+        let Rich = p[35];
+        let Liver = p[36];
+        let Skin_e = p[37];
+        let Skin_u = p[38];
+        let Skin_sc_e = p[39];
+        let Skin_sc_u = p[40];
+        let Poor = p[45];
+        let Art = p[46];
+        let Ven = p[51];
+        vec![Fat, Rich, Poor, Liver, Liver, Gut, Skin_u, Skin_e, Skin_sc_u, Skin_sc_e, Art, Ven, Urine, Air]
+    };
+
+    let eval_mass = |y: &[f64], _p: &[f64]| -> f64 {
+        y[0] + y[1] + y[2] + y[3] + y[4] + y[5] + y[6] + y[7] + y[8] + y[9] + y[10] + y[11] + y[12] + y[13]
+    };
+
+    Equations {
+        rhs: EuromixModel::rhs,
+        jac: EuromixModel::jac,
+        init: EuromixModel::init,
+        roots: no_roots,
+        triggers: no_triggers,
+        apply_events: no_events,
+        observables: eval_observables,
+        volumes: eval_volumes,
+        mass: eval_mass,
+        jac_matrix: None,
+    }
+}
+
+/// Solver stats of the last run of the last simulation call (JSON, null if it never started)
+pub fn get_last_run_stats() -> String {
+    LAST_RUN_STATS.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+pub fn run_simulation_at_times(params: &str, times: &[f64]) -> String {
+    wasm_pk_core::run_simulation_at_times::<EuromixModel>(params, times)
+}
+
+/// Run a simulation and return `time,<species...>` CSV, one row per stored time
+pub fn run_simulation_csv(params: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_csv::<EuromixModel>(params)
+}
+
+/// Cmax, Tmax, AUC, AUC(0-inf), terminal half-life and trough of one species (JSON)
+pub fn compute_pk_metrics(result_json: &str, species: &str) -> Result<String, String> {
+    wasm_pk_core::compute_pk_metrics(result_json, species)
+}
+
+/// AUC of one species over each `[t1, t2]` window (JSON array, same order)
+pub fn compute_partial_auc(result_json: &str, species: &str, windows: &str) -> Result<String, String> {
+    wasm_pk_core::compute_partial_auc(result_json, species, windows)
+}
+
+/// Run the model once per value of one parameter, e.g. body weight from 20 to 100 kg
+pub fn run_parameter_sweep(base_params: &str, sweep: &str) -> Result<String, String> {
+    wasm_pk_core::run_parameter_sweep::<EuromixModel>(base_params, sweep)
+}
+
+/// Continue a run_simulation result from its last recorded state for `additional_time`,
+/// with `new_params` merged over the parameters that run used. Its doses, output_times
+/// and uptake windows are not repeated; times in `new_params` count from the
+/// continuation start. The segment is returned on the combined time axis, after
+/// the earlier trajectory when `concatenate` is set.
+pub fn continue_simulation(previous_result: &str, new_params: &str, additional_time: f64, concatenate: bool) -> Result<String, String> {
+    wasm_pk_core::continue_simulation::<EuromixModel>(previous_result, new_params, additional_time, concatenate)
+}
+
+/// Checkpoint of the last run of the last simulation call (JSON, null if it never
+/// started or its final state is not finite)
+pub fn export_checkpoint() -> String {
+    LAST_CHECKPOINT.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+/// Resume an exported checkpoint up to the absolute `final_time` in `params`, which are
+/// merged over the checkpoint's parameters. Its pending doses are applied unless
+/// `params` gives new doses. The result and the new checkpoint use absolute time.
+pub fn run_from_checkpoint(checkpoint: &str, params: &str) -> Result<String, String> {
+    wasm_pk_core::run_from_checkpoint::<EuromixModel>(checkpoint, params)
+}
+
+const SPECIES_IDS: &[&str] = &["QFat", "QRich", "QPoor", "QLiver", "QMetab", "QGut", "QSkin_u", "QSkin_e", "QSkin_sc_u", "QSkin_sc_e", "QArt", "QVen", "QExcret", "QAir"];
+
+/// Parameters read by the derivative of each state, for failure hints
+const STATE_PARAMETERS: [&[&str]; 14] = [&["Art", "FFat", "Fat", "PCFat"], &["Art", "FRich", "PCRich", "Rich"], &["Art", "FPoor", "PCPoor", "Poor"], &["Art", "CLH", "FLiver", "Km", "Liver", "Michaelis", "PCLiver", "Vmax", "fub", "kGut"], &["CLH", "Km", "Liver", "Michaelis", "PCLiver", "Vmax", "fub"], &["kGut"], &["Art", "FSkin_u", "PCSkin", "PCSkin_sc", "Skin_sc_u", "Skin_u", "f_su"], &["Art", "FSkin_e", "PCSkin", "PCSkin_sc", "Skin_e", "Skin_sc_e", "f_se"], &["PCSkin_sc", "Skin_sc_u", "Skin_u", "f_su"], &["PCSkin_sc", "Skin_e", "Skin_sc_e", "f_se"], &["Air", "Art", "FBlood", "FFat", "FLiver", "FPoor", "FRich", "FSkin_e", "FSkin_u", "Falv", "Ke", "PCAir", "Ven", "fub"], &["FBlood", "FFat", "FLiver", "FPoor", "FRich", "FSkin_e", "FSkin_u", "Fat", "Liver", "PCFat", "PCLiver", "PCPoor", "PCRich", "PCSkin", "Poor", "Rich", "Skin_e", "Skin_u", "Ven"], &["Art", "Ke", "fub"], &["Air", "Art", "FBlood", "Falv", "PCAir"]];
+
+/// SBML ids of the assignment-rule observables, in evaluation order
+const OBSERVABLE_IDS: [&str; 21] = ["Fat", "Rich", "Liver", "Skin_e", "Skin_u", "Skin_sc_e", "Skin_sc_u", "f_su", "f_se", "VBlood", "FBlood", "Poor", "Art", "FFat", "FPoor", "FLiver", "FSkin", "Ven", "FRich", "FSkin_e", "FSkin_u"];
+
+/// What the shared solver loop and option checks need to know about the model
+const MODEL: ModelInfo = ModelInfo {
+    species: SPECIES_IDS,
+    state_parameters: &STATE_PARAMETERS,
+    event_ids: &[],
+    event_targets: &[],
+    event_initial_values: &[],
+    event_persistent: &[],
+    observables: &OBSERVABLE_IDS,
+    species_is_amount: &SPECIES_IS_AMOUNT,
+    species_volumes: &SPECIES_VOLUMES,
+    series_units: &SERIES_UNITS,
+    time_seconds: MODEL_TIME_SECONDS,
+    time_unit: MODEL_TIME_UNIT,
+    parameters: PARAMETER_ORDER,
+    parameter_checks: PARAMETER_CHECKS,
+    algebraic: &[],
+};
+
+/// Run a simulation and return the trajectory as little-endian f64 bytes
+pub fn run_simulation_binary(params: &str) -> Result<Vec<u8>, String> {
+    wasm_pk_core::run_simulation_binary::<EuromixModel>(params)
+}
+
+/// Describe the run_simulation_binary layout and its species order (JSON)
+pub fn get_binary_layout() -> String {
+    wasm_pk_core::get_binary_layout::<EuromixModel>()
+}
+
+/// Decode run_simulation_binary output into times and named species series
+pub fn decode_binary_result(bytes: &[u8]) -> Result<(Vec<f64>, OrderedSeries), String> {
+    wasm_pk_core::decode_binary_result::<EuromixModel>(bytes)
+}
+
+/// One observable of get_model_metadata
+#[derive(Serialize)]
+struct ObservableInfo {
+    id: &'static str,
+    units: Option<&'static str>,
+}
+
+/// SBML document the model was generated from
+#[derive(Serialize)]
+struct SbmlSource {
+    id: Option<&'static str>,
+    name: Option<&'static str>,
+    level: Option<u32>,
+    version: Option<u32>,
+    /// SHA-256 of the source file
+    sha256: Option<&'static str>,
+}
+
+/// Generator that wrote this module
+#[derive(Serialize)]
+struct GeneratorInfo {
+    name: &'static str,
+    version: Option<&'static str>,
+    /// ISO 8601 UTC time of generation
+    generated_at: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct ModelMetadata {
+    model_id: &'static str,
+    num_species: usize,
+    num_parameters: usize,
+    time_units: &'static str,
+    substance_units: &'static str,
+    volume_units: &'static str,
+    sbml: SbmlSource,
+    generator: GeneratorInfo,
+    diffsol_version: &'static str,
+    /// Optional features compiled in: "events", "sensitivities", "embedded_sbml"
+    features: &'static [&'static str],
+    observables: &'static [ObservableInfo],
+}
+
+pub fn get_model_metadata() -> String {
+    let metadata = ModelMetadata {
+        model_id: "euromix",
+        num_species: 14,
+        num_parameters: 34,
+        time_units: "HR",
+        substance_units: "MilliMOL",
+        volume_units: "L",
+        sbml: SbmlSource { id: Some("euromix"), name: None, level: Some(3), version: Some(2), sha256: Some("f938dd3639ac38d7e86702c8b285f93c8e550ee29fc02b6576180915dcdda900") },
+        generator: GeneratorInfo { name: "sbml_rust_generator", version: Some("1.0.0"), generated_at: Some("2026-01-01T00:00:00Z") },
+        diffsol_version: "0.6.3",
+        features: &[],
+        observables: &[
+            ObservableInfo { id: "Fat", units: None },
+            ObservableInfo { id: "Rich", units: None },
+            ObservableInfo { id: "Liver", units: None },
+            ObservableInfo { id: "Skin_e", units: None },
+            ObservableInfo { id: "Skin_u", units: None },
+            ObservableInfo { id: "Skin_sc_e", units: None },
+            ObservableInfo { id: "Skin_sc_u", units: None },
+            ObservableInfo { id: "f_su", units: Some("L_PER_HR") },
+            ObservableInfo { id: "f_se", units: Some("L_PER_HR") },
+            ObservableInfo { id: "VBlood", units: Some("L") },
+            ObservableInfo { id: "FBlood", units: Some("L_PER_HR") },
+            ObservableInfo { id: "Poor", units: None },
+            ObservableInfo { id: "Art", units: None },
+            ObservableInfo { id: "FFat", units: Some("L_PER_HR") },
+            ObservableInfo { id: "FPoor", units: Some("L_PER_HR") },
+            ObservableInfo { id: "FLiver", units: Some("L_PER_HR") },
+            ObservableInfo { id: "FSkin", units: Some("L_PER_HR") },
+            ObservableInfo { id: "Ven", units: None },
+            ObservableInfo { id: "FRich", units: Some("L_PER_HR") },
+            ObservableInfo { id: "FSkin_e", units: Some("L_PER_HR") },
+            ObservableInfo { id: "FSkin_u", units: Some("L_PER_HR") },
+        ],
+    };
+    serde_json::to_string(&metadata).unwrap()
+}
+
+/// One entry of get_parameters_info
+#[derive(Serialize)]
+struct ParameterInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    units: Option<&'static str>,
+    /// SBML notes as plain text
+    description: Option<&'static str>,
+    default_value: f64,
+    required: bool,
+    is_compartment: bool,
+    /// Soft bounds for input forms; validate_parameters has the hard checks
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+const PARAMETER_INFO: &[ParameterInfo] = &[
+    ParameterInfo { id: "BM", name: Some("body weight"), units: Some("KiloGM"), description: None, default_value: 70.0, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "BSA", name: Some("body surface area"), units: Some("DeciM2"), description: None, default_value: 190.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "scVFat", name: Some("fat volume as fraction of total body weight"), units: Some("L_PER_KiloGM"), description: None, default_value: 0.209, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scVRich", name: Some("richly perfused tissues volume as fraction of total body weight"), units: Some("L_PER_KiloGM"), description: None, default_value: 0.105, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scVLiver", name: Some("liver volume as fraction of total body weight"), units: Some("L_PER_KiloGM"), description: None, default_value: 0.024, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scVBlood", name: Some("blood volume as fraction of total body weight"), units: Some("L_PER_KiloGM"), description: None, default_value: 0.068, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scVArt", name: Some("arterial blood volume as fraction of total blood volume"), units: Some("UNITLESS"), description: None, default_value: 0.333333333333333, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scFBlood", name: Some("total blood flow per unit mass"), units: Some("L_PER_KiloGM_HR"), description: None, default_value: 4.8, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "scFFat", name: Some("fraction of blood flow going to adipose tissue"), units: Some("UNITLESS"), description: None, default_value: 0.085, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scFPoor", name: Some("fraction total blood flow going to poorly perfused tissue"), units: Some("UNITLESS"), description: None, default_value: 0.12, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scFLiver", name: Some("fraction total blood flow going to liver"), units: Some("UNITLESS"), description: None, default_value: 0.27, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "scFSkin", name: Some("fraction total blood flow going to skin"), units: Some("UNITLESS"), description: None, default_value: 0.05, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "fSA_exposed", name: Some("fraction of skin surface area actually exposed"), units: Some("UNITLESS"), description: None, default_value: 0.1, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "Height_sc", name: Some("thickness stratum corneum"), units: Some("DeciM"), description: None, default_value: 0.0001, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Height_vs", name: Some("thickness viable epidermis"), units: Some("DeciM"), description: None, default_value: 0.0122, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Falv", name: Some("alveolar ventilation rate"), units: Some("L_PER_HR"), description: None, default_value: 2220.0, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "PCFat", name: Some("partition coefficient fat over blood"), units: Some("UNITLESS"), description: None, default_value: 2.53, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCLiver", name: Some("partition coefficient liver over blood"), units: Some("UNITLESS"), description: None, default_value: 0.923, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCRich", name: Some("partition coefficient poorly perfused tissue over blood"), units: Some("UNITLESS"), description: None, default_value: 0.875, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCPoor", name: Some("partition coefficient richly perfused tissue over blood"), units: Some("UNITLESS"), description: None, default_value: 0.647, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCSkin_sc", name: Some("partition coefficient viable skin over stratum corneum"), units: Some("UNITLESS"), description: None, default_value: 0.889, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCSkin", name: Some("partition coefficient viable skin over blood"), units: Some("UNITLESS"), description: None, default_value: 0.889, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "PCAir", name: Some("partition coefficient blood over air"), units: Some("UNITLESS"), description: None, default_value: 1e+99, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "kGut", name: Some("absorption rate constant gut"), units: Some("PER_H"), description: None, default_value: 1.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Kp_sc_vs", name: Some("diffusion rate from stratum corneum to viable epidermis"), units: Some("DeciM_PER_HR"), description: None, default_value: 0.01, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Km", name: Some("Michaelis-Menten constant liver"), units: Some("MilliMOL"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Michaelis", name: Some("flag for Michaelis-Menten or linear metabolism"), units: Some("UNITLESS"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Vmax", name: Some("maximum rate of metabolism in the liver"), units: Some("MilliMOL_PER_L_HR"), description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "CLH", name: Some("hepatic clearance rate"), units: Some("L_PER_HR"), description: None, default_value: 132.0, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "Ke", name: Some("renal excretion rate"), units: Some("L_PER_HR"), description: None, default_value: 7.5, required: true, is_compartment: false, min: Some(0.0), max: None },
+    ParameterInfo { id: "fub", name: Some("fraction unbound in blood"), units: Some("UNITLESS"), description: None, default_value: 0.51, required: true, is_compartment: false, min: Some(0.0), max: Some(1.0) },
+    ParameterInfo { id: "Air", name: Some("alveolar air"), units: Some("L"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+    ParameterInfo { id: "Urine", name: Some("urine"), units: Some("L"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+    ParameterInfo { id: "Gut", name: Some("gut"), units: Some("L"), description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+];
+
+pub fn get_parameters_info() -> String {
+    serde_json::to_string(PARAMETER_INFO).unwrap()
+}
+
+/// One entry of get_species_info
+#[derive(Serialize)]
+struct SpeciesInfo {
+    id: &'static str,
+    initial_amount: f64,
+    #[serde(rename = "override")]
+    override_field: &'static str,
+    units: Option<&'static str>,
+    compartment: Option<&'static str>,
+    compartment_volume: Option<f64>,
+    /// False when the SBML value is a concentration (hasOnlySubstanceUnits=false)
+    is_amount: bool,
+    is_boundary: bool,
+}
+
+pub fn get_species_info() -> String {
+    let p = parameter_vector(&EuromixModel::default_params());
+    let species: &[SpeciesInfo] = &[
+        SpeciesInfo { id: "QFat", initial_amount: 0.0, override_field: "init_QFat", units: Some("MilliMOL"), compartment: Some("Fat"), compartment_volume: Some(p[34]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QRich", initial_amount: 0.0, override_field: "init_QRich", units: Some("MilliMOL"), compartment: Some("Rich"), compartment_volume: Some(p[35]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QPoor", initial_amount: 0.0, override_field: "init_QPoor", units: Some("MilliMOL"), compartment: Some("Poor"), compartment_volume: Some(p[45]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QLiver", initial_amount: 0.0, override_field: "init_QLiver", units: Some("MilliMOL"), compartment: Some("Liver"), compartment_volume: Some(p[36]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QMetab", initial_amount: 0.0, override_field: "init_QMetab", units: Some("MilliMOL"), compartment: Some("Liver"), compartment_volume: Some(p[36]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QGut", initial_amount: 1.0, override_field: "init_QGut", units: Some("MilliMOL"), compartment: Some("Gut"), compartment_volume: Some(p[33]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QSkin_u", initial_amount: 0.0, override_field: "init_QSkin_u", units: Some("MilliMOL"), compartment: Some("Skin_u"), compartment_volume: Some(p[38]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QSkin_e", initial_amount: 0.0, override_field: "init_QSkin_e", units: Some("MilliMOL"), compartment: Some("Skin_e"), compartment_volume: Some(p[37]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QSkin_sc_u", initial_amount: 0.0, override_field: "init_QSkin_sc_u", units: Some("MilliMOL"), compartment: Some("Skin_sc_u"), compartment_volume: Some(p[40]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QSkin_sc_e", initial_amount: 0.0, override_field: "init_QSkin_sc_e", units: Some("MilliMOL"), compartment: Some("Skin_sc_e"), compartment_volume: Some(p[39]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QArt", initial_amount: 0.0, override_field: "init_QArt", units: Some("MilliMOL"), compartment: Some("Art"), compartment_volume: Some(p[46]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QVen", initial_amount: 0.0, override_field: "init_QVen", units: Some("MilliMOL"), compartment: Some("Ven"), compartment_volume: Some(p[51]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QExcret", initial_amount: 0.0, override_field: "init_QExcret", units: Some("MilliMOL"), compartment: Some("Urine"), compartment_volume: Some(p[32]), is_amount: true, is_boundary: false },
+        SpeciesInfo { id: "QAir", initial_amount: 0.0, override_field: "init_QAir", units: Some("MilliMOL"), compartment: Some("Air"), compartment_volume: Some(p[31]), is_amount: true, is_boundary: false },
+    ];
+    serde_json::to_string(&species).unwrap()
+}
+
+/// Whether each species' values are amounts rather than concentrations
+const SPECIES_IS_AMOUNT: [bool; 14] = [true, true, true, true, true, true, true, true, true, true, true, true, true, true];
+/// Compartment converting each species between amount and concentration, or why none does
+const SPECIES_VOLUMES: [Result<&str, &str>; 14] = [Ok("Fat"), Ok("Rich"), Ok("Poor"), Ok("Liver"), Ok("Liver"), Ok("Gut"), Ok("Skin_u"), Ok("Skin_e"), Ok("Skin_sc_u"), Ok("Skin_sc_e"), Ok("Art"), Ok("Ven"), Ok("Urine"), Ok("Air")];
+
+/// Unit of each species and observable series; None where the SBML gives none
+const SERIES_UNITS: [(&str, Option<&str>); 35] = [
+    ("QFat", Some("MilliMOL")),
+    ("QRich", Some("MilliMOL")),
+    ("QPoor", Some("MilliMOL")),
+    ("QLiver", Some("MilliMOL")),
+    ("QMetab", Some("MilliMOL")),
+    ("QGut", Some("MilliMOL")),
+    ("QSkin_u", Some("MilliMOL")),
+    ("QSkin_e", Some("MilliMOL")),
+    ("QSkin_sc_u", Some("MilliMOL")),
+    ("QSkin_sc_e", Some("MilliMOL")),
+    ("QArt", Some("MilliMOL")),
+    ("QVen", Some("MilliMOL")),
+    ("QExcret", Some("MilliMOL")),
+    ("QAir", Some("MilliMOL")),
+    ("Fat", None),
+    ("Rich", None),
+    ("Liver", None),
+    ("Skin_e", None),
+    ("Skin_u", None),
+    ("Skin_sc_e", None),
+    ("Skin_sc_u", None),
+    ("f_su", Some("L_PER_HR")),
+    ("f_se", Some("L_PER_HR")),
+    ("VBlood", Some("L")),
+    ("FBlood", Some("L_PER_HR")),
+    ("Poor", None),
+    ("Art", None),
+    ("FFat", Some("L_PER_HR")),
+    ("FPoor", Some("L_PER_HR")),
+    ("FLiver", Some("L_PER_HR")),
+    ("FSkin", Some("L_PER_HR")),
+    ("Ven", None),
+    ("FRich", Some("L_PER_HR")),
+    ("FSkin_e", Some("L_PER_HR")),
+    ("FSkin_u", Some("L_PER_HR")),
+];
+
+/// One entry of get_compartments_info
+#[derive(Serialize)]
+struct CompartmentInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    /// Size at the default parameters; None when it changes during a run
+    size: Option<f64>,
+    /// Rule or initial assignment computing the size, e.g. "BM*scVLiver"
+    formula: Option<&'static str>,
+    units: Option<&'static str>,
+    species: &'static [&'static str],
+}
+
+pub fn get_compartments_info() -> String {
+    let p = parameter_vector(&EuromixModel::default_params());
+    let compartments: &[CompartmentInfo] = &[
+        CompartmentInfo { id: "Air", name: Some("alveolar air"), size: Some(p[31]), formula: None, units: Some("L"), species: &["QAir"] },
+        CompartmentInfo { id: "Urine", name: Some("urine"), size: Some(p[32]), formula: None, units: Some("L"), species: &["QExcret"] },
+        CompartmentInfo { id: "Fat", name: Some("adipose tissue"), size: Some(p[34]), formula: Some("BM*scVFat"), units: Some("L"), species: &["QFat"] },
+        CompartmentInfo { id: "Rich", name: Some("richly perfused tissue"), size: Some(p[35]), formula: Some("BM*scVRich"), units: Some("L"), species: &["QRich"] },
+        CompartmentInfo { id: "Liver", name: Some("liver"), size: Some(p[36]), formula: Some("BM*scVLiver"), units: Some("L"), species: &["QLiver", "QMetab"] },
+        CompartmentInfo { id: "Art", name: Some("arterial blood"), size: Some(p[46]), formula: Some("VBlood*scVArt"), units: Some("L"), species: &["QArt"] },
+        CompartmentInfo { id: "Ven", name: Some("venous blood"), size: Some(p[51]), formula: Some("-Art + VBlood"), units: Some("L"), species: &["QVen"] },
+        CompartmentInfo { id: "Skin_e", name: Some("viable epidermis exposed skin"), size: Some(p[37]), formula: Some("BSA*Height_vs*fSA_exposed"), units: Some("L"), species: &["QSkin_e"] },
+        CompartmentInfo { id: "Skin_u", name: Some("viable epidermis unexposed skin"), size: Some(p[38]), formula: Some("BSA*Height_vs*(1 - fSA_exposed)"), units: Some("L"), species: &["QSkin_u"] },
+        CompartmentInfo { id: "Skin_sc_e", name: Some("stratum corneum exposed skin"), size: Some(p[39]), formula: Some("BSA*Height_sc*fSA_exposed"), units: Some("L"), species: &["QSkin_sc_e"] },
+        CompartmentInfo { id: "Skin_sc_u", name: Some("stratum corneum unexposed skin"), size: Some(p[40]), formula: Some("BSA*Height_sc*(1 - fSA_exposed)"), units: Some("L"), species: &["QSkin_sc_u"] },
+        CompartmentInfo { id: "Poor", name: Some("poorly perfused tissue"), size: Some(p[45]), formula: Some("BM*(-scVBlood - scVFat - scVLiver - scVRich + 0.9) - Skin_e - Skin_sc_e - Skin_sc_u - Skin_u"), units: Some("L"), species: &["QPoor"] },
+        CompartmentInfo { id: "Gut", name: Some("gut"), size: Some(p[33]), formula: None, units: Some("L"), species: &["QGut"] },
+    ];
+    serde_json::to_string(&compartments).unwrap()
+}
+
+pub fn get_default_parameters() -> String {
+    let defaults: &[(&str, f64)] = &[
+        ("BM", 70.0),
+        ("BSA", 190.0),
+        ("scVFat", 0.209),
+        ("scVRich", 0.105),
+        ("scVLiver", 0.024),
+        ("scVBlood", 0.068),
+        ("scVArt", 0.333333333333333),
+        ("scFBlood", 4.8),
+        ("scFFat", 0.085),
+        ("scFPoor", 0.12),
+        ("scFLiver", 0.27),
+        ("scFSkin", 0.05),
+        ("fSA_exposed", 0.1),
+        ("Height_sc", 0.0001),
+        ("Height_vs", 0.0122),
+        ("Falv", 2220.0),
+        ("PCFat", 2.53),
+        ("PCLiver", 0.923),
+        ("PCRich", 0.875),
+        ("PCPoor", 0.647),
+        ("PCSkin_sc", 0.889),
+        ("PCSkin", 0.889),
+        ("PCAir", 1e+99),
+        ("kGut", 1.0),
+        ("Kp_sc_vs", 0.01),
+        ("Km", 0.0),
+        ("Michaelis", 0.0),
+        ("Vmax", 0.0),
+        ("CLH", 132.0),
+        ("Ke", 7.5),
+        ("fub", 0.51),
+        ("Air", 1.0),
+        ("Urine", 1.0),
+        ("Gut", 1.0),
+        ("final_time", 24.0),
+    ];
+    let defaults: serde_json::Map<String, serde_json::Value> = defaults.iter().map(|&(id, value)| (id.to_string(), value.into())).collect();
+    serde_json::Value::Object(defaults).to_string()
+}
+/// Equations in model order, serialized as a JSON object
+struct EquationTable(&'static [(&'static str, &'static str)]);
+
+impl Serialize for EquationTable {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (id, expression) in self.0 {
+            map.serialize_entry(id, expression)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Serialize)]
+struct ModelEquations {
+    derivatives: EquationTable,
+    assignment_rules: EquationTable,
+}
+
+pub fn get_model_equations() -> String {
+    let equations = ModelEquations {
+        derivatives: EquationTable(&[
+            ("QFat", "-FFat*QFat/(Fat*PCFat) + FFat*QArt/Art"),
+            ("QRich", "-FRich*QRich/(PCRich*Rich) + FRich*QArt/Art"),
+            ("QPoor", "-FPoor*QPoor/(PCPoor*Poor) + FPoor*QArt/Art"),
+            ("QLiver", "-FLiver*QLiver/(Liver*PCLiver) + QGut*kGut - fub*Piecewise((Liver*Vmax*Piecewise((QLiver/Liver, Liver > 0), (0, True))/(Km*PCLiver + Piecewise((QLiver/Liver, Liver > 0), (0, True))), Michaelis > 0.5), (CLH*Piecewise((QLiver/Liver, Liver > 0), (0, True))/PCLiver, True)) + FLiver*QArt/Art"),
+            ("QMetab", "fub*Piecewise((Liver*Vmax*Piecewise((QLiver/Liver, Liver > 0), (0, True))/(Km*PCLiver + Piecewise((QLiver/Liver, Liver > 0), (0, True))), Michaelis > 0.5), (CLH*Piecewise((QLiver/Liver, Liver > 0), (0, True))/PCLiver, True))"),
+            ("QGut", "-QGut*kGut"),
+            ("QSkin_u", "-FSkin_u*Piecewise((QSkin_u/Skin_u, Skin_u > 0), (0, True))/PCSkin + f_su*Piecewise((QSkin_sc_u/Skin_sc_u, Skin_sc_u > 0), (0, True)) - f_su*Piecewise((QSkin_u/Skin_u, Skin_u > 0), (0, True))/PCSkin_sc + FSkin_u*QArt/Art"),
+            ("QSkin_e", "-FSkin_e*Piecewise((QSkin_e/Skin_e, Skin_e > 0), (0, True))/PCSkin + f_se*Piecewise((QSkin_sc_e/Skin_sc_e, Skin_sc_e > 0), (0, True)) - f_se*Piecewise((QSkin_e/Skin_e, Skin_e > 0), (0, True))/PCSkin_sc + FSkin_e*QArt/Art"),
+            ("QSkin_sc_u", "-f_su*Piecewise((QSkin_sc_u/Skin_sc_u, Skin_sc_u > 0), (0, True)) + f_su*Piecewise((QSkin_u/Skin_u, Skin_u > 0), (0, True))/PCSkin_sc"),
+            ("QSkin_sc_e", "-f_se*Piecewise((QSkin_sc_e/Skin_sc_e, Skin_sc_e > 0), (0, True)) + f_se*Piecewise((QSkin_e/Skin_e, Skin_e > 0), (0, True))/PCSkin_sc"),
+            ("QArt", "FBlood*QVen/Ven - FFat*QArt/Art - FLiver*QArt/Art - FPoor*QArt/Art - FRich*QArt/Art - FSkin_e*QArt/Art - FSkin_u*QArt/Art - Falv*QArt/(Art*PCAir) - Ke*QArt*fub/Art + FBlood*QAir/Air"),
+            ("QVen", "-FBlood*QVen/Ven + FFat*QFat/(Fat*PCFat) + FLiver*QLiver/(Liver*PCLiver) + FPoor*QPoor/(PCPoor*Poor) + FRich*QRich/(PCRich*Rich) + FSkin_e*Piecewise((QSkin_e/Skin_e, Skin_e > 0), (0, True))/PCSkin + FSkin_u*Piecewise((QSkin_u/Skin_u, Skin_u > 0), (0, True))/PCSkin"),
+            ("QExcret", "Ke*QArt*fub/Art"),
+            ("QAir", "Falv*QArt/(Art*PCAir) - FBlood*QAir/Air"),
+        ]),
+        assignment_rules: EquationTable(&[
+            ("Fat", "BM*scVFat"),
+            ("Rich", "BM*scVRich"),
+            ("Liver", "BM*scVLiver"),
+            ("Skin_e", "BSA*Height_vs*fSA_exposed"),
+            ("Skin_u", "BSA*Height_vs*(1 - fSA_exposed)"),
+            ("Skin_sc_e", "BSA*Height_sc*fSA_exposed"),
+            ("Skin_sc_u", "BSA*Height_sc*(1 - fSA_exposed)"),
+            ("f_su", "BSA*Kp_sc_vs*(1 - fSA_exposed)"),
+            ("f_se", "BSA*Kp_sc_vs*fSA_exposed"),
+            ("VBlood", "BM*scVBlood"),
+            ("FBlood", "BM*scFBlood"),
+            ("Poor", "BM*(-scVBlood - scVFat - scVLiver - scVRich + 0.9) - Skin_e - Skin_sc_e - Skin_sc_u - Skin_u"),
+            ("Art", "VBlood*scVArt"),
+            ("FFat", "FBlood*scFFat"),
+            ("FPoor", "FBlood*scFPoor"),
+            ("FLiver", "FBlood*scFLiver"),
+            ("FSkin", "FBlood*scFSkin"),
+            ("Ven", "-Art + VBlood"),
+            ("FRich", "FBlood - FFat - FLiver - FPoor - FSkin"),
+            ("FSkin_e", "FSkin*fSA_exposed"),
+            ("FSkin_u", "FSkin - FSkin_e"),
+        ]),
+    };
+    serde_json::to_string_pretty(&equations).unwrap()
+}
+/// SBML document this module was generated from
+pub fn get_model_sbml() -> Result<String, String> {
+    Err("the source SBML was not embedded in this build (generated with embed_sbml=False)".to_string())
+}
+/// TypeScript definitions of the parameters, result and metadata of this model
+const TYPES_TS: &str = r#"// TypeScript definitions for euromix, generated with the module
+
+export type SpeciesId = "QFat" | "QRich" | "QPoor" | "QLiver" | "QMetab" | "QGut" | "QSkin_u" | "QSkin_e" | "QSkin_sc_u" | "QSkin_sc_e" | "QArt" | "QVen" | "QExcret" | "QAir";
+export type ObservableId = "Fat" | "Rich" | "Liver" | "Skin_e" | "Skin_u" | "Skin_sc_e" | "Skin_sc_u" | "f_su" | "f_se" | "VBlood" | "FBlood" | "Poor" | "Art" | "FFat" | "FPoor" | "FLiver" | "FSkin" | "Ven" | "FRich" | "FSkin_e" | "FSkin_u";
+export type SolverMethod = "bdf" | "tr_bdf2";
+export type JacobianMode = "analytic" | "finite_difference";
+export type SimulationMode = "transient" | "steady_state";
+export type TimeUnit = "s" | "min" | "h" | "d";
+export type OutputKind = "amount" | "concentration" | "both";
+
+export interface Dose {
+  time: number;
+  amount: number;
+  target: SpeciesId;
+  units?: string | null;
+}
+
+/** Input of run_simulation; omitted fields take the model defaults */
+export interface SimulationParams {
+  BM?: number;
+  BSA?: number;
+  scVFat?: number;
+  scVRich?: number;
+  scVLiver?: number;
+  scVBlood?: number;
+  scVArt?: number;
+  scFBlood?: number;
+  scFFat?: number;
+  scFPoor?: number;
+  scFLiver?: number;
+  scFSkin?: number;
+  fSA_exposed?: number;
+  Height_sc?: number;
+  Height_vs?: number;
+  Falv?: number;
+  PCFat?: number;
+  PCLiver?: number;
+  PCRich?: number;
+  PCPoor?: number;
+  PCSkin_sc?: number;
+  PCSkin?: number;
+  PCAir?: number;
+  kGut?: number;
+  Kp_sc_vs?: number;
+  Km?: number;
+  Michaelis?: number;
+  Vmax?: number;
+  CLH?: number;
+  Ke?: number;
+  fub?: number;
+  Air?: number;
+  Urine?: number;
+  Gut?: number;
+  init_QFat?: number | null;
+  init_QRich?: number | null;
+  init_QPoor?: number | null;
+  init_QLiver?: number | null;
+  init_QMetab?: number | null;
+  init_QGut?: number | null;
+  init_QSkin_u?: number | null;
+  init_QSkin_e?: number | null;
+  init_QSkin_sc_u?: number | null;
+  init_QSkin_sc_e?: number | null;
+  init_QArt?: number | null;
+  init_QVen?: number | null;
+  init_QExcret?: number | null;
+  init_QAir?: number | null;
+  t_start?: number | null;
+  final_time?: number | null;
+  time_unit?: TimeUnit | null;
+  convert_output_time?: boolean | null;
+  output_times?: number[] | null;
+  output_dt?: number | null;
+  max_output_points?: number | null;
+  include_observables?: boolean | null;
+  output_kind?: OutputKind | null;
+  include_parameters?: boolean | null;
+  mass_balance?: boolean | null;
+  validate?: boolean | null;
+  doses?: Dose[] | null;
+  rtol?: number | null;
+  atol?: number | null;
+  max_steps?: number | null;
+  max_step?: number | null;
+  initial_step?: number | null;
+  check_finite?: boolean | null;
+  clamp_nonnegative?: boolean | null;
+  clamp_threshold?: number | null;
+  solver?: SolverMethod | null;
+  jacobian?: JacobianMode | null;
+  mode?: SimulationMode | null;
+  steady_state_tol?: number | null;
+  steady_state_exclude?: string[] | null;
+  steady_state_min_time?: number | null;
+}
+
+export interface SimulationResult {
+  species: Record<SpeciesId, number[]>;
+  time: number[];
+  /** null when the parameters were rejected or include_parameters is false */
+  parameters: SimulationParams | null;
+  /** Filled when include_observables is set */
+  observables: Partial<Record<ObservableId, number[]>>;
+  /** Unit of each species and observable series and of time; null if unknown */
+  units: Record<string, string | null>;
+  /** Filled when output_kind asks for them */
+  amounts: Partial<Record<SpeciesId, number[]>>;
+  concentrations: Partial<Record<SpeciesId, number[]>>;
+  /** Species left in their native representation, and why */
+  notes: string[];
+  steady_state: SteadyState | null;
+  applied_doses: Dose[];
+  events: EventRecord[];
+  /** Whether each event trigger held at the end; continuations start from it */
+  trigger_state: boolean[];
+  steps: number;
+  stats: SolverStats | null;
+  diagnostics: Diagnostics | null;
+  error: SimulationError | null;
+  timing: Timing;
+}
+
+/** Wall-clock milliseconds of the call */
+export interface Timing {
+  solve_ms: number;
+  serialize_ms: number;
+  total_ms: number;
+}
+
+export interface SteadyState {
+  reached: boolean;
+  time: number;
+  rms: number;
+  state: number[];
+}
+
+export interface EventRecord {
+  time: number;
+  id: string;
+  assignments: Partial<Record<SpeciesId, number>>;
+}
+
+export interface SolverStats {
+  steps: number;
+  rhs_evals: number;
+  jacobian_evals: number;
+  newton_iterations: number;
+  newton_failures: number;
+  final_step_size: number;
+}
+
+export interface Diagnostics {
+  total_mass: number[];
+  max_mass_drift: number;
+}
+
+export interface SimulationError {
+  kind: "invalid_params" | "setup" | "solver_failure" | "event_error" | "max_steps_exceeded" | "cancelled";
+  message: string;
+  field: string | null;
+  time: number | null;
+  state: number[] | null;
+  hint: string | null;
+}
+
+/** get_model_metadata */
+export interface ModelMetadata {
+  model_id: string;
+  num_species: number;
+  num_parameters: number;
+  time_units: string;
+  substance_units: string;
+  volume_units: string;
+  sbml: {
+    id: string | null;
+    name: string | null;
+    level: number | null;
+    version: number | null;
+    sha256: string | null;
+  };
+  generator: { name: string; version: string | null; generated_at: string | null };
+  diffsol_version: string;
+  features: ("events" | "sensitivities" | "embedded_sbml")[];
+  observables: { id: ObservableId; units: string | null }[];
+}
+
+/** One entry of get_parameters_info */
+export interface ParameterInfo {
+  id: keyof SimulationParams;
+  name: string | null;
+  units: string | null;
+  description: string | null;
+  default_value: number;
+  required: boolean;
+  is_compartment: boolean;
+  min: number | null;
+  max: number | null;
+}
+
+/** One entry of get_species_info */
+export interface SpeciesInfo {
+  id: SpeciesId;
+  initial_amount: number;
+  override: keyof SimulationParams;
+  units: string | null;
+  compartment: string | null;
+  compartment_volume: number | null;
+  is_amount: boolean;
+  is_boundary: boolean;
+}
+
+/** One entry of get_compartments_info */
+export interface CompartmentInfo {
+  id: string;
+  name: string | null;
+  size: number | null;
+  formula: string | null;
+  units: string | null;
+  species: SpeciesId[];
+}
+
+/** get_model_equations */
+export interface ModelEquations {
+  derivatives: Record<SpeciesId, string>;
+  assignment_rules: Record<string, string>;
+}
+"#;
+
+pub fn get_types_ts() -> String {
+    TYPES_TS.to_string()
+}
+
@@ -0,0 +1,751 @@
+// Generated native Rust code from SBML model: event_dose
+// Uses SymPy CSE for optimized derivatives and Jacobian
+
+use serde::{Deserialize, Serialize};
+use wasm_pk_core::*;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulationParams {
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub ka: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub ke: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Dose: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Vd: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Mr: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub c: f64,
+
+    // Initial amounts (optional, for runtime dosing)
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Gut: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Cp: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Urine: Option<f64>,
+    /// Times, outputs, doses and solver settings, the same for every model
+    #[serde(flatten)]
+    pub options: SimulationOptions,
+}
+
+/// Seconds per model time unit, if the SBML declares its time units
+const MODEL_TIME_SECONDS: Option<f64> = Some(3600.0);
+/// SBML id of the model's time units
+const MODEL_TIME_UNIT: Option<&str> = Some("HR");
+
+/// Model time units per time_unit (1 when it is not given)
+fn time_scale(sim_params: &SimulationParams) -> f64 {
+    sim_params.options.time_scale(&MODEL)
+}
+
+/// The parameters with every time and step size in model time units
+fn in_model_time(mut sim_params: SimulationParams) -> SimulationParams {
+    let scale = time_scale(&sim_params);
+    sim_params.options = sim_params.options.in_model_time(scale);
+    sim_params
+}
+
+/// The parameters with the defaults a run uses for its times and solver options
+fn with_defaults(mut sim_params: SimulationParams) -> SimulationParams {
+    sim_params.options = sim_params.options.with_defaults(&MODEL);
+    sim_params
+}
+
+pub fn run_simulation(params: &str) -> String {
+    wasm_pk_core::simulate::<EventDoseModel>(params)
+}
+
+/// Run one simulation per parameter object; each result carries its own error
+pub fn run_simulation_batch(params_list: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_batch::<EventDoseModel>(params_list)
+}
+
+/// Names of the solver parameter vector entries, in index order
+const PARAMETER_ORDER: &[&str] = &["ka", "ke", "Dose", "Vd", "Mr", "c", "Vtot", "init_Gut", "init_Cp", "init_Urine"];
+
+/// Index mapping of the solver parameter vector: model parameters, derived
+/// parameter values, then the initial state as init_<species> (JSON array)
+pub fn get_parameter_order() -> String {
+    serde_json::to_string(PARAMETER_ORDER).unwrap()
+}
+
+/// Resolve one run's parameters into the solver parameter vector
+fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+    let ka = sim_params.ka;
+    let ke = sim_params.ke;
+    let Dose = sim_params.Dose;
+    let Vd = sim_params.Vd;
+    let Mr = sim_params.Mr;
+    let c = sim_params.c;
+    let Vtot = 2.0*Vd;
+
+    vec![
+        ka,
+        ke,
+        Dose,
+        Vd,
+        Mr,
+        c,
+        Vtot,
+        sim_params.init_Gut.unwrap_or(0.0),
+        sim_params.init_Cp.unwrap_or(0.0),
+        sim_params.init_Urine.unwrap_or(0.0),
+    ]
+}
+
+/// Range of each checked entry of p, with the parameters a derived value reads
+const PARAMETER_CHECKS: &[ParameterCheck] = &[
+    ("ka", 0, ParameterRange::Unknown, &[]),
+    ("ke", 1, ParameterRange::Unknown, &[]),
+    ("Dose", 2, ParameterRange::Unknown, &[]),
+    ("Vd", 3, ParameterRange::Unknown, &[]),
+    ("Mr", 4, ParameterRange::Unknown, &[]),
+    ("c", 5, ParameterRange::NonNegative, &[]),
+];
+
+/// Check parameters without running: `{errors, warnings}`, each `{field, message}` (JSON)
+pub fn validate_parameters(params: &str) -> String {
+    wasm_pk_core::validate_parameters::<EventDoseModel>(params)
+}
+
+/// The model as a type, for code generic over models; see PkModel
+pub struct EventDoseModel;
+
+impl PkModel for EventDoseModel {
+    type Params = SimulationParams;
+    const N_STATES: usize = 3;
+    const INFO: ModelInfo = MODEL;
+
+    fn rhs(y: &[f64], p: &[f64], _t: f64, _windows: &[UptakeWindow], dy: &mut [f64]) {
+        // Parameters from p
+        let ka = p[0];
+        let ke = p[1];
+
+        // Map species names to y indices
+        let Gut = y[0];
+        let Cp = y[1];
+
+        // Temporary variables (CSE)
+        let x0 = Cp*ke;
+
+        // Derivatives
+        dy[0] = -Gut*ka;
+        dy[1] = Gut*ka - x0;
+        dy[2] = x0;
+    }
+
+    fn jac(_y: &[f64], p: &[f64], _t: f64, v: &[f64], jv: &mut [f64]) {
+        jv.fill(0.0);
+
+        // Parameters from p
+        let ka = p[0];
+        let ke = p[1];
+
+        // Map species names to y indices
+
+
+        // Temporary variables (CSE)
+        let x1 = ka;
+        let x2 = ke;
+
+        // Jacobian-Vector Product
+        jv[0] += (-x1) * v[0];
+        jv[1] += (x1) * v[0];
+        jv[1] += (-x2) * v[1];
+        jv[2] += (x2) * v[1];
+    }
+
+    fn init(p: &[f64], _t: f64, y: &mut [f64]) {
+        y.copy_from_slice(&p[7..10]);
+    }
+    fn defaults() -> serde_json::Value {
+        serde_json::from_str(&get_default_parameters()).unwrap()
+    }
+
+    fn options(sim_params: &SimulationParams) -> &SimulationOptions {
+        &sim_params.options
+    }
+
+    fn options_mut(sim_params: &mut SimulationParams) -> &mut SimulationOptions {
+        &mut sim_params.options
+    }
+
+    fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+        parameter_vector(sim_params)
+    }
+
+    fn build_problem() -> BuiltProblem<'static> {
+        BuiltProblem::dense(MODEL, equations())
+    }
+
+    fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+        prepare_run(sim_params)
+    }
+}
+
+/// The run of checked parameters: the solver works in model time units, while
+/// results and checkpoints keep the caller's
+fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+    let resolved = serde_json::to_value(with_defaults(sim_params.clone())).unwrap();
+    let scale = time_scale(&sim_params);
+    let sim_params = in_model_time(sim_params);
+    let p = parameter_vector(&sim_params);
+    Ok(Run {
+        p,
+        resolved,
+        scale,
+        uptake_windows: vec![],
+        options: sim_params.options,
+    })
+}
+
+/// The model's functions for the solver; the closures capture nothing, so they
+/// are plain function pointers
+fn equations() -> Equations<'static> {
+    let root_fn = |_y: &[f64], _p: &[f64], t: f64, roots: &mut [f64]| {
+        // Event dose1: t >= 2
+        roots[0] = (t - 2.0);
+    };
+
+    let eval_triggers = |_y: &[f64], t: f64, _p: &[f64]| -> Vec<bool> {
+        vec![t >= 2.0]
+    };
+
+    let apply_events = |y: &[f64], _t: f64, fire: &[bool], p: &[f64]| -> Vec<f64> {
+        let Dose = p[2];
+        let Gut = y[0];
+        let mut y_new = y.to_vec();
+        // Event: dose1
+        if fire[0] {
+            y_new[0] = Dose + Gut;
+        }
+        y_new
+    };
+
+    let eval_observables = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let Vtot = p[6];
+        vec![Vtot]
+    };
+
+    let eval_volumes = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let c = p[5];
+        vec![c, c, c]
+    };
+
+    let eval_mass = |y: &[f64], _p: &[f64]| -> f64 {
+        y[0] + y[1] + y[2]
+    };
+
+    Equations {
+        rhs: EventDoseModel::rhs,
+        jac: EventDoseModel::jac,
+        init: EventDoseModel::init,
+        roots: root_fn,
+        triggers: eval_triggers,
+        apply_events,
+        observables: eval_observables,
+        volumes: eval_volumes,
+        mass: eval_mass,
+        jac_matrix: None,
+    }
+}
+
+/// Solver stats of the last run of the last simulation call (JSON, null if it never started)
+pub fn get_last_run_stats() -> String {
+    LAST_RUN_STATS.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+pub fn run_simulation_at_times(params: &str, times: &[f64]) -> String {
+    wasm_pk_core::run_simulation_at_times::<EventDoseModel>(params, times)
+}
+
+/// Run a simulation and return `time,<species...>` CSV, one row per stored time
+pub fn run_simulation_csv(params: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_csv::<EventDoseModel>(params)
+}
+
+/// Cmax, Tmax, AUC, AUC(0-inf), terminal half-life and trough of one species (JSON)
+pub fn compute_pk_metrics(result_json: &str, species: &str) -> Result<String, String> {
+    wasm_pk_core::compute_pk_metrics(result_json, species)
+}
+
+/// AUC of one species over each `[t1, t2]` window (JSON array, same order)
+pub fn compute_partial_auc(result_json: &str, species: &str, windows: &str) -> Result<String, String> {
+    wasm_pk_core::compute_partial_auc(result_json, species, windows)
+}
+
+/// Run the model once per value of one parameter, e.g. body weight from 20 to 100 kg
+pub fn run_parameter_sweep(base_params: &str, sweep: &str) -> Result<String, String> {
+    wasm_pk_core::run_parameter_sweep::<EventDoseModel>(base_params, sweep)
+}
+
+/// Continue a run_simulation result from its last recorded state for `additional_time`,
+/// with `new_params` merged over the parameters that run used. Its doses, output_times
+/// and uptake windows are not repeated; times in `new_params` count from the
+/// continuation start. The segment is returned on the combined time axis, after
+/// the earlier trajectory when `concatenate` is set.
+pub fn continue_simulation(previous_result: &str, new_params: &str, additional_time: f64, concatenate: bool) -> Result<String, String> {
+    wasm_pk_core::continue_simulation::<EventDoseModel>(previous_result, new_params, additional_time, concatenate)
+}
+
+/// Checkpoint of the last run of the last simulation call (JSON, null if it never
+/// started or its final state is not finite)
+pub fn export_checkpoint() -> String {
+    LAST_CHECKPOINT.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+/// Resume an exported checkpoint up to the absolute `final_time` in `params`, which are
+/// merged over the checkpoint's parameters. Its pending doses are applied unless
+/// `params` gives new doses. The result and the new checkpoint use absolute time.
+pub fn run_from_checkpoint(checkpoint: &str, params: &str) -> Result<String, String> {
+    wasm_pk_core::run_from_checkpoint::<EventDoseModel>(checkpoint, params)
+}
+
+const SPECIES_IDS: &[&str] = &["Gut", "Cp", "Urine"];
+
+/// SBML ids of the events, in root function order
+const EVENT_IDS: [&str; 1] = ["dose1"];
+/// State indices assigned by each event
+const EVENT_TARGETS: [&[usize]; 1] = [&[0]];
+/// Value of each trigger before the start, so a false one fires there if it holds
+const EVENT_INITIAL_VALUES: [bool; 1] = [true];
+/// Whether each event still fires when events firing with it make its trigger false
+const EVENT_PERSISTENT: [bool; 1] = [true];
+
+/// Parameters read by the derivative of each state, for failure hints
+const STATE_PARAMETERS: [&[&str]; 3] = [&["ka"], &["ka", "ke"], &["ke"]];
+
+/// SBML ids of the assignment-rule observables, in evaluation order
+const OBSERVABLE_IDS: [&str; 1] = ["Vtot"];
+
+/// What the shared solver loop and option checks need to know about the model
+const MODEL: ModelInfo = ModelInfo {
+    species: SPECIES_IDS,
+    state_parameters: &STATE_PARAMETERS,
+    event_ids: &EVENT_IDS,
+    event_targets: &EVENT_TARGETS,
+    event_initial_values: &EVENT_INITIAL_VALUES,
+    event_persistent: &EVENT_PERSISTENT,
+    observables: &OBSERVABLE_IDS,
+    species_is_amount: &SPECIES_IS_AMOUNT,
+    species_volumes: &SPECIES_VOLUMES,
+    series_units: &SERIES_UNITS,
+    time_seconds: MODEL_TIME_SECONDS,
+    time_unit: MODEL_TIME_UNIT,
+    parameters: PARAMETER_ORDER,
+    parameter_checks: PARAMETER_CHECKS,
+    algebraic: &[],
+};
+
+/// Run a simulation and return the trajectory as little-endian f64 bytes
+pub fn run_simulation_binary(params: &str) -> Result<Vec<u8>, String> {
+    wasm_pk_core::run_simulation_binary::<EventDoseModel>(params)
+}
+
+/// Describe the run_simulation_binary layout and its species order (JSON)
+pub fn get_binary_layout() -> String {
+    wasm_pk_core::get_binary_layout::<EventDoseModel>()
+}
+
+/// Decode run_simulation_binary output into times and named species series
+pub fn decode_binary_result(bytes: &[u8]) -> Result<(Vec<f64>, OrderedSeries), String> {
+    wasm_pk_core::decode_binary_result::<EventDoseModel>(bytes)
+}
+
+/// One observable of get_model_metadata
+#[derive(Serialize)]
+struct ObservableInfo {
+    id: &'static str,
+    units: Option<&'static str>,
+}
+
+/// SBML document the model was generated from
+#[derive(Serialize)]
+struct SbmlSource {
+    id: Option<&'static str>,
+    name: Option<&'static str>,
+    level: Option<u32>,
+    version: Option<u32>,
+    /// SHA-256 of the source file
+    sha256: Option<&'static str>,
+}
+
+/// Generator that wrote this module
+#[derive(Serialize)]
+struct GeneratorInfo {
+    name: &'static str,
+    version: Option<&'static str>,
+    /// ISO 8601 UTC time of generation
+    generated_at: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct ModelMetadata {
+    model_id: &'static str,
+    num_species: usize,
+    num_parameters: usize,
+    time_units: &'static str,
+    substance_units: &'static str,
+    volume_units: &'static str,
+    sbml: SbmlSource,
+    generator: GeneratorInfo,
+    diffsol_version: &'static str,
+    /// Optional features compiled in: "events", "sensitivities", "embedded_sbml"
+    features: &'static [&'static str],
+    observables: &'static [ObservableInfo],
+}
+
+pub fn get_model_metadata() -> String {
+    let metadata = ModelMetadata {
+        model_id: "event_dose",
+        num_species: 3,
+        num_parameters: 6,
+        time_units: "HR",
+        substance_units: "MilliMOL",
+        volume_units: "L",
+        sbml: SbmlSource { id: Some("event_dose"), name: None, level: None, version: None, sha256: None },
+        generator: GeneratorInfo { name: "sbml_rust_generator", version: Some("1.0.0"), generated_at: Some("2026-01-01T00:00:00Z") },
+        diffsol_version: "0.6.3",
+        features: &["events"],
+        observables: &[
+            ObservableInfo { id: "Vtot", units: None },
+        ],
+    };
+    serde_json::to_string(&metadata).unwrap()
+}
+
+/// One entry of get_parameters_info
+#[derive(Serialize)]
+struct ParameterInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    units: Option<&'static str>,
+    /// SBML notes as plain text
+    description: Option<&'static str>,
+    default_value: f64,
+    required: bool,
+    is_compartment: bool,
+    /// Soft bounds for input forms; validate_parameters has the hard checks
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+const PARAMETER_INFO: &[ParameterInfo] = &[
+    ParameterInfo { id: "ka", name: None, units: None, description: None, default_value: 1.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "ke", name: None, units: None, description: None, default_value: 0.2, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Dose", name: None, units: None, description: None, default_value: 10.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Vd", name: None, units: None, description: None, default_value: 5.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Mr", name: None, units: None, description: None, default_value: 300.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "c", name: None, units: None, description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+];
+
+pub fn get_parameters_info() -> String {
+    serde_json::to_string(PARAMETER_INFO).unwrap()
+}
+
+/// One entry of get_species_info
+#[derive(Serialize)]
+struct SpeciesInfo {
+    id: &'static str,
+    initial_amount: f64,
+    #[serde(rename = "override")]
+    override_field: &'static str,
+    units: Option<&'static str>,
+    compartment: Option<&'static str>,
+    compartment_volume: Option<f64>,
+    /// False when the SBML value is a concentration (hasOnlySubstanceUnits=false)
+    is_amount: bool,
+    is_boundary: bool,
+}
+
+pub fn get_species_info() -> String {
+    let p = parameter_vector(&EventDoseModel::default_params());
+    let species: &[SpeciesInfo] = &[
+        SpeciesInfo { id: "Gut", initial_amount: 0.0, override_field: "init_Gut", units: None, compartment: Some("c"), compartment_volume: Some(p[5]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Cp", initial_amount: 0.0, override_field: "init_Cp", units: None, compartment: Some("c"), compartment_volume: Some(p[5]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "Urine", initial_amount: 0.0, override_field: "init_Urine", units: None, compartment: Some("c"), compartment_volume: Some(p[5]), is_amount: false, is_boundary: false },
+    ];
+    serde_json::to_string(&species).unwrap()
+}
+
+/// Whether each species' values are amounts rather than concentrations
+const SPECIES_IS_AMOUNT: [bool; 3] = [false, false, false];
+/// Compartment converting each species between amount and concentration, or why none does
+const SPECIES_VOLUMES: [Result<&str, &str>; 3] = [Ok("c"), Ok("c"), Ok("c")];
+
+/// Unit of each species and observable series; None where the SBML gives none
+const SERIES_UNITS: [(&str, Option<&str>); 4] = [
+    ("Gut", None),
+    ("Cp", None),
+    ("Urine", None),
+    ("Vtot", None),
+];
+
+/// One entry of get_compartments_info
+#[derive(Serialize)]
+struct CompartmentInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    /// Size at the default parameters; None when it changes during a run
+    size: Option<f64>,
+    /// Rule or initial assignment computing the size, e.g. "BM*scVLiver"
+    formula: Option<&'static str>,
+    units: Option<&'static str>,
+    species: &'static [&'static str],
+}
+
+pub fn get_compartments_info() -> String {
+    let p = parameter_vector(&EventDoseModel::default_params());
+    let compartments: &[CompartmentInfo] = &[
+        CompartmentInfo { id: "c", name: None, size: Some(p[5]), formula: None, units: None, species: &["Gut", "Cp", "Urine"] },
+    ];
+    serde_json::to_string(&compartments).unwrap()
+}
+
+pub fn get_default_parameters() -> String {
+    let defaults: &[(&str, f64)] = &[
+        ("ka", 1.0),
+        ("ke", 0.2),
+        ("Dose", 10.0),
+        ("Vd", 5.0),
+        ("Mr", 300.0),
+        ("c", 1.0),
+        ("final_time", 24.0),
+    ];
+    let defaults: serde_json::Map<String, serde_json::Value> = defaults.iter().map(|&(id, value)| (id.to_string(), value.into())).collect();
+    serde_json::Value::Object(defaults).to_string()
+}
+/// Equations in model order, serialized as a JSON object
+struct EquationTable(&'static [(&'static str, &'static str)]);
+
+impl Serialize for EquationTable {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (id, expression) in self.0 {
+            map.serialize_entry(id, expression)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Serialize)]
+struct ModelEquations {
+    derivatives: EquationTable,
+    assignment_rules: EquationTable,
+}
+
+pub fn get_model_equations() -> String {
+    let equations = ModelEquations {
+        derivatives: EquationTable(&[
+            ("Gut", "-Gut*ka"),
+            ("Cp", "-Cp*ke + Gut*ka"),
+            ("Urine", "Cp*ke"),
+        ]),
+        assignment_rules: EquationTable(&[
+            ("Vtot", "2*Vd"),
+        ]),
+    };
+    serde_json::to_string_pretty(&equations).unwrap()
+}
+/// SBML document this module was generated from
+pub fn get_model_sbml() -> Result<String, String> {
+    Err("this model was not generated from an SBML file".to_string())
+}
+/// TypeScript definitions of the parameters, result and metadata of this model
+const TYPES_TS: &str = r#"// TypeScript definitions for event_dose, generated with the module
+
+export type SpeciesId = "Gut" | "Cp" | "Urine";
+export type ObservableId = "Vtot";
+export type SolverMethod = "bdf" | "tr_bdf2";
+export type JacobianMode = "analytic" | "finite_difference";
+export type SimulationMode = "transient" | "steady_state";
+export type TimeUnit = "s" | "min" | "h" | "d";
+export type OutputKind = "amount" | "concentration" | "both";
+
+export interface Dose {
+  time: number;
+  amount: number;
+  target: SpeciesId;
+  units?: string | null;
+}
+
+/** Input of run_simulation; omitted fields take the model defaults */
+export interface SimulationParams {
+  ka?: number;
+  ke?: number;
+  Dose?: number;
+  Vd?: number;
+  Mr?: number;
+  c?: number;
+  init_Gut?: number | null;
+  init_Cp?: number | null;
+  init_Urine?: number | null;
+  t_start?: number | null;
+  final_time?: number | null;
+  time_unit?: TimeUnit | null;
+  convert_output_time?: boolean | null;
+  output_times?: number[] | null;
+  output_dt?: number | null;
+  max_output_points?: number | null;
+  include_observables?: boolean | null;
+  output_kind?: OutputKind | null;
+  include_parameters?: boolean | null;
+  mass_balance?: boolean | null;
+  validate?: boolean | null;
+  doses?: Dose[] | null;
+  rtol?: number | null;
+  atol?: number | null;
+  max_steps?: number | null;
+  max_step?: number | null;
+  initial_step?: number | null;
+  check_finite?: boolean | null;
+  clamp_nonnegative?: boolean | null;
+  clamp_threshold?: number | null;
+  solver?: SolverMethod | null;
+  jacobian?: JacobianMode | null;
+  mode?: SimulationMode | null;
+  steady_state_tol?: number | null;
+  steady_state_exclude?: string[] | null;
+  steady_state_min_time?: number | null;
+}
+
+export interface SimulationResult {
+  species: Record<SpeciesId, number[]>;
+  time: number[];
+  /** null when the parameters were rejected or include_parameters is false */
+  parameters: SimulationParams | null;
+  /** Filled when include_observables is set */
+  observables: Partial<Record<ObservableId, number[]>>;
+  /** Unit of each species and observable series and of time; null if unknown */
+  units: Record<string, string | null>;
+  /** Filled when output_kind asks for them */
+  amounts: Partial<Record<SpeciesId, number[]>>;
+  concentrations: Partial<Record<SpeciesId, number[]>>;
+  /** Species left in their native representation, and why */
+  notes: string[];
+  steady_state: SteadyState | null;
+  applied_doses: Dose[];
+  events: EventRecord[];
+  /** Whether each event trigger held at the end; continuations start from it */
+  trigger_state: boolean[];
+  steps: number;
+  stats: SolverStats | null;
+  diagnostics: Diagnostics | null;
+  error: SimulationError | null;
+  timing: Timing;
+}
+
+/** Wall-clock milliseconds of the call */
+export interface Timing {
+  solve_ms: number;
+  serialize_ms: number;
+  total_ms: number;
+}
+
+export interface SteadyState {
+  reached: boolean;
+  time: number;
+  rms: number;
+  state: number[];
+}
+
+export interface EventRecord {
+  time: number;
+  id: string;
+  assignments: Partial<Record<SpeciesId, number>>;
+}
+
+export interface SolverStats {
+  steps: number;
+  rhs_evals: number;
+  jacobian_evals: number;
+  newton_iterations: number;
+  newton_failures: number;
+  final_step_size: number;
+}
+
+export interface Diagnostics {
+  total_mass: number[];
+  max_mass_drift: number;
+}
+
+export interface SimulationError {
+  kind: "invalid_params" | "setup" | "solver_failure" | "event_error" | "max_steps_exceeded" | "cancelled";
+  message: string;
+  field: string | null;
+  time: number | null;
+  state: number[] | null;
+  hint: string | null;
+}
+
+/** get_model_metadata */
+export interface ModelMetadata {
+  model_id: string;
+  num_species: number;
+  num_parameters: number;
+  time_units: string;
+  substance_units: string;
+  volume_units: string;
+  sbml: {
+    id: string | null;
+    name: string | null;
+    level: number | null;
+    version: number | null;
+    sha256: string | null;
+  };
+  generator: { name: string; version: string | null; generated_at: string | null };
+  diffsol_version: string;
+  features: ("events" | "sensitivities" | "embedded_sbml")[];
+  observables: { id: ObservableId; units: string | null }[];
+}
+
+/** One entry of get_parameters_info */
+export interface ParameterInfo {
+  id: keyof SimulationParams;
+  name: string | null;
+  units: string | null;
+  description: string | null;
+  default_value: number;
+  required: boolean;
+  is_compartment: boolean;
+  min: number | null;
+  max: number | null;
+}
+
+/** One entry of get_species_info */
+export interface SpeciesInfo {
+  id: SpeciesId;
+  initial_amount: number;
+  override: keyof SimulationParams;
+  units: string | null;
+  compartment: string | null;
+  compartment_volume: number | null;
+  is_amount: boolean;
+  is_boundary: boolean;
+}
+
+/** One entry of get_compartments_info */
+export interface CompartmentInfo {
+  id: string;
+  name: string | null;
+  size: number | null;
+  formula: string | null;
+  units: string | null;
+  species: SpeciesId[];
+}
+
+/** get_model_equations */
+export interface ModelEquations {
+  derivatives: Record<SpeciesId, string>;
+  assignment_rules: Record<string, string>;
+}
+"#;
+
+pub fn get_types_ts() -> String {
+    TYPES_TS.to_string()
+}
+
@@ -0,0 +1,1406 @@
+// Generated WASM-compatible Rust code from SBML model: oral_window
+// Uses SymPy CSE for optimized derivatives and Jacobian
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use wasm_pk_core::*;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulationParams {
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Kabs: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub t0: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Kelm: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub EoA_O: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub D_o: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub vplasma: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub period_O: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub n_O: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub comp1: f64,
+
+    // Initial amounts (optional, for runtime dosing)
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_Aplasma: Option<f64>,
+    pub uptake_windows: Option<Vec<UptakeWindow>>,
+    /// Times, outputs, doses and solver settings, the same for every model
+    #[serde(flatten)]
+    pub options: SimulationOptions,
+}
+
+/// Seconds per model time unit, if the SBML declares its time units
+const MODEL_TIME_SECONDS: Option<f64> = None;
+/// SBML id of the model's time units
+const MODEL_TIME_UNIT: Option<&str> = None;
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, delay: i32);
+}
+
+/// Set what the model logs: "off", "error", "info" (run starts, ignored doses) or
+/// "debug" (also batch progress, the steps of each run and every event firing)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn set_log_level(level: &str) -> Result<(), String> {
+    wasm_pk_core::set_log_level(level)
+}
+
+/// Runs when the module is instantiated, so a panic logs its message and location
+/// with console.error rather than only trapping as "unreachable"
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen(start)]
+pub fn init_panic_hook() {
+    console_error_panic_hook::set_once();
+}
+
+/// Model time units per time_unit (1 when it is not given)
+fn time_scale(sim_params: &SimulationParams) -> f64 {
+    sim_params.options.time_scale(&MODEL)
+}
+
+/// The parameters with every time and step size in model time units
+fn in_model_time(mut sim_params: SimulationParams) -> SimulationParams {
+    let scale = time_scale(&sim_params);
+    sim_params.options = sim_params.options.in_model_time(scale);
+    for window in sim_params.uptake_windows.iter_mut().flatten() {
+        window.t0 *= scale;
+        window.t1 *= scale;
+    }
+    sim_params
+}
+
+/// The parameters with the defaults a run uses for its times and solver options
+fn with_defaults(mut sim_params: SimulationParams) -> SimulationParams {
+    sim_params.options = sim_params.options.with_defaults(&MODEL);
+    sim_params
+}
+
+/// The uptake windows of a run in model time units: its uptake_windows, or the
+/// model's pulse repeated as its parameters ask
+fn uptake_windows(sim_params: &SimulationParams, p: &[f64]) -> Result<Vec<UptakeWindow>, SimulationError> {
+    match &sim_params.uptake_windows {
+        Some(windows) => Ok(windows.clone()),
+        None => repeated_windows(p[1], 0.0003, ("n_O", p[7]), ("period_O", p[6])),
+    }
+}
+
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_simulation(params: &str) -> String {
+    wasm_pk_core::simulate::<OralWindowModel>(params)
+}
+
+/// Run one simulation per parameter object; each result carries its own error
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_simulation_batch(params_list: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_batch::<OralWindowModel>(params_list)
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub fn run_simulation_js(params: JsValue) -> Result<JsValue, JsValue> {
+    let call_start = now_ms();
+    let sim_params = params_from_js(params)?;
+    result_to_js(OralWindowModel::simulate_run(sim_params), call_start)
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+fn params_from_js(params: JsValue) -> Result<SimulationParams, JsValue> {
+    let overrides: serde_json::Map<String, serde_json::Value> =
+        serde_wasm_bindgen::from_value(params).map_err(|e| JsError::new(&e.to_string()))?;
+    resolve_params::<OralWindowModel>(overrides).map_err(JsValue::from)
+}
+
+/// Call `callback(fraction, steps_taken)` while simulations run, `fraction` going from 0
+/// at t_start to 1 at final_time: at most once per `interval_ms` (default 100), or
+/// every `every_steps` solver steps if given. Returning `false` cancels the run, which
+/// then fails with a `cancelled` error at the time reached.
+/// Applies to every entry point, per run in batches; pass no callback to remove it.
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub fn set_progress_callback(callback: Option<js_sys::Function>, interval_ms: Option<f64>, every_steps: Option<usize>) {
+    wasm_pk_core::set_progress_callback(callback, interval_ms, every_steps)
+}
+
+/// Solver steps between yields of run_simulation_async
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+const DEFAULT_CHUNK_STEPS: usize = 1000;
+
+/// run_simulation_js without blocking the event loop: every `chunk_steps` solver
+/// steps (default 1000) the run waits for the next macrotask. Resolves to the
+/// result object; rejects on invalid parameters.
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub async fn run_simulation_async(params: JsValue, chunk_steps: Option<usize>) -> Result<JsValue, JsValue> {
+    let call_start = now_ms();
+    let sim_params = params_from_js(params)?;
+    let chunk_steps = chunk_steps.unwrap_or(DEFAULT_CHUNK_STEPS).max(1);
+    let mut reporter = ProgressReporter::default();
+    let mut yielded_at = 0;
+    let pause = move |fraction: f64, steps: usize| {
+        let go_on = reporter.report(fraction, steps);
+        let due = steps >= yielded_at + chunk_steps;
+        if due {
+            yielded_at = steps;
+        }
+        async move {
+            if due {
+                next_tick().await;
+            }
+            go_on
+        }
+    };
+    let result = OralWindowModel::build_problem().solve(vec![prepare_run(sim_params)], |_| {}, pause).await.pop().unwrap();
+    result_to_js(result, call_start)
+}
+
+/// Resolve on the next macrotask, after the browser has had a chance to render
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+async fn next_tick() {
+    let tick = js_sys::Promise::new(&mut |resolve, _| set_timeout(&resolve, 0));
+    // The promise only ever resolves
+    let _ = wasm_bindgen_futures::JsFuture::from(tick).await;
+}
+
+/// Simulation result kept in WASM memory, read as typed arrays
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub struct SimulationHandle {
+    result: SimulationResult,
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+impl SimulationHandle {
+    /// Stored output times (Float64Array)
+    pub fn times(&self) -> Vec<f64> {
+        self.result.time.clone()
+    }
+
+    /// Trajectory of one species by SBML id (Float64Array)
+    pub fn species(&self, name: &str) -> Result<Vec<f64>, JsError> {
+        self.result
+            .species
+            .get(name)
+            .cloned()
+            .ok_or_else(|| JsError::new(&format!("unknown species {}", name)))
+    }
+
+    /// Species ids in SBML order
+    pub fn species_names(&self) -> Vec<String> {
+        self.result.species.0.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Solver failure message, if integration stopped early
+    pub fn error(&self) -> Option<String> {
+        self.result.error.as_ref().map(|e| e.message.clone())
+    }
+}
+
+/// Fast path for large trajectories: no JSON in either direction
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub fn run_simulation_typed(params: JsValue) -> Result<SimulationHandle, JsValue> {
+    let sim_params = params_from_js(params)?;
+    Ok(SimulationHandle { result: OralWindowModel::simulate_run(sim_params) })
+}
+
+/// Parameters resolved and a problem built once for repeated runs, e.g. behind a
+/// dashboard slider
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub struct ModelSession {
+    session: Session<OralWindowModel>,
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[wasm_bindgen]
+impl ModelSession {
+    /// Start from a parameter object merged over the defaults, as run_simulation_js does
+    #[wasm_bindgen(constructor)]
+    pub fn new(params: JsValue) -> Result<ModelSession, JsValue> {
+        Ok(ModelSession { session: Session::new(params_from_js(params)?)? })
+    }
+
+    /// Change one numeric parameter or option; rejected values leave the session unchanged
+    pub fn set_parameter(&mut self, name: &str, value: f64) -> Result<(), JsValue> {
+        Ok(self.session.set_parameter(name, value)?)
+    }
+
+    pub fn set_final_time(&mut self, final_time: f64) -> Result<(), JsValue> {
+        self.set_parameter("final_time", final_time)
+    }
+
+    /// Run with the current parameters; the session can be run again
+    pub fn run(&mut self) -> SimulationHandle {
+        SimulationHandle { result: self.session.run() }
+    }
+}
+
+/// run_simulation for C callers: `params_json` is a NUL-terminated JSON string and
+/// the result JSON is released with wasm_pk_free_string.
+///
+/// # Safety
+/// `params_json` must be null or point to a NUL-terminated string.
+#[cfg(feature = "c-api")]
+#[no_mangle]
+pub unsafe extern "C" fn wasm_pk_run_simulation(params_json: *const std::ffi::c_char) -> *mut std::ffi::c_char {
+    let call_start = now_ms();
+    let params = if params_json.is_null() {
+        Err("params_json is null".to_string())
+    } else {
+        unsafe { std::ffi::CStr::from_ptr(params_json) }.to_str().map_err(|e| format!("params_json: {}", e))
+    };
+    let json = match params {
+        Ok(params) => run_simulation(params),
+        Err(e) => result_to_json(failed_result(SimulationError::invalid(e)), call_start),
+    };
+    into_c_string(json)
+}
+
+/// get_default_parameters for C callers; release the string with wasm_pk_free_string
+#[cfg(feature = "c-api")]
+#[no_mangle]
+pub extern "C" fn wasm_pk_get_default_parameters() -> *mut std::ffi::c_char {
+    into_c_string(get_default_parameters())
+}
+
+/// Release a string returned by a wasm_pk_ function; null is ignored
+///
+/// # Safety
+/// `s` must be null or a string from this library that was not released yet.
+#[cfg(feature = "c-api")]
+#[no_mangle]
+pub unsafe extern "C" fn wasm_pk_free_string(s: *mut std::ffi::c_char) {
+    if !s.is_null() {
+        drop(unsafe { std::ffi::CString::from_raw(s) });
+    }
+}
+
+#[cfg(feature = "c-api")]
+fn into_c_string(json: String) -> *mut std::ffi::c_char {
+    // serde_json escapes NUL, so the JSON has no interior one
+    std::ffi::CString::new(json).unwrap().into_raw()
+}
+
+#[cfg(all(feature = "wasm", feature = "wasi"))]
+compile_error!("features `wasm` and `wasi` export the same names; build wasi with --no-default-features");
+
+/// Exports of the `wasi` feature: UTF-8 strings as pointer and length in linear memory.
+/// Every returned buffer, and every wasm_pk_alloc one, is released with wasm_pk_free.
+#[cfg(feature = "wasi")]
+mod wasi {
+    /// Buffer of `len` bytes for the host to write input into
+    #[no_mangle]
+    pub extern "C" fn wasm_pk_alloc(len: usize) -> *mut u8 {
+        Box::into_raw(vec![0u8; len].into_boxed_slice()) as *mut u8
+    }
+
+    /// Release a buffer from wasm_pk_alloc or a returned one; null is ignored
+    ///
+    /// # Safety
+    /// `ptr` must be null or a buffer of exactly `len` bytes from this module that was
+    /// not released yet.
+    #[no_mangle]
+    pub unsafe extern "C" fn wasm_pk_free(ptr: *mut u8, len: usize) {
+        if !ptr.is_null() {
+            drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) });
+        }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    unsafe fn into_buffer(json: String, out_len: *mut usize) -> *mut u8 {
+        let bytes = json.into_bytes().into_boxed_slice();
+        unsafe { *out_len = bytes.len() };
+        Box::into_raw(bytes) as *mut u8
+    }
+
+    /// # Safety
+    /// `ptr` must be null or point to `len` readable bytes.
+    unsafe fn read_str<'a>(ptr: *const u8, len: usize) -> Result<&'a str, String> {
+        if ptr.is_null() {
+            return Err("params is null".to_string());
+        }
+        std::str::from_utf8(unsafe { std::slice::from_raw_parts(ptr, len) }).map_err(|e| format!("params: {}", e))
+    }
+
+    /// run_simulation on the `params_len` bytes of JSON at `params`
+    ///
+    /// # Safety
+    /// `params` must be null or point to `params_len` readable bytes, and `out_len` to
+    /// writable memory.
+    #[no_mangle]
+    pub unsafe extern "C" fn run_simulation(params: *const u8, params_len: usize, out_len: *mut usize) -> *mut u8 {
+        let call_start = super::now_ms();
+        let json = match unsafe { read_str(params, params_len) } {
+            Ok(params) => super::run_simulation(params),
+            Err(e) => super::result_to_json(super::failed_result(super::SimulationError::invalid(e)), call_start),
+        };
+        unsafe { into_buffer(json, out_len) }
+    }
+
+    /// validate_parameters on the `params_len` bytes of JSON at `params`
+    ///
+    /// # Safety
+    /// As for run_simulation.
+    #[no_mangle]
+    pub unsafe extern "C" fn validate_parameters(params: *const u8, params_len: usize, out_len: *mut usize) -> *mut u8 {
+        let json = match unsafe { read_str(params, params_len) } {
+            Ok(params) => super::validate_parameters(params),
+            Err(message) => {
+                let issue = super::ParameterIssue { field: None, message };
+                serde_json::to_string(&super::ParameterReport { errors: vec![issue], warnings: vec![] }).unwrap()
+            }
+        };
+        unsafe { into_buffer(json, out_len) }
+    }
+
+    /// set_log_level on the `level_len` bytes at `level`; 0 on success, -1 for an
+    /// unknown level
+    ///
+    /// # Safety
+    /// `level` must be null or point to `level_len` readable bytes.
+    #[no_mangle]
+    pub unsafe extern "C" fn set_log_level(level: *const u8, level_len: usize) -> i32 {
+        match unsafe { read_str(level, level_len) }.and_then(super::set_log_level) {
+            Ok(()) => 0,
+            Err(_) => -1,
+        }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[no_mangle]
+    pub unsafe extern "C" fn get_default_parameters(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_default_parameters(), out_len) }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[cfg(not(feature = "minimal"))]
+    #[no_mangle]
+    pub unsafe extern "C" fn get_parameters_info(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_parameters_info(), out_len) }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[cfg(not(feature = "minimal"))]
+    #[no_mangle]
+    pub unsafe extern "C" fn get_species_info(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_species_info(), out_len) }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[cfg(not(feature = "minimal"))]
+    #[no_mangle]
+    pub unsafe extern "C" fn get_compartments_info(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_compartments_info(), out_len) }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[cfg(not(feature = "minimal"))]
+    #[no_mangle]
+    pub unsafe extern "C" fn get_model_metadata(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_model_metadata(), out_len) }
+    }
+
+    /// # Safety
+    /// `out_len` must point to writable memory.
+    #[cfg(not(feature = "minimal"))]
+    #[no_mangle]
+    pub unsafe extern "C" fn get_model_equations(out_len: *mut usize) -> *mut u8 {
+        unsafe { into_buffer(super::get_model_equations(), out_len) }
+    }
+}
+
+/// Python module of the `python` feature, built with maturin as `sbml_model`.
+/// JSON arguments and results are dicts and lists on the Python side.
+#[cfg(feature = "python")]
+mod python {
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+
+    /// Python objects of the JSON the model returns
+    fn loads<'py>(py: Python<'py>, json: &str) -> PyResult<Bound<'py, PyAny>> {
+        py.import("json")?.call_method1("loads", (json,))
+    }
+
+    /// JSON of a dict or list argument; a string is taken as JSON already
+    fn dumps(value: &Bound<'_, PyAny>) -> PyResult<String> {
+        match value.extract::<String>() {
+            Ok(json) => Ok(json),
+            Err(_) => value.py().import("json")?.call_method1("dumps", (value,))?.extract(),
+        }
+    }
+
+    fn loads_ok<'py>(py: Python<'py>, result: Result<String, String>) -> PyResult<Bound<'py, PyAny>> {
+        loads(py, &result.map_err(PyValueError::new_err)?)
+    }
+
+    /// Run one simulation with `params` merged over the defaults; a failed run
+    /// has its message in the result's error
+    #[pyfunction]
+    #[pyo3(signature = (params = None))]
+    fn run_simulation<'py>(py: Python<'py>, params: Option<&Bound<'py, PyAny>>) -> PyResult<Bound<'py, PyAny>> {
+        let params = params.map(dumps).transpose()?.unwrap_or_else(|| "{}".to_string());
+        loads(py, &super::run_simulation(&params))
+    }
+
+    /// One result per parameter dict, each with its own error
+    #[pyfunction]
+    fn run_simulation_batch<'py>(py: Python<'py>, params_list: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        loads_ok(py, super::run_simulation_batch(&dumps(params_list)?))
+    }
+
+    /// Every problem with `params`, without running
+    #[pyfunction]
+    fn validate_parameters<'py>(py: Python<'py>, params: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        loads(py, &super::validate_parameters(&dumps(params)?))
+    }
+
+    /// Cmax, Tmax, AUC and half-life of one species of a result
+    #[pyfunction]
+    fn compute_pk_metrics<'py>(py: Python<'py>, result: &Bound<'py, PyAny>, species: &str) -> PyResult<Bound<'py, PyAny>> {
+        loads_ok(py, super::compute_pk_metrics(&dumps(result)?, species))
+    }
+
+    /// Default value of every parameter and option
+    #[pyfunction]
+    fn get_default_parameters(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_default_parameters())
+    }
+
+    /// Name, default, units and range of each parameter
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_parameters_info(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_parameters_info())
+    }
+
+    /// Id, initial value, units and compartment of each species
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_species_info(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_species_info())
+    }
+
+    /// Size, formula, units and species of each compartment
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_compartments_info(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_compartments_info())
+    }
+
+    /// Model id, sizes, units and provenance
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_model_metadata(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_model_metadata())
+    }
+
+    /// Derivatives and assignment rules as text
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_model_equations(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        loads(py, &super::get_model_equations())
+    }
+
+    /// The SBML document the model was generated from
+    #[cfg(not(feature = "minimal"))]
+    #[pyfunction]
+    fn get_model_sbml() -> PyResult<String> {
+        super::get_model_sbml().map_err(PyValueError::new_err)
+    }
+
+    /// Set what the model logs to stderr: "off", "error", "info" or "debug"
+    #[pyfunction]
+    fn set_log_level(level: &str) -> PyResult<()> {
+        super::set_log_level(level).map_err(PyValueError::new_err)
+    }
+
+    #[pymodule]
+    fn sbml_model(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(run_simulation, m)?)?;
+        m.add_function(wrap_pyfunction!(run_simulation_batch, m)?)?;
+        m.add_function(wrap_pyfunction!(validate_parameters, m)?)?;
+        m.add_function(wrap_pyfunction!(compute_pk_metrics, m)?)?;
+        m.add_function(wrap_pyfunction!(get_default_parameters, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_parameters_info, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_species_info, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_compartments_info, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_model_metadata, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_model_equations, m)?)?;
+        #[cfg(not(feature = "minimal"))]
+        m.add_function(wrap_pyfunction!(get_model_sbml, m)?)?;
+        m.add_function(wrap_pyfunction!(set_log_level, m)?)?;
+        Ok(())
+    }
+}
+
+/// Names of the solver parameter vector entries, in index order
+const PARAMETER_ORDER: &[&str] = &["Kabs", "t0", "Kelm", "EoA_O", "D_o", "vplasma", "period_O", "n_O", "comp1", "koa", "t1", "uptake_O", "init_Aplasma"];
+
+/// Index mapping of the solver parameter vector: model parameters, derived
+/// parameter values, then the initial state as init_<species> (JSON array)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_parameter_order() -> String {
+    serde_json::to_string(PARAMETER_ORDER).unwrap()
+}
+
+/// Resolve one run's parameters into the solver parameter vector
+fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+    let Kabs = sim_params.Kabs;
+    let t0 = sim_params.t0;
+    let Kelm = sim_params.Kelm;
+    let EoA_O = sim_params.EoA_O;
+    let D_o = sim_params.D_o;
+    let vplasma = sim_params.vplasma;
+    let period_O = sim_params.period_O;
+    let n_O = sim_params.n_O;
+    let comp1 = sim_params.comp1;
+    let koa = 120000000.0*D_o*EoA_O;
+    let t1 = period_O + t0;
+    let uptake_O = D_o*EoA_O*n_O.powi(-1);
+
+    vec![
+        Kabs,
+        t0,
+        Kelm,
+        EoA_O,
+        D_o,
+        vplasma,
+        period_O,
+        n_O,
+        comp1,
+        koa,
+        t1,
+        uptake_O,
+        sim_params.init_Aplasma.unwrap_or(0.0),
+    ]
+}
+
+/// Range of each checked entry of p, with the parameters a derived value reads
+const PARAMETER_CHECKS: &[ParameterCheck] = &[
+    ("Kabs", 0, ParameterRange::Unknown, &[]),
+    ("t0", 1, ParameterRange::Unknown, &[]),
+    ("Kelm", 2, ParameterRange::Unknown, &[]),
+    ("EoA_O", 3, ParameterRange::Unknown, &[]),
+    ("D_o", 4, ParameterRange::Unknown, &[]),
+    ("vplasma", 5, ParameterRange::Unknown, &[]),
+    ("period_O", 6, ParameterRange::Unknown, &[]),
+    ("n_O", 7, ParameterRange::Unknown, &[]),
+    ("comp1", 8, ParameterRange::NonNegative, &[]),
+];
+
+/// Check parameters without running: `{errors, warnings}`, each `{field, message}` (JSON)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn validate_parameters(params: &str) -> String {
+    wasm_pk_core::validate_parameters::<OralWindowModel>(params)
+}
+
+/// The model as a type, for code generic over models; see PkModel
+pub struct OralWindowModel;
+
+impl PkModel for OralWindowModel {
+    type Params = SimulationParams;
+    const N_STATES: usize = 1;
+    const INFO: ModelInfo = MODEL;
+
+    fn rhs(y: &[f64], p: &[f64], t: f64, windows: &[UptakeWindow], dy: &mut [f64]) {
+        // Parameters from p
+        let Kabs = p[0];
+        let t0 = p[1];
+        let Kelm = p[2];
+        let koa = p[9];
+        let t1 = p[10];
+
+        // Map species names to y indices
+        let Aplasma = y[0];
+
+        // Dose-window terms summed over the run's uptake windows
+        let uptake_Aplasma = window_sum(windows, t0, t1, |t0, t1| 0.5*Kabs*koa*((100.0*(t - t0)*(-t0 + t1).powi(-1)).tanh() - (100.0*(t - t1)*(-t0 + t1).powi(-1)).tanh()));
+
+        // Temporary variables (CSE)
+        let x0 = Kelm;
+
+        // Derivatives
+        dy[0] = -Aplasma*x0 + uptake_Aplasma;
+    }
+
+    fn jac(_y: &[f64], p: &[f64], _t: f64, v: &[f64], jv: &mut [f64]) {
+        jv.fill(0.0);
+
+        // Parameters from p
+        let Kelm = p[2];
+
+        // Map species names to y indices
+
+
+        // Temporary variables (CSE)
+        let x0 = Kelm;
+
+        // Jacobian-Vector Product
+        jv[0] += (-x0) * v[0];
+    }
+
+    fn init(p: &[f64], _t: f64, y: &mut [f64]) {
+        y.copy_from_slice(&p[12..13]);
+    }
+    fn defaults() -> serde_json::Value {
+        serde_json::from_str(&get_default_parameters()).unwrap()
+    }
+
+    fn options(sim_params: &SimulationParams) -> &SimulationOptions {
+        &sim_params.options
+    }
+
+    fn options_mut(sim_params: &mut SimulationParams) -> &mut SimulationOptions {
+        &mut sim_params.options
+    }
+
+    fn check(sim_params: &SimulationParams) -> Result<(), SimulationError> {
+        if let Some(ref windows) = sim_params.uptake_windows {
+            check_uptake_windows(windows)?;
+        }
+        uptake_windows(sim_params, &parameter_vector(sim_params))?;
+        Ok(())
+    }
+
+    fn shift_schedule(sim_params: &mut SimulationParams, offset: f64) {
+        sim_params.options.shift_schedule(offset);
+        for window in sim_params.uptake_windows.iter_mut().flatten() {
+            window.t0 += offset;
+            window.t1 += offset;
+        }
+    }
+
+    fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+        parameter_vector(sim_params)
+    }
+
+    fn build_problem() -> BuiltProblem<'static> {
+        BuiltProblem::dense(MODEL, equations())
+    }
+
+    fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+        prepare_run(sim_params)
+    }
+}
+
+/// The run of checked parameters: the solver works in model time units, while
+/// results and checkpoints keep the caller's
+fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+    let resolved = serde_json::to_value(with_defaults(sim_params.clone())).unwrap();
+    let scale = time_scale(&sim_params);
+    let sim_params = in_model_time(sim_params);
+    let p = parameter_vector(&sim_params);
+    Ok(Run {
+        uptake_windows: uptake_windows(&sim_params, &p)?,
+        p,
+        resolved,
+        scale,
+        options: sim_params.options,
+    })
+}
+
+/// The model's functions for the solver; the closures capture nothing, so they
+/// are plain function pointers
+fn equations() -> Equations<'static> {
+    let eval_observables = |y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let vplasma = p[5];
+        let Aplasma = y[0];
+        let cplasma = Aplasma*vplasma.powi(-1);
+        vec![cplasma]
+    };
+
+    let eval_volumes = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let comp1 = p[8];
+        vec![comp1]
+    };
+
+    let eval_mass = |y: &[f64], _p: &[f64]| -> f64 {
+        y[0]
+    };
+
+    Equations {
+        rhs: OralWindowModel::rhs,
+        jac: OralWindowModel::jac,
+        init: OralWindowModel::init,
+        roots: no_roots,
+        triggers: no_triggers,
+        apply_events: no_events,
+        observables: eval_observables,
+        volumes: eval_volumes,
+        mass: eval_mass,
+        jac_matrix: None,
+    }
+}
+
+/// Solver stats of the last run of the last simulation call (JSON, null if it never started)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_last_run_stats() -> String {
+    LAST_RUN_STATS.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_simulation_at_times(params: &str, times: &[f64]) -> String {
+    wasm_pk_core::run_simulation_at_times::<OralWindowModel>(params, times)
+}
+
+/// Run a simulation and return `time,<species...>` CSV, one row per stored time
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_simulation_csv(params: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_csv::<OralWindowModel>(params)
+}
+
+/// Cmax, Tmax, AUC, AUC(0-inf), terminal half-life and trough of one species (JSON)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn compute_pk_metrics(result_json: &str, species: &str) -> Result<String, String> {
+    wasm_pk_core::compute_pk_metrics(result_json, species)
+}
+
+/// AUC of one species over each `[t1, t2]` window (JSON array, same order)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn compute_partial_auc(result_json: &str, species: &str, windows: &str) -> Result<String, String> {
+    wasm_pk_core::compute_partial_auc(result_json, species, windows)
+}
+
+/// Run the model once per value of one parameter, e.g. body weight from 20 to 100 kg
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_parameter_sweep(base_params: &str, sweep: &str) -> Result<String, String> {
+    wasm_pk_core::run_parameter_sweep::<OralWindowModel>(base_params, sweep)
+}
+
+/// Continue a run_simulation result from its last recorded state for `additional_time`,
+/// with `new_params` merged over the parameters that run used. Its doses, output_times
+/// and uptake windows are not repeated; times in `new_params` count from the
+/// continuation start. The segment is returned on the combined time axis, after
+/// the earlier trajectory when `concatenate` is set.
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn continue_simulation(previous_result: &str, new_params: &str, additional_time: f64, concatenate: bool) -> Result<String, String> {
+    wasm_pk_core::continue_simulation::<OralWindowModel>(previous_result, new_params, additional_time, concatenate)
+}
+
+/// Checkpoint of the last run of the last simulation call (JSON, null if it never
+/// started or its final state is not finite)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn export_checkpoint() -> String {
+    LAST_CHECKPOINT.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+/// Resume an exported checkpoint up to the absolute `final_time` in `params`, which are
+/// merged over the checkpoint's parameters. Its pending doses are applied unless
+/// `params` gives new doses. The result and the new checkpoint use absolute time.
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_from_checkpoint(checkpoint: &str, params: &str) -> Result<String, String> {
+    wasm_pk_core::run_from_checkpoint::<OralWindowModel>(checkpoint, params)
+}
+
+const SPECIES_IDS: &[&str] = &["Aplasma"];
+
+/// Parameters read by the derivative of each state, for failure hints
+const STATE_PARAMETERS: [&[&str]; 1] = [&["Kabs", "Kelm", "koa", "t0", "t1"]];
+
+/// SBML ids of the assignment-rule observables, in evaluation order
+const OBSERVABLE_IDS: [&str; 1] = ["cplasma"];
+
+/// What the shared solver loop and option checks need to know about the model
+const MODEL: ModelInfo = ModelInfo {
+    species: SPECIES_IDS,
+    state_parameters: &STATE_PARAMETERS,
+    event_ids: &[],
+    event_targets: &[],
+    event_initial_values: &[],
+    event_persistent: &[],
+    observables: &OBSERVABLE_IDS,
+    species_is_amount: &SPECIES_IS_AMOUNT,
+    species_volumes: &SPECIES_VOLUMES,
+    series_units: &SERIES_UNITS,
+    time_seconds: MODEL_TIME_SECONDS,
+    time_unit: MODEL_TIME_UNIT,
+    parameters: PARAMETER_ORDER,
+    parameter_checks: PARAMETER_CHECKS,
+    algebraic: &[],
+};
+
+/// Run a simulation and return the trajectory as little-endian f64 bytes
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn run_simulation_binary(params: &str) -> Result<Vec<u8>, String> {
+    wasm_pk_core::run_simulation_binary::<OralWindowModel>(params)
+}
+
+/// Describe the run_simulation_binary layout and its species order (JSON)
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_binary_layout() -> String {
+    wasm_pk_core::get_binary_layout::<OralWindowModel>()
+}
+
+/// Decode run_simulation_binary output into times and named species series
+pub fn decode_binary_result(bytes: &[u8]) -> Result<(Vec<f64>, OrderedSeries), String> {
+    wasm_pk_core::decode_binary_result::<OralWindowModel>(bytes)
+}
+
+/// One observable of get_model_metadata
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct ObservableInfo {
+    id: &'static str,
+    units: Option<&'static str>,
+}
+
+/// SBML document the model was generated from
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct SbmlSource {
+    id: Option<&'static str>,
+    name: Option<&'static str>,
+    level: Option<u32>,
+    version: Option<u32>,
+    /// SHA-256 of the source file
+    sha256: Option<&'static str>,
+}
+
+/// Generator that wrote this module
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct GeneratorInfo {
+    name: &'static str,
+    version: Option<&'static str>,
+    /// ISO 8601 UTC time of generation
+    generated_at: Option<&'static str>,
+}
+
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct ModelMetadata {
+    model_id: &'static str,
+    num_species: usize,
+    num_parameters: usize,
+    time_units: &'static str,
+    substance_units: &'static str,
+    volume_units: &'static str,
+    sbml: SbmlSource,
+    generator: GeneratorInfo,
+    diffsol_version: &'static str,
+    /// Optional features compiled in: "events", "sensitivities", "embedded_sbml"
+    features: &'static [&'static str],
+    observables: &'static [ObservableInfo],
+}
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_model_metadata() -> String {
+    let metadata = ModelMetadata {
+        model_id: "oral_window",
+        num_species: 1,
+        num_parameters: 9,
+        time_units: "HR",
+        substance_units: "MilliMOL",
+        volume_units: "L",
+        sbml: SbmlSource { id: None, name: None, level: None, version: None, sha256: None },
+        generator: GeneratorInfo { name: "sbml_rust_generator", version: Some("1.0.0"), generated_at: Some("2026-01-01T00:00:00Z") },
+        diffsol_version: "0.6.3",
+        features: &[],
+        observables: &[
+            ObservableInfo { id: "cplasma", units: Some("MilliMOL_per_L") },
+        ],
+    };
+    serde_json::to_string(&metadata).unwrap()
+}
+
+/// One entry of get_parameters_info
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct ParameterInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    units: Option<&'static str>,
+    /// SBML notes as plain text
+    description: Option<&'static str>,
+    default_value: f64,
+    required: bool,
+    is_compartment: bool,
+    /// Soft bounds for input forms; validate_parameters has the hard checks
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+#[cfg(not(feature = "minimal"))]
+const PARAMETER_INFO: &[ParameterInfo] = &[
+    ParameterInfo { id: "Kabs", name: None, units: None, description: None, default_value: 0.4, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "t0", name: None, units: None, description: None, default_value: 0.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Kelm", name: None, units: None, description: None, default_value: 0.13, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "EoA_O", name: None, units: None, description: None, default_value: 1.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "D_o", name: None, units: None, description: None, default_value: 1.3381102, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "vplasma", name: None, units: None, description: None, default_value: 3.6, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "period_O", name: None, units: None, description: None, default_value: 0.0003, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "n_O", name: None, units: None, description: None, default_value: 1.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "comp1", name: None, units: None, description: None, default_value: 3.6, required: true, is_compartment: true, min: Some(0.0), max: None },
+];
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_parameters_info() -> String {
+    serde_json::to_string(PARAMETER_INFO).unwrap()
+}
+
+/// One entry of get_species_info
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct SpeciesInfo {
+    id: &'static str,
+    initial_amount: f64,
+    #[serde(rename = "override")]
+    override_field: &'static str,
+    units: Option<&'static str>,
+    compartment: Option<&'static str>,
+    compartment_volume: Option<f64>,
+    /// False when the SBML value is a concentration (hasOnlySubstanceUnits=false)
+    is_amount: bool,
+    is_boundary: bool,
+}
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_species_info() -> String {
+    let p = parameter_vector(&OralWindowModel::default_params());
+    let species: &[SpeciesInfo] = &[
+        SpeciesInfo { id: "Aplasma", initial_amount: 0.0, override_field: "init_Aplasma", units: None, compartment: Some("comp1"), compartment_volume: Some(p[8]), is_amount: false, is_boundary: false },
+    ];
+    serde_json::to_string(&species).unwrap()
+}
+
+/// Whether each species' values are amounts rather than concentrations
+const SPECIES_IS_AMOUNT: [bool; 1] = [false];
+/// Compartment converting each species between amount and concentration, or why none does
+const SPECIES_VOLUMES: [Result<&str, &str>; 1] = [Ok("comp1")];
+
+/// Unit of each species and observable series; None where the SBML gives none
+const SERIES_UNITS: [(&str, Option<&str>); 2] = [
+    ("Aplasma", None),
+    ("cplasma", Some("MilliMOL_per_L")),
+];
+
+/// One entry of get_compartments_info
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct CompartmentInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    /// Size at the default parameters; None when it changes during a run
+    size: Option<f64>,
+    /// Rule or initial assignment computing the size, e.g. "BM*scVLiver"
+    formula: Option<&'static str>,
+    units: Option<&'static str>,
+    species: &'static [&'static str],
+}
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_compartments_info() -> String {
+    let p = parameter_vector(&OralWindowModel::default_params());
+    let compartments: &[CompartmentInfo] = &[
+        CompartmentInfo { id: "comp1", name: None, size: Some(p[8]), formula: None, units: None, species: &["Aplasma"] },
+    ];
+    serde_json::to_string(&compartments).unwrap()
+}
+
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_default_parameters() -> String {
+    let defaults: &[(&str, f64)] = &[
+        ("Kabs", 0.4),
+        ("t0", 0.0),
+        ("Kelm", 0.13),
+        ("EoA_O", 1.0),
+        ("D_o", 1.3381102),
+        ("vplasma", 3.6),
+        ("period_O", 0.0003),
+        ("n_O", 1.0),
+        ("comp1", 3.6),
+        ("final_time", 24.0),
+    ];
+    let defaults: serde_json::Map<String, serde_json::Value> = defaults.iter().map(|&(id, value)| (id.to_string(), value.into())).collect();
+    serde_json::Value::Object(defaults).to_string()
+}
+/// Equations in model order, serialized as a JSON object
+#[cfg(not(feature = "minimal"))]
+struct EquationTable(&'static [(&'static str, &'static str)]);
+
+#[cfg(not(feature = "minimal"))]
+impl Serialize for EquationTable {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (id, expression) in self.0 {
+            map.serialize_entry(id, expression)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(not(feature = "minimal"))]
+#[derive(Serialize)]
+struct ModelEquations {
+    derivatives: EquationTable,
+    assignment_rules: EquationTable,
+}
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_model_equations() -> String {
+    let equations = ModelEquations {
+        derivatives: EquationTable(&[
+            ("Aplasma", "-Aplasma*Kelm + 0.5*Kabs*koa*(tanh(100*t - 100*t0) - tanh(100*t - 100*t1))"),
+        ]),
+        assignment_rules: EquationTable(&[
+            ("cplasma", "Aplasma/vplasma"),
+        ]),
+    };
+    serde_json::to_string_pretty(&equations).unwrap()
+}
+/// SBML document this module was generated from
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_model_sbml() -> Result<String, String> {
+    Err("this model was not generated from an SBML file".to_string())
+}
+/// TypeScript definitions of the parameters, result and metadata of this model
+#[cfg(not(feature = "minimal"))]
+const TYPES_TS: &str = r#"// TypeScript definitions for oral_window, generated with the module
+
+export type SpeciesId = "Aplasma";
+export type ObservableId = "cplasma";
+export type SolverMethod = "bdf" | "tr_bdf2";
+export type JacobianMode = "analytic" | "finite_difference";
+export type SimulationMode = "transient" | "steady_state";
+export type TimeUnit = "s" | "min" | "h" | "d";
+export type OutputKind = "amount" | "concentration" | "both";
+
+export interface Dose {
+  time: number;
+  amount: number;
+  target: SpeciesId;
+  units?: string | null;
+}
+
+export interface UptakeWindow {
+  t0: number;
+  t1: number;
+  fraction: number;
+}
+
+/** Input of run_simulation; omitted fields take the model defaults */
+export interface SimulationParams {
+  Kabs?: number;
+  t0?: number;
+  Kelm?: number;
+  EoA_O?: number;
+  D_o?: number;
+  vplasma?: number;
+  period_O?: number;
+  n_O?: number;
+  comp1?: number;
+  init_Aplasma?: number | null;
+  t_start?: number | null;
+  final_time?: number | null;
+  time_unit?: TimeUnit | null;
+  convert_output_time?: boolean | null;
+  output_times?: number[] | null;
+  output_dt?: number | null;
+  max_output_points?: number | null;
+  include_observables?: boolean | null;
+  output_kind?: OutputKind | null;
+  include_parameters?: boolean | null;
+  mass_balance?: boolean | null;
+  validate?: boolean | null;
+  doses?: Dose[] | null;
+  uptake_windows?: UptakeWindow[] | null;
+  rtol?: number | null;
+  atol?: number | null;
+  max_steps?: number | null;
+  max_step?: number | null;
+  initial_step?: number | null;
+  check_finite?: boolean | null;
+  clamp_nonnegative?: boolean | null;
+  clamp_threshold?: number | null;
+  solver?: SolverMethod | null;
+  jacobian?: JacobianMode | null;
+  mode?: SimulationMode | null;
+  steady_state_tol?: number | null;
+  steady_state_exclude?: string[] | null;
+  steady_state_min_time?: number | null;
+}
+
+export interface SimulationResult {
+  species: Record<SpeciesId, number[]>;
+  time: number[];
+  /** null when the parameters were rejected or include_parameters is false */
+  parameters: SimulationParams | null;
+  /** Filled when include_observables is set */
+  observables: Partial<Record<ObservableId, number[]>>;
+  /** Unit of each species and observable series and of time; null if unknown */
+  units: Record<string, string | null>;
+  /** Filled when output_kind asks for them */
+  amounts: Partial<Record<SpeciesId, number[]>>;
+  concentrations: Partial<Record<SpeciesId, number[]>>;
+  /** Species left in their native representation, and why */
+  notes: string[];
+  steady_state: SteadyState | null;
+  applied_doses: Dose[];
+  events: EventRecord[];
+  /** Whether each event trigger held at the end; continuations start from it */
+  trigger_state: boolean[];
+  steps: number;
+  stats: SolverStats | null;
+  diagnostics: Diagnostics | null;
+  error: SimulationError | null;
+  timing: Timing;
+}
+
+/** Wall-clock milliseconds of the call */
+export interface Timing {
+  solve_ms: number;
+  serialize_ms: number;
+  total_ms: number;
+}
+
+export interface SteadyState {
+  reached: boolean;
+  time: number;
+  rms: number;
+  state: number[];
+}
+
+export interface EventRecord {
+  time: number;
+  id: string;
+  assignments: Partial<Record<SpeciesId, number>>;
+}
+
+export interface SolverStats {
+  steps: number;
+  rhs_evals: number;
+  jacobian_evals: number;
+  newton_iterations: number;
+  newton_failures: number;
+  final_step_size: number;
+}
+
+export interface Diagnostics {
+  total_mass: number[];
+  max_mass_drift: number;
+}
+
+export interface SimulationError {
+  kind: "invalid_params" | "setup" | "solver_failure" | "event_error" | "max_steps_exceeded" | "cancelled";
+  message: string;
+  field: string | null;
+  time: number | null;
+  state: number[] | null;
+  hint: string | null;
+}
+
+/** get_model_metadata */
+export interface ModelMetadata {
+  model_id: string;
+  num_species: number;
+  num_parameters: number;
+  time_units: string;
+  substance_units: string;
+  volume_units: string;
+  sbml: {
+    id: string | null;
+    name: string | null;
+    level: number | null;
+    version: number | null;
+    sha256: string | null;
+  };
+  generator: { name: string; version: string | null; generated_at: string | null };
+  diffsol_version: string;
+  features: ("events" | "sensitivities" | "embedded_sbml")[];
+  observables: { id: ObservableId; units: string | null }[];
+}
+
+/** One entry of get_parameters_info */
+export interface ParameterInfo {
+  id: keyof SimulationParams;
+  name: string | null;
+  units: string | null;
+  description: string | null;
+  default_value: number;
+  required: boolean;
+  is_compartment: boolean;
+  min: number | null;
+  max: number | null;
+}
+
+/** One entry of get_species_info */
+export interface SpeciesInfo {
+  id: SpeciesId;
+  initial_amount: number;
+  override: keyof SimulationParams;
+  units: string | null;
+  compartment: string | null;
+  compartment_volume: number | null;
+  is_amount: boolean;
+  is_boundary: boolean;
+}
+
+/** One entry of get_compartments_info */
+export interface CompartmentInfo {
+  id: string;
+  name: string | null;
+  size: number | null;
+  formula: string | null;
+  units: string | null;
+  species: SpeciesId[];
+}
+
+/** get_model_equations */
+export interface ModelEquations {
+  derivatives: Record<SpeciesId, string>;
+  assignment_rules: Record<string, string>;
+}
+"#;
+
+#[cfg(not(feature = "minimal"))]
+#[cfg_attr(all(feature = "wasm", target_arch = "wasm32"), wasm_bindgen)]
+pub fn get_types_ts() -> String {
+    TYPES_TS.to_string()
+}
+
+#[cfg(all(test, feature = "wasm", target_arch = "wasm32"))]
+mod browser_tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[cfg(not(feature = "node-tests"))]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn get(value: &JsValue, key: &str) -> JsValue {
+        js_sys::Reflect::get(value, &key.into()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn defaults_run_through_js() {
+        // Parsed, changed and serialized again in JS, as a page passes parameters
+        let params = js_sys::JSON::parse(&get_default_parameters()).unwrap();
+        js_sys::Reflect::set(&params, &"final_time".into(), &2.0.into()).unwrap();
+        let text = String::from(js_sys::JSON::stringify(&params).unwrap());
+        let result = js_sys::JSON::parse(&run_simulation(&text)).unwrap();
+        assert!(get(&result, "error").is_null());
+        let time = js_sys::Array::from(&get(&result, "time"));
+        assert_eq!(time.get(0).as_f64(), Some(0.0));
+        assert_eq!(time.get(time.length() - 1).as_f64(), Some(2.0));
+        assert_eq!(get(&get(&result, "parameters"), "final_time").as_f64(), Some(2.0));
+        // The first point is the model's initial state for the defaults
+        let p = OralWindowModel::parameter_vector(&OralWindowModel::default_params());
+        let mut y = vec![0.0; OralWindowModel::N_STATES];
+        OralWindowModel::init(&p, 0.0, &mut y);
+        let species = get(&result, "species");
+        for (name, y0) in SPECIES_IDS.iter().zip(&y) {
+            assert_eq!(js_sys::Array::from(&get(&species, name)).get(0).as_f64(), Some(*y0), "{}", name);
+        }
+        // The same run with JS objects in and out
+        let direct = run_simulation_js(params).unwrap();
+        let json = |value: &JsValue| String::from(js_sys::JSON::stringify(value).unwrap());
+        for key in ["time", "species"] {
+            assert_eq!(json(&get(&direct, key)), json(&get(&result, key)), "{}", key);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn malformed_params_are_errors_in_js() {
+        let result = js_sys::JSON::parse(&run_simulation(r#"{"final_time": 2,"#)).unwrap();
+        assert_eq!(get(&get(&result, "error"), "kind").as_string().as_deref(), Some("invalid_params"));
+        // Non-ASCII text survives both string conversions
+        let result = js_sys::JSON::parse(&run_simulation(r#"{"dosis_µg": 1}"#)).unwrap();
+        let message = get(&get(&result, "error"), "message").as_string().unwrap();
+        assert!(message.contains("dosis_µg"), "{}", message);
+        assert!(run_simulation_js(JsValue::from_str("{")).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn large_results_and_logging_reach_js() {
+        // Debug logging calls the console import on every step and event
+        set_log_level("debug").unwrap();
+        let logged = run_simulation(r#"{"final_time": 1}"#);
+        set_log_level(if cfg!(debug_assertions) { "info" } else { "error" }).unwrap();
+        assert!(get(&js_sys::JSON::parse(&logged).unwrap(), "error").is_null());
+        // Tens of thousands of points, megabytes of JSON
+        let output = run_simulation(r#"{"max_step": 0.001}"#);
+        let result = js_sys::JSON::parse(&output).unwrap();
+        assert!(get(&result, "error").is_null());
+        assert!(js_sys::Array::from(&get(&result, "time")).length() > 24_000);
+    }
+
+    #[wasm_bindgen_test]
+    async fn async_run_matches_sync_run() {
+        let params = js_sys::JSON::parse(&get_default_parameters()).unwrap();
+        let sync = run_simulation_js(params.clone()).unwrap();
+        // A small chunk so the run yields many times
+        let result = run_simulation_async(params, Some(7)).await.unwrap();
+        let json = |value: &JsValue| String::from(js_sys::JSON::stringify(value).unwrap());
+        assert_eq!(json(&result), json(&sync));
+    }
+
+    #[wasm_bindgen_test]
+    fn session_runs_match_stateless_runs() {
+        let mut session = ModelSession::new(js_sys::Object::new().into()).unwrap();
+        session.set_final_time(2.0).unwrap();
+        session.set_parameter("rtol", 1e-8).unwrap();
+        assert!(session.set_parameter("not_a_parameter", 1.0).is_err());
+        assert!(session.set_parameter("rtol", -1.0).is_err());
+        let params = js_sys::JSON::parse(r#"{"final_time": 2.0, "rtol": 1e-8}"#).unwrap();
+        let expected = run_simulation_typed(params).unwrap();
+        for _ in 0..2 {
+            let handle = session.run();
+            assert_eq!(handle.times(), expected.times());
+            for name in SPECIES_IDS {
+                assert_eq!(handle.species(name).unwrap(), expected.species(name).unwrap());
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn progress_callback_reports_fraction_and_steps() {
+        let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let record = calls.clone();
+        let callback = Closure::<dyn FnMut(f64, f64)>::new(move |fraction, steps| record.borrow_mut().push((fraction, steps)));
+        set_progress_callback(Some(callback.as_ref().unchecked_ref::<js_sys::Function>().clone()), None, Some(5));
+        let result = run_simulation_js(js_sys::Object::new().into());
+        set_progress_callback(None, None, None);
+        assert!(result.is_ok());
+        let calls = calls.borrow();
+        assert!(!calls.is_empty());
+        assert!(calls.windows(2).all(|pair| pair[0].0 <= pair[1].0 && pair[0].1 < pair[1].1));
+        assert!(calls.iter().all(|&(fraction, steps)| (0.0..=1.0).contains(&fraction) && steps % 5.0 == 0.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn throwing_progress_callback_is_ignored() {
+        let callback = js_sys::Function::new_with_args("fraction, steps", "throw new Error('progress bar failed')");
+        set_progress_callback(Some(callback), None, Some(1));
+        let result = run_simulation_js(js_sys::Object::new().into());
+        set_progress_callback(None, None, None);
+        assert!(js_sys::Reflect::get(&result.unwrap(), &"error".into()).unwrap().is_null());
+    }
+}
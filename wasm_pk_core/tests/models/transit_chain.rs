@@ -0,0 +1,1557 @@
+// Generated native Rust code from SBML model: transit_chain
+// Uses SymPy CSE for optimized derivatives and Jacobian
+
+use serde::{Deserialize, Serialize};
+use wasm_pk_core::*;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulationParams {
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub ktr: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Vmax: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub Km: f64,
+    #[serde(deserialize_with = "lenient_f64::deserialize")]
+    pub c: f64,
+
+    // Initial amounts (optional, for runtime dosing)
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T0: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T1: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T2: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T3: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T4: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T5: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T6: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T7: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T8: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T9: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T10: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T11: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T12: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T13: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T14: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T15: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T16: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T17: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T18: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T19: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T20: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T21: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T22: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T23: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T24: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T25: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T26: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T27: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T28: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T29: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T30: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T31: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T32: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T33: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T34: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T35: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T36: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T37: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T38: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T39: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T40: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T41: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T42: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T43: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T44: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T45: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T46: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T47: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T48: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T49: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T50: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T51: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T52: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T53: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T54: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T55: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T56: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T57: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T58: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T59: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T60: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T61: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T62: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T63: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T64: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T65: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T66: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T67: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T68: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T69: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T70: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T71: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T72: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T73: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T74: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T75: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T76: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T77: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T78: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64::option")]
+    pub init_T79: Option<f64>,
+    /// Times, outputs, doses and solver settings, the same for every model
+    #[serde(flatten)]
+    pub options: SimulationOptions,
+}
+
+/// Seconds per model time unit, if the SBML declares its time units
+const MODEL_TIME_SECONDS: Option<f64> = None;
+/// SBML id of the model's time units
+const MODEL_TIME_UNIT: Option<&str> = None;
+
+/// Model time units per time_unit (1 when it is not given)
+fn time_scale(sim_params: &SimulationParams) -> f64 {
+    sim_params.options.time_scale(&MODEL)
+}
+
+/// The parameters with every time and step size in model time units
+fn in_model_time(mut sim_params: SimulationParams) -> SimulationParams {
+    let scale = time_scale(&sim_params);
+    sim_params.options = sim_params.options.in_model_time(scale);
+    sim_params
+}
+
+/// The parameters with the defaults a run uses for its times and solver options
+fn with_defaults(mut sim_params: SimulationParams) -> SimulationParams {
+    sim_params.options = sim_params.options.with_defaults(&MODEL);
+    sim_params
+}
+
+pub fn run_simulation(params: &str) -> String {
+    wasm_pk_core::simulate::<TransitChainModel>(params)
+}
+
+/// Run one simulation per parameter object; each result carries its own error
+pub fn run_simulation_batch(params_list: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_batch::<TransitChainModel>(params_list)
+}
+
+/// Names of the solver parameter vector entries, in index order
+const PARAMETER_ORDER: &[&str] = &["ktr", "Vmax", "Km", "c", "init_T0", "init_T1", "init_T2", "init_T3", "init_T4", "init_T5", "init_T6", "init_T7", "init_T8", "init_T9", "init_T10", "init_T11", "init_T12", "init_T13", "init_T14", "init_T15", "init_T16", "init_T17", "init_T18", "init_T19", "init_T20", "init_T21", "init_T22", "init_T23", "init_T24", "init_T25", "init_T26", "init_T27", "init_T28", "init_T29", "init_T30", "init_T31", "init_T32", "init_T33", "init_T34", "init_T35", "init_T36", "init_T37", "init_T38", "init_T39", "init_T40", "init_T41", "init_T42", "init_T43", "init_T44", "init_T45", "init_T46", "init_T47", "init_T48", "init_T49", "init_T50", "init_T51", "init_T52", "init_T53", "init_T54", "init_T55", "init_T56", "init_T57", "init_T58", "init_T59", "init_T60", "init_T61", "init_T62", "init_T63", "init_T64", "init_T65", "init_T66", "init_T67", "init_T68", "init_T69", "init_T70", "init_T71", "init_T72", "init_T73", "init_T74", "init_T75", "init_T76", "init_T77", "init_T78", "init_T79"];
+
+/// Index mapping of the solver parameter vector: model parameters, derived
+/// parameter values, then the initial state as init_<species> (JSON array)
+pub fn get_parameter_order() -> String {
+    serde_json::to_string(PARAMETER_ORDER).unwrap()
+}
+
+/// Resolve one run's parameters into the solver parameter vector
+fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+    let ktr = sim_params.ktr;
+    let Vmax = sim_params.Vmax;
+    let Km = sim_params.Km;
+    let c = sim_params.c;
+
+
+    vec![
+        ktr,
+        Vmax,
+        Km,
+        c,
+        sim_params.init_T0.unwrap_or(10.0),
+        sim_params.init_T1.unwrap_or(0.0),
+        sim_params.init_T2.unwrap_or(0.0),
+        sim_params.init_T3.unwrap_or(0.0),
+        sim_params.init_T4.unwrap_or(0.0),
+        sim_params.init_T5.unwrap_or(0.0),
+        sim_params.init_T6.unwrap_or(0.0),
+        sim_params.init_T7.unwrap_or(0.0),
+        sim_params.init_T8.unwrap_or(0.0),
+        sim_params.init_T9.unwrap_or(0.0),
+        sim_params.init_T10.unwrap_or(0.0),
+        sim_params.init_T11.unwrap_or(0.0),
+        sim_params.init_T12.unwrap_or(0.0),
+        sim_params.init_T13.unwrap_or(0.0),
+        sim_params.init_T14.unwrap_or(0.0),
+        sim_params.init_T15.unwrap_or(0.0),
+        sim_params.init_T16.unwrap_or(0.0),
+        sim_params.init_T17.unwrap_or(0.0),
+        sim_params.init_T18.unwrap_or(0.0),
+        sim_params.init_T19.unwrap_or(0.0),
+        sim_params.init_T20.unwrap_or(0.0),
+        sim_params.init_T21.unwrap_or(0.0),
+        sim_params.init_T22.unwrap_or(0.0),
+        sim_params.init_T23.unwrap_or(0.0),
+        sim_params.init_T24.unwrap_or(0.0),
+        sim_params.init_T25.unwrap_or(0.0),
+        sim_params.init_T26.unwrap_or(0.0),
+        sim_params.init_T27.unwrap_or(0.0),
+        sim_params.init_T28.unwrap_or(0.0),
+        sim_params.init_T29.unwrap_or(0.0),
+        sim_params.init_T30.unwrap_or(0.0),
+        sim_params.init_T31.unwrap_or(0.0),
+        sim_params.init_T32.unwrap_or(0.0),
+        sim_params.init_T33.unwrap_or(0.0),
+        sim_params.init_T34.unwrap_or(0.0),
+        sim_params.init_T35.unwrap_or(0.0),
+        sim_params.init_T36.unwrap_or(0.0),
+        sim_params.init_T37.unwrap_or(0.0),
+        sim_params.init_T38.unwrap_or(0.0),
+        sim_params.init_T39.unwrap_or(0.0),
+        sim_params.init_T40.unwrap_or(0.0),
+        sim_params.init_T41.unwrap_or(0.0),
+        sim_params.init_T42.unwrap_or(0.0),
+        sim_params.init_T43.unwrap_or(0.0),
+        sim_params.init_T44.unwrap_or(0.0),
+        sim_params.init_T45.unwrap_or(0.0),
+        sim_params.init_T46.unwrap_or(0.0),
+        sim_params.init_T47.unwrap_or(0.0),
+        sim_params.init_T48.unwrap_or(0.0),
+        sim_params.init_T49.unwrap_or(0.0),
+        sim_params.init_T50.unwrap_or(0.0),
+        sim_params.init_T51.unwrap_or(0.0),
+        sim_params.init_T52.unwrap_or(0.0),
+        sim_params.init_T53.unwrap_or(0.0),
+        sim_params.init_T54.unwrap_or(0.0),
+        sim_params.init_T55.unwrap_or(0.0),
+        sim_params.init_T56.unwrap_or(0.0),
+        sim_params.init_T57.unwrap_or(0.0),
+        sim_params.init_T58.unwrap_or(0.0),
+        sim_params.init_T59.unwrap_or(0.0),
+        sim_params.init_T60.unwrap_or(0.0),
+        sim_params.init_T61.unwrap_or(0.0),
+        sim_params.init_T62.unwrap_or(0.0),
+        sim_params.init_T63.unwrap_or(0.0),
+        sim_params.init_T64.unwrap_or(0.0),
+        sim_params.init_T65.unwrap_or(0.0),
+        sim_params.init_T66.unwrap_or(0.0),
+        sim_params.init_T67.unwrap_or(0.0),
+        sim_params.init_T68.unwrap_or(0.0),
+        sim_params.init_T69.unwrap_or(0.0),
+        sim_params.init_T70.unwrap_or(0.0),
+        sim_params.init_T71.unwrap_or(0.0),
+        sim_params.init_T72.unwrap_or(0.0),
+        sim_params.init_T73.unwrap_or(0.0),
+        sim_params.init_T74.unwrap_or(0.0),
+        sim_params.init_T75.unwrap_or(0.0),
+        sim_params.init_T76.unwrap_or(0.0),
+        sim_params.init_T77.unwrap_or(0.0),
+        sim_params.init_T78.unwrap_or(0.0),
+        sim_params.init_T79.unwrap_or(0.0),
+    ]
+}
+
+/// Range of each checked entry of p, with the parameters a derived value reads
+const PARAMETER_CHECKS: &[ParameterCheck] = &[
+    ("ktr", 0, ParameterRange::Unknown, &[]),
+    ("Vmax", 1, ParameterRange::Unknown, &[]),
+    ("Km", 2, ParameterRange::Unknown, &[]),
+    ("c", 3, ParameterRange::NonNegative, &[]),
+];
+
+/// Check parameters without running: `{errors, warnings}`, each `{field, message}` (JSON)
+pub fn validate_parameters(params: &str) -> String {
+    wasm_pk_core::validate_parameters::<TransitChainModel>(params)
+}
+
+/// The model as a type, for code generic over models; see PkModel
+pub struct TransitChainModel;
+
+impl PkModel for TransitChainModel {
+    type Params = SimulationParams;
+    const N_STATES: usize = 80;
+    const INFO: ModelInfo = MODEL;
+
+    fn rhs(y: &[f64], p: &[f64], _t: f64, _windows: &[UptakeWindow], dy: &mut [f64]) {
+        // Parameters from p
+        let ktr = p[0];
+        let Vmax = p[1];
+        let Km = p[2];
+
+        // Map species names to y indices
+        let T0 = y[0];
+        let T1 = y[1];
+        let T2 = y[2];
+        let T3 = y[3];
+        let T4 = y[4];
+        let T5 = y[5];
+        let T6 = y[6];
+        let T7 = y[7];
+        let T8 = y[8];
+        let T9 = y[9];
+        let T10 = y[10];
+        let T11 = y[11];
+        let T12 = y[12];
+        let T13 = y[13];
+        let T14 = y[14];
+        let T15 = y[15];
+        let T16 = y[16];
+        let T17 = y[17];
+        let T18 = y[18];
+        let T19 = y[19];
+        let T20 = y[20];
+        let T21 = y[21];
+        let T22 = y[22];
+        let T23 = y[23];
+        let T24 = y[24];
+        let T25 = y[25];
+        let T26 = y[26];
+        let T27 = y[27];
+        let T28 = y[28];
+        let T29 = y[29];
+        let T30 = y[30];
+        let T31 = y[31];
+        let T32 = y[32];
+        let T33 = y[33];
+        let T34 = y[34];
+        let T35 = y[35];
+        let T36 = y[36];
+        let T37 = y[37];
+        let T38 = y[38];
+        let T39 = y[39];
+        let T40 = y[40];
+        let T41 = y[41];
+        let T42 = y[42];
+        let T43 = y[43];
+        let T44 = y[44];
+        let T45 = y[45];
+        let T46 = y[46];
+        let T47 = y[47];
+        let T48 = y[48];
+        let T49 = y[49];
+        let T50 = y[50];
+        let T51 = y[51];
+        let T52 = y[52];
+        let T53 = y[53];
+        let T54 = y[54];
+        let T55 = y[55];
+        let T56 = y[56];
+        let T57 = y[57];
+        let T58 = y[58];
+        let T59 = y[59];
+        let T60 = y[60];
+        let T61 = y[61];
+        let T62 = y[62];
+        let T63 = y[63];
+        let T64 = y[64];
+        let T65 = y[65];
+        let T66 = y[66];
+        let T67 = y[67];
+        let T68 = y[68];
+        let T69 = y[69];
+        let T70 = y[70];
+        let T71 = y[71];
+        let T72 = y[72];
+        let T73 = y[73];
+        let T74 = y[74];
+        let T75 = y[75];
+        let T76 = y[76];
+        let T77 = y[77];
+        let T78 = y[78];
+        let T79 = y[79];
+
+        // Temporary variables (CSE)
+        let x0 = ktr;
+        let x1 = -T9;
+        let x2 = (Km + T79).powi(-1);
+        let x3 = T79*x2;
+
+        // Derivatives
+        dy[0] = -T0*x0;
+        dy[1] = x0*(T0 - T1);
+        dy[2] = x0*(T1 - T2);
+        dy[3] = x0*(T2 - T3);
+        dy[4] = x0*(T3 - T4);
+        dy[5] = x0*(T4 - T5);
+        dy[6] = x0*(T5 - T6);
+        dy[7] = x0*(T6 - T7);
+        dy[8] = x0*(T7 - T8);
+        dy[9] = x0*(T8 + x1);
+        dy[10] = x0*(-T10 - x1);
+        dy[11] = x0*(T10 - T11);
+        dy[12] = x0*(T11 - T12);
+        dy[13] = x0*(T12 - T13);
+        dy[14] = x0*(T13 - T14);
+        dy[15] = x0*(T14 - T15);
+        dy[16] = x0*(T15 - T16);
+        dy[17] = x0*(T16 - T17);
+        dy[18] = x0*(T17 - T18);
+        dy[19] = x0*(T18 - T19);
+        dy[20] = x0*(T19 - T20);
+        dy[21] = x0*(T20 - T21);
+        dy[22] = x0*(T21 - T22);
+        dy[23] = x0*(T22 - T23);
+        dy[24] = x0*(T23 - T24);
+        dy[25] = x0*(T24 - T25);
+        dy[26] = x0*(T25 - T26);
+        dy[27] = x0*(T26 - T27);
+        dy[28] = x0*(T27 - T28);
+        dy[29] = x0*(T28 - T29);
+        dy[30] = x0*(T29 - T30);
+        dy[31] = x0*(T30 - T31);
+        dy[32] = x0*(T31 - T32);
+        dy[33] = x0*(T32 - T33);
+        dy[34] = x0*(T33 - T34);
+        dy[35] = x0*(T34 - T35);
+        dy[36] = x0*(T35 - T36);
+        dy[37] = x0*(T36 - T37);
+        dy[38] = x0*(T37 - T38);
+        dy[39] = x0*(T38 - T39);
+        dy[40] = x0*(T39 - T40);
+        dy[41] = x0*(T40 - T41);
+        dy[42] = x0*(T41 - T42);
+        dy[43] = x0*(T42 - T43);
+        dy[44] = x0*(T43 - T44);
+        dy[45] = x0*(T44 - T45);
+        dy[46] = x0*(T45 - T46);
+        dy[47] = x0*(T46 - T47);
+        dy[48] = x0*(T47 - T48);
+        dy[49] = x0*(T48 - T49);
+        dy[50] = x0*(T49 - T50);
+        dy[51] = x0*(T50 - T51);
+        dy[52] = x0*(T51 - T52);
+        dy[53] = x0*(T52 - T53);
+        dy[54] = x0*(T53 - T54);
+        dy[55] = x0*(T54 - T55);
+        dy[56] = x0*(T55 - T56);
+        dy[57] = x0*(T56 - T57);
+        dy[58] = x0*(T57 - T58);
+        dy[59] = x0*(T58 - T59);
+        dy[60] = x0*(T59 - T60);
+        dy[61] = x0*(T60 - T61);
+        dy[62] = x0*(T61 - T62);
+        dy[63] = x0*(T62 - T63);
+        dy[64] = x0*(T63 - T64);
+        dy[65] = x0*(T64 - T65);
+        dy[66] = x0*(T65 - T66);
+        dy[67] = x0*(T66 - T67);
+        dy[68] = x0*(T67 - T68);
+        dy[69] = x0*(T68 - T69);
+        dy[70] = x0*(T69 - T70);
+        dy[71] = x0*(T70 - T71);
+        dy[72] = x0*(T71 - T72);
+        dy[73] = x0*(T72 - T73);
+        dy[74] = x0*(T73 - T74);
+        dy[75] = x0*(T74 - T75);
+        dy[76] = x0*(T75 - T76);
+        dy[77] = x0*(T76 - T77);
+        dy[78] = x0*(T77 - T78);
+        dy[79] = T78*ktr - Vmax*x3;
+    }
+
+    fn jac(y: &[f64], p: &[f64], _t: f64, v: &[f64], jv: &mut [f64]) {
+        jv.fill(0.0);
+
+        // Parameters from p
+        let ktr = p[0];
+        let Vmax = p[1];
+        let Km = p[2];
+
+        // Map species names to y indices
+        let T79 = y[79];
+
+        // Temporary variables (CSE)
+        let x0 = ktr;
+        let x2 = (Km + T79).powi(-1);
+        let x3 = T79*x2;
+        let x4 = -x0;
+
+        // Jacobian-Vector Product
+        jv[0] += (x4) * v[0];
+        jv[1] += (x0) * v[0];
+        jv[1] += (x4) * v[1];
+        jv[2] += (x0) * v[1];
+        jv[2] += (x4) * v[2];
+        jv[3] += (x0) * v[2];
+        jv[3] += (x4) * v[3];
+        jv[4] += (x0) * v[3];
+        jv[4] += (x4) * v[4];
+        jv[5] += (x0) * v[4];
+        jv[5] += (x4) * v[5];
+        jv[6] += (x0) * v[5];
+        jv[6] += (x4) * v[6];
+        jv[7] += (x0) * v[6];
+        jv[7] += (x4) * v[7];
+        jv[8] += (x0) * v[7];
+        jv[8] += (x4) * v[8];
+        jv[9] += (x0) * v[8];
+        jv[9] += (x4) * v[9];
+        jv[10] += (x0) * v[9];
+        jv[10] += (x4) * v[10];
+        jv[11] += (x0) * v[10];
+        jv[11] += (x4) * v[11];
+        jv[12] += (x0) * v[11];
+        jv[12] += (x4) * v[12];
+        jv[13] += (x0) * v[12];
+        jv[13] += (x4) * v[13];
+        jv[14] += (x0) * v[13];
+        jv[14] += (x4) * v[14];
+        jv[15] += (x0) * v[14];
+        jv[15] += (x4) * v[15];
+        jv[16] += (x0) * v[15];
+        jv[16] += (x4) * v[16];
+        jv[17] += (x0) * v[16];
+        jv[17] += (x4) * v[17];
+        jv[18] += (x0) * v[17];
+        jv[18] += (x4) * v[18];
+        jv[19] += (x0) * v[18];
+        jv[19] += (x4) * v[19];
+        jv[20] += (x0) * v[19];
+        jv[20] += (x4) * v[20];
+        jv[21] += (x0) * v[20];
+        jv[21] += (x4) * v[21];
+        jv[22] += (x0) * v[21];
+        jv[22] += (x4) * v[22];
+        jv[23] += (x0) * v[22];
+        jv[23] += (x4) * v[23];
+        jv[24] += (x0) * v[23];
+        jv[24] += (x4) * v[24];
+        jv[25] += (x0) * v[24];
+        jv[25] += (x4) * v[25];
+        jv[26] += (x0) * v[25];
+        jv[26] += (x4) * v[26];
+        jv[27] += (x0) * v[26];
+        jv[27] += (x4) * v[27];
+        jv[28] += (x0) * v[27];
+        jv[28] += (x4) * v[28];
+        jv[29] += (x0) * v[28];
+        jv[29] += (x4) * v[29];
+        jv[30] += (x0) * v[29];
+        jv[30] += (x4) * v[30];
+        jv[31] += (x0) * v[30];
+        jv[31] += (x4) * v[31];
+        jv[32] += (x0) * v[31];
+        jv[32] += (x4) * v[32];
+        jv[33] += (x0) * v[32];
+        jv[33] += (x4) * v[33];
+        jv[34] += (x0) * v[33];
+        jv[34] += (x4) * v[34];
+        jv[35] += (x0) * v[34];
+        jv[35] += (x4) * v[35];
+        jv[36] += (x0) * v[35];
+        jv[36] += (x4) * v[36];
+        jv[37] += (x0) * v[36];
+        jv[37] += (x4) * v[37];
+        jv[38] += (x0) * v[37];
+        jv[38] += (x4) * v[38];
+        jv[39] += (x0) * v[38];
+        jv[39] += (x4) * v[39];
+        jv[40] += (x0) * v[39];
+        jv[40] += (x4) * v[40];
+        jv[41] += (x0) * v[40];
+        jv[41] += (x4) * v[41];
+        jv[42] += (x0) * v[41];
+        jv[42] += (x4) * v[42];
+        jv[43] += (x0) * v[42];
+        jv[43] += (x4) * v[43];
+        jv[44] += (x0) * v[43];
+        jv[44] += (x4) * v[44];
+        jv[45] += (x0) * v[44];
+        jv[45] += (x4) * v[45];
+        jv[46] += (x0) * v[45];
+        jv[46] += (x4) * v[46];
+        jv[47] += (x0) * v[46];
+        jv[47] += (x4) * v[47];
+        jv[48] += (x0) * v[47];
+        jv[48] += (x4) * v[48];
+        jv[49] += (x0) * v[48];
+        jv[49] += (x4) * v[49];
+        jv[50] += (x0) * v[49];
+        jv[50] += (x4) * v[50];
+        jv[51] += (x0) * v[50];
+        jv[51] += (x4) * v[51];
+        jv[52] += (x0) * v[51];
+        jv[52] += (x4) * v[52];
+        jv[53] += (x0) * v[52];
+        jv[53] += (x4) * v[53];
+        jv[54] += (x0) * v[53];
+        jv[54] += (x4) * v[54];
+        jv[55] += (x0) * v[54];
+        jv[55] += (x4) * v[55];
+        jv[56] += (x0) * v[55];
+        jv[56] += (x4) * v[56];
+        jv[57] += (x0) * v[56];
+        jv[57] += (x4) * v[57];
+        jv[58] += (x0) * v[57];
+        jv[58] += (x4) * v[58];
+        jv[59] += (x0) * v[58];
+        jv[59] += (x4) * v[59];
+        jv[60] += (x0) * v[59];
+        jv[60] += (x4) * v[60];
+        jv[61] += (x0) * v[60];
+        jv[61] += (x4) * v[61];
+        jv[62] += (x0) * v[61];
+        jv[62] += (x4) * v[62];
+        jv[63] += (x0) * v[62];
+        jv[63] += (x4) * v[63];
+        jv[64] += (x0) * v[63];
+        jv[64] += (x4) * v[64];
+        jv[65] += (x0) * v[64];
+        jv[65] += (x4) * v[65];
+        jv[66] += (x0) * v[65];
+        jv[66] += (x4) * v[66];
+        jv[67] += (x0) * v[66];
+        jv[67] += (x4) * v[67];
+        jv[68] += (x0) * v[67];
+        jv[68] += (x4) * v[68];
+        jv[69] += (x0) * v[68];
+        jv[69] += (x4) * v[69];
+        jv[70] += (x0) * v[69];
+        jv[70] += (x4) * v[70];
+        jv[71] += (x0) * v[70];
+        jv[71] += (x4) * v[71];
+        jv[72] += (x0) * v[71];
+        jv[72] += (x4) * v[72];
+        jv[73] += (x0) * v[72];
+        jv[73] += (x4) * v[73];
+        jv[74] += (x0) * v[73];
+        jv[74] += (x4) * v[74];
+        jv[75] += (x0) * v[74];
+        jv[75] += (x4) * v[75];
+        jv[76] += (x0) * v[75];
+        jv[76] += (x4) * v[76];
+        jv[77] += (x0) * v[76];
+        jv[77] += (x4) * v[77];
+        jv[78] += (x0) * v[77];
+        jv[78] += (x4) * v[78];
+        jv[79] += (x0) * v[78];
+        jv[79] += (Vmax*x2*(x3 - 1.0)) * v[79];
+    }
+
+    fn init(p: &[f64], _t: f64, y: &mut [f64]) {
+        y.copy_from_slice(&p[4..84]);
+    }
+    fn defaults() -> serde_json::Value {
+        serde_json::from_str(&get_default_parameters()).unwrap()
+    }
+
+    fn options(sim_params: &SimulationParams) -> &SimulationOptions {
+        &sim_params.options
+    }
+
+    fn options_mut(sim_params: &mut SimulationParams) -> &mut SimulationOptions {
+        &mut sim_params.options
+    }
+
+    fn parameter_vector(sim_params: &SimulationParams) -> Vec<f64> {
+        parameter_vector(sim_params)
+    }
+
+    fn build_problem() -> BuiltProblem<'static> {
+        BuiltProblem::dense(MODEL, equations())
+    }
+
+    fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+        prepare_run(sim_params)
+    }
+}
+
+/// The run of checked parameters: the solver works in model time units, while
+/// results and checkpoints keep the caller's
+fn prepare_run(sim_params: SimulationParams) -> Result<Run, SimulationError> {
+    let resolved = serde_json::to_value(with_defaults(sim_params.clone())).unwrap();
+    let scale = time_scale(&sim_params);
+    let sim_params = in_model_time(sim_params);
+    let p = parameter_vector(&sim_params);
+    Ok(Run {
+        p,
+        resolved,
+        scale,
+        uptake_windows: vec![],
+        options: sim_params.options,
+    })
+}
+
+/// The model's functions for the solver; the closures capture nothing, so they
+/// are plain function pointers
+fn equations() -> Equations<'static> {
+    let eval_observables = |_y: &[f64], _t: f64, _p: &[f64]| -> Vec<f64> {
+        vec![]
+    };
+
+    let eval_volumes = |_y: &[f64], _t: f64, p: &[f64]| -> Vec<f64> {
+        let c = p[3];
+        vec![c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c, c]
+    };
+
+    let eval_mass = |y: &[f64], _p: &[f64]| -> f64 {
+        y[0] + y[1] + y[2] + y[3] + y[4] + y[5] + y[6] + y[7] + y[8] + y[9] + y[10] + y[11] + y[12] + y[13] + y[14] + y[15] + y[16] + y[17] + y[18] + y[19] + y[20] + y[21] + y[22] + y[23] + y[24] + y[25] + y[26] + y[27] + y[28] + y[29] + y[30] + y[31] + y[32] + y[33] + y[34] + y[35] + y[36] + y[37] + y[38] + y[39] + y[40] + y[41] + y[42] + y[43] + y[44] + y[45] + y[46] + y[47] + y[48] + y[49] + y[50] + y[51] + y[52] + y[53] + y[54] + y[55] + y[56] + y[57] + y[58] + y[59] + y[60] + y[61] + y[62] + y[63] + y[64] + y[65] + y[66] + y[67] + y[68] + y[69] + y[70] + y[71] + y[72] + y[73] + y[74] + y[75] + y[76] + y[77] + y[78] + y[79]
+    };
+
+    Equations {
+        rhs: TransitChainModel::rhs,
+        jac: TransitChainModel::jac,
+        init: TransitChainModel::init,
+        roots: no_roots,
+        triggers: no_triggers,
+        apply_events: no_events,
+        observables: eval_observables,
+        volumes: eval_volumes,
+        mass: eval_mass,
+        jac_matrix: None,
+    }
+}
+
+/// Solver stats of the last run of the last simulation call (JSON, null if it never started)
+pub fn get_last_run_stats() -> String {
+    LAST_RUN_STATS.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+pub fn run_simulation_at_times(params: &str, times: &[f64]) -> String {
+    wasm_pk_core::run_simulation_at_times::<TransitChainModel>(params, times)
+}
+
+/// Run a simulation and return `time,<species...>` CSV, one row per stored time
+pub fn run_simulation_csv(params: &str) -> Result<String, String> {
+    wasm_pk_core::run_simulation_csv::<TransitChainModel>(params)
+}
+
+/// Cmax, Tmax, AUC, AUC(0-inf), terminal half-life and trough of one species (JSON)
+pub fn compute_pk_metrics(result_json: &str, species: &str) -> Result<String, String> {
+    wasm_pk_core::compute_pk_metrics(result_json, species)
+}
+
+/// AUC of one species over each `[t1, t2]` window (JSON array, same order)
+pub fn compute_partial_auc(result_json: &str, species: &str, windows: &str) -> Result<String, String> {
+    wasm_pk_core::compute_partial_auc(result_json, species, windows)
+}
+
+/// Run the model once per value of one parameter, e.g. body weight from 20 to 100 kg
+pub fn run_parameter_sweep(base_params: &str, sweep: &str) -> Result<String, String> {
+    wasm_pk_core::run_parameter_sweep::<TransitChainModel>(base_params, sweep)
+}
+
+/// Continue a run_simulation result from its last recorded state for `additional_time`,
+/// with `new_params` merged over the parameters that run used. Its doses, output_times
+/// and uptake windows are not repeated; times in `new_params` count from the
+/// continuation start. The segment is returned on the combined time axis, after
+/// the earlier trajectory when `concatenate` is set.
+pub fn continue_simulation(previous_result: &str, new_params: &str, additional_time: f64, concatenate: bool) -> Result<String, String> {
+    wasm_pk_core::continue_simulation::<TransitChainModel>(previous_result, new_params, additional_time, concatenate)
+}
+
+/// Checkpoint of the last run of the last simulation call (JSON, null if it never
+/// started or its final state is not finite)
+pub fn export_checkpoint() -> String {
+    LAST_CHECKPOINT.with(|last| serde_json::to_string(&*last.borrow()).unwrap())
+}
+
+/// Resume an exported checkpoint up to the absolute `final_time` in `params`, which are
+/// merged over the checkpoint's parameters. Its pending doses are applied unless
+/// `params` gives new doses. The result and the new checkpoint use absolute time.
+pub fn run_from_checkpoint(checkpoint: &str, params: &str) -> Result<String, String> {
+    wasm_pk_core::run_from_checkpoint::<TransitChainModel>(checkpoint, params)
+}
+
+const SPECIES_IDS: &[&str] = &["T0", "T1", "T2", "T3", "T4", "T5", "T6", "T7", "T8", "T9", "T10", "T11", "T12", "T13", "T14", "T15", "T16", "T17", "T18", "T19", "T20", "T21", "T22", "T23", "T24", "T25", "T26", "T27", "T28", "T29", "T30", "T31", "T32", "T33", "T34", "T35", "T36", "T37", "T38", "T39", "T40", "T41", "T42", "T43", "T44", "T45", "T46", "T47", "T48", "T49", "T50", "T51", "T52", "T53", "T54", "T55", "T56", "T57", "T58", "T59", "T60", "T61", "T62", "T63", "T64", "T65", "T66", "T67", "T68", "T69", "T70", "T71", "T72", "T73", "T74", "T75", "T76", "T77", "T78", "T79"];
+
+/// Parameters read by the derivative of each state, for failure hints
+const STATE_PARAMETERS: [&[&str]; 80] = [&["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["ktr"], &["Km", "Vmax", "ktr"]];
+
+/// SBML ids of the assignment-rule observables, in evaluation order
+const OBSERVABLE_IDS: [&str; 0] = [];
+
+/// What the shared solver loop and option checks need to know about the model
+const MODEL: ModelInfo = ModelInfo {
+    species: SPECIES_IDS,
+    state_parameters: &STATE_PARAMETERS,
+    event_ids: &[],
+    event_targets: &[],
+    event_initial_values: &[],
+    event_persistent: &[],
+    observables: &OBSERVABLE_IDS,
+    species_is_amount: &SPECIES_IS_AMOUNT,
+    species_volumes: &SPECIES_VOLUMES,
+    series_units: &SERIES_UNITS,
+    time_seconds: MODEL_TIME_SECONDS,
+    time_unit: MODEL_TIME_UNIT,
+    parameters: PARAMETER_ORDER,
+    parameter_checks: PARAMETER_CHECKS,
+    algebraic: &[],
+};
+
+/// Run a simulation and return the trajectory as little-endian f64 bytes
+pub fn run_simulation_binary(params: &str) -> Result<Vec<u8>, String> {
+    wasm_pk_core::run_simulation_binary::<TransitChainModel>(params)
+}
+
+/// Describe the run_simulation_binary layout and its species order (JSON)
+pub fn get_binary_layout() -> String {
+    wasm_pk_core::get_binary_layout::<TransitChainModel>()
+}
+
+/// Decode run_simulation_binary output into times and named species series
+pub fn decode_binary_result(bytes: &[u8]) -> Result<(Vec<f64>, OrderedSeries), String> {
+    wasm_pk_core::decode_binary_result::<TransitChainModel>(bytes)
+}
+
+/// One observable of get_model_metadata
+#[derive(Serialize)]
+struct ObservableInfo {
+    id: &'static str,
+    units: Option<&'static str>,
+}
+
+/// SBML document the model was generated from
+#[derive(Serialize)]
+struct SbmlSource {
+    id: Option<&'static str>,
+    name: Option<&'static str>,
+    level: Option<u32>,
+    version: Option<u32>,
+    /// SHA-256 of the source file
+    sha256: Option<&'static str>,
+}
+
+/// Generator that wrote this module
+#[derive(Serialize)]
+struct GeneratorInfo {
+    name: &'static str,
+    version: Option<&'static str>,
+    /// ISO 8601 UTC time of generation
+    generated_at: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct ModelMetadata {
+    model_id: &'static str,
+    num_species: usize,
+    num_parameters: usize,
+    time_units: &'static str,
+    substance_units: &'static str,
+    volume_units: &'static str,
+    sbml: SbmlSource,
+    generator: GeneratorInfo,
+    diffsol_version: &'static str,
+    /// Optional features compiled in: "events", "sensitivities", "embedded_sbml"
+    features: &'static [&'static str],
+    observables: &'static [ObservableInfo],
+}
+
+pub fn get_model_metadata() -> String {
+    let metadata = ModelMetadata {
+        model_id: "transit_chain",
+        num_species: 80,
+        num_parameters: 4,
+        time_units: "HR",
+        substance_units: "MilliMOL",
+        volume_units: "L",
+        sbml: SbmlSource { id: None, name: None, level: None, version: None, sha256: None },
+        generator: GeneratorInfo { name: "sbml_rust_generator", version: Some("1.0.0"), generated_at: Some("2026-01-01T00:00:00Z") },
+        diffsol_version: "0.6.3",
+        features: &[],
+        observables: &[
+        ],
+    };
+    serde_json::to_string(&metadata).unwrap()
+}
+
+/// One entry of get_parameters_info
+#[derive(Serialize)]
+struct ParameterInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    units: Option<&'static str>,
+    /// SBML notes as plain text
+    description: Option<&'static str>,
+    default_value: f64,
+    required: bool,
+    is_compartment: bool,
+    /// Soft bounds for input forms; validate_parameters has the hard checks
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+const PARAMETER_INFO: &[ParameterInfo] = &[
+    ParameterInfo { id: "ktr", name: None, units: None, description: None, default_value: 4.0, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Vmax", name: None, units: None, description: None, default_value: 0.5, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "Km", name: None, units: None, description: None, default_value: 0.2, required: true, is_compartment: false, min: None, max: None },
+    ParameterInfo { id: "c", name: None, units: None, description: None, default_value: 1.0, required: true, is_compartment: true, min: Some(0.0), max: None },
+];
+
+pub fn get_parameters_info() -> String {
+    serde_json::to_string(PARAMETER_INFO).unwrap()
+}
+
+/// One entry of get_species_info
+#[derive(Serialize)]
+struct SpeciesInfo {
+    id: &'static str,
+    initial_amount: f64,
+    #[serde(rename = "override")]
+    override_field: &'static str,
+    units: Option<&'static str>,
+    compartment: Option<&'static str>,
+    compartment_volume: Option<f64>,
+    /// False when the SBML value is a concentration (hasOnlySubstanceUnits=false)
+    is_amount: bool,
+    is_boundary: bool,
+}
+
+pub fn get_species_info() -> String {
+    let p = parameter_vector(&TransitChainModel::default_params());
+    let species: &[SpeciesInfo] = &[
+        SpeciesInfo { id: "T0", initial_amount: 10.0, override_field: "init_T0", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T1", initial_amount: 0.0, override_field: "init_T1", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T2", initial_amount: 0.0, override_field: "init_T2", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T3", initial_amount: 0.0, override_field: "init_T3", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T4", initial_amount: 0.0, override_field: "init_T4", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T5", initial_amount: 0.0, override_field: "init_T5", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T6", initial_amount: 0.0, override_field: "init_T6", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T7", initial_amount: 0.0, override_field: "init_T7", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T8", initial_amount: 0.0, override_field: "init_T8", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T9", initial_amount: 0.0, override_field: "init_T9", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T10", initial_amount: 0.0, override_field: "init_T10", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T11", initial_amount: 0.0, override_field: "init_T11", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T12", initial_amount: 0.0, override_field: "init_T12", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T13", initial_amount: 0.0, override_field: "init_T13", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T14", initial_amount: 0.0, override_field: "init_T14", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T15", initial_amount: 0.0, override_field: "init_T15", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T16", initial_amount: 0.0, override_field: "init_T16", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T17", initial_amount: 0.0, override_field: "init_T17", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T18", initial_amount: 0.0, override_field: "init_T18", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T19", initial_amount: 0.0, override_field: "init_T19", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T20", initial_amount: 0.0, override_field: "init_T20", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T21", initial_amount: 0.0, override_field: "init_T21", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T22", initial_amount: 0.0, override_field: "init_T22", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T23", initial_amount: 0.0, override_field: "init_T23", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T24", initial_amount: 0.0, override_field: "init_T24", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T25", initial_amount: 0.0, override_field: "init_T25", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T26", initial_amount: 0.0, override_field: "init_T26", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T27", initial_amount: 0.0, override_field: "init_T27", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T28", initial_amount: 0.0, override_field: "init_T28", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T29", initial_amount: 0.0, override_field: "init_T29", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T30", initial_amount: 0.0, override_field: "init_T30", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T31", initial_amount: 0.0, override_field: "init_T31", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T32", initial_amount: 0.0, override_field: "init_T32", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T33", initial_amount: 0.0, override_field: "init_T33", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T34", initial_amount: 0.0, override_field: "init_T34", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T35", initial_amount: 0.0, override_field: "init_T35", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T36", initial_amount: 0.0, override_field: "init_T36", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T37", initial_amount: 0.0, override_field: "init_T37", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T38", initial_amount: 0.0, override_field: "init_T38", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T39", initial_amount: 0.0, override_field: "init_T39", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T40", initial_amount: 0.0, override_field: "init_T40", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T41", initial_amount: 0.0, override_field: "init_T41", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T42", initial_amount: 0.0, override_field: "init_T42", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T43", initial_amount: 0.0, override_field: "init_T43", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T44", initial_amount: 0.0, override_field: "init_T44", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T45", initial_amount: 0.0, override_field: "init_T45", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T46", initial_amount: 0.0, override_field: "init_T46", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T47", initial_amount: 0.0, override_field: "init_T47", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T48", initial_amount: 0.0, override_field: "init_T48", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T49", initial_amount: 0.0, override_field: "init_T49", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T50", initial_amount: 0.0, override_field: "init_T50", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T51", initial_amount: 0.0, override_field: "init_T51", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T52", initial_amount: 0.0, override_field: "init_T52", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T53", initial_amount: 0.0, override_field: "init_T53", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T54", initial_amount: 0.0, override_field: "init_T54", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T55", initial_amount: 0.0, override_field: "init_T55", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T56", initial_amount: 0.0, override_field: "init_T56", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T57", initial_amount: 0.0, override_field: "init_T57", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T58", initial_amount: 0.0, override_field: "init_T58", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T59", initial_amount: 0.0, override_field: "init_T59", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T60", initial_amount: 0.0, override_field: "init_T60", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T61", initial_amount: 0.0, override_field: "init_T61", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T62", initial_amount: 0.0, override_field: "init_T62", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T63", initial_amount: 0.0, override_field: "init_T63", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T64", initial_amount: 0.0, override_field: "init_T64", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T65", initial_amount: 0.0, override_field: "init_T65", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T66", initial_amount: 0.0, override_field: "init_T66", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T67", initial_amount: 0.0, override_field: "init_T67", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T68", initial_amount: 0.0, override_field: "init_T68", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T69", initial_amount: 0.0, override_field: "init_T69", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T70", initial_amount: 0.0, override_field: "init_T70", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T71", initial_amount: 0.0, override_field: "init_T71", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T72", initial_amount: 0.0, override_field: "init_T72", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T73", initial_amount: 0.0, override_field: "init_T73", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T74", initial_amount: 0.0, override_field: "init_T74", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T75", initial_amount: 0.0, override_field: "init_T75", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T76", initial_amount: 0.0, override_field: "init_T76", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T77", initial_amount: 0.0, override_field: "init_T77", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T78", initial_amount: 0.0, override_field: "init_T78", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+        SpeciesInfo { id: "T79", initial_amount: 0.0, override_field: "init_T79", units: None, compartment: Some("c"), compartment_volume: Some(p[3]), is_amount: false, is_boundary: false },
+    ];
+    serde_json::to_string(&species).unwrap()
+}
+
+/// Whether each species' values are amounts rather than concentrations
+const SPECIES_IS_AMOUNT: [bool; 80] = [false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false];
+/// Compartment converting each species between amount and concentration, or why none does
+const SPECIES_VOLUMES: [Result<&str, &str>; 80] = [Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c"), Ok("c")];
+
+/// Unit of each species and observable series; None where the SBML gives none
+const SERIES_UNITS: [(&str, Option<&str>); 80] = [
+    ("T0", None),
+    ("T1", None),
+    ("T2", None),
+    ("T3", None),
+    ("T4", None),
+    ("T5", None),
+    ("T6", None),
+    ("T7", None),
+    ("T8", None),
+    ("T9", None),
+    ("T10", None),
+    ("T11", None),
+    ("T12", None),
+    ("T13", None),
+    ("T14", None),
+    ("T15", None),
+    ("T16", None),
+    ("T17", None),
+    ("T18", None),
+    ("T19", None),
+    ("T20", None),
+    ("T21", None),
+    ("T22", None),
+    ("T23", None),
+    ("T24", None),
+    ("T25", None),
+    ("T26", None),
+    ("T27", None),
+    ("T28", None),
+    ("T29", None),
+    ("T30", None),
+    ("T31", None),
+    ("T32", None),
+    ("T33", None),
+    ("T34", None),
+    ("T35", None),
+    ("T36", None),
+    ("T37", None),
+    ("T38", None),
+    ("T39", None),
+    ("T40", None),
+    ("T41", None),
+    ("T42", None),
+    ("T43", None),
+    ("T44", None),
+    ("T45", None),
+    ("T46", None),
+    ("T47", None),
+    ("T48", None),
+    ("T49", None),
+    ("T50", None),
+    ("T51", None),
+    ("T52", None),
+    ("T53", None),
+    ("T54", None),
+    ("T55", None),
+    ("T56", None),
+    ("T57", None),
+    ("T58", None),
+    ("T59", None),
+    ("T60", None),
+    ("T61", None),
+    ("T62", None),
+    ("T63", None),
+    ("T64", None),
+    ("T65", None),
+    ("T66", None),
+    ("T67", None),
+    ("T68", None),
+    ("T69", None),
+    ("T70", None),
+    ("T71", None),
+    ("T72", None),
+    ("T73", None),
+    ("T74", None),
+    ("T75", None),
+    ("T76", None),
+    ("T77", None),
+    ("T78", None),
+    ("T79", None),
+];
+
+/// One entry of get_compartments_info
+#[derive(Serialize)]
+struct CompartmentInfo {
+    id: &'static str,
+    name: Option<&'static str>,
+    /// Size at the default parameters; None when it changes during a run
+    size: Option<f64>,
+    /// Rule or initial assignment computing the size, e.g. "BM*scVLiver"
+    formula: Option<&'static str>,
+    units: Option<&'static str>,
+    species: &'static [&'static str],
+}
+
+pub fn get_compartments_info() -> String {
+    let p = parameter_vector(&TransitChainModel::default_params());
+    let compartments: &[CompartmentInfo] = &[
+        CompartmentInfo { id: "c", name: None, size: Some(p[3]), formula: None, units: None, species: &["T0", "T1", "T2", "T3", "T4", "T5", "T6", "T7", "T8", "T9", "T10", "T11", "T12", "T13", "T14", "T15", "T16", "T17", "T18", "T19", "T20", "T21", "T22", "T23", "T24", "T25", "T26", "T27", "T28", "T29", "T30", "T31", "T32", "T33", "T34", "T35", "T36", "T37", "T38", "T39", "T40", "T41", "T42", "T43", "T44", "T45", "T46", "T47", "T48", "T49", "T50", "T51", "T52", "T53", "T54", "T55", "T56", "T57", "T58", "T59", "T60", "T61", "T62", "T63", "T64", "T65", "T66", "T67", "T68", "T69", "T70", "T71", "T72", "T73", "T74", "T75", "T76", "T77", "T78", "T79"] },
+    ];
+    serde_json::to_string(&compartments).unwrap()
+}
+
+pub fn get_default_parameters() -> String {
+    let defaults: &[(&str, f64)] = &[
+        ("ktr", 4.0),
+        ("Vmax", 0.5),
+        ("Km", 0.2),
+        ("c", 1.0),
+        ("final_time", 24.0),
+    ];
+    let defaults: serde_json::Map<String, serde_json::Value> = defaults.iter().map(|&(id, value)| (id.to_string(), value.into())).collect();
+    serde_json::Value::Object(defaults).to_string()
+}
+/// Equations in model order, serialized as a JSON object
+struct EquationTable(&'static [(&'static str, &'static str)]);
+
+impl Serialize for EquationTable {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (id, expression) in self.0 {
+            map.serialize_entry(id, expression)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Serialize)]
+struct ModelEquations {
+    derivatives: EquationTable,
+    assignment_rules: EquationTable,
+}
+
+pub fn get_model_equations() -> String {
+    let equations = ModelEquations {
+        derivatives: EquationTable(&[
+            ("T0", "-T0*ktr"),
+            ("T1", "T0*ktr - T1*ktr"),
+            ("T2", "T1*ktr - T2*ktr"),
+            ("T3", "T2*ktr - T3*ktr"),
+            ("T4", "T3*ktr - T4*ktr"),
+            ("T5", "T4*ktr - T5*ktr"),
+            ("T6", "T5*ktr - T6*ktr"),
+            ("T7", "T6*ktr - T7*ktr"),
+            ("T8", "T7*ktr - T8*ktr"),
+            ("T9", "T8*ktr - T9*ktr"),
+            ("T10", "-T10*ktr + T9*ktr"),
+            ("T11", "T10*ktr - T11*ktr"),
+            ("T12", "T11*ktr - T12*ktr"),
+            ("T13", "T12*ktr - T13*ktr"),
+            ("T14", "T13*ktr - T14*ktr"),
+            ("T15", "T14*ktr - T15*ktr"),
+            ("T16", "T15*ktr - T16*ktr"),
+            ("T17", "T16*ktr - T17*ktr"),
+            ("T18", "T17*ktr - T18*ktr"),
+            ("T19", "T18*ktr - T19*ktr"),
+            ("T20", "T19*ktr - T20*ktr"),
+            ("T21", "T20*ktr - T21*ktr"),
+            ("T22", "T21*ktr - T22*ktr"),
+            ("T23", "T22*ktr - T23*ktr"),
+            ("T24", "T23*ktr - T24*ktr"),
+            ("T25", "T24*ktr - T25*ktr"),
+            ("T26", "T25*ktr - T26*ktr"),
+            ("T27", "T26*ktr - T27*ktr"),
+            ("T28", "T27*ktr - T28*ktr"),
+            ("T29", "T28*ktr - T29*ktr"),
+            ("T30", "T29*ktr - T30*ktr"),
+            ("T31", "T30*ktr - T31*ktr"),
+            ("T32", "T31*ktr - T32*ktr"),
+            ("T33", "T32*ktr - T33*ktr"),
+            ("T34", "T33*ktr - T34*ktr"),
+            ("T35", "T34*ktr - T35*ktr"),
+            ("T36", "T35*ktr - T36*ktr"),
+            ("T37", "T36*ktr - T37*ktr"),
+            ("T38", "T37*ktr - T38*ktr"),
+            ("T39", "T38*ktr - T39*ktr"),
+            ("T40", "T39*ktr - T40*ktr"),
+            ("T41", "T40*ktr - T41*ktr"),
+            ("T42", "T41*ktr - T42*ktr"),
+            ("T43", "T42*ktr - T43*ktr"),
+            ("T44", "T43*ktr - T44*ktr"),
+            ("T45", "T44*ktr - T45*ktr"),
+            ("T46", "T45*ktr - T46*ktr"),
+            ("T47", "T46*ktr - T47*ktr"),
+            ("T48", "T47*ktr - T48*ktr"),
+            ("T49", "T48*ktr - T49*ktr"),
+            ("T50", "T49*ktr - T50*ktr"),
+            ("T51", "T50*ktr - T51*ktr"),
+            ("T52", "T51*ktr - T52*ktr"),
+            ("T53", "T52*ktr - T53*ktr"),
+            ("T54", "T53*ktr - T54*ktr"),
+            ("T55", "T54*ktr - T55*ktr"),
+            ("T56", "T55*ktr - T56*ktr"),
+            ("T57", "T56*ktr - T57*ktr"),
+            ("T58", "T57*ktr - T58*ktr"),
+            ("T59", "T58*ktr - T59*ktr"),
+            ("T60", "T59*ktr - T60*ktr"),
+            ("T61", "T60*ktr - T61*ktr"),
+            ("T62", "T61*ktr - T62*ktr"),
+            ("T63", "T62*ktr - T63*ktr"),
+            ("T64", "T63*ktr - T64*ktr"),
+            ("T65", "T64*ktr - T65*ktr"),
+            ("T66", "T65*ktr - T66*ktr"),
+            ("T67", "T66*ktr - T67*ktr"),
+            ("T68", "T67*ktr - T68*ktr"),
+            ("T69", "T68*ktr - T69*ktr"),
+            ("T70", "T69*ktr - T70*ktr"),
+            ("T71", "T70*ktr - T71*ktr"),
+            ("T72", "T71*ktr - T72*ktr"),
+            ("T73", "T72*ktr - T73*ktr"),
+            ("T74", "T73*ktr - T74*ktr"),
+            ("T75", "T74*ktr - T75*ktr"),
+            ("T76", "T75*ktr - T76*ktr"),
+            ("T77", "T76*ktr - T77*ktr"),
+            ("T78", "T77*ktr - T78*ktr"),
+            ("T79", "T78*ktr - T79*Vmax/(Km + T79)"),
+        ]),
+        assignment_rules: EquationTable(&[
+        ]),
+    };
+    serde_json::to_string_pretty(&equations).unwrap()
+}
+/// SBML document this module was generated from
+pub fn get_model_sbml() -> Result<String, String> {
+    Err("this model was not generated from an SBML file".to_string())
+}
+/// TypeScript definitions of the parameters, result and metadata of this model
+const TYPES_TS: &str = r#"// TypeScript definitions for transit_chain, generated with the module
+
+export type SpeciesId = "T0" | "T1" | "T2" | "T3" | "T4" | "T5" | "T6" | "T7" | "T8" | "T9" | "T10" | "T11" | "T12" | "T13" | "T14" | "T15" | "T16" | "T17" | "T18" | "T19" | "T20" | "T21" | "T22" | "T23" | "T24" | "T25" | "T26" | "T27" | "T28" | "T29" | "T30" | "T31" | "T32" | "T33" | "T34" | "T35" | "T36" | "T37" | "T38" | "T39" | "T40" | "T41" | "T42" | "T43" | "T44" | "T45" | "T46" | "T47" | "T48" | "T49" | "T50" | "T51" | "T52" | "T53" | "T54" | "T55" | "T56" | "T57" | "T58" | "T59" | "T60" | "T61" | "T62" | "T63" | "T64" | "T65" | "T66" | "T67" | "T68" | "T69" | "T70" | "T71" | "T72" | "T73" | "T74" | "T75" | "T76" | "T77" | "T78" | "T79";
+export type ObservableId = never;
+export type SolverMethod = "bdf" | "tr_bdf2";
+export type JacobianMode = "analytic" | "finite_difference";
+export type SimulationMode = "transient" | "steady_state";
+export type TimeUnit = "s" | "min" | "h" | "d";
+export type OutputKind = "amount" | "concentration" | "both";
+
+export interface Dose {
+  time: number;
+  amount: number;
+  target: SpeciesId;
+  units?: string | null;
+}
+
+/** Input of run_simulation; omitted fields take the model defaults */
+export interface SimulationParams {
+  ktr?: number;
+  Vmax?: number;
+  Km?: number;
+  c?: number;
+  init_T0?: number | null;
+  init_T1?: number | null;
+  init_T2?: number | null;
+  init_T3?: number | null;
+  init_T4?: number | null;
+  init_T5?: number | null;
+  init_T6?: number | null;
+  init_T7?: number | null;
+  init_T8?: number | null;
+  init_T9?: number | null;
+  init_T10?: number | null;
+  init_T11?: number | null;
+  init_T12?: number | null;
+  init_T13?: number | null;
+  init_T14?: number | null;
+  init_T15?: number | null;
+  init_T16?: number | null;
+  init_T17?: number | null;
+  init_T18?: number | null;
+  init_T19?: number | null;
+  init_T20?: number | null;
+  init_T21?: number | null;
+  init_T22?: number | null;
+  init_T23?: number | null;
+  init_T24?: number | null;
+  init_T25?: number | null;
+  init_T26?: number | null;
+  init_T27?: number | null;
+  init_T28?: number | null;
+  init_T29?: number | null;
+  init_T30?: number | null;
+  init_T31?: number | null;
+  init_T32?: number | null;
+  init_T33?: number | null;
+  init_T34?: number | null;
+  init_T35?: number | null;
+  init_T36?: number | null;
+  init_T37?: number | null;
+  init_T38?: number | null;
+  init_T39?: number | null;
+  init_T40?: number | null;
+  init_T41?: number | null;
+  init_T42?: number | null;
+  init_T43?: number | null;
+  init_T44?: number | null;
+  init_T45?: number | null;
+  init_T46?: number | null;
+  init_T47?: number | null;
+  init_T48?: number | null;
+  init_T49?: number | null;
+  init_T50?: number | null;
+  init_T51?: number | null;
+  init_T52?: number | null;
+  init_T53?: number | null;
+  init_T54?: number | null;
+  init_T55?: number | null;
+  init_T56?: number | null;
+  init_T57?: number | null;
+  init_T58?: number | null;
+  init_T59?: number | null;
+  init_T60?: number | null;
+  init_T61?: number | null;
+  init_T62?: number | null;
+  init_T63?: number | null;
+  init_T64?: number | null;
+  init_T65?: number | null;
+  init_T66?: number | null;
+  init_T67?: number | null;
+  init_T68?: number | null;
+  init_T69?: number | null;
+  init_T70?: number | null;
+  init_T71?: number | null;
+  init_T72?: number | null;
+  init_T73?: number | null;
+  init_T74?: number | null;
+  init_T75?: number | null;
+  init_T76?: number | null;
+  init_T77?: number | null;
+  init_T78?: number | null;
+  init_T79?: number | null;
+  t_start?: number | null;
+  final_time?: number | null;
+  time_unit?: TimeUnit | null;
+  convert_output_time?: boolean | null;
+  output_times?: number[] | null;
+  output_dt?: number | null;
+  max_output_points?: number | null;
+  include_observables?: boolean | null;
+  output_kind?: OutputKind | null;
+  include_parameters?: boolean | null;
+  mass_balance?: boolean | null;
+  validate?: boolean | null;
+  doses?: Dose[] | null;
+  rtol?: number | null;
+  atol?: number | null;
+  max_steps?: number | null;
+  max_step?: number | null;
+  initial_step?: number | null;
+  check_finite?: boolean | null;
+  clamp_nonnegative?: boolean | null;
+  clamp_threshold?: number | null;
+  solver?: SolverMethod | null;
+  jacobian?: JacobianMode | null;
+  mode?: SimulationMode | null;
+  steady_state_tol?: number | null;
+  steady_state_exclude?: string[] | null;
+  steady_state_min_time?: number | null;
+}
+
+export interface SimulationResult {
+  species: Record<SpeciesId, number[]>;
+  time: number[];
+  /** null when the parameters were rejected or include_parameters is false */
+  parameters: SimulationParams | null;
+  /** Filled when include_observables is set */
+  observables: Partial<Record<ObservableId, number[]>>;
+  /** Unit of each species and observable series and of time; null if unknown */
+  units: Record<string, string | null>;
+  /** Filled when output_kind asks for them */
+  amounts: Partial<Record<SpeciesId, number[]>>;
+  concentrations: Partial<Record<SpeciesId, number[]>>;
+  /** Species left in their native representation, and why */
+  notes: string[];
+  steady_state: SteadyState | null;
+  applied_doses: Dose[];
+  events: EventRecord[];
+  /** Whether each event trigger held at the end; continuations start from it */
+  trigger_state: boolean[];
+  steps: number;
+  stats: SolverStats | null;
+  diagnostics: Diagnostics | null;
+  error: SimulationError | null;
+  timing: Timing;
+}
+
+/** Wall-clock milliseconds of the call */
+export interface Timing {
+  solve_ms: number;
+  serialize_ms: number;
+  total_ms: number;
+}
+
+export interface SteadyState {
+  reached: boolean;
+  time: number;
+  rms: number;
+  state: number[];
+}
+
+export interface EventRecord {
+  time: number;
+  id: string;
+  assignments: Partial<Record<SpeciesId, number>>;
+}
+
+export interface SolverStats {
+  steps: number;
+  rhs_evals: number;
+  jacobian_evals: number;
+  newton_iterations: number;
+  newton_failures: number;
+  final_step_size: number;
+}
+
+export interface Diagnostics {
+  total_mass: number[];
+  max_mass_drift: number;
+}
+
+export interface SimulationError {
+  kind: "invalid_params" | "setup" | "solver_failure" | "event_error" | "max_steps_exceeded" | "cancelled";
+  message: string;
+  field: string | null;
+  time: number | null;
+  state: number[] | null;
+  hint: string | null;
+}
+
+/** get_model_metadata */
+export interface ModelMetadata {
+  model_id: string;
+  num_species: number;
+  num_parameters: number;
+  time_units: string;
+  substance_units: string;
+  volume_units: string;
+  sbml: {
+    id: string | null;
+    name: string | null;
+    level: number | null;
+    version: number | null;
+    sha256: string | null;
+  };
+  generator: { name: string; version: string | null; generated_at: string | null };
+  diffsol_version: string;
+  features: ("events" | "sensitivities" | "embedded_sbml")[];
+  observables: { id: ObservableId; units: string | null }[];
+}
+
+/** One entry of get_parameters_info */
+export interface ParameterInfo {
+  id: keyof SimulationParams;
+  name: string | null;
+  units: string | null;
+  description: string | null;
+  default_value: number;
+  required: boolean;
+  is_compartment: boolean;
+  min: number | null;
+  max: number | null;
+}
+
+/** One entry of get_species_info */
+export interface SpeciesInfo {
+  id: SpeciesId;
+  initial_amount: number;
+  override: keyof SimulationParams;
+  units: string | null;
+  compartment: string | null;
+  compartment_volume: number | null;
+  is_amount: boolean;
+  is_boundary: boolean;
+}
+
+/** One entry of get_compartments_info */
+export interface CompartmentInfo {
+  id: string;
+  name: string | null;
+  size: number | null;
+  formula: string | null;
+  units: string | null;
+  species: SpeciesId[];
+}
+
+/** get_model_equations */
+export interface ModelEquations {
+  derivatives: Record<SpeciesId, string>;
+  assignment_rules: Record<string, string>;
+}
+"#;
+
+pub fn get_types_ts() -> String {
+    TYPES_TS.to_string()
+}
+
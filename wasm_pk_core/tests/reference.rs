@@ -0,0 +1,115 @@
+//! Trajectories of generated models against reference time-courses
+//!
+//! Each model is run natively on the time grid of a CSV under testdata, with
+//! one column per species, and must stay within a relative tolerance of it.
+//! The error of a species is its largest deviation over the run divided by
+//! the largest magnitude of its reference, so species that start or stay at
+//! zero do not blow up the ratio. A failure names the worst species and time.
+//! To add a model, check its generated source into tests/models, its
+//! reference (e.g. a COPASI or roadrunner export) into testdata and add a
+//! `reference_test!` line below.
+
+#![cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+
+use serde_json::Value;
+use std::path::PathBuf;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/event_dose.rs"]
+mod event_dose;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/protein_binding.rs"]
+mod protein_binding;
+
+/// Columns of a reference CSV: the times and each species' values
+struct Reference {
+    time: Vec<f64>,
+    species: Vec<(String, Vec<f64>)>,
+}
+
+/// Read testdata/<file>, whose header is the time column then species ids
+///
+/// Ids may be wrapped in brackets and the header may start with `#`, as in
+/// COPASI and roadrunner exports.
+fn read_reference(file: &str) -> Reference {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "testdata", file].iter().collect();
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().unwrap_or_else(|| panic!("{}: empty file", file));
+    let ids: Vec<String> = header
+        .trim_start_matches('#')
+        .split(',')
+        .map(|id| id.trim().trim_start_matches('[').trim_end_matches(']').to_string())
+        .collect();
+    let mut columns = vec![Vec::new(); ids.len()];
+    for (row, line) in lines.enumerate() {
+        let values: Vec<&str> = line.split(',').collect();
+        assert_eq!(values.len(), ids.len(), "{}: row {} has {} columns", file, row + 2, values.len());
+        for (column, value) in columns.iter_mut().zip(values) {
+            column.push(value.trim().parse().unwrap_or_else(|e| panic!("{}: row {}: {:?}: {}", file, row + 2, value, e)));
+        }
+    }
+    let time = columns.remove(0);
+    Reference { time, species: ids.into_iter().skip(1).zip(columns).collect() }
+}
+
+/// Run `run_simulation` on the reference's grid and compare every species with it
+///
+/// `params` are extra run_simulation parameters, such as tight solver tolerances.
+fn check_reference(model: &str, run_simulation: fn(&str) -> String, file: &str, params: &str, tolerance: f64) {
+    let reference = read_reference(file);
+    let mut options: serde_json::Map<String, Value> = serde_json::from_str(params).unwrap();
+    options.insert("output_times".into(), reference.time.clone().into());
+    options.insert("final_time".into(), reference.time.last().copied().unwrap_or(0.0).into());
+    let result: Value = serde_json::from_str(&run_simulation(&Value::Object(options).to_string())).unwrap();
+    assert!(result["error"].is_null(), "{}: {}", model, result["error"]);
+    let time: Vec<f64> = serde_json::from_value(result["time"].clone()).unwrap();
+    assert_eq!(time, reference.time, "{}: output times differ from {}", model, file);
+    // Relative error, time and simulated and reference values of the worst point per species
+    let mut errors = Vec::new();
+    for (id, expected) in &reference.species {
+        let actual: Vec<f64> = serde_json::from_value(result["species"][id].clone())
+            .unwrap_or_else(|_| panic!("{}: no species {} from {}", model, id, file));
+        let scale = expected.iter().fold(1e-12_f64, |m, v| m.max(v.abs()));
+        let worst = time
+            .iter()
+            .zip(actual.iter().zip(expected))
+            .map(|(&t, (&a, &e))| ((a - e).abs() / scale, t, a, e))
+            .fold((0.0, 0.0, 0.0, 0.0), |worst, point| if point.0 > worst.0 { point } else { worst });
+        errors.push((id, worst));
+    }
+    let (id, (error, t, actual, expected)) = errors
+        .into_iter()
+        .max_by(|a, b| a.1 .0.total_cmp(&b.1 .0))
+        .unwrap_or_else(|| panic!("{}: {} has no species columns", model, file));
+    assert!(
+        error <= tolerance,
+        "{}: {} off by {:e} (relative, tolerance {:e}) at t = {}: {} vs reference {}",
+        model, id, error, tolerance, t, actual, expected
+    );
+}
+
+/// Compare `$model` with testdata/`$file`, passing `$params` to run_simulation
+macro_rules! reference_test {
+    ($name:ident, $model:ident, $file:expr, $params:expr, $tolerance:expr) => {
+        #[test]
+        fn $name() {
+            check_reference(stringify!($model), $model::run_simulation, $file, $params, $tolerance);
+        }
+    };
+}
+
+// Closed-form solution: a bolus of Dose into Gut at t = 2, then first-order
+// absorption and elimination (see testdata/README.md)
+reference_test!(event_dose_matches_reference, event_dose, "event_dose.csv", r#"{"rtol": 1e-10, "atol": 1e-12}"#, 1e-6);
+
+// A DAE: the free concentration Cfree is fixed by an algebraic rule, the
+// binding equilibrium, at every step (see testdata/README.md)
+reference_test!(protein_binding_matches_reference, protein_binding, "protein_binding.csv", r#"{"rtol": 1e-10, "atol": 1e-12}"#, 1e-6);
+
+// The initial value of Cfree is only a guess for the algebraic rule, so a
+// guess off the solution must give the same trajectory
+reference_test!(
+    protein_binding_ignores_the_algebraic_guess, protein_binding, "protein_binding.csv", r#"{"Cfree": 0.2, "rtol": 1e-10, "atol": 1e-12}"#, 1e-6
+);
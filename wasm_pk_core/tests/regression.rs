@@ -0,0 +1,324 @@
+//! Golden results of the fixture models in tests/models
+//!
+//! The fixtures are generated from tests/fixture_models.py. Every scenario
+//! runs through a model's exported entry points and its output, without
+//! wall-clock timings, must equal the JSON under tests/golden exactly.
+//! After a deliberate change of results, rewrite the golden files with
+//! `WASM_PK_BLESS=1 cargo test --test regression` and review their diff.
+//! Without wasm32 the fixtures' JS glue is compiled out, so the suite also
+//! runs natively with the `wasm` feature on.
+
+#![cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+
+use serde_json::Value;
+use std::path::PathBuf;
+use wasm_pk_core::{simulate, PkModel};
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/two_compartment.rs"]
+mod two_compartment;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/event_dose.rs"]
+mod event_dose;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/infusion.rs"]
+mod infusion;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/oral_window.rs"]
+mod oral_window;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/transit_chain.rs"]
+mod transit_chain;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/transit_chain_sparse.rs"]
+mod transit_chain_sparse;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/event_dose_jacobian_matrix.rs"]
+mod event_dose_jacobian_matrix;
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/transit_chain_jacobian_matrix.rs"]
+mod transit_chain_jacobian_matrix;
+
+/// Parameter sets run through run_simulation for every model
+const SCENARIOS: &[(&str, &str)] = &[
+    ("defaults", "{}"),
+    ("output_times", r#"{"output_times": [0, 0.5, 1, 2, 3.5, 8, 24]}"#),
+    ("output_dt", r#"{"output_dt": 0.75, "final_time": 10}"#),
+    ("output_dt_max_output_points", r#"{"output_dt": 0.5, "max_output_points": 10}"#),
+    ("t_start", r#"{"t_start": 1, "final_time": 12}"#),
+    ("t_start_output_times", r#"{"t_start": 1, "final_time": 12, "output_times": [1.5, 2, 11]}"#),
+    ("max_output_points", r#"{"max_output_points": 8}"#),
+    ("steady_state", r#"{"mode": "steady_state", "final_time": 2000, "steady_state_tol": 1e-4}"#),
+    ("outputs", r#"{"include_observables": true, "mass_balance": true, "output_kind": "both", "include_parameters": false}"#),
+    ("clamp", r#"{"clamp_nonnegative": true, "clamp_threshold": 1}"#),
+    ("tr_bdf2", r#"{"solver": "tr_bdf2", "rtol": 1e-8, "atol": 1e-10}"#),
+    ("finite_difference", r#"{"jacobian": "finite_difference"}"#),
+    ("step_limits", r#"{"max_step": 0.25, "initial_step": 0.01}"#),
+    ("max_steps", r#"{"max_steps": 5}"#),
+    ("time_unit", r#"{"time_unit": "min", "final_time": 600, "convert_output_time": true}"#),
+    ("bad_number", r#"{"final_time": "abc"}"#),
+    ("bad_option", r#"{"rtol": -1}"#),
+    ("unknown", r#"{"rtol_": 1e-6, "uptake_windows": []}"#),
+    ("final_before_start", r#"{"t_start": 5, "final_time": 2}"#),
+];
+
+/// Drop the wall-clock timings, the only output that differs between runs
+fn strip_timing(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.remove("timing");
+            map.values_mut().for_each(strip_timing);
+        }
+        Value::Array(values) => values.iter_mut().for_each(strip_timing),
+        _ => {}
+    }
+}
+
+/// JSON output as a value, an error as {"err": message}, and text as a string
+fn normalize(output: Result<String, String>) -> Value {
+    let mut value = match output {
+        Ok(text) => serde_json::from_str(&text).unwrap_or(Value::String(text)),
+        Err(message) => serde_json::json!({ "err": message }),
+    };
+    strip_timing(&mut value);
+    value
+}
+
+/// Compare with tests/golden/<model>.json, or rewrite it under WASM_PK_BLESS
+fn check_golden(model: &str, outputs: Vec<(String, Value)>) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "golden", &format!("{}.json", model)].iter().collect();
+    let actual = Value::Object(outputs.into_iter().collect());
+    if std::env::var_os("WASM_PK_BLESS").is_some() {
+        std::fs::write(&path, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+        return;
+    }
+    let golden: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    for (scenario, expected) in golden.as_object().unwrap() {
+        assert_eq!(actual.get(scenario), Some(expected), "{}: scenario {} changed", model, scenario);
+    }
+    assert_eq!(actual.as_object().unwrap().len(), golden.as_object().unwrap().len(), "{}: scenarios added", model);
+}
+
+/// Run the shared scenarios and the model's own through every entry point of `$model`
+macro_rules! golden_test {
+    ($name:ident, $model:ident, $species:expr, $parameter:expr, [$(($scenario:expr, $params:expr)),* $(,)?]) => {
+        #[test]
+        fn $name() {
+            use $model as model;
+            let species: &str = $species;
+            let mut outputs = Vec::new();
+            let own: &[(&str, String)] = &[
+                ("doses", format!(
+                    r#"{{"doses": [{{"time": 0, "amount": 0.5, "target": "{0}"}}, {{"time": 3, "amount": "1.5", "target": "{0}"}}, {{"time": 30, "amount": 1, "target": "{0}"}}], "mass_balance": true}}"#,
+                    species
+                )),
+                ("dose_unknown_species", r#"{"doses": [{"time": 1, "amount": 1, "target": "nope"}]}"#.to_string()),
+                $(($scenario, $params.to_string()),)*
+            ];
+            let runs = SCENARIOS.iter().map(|&(name, params)| (name, params.to_string())).chain(own.iter().cloned());
+            for (name, params) in runs {
+                outputs.push((name.to_string(), normalize(Ok(model::run_simulation(&params)))));
+            }
+            model::run_simulation("{}");
+            outputs.push(("last_run_stats".to_string(), normalize(Ok(model::get_last_run_stats()))));
+            outputs.push(("default_parameters".to_string(), normalize(Ok(model::get_default_parameters()))));
+            let batch = r#"[{}, {"final_time": 6}, {"rtol": 0}]"#;
+            outputs.push(("batch".to_string(), normalize(model::run_simulation_batch(batch))));
+            let csv = model::run_simulation_csv(r#"{"final_time": 6}"#);
+            outputs.push(("csv".to_string(), csv.map_or_else(|e| serde_json::json!({ "err": e }), Value::String)));
+            let sweep = format!(r#"{{"parameter": "{}", "values": [0.5, 1, 2], "species": "{}"}}"#, $parameter, species);
+            outputs.push(("sweep".to_string(), normalize(model::run_parameter_sweep("{}", &sweep))));
+            let previous = model::run_simulation(r#"{"final_time": 6}"#);
+            let doses = format!(r#"{{"doses": [{{"time": 1, "amount": 2, "target": "{}"}}]}}"#, species);
+            outputs.push(("continue".to_string(), normalize(model::continue_simulation(&previous, &doses, 4.0, true))));
+            outputs.push(("continue_segment".to_string(), normalize(model::continue_simulation(&previous, "{}", 4.0, false))));
+            model::run_simulation(&format!(r#"{{"final_time": 6, "doses": [{{"time": 8, "amount": 1, "target": "{}"}}]}}"#, species));
+            let checkpoint = model::export_checkpoint();
+            outputs.push(("checkpoint".to_string(), normalize(Ok(checkpoint.clone()))));
+            outputs.push(("from_checkpoint".to_string(), normalize(model::run_from_checkpoint(&checkpoint, r#"{"final_time": 12}"#))));
+            check_golden(stringify!($model), outputs);
+        }
+    };
+}
+
+golden_test!(two_compartment_matches_golden, two_compartment, "A", "k1", [
+    ("overrides", r#"{"k1": "0.8", "init_A": 2, "V": 3}"#),
+]);
+
+golden_test!(event_dose_matches_golden, event_dose, "Gut", "ka", [
+    ("event_at_output_time", r#"{"output_times": [0, 1, 2, 2.5, 10]}"#),
+    ("event_on_dt_grid", r#"{"output_dt": 0.5, "final_time": 4, "doses": [{"time": 3, "amount": 1, "target": "Cp"}]}"#),
+    ("event_after_final_time", r#"{"final_time": 1.5}"#),
+    ("dose_with_event", r#"{"doses": [{"time": 2, "amount": 1, "target": "Cp"}]}"#),
+    ("time_unit_days", r#"{"time_unit": "d", "final_time": 2}"#),
+]);
+
+golden_test!(infusion_matches_golden, infusion, "Cve", "ke", [
+    ("infusion", r#"{"Ri": 2, "PODOSE": 4, "include_observables": true}"#),
+]);
+
+golden_test!(oral_window_matches_golden, oral_window, "Aplasma", "Kabs", [
+    ("window_model_time", r#"{"t0": 2, "final_time": 30}"#),
+    ("uptake_windows", r#"{"uptake_windows": [{"t0": 1, "t1": 1.5, "fraction": 0.5}, {"t0": 6, "t1": 6.25, "fraction": "0.5"}], "include_observables": true}"#),
+    ("overlapping_windows", r#"{"uptake_windows": [{"t0": 1, "t1": 3, "fraction": 1}, {"t0": 2, "t1": 4, "fraction": 1}]}"#),
+    ("repeated_uptake", r#"{"t0": 1, "n_O": 2, "period_O": 12}"#),
+]);
+
+#[test]
+fn sensitivities_match_golden() {
+    let output = two_compartment::run_simulation_with_sensitivities(r#"{"output_times": [0, 1, 4, 24]}"#, vec!["k1".to_string(), "V".to_string()]);
+    check_golden("two_compartment_sensitivities", vec![("k1_V".to_string(), normalize(output))]);
+}
+
+/// Species `id` of a run_simulation result at `t`, linear between stored points
+fn sample(result: &Value, id: &str, t: f64) -> f64 {
+    let time: Vec<f64> = serde_json::from_value(result["time"].clone()).unwrap();
+    let values: Vec<f64> = serde_json::from_value(result["species"][id].clone()).unwrap();
+    let i = time.partition_point(|&s| s <= t).clamp(1, time.len() - 1);
+    let w = (t - time[i - 1]) / (time[i] - time[i - 1]);
+    values[i - 1] + w * (values[i] - values[i - 1])
+}
+
+#[test]
+fn output_dt_matches_internal_steps() {
+    let run = |params: &str| serde_json::from_str::<Value>(&event_dose::run_simulation(params)).unwrap();
+    // Fine internal steps, so that linear interpolation between them is accurate
+    let steps = run(r#"{"final_time": 6, "rtol": 1e-10, "atol": 1e-12, "max_step": 0.001}"#);
+    let dense = run(r#"{"final_time": 6, "rtol": 1e-10, "atol": 1e-12, "output_dt": 0.3}"#);
+    let time: Vec<f64> = serde_json::from_value(dense["time"].clone()).unwrap();
+    // 21 grid points plus the pre- and post-event states of the event at t = 2
+    assert_eq!(time.len(), 23);
+    assert_eq!(time.iter().filter(|&&t| (t - 2.0).abs() < 1e-6).count(), 2);
+    assert_eq!(time.last(), Some(&6.0));
+    for id in ["Gut", "Cp"] {
+        let values: Vec<f64> = serde_json::from_value(dense["species"][id].clone()).unwrap();
+        // Away from the jump, where the internal-step series is smooth
+        for (&t, &value) in time.iter().zip(&values).filter(|(&t, _)| (t - 2.0).abs() > 0.01) {
+            let expected = sample(&steps, id, t);
+            assert!((value - expected).abs() <= 1e-5 * expected.abs().max(1.0), "{} at t = {}: {} vs {}", id, t, value, expected);
+        }
+    }
+}
+
+/// Assert that two run_simulation results agree on `species` within `rtol * |value| + atol`
+fn assert_results_close(expected: &str, actual: &str, species: &[&str], (rtol, atol): (f64, f64), context: &str) {
+    let expected: Value = serde_json::from_str(expected).unwrap();
+    let actual: Value = serde_json::from_str(actual).unwrap();
+    assert!(actual["error"].is_null(), "{}", actual["error"]);
+    assert_eq!(expected["time"], actual["time"], "{}", context);
+    let times: Vec<f64> = serde_json::from_value(expected["time"].clone()).unwrap();
+    for id in species {
+        let a: Vec<f64> = serde_json::from_value(expected["species"][id].clone()).unwrap();
+        let b: Vec<f64> = serde_json::from_value(actual["species"][id].clone()).unwrap();
+        for (t, (a, b)) in times.iter().zip(a.iter().zip(&b)) {
+            assert!((a - b).abs() <= rtol * a.abs() + atol, "{} at t = {} ({}): {} vs {}", id, t, context, a, b);
+        }
+    }
+}
+
+#[test]
+fn sparse_jacobian_matches_dense() {
+    let times: Vec<f64> = (0..=24).map(|i| i as f64 * 0.5).collect();
+    for extra in [
+        r#""rtol": 1e-8, "atol": 1e-10"#,
+        r#""rtol": 1e-8, "atol": 1e-10, "doses": [{"time": 6, "amount": 5, "target": "T0"}]"#,
+        r#""solver": "tr_bdf2""#,
+    ] {
+        let params = format!(r#"{{"output_times": {:?}, {}}}"#, times, extra);
+        let dense = transit_chain::run_simulation(&params);
+        let sparse = transit_chain_sparse::run_simulation(&params);
+        assert_results_close(&dense, &sparse, transit_chain::TransitChainModel::species_names(), (1e-6, 1e-9), extra);
+    }
+}
+
+#[test]
+fn finite_difference_jacobian_matches_analytic() {
+    // run_simulation, species ids and a dose target of each fixture
+    type Model = (fn(&str) -> String, &'static [&'static str], &'static str);
+    let models: &[Model] = &[
+        (two_compartment::run_simulation, two_compartment::TwoCompartmentModel::species_names(), "A"),
+        (event_dose::run_simulation, event_dose::EventDoseModel::species_names(), "Gut"),
+        (infusion::run_simulation, infusion::InfusionModel::species_names(), "Cve"),
+        (oral_window::run_simulation, oral_window::OralWindowModel::species_names(), "Aplasma"),
+        (transit_chain::run_simulation, transit_chain::TransitChainModel::species_names(), "T0"),
+        (transit_chain_sparse::run_simulation, transit_chain::TransitChainModel::species_names(), "T0"),
+        (event_dose_jacobian_matrix::run_simulation, event_dose::EventDoseModel::species_names(), "Gut"),
+        (transit_chain_jacobian_matrix::run_simulation, transit_chain::TransitChainModel::species_names(), "T0"),
+    ];
+    let times: Vec<f64> = (0..=48).map(|i| i as f64 * 0.5).collect();
+    for &(run_simulation, species, target) in models {
+        // The default scenario, and with a dose
+        for extra in ["", &format!(r#", "doses": [{{"time": 3, "amount": 1, "target": "{}"}}]"#, target)] {
+            let params = |jacobian: &str| format!(r#"{{"output_times": {:?}, "jacobian": "{}"{}}}"#, times, jacobian, extra);
+            let analytic = run_simulation(&params("analytic"));
+            let finite_difference = run_simulation(&params("finite_difference"));
+            // Within ten times the default rtol and atol of 1e-6
+            assert_results_close(&analytic, &finite_difference, species, (1e-5, 1e-5), &params("finite_difference"));
+            // The differences evaluate the rhs, where the analytic mode calls jac
+            let rhs_evals = |result: &str| serde_json::from_str::<Value>(result).unwrap()["stats"]["rhs_evals"].as_u64().unwrap();
+            assert!(rhs_evals(&finite_difference) > rhs_evals(&analytic));
+        }
+    }
+}
+
+#[test]
+fn jacobian_matrix_matches_jacobian_products() {
+    let times: Vec<f64> = (0..=24).map(|i| i as f64 * 0.5).collect();
+    for extra in [
+        r#""rtol": 1e-8, "atol": 1e-10"#,
+        r#""rtol": 1e-8, "atol": 1e-10, "doses": [{"time": 3, "amount": 2, "target": "Gut"}]"#,
+        r#""solver": "tr_bdf2""#,
+    ] {
+        let params = format!(r#"{{"output_times": {:?}, {}}}"#, times, extra);
+        let products = event_dose::run_simulation(&params);
+        let matrix = event_dose_jacobian_matrix::run_simulation(&params);
+        assert_results_close(&products, &matrix, event_dose::EventDoseModel::species_names(), (1e-6, 1e-9), extra);
+    }
+    // Sparse, where the products would go through a coloring of the pattern
+    for extra in [r#""rtol": 1e-8, "atol": 1e-10"#, r#""solver": "tr_bdf2""#] {
+        let params = format!(r#"{{"output_times": {:?}, {}}}"#, times, extra);
+        let products = transit_chain_sparse::run_simulation(&params);
+        let matrix = transit_chain_jacobian_matrix::run_simulation(&params);
+        assert_results_close(&products, &matrix, transit_chain::TransitChainModel::species_names(), (1e-6, 1e-9), extra);
+    }
+}
+
+/// Check a model through its PkModel type alone, as generic tooling sees it
+fn check_pk_model<Model: PkModel>() {
+    assert_eq!(Model::species_names().len(), Model::N_STATES);
+    let params = Model::default_params();
+    let p = Model::parameter_vector(&params);
+    assert_eq!(p.len(), Model::INFO.parameters.len());
+    let mut y = vec![0.0; Model::N_STATES];
+    Model::init(&p, 0.0, &mut y);
+    let mut dy = y.clone();
+    Model::rhs(&y, &p, 0.0, &[], &mut dy);
+    assert!(dy.iter().all(|value| value.is_finite()));
+    let result = Model::simulate_runs(vec![Ok(params)], |_| {}).pop().unwrap();
+    assert!(result.error.is_none());
+    for (k, id) in Model::species_names().iter().enumerate() {
+        assert_eq!(result.species.get(id).unwrap()[0], y[k], "{}", id);
+    }
+    assert!(Model::parse_params(r#"{"rtol": -1}"#).is_err());
+}
+
+#[test]
+fn models_implement_pk_model() {
+    check_pk_model::<two_compartment::TwoCompartmentModel>();
+    check_pk_model::<event_dose::EventDoseModel>();
+    check_pk_model::<infusion::InfusionModel>();
+    check_pk_model::<oral_window::OralWindowModel>();
+    check_pk_model::<transit_chain_sparse::TransitChainModel>();
+    let params = r#"{"final_time": 6}"#;
+    assert_eq!(
+        normalize(Ok(simulate::<oral_window::OralWindowModel>(params))),
+        normalize(Ok(oral_window::run_simulation(params)))
+    );
+}
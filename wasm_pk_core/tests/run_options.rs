@@ -0,0 +1,261 @@
+//! Run options checked once, through one generated model
+//!
+//! Tolerances, output grids and their thinning, observables, clamping,
+//! failure hints, dose ordering, batches, sessions, continuations, checkpoints
+//! and sweeps are handled by the shared solver loop and entry points whatever
+//! the model, so one fixture shows they work; tests/exports.rs runs the
+//! checks that depend on the model for every fixture.
+
+#![cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+
+use serde_json::{json, Value};
+use wasm_pk_core::{Checkpoint, PkModel, Session, SimulationResult};
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/two_compartment.rs"]
+mod two_compartment;
+
+use two_compartment::*;
+
+fn run(params: Value) -> SimulationResult {
+    serde_json::from_str(&run_simulation(&params.to_string())).unwrap()
+}
+
+/// Message and field of a refused run
+fn refusal(params: Value) -> (String, Option<String>) {
+    let result = run(params);
+    let error = result.error.unwrap();
+    assert_eq!(error.kind, "invalid_params");
+    assert_eq!(result.steps, 0);
+    (error.message, error.field)
+}
+
+fn last(result: &SimulationResult, id: &str) -> f64 {
+    *result.species.get(id).unwrap().last().unwrap()
+}
+
+#[test]
+fn partial_parameters_merge_over_the_defaults() {
+    let defaults: Value = serde_json::from_str(&get_default_parameters()).unwrap();
+    let result = run(json!({"k1": 0.7, "final_time": 1.0}));
+    assert_eq!(result.parameters["k1"], 0.7);
+    for (key, value) in defaults.as_object().unwrap() {
+        if key != "k1" && key != "final_time" {
+            assert_eq!(&result.parameters[key], value, "{}", key);
+        }
+    }
+    assert_ne!(last(&result, "A"), last(&run(json!({"final_time": 1.0})), "A"));
+}
+
+#[test]
+fn tolerances_are_checked_and_applied() {
+    for name in ["rtol", "atol"] {
+        let (message, field) = refusal(json!({ name: -1.0 }));
+        assert_eq!(message, format!("{} must be a positive number, got -1", name));
+        assert_eq!(field.as_deref(), Some(name));
+    }
+    let loose = run(json!({"rtol": 1e-3, "atol": 1e-3}));
+    let tight = run(json!({"rtol": 1e-10, "atol": 1e-12}));
+    assert!(tight.steps > loose.steps);
+    assert_eq!((tight.parameters["rtol"].as_f64(), tight.parameters["atol"].as_f64()), (Some(1e-10), Some(1e-12)));
+}
+
+#[test]
+fn output_times_are_checked_and_interpolated() {
+    let params = json!({"final_time": 2.0, "rtol": 1e-10, "atol": 1e-12});
+    let full = run(params.clone());
+    let mut sampled = params.clone();
+    sampled["output_times"] = json!([0.0, 0.75, 2.0]);
+    let sampled = run(sampled);
+    assert_eq!(sampled.time, [0.0, 0.75, 2.0]);
+    for (id, values) in &sampled.species.0 {
+        assert_eq!(values[0], full.species.get(id).unwrap()[0]);
+        assert!((values[2] - last(&full, id)).abs() <= 1e-8 * values[2].abs().max(1.0), "{}", id);
+    }
+    let at_times: SimulationResult = serde_json::from_str(&run_simulation_at_times(&params.to_string(), &[0.0, 0.75, 2.0])).unwrap();
+    assert_eq!(at_times.time, sampled.time);
+    assert_eq!(at_times.species.0, sampled.species.0);
+
+    let error = |times: Value| refusal(json!({"final_time": 2.0, "output_times": times})).0;
+    assert_eq!(error(json!([0.0, 1.0, 1.0])), "output_times must be strictly increasing");
+    assert_eq!(error(json!([0.0, 3.0])), "output_times must lie within [0, 2]");
+    assert_eq!(error(json!([])), "output_times must not be empty");
+}
+
+#[test]
+fn output_dt_gives_an_even_grid() {
+    let result = run(json!({"final_time": 1.0, "output_dt": 0.25}));
+    assert_eq!(result.time, [0.0, 0.25, 0.5, 0.75, 1.0]);
+    let (message, field) = refusal(json!({"output_dt": 0.5, "output_times": [0.0, 1.0]}));
+    assert_eq!((message.as_str(), field.as_deref()), ("output_dt and output_times cannot both be given", Some("output_dt")));
+    assert_eq!(
+        refusal(json!({"final_time": 1.0, "output_dt": 0.01, "max_output_points": 10})).0,
+        "output_dt gives 101 points, more than max_output_points"
+    );
+    assert_eq!(refusal(json!({"output_dt": 0.0})).0, "output_dt must be a positive number, got 0");
+}
+
+#[test]
+fn max_output_points_thins_internal_steps() {
+    let every = run(json!({"max_step": 0.01}));
+    assert!(every.time.len() > 2000);
+    let thinned = run(json!({"max_step": 0.01, "max_output_points": 50}));
+    assert!(thinned.error.is_none());
+    assert!(thinned.time.len() <= 50 && thinned.time.len() > 10, "{}", thinned.time.len());
+    // Thinning keeps the start and always records the final state
+    assert_eq!((thinned.time[0], *thinned.time.last().unwrap()), (0.0, 24.0));
+    assert!((last(&thinned, "A") - last(&every, "A")).abs() <= 1e-9 * last(&every, "A").abs().max(1.0));
+    assert!(thinned.time.iter().all(|t| every.time.contains(t)));
+    assert_eq!(refusal(json!({"max_output_points": 1})).0, "max_output_points must be at least 2");
+    assert_eq!(
+        refusal(json!({"final_time": 2.0, "output_times": [0.0, 1.0, 2.0], "max_output_points": 2})).0,
+        "output_times has more points than max_output_points"
+    );
+}
+
+#[test]
+fn observables_are_opt_in() {
+    let plain = run(json!({"final_time": 1.0}));
+    assert!(plain.observables.0.is_empty());
+    let result = run(json!({"final_time": 1.0, "include_observables": true}));
+    let ids: Vec<&str> = result.observables.0.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["W", "Wb", "Conc"]);
+    for (id, values) in &result.observables.0 {
+        assert_eq!(values.len(), result.time.len(), "{}", id);
+    }
+    assert_eq!(result.species.0, plain.species.0);
+}
+
+#[test]
+fn failures_report_the_state_and_a_hint() {
+    let dose = json!({"target": "A", "time": 1.0, "amount": 1e308});
+    let result: Value = serde_json::from_str(&run_simulation(&json!({"final_time": 2.0, "doses": [dose, dose]}).to_string())).unwrap();
+    let error = &result["error"];
+    assert_eq!(error["kind"], "solver_failure");
+    // JSON has no infinity; the state has one entry per species
+    assert_eq!(error["state"].as_array().unwrap().len(), 2);
+    assert_eq!(error["hint"], "state y[0] (A) = inf, consider checking V, W, k1");
+}
+
+#[test]
+fn clamping_projects_negative_states_within_the_threshold() {
+    let clamped = run(json!({"init_A": -1e-3, "final_time": 1.0, "clamp_nonnegative": true}));
+    assert!(clamped.error.is_none());
+    assert!(clamped.species.get("A").unwrap()[1..].iter().all(|&v| v >= 0.0));
+    let refused = run(json!({"init_A": -1.0, "final_time": 1.0, "clamp_nonnegative": true, "clamp_threshold": 0.5}));
+    let message = refused.error.unwrap().message;
+    assert!(message.starts_with("negative value of A (y[0]) = -1e0 at t = 0"), "{}", message);
+    assert!(message.ends_with("beyond clamp_threshold"), "{}", message);
+}
+
+#[test]
+fn steady_state_exclude_names_species() {
+    let (message, field) = refusal(json!({"mode": "steady_state", "steady_state_exclude": ["Q"]}));
+    assert_eq!((message.as_str(), field.as_deref()), ("steady_state_exclude: unknown species Q", Some("steady_state_exclude")));
+    let result = run(json!({"final_time": 1.0, "mode": "steady_state", "steady_state_tol": 1e300, "steady_state_exclude": ["B"]}));
+    assert!(result.steady_state.unwrap().reached);
+}
+
+#[test]
+fn doses_apply_in_time_order() {
+    let result = run(json!({"final_time": 3.0, "doses": [
+        {"time": 2.0, "amount": 2.0, "target": "A"},
+        {"time": 1.0, "amount": 1.0, "target": "B"},
+    ]}));
+    let applied: Vec<(f64, &str)> = result.applied_doses.iter().map(|dose| (dose.time, dose.target.as_str())).collect();
+    assert_eq!(applied, [(1.0, "B"), (2.0, "A")]);
+    let (message, field) = refusal(json!({"doses": [{"time": 1.0, "amount": f64::MAX, "target": "A"}, {"time": -1.0, "amount": 1.0, "target": "A"}]}));
+    assert_eq!((message.as_str(), field.as_deref()), ("doses: invalid dose of 1 to A at t = -1", Some("doses")));
+}
+
+#[test]
+fn batch_runs_match_single_runs() {
+    let params = [json!({"k1": 0.3, "final_time": 2.0}), json!({"k1": 0.9, "final_time": 3.0, "solver": "tr_bdf2"})];
+    let batch: Vec<SimulationResult> = serde_json::from_str(&run_simulation_batch(&json!(params).to_string()).unwrap()).unwrap();
+    // The runs share one problem, so each must take its own parameters and options
+    for (result, params) in batch.iter().zip(params) {
+        let single = run(params);
+        assert_eq!(result.time, single.time);
+        assert_eq!(result.species.0, single.species.0);
+        assert_eq!(result.parameters, single.parameters);
+    }
+}
+
+#[test]
+fn session_runs_match_stateless_runs() {
+    let mut session = Session::<TwoCompartmentModel>::new(TwoCompartmentModel::default_params()).unwrap();
+    session.set_parameter("final_time", 2.0).unwrap();
+    assert!(session.set_parameter("not_a_parameter", 1.0).is_err());
+    assert!(session.set_parameter("rtol", -1.0).is_err());
+    // The kept problem must take each change of p and of the options
+    for (name, value) in [("k1", 0.3), ("rtol", 1e-8), ("k1", 0.9)] {
+        session.set_parameter(name, value).unwrap();
+        let single = TwoCompartmentModel::simulate_run(session.params().clone());
+        for _ in 0..2 {
+            let result = session.run();
+            assert_eq!(result.time, single.time);
+            assert_eq!(result.species.0, single.species.0);
+            assert_eq!(result.parameters, single.parameters);
+        }
+    }
+    assert_eq!(session.params().k1, 0.9);
+}
+
+#[test]
+fn continuations_do_not_repeat_the_schedule() {
+    let first = run_simulation(&json!({"final_time": 2.0, "output_times": [0.0, 1.0, 2.0], "doses": [{"time": 1.0, "amount": 1.0, "target": "A"}]}).to_string());
+    let segment: SimulationResult = serde_json::from_str(&continue_simulation(&first, "{}", 2.0, false).unwrap()).unwrap();
+    assert!(segment.error.is_none());
+    assert!(segment.applied_doses.is_empty());
+    assert_eq!(segment.parameters["t_start"], 2.0);
+    assert!(segment.parameters["doses"].is_null() && segment.parameters["output_times"].is_null());
+    // New doses are timed from the end of the previous run
+    let new_dose = json!({"doses": [{"time": 1.0, "amount": 1.0, "target": "B"}]}).to_string();
+    let dosed: SimulationResult = serde_json::from_str(&continue_simulation(&first, &new_dose, 2.0, false).unwrap()).unwrap();
+    assert_eq!(dosed.applied_doses.len(), 1);
+    assert_eq!(dosed.applied_doses[0].time, 3.0);
+}
+
+#[test]
+fn checkpoints_carry_pending_doses() {
+    let doses = json!([{"time": 0.5, "amount": 1.0, "target": "A"}, {"time": 1.5, "amount": 2.0, "target": "A"}]);
+    let first = run(json!({"final_time": 1.0, "doses": doses}));
+    assert_eq!(first.applied_doses.len(), 1);
+    let checkpoint = export_checkpoint();
+    let saved: Checkpoint = serde_json::from_str(&checkpoint).unwrap();
+    assert_eq!(saved.pending_doses.len(), 1);
+    assert_eq!((saved.pending_doses[0].time, saved.pending_doses[0].amount), (1.5, 2.0));
+    let resumed: SimulationResult = serde_json::from_str(&run_from_checkpoint(&checkpoint, r#"{"final_time": 2.0}"#).unwrap()).unwrap();
+    let applied: Vec<f64> = resumed.applied_doses.iter().map(|dose| dose.time).collect();
+    assert_eq!(applied, [1.5]);
+    // The resumed run is the uninterrupted one
+    let whole = run(json!({"final_time": 2.0, "doses": doses}));
+    for (id, _) in &whole.species.0 {
+        assert!((last(&resumed, id) - last(&whole, id)).abs() <= 1e-4 * last(&whole, id).abs().max(1.0), "{}", id);
+    }
+}
+
+#[test]
+fn sweeps_space_values_and_name_bad_parameters() {
+    let values = |sweep: Value| -> Vec<f64> {
+        let entries: Value = serde_json::from_str(&run_parameter_sweep(r#"{"final_time": 0.5}"#, &sweep.to_string()).unwrap()).unwrap();
+        entries.as_array().unwrap().iter().map(|entry| entry["value"].as_f64().unwrap()).collect()
+    };
+    assert_eq!(values(json!({"parameter": "k1", "min": 0.2, "max": 1.0, "n": 5})), [0.2, 0.4, 0.6000000000000001, 0.8, 1.0]);
+    assert_eq!(values(json!({"parameter": "k2", "values": [0.5, 0.05]})), [0.5, 0.05]);
+    assert_eq!(run_parameter_sweep("{}", r#"{"parameter": "nope", "values": [1.0]}"#).unwrap_err(), "unknown parameter nope");
+    assert!(run_parameter_sweep("{}", r#"{"parameter": "k1", "min": 0.2, "max": 1.0, "n": 1}"#).is_err());
+    assert!(run_parameter_sweep("{}", r#"{"parameter": "k1", "values": [1.0], "species": "Q"}"#).is_err());
+}
+
+#[test]
+fn parameter_checks_warn_without_a_range() {
+    let report: Value = serde_json::from_str(&validate_parameters(r#"{"k1": -1.0, "V": -2.0}"#)).unwrap();
+    assert_eq!(report["errors"], json!([{"field": "V", "message": "V must not be negative, got -2"}]));
+    assert_eq!(report["warnings"], json!([{"field": "k1", "message": "k1 is negative, got -1"}]));
+    // Only errors refuse a validated run
+    assert!(run(json!({"k1": -0.1, "final_time": 0.5, "validate": true})).error.is_none());
+    assert_eq!(refusal(json!({"V": -2.0, "validate": true})).0, "invalid parameters: V must not be negative, got -2");
+    // Without validate the ranges are not checked
+    assert!(run(json!({"V": -2.0, "final_time": 0.5})).error.is_none());
+}
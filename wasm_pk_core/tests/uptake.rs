@@ -0,0 +1,110 @@
+//! Oral uptake windows of the oral_window fixture, the BPA model's uptake term
+//!
+//! The model absorbs through n_O smoothed pulses, the first from t0 to t1 and
+//! each later one period_O after the last; uptake_windows replaces them. Each
+//! window adds Kabs * koa * (t1 - t0) to Aplasma, less the part of its ramp
+//! that falls before the start of the run.
+
+#![cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+
+use serde_json::{json, Value};
+
+#[allow(non_snake_case, unexpected_cfgs, unused_parens, dead_code)]
+#[path = "models/oral_window.rs"]
+mod oral_window;
+
+/// Kabs * koa * (t1 - t0) with the model defaults
+const WINDOW_AMOUNT: f64 = 0.4 * 1.2e8 * 1.3381102 * 0.0003;
+
+fn aplasma(params: Value) -> (Vec<f64>, Vec<f64>) {
+    let result: Value = serde_json::from_str(&oral_window::run_simulation(&params.to_string())).unwrap();
+    assert!(result["error"].is_null(), "{} with {}", result["error"], params);
+    let series = |values: &Value| -> Vec<f64> { serde_json::from_value(values.clone()).unwrap() };
+    (series(&result["time"]), series(&result["species"]["Aplasma"]))
+}
+
+fn error(params: Value) -> Value {
+    let result: Value = serde_json::from_str(&oral_window::run_simulation(&params.to_string())).unwrap();
+    assert!(result["time"].as_array().is_none_or(|time| time.is_empty()), "{} ran", params);
+    result["error"].clone()
+}
+
+#[test]
+fn single_uptake_peak_matches_square_pulse() {
+    // The run starts at t0, so half of the first ramp, ln(2) / (2 pulse_sharpness)
+    // of the window, is not absorbed
+    let (_, values) = aplasma(json!({ "final_time": 36.0 }));
+    let peak = values.iter().cloned().fold(f64::MIN, f64::max);
+    let expected = WINDOW_AMOUNT * (1.0 - 2f64.ln() / 200.0);
+    assert!((peak / expected - 1.0).abs() < 1e-3, "peak {} vs {}", peak, expected);
+}
+
+#[test]
+fn repeated_uptakes_give_one_peak_each() {
+    let (time, values) = aplasma(json!({ "t0": 1.0, "n_O": 3, "period_O": 8.0, "final_time": 24.0 }));
+    // Local maxima at least 10% above the lowest value since the previous
+    // peak; solver ripple around a plateau is far smaller
+    let mut peaks = Vec::new();
+    let mut trough = f64::MAX;
+    for i in 1..values.len() - 1 {
+        trough = trough.min(values[i]);
+        if values[i] >= values[i - 1] && values[i] >= values[i + 1] && values[i] > 1.1 * trough {
+            peaks.push(time[i]);
+            trough = values[i];
+        }
+    }
+    assert_eq!(peaks.len(), 3, "peaks at {:?}", peaks);
+    for (peak, start) in peaks.iter().zip([1.0, 9.0, 17.0]) {
+        assert!(*peak > start && *peak < start + 0.01, "peaks at {:?}", peaks);
+    }
+}
+
+#[test]
+fn absorbed_amount_scales_with_the_count() {
+    for count in [1, 2, 3] {
+        let (_, values) = aplasma(json!({ "t0": 1.0, "Kelm": 0.0, "n_O": count, "period_O": 8.0, "final_time": 24.0 }));
+        let ratio = values.last().unwrap() / WINDOW_AMOUNT;
+        assert!((ratio - count as f64).abs() < 1e-3, "count {}: ratio {}", count, ratio);
+    }
+}
+
+#[test]
+fn repeated_uptakes_match_the_windows_they_stand_for() {
+    let repeated = aplasma(json!({ "t0": 1.0, "Kelm": 0.0, "n_O": 2, "period_O": 8.0, "final_time": 24.0 }));
+    let windows = json!([{ "t0": 1.0, "t1": 1.0003, "fraction": 1.0 }, { "t0": 9.0, "t1": 9.0003, "fraction": 1.0 }]);
+    assert_eq!(repeated, aplasma(json!({ "t0": 1.0, "Kelm": 0.0, "final_time": 24.0, "uptake_windows": windows })));
+    // Explicit windows replace the repeated pulse
+    assert_eq!(repeated, aplasma(json!({ "t0": 1.0, "Kelm": 0.0, "n_O": 3, "final_time": 24.0, "uptake_windows": windows })));
+}
+
+#[test]
+fn uptake_windows_sum_their_fractions() {
+    let absorbed = |windows: Value| {
+        let (_, values) = aplasma(json!({ "t0": 1.0, "Kelm": 0.0, "final_time": 24.0, "uptake_windows": windows }));
+        values.last().unwrap() / WINDOW_AMOUNT
+    };
+    // 0.5 * 2 + 1 + 0.25 windows of the model's length
+    let ratio = absorbed(json!([
+        { "t0": 1.0, "t1": 1.0006, "fraction": 0.5 },
+        { "t0": 5.0, "t1": 5.0003, "fraction": 1.0 },
+        { "t0": 10.0, "t1": 10.0003, "fraction": 0.25 },
+    ]));
+    assert!((ratio - 2.25).abs() < 1e-3, "ratio {}", ratio);
+}
+
+#[test]
+fn invalid_repeats_are_refused() {
+    for (params, field, message) in [
+        (json!({ "n_O": 0 }), "n_O", "n_O must be a whole number of uptakes, at least 1, got 0"),
+        (json!({ "n_O": 2.5 }), "n_O", "n_O must be a whole number of uptakes, at least 1, got 2.5"),
+        (json!({ "n_O": 1e15 }), "n_O", "n_O must be at most 10000 uptakes, got 1000000000000000"),
+        (json!({ "n_O": 2, "period_O": 0.0001 }), "period_O", "period_O must be at least the uptake pulse of 0.0003, got 0.0001"),
+    ] {
+        let error = error(params.clone());
+        assert_eq!(error["kind"], "invalid_params", "{}: {}", params, error);
+        assert_eq!(error["field"], field, "{}: {}", params, error);
+        assert_eq!(error["message"], message, "{}: {}", params, error);
+        let report: Value = serde_json::from_str(&oral_window::validate_parameters(&params.to_string())).unwrap();
+        assert_eq!(report["errors"][0]["field"], field, "{}: {}", params, report);
+    }
+}